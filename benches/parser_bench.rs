@@ -19,7 +19,7 @@ fn generate_large_tasks_md(phases: usize, tasks_per_phase: usize) -> String {
             md.push_str(&format!(
                 "### [{status}] P{p}-T{t}: Task {t} description here\n"
             ));
-            md.push_str(&format!("- **담당**: @backend-specialist\n"));
+            md.push_str("- **담당**: @backend-specialist\n");
             if t > 0 {
                 md.push_str(&format!("- **blocked_by**: P{p}-T{}\n", t - 1));
             }
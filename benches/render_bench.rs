@@ -6,7 +6,7 @@ use simple_claude_board::data::state::DashboardState;
 use simple_claude_board::ui::claude_output::AgentPanel;
 use simple_claude_board::ui::detail::DetailWidget;
 use simple_claude_board::ui::gantt::{GanttState, GanttWidget};
-use simple_claude_board::ui::help::HelpOverlay;
+use simple_claude_board::ui::help::{HelpOverlay, HelpState};
 use simple_claude_board::ui::layout::DashboardLayout;
 use simple_claude_board::ui::statusbar::StatusBar;
 
@@ -118,7 +118,7 @@ fn bench_help_overlay_render(c: &mut Criterion) {
     c.bench_function("help_overlay_render", |b| {
         b.iter(|| {
             let mut buf = Buffer::empty(area);
-            HelpOverlay.render(black_box(area), &mut buf);
+            HelpOverlay.render(black_box(area), &mut buf, &mut HelpState::default());
             black_box(buf);
         })
     });
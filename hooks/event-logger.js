@@ -12,6 +12,10 @@
  *   PostToolUse[Task]             -> agent_end
  *   PreToolUse[Edit|Write|...]    -> tool_start
  *   PostToolUse[Edit|Write|...]   -> tool_end
+ *
+ * Before each tool call the hook also reads ~/.claude/dashboard/commands.jsonl,
+ * where the dashboard writes pause/resume/abort signals. While an agent's
+ * latest signal is pause or abort, its tool calls are refused (exit code 2).
  */
 
 const fs = require('fs');
@@ -20,13 +24,12 @@ const os = require('os');
 
 const EVENTS_DIR = path.join(os.homedir(), '.claude', 'dashboard');
 const EVENTS_FILE = path.join(EVENTS_DIR, 'events.jsonl');
+const COMMANDS_FILE = path.join(EVENTS_DIR, 'commands.jsonl');
 const SESSION_ID_FILE = path.join(os.tmpdir(), 'claude-dashboard-session-id');
 
-// Tools we track (Task is handled separately as agent events)
-const TRACKED_TOOLS = new Set([
-  'Edit', 'Write', 'Read', 'Bash', 'Grep', 'Glob',
-  'NotebookEdit', 'WebFetch', 'WebSearch'
-]);
+// Tools we track (Task is handled separately as agent events); `init`
+// replaces this with the matcher it registers the hook with
+const TRACKED_TOOLS = new RegExp('^(?:Edit|Write|Read|Bash|Grep|Glob|NotebookEdit|WebFetch|WebSearch)$');
 
 /**
  * Read and parse JSON from stdin (Claude hook input).
@@ -85,6 +88,54 @@ function appendEvent(event) {
   }
 }
 
+/**
+ * Latest dashboard signal for an agent ('pause', 'resume', 'abort'), or null.
+ * Signals carrying a session_id only apply to that session.
+ */
+function latestSignal(agentId, sessionId) {
+  let content;
+  try {
+    content = fs.readFileSync(COMMANDS_FILE, 'utf8');
+  } catch {
+    return null;
+  }
+  let signal = null;
+  for (const line of content.split('\n')) {
+    if (!line.trim()) continue;
+    try {
+      const command = JSON.parse(line);
+      if (command.agent_id !== agentId) continue;
+      if (command.session_id && command.session_id !== sessionId) continue;
+      signal = command.type;
+    } catch {
+      // Skip partial lines
+    }
+  }
+  return signal;
+}
+
+/**
+ * Refuse the tool call when the dashboard paused or aborted the agent.
+ * Exit code 2 blocks a PreToolUse call and shows stderr to Claude.
+ * @returns {boolean} true when the call was refused
+ */
+function refuseIfSignalled(agentId, sessionId) {
+  const signal = latestSignal(agentId, sessionId);
+  if (signal === 'pause') {
+    process.stderr.write(
+      `Agent ${agentId} is paused from the dashboard. Wait and retry later.\n`
+    );
+  } else if (signal === 'abort') {
+    process.stderr.write(
+      `Agent ${agentId} was aborted from the dashboard. Stop working on the current task.\n`
+    );
+  } else {
+    return false;
+  }
+  process.exitCode = 2;
+  return true;
+}
+
 /**
  * Determine if this is a Pre or Post hook from the hook_event_name field.
  */
@@ -107,6 +158,34 @@ function extractTaskId(prompt) {
   return null;
 }
 
+/**
+ * Short summary of the non-file arguments of a tool call, so the dashboard
+ * can spot an agent repeating the exact same call.
+ * @returns {string|null}
+ */
+function summarizeArgs(toolName, toolInput) {
+  let args = null;
+  switch (toolName) {
+    case 'Bash':
+      args = toolInput.command;
+      break;
+    case 'Grep':
+    case 'Glob':
+      args = toolInput.path ? `${toolInput.pattern} in ${toolInput.path}` : toolInput.pattern;
+      break;
+    case 'WebFetch':
+      args = toolInput.url;
+      break;
+    case 'WebSearch':
+      args = toolInput.query;
+      break;
+    default:
+      return null;
+  }
+  if (typeof args !== 'string' || !args) return null;
+  return args.length > 200 ? args.slice(0, 200) : args;
+}
+
 async function main() {
   const input = await readStdin();
   const hookEventName = input.hook_event_name || '';
@@ -117,6 +196,8 @@ async function main() {
   const agentId = process.env.CLAUDE_AGENT_ROLE || 'main';
   const sessionId = getSessionId();
   const timestamp = new Date().toISOString();
+  // Lets the dashboard show which git branch/worktree the agent works in
+  const cwd = input.cwd || process.cwd();
 
   const pre = isPreHook(hookEventName);
 
@@ -124,6 +205,7 @@ async function main() {
   if (toolName === 'Task' || hookEventName.includes('Task')) {
     const subagentType = toolInput.subagent_type || 'unknown';
     const taskId = extractTaskId(toolInput.prompt) || 'unknown';
+    if (pre && refuseIfSignalled(subagentType, sessionId)) return;
 
     appendEvent({
       event_type: pre ? 'agent_start' : 'agent_end',
@@ -132,20 +214,29 @@ async function main() {
       task_id: taskId,
       session_id: sessionId,
       tool_name: subagentType,
+      cwd,
     });
     return;
   }
 
   // Other tracked tools -> tool_start / tool_end
-  if (TRACKED_TOOLS.has(toolName)) {
-    appendEvent({
+  if (TRACKED_TOOLS.test(toolName)) {
+    if (pre && refuseIfSignalled(agentId, sessionId)) return;
+    const event = {
       event_type: pre ? 'tool_start' : 'tool_end',
       timestamp,
       agent_id: agentId,
       task_id: 'unknown',
       session_id: sessionId,
       tool_name: toolName,
-    });
+      cwd,
+    };
+    // File tools: remember the path so the dashboard can open it
+    const filePath = toolInput.file_path || toolInput.notebook_path;
+    if (filePath) event.file_path = filePath;
+    const args = summarizeArgs(toolName, toolInput);
+    if (args) event.args = args;
+    appendEvent(event);
     return;
   }
 }
@@ -0,0 +1,170 @@
+//! User-defined key actions
+//!
+//! Shell commands bound to function keys in the `[actions]` config section,
+//! e.g. `F5 = "cargo test {task_id}"`. `{task_id}`, `{agent}` and `{file}`
+//! are filled in from the selection (shell-quoted); the main loop suspends
+//! the TUI while the command runs and reports its exit status.
+
+use std::process::Command;
+
+/// Template variables and the selection values they expand to
+const VARIABLES: [&str; 3] = ["task_id", "agent", "file"];
+
+/// A command bound to a function key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomAction {
+    /// Function key number (`F5` → 5)
+    pub key: u8,
+    /// Command template run with `sh -c`
+    pub command: String,
+}
+
+impl CustomAction {
+    /// Parse an `[actions]` entry; `key` is `F1`..`F12` (any case)
+    pub fn parse(key: &str, command: &str) -> Result<Self, String> {
+        let number = key
+            .strip_prefix(['F', 'f'])
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|n| (1..=12).contains(n))
+            .ok_or_else(|| format!("`actions.{key}`: keys must be F1 to F12"))?;
+        if command.trim().is_empty() {
+            return Err(format!("`actions.{key}` must not be empty"));
+        }
+        // Unknown `{name}` placeholders are most likely typos
+        let mut rest = command;
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}') else {
+                break;
+            };
+            let name = &rest[open + 1..open + close];
+            if !VARIABLES.contains(&name) {
+                return Err(format!(
+                    "`actions.{key}`: unknown variable `{{{name}}}` (expected {{task_id}}, {{agent}} or {{file}})"
+                ));
+            }
+            rest = &rest[open + close + 1..];
+        }
+        Ok(Self {
+            key: number,
+            command: command.to_string(),
+        })
+    }
+}
+
+/// Values for the template variables; `None` when the selection has none
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActionContext {
+    pub task_id: Option<String>,
+    pub agent: Option<String>,
+    pub file: Option<String>,
+}
+
+/// A command the main loop should run with the TUI suspended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellRequest {
+    /// Shown in the exit status notice, e.g. `F5`
+    pub label: String,
+    pub command: String,
+}
+
+/// `'value'`, safe to paste into a `sh` command line
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// `"value"`, safe to paste into a `cmd` command line
+fn cmd_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// `value` quoted for the shell [`shell_command`] runs
+pub fn quote(value: &str) -> String {
+    if cfg!(windows) {
+        cmd_quote(value)
+    } else {
+        shell_quote(value)
+    }
+}
+
+/// Fill the variables of `template` from `context`; the error names the
+/// first variable the selection does not provide
+pub fn expand(template: &str, context: &ActionContext) -> Result<String, String> {
+    let mut command = template.to_string();
+    for name in VARIABLES {
+        let placeholder = format!("{{{name}}}");
+        if !command.contains(&placeholder) {
+            continue;
+        }
+        let value = match name {
+            "task_id" => context.task_id.as_deref(),
+            "agent" => context.agent.as_deref(),
+            _ => context.file.as_deref(),
+        };
+        let value = value.ok_or_else(|| format!("no {placeholder} for the selection"))?;
+        command = command.replace(&placeholder, &quote(value));
+    }
+    Ok(command)
+}
+
+/// `sh -c <command>`, or `cmd /C <command>` on Windows
+pub fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(command);
+        return shell;
+    }
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_function_keys_and_known_variables() {
+        let action = CustomAction::parse("F5", "cargo test {task_id}").unwrap();
+        assert_eq!(action.key, 5);
+        assert_eq!(CustomAction::parse("f12", "make").unwrap().key, 12);
+
+        assert!(CustomAction::parse("F13", "make").is_err());
+        assert!(CustomAction::parse("x", "make").is_err());
+        assert!(CustomAction::parse("F1", " ").is_err());
+        let err = CustomAction::parse("F2", "open {path}").unwrap_err();
+        assert!(err.contains("{path}"), "{err}");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn expand_quotes_values_and_reports_missing_ones() {
+        let context = ActionContext {
+            task_id: Some("P1-T2".to_string()),
+            agent: None,
+            file: Some("src/it's.rs".to_string()),
+        };
+        assert_eq!(
+            expand("cargo test {task_id} && wc {file}", &context).unwrap(),
+            r"cargo test 'P1-T2' && wc 'src/it'\''s.rs'"
+        );
+        assert_eq!(
+            expand("notify {agent}", &context).unwrap_err(),
+            "no {agent} for the selection"
+        );
+        assert_eq!(expand("make", &ActionContext::default()).unwrap(), "make");
+    }
+
+    #[test]
+    fn cmd_quote_doubles_quotes() {
+        assert_eq!(cmd_quote(r#"C:\a "b".rs"#), r#""C:\a ""b"".rs""#);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn shell_command_runs_through_sh() {
+        let command = shell_command("exit 3");
+        assert_eq!(command.get_program(), "sh");
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, vec!["-c", "exit 3"]);
+    }
+}
@@ -0,0 +1,188 @@
+//! Bell and desktop-notification escalation
+//!
+//! Opt-in alerts for agent errors, failed tasks and budget crossings while
+//! the terminal is unfocused: a terminal bell and/or an OSC 9 notification
+//! (shown by iTerm2, kitty, WezTerm, Windows Terminal and others; passed
+//! through tmux). Each
+//! category has a threshold: an alert fires once that many of its events
+//! arrived since the last alert of the category, and regaining focus starts
+//! the counts over.
+
+use std::collections::HashMap;
+
+use crate::data::tasks_parser::TaskStatus;
+use crate::data::transitions::Transition;
+
+/// Threshold keys: the error categories, then failed tasks and budgets
+pub const CATEGORIES: [&str; 7] = [
+    "type",
+    "runtime",
+    "network",
+    "permission",
+    "unknown",
+    "task_failed",
+    "budget",
+];
+
+/// The `[alerts]` config section
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlertConfig {
+    /// Ring the terminal bell
+    pub bell: bool,
+    /// Send an OSC 9 desktop notification
+    pub osc: bool,
+    /// Also alert while the terminal has focus
+    pub when_focused: bool,
+    /// Events per alert by category; 1 when unset, 0 turns a category off
+    pub thresholds: HashMap<String, u64>,
+}
+
+impl AlertConfig {
+    pub fn enabled(&self) -> bool {
+        self.bell || self.osc
+    }
+
+    pub fn threshold(&self, category: &str) -> u64 {
+        self.thresholds.get(category).copied().unwrap_or(1)
+    }
+}
+
+/// Events counted towards the next alert of each category
+#[derive(Debug, Clone, Default)]
+pub struct Alerter {
+    counts: HashMap<String, u64>,
+}
+
+impl Alerter {
+    /// Count `transitions` and return the alert text when a category reaches
+    /// its threshold. Errors of agents for which `muted` holds are skipped.
+    pub fn observe(
+        &mut self,
+        config: &AlertConfig,
+        transitions: &[Transition],
+        muted: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        let mut messages = Vec::new();
+        for transition in transitions {
+            let category = match transition {
+                Transition::AgentError {
+                    agent_id, category, ..
+                } if !muted(agent_id) => category.to_string().to_lowercase(),
+                Transition::TaskStatus {
+                    to: TaskStatus::Failed,
+                    ..
+                } => "task_failed".to_string(),
+                Transition::Budget(_) => "budget".to_string(),
+                _ => continue,
+            };
+            let threshold = config.threshold(&category);
+            if threshold == 0 {
+                continue;
+            }
+            let count = self.counts.entry(category.clone()).or_default();
+            *count += 1;
+            if *count >= threshold {
+                *count = 0;
+                let summary = transition.summary();
+                messages.push(if threshold > 1 {
+                    format!("{threshold}x {category}: {summary}")
+                } else {
+                    summary
+                });
+            }
+        }
+        (!messages.is_empty()).then(|| messages.join(" | "))
+    }
+
+    /// Start counting from zero, e.g. when the terminal regains focus
+    pub fn reset(&mut self) {
+        self.counts.clear();
+    }
+}
+
+/// OSC 9 notification escape for `message`, wrapped for tmux passthrough
+/// when `in_tmux`
+pub fn osc9(message: &str, in_tmux: bool) -> String {
+    // Control characters would end or corrupt the sequence
+    let text: String = message.chars().filter(|c| !c.is_control()).collect();
+    tmux_passthrough(format!("\x1b]9;{text}\x07"), in_tmux)
+}
+
+/// `sequence` wrapped so tmux hands it to the outer terminal when `in_tmux`
+pub fn tmux_passthrough(sequence: String, in_tmux: bool) -> String {
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::rules::ErrorCategory;
+
+    fn error(agent: &str, category: ErrorCategory) -> Transition {
+        Transition::AgentError {
+            agent_id: agent.to_string(),
+            task_id: "P1-T1".to_string(),
+            message: "boom".to_string(),
+            category,
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn alerts_when_a_category_reaches_its_threshold() {
+        let config = AlertConfig {
+            bell: true,
+            thresholds: HashMap::from([("network".to_string(), 2), ("type".to_string(), 0)]),
+            ..Default::default()
+        };
+        let mut alerter = Alerter::default();
+        let muted = |agent: &str| agent == "quiet";
+
+        assert_eq!(
+            alerter.observe(&config, &[error("a", ErrorCategory::Network)], muted),
+            None
+        );
+        assert_eq!(
+            alerter.observe(&config, &[error("a", ErrorCategory::Network)], muted),
+            Some("2x network: a error on P1-T1: boom".to_string())
+        );
+        assert_eq!(
+            alerter.observe(&config, &[error("a", ErrorCategory::Type)], muted),
+            None
+        );
+        assert_eq!(
+            alerter.observe(&config, &[error("quiet", ErrorCategory::Runtime)], muted),
+            None
+        );
+
+        let failed = Transition::TaskStatus {
+            task_id: "P1-T2".to_string(),
+            task_name: "Writer".to_string(),
+            phase_id: "P1".to_string(),
+            from: TaskStatus::InProgress,
+            to: TaskStatus::Failed,
+        };
+        let both = [error("a", ErrorCategory::Runtime), failed];
+        assert_eq!(
+            alerter.observe(&config, &both, muted).unwrap(),
+            "a error on P1-T1: boom | P1-T2 Writer: in_progress -> failed"
+        );
+
+        alerter.observe(&config, &[error("a", ErrorCategory::Network)], muted);
+        alerter.reset();
+        assert_eq!(
+            alerter.observe(&config, &[error("a", ErrorCategory::Network)], muted),
+            None
+        );
+    }
+
+    #[test]
+    fn osc9_is_wrapped_for_tmux() {
+        assert_eq!(osc9("a\nb", false), "\x1b]9;ab\x07");
+        assert_eq!(osc9("hi", true), "\x1bPtmux;\x1b\x1b]9;hi\x07\x1b\\");
+    }
+}
@@ -0,0 +1,190 @@
+//! Critical path over the `blocked_by` dependency graph
+//!
+//! Each open task takes its estimated time left (`task_eta`, or an hour
+//! when nothing has finished yet to average over); completed tasks take
+//! none. The longest chain of remaining work decides when everything can be
+//! done. Tasks on it have no slack: any delay there delays the whole plan,
+//! so they are where more agents help. Tasks in a dependency cycle are left
+//! out, and without any dependencies there is no path to speak of.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::{ParsedTask, TaskStatus};
+
+/// Time left assumed for an open task when no task has finished yet
+const DEFAULT_TASK_SECS: i64 = 3600;
+
+/// Critical path and slack of the open tasks
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CriticalPath {
+    /// Open tasks on the longest chain of remaining work, in order
+    pub tasks: Vec<String>,
+    /// Estimated time until every task is done
+    pub remaining_secs: i64,
+    /// How long each open task can slip without delaying the finish
+    pub slack_secs: HashMap<String, i64>,
+    /// In-progress task on the critical path, the one gating completion
+    pub gating: Option<String>,
+}
+
+impl CriticalPath {
+    pub fn compute(state: &DashboardState, now: DateTime<Utc>) -> Self {
+        let tasks: Vec<&ParsedTask> = state.phases.iter().flat_map(|p| &p.tasks).collect();
+        let index: HashMap<&str, usize> = tasks
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.id.as_str(), i))
+            .collect();
+        let duration: Vec<i64> = tasks
+            .iter()
+            .map(|t| match t.status {
+                TaskStatus::Completed => 0,
+                _ => state
+                    .task_eta(t, now)
+                    .map_or(DEFAULT_TASK_SECS, |eta| eta.num_seconds()),
+            })
+            .collect();
+        let deps: Vec<Vec<usize>> = tasks
+            .iter()
+            .map(|t| {
+                t.blocked_by
+                    .iter()
+                    .filter_map(|d| index.get(d.as_str()).copied())
+                    .collect()
+            })
+            .collect();
+
+        if deps.iter().all(Vec::is_empty) {
+            return Self::default();
+        }
+        let Some(order) = topological_order(&deps) else {
+            return Self::default();
+        };
+        let mut ordered = vec![false; tasks.len()];
+        for &t in &order {
+            ordered[t] = true;
+        }
+
+        // Earliest finish, going forward from tasks without dependencies
+        let mut finish = vec![0i64; tasks.len()];
+        for &t in &order {
+            let start = deps[t].iter().map(|&d| finish[d]).max().unwrap_or(0);
+            finish[t] = start + duration[t];
+        }
+        let remaining_secs = finish.iter().copied().max().unwrap_or(0);
+
+        // Latest finish that does not delay the end, going backward
+        let mut latest = vec![remaining_secs; tasks.len()];
+        for &t in order.iter().rev() {
+            let latest_start = latest[t] - duration[t];
+            for &d in &deps[t] {
+                latest[d] = latest[d].min(latest_start);
+            }
+        }
+
+        let open = |t: usize| tasks[t].status != TaskStatus::Completed;
+        let slack_secs = (0..tasks.len())
+            .filter(|&t| open(t) && ordered[t])
+            .map(|t| (tasks[t].id.clone(), latest[t] - finish[t]))
+            .collect();
+
+        // Walk back from the last task to finish along zero-slack links
+        let mut path = Vec::new();
+        let mut current = order
+            .iter()
+            .copied()
+            .filter(|&t| open(t))
+            .max_by_key(|&t| finish[t]);
+        while let Some(t) = current {
+            path.push(t);
+            let start = finish[t] - duration[t];
+            current = deps[t]
+                .iter()
+                .copied()
+                .find(|&d| open(d) && finish[d] == start);
+        }
+        path.reverse();
+
+        let gating = path
+            .iter()
+            .find(|&&t| tasks[t].status == TaskStatus::InProgress)
+            .map(|&t| tasks[t].id.clone());
+        Self {
+            tasks: path.into_iter().map(|t| tasks[t].id.clone()).collect(),
+            remaining_secs,
+            slack_secs,
+            gating,
+        }
+    }
+
+    pub fn contains(&self, task_id: &str) -> bool {
+        self.tasks.iter().any(|t| t == task_id)
+    }
+}
+
+/// Tasks ordered so each comes after its dependencies, leaving out those in
+/// a cycle (or depending on one); `None` when no task is left
+fn topological_order(deps: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let mut waiting: Vec<usize> = deps.iter().map(Vec::len).collect();
+    let mut dependents = vec![Vec::new(); deps.len()];
+    for (t, ds) in deps.iter().enumerate() {
+        for &d in ds {
+            dependents[d].push(t);
+        }
+    }
+    let mut order: Vec<usize> = (0..deps.len()).filter(|&t| waiting[t] == 0).collect();
+    let mut next = 0;
+    while next < order.len() {
+        for &t in &dependents[order[next]] {
+            waiting[t] -= 1;
+            if waiting[t] == 0 {
+                order.push(t);
+            }
+        }
+        next += 1;
+    }
+    (!order.is_empty()).then_some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_chain_of_open_work_is_critical() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n\
+             ### [x] T1: Schema\n\n\
+             ### [InProgress] T2: Parser\n- **blocked_by**: T1\n\n\
+             ### [ ] T3: Writer\n- **blocked_by**: T2\n\n\
+             ### [ ] T4: Docs\n- **blocked_by**: T1\n\n\
+             ### [ ] T5: Loop A\n- **blocked_by**: T6\n\n\
+             ### [ ] T6: Loop B\n- **blocked_by**: T5\n",
+        )
+        .unwrap();
+        let path = CriticalPath::compute(&state, Utc::now());
+
+        assert_eq!(path.tasks, ["T2", "T3"]);
+        assert_eq!(path.remaining_secs, 2 * DEFAULT_TASK_SECS);
+        assert_eq!(path.gating.as_deref(), Some("T2"));
+        assert_eq!(path.slack_secs["T3"], 0);
+        assert_eq!(path.slack_secs["T4"], DEFAULT_TASK_SECS);
+        // Completed and cyclic tasks have no slack entry
+        assert!(!path.slack_secs.contains_key("T1"));
+        assert!(!path.slack_secs.contains_key("T5"));
+        assert!(path.contains("T3") && !path.contains("T4"));
+
+        let independent = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [ ] T1: A\n\n### [ ] T2: B\n",
+        )
+        .unwrap();
+        assert_eq!(
+            CriticalPath::compute(&independent, Utc::now()),
+            CriticalPath::default()
+        );
+    }
+}
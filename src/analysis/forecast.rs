@@ -0,0 +1,222 @@
+//! Completion-time forecast
+//!
+//! Two estimates of the time left for the open tasks are averaged: the open
+//! count divided by the recent velocity (tasks closed per hour over the last
+//! day), and each phase's open tasks times its average task duration (their
+//! `est:` estimates where given), spread over the running agents. The
+//! longest chain of dependent work cannot be sped up by more agents, so the
+//! critical path sets a floor. Without a finished task, a velocity or an
+//! estimate there is nothing to go on and no forecast.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::analysis::metrics::format_secs;
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Projected finish of the open tasks
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Forecast {
+    pub open_tasks: usize,
+    /// Open tasks at the recent closing rate
+    pub velocity_secs: Option<i64>,
+    /// Per-phase estimates or average task durations, spread over the
+    /// running agents
+    pub phase_secs: Option<i64>,
+    /// Remaining work on the critical path, the least time left
+    pub critical_path_secs: Option<i64>,
+    /// Combined estimate; `None` without anything to go on
+    pub remaining_secs: Option<i64>,
+    pub finish_at: Option<DateTime<Utc>>,
+}
+
+impl Forecast {
+    pub fn compute(state: &DashboardState, now: DateTime<Utc>) -> Self {
+        let open_tasks = state
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| t.status != TaskStatus::Completed)
+            .count();
+        if open_tasks == 0 {
+            return Self {
+                remaining_secs: (state.total_tasks > 0).then_some(0),
+                finish_at: (state.total_tasks > 0).then_some(now),
+                ..Self::default()
+            };
+        }
+
+        let velocity_secs = state
+            .velocity(now)
+            .per_hour
+            .filter(|&rate| rate > 0.0)
+            .map(|rate| (open_tasks as f64 / rate * 3600.0) as i64);
+        // Phases nobody has started on yet go by the average of all tasks
+        let finished: Vec<Duration> = state
+            .task_times
+            .values()
+            .filter_map(|t| Some(t.completed_at? - t.started_at?))
+            .collect();
+        let average = (!finished.is_empty())
+            .then(|| finished.iter().copied().sum::<Duration>() / finished.len() as i32);
+        let phase_remaining: Vec<Duration> = state
+            .phases
+            .iter()
+            .filter_map(|p| {
+                let open = p
+                    .tasks
+                    .iter()
+                    .filter(|t| t.status != TaskStatus::Completed)
+                    .count();
+                state
+                    .phase_timing(p, now)
+                    .remaining
+                    .or_else(|| average.filter(|_| open > 0).map(|a| a * open as i32))
+            })
+            .collect();
+        let phase_secs = (!phase_remaining.is_empty()).then(|| {
+            let serial: Duration = phase_remaining.into_iter().sum();
+            serial.num_seconds() / state.active_agents().max(1) as i64
+        });
+        let path = state.critical_path(now);
+        let critical_path_secs = (!path.tasks.is_empty()).then_some(path.remaining_secs);
+
+        let estimates: Vec<i64> = [velocity_secs, phase_secs].into_iter().flatten().collect();
+        let remaining_secs = (!estimates.is_empty()).then(|| {
+            let mean = estimates.iter().sum::<i64>() / estimates.len() as i64;
+            mean.max(critical_path_secs.unwrap_or(0))
+        });
+        Self {
+            open_tasks,
+            velocity_secs,
+            phase_secs,
+            critical_path_secs,
+            remaining_secs,
+            finish_at: remaining_secs.map(|secs| now + Duration::seconds(secs)),
+        }
+    }
+
+    /// One line for reports: `~3h20m left, done around 2026-02-08 14:20 UTC
+    /// (velocity 2h50m, phase averages 3h50m, critical path 1h00m)`
+    pub fn summary(&self) -> String {
+        let (Some(remaining), Some(finish)) = (self.remaining_secs, self.finish_at) else {
+            return "not enough history yet".to_string();
+        };
+        if self.open_tasks == 0 {
+            return "all tasks done".to_string();
+        }
+        let bases: Vec<String> = [
+            ("velocity", self.velocity_secs),
+            ("phase averages", self.phase_secs),
+            ("critical path", self.critical_path_secs),
+        ]
+        .into_iter()
+        .filter_map(|(name, secs)| Some(format!("{name} {}", format_secs(secs?))))
+        .collect();
+        format!(
+            "~{} left, done around {} ({})",
+            format_secs(remaining),
+            finish.format("%Y-%m-%d %H:%M UTC"),
+            bases.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::velocity::ProgressSample;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        format!("2026-02-08T{time}:00Z").parse().unwrap()
+    }
+
+    #[test]
+    fn combines_velocity_and_phase_averages() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [x] P1-T1: A\n\n### [ ] P1-T2: B\n\n### [ ] P1-T3: C\n",
+        )
+        .unwrap();
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T08:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T09:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+        ];
+        state.update_from_events(&parse_hook_events(&events.join("\n")).events);
+        // No velocity yet: two open tasks at an hour each
+        state.progress_samples.clear();
+        let forecast = Forecast::compute(&state, at("10:00"));
+        assert_eq!(forecast.velocity_secs, None);
+        assert_eq!(forecast.phase_secs, Some(7200));
+        assert_eq!(forecast.remaining_secs, Some(7200));
+        assert_eq!(forecast.finish_at, Some(at("12:00")));
+
+        // Two tasks closed per hour over the last two hours
+        state.progress_samples = vec![
+            ProgressSample {
+                at: at("08:00"),
+                open: 6,
+                closed: 0,
+            },
+            ProgressSample {
+                at: at("10:00"),
+                open: 2,
+                closed: 4,
+            },
+        ];
+        let forecast = Forecast::compute(&state, at("10:00"));
+        assert_eq!(forecast.velocity_secs, Some(3600));
+        assert_eq!(forecast.remaining_secs, Some(5400));
+        assert_eq!(
+            forecast.summary(),
+            "~1h30m left, done around 2026-02-08 11:30 UTC (velocity 1h00m, phase averages 2h00m)"
+        );
+
+        let empty = Forecast::compute(&DashboardState::default(), at("10:00"));
+        assert_eq!(empty.summary(), "not enough history yet");
+    }
+
+    #[test]
+    fn estimates_forecast_before_any_history() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [ ] P1-T1: A\n- est: 2h\n\n### [ ] P1-T2: B\n- est: 30m\n",
+        )
+        .unwrap();
+        state.progress_samples.clear();
+        let forecast = Forecast::compute(&state, at("10:00"));
+        assert_eq!(forecast.phase_secs, Some(9000));
+        assert_eq!(forecast.finish_at, Some(at("12:30")));
+    }
+
+    #[test]
+    fn critical_path_is_a_floor() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [x] T1: A\n\n### [ ] T2: B\n- **blocked_by**: T1\n\n\
+             ### [ ] T3: C\n- **blocked_by**: T2\n",
+        )
+        .unwrap();
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T08:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T09:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+        ];
+        state.update_from_events(&parse_hook_events(&events.join("\n")).events);
+        state.progress_samples = vec![
+            ProgressSample {
+                at: at("09:00"),
+                open: 6,
+                closed: 0,
+            },
+            ProgressSample {
+                at: at("10:00"),
+                open: 2,
+                closed: 4,
+            },
+        ];
+        // Velocity says 30m and phases 2h, but T2 → T3 takes 2h in a row
+        let forecast = Forecast::compute(&state, at("10:00"));
+        assert_eq!(forecast.velocity_secs, Some(1800));
+        assert_eq!(forecast.critical_path_secs, Some(7200));
+        assert_eq!(forecast.remaining_secs, Some(7200));
+    }
+}
@@ -0,0 +1,113 @@
+//! Agent health from its recent error rate
+//!
+//! Every agent keeps the outcomes of its last [`HEALTH_WINDOW`] tool calls
+//! and errors. The share of errors among them is its rolling error rate,
+//! and the `[health]` thresholds turn that into Healthy, Degraded or
+//! Failing, so an agent stuck in a failing loop stands out long before its
+//! error count looks alarming. Too few outcomes say nothing yet.
+
+use crate::data::state::AgentState;
+
+/// Outcomes kept per agent for its rolling error rate
+pub const HEALTH_WINDOW: usize = 20;
+
+/// Outcomes needed before an agent can be anything but healthy
+pub const MIN_OUTCOMES: usize = 5;
+
+/// Derived health of an agent, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AgentHealth {
+    Healthy,
+    Degraded,
+    Failing,
+}
+
+impl AgentHealth {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AgentHealth::Healthy => "healthy",
+            AgentHealth::Degraded => "degraded",
+            AgentHealth::Failing => "failing",
+        }
+    }
+}
+
+/// Error rates at which agents degrade and fail (`[health]`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthThresholds {
+    pub degraded: f64,
+    pub failing: f64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            degraded: 0.2,
+            failing: 0.5,
+        }
+    }
+}
+
+impl HealthThresholds {
+    /// Health of `agent` by its rolling error rate
+    pub fn assess(&self, agent: &AgentState) -> AgentHealth {
+        match error_rate(agent) {
+            Some(rate) if rate >= self.failing => AgentHealth::Failing,
+            Some(rate) if rate >= self.degraded => AgentHealth::Degraded,
+            _ => AgentHealth::Healthy,
+        }
+    }
+}
+
+/// Share of errors among the agent's recent outcomes; `None` until there
+/// are [`MIN_OUTCOMES`]
+pub fn error_rate(agent: &AgentState) -> Option<f64> {
+    let outcomes = &agent.recent_outcomes;
+    if outcomes.len() < MIN_OUTCOMES {
+        return None;
+    }
+    let errors = outcomes.iter().filter(|&&failed| failed).count();
+    Some(errors as f64 / outcomes.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+    use crate::data::state::DashboardState;
+
+    fn event(kind: &str, minute: usize) -> String {
+        format!(
+            r#"{{"event_type":"{kind}","timestamp":"2026-02-08T10:{minute:02}:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash","error_message":"exit 1"}}"#
+        )
+    }
+
+    #[test]
+    fn rolling_error_rate_sets_health() {
+        let thresholds = HealthThresholds::default();
+        let mut state = DashboardState::default();
+        let calls: Vec<String> = (0..4).map(|i| event("tool_end", i)).collect();
+        state.update_from_events(&parse_hook_events(&calls.join("\n")).events);
+        // Too few outcomes to judge
+        assert_eq!(error_rate(&state.agents["a"]), None);
+
+        let errors = [event("error", 4), event("tool_end", 5)].join("\n");
+        state.update_from_events(&parse_hook_events(&errors).events);
+        assert_eq!(error_rate(&state.agents["a"]), Some(1.0 / 6.0));
+        assert_eq!(thresholds.assess(&state.agents["a"]), AgentHealth::Healthy);
+
+        let errors: Vec<String> = (6..12).map(|i| event("error", i)).collect();
+        state.update_from_events(&parse_hook_events(&errors.join("\n")).events);
+        assert_eq!(thresholds.assess(&state.agents["a"]), AgentHealth::Failing);
+
+        // Only the last HEALTH_WINDOW outcomes count
+        let calls: Vec<String> = (12..30).map(|i| event("tool_end", i)).collect();
+        state.update_from_events(&parse_hook_events(&calls.join("\n")).events);
+        assert_eq!(error_rate(&state.agents["a"]), Some(0.1));
+        let strict = HealthThresholds {
+            degraded: 0.1,
+            failing: 0.5,
+        };
+        assert_eq!(strict.assess(&state.agents["a"]), AgentHealth::Degraded);
+    }
+}
@@ -0,0 +1,150 @@
+//! Repetitive tool loop detection
+//!
+//! An agent that keeps issuing the same tool call with the same arguments —
+//! re-reading one file, re-running one failing command — is usually stuck.
+//! Among each agent's recent tool calls, those within `[loops]` `window_secs`
+//! of its latest call are grouped by tool and target (file or arguments);
+//! a group reaching `repeats` calls is reported as a loop. Calls without a
+//! target are never grouped, since there is nothing to compare.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::analysis::metrics::format_secs;
+use crate::data::state::AgentState;
+
+/// Repeats and window that make a loop (`[loops]`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopThresholds {
+    /// Identical calls needed; at most the tool calls kept per agent
+    pub repeats: usize,
+    pub window_secs: i64,
+}
+
+impl Default for LoopThresholds {
+    fn default() -> Self {
+        Self {
+            repeats: 5,
+            window_secs: 120,
+        }
+    }
+}
+
+/// The same call made over and over by one agent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolLoop {
+    pub tool_name: String,
+    /// File path or argument summary the calls share
+    pub target: String,
+    pub count: usize,
+    pub first_at: DateTime<Utc>,
+    pub last_at: DateTime<Utc>,
+}
+
+impl ToolLoop {
+    /// `Read src/app.rs × 6 in 1m30s`
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {} \u{00D7} {} in {}",
+            self.tool_name,
+            self.target,
+            self.count,
+            format_secs((self.last_at - self.first_at).num_seconds())
+        )
+    }
+}
+
+impl LoopThresholds {
+    /// The most repeated call of `agent` within the window before its
+    /// latest call, if it was repeated often enough
+    pub fn detect(&self, agent: &AgentState) -> Option<ToolLoop> {
+        let latest = agent.tool_invocations.last()?.started_at;
+        let since = latest - Duration::seconds(self.window_secs);
+        let mut groups: Vec<ToolLoop> = Vec::new();
+        for inv in agent
+            .tool_invocations
+            .iter()
+            .filter(|inv| inv.started_at >= since)
+        {
+            let Some(target) = inv.file_path.as_ref().or(inv.args.as_ref()) else {
+                continue;
+            };
+            match groups
+                .iter_mut()
+                .find(|g| *g.tool_name == *inv.tool_name && &g.target == target)
+            {
+                Some(group) => {
+                    group.count += 1;
+                    group.last_at = inv.started_at;
+                }
+                None => groups.push(ToolLoop {
+                    tool_name: inv.tool_name.to_string(),
+                    target: target.clone(),
+                    count: 1,
+                    first_at: inv.started_at,
+                    last_at: inv.started_at,
+                }),
+            }
+        }
+        groups
+            .into_iter()
+            .filter(|g| g.count >= self.repeats)
+            .max_by(|a, b| a.count.cmp(&b.count).then(a.last_at.cmp(&b.last_at)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+    use crate::data::state::DashboardState;
+
+    fn read(second: usize, file: &str) -> String {
+        format!(
+            r#"{{"event_type":"tool_start","timestamp":"2026-02-08T10:{:02}:{:02}Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Read","file_path":"{file}"}}"#,
+            second / 60,
+            second % 60
+        )
+    }
+
+    #[test]
+    fn repeated_calls_within_the_window_are_a_loop() {
+        let thresholds = LoopThresholds::default();
+        let mut state = DashboardState::default();
+        let mut lines: Vec<String> = (0..4).map(|i| read(i * 10, "src/app.rs")).collect();
+        lines.push(read(45, "src/main.rs"));
+        state.update_from_events(&parse_hook_events(&lines.join("\n")).events);
+        assert_eq!(thresholds.detect(&state.agents["a"]), None);
+
+        state.update_from_events(&parse_hook_events(&read(90, "src/app.rs")).events);
+        let found = thresholds.detect(&state.agents["a"]).unwrap();
+        assert_eq!(found.target, "src/app.rs");
+        assert_eq!(found.count, 5);
+        assert_eq!(found.summary(), "Read src/app.rs \u{00D7} 5 in 1m30s");
+
+        // Calls outside the window no longer count
+        state.update_from_events(&parse_hook_events(&read(150, "src/lib.rs")).events);
+        assert_eq!(thresholds.detect(&state.agents["a"]), None);
+    }
+
+    #[test]
+    fn bash_commands_are_told_apart_by_arguments() {
+        let bash = |second: usize, command: &str| {
+            format!(
+                r#"{{"event_type":"tool_start","timestamp":"2026-02-08T10:00:{second:02}Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash","args":"{command}"}}"#
+            )
+        };
+        let lines: Vec<String> = (0..6)
+            .map(|i| bash(i * 5, if i % 2 == 0 { "cargo test" } else { "ls" }))
+            .collect();
+        let mut state = DashboardState::default();
+        state.update_from_events(&parse_hook_events(&lines.join("\n")).events);
+        let thresholds = LoopThresholds {
+            repeats: 3,
+            window_secs: 60,
+        };
+        let found = thresholds.detect(&state.agents["a"]).unwrap();
+        // Both repeat three times; the latest one wins
+        assert_eq!(found.target, "ls");
+        assert_eq!(LoopThresholds::default().detect(&state.agents["a"]), None);
+    }
+}
@@ -0,0 +1,632 @@
+//! Performance metrics derived from the dashboard state
+//!
+//! How long finished tasks took (average and percentiles), how many tasks
+//! each agent finishes per hour, how long each tool's calls take and how
+//! often they end in an error (overall and per agent), how long agents
+//! spend running, idle or in error, how many tokens each task cost, when
+//! each phase actually started and ended, and how fast tasks are closed.
+//! Used by the agent drill-down and the `stats` subcommand.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::analysis::critical_path::CriticalPath;
+use crate::analysis::forecast::Forecast;
+use crate::analysis::velocity::{ProgressSample, Velocity};
+use crate::data::state::{AgentState, AgentStatus, DashboardState};
+
+/// Summary of a set of durations, in seconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct DurationStats {
+    pub count: usize,
+    pub mean_secs: i64,
+    pub p50_secs: i64,
+    pub p90_secs: i64,
+    pub max_secs: i64,
+}
+
+impl DurationStats {
+    /// Stats of `secs`; all zero when it is empty
+    pub fn from_secs(mut secs: Vec<i64>) -> Self {
+        if secs.is_empty() {
+            return Self::default();
+        }
+        secs.sort_unstable();
+        let count = secs.len();
+        Self {
+            count,
+            mean_secs: secs.iter().sum::<i64>() / count as i64,
+            p50_secs: percentile(&secs, 50),
+            p90_secs: percentile(&secs, 90),
+            max_secs: secs[count - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty `values`
+fn percentile(values: &[i64], pct: usize) -> i64 {
+    let rank = (pct * values.len()).div_ceil(100).max(1);
+    values[rank - 1]
+}
+
+/// Throughput and time-in-status of one agent
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgentMetrics {
+    pub agent_id: String,
+    pub tasks_finished: usize,
+    /// Time between the agent's first and latest event
+    pub active_secs: i64,
+    /// Tasks finished per hour active; `None` before a minute has passed
+    pub tasks_per_hour: Option<f64>,
+    pub running_secs: i64,
+    pub idle_secs: i64,
+    pub error_secs: i64,
+    /// The agent's own tool calls, most called first
+    pub tools: Vec<ToolMetrics>,
+}
+
+impl AgentMetrics {
+    /// Metrics of `agent`, counting its current status as lasting until `now`
+    pub fn of(agent: &AgentState, now: DateTime<Utc>) -> Self {
+        let tasks_finished = agent
+            .task_history
+            .iter()
+            .filter(|e| e.completed_at.is_some())
+            .count();
+        let active_secs = match (agent.first_seen, agent.last_seen) {
+            (Some(first), Some(last)) => (last - first).num_seconds().max(0),
+            _ => 0,
+        };
+        let tasks_per_hour =
+            (active_secs >= 60).then(|| tasks_finished as f64 * 3600.0 / active_secs as f64);
+
+        let mut times = agent.status_time;
+        if let Some(last) = agent.status_history.last() {
+            let current = (now - last.at).num_milliseconds().max(0);
+            match agent.status {
+                AgentStatus::Idle => times.idle_ms += current,
+                AgentStatus::Running => times.running_ms += current,
+                AgentStatus::Error => times.error_ms += current,
+            }
+        }
+
+        Self {
+            agent_id: agent.agent_id.clone(),
+            tasks_finished,
+            active_secs,
+            tasks_per_hour,
+            running_secs: times.running_ms / 1000,
+            idle_secs: times.idle_ms / 1000,
+            error_secs: times.error_ms / 1000,
+            tools: tool_metrics([agent]),
+        }
+    }
+}
+
+/// Tokens and cost attributed to one task
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskCost {
+    pub task_id: String,
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Tasks listed under "Most expensive tasks" by `stats`
+const MAX_EXPENSIVE_TASKS: usize = 5;
+
+/// Actual span of one phase, from its first task event to its last
+/// completion
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseSpan {
+    pub phase_id: String,
+    pub started_at: DateTime<Utc>,
+    /// `None` while some of its tasks are still open
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Until `ended_at`, or until now for a phase still open
+    pub duration_secs: i64,
+}
+
+/// Calls, failures and duration of one tool
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolMetrics {
+    pub tool_name: String,
+    pub calls: usize,
+    pub failures: usize,
+    /// Failures per call, 0.0–1.0
+    pub failure_rate: f64,
+    /// Mean time from `tool_start` to `tool_end`; `None` when no call has
+    /// ended yet
+    pub mean_ms: Option<i64>,
+}
+
+/// Calls of one tool that fail unusually often for one agent
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolOffender {
+    pub agent_id: String,
+    #[serde(flatten)]
+    pub tool: ToolMetrics,
+}
+
+impl ToolOffender {
+    /// "Bash fails 30% of the time for agent X (3 of 10 calls)"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} fails {:.0}% of the time for {} ({} of {} calls)",
+            self.tool.tool_name,
+            self.tool.failure_rate * 100.0,
+            self.agent_id,
+            self.tool.failures,
+            self.tool.calls
+        )
+    }
+}
+
+/// Calls an agent must have made with a tool before its failure rate counts
+/// as a pattern rather than bad luck
+const MIN_OFFENDER_CALLS: usize = 3;
+
+/// Offenders listed by `stats`
+const MAX_OFFENDERS: usize = 5;
+
+/// Tool metrics summed over `agents`, most called first
+fn tool_metrics<'a>(agents: impl IntoIterator<Item = &'a AgentState>) -> Vec<ToolMetrics> {
+    // Calls, failures, ended calls, total milliseconds of the ended calls
+    let mut totals: HashMap<&str, (usize, usize, usize, i64)> = HashMap::new();
+    for agent in agents {
+        for (tool, calls) in &agent.tool_counts {
+            totals.entry(tool).or_default().0 += calls;
+        }
+        for (tool, failures) in &agent.tool_failures {
+            totals.entry(tool).or_default().1 += failures;
+        }
+        for (tool, time) in &agent.tool_time {
+            let entry = totals.entry(tool).or_default();
+            entry.2 += time.ended;
+            entry.3 += time.total_ms;
+        }
+    }
+    let mut tools: Vec<ToolMetrics> = totals
+        .into_iter()
+        .map(|(tool, (calls, failures, ended, total_ms))| ToolMetrics {
+            tool_name: tool.to_string(),
+            calls,
+            failures,
+            failure_rate: if calls > 0 {
+                (failures as f64 / calls as f64).min(1.0)
+            } else {
+                0.0
+            },
+            mean_ms: (ended > 0).then(|| total_ms / ended as i64),
+        })
+        .collect();
+    tools.sort_by(|a, b| {
+        b.calls
+            .cmp(&a.calls)
+            .then_with(|| a.tool_name.cmp(&b.tool_name))
+    });
+    tools
+}
+
+/// Performance metrics of the whole board
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Metrics {
+    /// Durations of tasks with both a start and an end event
+    pub task_durations: DurationStats,
+    /// Sorted by agent id
+    pub agents: Vec<AgentMetrics>,
+    /// Most called first
+    pub tools: Vec<ToolMetrics>,
+    /// Agent/tool pairs with the highest failure rates, worst first
+    pub tool_offenders: Vec<ToolOffender>,
+    /// Tasks with reported usage, most expensive first
+    pub task_costs: Vec<TaskCost>,
+    /// Phases with at least one task event, in TASKS.md order
+    pub phases: Vec<PhaseSpan>,
+    pub velocity: Velocity,
+    /// Open/closed task counts over time, oldest first
+    pub burndown: Vec<ProgressSample>,
+    pub critical_path: CriticalPath,
+    pub forecast: Forecast,
+}
+
+impl Metrics {
+    pub fn compute(state: &DashboardState, now: DateTime<Utc>) -> Self {
+        let task_durations = DurationStats::from_secs(
+            state
+                .task_times
+                .values()
+                .filter_map(|t| Some((t.completed_at? - t.started_at?).num_seconds()))
+                .filter(|&secs| secs >= 0)
+                .collect(),
+        );
+
+        let mut agents: Vec<AgentMetrics> = state
+            .agents
+            .values()
+            .map(|a| AgentMetrics::of(a, now))
+            .collect();
+        agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+
+        let tools = tool_metrics(state.agents.values());
+        let tool_offenders = worst_offenders(&agents, MAX_OFFENDERS);
+
+        let mut task_costs: Vec<TaskCost> = state
+            .task_times
+            .iter()
+            .filter(|(_, t)| t.tokens > 0 || t.cost_usd > 0.0)
+            .map(|(id, t)| TaskCost {
+                task_id: id.clone(),
+                tokens: t.tokens,
+                cost_usd: t.cost_usd,
+            })
+            .collect();
+        task_costs.sort_by(|a, b| {
+            b.cost_usd
+                .total_cmp(&a.cost_usd)
+                .then_with(|| b.tokens.cmp(&a.tokens))
+                .then_with(|| a.task_id.cmp(&b.task_id))
+        });
+
+        let phases = state
+            .phases
+            .iter()
+            .filter_map(|p| {
+                let started_at = p.started_at?;
+                Some(PhaseSpan {
+                    phase_id: p.id.clone(),
+                    started_at,
+                    ended_at: p.ended_at,
+                    duration_secs: (p.ended_at.unwrap_or(now) - started_at).num_seconds(),
+                })
+            })
+            .collect();
+
+        let burndown = state.burndown();
+        Self {
+            task_durations,
+            agents,
+            tools,
+            tool_offenders,
+            task_costs,
+            phases,
+            velocity: Velocity::from_samples(&burndown, now),
+            burndown,
+            critical_path: state.critical_path(now),
+            forecast: state.forecast(now),
+        }
+    }
+
+    /// Plain-text report printed by `stats`
+    pub fn to_text(&self) -> String {
+        let d = &self.task_durations;
+        let mut text = if d.count == 0 {
+            "Tasks: none finished yet\n".to_string()
+        } else {
+            format!(
+                "Tasks: {} finished, avg {}, p50 {}, p90 {}, max {}\n",
+                d.count,
+                format_secs(d.mean_secs),
+                format_secs(d.p50_secs),
+                format_secs(d.p90_secs),
+                format_secs(d.max_secs)
+            )
+        };
+        let rate = |r: Option<f64>, unit: &str| {
+            r.map_or_else(|| "-".to_string(), |r| format!("{r:.1}/{unit}"))
+        };
+        text.push_str(&format!(
+            "Velocity: {} tasks, {} tasks\n",
+            rate(self.velocity.per_hour, "h"),
+            rate(self.velocity.per_day, "day")
+        ));
+        let path = &self.critical_path;
+        if !path.tasks.is_empty() {
+            text.push_str(&format!(
+                "Critical path: {} (~{} left",
+                path.tasks.join(" \u{2192} "),
+                format_secs(path.remaining_secs)
+            ));
+            if let Some(ref gating) = path.gating {
+                text.push_str(&format!(", gated by {gating}"));
+            }
+            text.push_str(")\n");
+        }
+        text.push_str(&format!("Forecast: {}\n", self.forecast.summary()));
+        if !self.phases.is_empty() {
+            text.push_str("\nPhases:\n");
+            let width = self
+                .phases
+                .iter()
+                .map(|p| p.phase_id.len())
+                .max()
+                .unwrap_or(0);
+            let time = |t: DateTime<Utc>| t.format("%m-%d %H:%M").to_string();
+            for p in &self.phases {
+                let end = p.ended_at.map_or_else(|| "open".to_string(), time);
+                text.push_str(&format!(
+                    "  {:<width$}  {} \u{2192} {:<11}  {}\n",
+                    p.phase_id,
+                    time(p.started_at),
+                    end,
+                    format_secs(p.duration_secs)
+                ));
+            }
+        }
+        if !self.agents.is_empty() {
+            text.push_str("\nAgents:\n");
+            let width = self
+                .agents
+                .iter()
+                .map(|a| a.agent_id.len())
+                .max()
+                .unwrap_or(0);
+            for a in &self.agents {
+                let rate = a
+                    .tasks_per_hour
+                    .map_or_else(|| "-".to_string(), |r| format!("{r:.1}/h"));
+                text.push_str(&format!(
+                    "  {:<width$}  {:>3} tasks  {:>7}  running {}  idle {}  error {}\n",
+                    a.agent_id,
+                    a.tasks_finished,
+                    rate,
+                    format_secs(a.running_secs),
+                    format_secs(a.idle_secs),
+                    format_secs(a.error_secs)
+                ));
+            }
+        }
+        if !self.tools.is_empty() {
+            text.push_str("\nTools:\n");
+            let width = self
+                .tools
+                .iter()
+                .map(|t| t.tool_name.len())
+                .max()
+                .unwrap_or(0);
+            for t in &self.tools {
+                text.push_str(&format!(
+                    "  {:<width$}  {:>5} calls  {:>4} failed ({:.1}%)  avg {}\n",
+                    t.tool_name,
+                    t.calls,
+                    t.failures,
+                    t.failure_rate * 100.0,
+                    t.mean_ms.map_or_else(|| "-".to_string(), format_ms)
+                ));
+            }
+        }
+        if !self.task_costs.is_empty() {
+            text.push_str("\nMost expensive tasks:\n");
+            let shown = &self.task_costs[..self.task_costs.len().min(MAX_EXPENSIVE_TASKS)];
+            let width = shown.iter().map(|t| t.task_id.len()).max().unwrap_or(0);
+            for t in shown {
+                text.push_str(&format!(
+                    "  {:<width$}  {:>7} tok  ${:.2}\n",
+                    t.task_id,
+                    format_tokens(t.tokens),
+                    t.cost_usd
+                ));
+            }
+        }
+        if !self.tool_offenders.is_empty() {
+            text.push_str("\nWorst offenders:\n");
+            for offender in &self.tool_offenders {
+                text.push_str(&format!("  {}\n", offender.summary()));
+            }
+        }
+        text
+    }
+}
+
+/// Failing agent/tool pairs of `agents` with enough calls to tell, highest
+/// failure rate first
+pub fn worst_offenders(agents: &[AgentMetrics], limit: usize) -> Vec<ToolOffender> {
+    let mut offenders: Vec<ToolOffender> = agents
+        .iter()
+        .flat_map(|a| {
+            a.tools
+                .iter()
+                .filter(|t| t.failures > 0 && t.calls >= MIN_OFFENDER_CALLS)
+                .map(|t| ToolOffender {
+                    agent_id: a.agent_id.clone(),
+                    tool: t.clone(),
+                })
+        })
+        .collect();
+    offenders.sort_by(|a, b| {
+        b.tool
+            .failure_rate
+            .total_cmp(&a.tool.failure_rate)
+            .then_with(|| b.tool.failures.cmp(&a.tool.failures))
+            .then_with(|| a.agent_id.cmp(&b.agent_id))
+            .then_with(|| a.tool.tool_name.cmp(&b.tool.tool_name))
+    });
+    offenders.truncate(limit);
+    offenders
+}
+
+/// `850ms`, `4.2s`, or `format_secs` from a minute on
+pub fn format_ms(ms: i64) -> String {
+    let ms = ms.max(0);
+    if ms >= 60_000 {
+        format_secs(ms / 1000)
+    } else if ms >= 1000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{ms}ms")
+    }
+}
+
+/// Format a token count compactly: `950`, `12.3k`, `1.2M`
+pub fn format_tokens(n: u64) -> String {
+    if n >= 1_000_000 {
+        format!("{:.1}M", n as f64 / 1_000_000.0)
+    } else if n >= 1_000 {
+        format!("{:.1}k", n as f64 / 1_000.0)
+    } else {
+        n.to_string()
+    }
+}
+
+/// `2h05m`, `12m30s`, or `40s`
+pub fn format_secs(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn state(lines: &[&str]) -> DashboardState {
+        let mut state = DashboardState::default();
+        state.update_from_events(&parse_hook_events(&lines.join("\n")).events);
+        state
+    }
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let stats = DurationStats::from_secs((1..=10).rev().map(|n| n * 60).collect());
+        assert_eq!(stats.count, 10);
+        assert_eq!(stats.mean_secs, 330);
+        assert_eq!(stats.p50_secs, 300);
+        assert_eq!(stats.p90_secs, 540);
+        assert_eq!(stats.max_secs, 600);
+        assert_eq!(DurationStats::from_secs(vec![]), DurationStats::default());
+        assert_eq!(DurationStats::from_secs(vec![7]).p90_secs, 7);
+    }
+
+    #[test]
+    fn computes_throughput_failure_rates_and_time_in_status() {
+        let state = state(&[
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:02:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"exit 1"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:05:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:06:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Read"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:20:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:30:00Z","agent_id":"a","task_id":"T2","session_id":"s"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T11:00:00Z","agent_id":"a","task_id":"T2","session_id":"s"}"#,
+        ]);
+        let now = "2026-02-08T11:10:00Z".parse().unwrap();
+        let metrics = state.metrics(now);
+
+        assert_eq!(metrics.task_durations.count, 2);
+        assert_eq!(metrics.task_durations.mean_secs, 25 * 60);
+
+        let agent = &metrics.agents[0];
+        assert_eq!(agent.tasks_finished, 2);
+        assert_eq!(agent.tasks_per_hour, Some(2.0));
+        // Error from 10:02 until the next tool call at 10:05
+        assert_eq!(agent.error_secs, 3 * 60);
+        assert_eq!(agent.running_secs, (2 + 15 + 30) * 60);
+        // 10:20–10:30, plus the ten minutes since the last event
+        assert_eq!(agent.idle_secs, 20 * 60);
+
+        let names: Vec<&str> = metrics.tools.iter().map(|t| t.tool_name.as_str()).collect();
+        assert_eq!(names, ["Bash", "Read"]);
+        assert_eq!(metrics.tools[0].failures, 1);
+        assert_eq!(metrics.tools[0].failure_rate, 0.5);
+        assert_eq!(metrics.tools[1].failures, 0);
+
+        let text = metrics.to_text();
+        assert!(text.starts_with("Tasks: 2 finished, avg 25m00s, p50 20m00s, p90 30m00s"));
+        assert!(text.contains("Velocity: - tasks, - tasks\n"));
+        assert!(text.contains("Forecast: not enough history yet\n"));
+        assert!(text.contains("  a    2 tasks    2.0/h  running 47m00s  idle 20m00s  error 3m00s"));
+        assert!(text.contains("  Bash      2 calls     1 failed (50.0%)  avg -"));
+    }
+
+    #[test]
+    fn phase_spans_run_from_first_event_to_last_completion() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n### [x] P1-T1: Parser\n### [x] P1-T2: Writer\n\
+             # Phase 2: UI\n### [ ] P2-T1: Gantt\n\
+             # Phase 3: Docs\n### [ ] P3-T1: README\n",
+        )
+        .unwrap();
+        state.update_from_events(
+            &parse_hook_events(
+                &[
+                    r#"{"event_type":"tool_start","timestamp":"2026-02-08T09:50:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s","tool_name":"Read"}"#,
+                    r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+                    r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:20:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+                    r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:20:00Z","agent_id":"a","task_id":"P1-T2","session_id":"s"}"#,
+                    r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:50:00Z","agent_id":"a","task_id":"P1-T2","session_id":"s"}"#,
+                    r#"{"event_type":"agent_start","timestamp":"2026-02-08T11:00:00Z","agent_id":"a","task_id":"P2-T1","session_id":"s"}"#,
+                ]
+                .join("\n"),
+            )
+            .events,
+        );
+        let now = "2026-02-08T11:30:00Z".parse().unwrap();
+        let metrics = state.metrics(now);
+
+        let ids: Vec<&str> = metrics.phases.iter().map(|p| p.phase_id.as_str()).collect();
+        assert_eq!(ids, ["P1", "P2"]);
+        assert_eq!(
+            metrics.phases[0].started_at,
+            "2026-02-08T09:50:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(metrics.phases[0].duration_secs, 60 * 60);
+        assert_eq!(metrics.phases[1].ended_at, None);
+        assert_eq!(metrics.phases[1].duration_secs, 30 * 60);
+
+        let text = metrics.to_text();
+        assert!(text.contains("\nPhases:\n  P1  02-08 09:50 \u{2192} 02-08 10:50  1h00m\n"));
+        assert!(text.contains("  P2  02-08 11:00 \u{2192} open         30m00s\n"));
+    }
+
+    #[test]
+    fn tool_durations_and_worst_offenders() {
+        let call = |agent: &str, secs: u32, tool: &str, fails: bool| {
+            let mut lines = vec![format!(
+                r#"{{"event_type":"tool_start","timestamp":"2026-02-08T10:{secs:02}:00Z","agent_id":"{agent}","task_id":"T1","session_id":"s","tool_name":"{tool}"}}"#
+            )];
+            if fails {
+                lines.push(format!(r#"{{"event_type":"error","timestamp":"2026-02-08T10:{secs:02}:01Z","agent_id":"{agent}","task_id":"T1","session_id":"s","error_message":"exit 1"}}"#));
+            }
+            lines.push(format!(r#"{{"event_type":"tool_end","timestamp":"2026-02-08T10:{secs:02}:02Z","agent_id":"{agent}","task_id":"T1","session_id":"s","tool_name":"{tool}"}}"#));
+            lines
+        };
+        let mut lines = Vec::new();
+        lines.extend(call("a", 1, "Bash", true));
+        lines.extend(call("a", 2, "Bash", false));
+        lines.extend(call("a", 3, "Bash", false));
+        lines.extend(call("b", 4, "Bash", true));
+        lines.extend(call("b", 5, "Bash", true));
+        lines.extend(call("b", 6, "Bash", false));
+        // Too few calls to count as a pattern
+        lines.extend(call("b", 7, "Edit", true));
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let metrics = state(&lines).metrics("2026-02-08T11:00:00Z".parse().unwrap());
+
+        assert_eq!(metrics.tools[0].calls, 6);
+        assert_eq!(metrics.tools[0].mean_ms, Some(2000));
+        assert_eq!(metrics.agents[0].tools[0].failures, 1);
+
+        let summaries: Vec<String> = metrics.tool_offenders.iter().map(|o| o.summary()).collect();
+        assert_eq!(
+            summaries,
+            [
+                "Bash fails 67% of the time for b (2 of 3 calls)",
+                "Bash fails 33% of the time for a (1 of 3 calls)",
+            ]
+        );
+        let text = metrics.to_text();
+        assert!(text.contains("avg 2.0s\n"));
+        assert!(text.contains("\nWorst offenders:\n  Bash fails 67%"));
+
+        assert!(metrics.task_costs.is_empty());
+        assert_eq!(format_ms(850), "850ms");
+        assert_eq!(format_ms(61_000), "1m01s");
+    }
+}
@@ -1 +1,7 @@
+pub mod critical_path;
+pub mod forecast;
+pub mod health;
+pub mod loops;
+pub mod metrics;
 pub mod rules;
+pub mod velocity;
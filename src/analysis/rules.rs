@@ -2,9 +2,52 @@
 //!
 //! Pattern-matching engine that categorizes error messages from hook events
 //! and provides retryable hints and actionable suggestions.
+//!
+//! Rules are tried from the highest priority down and the first match wins.
+//! Users can add their own rules in `~/.config/oh-my-claude-board/rules.toml`
+//! (priority 100 unless set, ahead of every built-in), and a section named
+//! like a built-in changes only the fields it sets, or drops the built-in
+//! with `enabled = false`:
+//!
+//! ```toml
+//! [rules.db-deadlock]
+//! patterns = ["deadlock detected", "could not serialize access"]
+//! category = "network"
+//! retryable = true
+//! suggestion = "Re-run; the test database is shared"
+//!
+//! [rules.missing-crate]
+//! regex = '^error\[E0432\]: unresolved import `(\w+)'
+//! category = "type"
+//! suggestion = "Module `$1` not found — check Cargo.toml"
+//!
+//! [rules.timeout]
+//! retryable = false
+//!
+//! [rules.dns]
+//! enabled = false
+//! ```
+//!
+//! A `regex` is compiled once when the file is loaded and matched against
+//! the message as is (add `(?i)` to ignore case); its capture groups fill
+//! `$1`, `$2`, `${name}` in the suggestion and in the optional `command`,
+//! a shell command the retry popup offers to run as a fix. In the command
+//! each value is shell-quoted, since the error text comes from the agent.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+use crate::actions;
+use crate::config::{self, ConfigValue};
 
 /// Error category derived from pattern matching
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ErrorCategory {
     Type,
     Runtime,
@@ -25,94 +68,152 @@ impl std::fmt::Display for ErrorCategory {
     }
 }
 
+impl FromStr for ErrorCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "type" => Ok(Self::Type),
+            "runtime" => Ok(Self::Runtime),
+            "network" => Ok(Self::Network),
+            "permission" => Ok(Self::Permission),
+            "unknown" => Ok(Self::Unknown),
+            other => Err(format!(
+                "unknown error category `{other}` (expected type, runtime, network, permission or unknown)"
+            )),
+        }
+    }
+}
+
+impl ErrorCategory {
+    /// Short tag shown next to errors in the agent panel
+    pub fn badge(&self) -> &'static str {
+        match self {
+            Self::Type => "TYPE",
+            Self::Runtime => "RUN",
+            Self::Network => "NET",
+            Self::Permission => "PERM",
+            Self::Unknown => "ERR",
+        }
+    }
+}
+
 /// Result of analyzing an error message
 #[derive(Debug, Clone)]
 pub struct ErrorAnalysis {
     pub category: ErrorCategory,
     pub retryable: bool,
-    pub suggestion: &'static str,
+    pub suggestion: Cow<'static, str>,
+    /// Shell command that may fix the error, offered by the retry popup
+    pub command: Option<Cow<'static, str>>,
 }
 
-/// Rule entry: pattern to match (lowercase), category, retryable, suggestion
-struct Rule {
+/// A built-in rule: lowercase patterns, category, retryable, suggestion
+struct BuiltIn {
+    name: &'static str,
+    priority: i64,
     patterns: &'static [&'static str],
     category: ErrorCategory,
     retryable: bool,
     suggestion: &'static str,
 }
 
-const RULES: &[Rule] = &[
+const BUILT_INS: &[BuiltIn] = &[
     // Permission
-    Rule {
+    BuiltIn {
+        name: "permission-denied",
+        priority: 40,
         patterns: &["permission denied"],
         category: ErrorCategory::Permission,
         retryable: false,
         suggestion: "Check file permissions",
     },
-    Rule {
+    BuiltIn {
+        name: "access-denied",
+        priority: 40,
         patterns: &["access denied"],
         category: ErrorCategory::Permission,
         retryable: false,
         suggestion: "Check access rights",
     },
     // Network
-    Rule {
+    BuiltIn {
+        name: "connection-refused",
+        priority: 30,
         patterns: &["connection refused"],
         category: ErrorCategory::Network,
         retryable: true,
         suggestion: "Check if service is running",
     },
-    Rule {
+    BuiltIn {
+        name: "timeout",
+        priority: 30,
         patterns: &["timeout", "timed out"],
         category: ErrorCategory::Network,
         retryable: true,
         suggestion: "Retry or increase timeout",
     },
-    Rule {
+    BuiltIn {
+        name: "rate-limit",
+        priority: 30,
         patterns: &["rate limit"],
         category: ErrorCategory::Network,
         retryable: true,
         suggestion: "Wait and retry",
     },
-    Rule {
+    BuiltIn {
+        name: "dns",
+        priority: 30,
         patterns: &["dns", "resolve"],
         category: ErrorCategory::Network,
         retryable: true,
         suggestion: "Check network connection",
     },
     // Type
-    Rule {
+    BuiltIn {
+        name: "type-error",
+        priority: 20,
         patterns: &["type error", "type mismatch"],
         category: ErrorCategory::Type,
         retryable: false,
         suggestion: "Fix type annotations",
     },
-    Rule {
+    BuiltIn {
+        name: "not-found",
+        priority: 20,
         patterns: &["cannot find", "not found"],
         category: ErrorCategory::Type,
         retryable: false,
         suggestion: "Check imports and paths",
     },
-    Rule {
+    BuiltIn {
+        name: "undefined",
+        priority: 20,
         patterns: &["undefined", "unresolved"],
         category: ErrorCategory::Type,
         retryable: false,
         suggestion: "Check variable/module names",
     },
     // Runtime
-    Rule {
+    BuiltIn {
+        name: "out-of-memory",
+        priority: 10,
         patterns: &["out of memory", "oom"],
         category: ErrorCategory::Runtime,
         retryable: false,
         suggestion: "Reduce memory usage",
     },
-    Rule {
+    BuiltIn {
+        name: "stack-overflow",
+        priority: 10,
         patterns: &["stack overflow"],
         category: ErrorCategory::Runtime,
         retryable: false,
         suggestion: "Check for infinite recursion",
     },
-    Rule {
+    BuiltIn {
+        name: "panic",
+        priority: 10,
         patterns: &["panic", "unwrap"],
         category: ErrorCategory::Runtime,
         retryable: false,
@@ -120,28 +221,372 @@ const RULES: &[Rule] = &[
     },
 ];
 
+/// Priority of user rules that do not set one: ahead of every built-in
+pub const DEFAULT_USER_PRIORITY: i64 = 100;
+
+/// Suggestion when no rule matches
+const FALLBACK_SUGGESTION: &str = "Investigate error details";
+
+/// Where a rule in effect comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSource {
+    BuiltIn,
+    /// A built-in with fields changed by the rules file
+    Overridden,
+    User,
+}
+
+impl RuleSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RuleSource::BuiltIn => "built-in",
+            RuleSource::Overridden => "built-in, overridden",
+            RuleSource::User => "rules.toml",
+        }
+    }
+}
+
+/// A rule in effect: a built-in, possibly overridden, or one from the
+/// user's rules file
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub name: Cow<'static, str>,
+    pub source: RuleSource,
+    /// Higher priorities are tried first
+    pub priority: i64,
+    /// Lowercase substrings, any of which matches
+    pub patterns: Vec<Cow<'static, str>>,
+    /// Matched against the original message; its captures fill the
+    /// suggestion template
+    pub regex: Option<Regex>,
+    pub category: ErrorCategory,
+    pub retryable: bool,
+    pub suggestion: Cow<'static, str>,
+    /// Shell command template offered as a fix, filled in like the
+    /// suggestion
+    pub command: Option<Cow<'static, str>>,
+}
+
+/// A rule that matched a message
+#[derive(Debug)]
+pub struct RuleMatch<'a> {
+    pub rule: &'a Rule,
+    /// The pattern or regex that matched
+    pub matched: String,
+    /// The suggestion with the regex captures filled in
+    pub suggestion: Cow<'static, str>,
+    /// The fix command with the regex captures filled in
+    pub command: Option<Cow<'static, str>>,
+}
+
+impl Rule {
+    /// How the rule matches `message` (`lower` is its lowercase form), if
+    /// it does
+    fn find_match(&self, message: &str, lower: &str) -> Option<RuleMatch<'_>> {
+        if let Some(regex) = &self.regex {
+            if let Some(captures) = regex.captures(message) {
+                let expand = |template: &str| {
+                    let mut out = String::new();
+                    captures.expand(template, &mut out);
+                    Cow::Owned(out)
+                };
+                return Some(RuleMatch {
+                    rule: self,
+                    matched: format!("regex {}", regex.as_str()),
+                    suggestion: expand(&self.suggestion),
+                    command: self
+                        .command
+                        .as_deref()
+                        .map(|template| Cow::Owned(expand_quoted(template, &captures))),
+                });
+            }
+        }
+        let pattern = self.patterns.iter().find(|p| lower.contains(p.as_ref()))?;
+        Some(RuleMatch {
+            rule: self,
+            matched: format!("pattern \"{pattern}\""),
+            suggestion: self.suggestion.clone(),
+            command: self.command.clone(),
+        })
+    }
+}
+
+/// `template` with `$1`, `$name`, `${name}` and `$$` filled in as
+/// `Captures::expand` does, but each value quoted for the shell
+fn expand_quoted(template: &str, captures: &Captures) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(at) = rest.find('$') {
+        out.push_str(&rest[..at]);
+        rest = &rest[at + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            out.push('$');
+            rest = after;
+            continue;
+        }
+        let (name, after) = match rest.strip_prefix('{').and_then(|r| r.split_once('}')) {
+            Some(braced) => braced,
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                rest.split_at(end)
+            }
+        };
+        if name.is_empty() {
+            out.push('$');
+            continue;
+        }
+        let value = match name.parse::<usize>() {
+            Ok(i) => captures.get(i),
+            Err(_) => captures.name(name),
+        };
+        out.push_str(&actions::quote(value.map_or("", |m| m.as_str())));
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The built-in rules, highest priority first
+pub fn built_in_rules() -> Vec<Rule> {
+    BUILT_INS
+        .iter()
+        .map(|b| Rule {
+            name: Cow::Borrowed(b.name),
+            source: RuleSource::BuiltIn,
+            priority: b.priority,
+            patterns: b.patterns.iter().map(|p| Cow::Borrowed(*p)).collect(),
+            regex: None,
+            category: b.category.clone(),
+            retryable: b.retryable,
+            suggestion: Cow::Borrowed(b.suggestion),
+            command: None,
+        })
+        .collect()
+}
+
+/// Rules in effect for this process; `None` until `set_rules` is called,
+/// which means the built-ins
+static RULES: RwLock<Option<Vec<Rule>>> = RwLock::new(None);
+
+/// File name of the user rules, inside the config directory
+pub const RULES_FILE: &str = "rules.toml";
+
+/// `~/.config/oh-my-claude-board/rules.toml`
+pub fn user_rules_path(home: &Path) -> PathBuf {
+    home.join(".config")
+        .join("oh-my-claude-board")
+        .join(RULES_FILE)
+}
+
+/// The built-ins merged with a rules file: one `[rules.<name>]` section per
+/// rule. A section named like a built-in changes only the fields it sets;
+/// `enabled = false` drops a rule. Sorted by priority, highest first; at
+/// equal priority user rules (in name order) come before built-ins.
+pub fn parse_rules(content: &str) -> Result<Vec<Rule>, String> {
+    let table = config::parse_table(content)?;
+    let mut sections: BTreeMap<&str, BTreeMap<&str, &ConfigValue>> = BTreeMap::new();
+    for (full_key, value) in &table {
+        let key = full_key
+            .strip_prefix("rules.")
+            .and_then(|key| key.rsplit_once('.'))
+            .ok_or_else(|| format!("`{full_key}` must be inside a [rules.<name>] section"))?;
+        sections.entry(key.0).or_default().insert(key.1, value);
+    }
+
+    let mut built_ins = built_in_rules();
+    let mut user = Vec::new();
+    let mut disabled = Vec::new();
+    for (name, fields) in sections {
+        let key = |field: &str| format!("rules.{name}.{field}");
+        let string = |field: &str| {
+            fields
+                .get(field)
+                .map(|value| {
+                    value
+                        .as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| format!("`{}` must be a string", key(field)))
+                })
+                .transpose()
+        };
+        let bool_field = |field: &str| match fields.get(field) {
+            Some(ConfigValue::Bool(b)) => Ok(Some(*b)),
+            Some(_) => Err(format!("`{}` must be true or false", key(field))),
+            None => Ok(None),
+        };
+        let patterns: Option<Vec<Cow<'static, str>>> = match fields.get("patterns") {
+            Some(ConfigValue::Array(items)) => Some(
+                items
+                    .iter()
+                    .map(|v| v.as_str().map(|p| Cow::<str>::Owned(p.to_lowercase())))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| format!("`{}` must be an array of strings", key("patterns")))?
+                    .into_iter()
+                    .filter(|p| !p.is_empty())
+                    .collect(),
+            ),
+            Some(_) => return Err(format!("`{}` must be an array of strings", key("patterns"))),
+            None => None,
+        };
+        let regex = string("regex")?
+            .map(|pattern| Regex::new(&pattern).map_err(|e| format!("`{}`: {e}", key("regex"))))
+            .transpose()?;
+        let category = string("category")?
+            .map(|c| {
+                c.parse::<ErrorCategory>()
+                    .map_err(|e| format!("`{}`: {e}", key("category")))
+            })
+            .transpose()?;
+        let priority = match fields.get("priority") {
+            Some(ConfigValue::Integer(n)) => Some(*n),
+            Some(_) => return Err(format!("`{}` must be an integer", key("priority"))),
+            None => None,
+        };
+        let retryable = bool_field("retryable")?;
+        let suggestion = string("suggestion")?;
+        let command = string("command")?;
+        if bool_field("enabled")? == Some(false) {
+            disabled.push(name);
+            continue;
+        }
+
+        let rule = match built_ins.iter_mut().find(|r| r.name == name) {
+            Some(rule) => {
+                rule.source = RuleSource::Overridden;
+                rule
+            }
+            None => {
+                let required = |field: &str| format!("`{}` is required", key(field));
+                user.push(Rule {
+                    name: Cow::Owned(name.to_string()),
+                    source: RuleSource::User,
+                    priority: DEFAULT_USER_PRIORITY,
+                    patterns: Vec::new(),
+                    regex: None,
+                    category: category.clone().ok_or_else(|| required("category"))?,
+                    retryable: false,
+                    suggestion: Cow::Owned(
+                        suggestion.clone().ok_or_else(|| required("suggestion"))?,
+                    ),
+                    command: None,
+                });
+                user.last_mut().expect("just pushed")
+            }
+        };
+        if let Some(patterns) = patterns {
+            rule.patterns = patterns;
+        }
+        if regex.is_some() {
+            rule.regex = regex;
+        }
+        if let Some(category) = category {
+            rule.category = category;
+        }
+        if let Some(priority) = priority {
+            rule.priority = priority;
+        }
+        if let Some(retryable) = retryable {
+            rule.retryable = retryable;
+        }
+        if let Some(suggestion) = suggestion {
+            rule.suggestion = Cow::Owned(suggestion);
+        }
+        if let Some(command) = command {
+            rule.command = (!command.trim().is_empty()).then_some(Cow::Owned(command));
+        }
+        if rule.regex.is_none() && rule.patterns.is_empty() {
+            return Err(format!("`rules.{name}` needs `patterns` or a `regex`"));
+        }
+    }
+
+    let mut rules = user;
+    rules.extend(
+        built_ins
+            .into_iter()
+            .filter(|r| !disabled.contains(&r.name.as_ref())),
+    );
+    rules.sort_by_key(|r| std::cmp::Reverse(r.priority));
+    Ok(rules)
+}
+
+/// The rules in effect with the rules file at `path`; the built-ins when
+/// the file does not exist
+pub fn load_rules(path: &Path) -> Result<Vec<Rule>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => parse_rules(&content).map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(built_in_rules()),
+        Err(e) => Err(format!("cannot read {}: {e}", path.display())),
+    }
+}
+
+/// Make `rules` the ones `analyze_error` uses
+pub fn set_rules(rules: Vec<Rule>) {
+    *RULES.write().unwrap_or_else(|e| e.into_inner()) = Some(rules);
+}
+
 /// Analyze an error message and return its category, retryable hint, and suggestion.
 ///
-/// Rules are matched in priority order (first match wins) using case-insensitive
-/// substring matching.
+/// Rules set with `set_rules` (the built-ins until then) are tried from the
+/// highest priority down and the first match wins: case-insensitive
+/// substring patterns or a regex.
 pub fn analyze_error(message: &str) -> ErrorAnalysis {
+    let rules = RULES.read().unwrap_or_else(|e| e.into_inner());
+    match rules.as_deref() {
+        Some(rules) => analyze_with(rules, message),
+        None => analyze_with(&built_in_rules(), message),
+    }
+}
+
+/// The first of `rules` matching `message`
+pub fn find_rule<'a>(rules: &'a [Rule], message: &str) -> Option<RuleMatch<'a>> {
     let lower = message.to_lowercase();
+    rules
+        .iter()
+        .find_map(|rule| rule.find_match(message, &lower))
+}
 
-    for rule in RULES {
-        if rule.patterns.iter().any(|p| lower.contains(p)) {
-            return ErrorAnalysis {
-                category: rule.category.clone(),
-                retryable: rule.retryable,
-                suggestion: rule.suggestion,
-            };
-        }
+/// `analyze_error` with `rules` in place of the ones in effect
+pub fn analyze_with(rules: &[Rule], message: &str) -> ErrorAnalysis {
+    match find_rule(rules, message) {
+        Some(m) => ErrorAnalysis {
+            category: m.rule.category.clone(),
+            retryable: m.rule.retryable,
+            suggestion: m.suggestion,
+            command: m.command,
+        },
+        None => ErrorAnalysis {
+            category: ErrorCategory::Unknown,
+            retryable: false,
+            suggestion: Cow::Borrowed(FALLBACK_SUGGESTION),
+            command: None,
+        },
     }
+}
 
-    ErrorAnalysis {
-        category: ErrorCategory::Unknown,
-        retryable: false,
-        suggestion: "Investigate error details",
+/// What `analyze` prints: the matching rule and its result
+pub fn explain(rules: &[Rule], message: &str) -> String {
+    let Some(m) = find_rule(rules, message) else {
+        return format!(
+            "No rule matched ({} rules checked)\nCategory:   Unknown\nRetryable:  no\nSuggestion: {FALLBACK_SUGGESTION}\n",
+            rules.len()
+        );
+    };
+    let mut text = format!(
+        "Rule:       {} ({}, priority {})\nMatched:    {}\nCategory:   {}\nRetryable:  {}\nSuggestion: {}\n",
+        m.rule.name,
+        m.rule.source.as_str(),
+        m.rule.priority,
+        m.matched,
+        m.rule.category,
+        if m.rule.retryable { "yes" } else { "no" },
+        m.suggestion
+    );
+    if let Some(command) = m.command {
+        text.push_str(&format!("Command:    {command}\n"));
     }
+    text
 }
 
 #[cfg(test)]
@@ -273,6 +718,199 @@ mod tests {
         assert_eq!(r.category, ErrorCategory::Permission);
     }
 
+    #[test]
+    fn user_rules_are_checked_before_the_built_ins() {
+        let rules = parse_rules(
+            r#"
+[rules.deadlock]
+patterns = ["Deadlock detected"]
+category = "network"
+retryable = true
+suggestion = "Re-run; the test database is shared"
+
+[rules.vendored]
+patterns = ["vendor/ not found"]
+category = "Runtime"
+suggestion = "Run make vendor"
+"#,
+        )
+        .unwrap();
+        assert_eq!(rules.len(), BUILT_INS.len() + 2);
+        assert_eq!(rules[0].name, "deadlock");
+        assert_eq!(rules[0].patterns, ["deadlock detected"]);
+        assert_eq!(rules[1].priority, DEFAULT_USER_PRIORITY);
+        assert!(!rules[1].retryable);
+
+        let r = analyze_with(&rules, "ERROR: deadlock detected in tx 42");
+        assert_eq!(r.category, ErrorCategory::Network);
+        assert!(r.retryable);
+        assert_eq!(r.suggestion, "Re-run; the test database is shared");
+        // Would be a Type error by the built-in "not found" rule
+        let r = analyze_with(&rules, "vendor/ not found");
+        assert_eq!(r.category, ErrorCategory::Runtime);
+        assert_eq!(r.suggestion, "Run make vendor");
+        // Anything else still falls through to the built-ins
+        let r = analyze_with(&rules, "permission denied");
+        assert_eq!(r.category, ErrorCategory::Permission);
+    }
+
+    #[test]
+    fn regex_rules_fill_the_suggestion_from_captures() {
+        let rules = parse_rules(
+            r#"
+[rules.missing-crate]
+regex = '^error\[E0432\]: unresolved import `(?P<krate>\w+)'
+category = "type"
+suggestion = "Module `$1` not found — check Cargo.toml (${krate})"
+"#,
+        )
+        .unwrap();
+        let r = analyze_with(
+            &rules,
+            "error[E0432]: unresolved import `serde_yaml`\n --> src/main.rs",
+        );
+        assert_eq!(r.category, ErrorCategory::Type);
+        assert_eq!(
+            r.suggestion,
+            "Module `serde_yaml` not found — check Cargo.toml (serde_yaml)"
+        );
+
+        let fix_rules = parse_rules(
+            r#"
+[rules.not-executable]
+regex = 'permission denied: (\S+\.sh)'
+category = "permission"
+suggestion = "Make $1 executable"
+command = "chmod +x $1"
+"#,
+        )
+        .unwrap();
+        let r = analyze_with(&fix_rules, "bash: permission denied: scripts/build.sh");
+        let quoted = actions::quote("scripts/build.sh");
+        assert_eq!(
+            r.command.as_deref(),
+            Some(format!("chmod +x {quoted}").as_str())
+        );
+        assert!(
+            explain(&fix_rules, "permission denied: a.sh").ends_with(&format!(
+                "Command:    chmod +x {}\n",
+                actions::quote("a.sh")
+            ))
+        );
+        assert_eq!(analyze_with(&fix_rules, "permission denied").command, None);
+        // Anchored: the same text later in the message does not match
+        let r = analyze_with(&rules, "warning: error[E0432]: unresolved import `x`");
+        assert!(!r.suggestion.starts_with("Module"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn captures_in_commands_are_shell_quoted() {
+        let rules = parse_rules(
+            r#"
+[rules.missing-file]
+regex = 'cannot open (?P<file>.+)'
+category = "runtime"
+suggestion = "Create $file"
+command = "touch ${file} && ls -- $1 $$HOME ${}"
+"#,
+        )
+        .unwrap();
+        let r = analyze_with(&rules, "cannot open a.txt; rm -rf ~ $(whoami) 'x'");
+        assert_eq!(
+            r.command.as_deref(),
+            Some(
+                r#"touch 'a.txt; rm -rf ~ $(whoami) '\''x'\''' && ls -- 'a.txt; rm -rf ~ $(whoami) '\''x'\''' $HOME ${}"#
+            )
+        );
+        // The suggestion is only shown, so it keeps the text as is
+        assert_eq!(r.suggestion, "Create a.txt; rm -rf ~ $(whoami) 'x'");
+    }
+
+    #[test]
+    fn invalid_user_rules_are_reported() {
+        let err = |content: &str| parse_rules(content).unwrap_err();
+        assert!(err("patterns = [\"x\"]").contains("[rules.<name>]"));
+        assert!(err("[rules.a]\ncategory = \"type\"\nsuggestion = \"s\"\n")
+            .contains("`rules.a` needs `patterns` or a `regex`"));
+        assert!(
+            err("[rules.a]\npatterns = [\"x\"]\ncategory = \"disk\"\nsuggestion = \"s\"\n")
+                .contains("unknown error category `disk`")
+        );
+        assert!(
+            err("[rules.a]\npatterns = []\ncategory = \"type\"\nsuggestion = \"s\"\n")
+                .contains("needs `patterns` or a `regex`")
+        );
+        assert!(err("[rules.a]\npatterns = [\"x\"]\ncategory = \"type\"\n")
+            .contains("`rules.a.suggestion` is required"));
+        assert!(err("[rules.timeout]\npriority = \"high\"\n").contains("must be an integer"));
+        assert!(
+            err("[rules.a]\nregex = \"(unclosed\"\ncategory = \"type\"\nsuggestion = \"s\"\n")
+                .contains("`rules.a.regex`")
+        );
+        assert_eq!(
+            load_rules(Path::new("/nonexistent/rules.toml"))
+                .unwrap()
+                .len(),
+            BUILT_INS.len()
+        );
+    }
+
+    #[test]
+    fn built_ins_can_be_overridden_disabled_and_reordered() {
+        let rules = parse_rules(
+            r#"
+# Our staging API is slow, not down
+[rules.timeout]
+retryable = false
+suggestion = "Ask #infra about staging"
+
+[rules.dns]
+enabled = false
+
+# Checked after the built-in "permission denied"
+[rules.sandbox]
+patterns = ["sandbox"]
+category = "permission"
+suggestion = "Allow the path in the sandbox profile"
+priority = 35
+"#,
+        )
+        .unwrap();
+        assert!(!rules.iter().any(|r| r.name == "dns"));
+        let timeout = rules.iter().find(|r| r.name == "timeout").unwrap();
+        assert_eq!(timeout.source, RuleSource::Overridden);
+        assert_eq!(timeout.patterns, ["timeout", "timed out"]);
+        assert_eq!(timeout.category, ErrorCategory::Network);
+
+        let r = analyze_with(&rules, "request timed out");
+        assert!(!r.retryable);
+        assert_eq!(r.suggestion, "Ask #infra about staging");
+        assert_eq!(
+            analyze_with(&rules, "could not resolve host").category,
+            ErrorCategory::Unknown
+        );
+        let r = analyze_with(&rules, "sandbox: permission denied");
+        assert_eq!(r.suggestion, "Check file permissions");
+        let r = analyze_with(&rules, "sandbox: connection refused");
+        assert_eq!(r.suggestion, "Allow the path in the sandbox profile");
+    }
+
+    #[test]
+    fn explain_names_the_matching_rule() {
+        let rules = built_in_rules();
+        let text = explain(&rules, "connection refused: localhost:5432");
+        assert_eq!(
+            text,
+            "Rule:       connection-refused (built-in, priority 30)\n\
+             Matched:    pattern \"connection refused\"\n\
+             Category:   Network\n\
+             Retryable:  yes\n\
+             Suggestion: Check if service is running\n"
+        );
+        assert!(explain(&rules, "all good").starts_with("No rule matched (12 rules checked)"));
+    }
+
     #[test]
     fn resolve_matches_network() {
         let r = analyze_error("could not resolve host");
@@ -0,0 +1,166 @@
+//! Velocity and burndown
+//!
+//! The dashboard samples how many tasks are open and closed whenever
+//! TASKS.md changes them. Completions seen in the hook events before the
+//! first sample fill in the earlier history, so a freshly started board (or
+//! the `stats` subcommand) still has a burndown. Velocity is the number of
+//! tasks closed per hour over the last day and per day over the last week.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Open and closed (completed) task counts at one point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressSample {
+    pub at: DateTime<Utc>,
+    pub open: usize,
+    pub closed: usize,
+}
+
+/// Tasks closed per hour and per day; `None` before a minute of history
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Velocity {
+    /// Over the last 24 hours, or the whole history if shorter
+    pub per_hour: Option<f64>,
+    /// Over the last 7 days, or the whole history if shorter
+    pub per_day: Option<f64>,
+}
+
+impl Velocity {
+    pub fn from_samples(samples: &[ProgressSample], now: DateTime<Utc>) -> Self {
+        Self {
+            per_hour: closed_per(samples, now, Duration::days(1), Duration::hours(1)),
+            per_day: closed_per(samples, now, Duration::days(7), Duration::days(1)),
+        }
+    }
+}
+
+/// Tasks closed in the `window` before `now`, per `unit`
+fn closed_per(
+    samples: &[ProgressSample],
+    now: DateTime<Utc>,
+    window: Duration,
+    unit: Duration,
+) -> Option<f64> {
+    let first = samples.first()?;
+    let latest = samples.last()?;
+    let start = (now - window).max(first.at);
+    let covered = (now - start).num_seconds();
+    if covered < 60 {
+        return None;
+    }
+    // Closed count as of the window start: the last sample at or before it
+    let before = samples
+        .iter()
+        .take_while(|s| s.at <= start)
+        .last()
+        .unwrap_or(first);
+    let closed = latest.closed.saturating_sub(before.closed);
+    Some(closed as f64 * unit.num_seconds() as f64 / covered as f64)
+}
+
+/// The burndown of `state`: event-derived completions before its first
+/// sample, then the samples themselves
+pub fn burndown(state: &DashboardState) -> Vec<ProgressSample> {
+    let Some(first) = state.progress_samples.first() else {
+        return Vec::new();
+    };
+    let total = first.open + first.closed;
+    let mut completions: Vec<DateTime<Utc>> = state
+        .phases
+        .iter()
+        .flat_map(|p| &p.tasks)
+        .filter(|t| t.status == TaskStatus::Completed)
+        .filter_map(|t| state.task_times.get(&t.id)?.completed_at)
+        .filter(|&at| at < first.at)
+        .collect();
+    completions.sort_unstable();
+
+    let base = first.closed.saturating_sub(completions.len());
+    let mut samples = Vec::with_capacity(completions.len() + state.progress_samples.len());
+    if let Some(&earliest) = completions.first() {
+        samples.push(ProgressSample {
+            at: earliest,
+            open: total - base,
+            closed: base,
+        });
+    }
+    for (i, at) in completions.into_iter().enumerate() {
+        let closed = base + i + 1;
+        samples.push(ProgressSample {
+            at,
+            open: total.saturating_sub(closed),
+            closed,
+        });
+    }
+    samples.extend_from_slice(&state.progress_samples);
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        format!("2026-02-08T{time}:00Z").parse().unwrap()
+    }
+
+    fn sample(time: &str, open: usize, closed: usize) -> ProgressSample {
+        ProgressSample {
+            at: at(time),
+            open,
+            closed,
+        }
+    }
+
+    #[test]
+    fn velocity_counts_tasks_closed_in_the_window() {
+        let samples = [
+            sample("08:00", 10, 0),
+            sample("09:00", 8, 2),
+            sample("10:00", 6, 4),
+        ];
+        let velocity = Velocity::from_samples(&samples, at("10:00"));
+        assert_eq!(velocity.per_hour, Some(2.0));
+        assert_eq!(velocity.per_day, Some(48.0));
+
+        assert_eq!(
+            Velocity::from_samples(&samples[..1], at("08:00")),
+            Velocity::default()
+        );
+        assert_eq!(
+            Velocity::from_samples(&[], at("08:00")),
+            Velocity::default()
+        );
+    }
+
+    #[test]
+    fn burndown_starts_with_completions_from_the_events() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [x] P1-T1: A\n\n### [x] P1-T2: B\n\n### [ ] P1-T3: C\n",
+        )
+        .unwrap();
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T08:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T09:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T09:10:00Z","agent_id":"a","task_id":"P1-T3","session_id":"s"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T09:30:00Z","agent_id":"a","task_id":"P1-T3","session_id":"s"}"#,
+        ];
+        state.update_from_events(&parse_hook_events(&events.join("\n")).events);
+        state.progress_samples = vec![sample("10:00", 1, 2)];
+
+        // P1-T3 is not completed in TASKS.md, so its agent_end does not count
+        assert_eq!(
+            burndown(&state),
+            [
+                sample("09:00", 2, 1),
+                sample("09:00", 1, 2),
+                sample("10:00", 1, 2),
+            ]
+        );
+    }
+}
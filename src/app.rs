@@ -1,14 +1,55 @@
 //! App state management and event loop
 
-use std::path::PathBuf;
-use std::time::Instant;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use crate::data::state::DashboardState;
+use tokio::sync::mpsc;
+
+use crate::actions::{self, ActionContext, ShellRequest};
+use crate::alerts::Alerter;
+use crate::analysis::rules;
+use crate::budget::BudgetWatch;
+use crate::config::{BudgetLevel, Config};
+use crate::data::auto_sync;
+use crate::data::commands::{self, ControlCommand, Signal};
+use crate::data::edit_history::{EditHistory, Snapshot};
+use crate::data::git_log;
+use crate::data::hook_parser::HookEvent;
+use crate::data::loader::{self, LoadMessage};
+use crate::data::session_history;
+use crate::data::source::{self, Update};
+use crate::data::state::{DashboardState, ErrorRecord, ToolInvocation};
+use crate::data::status_file;
+use crate::data::tail::Tail;
 use crate::data::tasks_parser::TaskStatus;
-use crate::data::tasks_writer;
+use crate::data::tasks_writer::{self, NewTask};
+use crate::data::transitions::{Baseline, Transition};
 use crate::data::watcher::FileChange;
+use crate::data::write_guard::{self, Conflict, EditTarget};
+use crate::editor::EditorRequest;
+use crate::event::{key_to_action, Action};
+use crate::pager;
+use crate::recording::Recorder;
+use crate::reminders::Reminders;
+use crate::ui::agent_picker::AgentPickerState;
+use crate::ui::block_reason::BlockReasonState;
+use crate::ui::bulk_status::{BulkOp, BulkStatusState};
+use crate::ui::detail::DetailWidget;
 use crate::ui::gantt::GanttState;
-use crate::ui::layout::FocusedPane;
+use crate::ui::gantt::GanttViewMode;
+use crate::ui::help::HelpState;
+use crate::ui::history::HistoryState;
+use crate::ui::layout::{FocusedPane, LayoutMode};
+use crate::ui::log_viewer::LogViewerState;
+use crate::ui::note_editor::NoteEditorState;
+use crate::ui::panel::{self, Panel};
+use crate::ui::phase_picker::PhasePickerState;
+use crate::ui::rename::{RenameState, RenameTarget};
+use crate::ui::signal_picker::SignalPickerState;
+use crate::ui::task_form::{self, FormOutcome, TaskFormState};
+use crate::ui::theme::ThemeName;
+use crate::view_state::{self, ViewState};
 
 /// Information about a retry target task
 #[derive(Debug, Clone)]
@@ -25,11 +66,120 @@ pub struct App {
     pub gantt_state: GanttState,
     pub focused: FocusedPane,
     pub show_help: bool,
+    /// Filter text and scroll position of the help overlay
+    pub help: HelpState,
     pub show_retry_modal: bool,
     pub retry_target: Option<RetryTarget>,
+    /// The retry modal asks whether to run the error's fix command
+    pub confirming_fix: bool,
     pub tasks_path: Option<PathBuf>,
     pub start_time: Instant,
     pub selected_agent: usize,
+    /// Agent shown in the drill-down detail view (opened with Enter in the Agents pane)
+    pub agent_drilldown: Option<String>,
+    /// Highlighted tool call in the drill-down, counted from the newest
+    pub drilldown_tool: usize,
+    /// Directories whose `*.jsonl` files are re-read when the session filter changes
+    pub event_dirs: Vec<PathBuf>,
+    pub show_session_picker: bool,
+    /// Selected entry in the session picker (0 = all sessions)
+    pub session_picker_selected: usize,
+    /// Progress of the background history load; `None` once it has finished
+    pub history_progress: Option<f32>,
+    pub config: Config,
+    /// Frame counter for the running-agent spinner, advanced on each tick
+    pub spinner_frame: usize,
+    /// Set when the file watcher failed to start or its channel closed
+    pub watcher_degraded: bool,
+    /// Raw JSONL viewer; `Some` while the overlay is open
+    pub log_viewer: Option<LogViewerState>,
+    pub layout_mode: LayoutMode,
+    /// Full-width overall progress gauge above the status bar
+    pub show_gauge: bool,
+    /// Add-task form; `Some` while the popup is open
+    pub task_form: Option<TaskFormState>,
+    /// Inline rename of the selected task or phase; `Some` while editing
+    pub rename: Option<RenameState>,
+    /// Agent picker for the selected task; `Some` while the popup is open
+    pub agent_picker: Option<AgentPickerState>,
+    /// Move-to-phase picker for the selected task; `Some` while open
+    pub phase_picker: Option<PhasePickerState>,
+    /// File the main loop should open in `$EDITOR` on its next iteration
+    pub editor_request: Option<EditorRequest>,
+    /// Text the main loop should pipe to `$PAGER` on its next iteration
+    pub pager_request: Option<String>,
+    /// Text the main loop should put on the clipboard on its next iteration
+    pub clipboard_request: Option<String>,
+    /// Past sessions view; `Some` while open
+    pub session_history: Option<HistoryState>,
+    /// Custom action command the main loop should run on its next iteration
+    pub shell_request: Option<ShellRequest>,
+    /// TASKS.md edits made from the dashboard, for undo/redo
+    pub history: EditHistory,
+    /// One-line notice in the status bar, cleared on the next key
+    pub status_message: Option<String>,
+    /// Bulk status popup for the selected phase; `Some` while open
+    pub bulk_status: Option<BulkStatusState>,
+    /// Note editor for the selected task; `Some` while the popup is open
+    pub note_editor: Option<NoteEditorState>,
+    /// Reason prompt for tasks being blocked; `Some` while the popup is open
+    pub block_reason: Option<BlockReasonState>,
+    /// Directory of the commands file agent signals are written to
+    pub commands_dir: Option<PathBuf>,
+    /// Signal popup for the selected agent; `Some` while open
+    pub signal_picker: Option<SignalPickerState>,
+    /// Follow-up reminders set with `t`
+    pub reminders: Reminders,
+    /// Set when a reminder came due; the main loop rings the terminal bell
+    pub bell: bool,
+    /// Edit refused because TASKS.md changed underneath it; `Some` while
+    /// the conflict dialog is open
+    pub conflict: Option<Conflict>,
+    /// State the next file change is compared with to find transitions
+    pub baseline: Baseline,
+    /// Budget levels the next file change is compared with
+    pub budget_watch: BudgetWatch,
+    /// Whether the terminal has focus, from focus change events
+    pub terminal_focused: bool,
+    /// Error and failure counts towards the next `[alerts]` escalation
+    pub alerter: Alerter,
+    /// Escalation the main loop should ring / send as OSC 9 next
+    pub alert: Option<String>,
+    /// Summary last written to `status.json`, to skip unchanged rewrites
+    last_status: Option<serde_json::Value>,
+    /// Events covered by the last `sessions.json` write, to skip unchanged
+    /// rewrites
+    last_history: Option<usize>,
+    /// Session recording (`--record`) the file changes are written to
+    recorder: Option<Recorder>,
+    /// Hook events up to this time are never written back by `auto_sync`
+    pub sync_since: chrono::DateTime<chrono::Utc>,
+    /// Latest event of each hook file already written back by `auto_sync`
+    pub synced_until: HashMap<PathBuf, chrono::DateTime<chrono::Utc>>,
+    /// How far each hook file has been applied to the dashboard, so a change
+    /// only costs what was appended
+    tail: Tail,
+    /// Hook files changed while a history load runs; read through the tail
+    /// it hands over, which covers everything before
+    deferred_hook_files: Vec<PathBuf>,
+    /// Channel the sources feed, so history reloads run on the loader
+    /// thread too; without one they read the files here
+    updates: Option<mpsc::UnboundedSender<Update>>,
+    /// A reload was asked for while a load ran; it starts when that ends
+    reload_pending: bool,
+    /// The first history load has finished
+    history_loaded: bool,
+    /// A view saved by an earlier launch was restored; its agent selection
+    /// waits here until the history has loaded
+    view_restored: bool,
+    restored_agent: Option<String>,
+    /// Custom panels registered by the embedding code, in Tab order
+    pub panels: Vec<Box<dyn Panel>>,
+    /// Home directory board.toml and rules.toml are read again from when
+    /// they change; `None` keeps the config the app was built with
+    pub config_home: Option<PathBuf>,
+    /// `--theme` from the command line, kept over reloaded configs
+    pub theme_override: Option<ThemeName>,
 }
 
 impl App {
@@ -40,16 +190,73 @@ impl App {
             gantt_state: GanttState::default(),
             focused: FocusedPane::TaskList,
             show_help: false,
+            help: HelpState::default(),
             show_retry_modal: false,
             retry_target: None,
+            confirming_fix: false,
             tasks_path: None,
             start_time: Instant::now(),
             selected_agent: 0,
+            agent_drilldown: None,
+            drilldown_tool: 0,
+            event_dirs: Vec::new(),
+            show_session_picker: false,
+            session_picker_selected: 0,
+            history_progress: None,
+            config: Config::default(),
+            spinner_frame: 0,
+            watcher_degraded: false,
+            log_viewer: None,
+            layout_mode: LayoutMode::default(),
+            show_gauge: false,
+            task_form: None,
+            rename: None,
+            agent_picker: None,
+            phase_picker: None,
+            editor_request: None,
+            pager_request: None,
+            clipboard_request: None,
+            session_history: None,
+            shell_request: None,
+            history: EditHistory::default(),
+            status_message: None,
+            bulk_status: None,
+            note_editor: None,
+            block_reason: None,
+            commands_dir: None,
+            signal_picker: None,
+            reminders: Reminders::default(),
+            bell: false,
+            conflict: None,
+            baseline: Baseline::default(),
+            budget_watch: BudgetWatch::default(),
+            terminal_focused: true,
+            alerter: Alerter::default(),
+            alert: None,
+            last_status: None,
+            last_history: None,
+            recorder: None,
+            sync_since: chrono::Utc::now(),
+            synced_until: HashMap::new(),
+            tail: Tail::default(),
+            deferred_hook_files: Vec::new(),
+            updates: None,
+            reload_pending: false,
+            history_loaded: false,
+            view_restored: false,
+            restored_agent: None,
+            panels: Vec::new(),
+            config_home: None,
+            theme_override: None,
         }
     }
 
     pub fn with_dashboard(mut self, dashboard: DashboardState) -> Self {
+        self.baseline = Baseline::capture(&dashboard);
+        self.budget_watch = BudgetWatch::capture(&dashboard, &self.config.budget);
         self.dashboard = dashboard;
+        self.dashboard.agent_identity = self.config.agent_identity();
+        self.dashboard.limits = self.config.limits;
         self
     }
 
@@ -58,16 +265,256 @@ impl App {
         self
     }
 
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.layout_mode = config.layout;
+        self.show_gauge = config.progress_gauge;
+        self.budget_watch = BudgetWatch::capture(&self.dashboard, &config.budget);
+        self.dashboard.agent_identity = config.agent_identity();
+        self.dashboard.limits = config.limits;
+        self.config = config;
+        self
+    }
+
+    /// Reload the config from `home` when the watcher reports a change,
+    /// keeping `theme` if one was given on the command line
+    pub fn with_config_reload(mut self, home: PathBuf, theme: Option<ThemeName>) -> Self {
+        self.config_home = Some(home);
+        self.theme_override = theme;
+        self
+    }
+
+    /// Add a custom panel after the built-in panes and any registered before
+    pub fn with_panel(mut self, panel: impl Panel + 'static) -> Self {
+        self.register_panel(Box::new(panel));
+        self
+    }
+
+    pub fn register_panel(&mut self, panel: Box<dyn Panel>) {
+        self.panels.push(panel);
+    }
+
+    pub fn with_event_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.event_dirs = dirs;
+        self
+    }
+
+    /// Reload history in the background, reporting on the channel the
+    /// sources feed
+    pub fn with_updates(mut self, tx: mpsc::UnboundedSender<Update>) -> Self {
+        self.updates = Some(tx);
+        self
+    }
+
+    pub fn with_commands_dir(mut self, dir: PathBuf) -> Self {
+        self.commands_dir = Some(dir);
+        self
+    }
+
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     pub fn quit(&mut self) {
         self.running = false;
     }
 
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
+        self.help = HelpState::default();
+    }
+
+    /// Forward a key to the help overlay, closing it when requested
+    pub fn help_key(&mut self, key: crossterm::event::KeyEvent) {
+        if !self.help.handle_key(key) {
+            self.toggle_help();
+        }
     }
 
     pub fn toggle_focus(&mut self) {
-        self.focused = self.focused.toggle();
+        self.focused = self.focused.next(self.panels.len());
+        self.agent_drilldown = None;
+    }
+
+    /// Apply a key press: the open overlay or prompt takes it, then a
+    /// custom panel, then the dashboard's own bindings
+    pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) {
+        self.handle_key_with(key, |_| false);
+    }
+
+    /// Like [`Self::handle_key`], but a key no overlay takes goes to
+    /// `intercept` first (e.g. replay controls); `true` means it was taken
+    pub fn handle_key_with(
+        &mut self,
+        key: crossterm::event::KeyEvent,
+        intercept: impl FnOnce(crossterm::event::KeyEvent) -> bool,
+    ) {
+        self.status_message = None;
+        if self.conflict.is_some() {
+            self.close_conflict();
+        } else if self.rename.is_some() {
+            self.rename_key(key);
+        } else if self.task_form.is_some() {
+            self.task_form_key(key);
+        } else if self.note_editor.is_some() {
+            self.note_editor_key(key);
+        } else if self.block_reason.is_some() {
+            self.block_reason_key(key);
+        } else if self.log_viewer.is_some() {
+            self.log_viewer_key(key);
+        } else if self.session_history.is_some() {
+            self.session_history_key(key);
+        } else if self.gantt_state.minimap_cursor.is_some() {
+            self.minimap_key(key);
+        } else if self.show_help {
+            self.help_key(key);
+        } else if self.agent_picker.is_some() {
+            match key_to_action(key) {
+                Action::MoveDown => self.agent_picker_move_down(),
+                Action::MoveUp => self.agent_picker_move_up(),
+                Action::Select => self.confirm_agent_picker(),
+                Action::Cancel | Action::Quit | Action::AssignAgent => self.close_agent_picker(),
+                _ => {}
+            }
+        } else if self.phase_picker.is_some() {
+            match key_to_action(key) {
+                Action::MoveDown => self.phase_picker_move_down(),
+                Action::MoveUp => self.phase_picker_move_up(),
+                Action::Select => self.confirm_phase_picker(),
+                Action::Cancel | Action::Quit | Action::MoveToPhase => self.close_phase_picker(),
+                _ => {}
+            }
+        } else if let Some(confirming) = self.bulk_status.as_ref().map(|p| p.confirming) {
+            match key_to_action(key) {
+                Action::Confirm if confirming => self.confirm_bulk_status(),
+                Action::MoveDown => self.bulk_status_move_down(),
+                Action::MoveUp => self.bulk_status_move_up(),
+                Action::Select if !confirming => self.bulk_status_choose(),
+                Action::Cancel | Action::Quit | Action::BulkStatus => self.close_bulk_status(),
+                _ => {}
+            }
+        } else if let Some(confirming) = self.signal_picker.as_ref().map(|p| p.confirming) {
+            match key_to_action(key) {
+                Action::Confirm if confirming => self.confirm_signal(),
+                Action::MoveDown => self.signal_picker_move_down(),
+                Action::MoveUp => self.signal_picker_move_up(),
+                Action::Select if !confirming => self.signal_picker_choose(),
+                Action::Cancel | Action::Quit | Action::SignalAgent => self.close_signal_picker(),
+                _ => {}
+            }
+        } else if self.show_session_picker {
+            match key_to_action(key) {
+                Action::MoveDown => self.session_picker_move_down(),
+                Action::MoveUp => self.session_picker_move_up(),
+                Action::Select => self.confirm_session_picker(),
+                Action::Cancel | Action::Quit | Action::ToggleSessionPicker => {
+                    self.close_session_picker()
+                }
+                _ => {}
+            }
+        } else if self.confirming_fix {
+            // Second step of [f]: y runs the fix, anything else goes back
+            match key_to_action(key) {
+                Action::Confirm => self.run_fix(),
+                _ => self.confirming_fix = false,
+            }
+        } else if self.show_retry_modal {
+            // Modal takes priority: only y/n/q/Esc
+            let retryable = self.retry_target.as_ref().is_some_and(|t| t.retryable);
+            match key_to_action(key) {
+                _ if matches!(key.code, crossterm::event::KeyCode::Char('f' | 'ㄹ'))
+                    && self.retry_fix().is_some() =>
+                {
+                    self.request_fix()
+                }
+                Action::AddTask if self.retry_error().is_some() => self.track_error_as_task(),
+                Action::Confirm if retryable => self.confirm_retry(),
+                Action::Cancel | Action::Quit => self.cancel_retry(),
+                // Non-retryable: any key closes
+                _ if !retryable => self.cancel_retry(),
+                _ => {}
+            }
+        } else if intercept(key) {
+            // Taken by the embedding code
+        } else if self.panel_key(key, &key_to_action(key)) {
+            // Taken by a custom panel
+        } else {
+            match key_to_action(key) {
+                Action::Quit => self.quit(),
+                Action::MoveDown => match self.focused {
+                    FocusedPane::Agents => self.agent_move_down(),
+                    _ => self.move_down(),
+                },
+                Action::MoveUp => match self.focused {
+                    FocusedPane::Agents => self.agent_move_up(),
+                    _ => self.move_up(),
+                },
+                Action::ToggleFocus => self.toggle_focus(),
+                Action::ToggleHelp => self.toggle_help(),
+                Action::ToggleCollapse => self.toggle_collapse(),
+                Action::ToggleView => self.toggle_view(),
+                Action::ToggleWrap => self.toggle_wrap(),
+                Action::Select => self.toggle_agent_drilldown(),
+                Action::ToggleSessionPicker => self.open_session_picker(),
+                Action::OpenLogViewer => self.open_log_viewer(),
+                Action::OpenHistory => self.open_session_history(),
+                Action::OpenMinimap => self.open_minimap(),
+                Action::ToggleLayout => self.toggle_layout(),
+                Action::ToggleGauge => self.toggle_gauge(),
+                Action::RetryRequest => self.open_retry_modal(),
+                Action::AddTask => self.open_task_form(),
+                Action::Rename => self.open_rename(),
+                Action::AssignAgent => self.open_agent_picker(),
+                Action::MoveTaskDown => self.move_task(1),
+                Action::MoveTaskUp => self.move_task(-1),
+                Action::MoveToPhase => self.open_phase_picker(),
+                Action::ResetTask => self.reset_failed_task(),
+                Action::OpenEditor => self.open_in_editor(),
+                Action::ArchivePhase => self.archive_phase(),
+                Action::Undo => self.undo(),
+                Action::Redo => self.redo(),
+                Action::BulkStatus => self.open_bulk_status(),
+                Action::BlockTask => self.open_block_prompt(),
+                Action::SignalAgent => self.open_signal_picker(),
+                Action::AcknowledgeErrors => self.acknowledge_errors(),
+                Action::MuteAgent => self.toggle_mute_agent(),
+                Action::Remind => self.toggle_reminder(),
+                Action::OpenDiff => self.open_task_diff(),
+                Action::OpenPager => self.open_pager(),
+                Action::CopySummary => self.copy_status_summary(),
+                Action::Custom(key) => self.run_custom_action(key),
+                // `n` outside a y/n prompt adds a note
+                Action::Cancel => self.open_note_editor(),
+                Action::Confirm | Action::None => {}
+            }
+        }
+    }
+
+    /// Give a key to the custom panels: the focused one sees its action
+    /// first, and an unbound key matching a panel's hotkey focuses that
+    /// panel. `true` when a panel took the key.
+    pub fn panel_key(&mut self, key: crossterm::event::KeyEvent, action: &Action) -> bool {
+        if let FocusedPane::Custom(i) = self.focused {
+            if let Some(panel) = self.panels.get_mut(i) {
+                if panel::forwards(action) && panel.handle_action(action, &self.dashboard) {
+                    return true;
+                }
+            }
+        }
+        let crossterm::event::KeyCode::Char(c) = key.code else {
+            return false;
+        };
+        if *action != Action::None {
+            return false;
+        }
+        match self.panels.iter().position(|p| p.hotkey() == Some(c)) {
+            Some(i) => {
+                self.focused = FocusedPane::Custom(i);
+                self.agent_drilldown = None;
+                true
+            }
+            None => false,
+        }
     }
 
     pub fn move_down(&mut self) {
@@ -90,6 +537,25 @@ impl App {
         self.gantt_state.toggle_view();
     }
 
+    /// Switch to the next pane arrangement
+    pub fn toggle_layout(&mut self) {
+        self.layout_mode = self.layout_mode.next();
+    }
+
+    pub fn toggle_gauge(&mut self) {
+        self.show_gauge = !self.show_gauge;
+    }
+
+    /// Whether an overlay detail pane (stacked layout) should be drawn
+    pub fn detail_overlay_visible(&self) -> bool {
+        self.focused == FocusedPane::Detail || self.agent_drilldown.is_some()
+    }
+
+    /// Toggle two-line wrapping of the selected task name
+    pub fn toggle_wrap(&mut self) {
+        self.gantt_state.toggle_wrap();
+    }
+
     /// Get sorted agent IDs (consistent order for UI)
     pub fn sorted_agent_ids(&self) -> Vec<String> {
         let mut ids: Vec<String> = self.dashboard.agents.keys().cloned().collect();
@@ -97,21 +563,258 @@ impl App {
         ids
     }
 
-    /// Move agent selection down
+    /// Tool calls of the agent shown in the drill-down, newest first
+    fn drilldown_tools(&self) -> Vec<&ToolInvocation> {
+        self.agent_drilldown
+            .as_ref()
+            .and_then(|id| self.dashboard.agents.get(id))
+            .map(|agent| agent.tool_invocations.iter().rev().collect())
+            .unwrap_or_default()
+    }
+
+    /// Move agent selection down (or the tool-call cursor in the drill-down)
     pub fn agent_move_down(&mut self) {
+        if self.agent_drilldown.is_some() {
+            if self.drilldown_tool + 1 < self.drilldown_tools().len() {
+                self.drilldown_tool += 1;
+            }
+            return;
+        }
         let count = self.dashboard.agents.len();
         if count > 0 && self.selected_agent < count - 1 {
             self.selected_agent += 1;
         }
     }
 
-    /// Move agent selection up
+    /// Move agent selection up (or the tool-call cursor in the drill-down)
     pub fn agent_move_up(&mut self) {
+        if self.agent_drilldown.is_some() {
+            self.drilldown_tool = self.drilldown_tool.saturating_sub(1);
+            return;
+        }
         if self.selected_agent > 0 {
             self.selected_agent -= 1;
         }
     }
 
+    /// Open or close the drill-down view for the selected agent
+    pub fn toggle_agent_drilldown(&mut self) {
+        if self.focused != FocusedPane::Agents {
+            return;
+        }
+        if self.agent_drilldown.is_some() {
+            self.agent_drilldown = None;
+        } else {
+            self.agent_drilldown = self.sorted_agent_ids().get(self.selected_agent).cloned();
+            self.drilldown_tool = 0;
+        }
+    }
+
+    /// Enter minimap navigation (task pane only)
+    pub fn open_minimap(&mut self) {
+        if self.focused == FocusedPane::TaskList {
+            self.gantt_state.open_minimap(&self.dashboard);
+        }
+    }
+
+    /// Handle a key while navigating the minimap
+    pub fn minimap_key(&mut self, key: crossterm::event::KeyEvent) {
+        use crossterm::event::KeyCode;
+
+        match key.code {
+            KeyCode::Left | KeyCode::Up | KeyCode::Char('h' | 'k' | 'ㅗ' | 'ㅏ') => {
+                self.gantt_state.minimap_move(&self.dashboard, -1)
+            }
+            KeyCode::Right | KeyCode::Down | KeyCode::Char('l' | 'j' | 'ㅣ' | 'ㅓ') => {
+                self.gantt_state.minimap_move(&self.dashboard, 1)
+            }
+            KeyCode::Enter => self.gantt_state.minimap_jump(&self.dashboard),
+            KeyCode::Esc | KeyCode::Char('m' | 'q' | 'ㅡ' | 'ㅂ') => {
+                self.gantt_state.close_minimap()
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the raw log viewer on the most recently written event file
+    pub fn open_log_viewer(&mut self) {
+        if let Some(path) = loader::newest_jsonl_file(&self.event_dirs) {
+            self.log_viewer = LogViewerState::open(&path).ok();
+        }
+    }
+
+    /// Open the past sessions view: stored sessions with the current ones
+    pub fn open_session_history(&mut self) {
+        let stored = self
+            .commands_dir
+            .as_deref()
+            .map(session_history::read)
+            .unwrap_or_default();
+        let sessions = session_history::merge(stored, &self.dashboard.sessions);
+        if sessions.is_empty() {
+            self.status_message = Some("No sessions recorded yet".to_string());
+            return;
+        }
+        self.session_history = Some(HistoryState::new(sessions));
+    }
+
+    /// Forward a key to the history view, closing it when requested
+    pub fn session_history_key(&mut self, key: crossterm::event::KeyEvent) {
+        if let Some(ref mut history) = self.session_history {
+            if !history.handle_key(key) {
+                self.session_history = None;
+            }
+        }
+    }
+
+    /// Forward a key to the log viewer, closing it when requested
+    pub fn log_viewer_key(&mut self, key: crossterm::event::KeyEvent) {
+        if let Some(ref mut viewer) = self.log_viewer {
+            if !viewer.handle_key(key) {
+                self.log_viewer = None;
+            }
+        }
+    }
+
+    /// Open the session picker with the active session preselected
+    pub fn open_session_picker(&mut self) {
+        self.session_picker_selected = match self.dashboard.session_filter {
+            Some(ref id) => self
+                .dashboard
+                .sorted_sessions()
+                .iter()
+                .position(|s| &s.session_id == id)
+                .map_or(0, |i| i + 1),
+            None => 0,
+        };
+        self.show_session_picker = true;
+    }
+
+    pub fn close_session_picker(&mut self) {
+        self.show_session_picker = false;
+    }
+
+    pub fn session_picker_move_down(&mut self) {
+        // Entry 0 is "All sessions", followed by one entry per session
+        let count = self.dashboard.sessions.len() + 1;
+        if self.session_picker_selected + 1 < count {
+            self.session_picker_selected += 1;
+        }
+    }
+
+    pub fn session_picker_move_up(&mut self) {
+        self.session_picker_selected = self.session_picker_selected.saturating_sub(1);
+    }
+
+    /// Apply the session chosen in the picker and close it
+    pub fn confirm_session_picker(&mut self) {
+        let filter = match self.session_picker_selected {
+            0 => None,
+            i => self
+                .dashboard
+                .sorted_sessions()
+                .get(i - 1)
+                .map(|s| s.session_id.clone()),
+        };
+        self.apply_session_filter(filter);
+        self.show_session_picker = false;
+    }
+
+    /// Limit the dashboard to one session (or all) and re-aggregate events
+    pub fn apply_session_filter(&mut self, session_id: Option<String>) {
+        self.dashboard.session_filter = session_id;
+        self.reload_history();
+        self.selected_agent = 0;
+        self.agent_drilldown = None;
+    }
+
+    /// The view to save for the next launch
+    pub fn view_state(&self) -> ViewState {
+        let phases = &self.dashboard.phases;
+        let selected = match self.selected_task() {
+            Some((pi, ti)) => Some(phases[pi].tasks[ti].id.clone()),
+            None => self
+                .gantt_state
+                .selected_phase_index(&self.dashboard)
+                .map(|pi| phases[pi].id.clone()),
+        };
+        let mut collapsed: Vec<String> = self
+            .gantt_state
+            .collapsed
+            .iter()
+            .filter_map(|&pi| phases.get(pi))
+            .map(|p| p.id.clone())
+            .collect();
+        collapsed.sort();
+        ViewState {
+            saved_at: Some(chrono::Utc::now()),
+            selected,
+            collapsed,
+            view_mode: self.gantt_state.view_mode,
+            layout_mode: self.layout_mode,
+            show_gauge: self.show_gauge,
+            focused: self.focused,
+            selected_agent: self.sorted_agent_ids().get(self.selected_agent).cloned(),
+            session_filter: self.dashboard.session_filter.clone(),
+            acknowledged_errors: self.dashboard.acknowledged_errors.clone(),
+            dismissed_errors: self.dashboard.dismissed_errors.clone(),
+            muted_agents: self.dashboard.muted_agents.clone(),
+        }
+    }
+
+    /// Put back a view saved by an earlier launch. Tasks and phases that are
+    /// gone are skipped; the session filter and agent selection take effect
+    /// once the history has loaded.
+    pub fn restore_view(&mut self, view: ViewState) {
+        let phases = &self.dashboard.phases;
+        self.gantt_state.collapsed = phases
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| view.collapsed.contains(&p.id))
+            .map(|(pi, _)| pi)
+            .collect();
+        self.gantt_state.view_mode = view.view_mode;
+        if let Some(id) = &view.selected {
+            let mut tasks = phases.iter().flat_map(|p| &p.tasks);
+            if let Some(flat) = tasks.position(|t| t.id == *id) {
+                self.gantt_state.select_flat_task(&self.dashboard, flat);
+            } else if let Some(pi) = phases.iter().position(|p| p.id == *id) {
+                self.gantt_state.select_phase(&self.dashboard, pi);
+            }
+        }
+        self.layout_mode = view.layout_mode;
+        self.show_gauge = view.show_gauge;
+        self.focused = match view.focused {
+            FocusedPane::Custom(i) if i >= self.panels.len() => FocusedPane::TaskList,
+            focused => focused,
+        };
+        self.dashboard.session_filter = view.session_filter;
+        self.dashboard.acknowledged_errors = view.acknowledged_errors;
+        self.dashboard.dismissed_errors = view.dismissed_errors;
+        self.dashboard.muted_agents = view.muted_agents;
+        self.restored_agent = view.selected_agent;
+        self.view_restored = true;
+    }
+
+    /// Restore the view saved by the last launch on this TASKS.md, if any
+    pub fn load_view(&mut self) {
+        let (Some(dir), Some(tasks)) = (&self.commands_dir, &self.tasks_path) else {
+            return;
+        };
+        if let Some(view) = view_state::read(dir, &view_state::project_key(tasks)) {
+            self.restore_view(view);
+        }
+    }
+
+    /// Save the view for the next launch on this TASKS.md, next to the
+    /// commands file
+    pub fn save_view(&self) -> std::io::Result<()> {
+        let (Some(dir), Some(tasks)) = (&self.commands_dir, &self.tasks_path) else {
+            return Ok(());
+        };
+        view_state::write(dir, &view_state::project_key(tasks), self.view_state())
+    }
+
     /// Open the retry modal for the currently selected task
     pub fn open_retry_modal(&mut self) {
         if let Some((pi, ti)) = self.selected_task() {
@@ -122,10 +825,7 @@ impl App {
             }
             // Check if there's a matching error with retryable info
             let retryable = self
-                .dashboard
-                .recent_errors
-                .iter()
-                .rfind(|e| e.task_id == task.id)
+                .latest_task_error(&task.id)
                 .map_or(true, |e| e.retryable); // default to retryable if no error record
 
             self.retry_target = Some(RetryTarget {
@@ -137,18 +837,119 @@ impl App {
         }
     }
 
+    /// Most recent recorded error for `task_id`
+    fn latest_task_error(&self, task_id: &str) -> Option<&ErrorRecord> {
+        self.dashboard
+            .recent_errors
+            .iter()
+            .rfind(|e| e.task_id == task_id)
+    }
+
+    /// Latest error of the task in the retry modal
+    pub fn retry_error(&self) -> Option<&ErrorRecord> {
+        self.latest_task_error(&self.retry_target.as_ref()?.task_id)
+    }
+
+    /// Fix command of the retry modal's error, from its error rule
+    pub fn retry_fix(&self) -> Option<&str> {
+        self.retry_error()?.command.as_deref()
+    }
+
+    /// Ask for confirmation before running the retry modal's fix command
+    pub fn request_fix(&mut self) {
+        self.confirming_fix = self.retry_fix().is_some();
+    }
+
+    /// Queue the confirmed fix command for the main loop, which suspends
+    /// the dashboard while it runs
+    pub fn run_fix(&mut self) {
+        if let Some(command) = self.retry_fix().map(str::to_string) {
+            let task_id = self
+                .retry_target
+                .as_ref()
+                .map(|t| t.task_id.clone())
+                .unwrap_or_default();
+            self.shell_request = Some(ShellRequest {
+                label: format!("{task_id} fix"),
+                command,
+            });
+        }
+        self.cancel_retry();
+    }
+
+    /// Append a pending task to the failed task's phase, named after the
+    /// retry modal's error and describing its suggestion and origin
+    pub fn track_error_as_task(&mut self) {
+        let (Some(error), Some(path)) = (self.retry_error().cloned(), self.tasks_path.clone())
+        else {
+            return;
+        };
+        let Some(pi) = self
+            .dashboard
+            .phases
+            .iter()
+            .position(|p| p.tasks.iter().any(|t| t.id == error.task_id))
+        else {
+            return;
+        };
+        let task_id = task_form::suggest_task_id(&self.dashboard, pi);
+        let name = error_task_name(&error.message);
+        let origin = match error.tool_name {
+            Some(ref tool) => format!("@{} via {tool}", error.agent_id),
+            None => format!("@{}", error.agent_id),
+        };
+        let spec = format!("{} (from {origin} on {})", error.suggestion, error.task_id);
+        let task = NewTask {
+            phase_id: &self.dashboard.phases[pi].id,
+            task_id: &task_id,
+            name: &name,
+            status: " ",
+            agent: None,
+            spec: Some(&spec),
+        };
+
+        if let Some(conflict) = write_conflict(
+            &self.dashboard,
+            &path,
+            &[EditTarget::PhaseHeading(task.phase_id)],
+        ) {
+            self.conflict = Some(conflict);
+            return;
+        }
+        let before = edit_snapshot(&path);
+        let result = tasks_writer::append_task(&path, &task);
+        self.history.record(&format!("add {task_id}"), before);
+        self.status_message = Some(match result {
+            Ok(true) => {
+                let _ = self.dashboard.reload_tasks_file(&path);
+                self.cancel_retry();
+                format!("Added {task_id} from error")
+            }
+            Ok(false) => "Phase not found in TASKS.md".to_string(),
+            Err(e) => format!("Write failed: {e}"),
+        });
+    }
+
     /// Confirm retry: update TASKS.md status to InProgress
     pub fn confirm_retry(&mut self) {
         if let Some(ref target) = self.retry_target.clone() {
             if target.retryable {
                 if let Some(ref path) = self.tasks_path {
-                    if let Ok(true) =
-                        tasks_writer::update_task_status(path, &target.task_id, "InProgress")
+                    if let Some(conflict) =
+                        write_conflict(&self.dashboard, path, &[EditTarget::Task(&target.task_id)])
                     {
+                        self.conflict = Some(conflict);
+                        self.cancel_retry();
+                        return;
+                    }
+                    let before = edit_snapshot(path);
+                    let result =
+                        tasks_writer::update_task_status(path, &target.task_id, "InProgress");
+                    self.history
+                        .record(&format!("retry {}", target.task_id), before);
+                    if let Ok(true) = result {
                         // Reload the tasks to reflect the change
-                        if let Ok(content) = std::fs::read_to_string(path) {
-                            let _ = self.dashboard.reload_tasks(&content);
-                        }
+                        let _ = self.dashboard.reload_tasks_file(path);
                     }
                 }
             }
@@ -157,211 +958,2522 @@ impl App {
         self.retry_target = None;
     }
 
-    /// Cancel the retry modal
-    pub fn cancel_retry(&mut self) {
-        self.show_retry_modal = false;
-        self.retry_target = None;
+    /// Reset the selected Failed task to Pending in TASKS.md so an agent
+    /// picks it up again; with `reset_clears_errors` its errors are dismissed
+    pub fn reset_failed_task(&mut self) {
+        let Some(path) = self.tasks_path.clone() else {
+            return;
+        };
+        let Some((pi, ti)) = self.selected_task() else {
+            return;
+        };
+        let task = &self.dashboard.phases[pi].tasks[ti];
+        if task.status != TaskStatus::Failed {
+            return;
+        }
+        let task_id = task.id.clone();
+        if let Some(conflict) =
+            write_conflict(&self.dashboard, &path, &[EditTarget::Task(&task_id)])
+        {
+            self.conflict = Some(conflict);
+            return;
+        }
+        let before = edit_snapshot(&path);
+        let result = tasks_writer::update_task_status(&path, &task_id, " ");
+        self.history.record(&format!("reset {task_id}"), before);
+        if let Ok(true) = result {
+            if self.config.reset_clears_errors {
+                self.dashboard.dismiss_task_errors(&task_id);
+            }
+            let _ = self.dashboard.reload_tasks_file(&path);
+        }
     }
 
-    /// Get the currently selected task as (phase_idx, task_idx)
-    pub fn selected_task(&self) -> Option<(usize, usize)> {
-        self.gantt_state.selected_task(&self.dashboard)
+    /// Open the bulk status popup on the selected phase header
+    pub fn open_bulk_status(&mut self) {
+        if self.tasks_path.is_none() {
+            return;
+        }
+        if let Some(pi) = self.gantt_state.selected_phase_index(&self.dashboard) {
+            self.bulk_status = BulkStatusState::new(&self.dashboard.phases[pi]);
+        }
     }
 
-    /// Handle a file change event from the watcher
-    pub fn handle_file_change(&mut self, change: &FileChange) {
-        match change {
-            FileChange::TasksModified(path) => {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    let _ = self.dashboard.reload_tasks(&content);
-                }
-            }
-            FileChange::HookEventCreated(path) | FileChange::HookEventModified(path) => {
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    let result = crate::data::hook_parser::parse_hook_events(&content);
-                    self.dashboard.reload_from_events(&result.events);
-                }
-            }
+    pub fn close_bulk_status(&mut self) {
+        self.bulk_status = None;
+    }
+
+    pub fn bulk_status_move_down(&mut self) {
+        if let Some(ref mut popup) = self.bulk_status {
+            popup.move_down();
         }
     }
-}
 
-impl Default for App {
-    fn default() -> Self {
-        Self::new()
+    pub fn bulk_status_move_up(&mut self) {
+        if let Some(ref mut popup) = self.bulk_status {
+            popup.move_up();
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Enter on an operation: ask for confirmation
+    pub fn bulk_status_choose(&mut self) {
+        if let Some(ref mut popup) = self.bulk_status {
+            popup.confirming = true;
+        }
+    }
 
-    #[test]
-    fn app_default() {
-        let app = App::new();
-        assert!(app.running);
-        assert!(!app.show_help);
-        assert_eq!(app.focused, FocusedPane::TaskList);
+    /// Write the confirmed operation to TASKS.md in one go, reload, and close
+    pub fn confirm_bulk_status(&mut self) {
+        if !self.bulk_status.as_ref().is_some_and(|p| p.confirming) {
+            return;
+        }
+        let Some(popup) = self.bulk_status.take() else {
+            return;
+        };
+        let (Some((op, ids)), Some(path)) = (popup.selected_op(), self.tasks_path.as_ref()) else {
+            return;
+        };
+        if *op == BulkOp::BlockRemaining {
+            // Written once a reason has been given
+            self.block_reason = Some(BlockReasonState::new(ids.clone(), None));
+            return;
+        }
+        let updates: Vec<(&str, &str)> = ids
+            .iter()
+            .map(|id| (id.as_str(), op.status_tag()))
+            .collect();
+        let targets: Vec<EditTarget> = ids.iter().map(|id| EditTarget::Task(id)).collect();
+        if let Some(conflict) = write_conflict(&self.dashboard, path, &targets) {
+            self.conflict = Some(conflict);
+            return;
+        }
+        let before = edit_snapshot(path);
+        let result = tasks_writer::set_task_statuses(path, &updates);
+        self.history.record(
+            &format!("{} in {}", op.label().to_lowercase(), popup.phase_id),
+            before,
+        );
+        if let Ok(changed) = result {
+            if changed > 0 {
+                let _ = self.dashboard.reload_tasks_file(path);
+            }
+        }
     }
 
-    #[test]
-    fn app_quit() {
-        let mut app = App::new();
-        app.quit();
-        assert!(!app.running);
+    /// Move the selected, fully completed phase to the archive file
+    pub fn archive_phase(&mut self) {
+        let Some(path) = self.tasks_path.clone() else {
+            return;
+        };
+        let Some(pi) = self.gantt_state.selected_phase_index(&self.dashboard) else {
+            return;
+        };
+        let phase = &self.dashboard.phases[pi];
+        if phase.tasks.is_empty() || phase.progress() < 1.0 {
+            return;
+        }
+        let phase_id = phase.id.clone();
+        if let Some(conflict) =
+            write_conflict(&self.dashboard, &path, &[EditTarget::Phase(&phase_id)])
+        {
+            self.conflict = Some(conflict);
+            return;
+        }
+        let archive = tasks_writer::archive_path(&path);
+        let before = edit_snapshot(&path);
+        let result = tasks_writer::archive_phase(&path, &archive, &phase_id);
+        self.history.record(&format!("archive {phase_id}"), before);
+        if let Ok(Some(_)) = result {
+            self.gantt_state.shift_collapsed(pi, true);
+            let _ = self.dashboard.reload_tasks_file(&path);
+            let last = self.dashboard.phases.len().saturating_sub(1);
+            self.gantt_state.select_phase(&self.dashboard, pi.min(last));
+        }
     }
 
-    #[test]
-    fn app_toggle_help() {
-        let mut app = App::new();
-        assert!(!app.show_help);
-        app.toggle_help();
-        assert!(app.show_help);
-        app.toggle_help();
-        assert!(!app.show_help);
+    /// Revert the last dashboard edit to TASKS.md (or its archive)
+    pub fn undo(&mut self) {
+        let result = self.history.undo();
+        self.after_history_step("undo", "Undid", result);
     }
 
-    #[test]
-    fn app_toggle_focus_3way() {
-        let mut app = App::new();
-        assert_eq!(app.focused, FocusedPane::TaskList);
-        app.toggle_focus();
-        assert_eq!(app.focused, FocusedPane::Detail);
-        app.toggle_focus();
-        assert_eq!(app.focused, FocusedPane::Agents);
-        app.toggle_focus();
-        assert_eq!(app.focused, FocusedPane::TaskList);
+    /// Re-apply the last undone edit
+    pub fn redo(&mut self) {
+        let result = self.history.redo();
+        self.after_history_step("redo", "Redid", result);
     }
 
-    #[test]
-    fn agent_navigation() {
-        let mut app = App::new();
-        // No agents: move does nothing
-        app.agent_move_down();
-        assert_eq!(app.selected_agent, 0);
+    /// Reload after an undo/redo, keeping collapsed phases by ID, and say
+    /// what happened in the status bar
+    fn after_history_step(
+        &mut self,
+        verb: &str,
+        done: &str,
+        result: anyhow::Result<Option<String>>,
+    ) {
+        self.status_message = Some(match result {
+            Ok(Some(label)) => format!("{done} {label}"),
+            Ok(None) => format!("Nothing to {verb}"),
+            Err(e) => format!("Cannot {verb}: {e}"),
+        });
+        let Some(path) = self.tasks_path.clone() else {
+            return;
+        };
+        let collapsed: Vec<String> = self
+            .gantt_state
+            .collapsed
+            .iter()
+            .filter_map(|&pi| self.dashboard.phases.get(pi).map(|p| p.id.clone()))
+            .collect();
+        let _ = self.dashboard.reload_tasks_file(&path);
+        self.gantt_state.collapsed = self
+            .dashboard
+            .phases
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| collapsed.contains(&p.id))
+            .map(|(pi, _)| pi)
+            .collect();
+    }
 
-        // Add some agents via events
-        use crate::data::hook_parser;
-        let input = include_str!("../tests/fixtures/sample_hooks/agent_events.jsonl");
-        let result = hook_parser::parse_hook_events(input);
-        app.dashboard.update_from_events(&result.events);
+    /// Ask the main loop to open the file of the highlighted drill-down tool
+    /// call, or TASKS.md at the selected task's heading
+    pub fn open_in_editor(&mut self) {
+        if self.agent_drilldown.is_some() {
+            let tools = self.drilldown_tools();
+            let path = tools
+                .get(self.drilldown_tool)
+                .and_then(|inv| inv.file_path.as_ref());
+            self.editor_request = path.map(|path| EditorRequest {
+                path: PathBuf::from(path),
+                line: None,
+            });
+            return;
+        }
+        let Some(path) = self.tasks_path.clone() else {
+            return;
+        };
+        if let Some((pi, ti)) = self.selected_task() {
+            self.editor_request = Some(EditorRequest {
+                path,
+                line: Some(self.dashboard.phases[pi].tasks[ti].line_number),
+            });
+        }
+    }
 
-        let input2 = include_str!("../tests/fixtures/sample_hooks/error_events.jsonl");
-        let result2 = hook_parser::parse_hook_events(input2);
-        app.dashboard.update_from_events(&result2.events);
+    /// Ask the main loop to page the selected agent's whole event history
+    /// in the Agents pane, otherwise the selection's full detail
+    pub fn open_pager(&mut self) {
+        if self.focused == FocusedPane::Agents || self.agent_drilldown.is_some() {
+            let Some(agent_id) = self
+                .agent_drilldown
+                .clone()
+                .or_else(|| self.sorted_agent_ids().get(self.selected_agent).cloned())
+            else {
+                return;
+            };
+            let mut events = Vec::new();
+            loader::for_each_batch(&self.event_dirs, |batch| {
+                events.extend(batch.into_iter().filter(|e| e.agent_id == agent_id));
+            });
+            events.sort_by_key(|e| e.timestamp);
+            if events.is_empty() {
+                self.status_message = Some(format!("No events recorded for {agent_id}"));
+                return;
+            }
+            self.pager_request = Some(pager::agent_history(&agent_id, &events));
+            return;
+        }
+        let detail = DetailWidget::from_selection_full(
+            &self.dashboard,
+            self.selected_task(),
+            self.gantt_state.selected,
+        );
+        self.pager_request = Some(detail.plain_text());
+    }
 
-        // Now we have >=2 agents
-        assert!(app.dashboard.agents.len() >= 2);
-        app.agent_move_down();
-        assert_eq!(app.selected_agent, 1);
-        app.agent_move_up();
-        assert_eq!(app.selected_agent, 0);
-        // Can't go below 0
-        app.agent_move_up();
-        assert_eq!(app.selected_agent, 0);
+    /// Ask the main loop to copy the status summary to the clipboard
+    pub fn copy_status_summary(&mut self) {
+        self.clipboard_request = Some(crate::clipboard::status_summary(
+            &self.dashboard,
+            chrono::Utc::now(),
+        ));
     }
 
-    #[test]
-    fn sorted_agent_ids() {
-        let mut app = App::new();
-        use crate::data::hook_parser;
-        let input = include_str!("../tests/fixtures/sample_hooks/agent_events.jsonl");
-        let result = hook_parser::parse_hook_events(input);
+    /// Directory whose git repository TASKS.md belongs to
+    fn repo_dir(&self) -> Option<PathBuf> {
+        let parent = self.tasks_path.as_ref()?.parent()?;
+        Some(if parent.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            parent.to_path_buf()
+        })
+    }
+
+    /// Re-read the project's git log so tasks show the commits naming them
+    pub fn refresh_commits(&mut self) {
+        if let Some(dir) = self.repo_dir() {
+            self.dashboard.commits = git_log::read_log(&dir);
+        }
+    }
+
+    /// Ask the main loop to show the diffs of the commits that mention the
+    /// selected task
+    pub fn open_task_diff(&mut self) {
+        let (Some((pi, ti)), Some(dir)) = (self.selected_task(), self.repo_dir()) else {
+            return;
+        };
+        let task_id = &self.dashboard.phases[pi].tasks[ti].id;
+        let commits = self.dashboard.commits_for_task(task_id);
+        if commits.is_empty() {
+            self.status_message = Some(format!("No commits mention {task_id}"));
+            return;
+        }
+        self.shell_request = Some(ShellRequest {
+            label: format!("{task_id} diff"),
+            command: git_log::show_command(&dir, &commits),
+        });
+    }
+
+    /// Template values for custom actions: the selected agent, its task and
+    /// the drill-down's highlighted file in the Agents pane; otherwise the
+    /// selected task, its agent and TASKS.md
+    fn action_context(&self) -> ActionContext {
+        if self.focused == FocusedPane::Agents {
+            let agent_id = self
+                .agent_drilldown
+                .clone()
+                .or_else(|| self.sorted_agent_ids().get(self.selected_agent).cloned());
+            let file = self
+                .drilldown_tools()
+                .get(self.drilldown_tool)
+                .and_then(|inv| inv.file_path.clone());
+            return ActionContext {
+                task_id: agent_id
+                    .as_ref()
+                    .and_then(|id| self.dashboard.agents.get(id))
+                    .and_then(|agent| agent.current_task.clone()),
+                agent: agent_id,
+                file,
+            };
+        }
+        let task = self
+            .selected_task()
+            .map(|(pi, ti)| &self.dashboard.phases[pi].tasks[ti]);
+        ActionContext {
+            task_id: task.map(|t| t.id.clone()),
+            agent: task.and_then(|t| t.agent.clone()),
+            file: self
+                .tasks_path
+                .as_ref()
+                .map(|p| p.to_string_lossy().into_owned()),
+        }
+    }
+
+    /// Queue the command bound to function key `key` for the main loop, or
+    /// explain in the status bar why it cannot run
+    pub fn run_custom_action(&mut self, key: u8) {
+        let Some(action) = self.config.actions.iter().find(|a| a.key == key) else {
+            return;
+        };
+        match actions::expand(&action.command, &self.action_context()) {
+            Ok(command) => {
+                self.shell_request = Some(ShellRequest {
+                    label: format!("F{key}"),
+                    command,
+                })
+            }
+            Err(e) => self.status_message = Some(format!("F{key}: {e}")),
+        }
+    }
+
+    /// Agent that signals and acknowledgements apply to: the selected one in
+    /// the Agents pane, otherwise the agent working on the selected task
+    fn target_agent(&self) -> Option<String> {
+        if self.focused == FocusedPane::Agents {
+            return self
+                .agent_drilldown
+                .clone()
+                .or_else(|| self.sorted_agent_ids().get(self.selected_agent).cloned());
+        }
+        let (pi, ti) = self.selected_task()?;
+        self.dashboard
+            .agent_for_task(&self.dashboard.phases[pi].tasks[ti].id)
+            .map(str::to_string)
+    }
+
+    pub fn open_signal_picker(&mut self) {
+        if self.commands_dir.is_none() {
+            return;
+        }
+        self.signal_picker = self
+            .target_agent()
+            .map(|agent_id| SignalPickerState::new(&agent_id));
+    }
+
+    pub fn close_signal_picker(&mut self) {
+        self.signal_picker = None;
+    }
+
+    pub fn signal_picker_move_down(&mut self) {
+        if let Some(ref mut picker) = self.signal_picker {
+            picker.move_down();
+        }
+    }
+
+    pub fn signal_picker_move_up(&mut self) {
+        if let Some(ref mut picker) = self.signal_picker {
+            picker.move_up();
+        }
+    }
+
+    /// Enter on a signal: abort asks for confirmation, the others are sent
+    pub fn signal_picker_choose(&mut self) {
+        let Some(ref mut picker) = self.signal_picker else {
+            return;
+        };
+        if picker.selected_signal() == Signal::Abort {
+            picker.confirming = true;
+        } else {
+            self.send_signal();
+        }
+    }
+
+    /// Send the confirmed abort
+    pub fn confirm_signal(&mut self) {
+        if self.signal_picker.as_ref().is_some_and(|p| p.confirming) {
+            self.send_signal();
+        }
+    }
+
+    /// Acknowledge the target agent's errors so they stop counting as open
+    pub fn acknowledge_errors(&mut self) {
+        let Some(agent_id) = self.target_agent() else {
+            return;
+        };
+        let count = self.dashboard.acknowledge_agent_errors(&agent_id);
+        self.status_message = Some(match count {
+            0 => format!("No open errors for {agent_id}"),
+            1 => format!("Acknowledged 1 error from {agent_id}"),
+            n => format!("Acknowledged {n} errors from {agent_id}"),
+        });
+    }
+
+    /// Mute the drilled-down agent's error alerts for `mute_minutes`, or
+    /// lift an active mute
+    pub fn toggle_mute_agent(&mut self) {
+        let Some(agent_id) = self.agent_drilldown.clone() else {
+            return;
+        };
+        let now = chrono::Utc::now();
+        if self.dashboard.muted_until(&agent_id, now).is_some() {
+            self.dashboard.unmute_agent(&agent_id);
+            self.status_message = Some(format!("Unmuted {agent_id}"));
+        } else {
+            let minutes = self.config.mute_minutes();
+            let until = now + chrono::Duration::minutes(minutes as i64);
+            self.dashboard.mute_agent(&agent_id, until);
+            self.status_message = Some(format!("Muted {agent_id} for {minutes} min"));
+        }
+    }
+
+    /// Set a reminder to check back on the selected agent's latest open
+    /// error (Agents pane) or the selected task, or cancel the one it has
+    pub fn toggle_reminder(&mut self) {
+        let (target, message) = if self.focused == FocusedPane::Agents {
+            let Some(agent_id) = self.target_agent() else {
+                return;
+            };
+            let message = match self.dashboard.open_errors_for_agent(&agent_id).next_back() {
+                Some(error) => format!("check {agent_id}: {}", error.message),
+                None => format!("check {agent_id}"),
+            };
+            (format!("agent:{agent_id}"), message)
+        } else {
+            let Some((pi, ti)) = self.selected_task() else {
+                return;
+            };
+            let task = &self.dashboard.phases[pi].tasks[ti];
+            (task.id.clone(), format!("check {}: {}", task.id, task.name))
+        };
+
+        let minutes = self.config.reminder_minutes();
+        let delay = Duration::from_secs(minutes * 60);
+        self.status_message = Some(
+            if self
+                .reminders
+                .toggle(&target, &message, Instant::now(), delay)
+            {
+                format!("Reminder in {minutes} min: {message}")
+            } else {
+                "Cancelled reminder".to_string()
+            },
+        );
+    }
+
+    /// Append the picker's signal to the commands file and close the picker
+    fn send_signal(&mut self) {
+        let (Some(picker), Some(dir)) = (self.signal_picker.take(), self.commands_dir.as_ref())
+        else {
+            return;
+        };
+        let signal = picker.selected_signal();
+        let agent = self.dashboard.agents.get(&picker.agent_id);
+        // The hook compares the raw agent id it runs as, so a folded agent
+        // is signalled through each of its instances
+        let targets: Vec<String> = match agent.filter(|a| !a.instances.is_empty()) {
+            Some(agent) => agent.instances.iter().cloned().collect(),
+            None => vec![picker.agent_id.clone()],
+        };
+        let timestamp = chrono::Utc::now();
+        let result = targets.iter().try_for_each(|target| {
+            let command = ControlCommand {
+                signal,
+                agent_id: target.clone(),
+                session_id: agent.and_then(|a| a.session_id.clone()),
+                task_id: agent.and_then(|a| a.current_task.clone()),
+                timestamp,
+            };
+            commands::append_command(dir, &command)
+        });
+        self.status_message = Some(match result {
+            Ok(()) if targets.len() > 1 => format!(
+                "Sent {} to {} ({} instances)",
+                signal.as_str(),
+                picker.agent_id,
+                targets.len()
+            ),
+            Ok(()) => format!("Sent {} to {}", signal.as_str(), picker.agent_id),
+            Err(e) => format!("Cannot signal {}: {e}", picker.agent_id),
+        });
+    }
+
+    /// Open the add-task form on the selected phase (or the selected task's phase)
+    pub fn open_task_form(&mut self) {
+        if self.tasks_path.is_none() {
+            return;
+        }
+        let phase = self
+            .gantt_state
+            .selected_phase_index(&self.dashboard)
+            .or_else(|| self.selected_task().map(|(pi, _)| pi))
+            .unwrap_or(0);
+        self.task_form = TaskFormState::new(&self.dashboard, phase);
+    }
+
+    /// Forward a key to the add-task form; on submit, append the task to
+    /// TASKS.md and reload. Write errors keep the form open.
+    pub fn task_form_key(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(ref mut form) = self.task_form else {
+            return;
+        };
+        match form.handle_key(key) {
+            FormOutcome::Editing => {}
+            FormOutcome::Cancel => self.task_form = None,
+            FormOutcome::Submit => {
+                let Some(ref path) = self.tasks_path else {
+                    return;
+                };
+                let task = form.new_task();
+                if let Some(conflict) = write_conflict(
+                    &self.dashboard,
+                    path,
+                    &[EditTarget::PhaseHeading(task.phase_id)],
+                ) {
+                    self.conflict = Some(conflict);
+                    return;
+                }
+                let before = edit_snapshot(path);
+                let result = tasks_writer::append_task(path, &task);
+                self.history
+                    .record(&format!("add {}", task.task_id), before);
+                match result {
+                    Ok(true) => {
+                        let _ = self.dashboard.reload_tasks_file(path);
+                        self.task_form = None;
+                    }
+                    Ok(false) => form.error = Some("Phase not found in TASKS.md".to_string()),
+                    Err(e) => form.error = Some(format!("Write failed: {e}")),
+                }
+            }
+        }
+    }
+
+    /// Start renaming the selected task or phase (tree view only, where the
+    /// name is shown)
+    pub fn open_rename(&mut self) {
+        if self.tasks_path.is_none()
+            || self.focused != FocusedPane::TaskList
+            || self.gantt_state.view_mode != GanttViewMode::Tree
+        {
+            return;
+        }
+        self.rename = if let Some((pi, ti)) = self.selected_task() {
+            let task = &self.dashboard.phases[pi].tasks[ti];
+            Some(RenameState::new(
+                RenameTarget::Task(task.id.clone()),
+                &task.name,
+            ))
+        } else {
+            self.gantt_state
+                .selected_phase_index(&self.dashboard)
+                .map(|pi| {
+                    let phase = &self.dashboard.phases[pi];
+                    RenameState::new(RenameTarget::Phase(phase.id.clone()), &phase.name)
+                })
+        };
+    }
+
+    /// Forward a key to the inline rename; on submit, write the new name to
+    /// TASKS.md and reload. Write errors keep the editor open.
+    pub fn rename_key(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(ref mut rename) = self.rename else {
+            return;
+        };
+        match rename.handle_key(key) {
+            FormOutcome::Editing => {}
+            FormOutcome::Cancel => self.rename = None,
+            FormOutcome::Submit => {
+                let Some(ref path) = self.tasks_path else {
+                    return;
+                };
+                let name = rename.input.text().trim();
+                let target = match rename.target {
+                    RenameTarget::Task(ref id) => EditTarget::Task(id),
+                    RenameTarget::Phase(ref id) => EditTarget::PhaseHeading(id),
+                };
+                if let Some(conflict) = write_conflict(&self.dashboard, path, &[target]) {
+                    self.conflict = Some(conflict);
+                    return;
+                }
+                let before = edit_snapshot(path);
+                let (result, id) = match rename.target {
+                    RenameTarget::Task(ref id) => (tasks_writer::rename_task(path, id, name), id),
+                    RenameTarget::Phase(ref id) => (tasks_writer::rename_phase(path, id, name), id),
+                };
+                self.history.record(&format!("rename {id}"), before);
+                match result {
+                    Ok(true) => {
+                        let _ = self.dashboard.reload_tasks_file(path);
+                        self.rename = None;
+                    }
+                    Ok(false) => rename.error = Some("Not found in TASKS.md".to_string()),
+                    Err(e) => rename.error = Some(format!("Write failed: {e}")),
+                }
+            }
+        }
+    }
+
+    /// Open the note editor for the selected task
+    pub fn open_note_editor(&mut self) {
+        if self.tasks_path.is_none() {
+            return;
+        }
+        if let Some((pi, ti)) = self.selected_task() {
+            let task = &self.dashboard.phases[pi].tasks[ti];
+            self.note_editor = Some(NoteEditorState::new(&task.id));
+        }
+    }
+
+    /// Forward a key to the note editor; on submit, append the note under the
+    /// task in TASKS.md with the current local time and reload. Write errors
+    /// keep the editor open.
+    pub fn note_editor_key(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(ref mut editor) = self.note_editor else {
+            return;
+        };
+        match editor.handle_key(key) {
+            FormOutcome::Editing => {}
+            FormOutcome::Cancel => self.note_editor = None,
+            FormOutcome::Submit => {
+                let Some(ref path) = self.tasks_path else {
+                    return;
+                };
+                let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+                if let Some(conflict) =
+                    write_conflict(&self.dashboard, path, &[EditTarget::Task(&editor.task_id)])
+                {
+                    self.conflict = Some(conflict);
+                    return;
+                }
+                let before = edit_snapshot(path);
+                let result = tasks_writer::append_task_note(
+                    path,
+                    &editor.task_id,
+                    editor.text().trim(),
+                    &timestamp,
+                );
+                self.history
+                    .record(&format!("note on {}", editor.task_id), before);
+                match result {
+                    Ok(true) => {
+                        let _ = self.dashboard.reload_tasks_file(path);
+                        self.note_editor = None;
+                    }
+                    Ok(false) => editor.error = Some("Task not found in TASKS.md".to_string()),
+                    Err(e) => editor.error = Some(format!("Write failed: {e}")),
+                }
+            }
+        }
+    }
+
+    /// Ask why the selected task is being blocked (editing the current
+    /// reason when it already is)
+    pub fn open_block_prompt(&mut self) {
+        if self.tasks_path.is_none() {
+            return;
+        }
+        if let Some((pi, ti)) = self.selected_task() {
+            let task = &self.dashboard.phases[pi].tasks[ti];
+            if task.status != TaskStatus::Completed {
+                self.block_reason = Some(BlockReasonState::new(
+                    vec![task.id.clone()],
+                    task.blocked_reason.as_deref(),
+                ));
+            }
+        }
+    }
+
+    /// Forward a key to the blocked reason prompt; on submit, mark the tasks
+    /// Blocked with the reason and reload. Write errors keep the prompt open.
+    pub fn block_reason_key(&mut self, key: crossterm::event::KeyEvent) {
+        let Some(ref mut prompt) = self.block_reason else {
+            return;
+        };
+        match prompt.handle_key(key) {
+            FormOutcome::Editing => {}
+            FormOutcome::Cancel => self.block_reason = None,
+            FormOutcome::Submit => {
+                let Some(ref path) = self.tasks_path else {
+                    return;
+                };
+                let ids: Vec<&str> = prompt.task_ids.iter().map(String::as_str).collect();
+                let targets: Vec<EditTarget> = ids.iter().map(|id| EditTarget::Task(id)).collect();
+                if let Some(conflict) = write_conflict(&self.dashboard, path, &targets) {
+                    self.conflict = Some(conflict);
+                    return;
+                }
+                let before = edit_snapshot(path);
+                let result = tasks_writer::block_tasks(path, &ids, prompt.input.text().trim());
+                self.history
+                    .record(&format!("block {}", ids.join(", ")), before);
+                match result {
+                    Ok(0) => prompt.error = Some("Task not found in TASKS.md".to_string()),
+                    Ok(_) => {
+                        let _ = self.dashboard.reload_tasks_file(path);
+                        self.block_reason = None;
+                    }
+                    Err(e) => prompt.error = Some(format!("Write failed: {e}")),
+                }
+            }
+        }
+    }
+
+    /// Open the agent picker for the selected task
+    pub fn open_agent_picker(&mut self) {
+        if self.tasks_path.is_none() {
+            return;
+        }
+        if let Some((pi, ti)) = self.selected_task() {
+            let task = &self.dashboard.phases[pi].tasks[ti];
+            self.agent_picker = AgentPickerState::new(&self.dashboard, task);
+        }
+    }
+
+    pub fn close_agent_picker(&mut self) {
+        self.agent_picker = None;
+    }
+
+    pub fn agent_picker_move_down(&mut self) {
+        if let Some(ref mut picker) = self.agent_picker {
+            picker.move_down();
+        }
+    }
+
+    pub fn agent_picker_move_up(&mut self) {
+        if let Some(ref mut picker) = self.agent_picker {
+            picker.move_up();
+        }
+    }
+
+    /// Write the picked agent to the task in TASKS.md, reload, and close
+    pub fn confirm_agent_picker(&mut self) {
+        if let (Some(picker), Some(path)) = (self.agent_picker.take(), &self.tasks_path) {
+            if let Some(agent) = picker.selected_agent() {
+                if let Some(conflict) =
+                    write_conflict(&self.dashboard, path, &[EditTarget::Task(&picker.task_id)])
+                {
+                    self.conflict = Some(conflict);
+                    return;
+                }
+                let before = edit_snapshot(path);
+                let result = tasks_writer::set_task_agent(path, &picker.task_id, agent);
+                self.history
+                    .record(&format!("assign {}", picker.task_id), before);
+                if let Ok(true) = result {
+                    let _ = self.dashboard.reload_tasks_file(path);
+                }
+            }
+        }
+    }
+
+    /// Reload TASKS.md after a write and select the task with `task_id`
+    fn reload_and_select(&mut self, path: &std::path::Path, task_id: &str) {
+        let _ = self.dashboard.reload_tasks_file(path);
+        let flat = self
+            .dashboard
+            .phases
+            .iter()
+            .flat_map(|p| p.tasks.iter())
+            .position(|t| t.id == task_id);
+        if let Some(flat) = flat {
+            self.gantt_state.select_flat_task(&self.dashboard, flat);
+        }
+    }
+
+    /// Swap the selected task with its neighbour in the same phase
+    /// (`delta` = 1 moves it down, -1 up) and write the order to TASKS.md
+    pub fn move_task(&mut self, delta: isize) {
+        let Some(path) = self.tasks_path.clone() else {
+            return;
+        };
+        let Some((pi, ti)) = self.selected_task() else {
+            return;
+        };
+        let tasks = &self.dashboard.phases[pi].tasks;
+        let Some(other) = ti.checked_add_signed(delta).and_then(|i| tasks.get(i)) else {
+            return;
+        };
+        let (task_id, other_id) = (tasks[ti].id.clone(), other.id.clone());
+        let targets = [EditTarget::Task(&task_id), EditTarget::Task(&other_id)];
+        if let Some(conflict) = write_conflict(&self.dashboard, &path, &targets) {
+            self.conflict = Some(conflict);
+            return;
+        }
+        let before = edit_snapshot(&path);
+        let result = tasks_writer::swap_tasks(&path, &task_id, &other_id);
+        self.history.record(&format!("move {task_id}"), before);
+        if let Ok(true) = result {
+            self.reload_and_select(&path, &task_id);
+        }
+    }
+
+    /// Open the move-to-phase picker for the selected task
+    pub fn open_phase_picker(&mut self) {
+        if self.tasks_path.is_none() || self.dashboard.phases.len() < 2 {
+            return;
+        }
+        if let Some((pi, ti)) = self.selected_task() {
+            self.phase_picker = PhasePickerState::new(&self.dashboard, pi, ti);
+        }
+    }
+
+    pub fn close_phase_picker(&mut self) {
+        self.phase_picker = None;
+    }
+
+    pub fn phase_picker_move_down(&mut self) {
+        if let Some(ref mut picker) = self.phase_picker {
+            picker.move_down();
+        }
+    }
+
+    pub fn phase_picker_move_up(&mut self) {
+        if let Some(ref mut picker) = self.phase_picker {
+            picker.move_up();
+        }
+    }
+
+    /// Move the task to the picked phase in TASKS.md, reload, and close
+    pub fn confirm_phase_picker(&mut self) {
+        let (Some(picker), Some(path)) = (self.phase_picker.take(), self.tasks_path.clone()) else {
+            return;
+        };
+        if let Some(phase_id) = picker.target_phase() {
+            let targets = [
+                EditTarget::Task(&picker.task_id),
+                EditTarget::PhaseHeading(phase_id),
+            ];
+            if let Some(conflict) = write_conflict(&self.dashboard, &path, &targets) {
+                self.conflict = Some(conflict);
+                return;
+            }
+            let before = edit_snapshot(&path);
+            let result = tasks_writer::move_task_to_phase(&path, &picker.task_id, phase_id);
+            self.history
+                .record(&format!("move {} to {phase_id}", picker.task_id), before);
+            if let Ok(true) = result {
+                self.reload_and_select(&path, &picker.task_id);
+            }
+        }
+    }
+
+    /// Close the conflict dialog, reloading TASKS.md so the edit can be made
+    /// again on the current version
+    pub fn close_conflict(&mut self) {
+        if self.conflict.take().is_none() {
+            return;
+        }
+        if let Some(path) = self.tasks_path.clone() {
+            let _ = self.dashboard.reload_tasks_file(&path);
+        }
+        self.status_message = Some("Reloaded TASKS.md; edit not applied".to_string());
+    }
+
+    /// Cancel the retry modal
+    pub fn cancel_retry(&mut self) {
+        self.show_retry_modal = false;
+        self.retry_target = None;
+        self.confirming_fix = false;
+    }
+
+    /// Get the currently selected task as (phase_idx, task_idx)
+    pub fn selected_task(&self) -> Option<(usize, usize)> {
+        self.gantt_state.selected_task(&self.dashboard)
+    }
+
+    /// Advance periodic animations and fire due reminders; the spinner
+    /// freezes while the watcher is down. True when the screen needs a
+    /// redraw: a spinner is on screen, a reminder fired or agents expired.
+    pub fn on_tick(&mut self) -> bool {
+        let mut changed = false;
+        if !self.watcher_degraded {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+            changed = self.dashboard.active_agents() > 0;
+        }
+        changed |= self.fire_reminders(Instant::now());
+        changed |= self.expire_idle_agents(chrono::Utc::now());
+        changed
+    }
+
+    /// Presume agents silent for the configured idle window finished, so
+    /// crashed agents stop counting as active; true when any did
+    fn expire_idle_agents(&mut self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let window = chrono::Duration::minutes(self.config.idle_minutes() as i64);
+        let expired = self.dashboard.expire_idle_agents(now, window) > 0;
+        if expired {
+            self.write_status_file();
+        }
+        expired
+    }
+
+    /// Show reminders due at `now` in the status bar and ask for the bell;
+    /// true when any came due
+    fn fire_reminders(&mut self, now: Instant) -> bool {
+        let due = self.reminders.take_due(now);
+        if due.is_empty() {
+            return false;
+        }
+        let messages: Vec<&str> = due.iter().map(|r| r.message.as_str()).collect();
+        self.status_message = Some(format!("Reminder: {}", messages.join(" | ")));
+        self.bell = true;
+        true
+    }
+
+    /// Handle a message from the background history loader
+    pub fn handle_load_message(&mut self, msg: LoadMessage) {
+        match msg {
+            LoadMessage::Progress(p) => self.history_progress = Some(p),
+            LoadMessage::Finished(loaded, tail) => {
+                self.history_progress = None;
+                if std::mem::take(&mut self.reload_pending) {
+                    // Aggregated before the filter or `[agents]` changed
+                    self.reload_history();
+                    return;
+                }
+                let refilter = loaded.session_filter != self.dashboard.session_filter;
+                self.dashboard.absorb_events(*loaded);
+                // Changes seen while loading are covered by the loaded state,
+                // up to where the tail stopped
+                self.tail = tail;
+                for path in std::mem::take(&mut self.deferred_hook_files) {
+                    self.apply_hook_file(&path);
+                }
+                // The loader aggregated every session, but a restored view
+                // filters one (unless it is gone by now)
+                if refilter {
+                    let sessions = &self.dashboard.sessions;
+                    let filter = self
+                        .dashboard
+                        .session_filter
+                        .clone()
+                        .filter(|id| sessions.contains_key(id));
+                    self.apply_session_filter(filter);
+                }
+                if let Some(agent) = self.restored_agent.take() {
+                    if let Some(i) = self.sorted_agent_ids().iter().position(|a| *a == agent) {
+                        self.selected_agent = i;
+                    }
+                }
+                // History is not news: only later changes notify
+                self.baseline = Baseline::capture(&self.dashboard);
+                self.budget_watch = BudgetWatch::capture(&self.dashboard, &self.config.budget);
+                self.write_status_file();
+                self.write_session_history();
+                // Let the user choose a session when several are present,
+                // unless the restored view already did
+                if !self.view_restored && !self.history_loaded && self.dashboard.sessions.len() > 1
+                {
+                    self.open_session_picker();
+                }
+                self.history_loaded = true;
+            }
+        }
+    }
+
+    /// Handle a file change event from the watcher
+    pub fn handle_file_change(&mut self, change: &FileChange) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            if let Err(e) = recorder.record(change) {
+                self.status_message = Some(format!("Recording stopped: {e}"));
+                self.recorder = None;
+            }
+        }
+        match change {
+            FileChange::TasksModified(path) => {
+                // Unreadable or not text: keep the last good tasks, say why
+                if let Err(e) = self.dashboard.reload_tasks_file(path) {
+                    self.status_message = Some(e.to_string());
+                }
+                // Tasks are usually ticked off right after their commit
+                self.refresh_commits();
+            }
+            FileChange::HookEventCreated(path) | FileChange::HookEventModified(path) => {
+                if self.history_progress.is_none() {
+                    self.apply_hook_file(path);
+                } else if !self.deferred_hook_files.contains(path) {
+                    // The loader's tail is not ours yet: reading now would
+                    // start the file over on this thread
+                    self.deferred_hook_files.push(path.clone());
+                }
+            }
+            FileChange::ConfigModified(_) => self.reload_config(),
+        }
+        self.after_change();
+    }
+
+    /// Apply what was appended to the hook file at `path` since it was last
+    /// read, or rebuild from disk when it shrank
+    fn apply_hook_file(&mut self, path: &Path) {
+        let Some(events) = self.tail.read_appended(path) else {
+            // Truncated or rotated: what it holds now is new to auto-sync
+            if self.config.auto_sync {
+                let events = Tail::default().read_new(path);
+                self.auto_sync_tasks(path, &events);
+            }
+            self.reload_history();
+            return;
+        };
+        // Before the events are applied, so runs failed earlier are known
+        if self.config.auto_sync {
+            self.auto_sync_tasks(path, &events);
+        }
+        self.dashboard.update_from_events(&events);
+        self.dashboard.refresh_checkouts();
+    }
+
+    /// Apply board.toml and rules.toml as they are now. A file that does not
+    /// parse leaves everything as it was; the toast says why.
+    pub fn reload_config(&mut self) {
+        let Some(home) = self.config_home.clone() else {
+            return;
+        };
+        let loaded = Config::load(&home).and_then(|config| {
+            rules::load_rules(&rules::user_rules_path(&home)).map(|rules| (config, rules))
+        });
+        let (mut config, error_rules) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.status_message = Some(format!("Config not reloaded: {e}"));
+                return;
+            }
+        };
+        if let Some(theme) = self.theme_override {
+            config.theme = theme;
+        }
+        rules::set_rules(error_rules);
+        // Only settings that changed override what was toggled since
+        if config.layout != self.config.layout {
+            self.layout_mode = config.layout;
+        }
+        if config.progress_gauge != self.config.progress_gauge {
+            self.show_gauge = config.progress_gauge;
+        }
+        let identity_changed = config.agent_identity != self.config.agent_identity;
+        // New thresholds start from the current levels rather than toasting
+        self.budget_watch = BudgetWatch::capture(&self.dashboard, &config.budget);
+        self.dashboard.limits = config.limits;
+        self.config = config;
+        if identity_changed {
+            self.dashboard.agent_identity = self.config.agent_identity();
+            self.reload_history();
+        }
+        self.dashboard.enforce_limits();
+        self.status_message = Some("Config reloaded".to_string());
+    }
+
+    /// Apply one update from the ingestion sources
+    pub fn handle_update(&mut self, update: Update) {
+        match update {
+            Update::Load(msg) => self.handle_load_message(msg),
+            Update::File(change) => self.handle_file_change(&change),
+            Update::Events(events) => {
+                self.dashboard.update_from_events(&events);
+                self.dashboard.refresh_checkouts();
+                self.after_change();
+            }
+            Update::Lost("files") => self.watcher_degraded = true,
+            Update::Lost(name) => self.status_message = Some(format!("{name} source closed")),
+        }
+    }
+
+    /// Notify about what the latest change did and persist the new state
+    fn after_change(&mut self) {
+        let mut transitions = self.baseline.advance(&self.dashboard);
+        let budget = self
+            .budget_watch
+            .advance(&self.dashboard, &self.config.budget);
+        // The worst crossing doubles as a toast
+        if let Some(worst) = budget.iter().max_by_key(|t| match t {
+            Transition::Budget(check) => check.level,
+            _ => BudgetLevel::Ok,
+        }) {
+            self.status_message = Some(worst.summary());
+        }
+        transitions.extend(budget);
+        self.notify(&transitions);
+        self.write_status_file();
+        self.write_session_history();
+    }
+
+    /// Aggregate the event history again from scratch, after the session
+    /// filter or `[agents]` changed or a hook file shrank. With an update
+    /// channel the loader thread reads the files and the progress shows as
+    /// on launch; one load runs at a time.
+    fn reload_history(&mut self) {
+        self.tail = Tail::default();
+        let Some(tx) = &self.updates else {
+            self.rebuild_events();
+            return;
+        };
+        if self.history_progress.is_some() {
+            self.reload_pending = true;
+            return;
+        }
+        self.history_progress = Some(0.0);
+        source::reload_history(
+            tx,
+            self.event_dirs.clone(),
+            self.dashboard.session_filter.clone(),
+            self.dashboard.agent_identity.clone(),
+        );
+    }
+
+    /// Rebuild the event-derived state by reading the files in the event
+    /// directories again in full, on this thread
+    fn rebuild_events(&mut self) {
+        self.dashboard.clear_events();
+        let dashboard = &mut self.dashboard;
+        for (path, _) in loader::collect_jsonl_files(&self.event_dirs) {
+            self.tail
+                .read_new_batches(&path, |batch| dashboard.update_from_events(&batch));
+        }
+        self.dashboard.refresh_checkouts();
+    }
+
+    /// Write the task statuses implied by the new events of the hook file at
+    /// `source` to TASKS.md
+    fn auto_sync_tasks(&mut self, source: &Path, events: &[HookEvent]) {
+        let since = self
+            .synced_until
+            .get(source)
+            .copied()
+            .unwrap_or(self.sync_since);
+        let Some(path) = self.tasks_path.clone() else {
+            return;
+        };
+        let failed_before = |id: &str| self.dashboard.task_times.get(id).is_some_and(|t| t.errored);
+        let changes: Vec<(String, TaskStatus)> =
+            auto_sync::implied_statuses(events, since, failed_before)
+                .into_iter()
+                .filter(|(id, status)| {
+                    self.dashboard
+                        .find_task(id)
+                        .is_some_and(|task| task.status != *status)
+                })
+                .collect();
+        let targets: Vec<EditTarget> = changes.iter().map(|(id, _)| EditTarget::Task(id)).collect();
+        if write_conflict(&self.dashboard, &path, &targets).is_some() {
+            // Tried again with the next event, once TASKS.md is reloaded
+            self.status_message = Some("Auto-sync skipped: TASKS.md changed on disk".to_string());
+            return;
+        }
+        if let Some(latest) = auto_sync::latest(events) {
+            self.synced_until
+                .insert(source.to_path_buf(), latest.max(since));
+        }
+        if changes.is_empty() {
+            return;
+        }
+        let updates: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|(id, status)| (id.as_str(), auto_sync::status_tag(status)))
+            .collect();
+        let before = edit_snapshot(&path);
+        let result = tasks_writer::set_task_statuses(&path, &updates);
+        self.history.record("auto-sync", before);
+        if let Ok(changed) = result {
+            if changed > 0 {
+                let _ = self.dashboard.reload_tasks_file(&path);
+                let synced: Vec<String> = changes
+                    .iter()
+                    .map(|(id, status)| format!("{id} {}", status.as_str()))
+                    .collect();
+                self.status_message = Some(format!("Synced {}", synced.join(", ")));
+            }
+        }
+    }
+
+    /// Merge the current sessions into `sessions.json` in the commands
+    /// directory when they saw new events
+    fn write_session_history(&mut self) {
+        let Some(dir) = &self.commands_dir else {
+            return;
+        };
+        let events: usize = self
+            .dashboard
+            .sessions
+            .values()
+            .map(|s| s.event_count)
+            .sum();
+        if events == 0 || self.last_history == Some(events) {
+            return;
+        }
+        let sessions = session_history::merge(session_history::read(dir), &self.dashboard.sessions);
+        if session_history::write(dir, &sessions).is_ok() {
+            self.last_history = Some(events);
+        }
+    }
+
+    /// Rewrite `status.json` in the commands directory when its summary
+    /// changed
+    fn write_status_file(&mut self) {
+        let Some(dir) = &self.commands_dir else {
+            return;
+        };
+        let now = chrono::Utc::now();
+        let summary = status_file::summary(&self.dashboard, now);
+        if self.last_status.as_ref() == Some(&summary) {
+            return;
+        }
+        if status_file::write(dir, &summary, now).is_ok() {
+            self.last_status = Some(summary);
+        }
+    }
+
+    /// Tell the configured webhooks about `transitions` and escalate errors
+    /// and failures while the terminal is unfocused
+    fn notify(&mut self, transitions: &[Transition]) {
+        if transitions.is_empty() {
+            return;
+        }
+        crate::webhooks::deliver(&self.config.webhooks, transitions);
+
+        let alerts = &self.config.alerts;
+        if alerts.enabled() && (!self.terminal_focused || alerts.when_focused) {
+            let now = chrono::Utc::now();
+            let dashboard = &self.dashboard;
+            let muted = |agent: &str| dashboard.muted_until(agent, now).is_some();
+            if let Some(message) = self.alerter.observe(alerts, transitions, muted) {
+                self.alert = Some(message);
+            }
+        }
+    }
+
+    /// Track terminal focus; coming back starts the alert counts over
+    pub fn set_terminal_focus(&mut self, focused: bool) {
+        if focused && !self.terminal_focused {
+            self.alerter.reset();
+        }
+        self.terminal_focused = focused;
+    }
+}
+
+/// The conflict, if TASKS.md changed since it was loaded in a way that
+/// makes an edit of `targets` ambiguous
+fn write_conflict(
+    dashboard: &DashboardState,
+    path: &std::path::Path,
+    targets: &[EditTarget],
+) -> Option<Conflict> {
+    write_guard::check(path, dashboard.loaded_tasks.as_ref()?, targets)
+}
+
+/// Task name for an error: its first line, shortened to fit a task row
+fn error_task_name(message: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = message.lines().next().unwrap_or("").trim();
+    if line.chars().count() <= MAX_CHARS {
+        return line.to_string();
+    }
+    let short: String = line.chars().take(MAX_CHARS - 1).collect();
+    format!("{}\u{2026}", short.trim_end())
+}
+
+/// TASKS.md and its archive as they are before a dashboard edit
+fn edit_snapshot(path: &std::path::Path) -> Snapshot {
+    Snapshot::take(&[path, &tasks_writer::archive_path(path)])
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_default() {
+        let app = App::new();
+        assert!(app.running);
+        assert!(!app.show_help);
+        assert_eq!(app.focused, FocusedPane::TaskList);
+    }
+
+    #[test]
+    fn app_quit() {
+        let mut app = App::new();
+        app.quit();
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn app_toggle_help() {
+        let mut app = App::new();
+        assert!(!app.show_help);
+        app.toggle_help();
+        assert!(app.show_help);
+        app.toggle_help();
+        assert!(!app.show_help);
+    }
+
+    #[test]
+    fn help_key_filters_and_closes() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = App::new();
+        app.toggle_help();
+        app.help_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert!(app.running, "q filters instead of quitting");
+        assert_eq!(app.help.filter, "q");
+        app.help_key(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE));
+        assert!(!app.show_help);
+        app.toggle_help();
+        assert!(app.help.filter.is_empty(), "reopening starts unfiltered");
+    }
+
+    #[test]
+    fn app_toggle_focus_3way() {
+        let mut app = App::new();
+        assert_eq!(app.focused, FocusedPane::TaskList);
+        app.toggle_focus();
+        assert_eq!(app.focused, FocusedPane::Detail);
+        app.toggle_focus();
+        assert_eq!(app.focused, FocusedPane::Agents);
+        app.toggle_focus();
+        assert_eq!(app.focused, FocusedPane::TaskList);
+    }
+
+    #[test]
+    fn custom_panels_join_focus_and_take_keys() {
+        use crate::ui::panel::tests::Ci;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = App::new().with_panel(Ci::default());
+        let key = |c| KeyEvent::new(c, KeyModifiers::NONE);
+        for _ in 0..3 {
+            app.toggle_focus();
+        }
+        assert_eq!(app.focused, FocusedPane::Custom(0));
+        app.toggle_focus();
+        assert_eq!(app.focused, FocusedPane::TaskList);
+
+        // Unbound hotkey jumps to the panel; a bound key does not
+        assert!(app.panel_key(key(KeyCode::Char('C')), &Action::None));
+        assert_eq!(app.focused, FocusedPane::Custom(0));
+        assert!(app.panel_key(key(KeyCode::Enter), &Action::Select));
+        assert!(!app.panel_key(key(KeyCode::Tab), &Action::ToggleFocus));
+        assert!(!app.panel_key(key(KeyCode::Char('j')), &Action::MoveDown));
+        // Key presses reach the panel the same way
+        app.focused = FocusedPane::TaskList;
+        app.handle_key(key(KeyCode::Char('C')));
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.focused, FocusedPane::Custom(0));
+        let lines = app.panels[0].build_lines(&app.dashboard, 20);
+        assert_eq!(lines[0].to_string(), "0 tasks, refreshed 2");
+
+        // A saved focus on a panel this build lacks falls back
+        let mut plain = App::new();
+        plain.restore_view(ViewState {
+            focused: FocusedPane::Custom(0),
+            ..ViewState::default()
+        });
+        assert_eq!(plain.focused, FocusedPane::TaskList);
+    }
+
+    #[test]
+    fn agent_navigation() {
+        let mut app = App::new();
+        // No agents: move does nothing
+        app.agent_move_down();
+        assert_eq!(app.selected_agent, 0);
+
+        // Add some agents via events
+        use crate::data::hook_parser;
+        let input = include_str!("../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+        app.dashboard.update_from_events(&result.events);
+
+        let input2 = include_str!("../tests/fixtures/sample_hooks/error_events.jsonl");
+        let result2 = hook_parser::parse_hook_events(input2);
+        app.dashboard.update_from_events(&result2.events);
+
+        // Now we have >=2 agents
+        assert!(app.dashboard.agents.len() >= 2);
+        app.agent_move_down();
+        assert_eq!(app.selected_agent, 1);
+        app.agent_move_up();
+        assert_eq!(app.selected_agent, 0);
+        // Can't go below 0
+        app.agent_move_up();
+        assert_eq!(app.selected_agent, 0);
+    }
+
+    #[test]
+    fn agent_drilldown_toggle() {
+        use crate::data::hook_parser;
+        let mut app = App::new();
+        let input = include_str!("../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+        app.dashboard.update_from_events(&result.events);
+
+        // Only available from the Agents pane
+        app.toggle_agent_drilldown();
+        assert!(app.agent_drilldown.is_none());
+
+        app.focused = FocusedPane::Agents;
+        app.toggle_agent_drilldown();
+        assert_eq!(app.agent_drilldown.as_deref(), Some("backend-specialist-1"));
+        app.toggle_agent_drilldown();
+        assert!(app.agent_drilldown.is_none());
+
+        // Leaving the pane closes the drill-down
+        app.toggle_agent_drilldown();
+        app.toggle_focus();
+        assert!(app.agent_drilldown.is_none());
+    }
+
+    #[test]
+    fn session_picker_filters_dashboard() {
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let mut dashboard = DashboardState::default();
+        dashboard.load_hook_events(&hooks_dir).unwrap();
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_event_dirs(vec![hooks_dir]);
+        let total_agents = app.dashboard.agents.len();
+
+        app.open_session_picker();
+        assert!(app.show_session_picker);
+        assert_eq!(app.session_picker_selected, 0);
+
+        app.session_picker_move_down();
+        let expected = app.dashboard.sorted_sessions()[0].session_id.clone();
+        app.confirm_session_picker();
+        assert!(!app.show_session_picker);
+        assert_eq!(
+            app.dashboard.session_filter.as_deref(),
+            Some(expected.as_str())
+        );
+        assert!(app.dashboard.agents.len() < total_agents);
+
+        // Reopening preselects the active session; choosing "All" restores the merge
+        app.open_session_picker();
+        assert_eq!(app.session_picker_selected, 1);
+        app.session_picker_move_up();
+        app.confirm_session_picker();
+        assert!(app.dashboard.session_filter.is_none());
+        assert_eq!(app.dashboard.agents.len(), total_agents);
+    }
+
+    #[test]
+    fn session_picker_navigation_bounds() {
+        let mut app = App::new();
+        app.session_picker_move_down();
+        assert_eq!(app.session_picker_selected, 0);
+        app.session_picker_move_up();
+        assert_eq!(app.session_picker_selected, 0);
+    }
+
+    #[test]
+    fn sorted_agent_ids() {
+        let mut app = App::new();
+        use crate::data::hook_parser;
+        let input = include_str!("../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
         app.dashboard.update_from_events(&result.events);
 
-        let ids = app.sorted_agent_ids();
-        assert!(!ids.is_empty());
-        // Should be sorted
-        let mut sorted = ids.clone();
-        sorted.sort();
-        assert_eq!(ids, sorted);
+        let ids = app.sorted_agent_ids();
+        assert!(!ids.is_empty());
+        // Should be sorted
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn app_navigation() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let dashboard = DashboardState::from_tasks_content(input).unwrap();
+        let mut app = App::new().with_dashboard(dashboard);
+        app.gantt_state.total_items = 11;
+
+        app.move_down();
+        assert_eq!(app.gantt_state.selected, 1);
+        assert_eq!(app.selected_task(), Some((0, 0)));
+
+        app.move_up();
+        assert_eq!(app.gantt_state.selected, 0);
+        assert!(app.selected_task().is_none()); // phase header
+    }
+
+    #[test]
+    fn app_with_dashboard() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let dashboard = DashboardState::from_tasks_content(input).unwrap();
+        let app = App::new().with_dashboard(dashboard);
+        assert_eq!(app.dashboard.total_tasks, 8);
+    }
+
+    #[test]
+    fn handle_file_change_tasks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 0: Setup\n\n### [x] P0-T0.1: Init project\n",
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        assert_eq!(app.dashboard.total_tasks, 0);
+
+        let change = FileChange::TasksModified(tasks_file);
+        app.handle_file_change(&change);
+        assert_eq!(app.dashboard.total_tasks, 1);
+    }
+
+    #[test]
+    fn unreadable_tasks_file_keeps_tasks_and_says_why() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [ ] P1-T1: One\n").unwrap();
+        let mut app = App::new();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+
+        std::fs::write(&tasks_file, b"\xff\xfe binary").unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file));
+        assert_eq!(app.dashboard.total_tasks, 1);
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|m| m.starts_with("tasks file is not text")));
+    }
+
+    #[test]
+    fn file_changes_rewrite_the_status_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [ ] P1-T1: One\n").unwrap();
+        let mut app = App::new().with_commands_dir(tmp.path().to_path_buf());
+        let status = tmp.path().join(status_file::STATUS_FILE);
+
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        let written = std::fs::read_to_string(&status).unwrap();
+        assert!(written.contains(r#""total":1"#), "got: {written}");
+
+        // Unchanged state leaves the file alone
+        std::fs::remove_file(&status).unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file.clone()));
+        assert!(!status.exists());
+
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [x] P1-T1: One\n").unwrap();
+        app.handle_file_change(&FileChange::TasksModified(tasks_file));
+        let written = std::fs::read_to_string(&status).unwrap();
+        assert!(written.contains(r#""completed":1"#), "got: {written}");
+    }
+
+    #[test]
+    fn hook_changes_persist_sessions_for_the_history_view() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let events = tmp.path().join("events.jsonl");
+        std::fs::write(
+            &events,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s1"}"#,
+        )
+        .unwrap();
+        let mut app = App::new().with_commands_dir(tmp.path().to_path_buf());
+        app.open_session_history();
+        assert!(app.session_history.is_none());
+
+        app.handle_file_change(&FileChange::HookEventModified(events));
+        assert_eq!(session_history::read(tmp.path()).len(), 1);
+
+        // Still listed once the events are gone
+        app.dashboard = DashboardState::default();
+        app.open_session_history();
+        let history = app.session_history.as_ref().unwrap();
+        assert_eq!(history.sessions[0].session_id, "s1");
+    }
+
+    #[test]
+    fn failed_task_alerts_only_while_unfocused() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [ ] P1-T1: One\n").unwrap();
+        let mut config = Config::default();
+        config.alerts.bell = true;
+        let mut app = App::new()
+            .with_config(config)
+            .with_dashboard(DashboardState::from_tasks_file(&tasks_file).unwrap());
+        let change = FileChange::TasksModified(tasks_file.clone());
+
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [Failed] P1-T1: One\n").unwrap();
+        app.handle_file_change(&change);
+        assert_eq!(app.alert, None);
+
+        app.set_terminal_focus(false);
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [ ] P1-T1: One\n").unwrap();
+        app.handle_file_change(&change);
+        std::fs::write(&tasks_file, "# Phase 1: A\n### [Failed] P1-T1: One\n").unwrap();
+        app.handle_file_change(&change);
+        assert_eq!(app.alert.as_deref(), Some("P1-T1 One: pending -> failed"));
+    }
+
+    #[test]
+    fn open_retry_modal_on_failed_task() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let dashboard = DashboardState::from_tasks_content(input).unwrap();
+        let mut app = App::new().with_dashboard(dashboard);
+        app.gantt_state.total_items = 11;
+
+        // Navigate to a Failed task: P1-R3-T1 (Phase 1, task index 2)
+        // Phase 0 header(0) + 2 tasks(1,2) + Phase 1 header(3) + task(4) + task(5) + task(6=Failed)
+        app.gantt_state.selected = 6;
+        app.open_retry_modal();
+        assert!(app.show_retry_modal);
+        assert!(app.retry_target.is_some());
+        let target = app.retry_target.as_ref().unwrap();
+        assert_eq!(target.task_id, "P1-R3-T1");
+    }
+
+    #[test]
+    fn open_retry_modal_ignored_for_completed_task() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let dashboard = DashboardState::from_tasks_content(input).unwrap();
+        let mut app = App::new().with_dashboard(dashboard);
+        app.gantt_state.total_items = 11;
+
+        // Navigate to a Completed task: P0-T0.1 (index 1)
+        app.gantt_state.selected = 1;
+        app.open_retry_modal();
+        assert!(!app.show_retry_modal);
+        assert!(app.retry_target.is_none());
+    }
+
+    #[test]
+    fn open_retry_modal_ignored_for_task_waiting_on_dependencies() {
+        let dashboard = DashboardState::from_tasks_content(
+            "# Phase 1: A\n### [ ] P1-T1: One\n### [ ] P1-T2: Two\n- depends: P1-T1\n",
+        )
+        .unwrap();
+        let mut app = App::new().with_dashboard(dashboard);
+        app.gantt_state.total_items = 3;
+        app.gantt_state.selected = 2;
+        app.open_retry_modal();
+        assert!(!app.show_retry_modal);
+    }
+
+    #[test]
+    fn retry_modal_runs_the_error_fix_after_confirmation() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let mut dashboard = DashboardState::from_tasks_content(input).unwrap();
+        dashboard.recent_errors.push(ErrorRecord {
+            agent_id: "a".to_string(),
+            task_id: "P1-R3-T1".to_string(),
+            message: "permission denied: scripts/build.sh".to_string(),
+            category: crate::analysis::rules::ErrorCategory::Permission,
+            retryable: false,
+            suggestion: "Make the script executable".into(),
+            command: Some("chmod +x scripts/build.sh".into()),
+            timestamp: chrono::Utc::now(),
+            tool_name: None,
+            count: 1,
+        });
+        let mut app = App::new().with_dashboard(dashboard);
+        app.gantt_state.total_items = 11;
+        app.gantt_state.selected = 6;
+        app.open_retry_modal();
+        assert_eq!(app.retry_fix(), Some("chmod +x scripts/build.sh"));
+
+        app.request_fix();
+        assert!(app.confirming_fix);
+        assert!(app.shell_request.is_none());
+        app.run_fix();
+        let request = app.shell_request.take().unwrap();
+        assert_eq!(request.label, "P1-R3-T1 fix");
+        assert_eq!(request.command, "chmod +x scripts/build.sh");
+        assert!(!app.show_retry_modal);
+        assert!(!app.confirming_fix);
+    }
+
+    #[test]
+    fn cancel_retry_closes_modal() {
+        let mut app = App::new();
+        app.show_retry_modal = true;
+        app.retry_target = Some(super::RetryTarget {
+            task_id: "T1".to_string(),
+            task_name: "Test".to_string(),
+            retryable: true,
+        });
+        app.cancel_retry();
+        assert!(!app.show_retry_modal);
+        assert!(app.retry_target.is_none());
+    }
+
+    #[test]
+    fn task_form_appends_task_and_reloads() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 0: Setup\n\n### [x] P0-T1: Init\n\n# Phase 1: Build\n\n### [ ] P1-T1: Core\n",
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&tasks_file).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(&content).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        // Selection on P0-T1 → form opens on phase 0
+        app.gantt_state.selected = 1;
+        app.open_task_form();
+        assert_eq!(app.task_form.as_ref().unwrap().id.text(), "P0-T2");
+        for c in "Lint".chars() {
+            app.task_form_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        app.task_form_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(app.task_form.is_none());
+        assert_eq!(app.dashboard.phases[0].tasks[1].name, "Lint");
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("### [x] P0-T1: Init\n\n### [ ] P0-T2: Lint\n\n# Phase 1"));
+    }
+
+    #[test]
+    fn rename_task_and_phase_writes_tasks_file() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 0: Setup\n\n### [ ] T1: Init @dev\n").unwrap();
+        let content = std::fs::read_to_string(&tasks_file).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(&content).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        app.gantt_state.selected = 1;
+        app.open_rename();
+        assert_eq!(app.rename.as_ref().unwrap().input.text(), "Init");
+        for c in " repo".chars() {
+            app.rename_key(key(KeyCode::Char(c)));
+        }
+        app.rename_key(key(KeyCode::Enter));
+        assert!(app.rename.is_none());
+        assert_eq!(app.dashboard.phases[0].tasks[0].name, "Init repo @dev");
+
+        app.gantt_state.selected = 0;
+        app.open_rename();
+        app.rename_key(key(KeyCode::Char('!')));
+        app.rename_key(key(KeyCode::Enter));
+        assert_eq!(app.dashboard.phases[0].name, "Setup!");
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert_eq!(result, "# Phase 0: Setup!\n\n### [ ] T1: Init repo @dev\n");
+
+        // Not offered in the Gantt bar view
+        app.toggle_view();
+        app.open_rename();
+        assert!(app.rename.is_none());
+    }
+
+    #[test]
+    fn note_editor_appends_timestamped_note() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks_file, "# Phase 0: Setup\n\n### [ ] T1: Init\n").unwrap();
+        let content = std::fs::read_to_string(&tasks_file).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(&content).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        // Phase header selected: no task to annotate
+        app.open_note_editor();
+        assert!(app.note_editor.is_none());
+
+        app.gantt_state.selected = 1;
+        app.open_note_editor();
+        for c in "flaky".chars() {
+            app.note_editor_key(key(KeyCode::Char(c)));
+        }
+        app.note_editor_key(key(KeyCode::Enter));
+        app.note_editor_key(key(KeyCode::Char('!')));
+        app.note_editor_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+
+        assert!(app.note_editor.is_none());
+        let notes = &app.dashboard.phases[0].tasks[0].notes;
+        assert_eq!(notes.len(), 2);
+        assert!(notes[0].ends_with(": flaky"), "{notes:?}");
+        assert_eq!(notes[1], "!");
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(
+            result.contains("### [ ] T1: Init\n  <!-- note "),
+            "{result}"
+        );
+    }
+
+    #[test]
+    fn agent_picker_reassigns_selected_task() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 0: A\n### [ ] T1: a\n- **담당**: @writer\n### [ ] T2: b\n- **담당**: @backend\n",
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&tasks_file).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(&content).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        // Phase header selected: nothing to assign
+        app.open_agent_picker();
+        assert!(app.agent_picker.is_none());
+
+        app.gantt_state.selected = 1;
+        app.open_agent_picker();
+        let picker = app.agent_picker.as_ref().unwrap();
+        assert_eq!(picker.selected_agent(), Some("writer"));
+        app.agent_picker_move_up();
+        app.confirm_agent_picker();
+
+        assert!(app.agent_picker.is_none());
+        assert_eq!(
+            app.dashboard.phases[0].tasks[0].agent.as_deref(),
+            Some("backend")
+        );
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.contains("### [ ] T1: a\n- **담당**: @backend\n"));
+    }
+
+    #[test]
+    fn auto_sync_writes_statuses_of_new_events_to_tasks_md() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 1: Core\n\n### [ ] P1-T1: Parser\n\n### [ ] P1-T2: Writer\n\n### [ ] P1-T3: Old\n",
+        )
+        .unwrap();
+        let dashboard = DashboardState::from_tasks_file(&tasks_file).unwrap();
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_config(Config::from_toml("auto_sync = true").unwrap())
+            .with_tasks_path(tasks_file.clone());
+        app.sync_since = "2026-02-08T09:00:00Z".parse().unwrap();
+
+        let hook_file = tmp.path().join("session.jsonl");
+        let mut lines = vec![
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T08:00:00Z","agent_id":"a","task_id":"P1-T3","session_id":"s"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:01:00Z","agent_id":"b","task_id":"P1-T2","session_id":"s"}"#,
+        ];
+        std::fs::write(&hook_file, lines.join("\n")).unwrap();
+        app.handle_file_change(&FileChange::HookEventModified(hook_file.clone()));
+        let written = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(written.contains("### [InProgress] P1-T1:"), "{written}");
+        assert!(written.contains("### [InProgress] P1-T2:"), "{written}");
+        // Finished before the dashboard started watching
+        assert!(written.contains("### [ ] P1-T3:"), "{written}");
+
+        lines.extend([
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:10:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:11:00Z","agent_id":"b","task_id":"P1-T2","session_id":"s","error_message":"boom"}"#,
+        ]);
+        std::fs::write(&hook_file, lines.join("\n")).unwrap();
+        app.handle_file_change(&FileChange::HookEventModified(hook_file));
+        let written = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(written.contains("### [x] P1-T1:"), "{written}");
+        assert!(written.contains("### [Failed] P1-T2:"), "{written}");
+        assert_eq!(app.dashboard.phases[0].tasks[1].status, TaskStatus::Failed);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Synced P1-T1 completed, P1-T2 failed")
+        );
+    }
+
+    #[test]
+    fn reset_failed_task_writes_pending_and_dismisses_errors() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            include_str!("../tests/fixtures/sample_tasks.md"),
+        )
+        .unwrap();
+        let mut dashboard = DashboardState::from_tasks_file(&tasks_file).unwrap();
+        let hooks = include_str!("../tests/fixtures/sample_hooks/error_events.jsonl");
+        dashboard.update_from_events(&crate::data::hook_parser::parse_hook_events(hooks).events);
+        let config = Config::from_toml("reset_clears_errors = true").unwrap();
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_config(config)
+            .with_tasks_path(tasks_file.clone());
+
+        // Completed task: untouched
+        app.gantt_state.selected = 1;
+        app.reset_failed_task();
+        assert_eq!(
+            app.dashboard.phases[0].tasks[0].status,
+            TaskStatus::Completed
+        );
+
+        app.gantt_state.selected = 6;
+        app.reset_failed_task();
+        assert_eq!(app.dashboard.phases[1].tasks[2].status, TaskStatus::Pending);
+        assert!(app.dashboard.recent_errors.is_empty());
+        let written = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(written.contains("### [ ] P1-R3-T1:"), "{written}");
+    }
+
+    #[test]
+    fn external_change_to_the_target_task_opens_a_conflict() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        let original = include_str!("../tests/fixtures/sample_tasks.md");
+        std::fs::write(&tasks_file, original).unwrap();
+        let dashboard = DashboardState::from_tasks_file(&tasks_file).unwrap();
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_tasks_path(tasks_file.clone());
+        app.gantt_state.total_items = 11;
+        app.gantt_state.selected = 6; // P1-R3-T1, Failed
+
+        // The orchestrator picks the task up before the dashboard reloads
+        let external = original.replace("[Failed] P1-R3-T1", "[InProgress] P1-R3-T1");
+        assert_ne!(external, original);
+        std::fs::write(&tasks_file, &external).unwrap();
+
+        app.reset_failed_task();
+        let conflict = app.conflict.as_ref().expect("conflict");
+        assert_eq!(conflict.target, "P1-R3-T1");
+        assert!(conflict.ours[0].contains("[Failed]"));
+        assert!(conflict.theirs[0].contains("[InProgress]"));
+        assert_eq!(std::fs::read_to_string(&tasks_file).unwrap(), external);
+        assert!(!app.history.can_undo());
+
+        app.close_conflict();
+        assert!(app.conflict.is_none());
+        assert_eq!(
+            app.dashboard.phases[1].tasks[2].status,
+            TaskStatus::InProgress
+        );
+
+        // A change elsewhere in the file does not block the edit
+        let (pi, ti) = app.selected_task().unwrap();
+        assert_eq!(app.dashboard.phases[pi].tasks[ti].id, "P1-R3-T1");
+        std::fs::write(&tasks_file, format!("{external}\n<!-- touched -->\n")).unwrap();
+        app.open_note_editor();
+        for c in "hi".chars() {
+            app.note_editor_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        app.note_editor_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert!(app.conflict.is_none());
+        assert!(app.note_editor.is_none());
+        assert!(std::fs::read_to_string(&tasks_file)
+            .unwrap()
+            .contains("<!-- touched -->"));
+    }
+
+    #[test]
+    fn track_error_as_task_appends_to_the_failed_tasks_phase() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            include_str!("../tests/fixtures/sample_tasks.md"),
+        )
+        .unwrap();
+        let mut dashboard = DashboardState::from_tasks_file(&tasks_file).unwrap();
+        let hooks = include_str!("../tests/fixtures/sample_hooks/error_events.jsonl");
+        dashboard.update_from_events(&crate::data::hook_parser::parse_hook_events(hooks).events);
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_tasks_path(tasks_file.clone());
+        app.gantt_state.total_items = 11;
+
+        app.gantt_state.selected = 6;
+        app.open_retry_modal();
+        assert_eq!(
+            app.retry_error().map(|e| e.message.as_str()),
+            Some("connection refused: localhost:5432")
+        );
+        let tasks_before = app.dashboard.phases[1].tasks.len();
+        app.track_error_as_task();
+
+        assert!(!app.show_retry_modal);
+        let added = app.dashboard.phases[1].tasks.last().unwrap();
+        assert_eq!(app.dashboard.phases[1].tasks.len(), tasks_before + 1);
+        assert_eq!(added.name, "connection refused: localhost:5432");
+        assert_eq!(added.status, TaskStatus::Pending);
+        assert!(added
+            .body
+            .contains("from @backend-specialist-2 on P1-R3-T1"));
+        assert_eq!(
+            app.status_message,
+            Some(format!("Added {} from error", added.id))
+        );
+        assert!(app.history.can_undo());
+    }
+
+    #[test]
+    fn error_task_name_is_first_line_shortened() {
+        assert_eq!(error_task_name("boom\nstack trace"), "boom");
+        let long = "x".repeat(80);
+        let name = error_task_name(&long);
+        assert_eq!(name.chars().count(), 60);
+        assert!(name.ends_with('\u{2026}'));
+    }
+
+    #[test]
+    fn open_in_editor_targets_task_line() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let dashboard = DashboardState::from_tasks_content(input).unwrap();
+        let mut app = App::new().with_dashboard(dashboard);
+        app.gantt_state.selected = 1;
+        app.open_in_editor();
+        assert!(app.editor_request.is_none()); // no tasks file
+
+        app = app.with_tasks_path(PathBuf::from("TASKS.md"));
+        app.gantt_state.selected = 0; // phase header
+        app.open_in_editor();
+        assert!(app.editor_request.is_none());
+        app.gantt_state.selected = 1;
+        app.open_in_editor();
+        assert_eq!(
+            app.editor_request,
+            Some(EditorRequest {
+                path: PathBuf::from("TASKS.md"),
+                line: Some(12),
+            })
+        );
+    }
+
+    #[test]
+    fn pager_gets_task_detail_or_agent_history() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let input = include_str!("../tests/fixtures/sample_hooks/agent_events.jsonl");
+        std::fs::write(tmp.path().join("events.jsonl"), input).unwrap();
+        let tasks = include_str!("../tests/fixtures/sample_tasks.md");
+        let mut dashboard = DashboardState::from_tasks_content(tasks).unwrap();
+        dashboard.update_from_events(&crate::data::hook_parser::parse_hook_events(input).events);
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_event_dirs(vec![tmp.path().to_path_buf()]);
+
+        app.gantt_state.selected = 1;
+        app.open_pager();
+        let task = &app.dashboard.phases[0].tasks[0];
+        let text = app.pager_request.take().unwrap();
+        assert!(text.contains(&task.id), "got: {text}");
+
+        app.focused = FocusedPane::Agents;
+        app.open_pager();
+        let agent = &app.sorted_agent_ids()[0];
+        let text = app.pager_request.take().unwrap();
+        assert!(
+            text.starts_with(&format!("Event history of {agent} (")),
+            "got: {text}"
+        );
+
+        app.event_dirs.clear();
+        app.open_pager();
+        assert!(app.pager_request.is_none());
+        assert!(app
+            .status_message
+            .unwrap()
+            .starts_with("No events recorded"));
     }
 
     #[test]
-    fn app_navigation() {
-        let input = include_str!("../tests/fixtures/sample_tasks.md");
-        let dashboard = DashboardState::from_tasks_content(input).unwrap();
-        let mut app = App::new().with_dashboard(dashboard);
-        app.gantt_state.total_items = 11;
+    fn drilldown_cursor_opens_tool_file() {
+        let input = include_str!("../tests/fixtures/tool_file_events.jsonl");
+        let result = crate::data::hook_parser::parse_hook_events(input);
+        let mut dashboard = DashboardState::default();
+        dashboard.update_from_events(&result.events);
+        let mut app = App::new()
+            .with_dashboard(dashboard)
+            .with_tasks_path(PathBuf::from("TASKS.md"));
+        app.focused = FocusedPane::Agents;
+        app.toggle_agent_drilldown();
 
-        app.move_down();
-        assert_eq!(app.gantt_state.selected, 1);
-        assert_eq!(app.selected_task(), Some((0, 0)));
+        app.open_in_editor();
+        assert_eq!(
+            app.editor_request.take().map(|r| r.path),
+            Some(PathBuf::from("src/lib.rs"))
+        );
+        app.agent_move_down();
+        app.agent_move_down(); // clamped at the oldest call
+        assert_eq!(app.drilldown_tool, 1);
+        app.open_in_editor();
+        assert_eq!(
+            app.editor_request.take().map(|r| r.path),
+            Some(PathBuf::from("src/main.rs"))
+        );
+        assert_eq!(app.selected_agent, 0);
+    }
 
-        app.move_up();
-        assert_eq!(app.gantt_state.selected, 0);
-        assert!(app.selected_task().is_none()); // phase header
+    #[test]
+    fn bulk_status_needs_confirmation_then_writes() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        let original = "# Phase 0: A\n\n### [x] T1: a\n\n### [ ] T2: b\n\n### [InProgress] T3: c\n";
+        std::fs::write(&tasks_file, original).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(original).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        app.gantt_state.selected = 1; // a task, not the phase
+        app.open_bulk_status();
+        assert!(app.bulk_status.is_none());
+
+        app.gantt_state.selected = 0;
+        app.open_bulk_status();
+        app.bulk_status_move_down(); // Block remaining
+        app.confirm_bulk_status(); // not confirming yet: no-op
+        assert!(app.bulk_status.is_some());
+        app.bulk_status_choose();
+        app.confirm_bulk_status();
+        assert!(app.bulk_status.is_none());
+        // Blocking asks for a reason before writing
+        assert_eq!(
+            app.block_reason.as_ref().unwrap().task_ids,
+            vec!["T2", "T3"]
+        );
+        app.block_reason_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.block_reason.is_none());
+        let statuses: Vec<TaskStatus> = app.dashboard.phases[0]
+            .tasks
+            .iter()
+            .map(|t| t.status.clone())
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                TaskStatus::Completed,
+                TaskStatus::Blocked,
+                TaskStatus::Blocked
+            ]
+        );
     }
 
     #[test]
-    fn app_with_dashboard() {
-        let input = include_str!("../tests/fixtures/sample_tasks.md");
-        let dashboard = DashboardState::from_tasks_content(input).unwrap();
-        let app = App::new().with_dashboard(dashboard);
-        assert_eq!(app.dashboard.total_tasks, 8);
+    fn block_prompt_writes_reason_and_prefills_it() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        let original = "# Phase 0: A\n\n### [x] T1: a\n\n### [ ] T2: b\n";
+        std::fs::write(&tasks_file, original).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(original).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        // Completed tasks are not blocked
+        app.gantt_state.selected = 1;
+        app.open_block_prompt();
+        assert!(app.block_reason.is_none());
+
+        app.gantt_state.selected = 2;
+        app.open_block_prompt();
+        for c in "API keys".chars() {
+            app.block_reason_key(key(KeyCode::Char(c)));
+        }
+        app.block_reason_key(key(KeyCode::Enter));
+        assert!(app.block_reason.is_none());
+        let task = &app.dashboard.phases[0].tasks[1];
+        assert_eq!(task.status, TaskStatus::Blocked);
+        assert_eq!(task.blocked_reason.as_deref(), Some("API keys"));
+        let result = std::fs::read_to_string(&tasks_file).unwrap();
+        assert!(result.ends_with("### [Blocked] T2: b\n- **blocked**: API keys\n"));
+
+        app.open_block_prompt();
+        assert_eq!(app.block_reason.as_ref().unwrap().input.text(), "API keys");
     }
 
     #[test]
-    fn handle_file_change_tasks() {
+    fn undo_and_redo_dashboard_edits() {
         let tmp = tempfile::TempDir::new().unwrap();
         let tasks_file = tmp.path().join("TASKS.md");
-        std::fs::write(
-            &tasks_file,
-            "# Phase 0: Setup\n\n### [x] P0-T0.1: Init project\n",
+        let original = "# Phase 0: A\n\n### [Failed] T1: a\n\n### [ ] T2: b\n";
+        std::fs::write(&tasks_file, original).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(original).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        app.undo();
+        assert_eq!(app.status_message.as_deref(), Some("Nothing to undo"));
+
+        app.gantt_state.selected = 1;
+        app.reset_failed_task();
+        app.move_task(1);
+        assert_eq!(app.dashboard.phases[0].tasks[1].id, "T1");
+
+        // The phase is renamed outside the dashboard; undoing the move keeps that
+        let external = std::fs::read_to_string(&tasks_file)
+            .unwrap()
+            .replace("Phase 0: A", "Phase 0: Alpha");
+        std::fs::write(&tasks_file, &external).unwrap();
+        app.undo();
+        assert_eq!(app.status_message.as_deref(), Some("Undid move T1"));
+        assert_eq!(
+            std::fs::read_to_string(&tasks_file).unwrap(),
+            "# Phase 0: Alpha\n\n### [ ] T1: a\n\n### [ ] T2: b\n"
+        );
+        assert_eq!(app.dashboard.phases[0].tasks[0].id, "T1");
+
+        app.undo();
+        assert_eq!(app.dashboard.phases[0].tasks[0].status, TaskStatus::Failed);
+        app.redo();
+        assert_eq!(app.status_message.as_deref(), Some("Redid reset T1"));
+        assert_eq!(app.dashboard.phases[0].tasks[0].status, TaskStatus::Pending);
+    }
+
+    #[test]
+    fn task_diff_shows_commits_naming_the_task() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(input).unwrap())
+            .with_tasks_path(PathBuf::from("TASKS.md"));
+        app.dashboard.commits =
+            git_log::parse_log("aaa\u{1f}P0-T0.1: setup\nbbb\u{1f}docs\nccc\u{1f}P0-T0.1 fixup\n");
+
+        app.gantt_state.selected = 1;
+        app.open_task_diff();
+        let request = app.shell_request.take().unwrap();
+        assert_eq!(request.label, "P0-T0.1 diff");
+        assert_eq!(request.command, "git -C '.' show aaa ccc");
+
+        app.gantt_state.selected = 2;
+        app.open_task_diff();
+        assert!(app.shell_request.is_none());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No commits mention P0-T0.2")
+        );
+    }
+
+    #[test]
+    fn custom_action_expands_selection() {
+        let input = include_str!("../tests/fixtures/sample_tasks.md");
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(input).unwrap())
+            .with_tasks_path(PathBuf::from("TASKS.md"));
+        app.config = Config::from_toml(
+            "[actions]\nF5 = \"cargo test {task_id}\"\nF6 = \"edit {file} # {agent}\"\n",
         )
         .unwrap();
 
+        // Phase header: no task to test
+        app.run_custom_action(5);
+        assert!(app.shell_request.is_none());
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("F5: no {task_id} for the selection")
+        );
+
+        app.gantt_state.selected = 1;
+        app.run_custom_action(5);
+        let request = app.shell_request.take().unwrap();
+        assert_eq!(request.label, "F5");
+        assert_eq!(request.command, "cargo test 'P0-T0.1'");
+
+        app.run_custom_action(6);
+        assert_eq!(
+            app.shell_request.take().unwrap().command,
+            "edit 'TASKS.md' # 'backend-specialist'"
+        );
+
+        // Unbound keys do nothing
+        app.run_custom_action(9);
+        assert!(app.shell_request.is_none());
+    }
+
+    #[test]
+    fn acknowledge_errors_of_selected_agent() {
         let mut app = App::new();
-        assert_eq!(app.dashboard.total_tasks, 0);
+        let input = include_str!("../tests/fixtures/sample_hooks/error_events.jsonl");
+        let result = crate::data::hook_parser::parse_hook_events(input);
+        app.dashboard.update_from_events(&result.events);
+        app.focused = FocusedPane::Agents;
 
-        let change = FileChange::TasksModified(tasks_file);
-        app.handle_file_change(&change);
-        assert_eq!(app.dashboard.total_tasks, 1);
+        app.acknowledge_errors();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Acknowledged 2 errors from backend-specialist-2")
+        );
+        assert_eq!(app.dashboard.total_open_errors(chrono::Utc::now()), 0);
+        assert_eq!(app.dashboard.recent_errors.len(), 2);
+
+        app.acknowledge_errors();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No open errors for backend-specialist-2")
+        );
     }
 
     #[test]
-    fn open_retry_modal_on_failed_task() {
+    fn reminder_on_the_selected_task_fires_once_due() {
         let input = include_str!("../tests/fixtures/sample_tasks.md");
         let dashboard = DashboardState::from_tasks_content(input).unwrap();
         let mut app = App::new().with_dashboard(dashboard);
+        app.config = Config::from_toml("reminder_minutes = 5").unwrap();
         app.gantt_state.total_items = 11;
+        app.move_down();
+        let (pi, ti) = app.selected_task().unwrap();
+        let task_id = app.dashboard.phases[pi].tasks[ti].id.clone();
 
-        // Navigate to a Failed task: P1-R3-T1 (Phase 1, task index 2)
-        // Phase 0 header(0) + 2 tasks(1,2) + Phase 1 header(3) + task(4) + task(5) + task(6=Failed)
-        app.gantt_state.selected = 6;
-        app.open_retry_modal();
-        assert!(app.show_retry_modal);
-        assert!(app.retry_target.is_some());
-        let target = app.retry_target.as_ref().unwrap();
-        assert_eq!(target.task_id, "P1-R3-T1");
+        app.toggle_reminder();
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .starts_with("Reminder in 5 min: check"));
+        let due = app.reminders.get(&task_id).unwrap().due;
+
+        app.fire_reminders(Instant::now());
+        assert!(!app.bell);
+        app.fire_reminders(due);
+        assert!(app.bell);
+        assert!(app.status_message.as_deref().unwrap().contains(&task_id));
+        assert!(app.reminders.is_empty());
+
+        // Setting it twice cancels it
+        app.toggle_reminder();
+        app.toggle_reminder();
+        assert_eq!(app.status_message.as_deref(), Some("Cancelled reminder"));
+        assert!(app.reminders.is_empty());
     }
 
     #[test]
-    fn open_retry_modal_ignored_for_completed_task() {
-        let input = include_str!("../tests/fixtures/sample_tasks.md");
-        let dashboard = DashboardState::from_tasks_content(input).unwrap();
-        let mut app = App::new().with_dashboard(dashboard);
-        app.gantt_state.total_items = 11;
+    fn mute_toggles_on_the_drilled_down_agent() {
+        let mut app = App::new();
+        app.config = Config::from_toml("mute_minutes = 5").unwrap();
+        let input = include_str!("../tests/fixtures/sample_hooks/error_events.jsonl");
+        let result = crate::data::hook_parser::parse_hook_events(input);
+        app.dashboard.update_from_events(&result.events);
+
+        // Only from the drill-down
+        app.toggle_mute_agent();
+        assert!(app.dashboard.muted_agents.is_empty());
+
+        app.agent_drilldown = Some("backend-specialist-2".to_string());
+        app.toggle_mute_agent();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Muted backend-specialist-2 for 5 min")
+        );
+        assert_eq!(app.dashboard.total_open_errors(chrono::Utc::now()), 0);
+
+        app.toggle_mute_agent();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Unmuted backend-specialist-2")
+        );
+        assert_eq!(app.dashboard.total_open_errors(chrono::Utc::now()), 2);
+    }
+
+    #[test]
+    fn signals_are_written_for_the_selected_agent() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut app = App::new().with_commands_dir(tmp.path().to_path_buf());
+        let input = include_str!("../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = crate::data::hook_parser::parse_hook_events(input);
+        app.dashboard.update_from_events(&result.events);
+        app.focused = FocusedPane::Agents;
+        let agent_id = app.sorted_agent_ids()[0].clone();
+
+        // Pause goes out right away
+        app.open_signal_picker();
+        app.signal_picker_choose();
+        assert!(app.signal_picker.is_none());
+        assert_eq!(
+            app.status_message,
+            Some(format!("Sent pause to {agent_id}"))
+        );
+
+        // Abort waits for y
+        app.open_signal_picker();
+        app.signal_picker_move_down();
+        app.signal_picker_move_down();
+        app.signal_picker_choose();
+        assert!(app.signal_picker.as_ref().is_some_and(|p| p.confirming));
+        app.confirm_signal();
+        assert!(app.signal_picker.is_none());
+
+        let content = std::fs::read_to_string(commands::commands_path(tmp.path())).unwrap();
+        let sent: Vec<ControlCommand> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].signal, Signal::Pause);
+        assert_eq!(sent[1].signal, Signal::Abort);
+        assert!(sent.iter().all(|c| c.agent_id == agent_id));
+    }
+
+    #[test]
+    fn signals_reach_every_instance_of_a_folded_agent() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut app = App::new().with_commands_dir(tmp.path().to_path_buf());
+        app.dashboard.agent_identity =
+            Some(crate::data::identity::AgentIdentity::new(r"^(.+)-\d+$").unwrap());
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        app.dashboard.load_hook_events(&hooks_dir).unwrap();
+        app.focused = FocusedPane::Agents;
+        app.selected_agent = app
+            .sorted_agent_ids()
+            .iter()
+            .position(|id| id == "backend-specialist")
+            .unwrap();
+
+        app.open_signal_picker();
+        app.signal_picker_choose();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Sent pause to backend-specialist (2 instances)")
+        );
+        let content = std::fs::read_to_string(commands::commands_path(tmp.path())).unwrap();
+        let ids: Vec<String> = content
+            .lines()
+            .map(|l| serde_json::from_str::<ControlCommand>(l).unwrap().agent_id)
+            .collect();
+        assert_eq!(ids, ["backend-specialist-1", "backend-specialist-2"]);
+    }
+
+    #[test]
+    fn archive_completed_phase_and_undo() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        let original =
+            "# Phase 0: A\n\n### [x] T1: a\n\n# Phase 1: B\n\n### [x] T2: b\n\n### [ ] T3: c\n";
+        std::fs::write(&tasks_file, original).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(original).unwrap())
+            .with_tasks_path(tasks_file.clone());
+
+        app.gantt_state.selected = 2; // P1: not complete
+        app.archive_phase();
+        assert!(!app.history.can_undo());
+
+        app.gantt_state.collapsed.insert(1);
+        app.gantt_state.selected = 0;
+        app.archive_phase();
+        assert_eq!(app.dashboard.phases.len(), 1);
+        assert_eq!(app.dashboard.phases[0].id, "P1");
+        assert!(app.gantt_state.collapsed.contains(&0));
+        let archive = tmp.path().join("TASKS.archive.md");
+        assert!(std::fs::read_to_string(&archive)
+            .unwrap()
+            .contains("### [x] T1: a"));
+
+        app.undo();
+        assert!(!app.history.can_undo());
+        assert!(!archive.exists());
+        assert_eq!(std::fs::read_to_string(&tasks_file).unwrap(), original);
+        assert_eq!(app.dashboard.phases.len(), 2);
+        assert_eq!(app.gantt_state.selected, 0);
+        assert!(app.gantt_state.collapsed.contains(&1));
+    }
+
+    #[test]
+    fn move_task_reorders_and_follows_selection() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            "# Phase 0: A\n\n### [ ] T1: a\n\n### [ ] T2: b\n\n# Phase 1: B\n\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        let content = std::fs::read_to_string(&tasks_file).unwrap();
+        let mut app = App::new()
+            .with_dashboard(DashboardState::from_tasks_content(&content).unwrap())
+            .with_tasks_path(tasks_file.clone());
 
-        // Navigate to a Completed task: P0-T0.1 (index 1)
         app.gantt_state.selected = 1;
-        app.open_retry_modal();
-        assert!(!app.show_retry_modal);
-        assert!(app.retry_target.is_none());
+        app.move_task(-1); // already first: no-op
+        app.move_task(1);
+        let ids: Vec<&str> = app.dashboard.phases[0]
+            .tasks
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["T2", "T1"]);
+        assert_eq!(app.gantt_state.selected, 2);
+        app.move_task(1); // last in phase: does not cross into P1
+        assert_eq!(app.dashboard.phases[1].tasks.len(), 1);
+
+        app.open_phase_picker();
+        assert_eq!(app.phase_picker.as_ref().unwrap().task_id, "T1");
+        app.confirm_phase_picker();
+        assert!(app.phase_picker.is_none());
+        assert_eq!(app.dashboard.phases[0].tasks.len(), 1);
+        assert_eq!(app.dashboard.phases[1].tasks[1].id, "T1");
+        // Selection follows the task: P0, T2, P1, T3, T1
+        assert_eq!(app.gantt_state.selected, 4);
     }
 
     #[test]
-    fn cancel_retry_closes_modal() {
-        let mut app = App::new();
-        app.show_retry_modal = true;
-        app.retry_target = Some(super::RetryTarget {
-            task_id: "T1".to_string(),
-            task_name: "Test".to_string(),
-            retryable: true,
-        });
-        app.cancel_retry();
-        assert!(!app.show_retry_modal);
-        assert!(app.retry_target.is_none());
+    fn task_form_needs_tasks_file() {
+        let mut app = App::new().with_dashboard(
+            DashboardState::from_tasks_content("# Phase 0: A\n### [ ] T1: a\n").unwrap(),
+        );
+        app.open_task_form();
+        assert!(app.task_form.is_none());
     }
 
     #[test]
@@ -421,6 +3533,214 @@ mod tests {
         assert!(result.contains("[Failed] T1:"));
     }
 
+    #[test]
+    fn minimap_keys_move_and_jump() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let content = include_str!("../tests/fixtures/sample_tasks.md");
+        let dashboard = DashboardState::from_tasks_content(content).unwrap();
+        let mut app = App::new().with_dashboard(dashboard);
+        app.gantt_state.minimap_width = app.dashboard.total_tasks;
+
+        app.open_minimap();
+        assert_eq!(app.gantt_state.minimap_cursor, Some(0));
+        app.minimap_key(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
+        app.minimap_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.gantt_state.minimap_cursor.is_none());
+        assert_eq!(app.selected_task(), Some((0, 1)));
+    }
+
+    #[test]
+    fn log_viewer_opens_newest_file_and_closes() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("events.jsonl"), "line one\nline two\n").unwrap();
+        let mut app = App::new().with_event_dirs(vec![dir.path().to_path_buf()]);
+        app.open_log_viewer();
+        let viewer = app.log_viewer.as_ref().expect("viewer should open");
+        assert_eq!(viewer.lines.len(), 2);
+        assert_eq!(viewer.error_lines.len(), 2);
+
+        app.log_viewer_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(app.log_viewer.is_none());
+    }
+
+    #[test]
+    fn log_viewer_without_files_stays_closed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new().with_event_dirs(vec![dir.path().to_path_buf()]);
+        app.open_log_viewer();
+        assert!(app.log_viewer.is_none());
+    }
+
+    #[test]
+    fn layout_toggle_and_overlay_visibility() {
+        let mut app = App::new();
+        assert_eq!(app.layout_mode, LayoutMode::Split);
+        app.toggle_layout();
+        assert_eq!(app.layout_mode, LayoutMode::Stacked);
+        assert!(!app.detail_overlay_visible());
+        app.toggle_focus();
+        assert_eq!(app.focused, FocusedPane::Detail);
+        assert!(app.detail_overlay_visible());
+    }
+
+    #[test]
+    fn config_sets_initial_layout() {
+        let config = Config::from_toml("layout = \"stacked\"").unwrap();
+        let app = App::new().with_config(config);
+        assert_eq!(app.layout_mode, LayoutMode::Stacked);
+    }
+
+    #[test]
+    fn gauge_follows_config_and_toggles() {
+        let config = Config::from_toml("progress_gauge = true").unwrap();
+        let mut app = App::new().with_config(config);
+        assert!(app.show_gauge);
+        app.toggle_gauge();
+        assert!(!app.show_gauge);
+    }
+
+    #[test]
+    fn tick_advances_spinner_unless_watcher_degraded() {
+        let mut app = App::new();
+        app.on_tick();
+        app.on_tick();
+        assert_eq!(app.spinner_frame, 2);
+
+        app.watcher_degraded = true;
+        app.on_tick();
+        assert_eq!(app.spinner_frame, 2);
+    }
+
+    #[test]
+    fn ticks_ask_for_a_redraw_only_while_a_spinner_shows() {
+        let mut app = App::new();
+        assert!(!app.on_tick());
+
+        let events = crate::data::hook_parser::parse_hook_events(&format!(
+            r#"{{"event_type":"agent_start","agent_id":"a","task_id":"T1","session_id":"s","timestamp":"{}"}}"#,
+            chrono::Utc::now().to_rfc3339()
+        ))
+        .events;
+        app.dashboard.update_from_events(&events);
+        assert!(app.on_tick());
+
+        app.watcher_degraded = true;
+        assert!(!app.on_tick());
+    }
+
+    #[test]
+    fn config_changes_apply_live_and_bad_ones_are_refused() {
+        let home = tempfile::TempDir::new().unwrap();
+        let claude = home.path().join(".claude");
+        std::fs::create_dir_all(&claude).unwrap();
+        let board = claude.join(crate::config::CONFIG_FILE);
+        let mut app = App::new().with_config_reload(home.path().to_path_buf(), None);
+
+        std::fs::write(
+            &board,
+            "theme = \"high-contrast\"\n[limits]\nmax_errors = 3\n",
+        )
+        .unwrap();
+        app.handle_file_change(&FileChange::ConfigModified(board.clone()));
+        assert_eq!(app.config.theme, ThemeName::HighContrast);
+        assert_eq!(app.dashboard.limits.max_errors, 3);
+        assert_eq!(app.status_message.as_deref(), Some("Config reloaded"));
+
+        std::fs::write(&board, "theme = 42\n").unwrap();
+        app.handle_file_change(&FileChange::ConfigModified(board));
+        assert_eq!(app.config.theme, ThemeName::HighContrast);
+        assert!(app
+            .status_message
+            .as_deref()
+            .is_some_and(|m| m.starts_with("Config not reloaded")));
+    }
+
+    #[test]
+    fn load_messages_update_progress_and_state() {
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let mut loaded = DashboardState::default();
+        loaded.load_hook_events(&hooks_dir).unwrap();
+
+        let mut app = App::new();
+        app.handle_load_message(LoadMessage::Progress(0.42));
+        assert_eq!(app.history_progress, Some(0.42));
+
+        app.handle_load_message(LoadMessage::Finished(Box::new(loaded), Tail::default()));
+        assert!(app.history_progress.is_none());
+        assert!(!app.dashboard.agents.is_empty());
+        // Fixture hooks contain several sessions, so the picker opens
+        assert!(app.show_session_picker);
+    }
+
+    #[test]
+    fn view_is_restored_on_the_next_launch() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks_file = tmp.path().join("TASKS.md");
+        std::fs::write(
+            &tasks_file,
+            include_str!("../tests/fixtures/sample_tasks.md"),
+        )
+        .unwrap();
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let launch = || {
+            let mut app = App::new()
+                .with_dashboard(DashboardState::from_tasks_file(&tasks_file).unwrap())
+                .with_tasks_path(tasks_file.clone())
+                .with_event_dirs(vec![hooks_dir.clone()])
+                .with_commands_dir(tmp.path().to_path_buf());
+            app.load_view();
+            let mut loaded = DashboardState::default();
+            loaded.load_hook_events(&hooks_dir).unwrap();
+            app.handle_load_message(LoadMessage::Finished(Box::new(loaded), Tail::default()));
+            app
+        };
+        let unsaved = |app: &App| ViewState {
+            saved_at: None,
+            ..app.view_state()
+        };
+
+        let mut app = launch();
+        let session = app.dashboard.sorted_sessions()[0].session_id.clone();
+        app.apply_session_filter(Some(session));
+        app.gantt_state.toggle_collapse(1);
+        app.gantt_state.selected = 2;
+        app.selected_agent = app.dashboard.agents.len() - 1;
+        app.layout_mode = LayoutMode::Stacked;
+        app.focused = FocusedPane::Agents;
+        let agent = app.sorted_agent_ids()[app.selected_agent].clone();
+        app.dashboard.acknowledge_agent_errors(&agent);
+        app.save_view().unwrap();
+        let saved = unsaved(&app);
+        assert_eq!(saved.selected.as_deref(), Some("P0-T0.2"));
+        assert_eq!(saved.collapsed, ["P1"]);
+
+        let app = launch();
+        assert_eq!(unsaved(&app), saved);
+        assert!(!app.show_session_picker);
+    }
+
+    #[test]
+    fn pushed_events_and_lost_sources_update_the_app() {
+        let mut app = App::new();
+        let events = crate::data::hook_parser::parse_hook_events(
+            r#"{"event_type":"agent_start","agent_id":"main","task_id":"T1","session_id":"s1","timestamp":"2026-02-08T00:00:00Z"}"#,
+        )
+        .events;
+        app.handle_update(Update::Events(events));
+        assert!(app.dashboard.agents.contains_key("main"));
+
+        app.handle_update(Update::Lost("stdin"));
+        assert!(!app.watcher_degraded);
+        assert_eq!(app.status_message.as_deref(), Some("stdin source closed"));
+        app.handle_update(Update::Lost("files"));
+        assert!(app.watcher_degraded);
+    }
+
     #[test]
     fn handle_file_change_hook() {
         let tmp = tempfile::TempDir::new().unwrap();
@@ -438,4 +3758,111 @@ mod tests {
         app.handle_file_change(&change);
         assert!(!app.dashboard.agents.is_empty());
     }
+
+    #[test]
+    fn hook_changes_apply_only_appended_events() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let line = |agent: &str, minute: u32| {
+            format!(
+                r#"{{"event_type":"tool_start","timestamp":"2026-02-08T10:{minute:02}:00Z","agent_id":"{agent}","task_id":"T1","session_id":"s","tool_name":"Bash"}}"#
+            ) + "\n"
+        };
+        let first = tmp.path().join("a.jsonl");
+        let second = tmp.path().join("b.jsonl");
+        std::fs::write(&first, line("a", 1) + &line("a", 2)).unwrap();
+        std::fs::write(&second, line("b", 1)).unwrap();
+        let mut app = App::new().with_event_dirs(vec![tmp.path().to_path_buf()]);
+        let count = |app: &App, agent: &str| app.dashboard.agents[agent].event_count;
+
+        app.handle_file_change(&FileChange::HookEventModified(first.clone()));
+        app.handle_file_change(&FileChange::HookEventModified(second.clone()));
+        assert_eq!((count(&app, "a"), count(&app, "b")), (2, 1));
+
+        // Only the appended line is applied, and the other file's agent stays
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&first)
+            .unwrap();
+        std::io::Write::write_all(&mut file, line("a", 3).as_bytes()).unwrap();
+        app.handle_file_change(&FileChange::HookEventModified(first.clone()));
+        assert_eq!((count(&app, "a"), count(&app, "b")), (3, 1));
+
+        // A truncated file rebuilds everything from what is on disk
+        std::fs::write(&first, line("a", 4)).unwrap();
+        app.handle_file_change(&FileChange::HookEventModified(first));
+        assert_eq!((count(&app, "a"), count(&app, "b")), (1, 1));
+        app.handle_file_change(&FileChange::HookEventModified(second));
+        assert_eq!(count(&app, "b"), 1);
+    }
+
+    #[test]
+    fn hook_changes_wait_for_the_history_load() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let line = |minute: u32| {
+            format!(
+                r#"{{"event_type":"tool_start","timestamp":"2026-02-08T10:{minute:02}:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}}"#
+            ) + "\n"
+        };
+        let file = tmp.path().join("a.jsonl");
+        std::fs::write(&file, line(1)).unwrap();
+        let mut app = App::new().with_event_dirs(vec![tmp.path().to_path_buf()]);
+        app.history_progress = Some(0.0);
+
+        // The loader read the first line; the second arrives before it hands over
+        let mut tail = Tail::default();
+        let mut loaded = DashboardState::default();
+        loaded.update_from_events(&tail.read_new(&file));
+        std::fs::write(&file, line(1) + &line(2)).unwrap();
+        app.handle_file_change(&FileChange::HookEventModified(file.clone()));
+        app.handle_file_change(&FileChange::HookEventModified(file.clone()));
+        assert!(app.dashboard.agents.is_empty());
+
+        app.handle_load_message(LoadMessage::Finished(Box::new(loaded), tail));
+        assert_eq!(app.dashboard.agents["a"].event_count, 2);
+    }
+
+    #[test]
+    fn reloads_run_on_the_loader_thread_one_at_a_time() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let line = |session: &str, agent: &str| {
+            format!(
+                r#"{{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"{agent}","task_id":"T1","session_id":"{session}"}}"#
+            ) + "\n"
+        };
+        std::fs::write(
+            tmp.path().join("a.jsonl"),
+            line("s1", "a") + &line("s2", "b"),
+        )
+        .unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut app = App::new()
+            .with_event_dirs(vec![tmp.path().to_path_buf()])
+            .with_updates(tx);
+        let mut finish = |app: &mut App| {
+            while app.history_progress.is_some() {
+                app.handle_update(rx.blocking_recv().unwrap());
+            }
+        };
+
+        // The first load offers the session picker, reloads do not
+        app.apply_session_filter(None);
+        finish(&mut app);
+        assert!(app.show_session_picker);
+        app.show_session_picker = false;
+
+        app.apply_session_filter(Some("s1".to_string()));
+        assert_eq!(app.history_progress, Some(0.0));
+        // Asked again before the first load is done: its result is dropped
+        app.apply_session_filter(Some("s2".to_string()));
+        finish(&mut app);
+        assert_eq!(app.sorted_agent_ids(), ["b"]);
+        assert!(!app.show_session_picker);
+
+        // A shrunk file reloads the same way
+        std::fs::write(tmp.path().join("a.jsonl"), line("s2", "c")).unwrap();
+        app.handle_file_change(&FileChange::HookEventModified(tmp.path().join("a.jsonl")));
+        assert!(app.history_progress.is_some());
+        finish(&mut app);
+        assert_eq!(app.sorted_agent_ids(), ["c"]);
+    }
 }
@@ -0,0 +1,259 @@
+//! Budget checks per session and per agent
+//!
+//! The `[budget]` section sets token and cost limits for the whole board,
+//! for each session and for each agent. A [`BudgetWatch`] remembers how
+//! close every scope was at the previous update; crossing into the warning
+//! band or past the limit becomes a [`Transition::Budget`], which the
+//! dashboard shows as a status message and hands to webhooks and alerts.
+//! An exhausted budget comes with the suggestion to stop what is spending.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::analysis::metrics::format_tokens;
+use crate::config::{Budget, BudgetLevel};
+use crate::data::state::DashboardState;
+use crate::data::transitions::Transition;
+
+/// What a budget limit applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BudgetScope {
+    /// Everything the board has seen
+    Total,
+    Session(String),
+    Agent(String),
+}
+
+impl fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetScope::Total => write!(f, "board"),
+            BudgetScope::Session(id) => write!(f, "session {id}"),
+            BudgetScope::Agent(id) => write!(f, "agent {id}"),
+        }
+    }
+}
+
+/// What a budget limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BudgetMetric {
+    Tokens,
+    Cost,
+}
+
+impl BudgetMetric {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BudgetMetric::Tokens => "tokens",
+            BudgetMetric::Cost => "cost",
+        }
+    }
+
+    /// `1.2M` tokens or `$4.30`
+    fn format(self, value: f64) -> String {
+        match self {
+            BudgetMetric::Tokens => format_tokens(value as u64),
+            BudgetMetric::Cost => format!("${value:.2}"),
+        }
+    }
+}
+
+/// Usage of one scope against one of its limits
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetCheck {
+    pub scope: BudgetScope,
+    pub metric: BudgetMetric,
+    pub used: f64,
+    pub limit: f64,
+    pub level: BudgetLevel,
+}
+
+impl BudgetCheck {
+    /// `$4.10 of $5.00` or `1.8M of 2.0M tokens`
+    pub fn usage(&self) -> String {
+        let suffix = match self.metric {
+            BudgetMetric::Tokens => " tokens",
+            BudgetMetric::Cost => "",
+        };
+        format!(
+            "{} of {}{suffix}",
+            self.metric.format(self.used),
+            self.metric.format(self.limit)
+        )
+    }
+
+    /// One-line description, with the hard-stop suggestion once exhausted
+    pub fn summary(&self) -> String {
+        let metric = match self.metric {
+            BudgetMetric::Tokens => "token",
+            BudgetMetric::Cost => "cost",
+        };
+        match self.level {
+            BudgetLevel::Over => format!(
+                "{} exhausted its {metric} budget ({}): {}",
+                self.scope,
+                self.usage(),
+                self.suggestion()
+            ),
+            _ => format!(
+                "{} at {:.0}% of its {metric} budget ({})",
+                self.scope,
+                self.used / self.limit * 100.0,
+                self.usage()
+            ),
+        }
+    }
+
+    /// What to stop once the budget is gone
+    pub fn suggestion(&self) -> &'static str {
+        match self.scope {
+            BudgetScope::Total => "consider stopping all agents",
+            BudgetScope::Session(_) => "consider ending the session",
+            BudgetScope::Agent(_) => "consider stopping the agent",
+        }
+    }
+}
+
+/// Every limit `budget` sets on `state`, board first, then sessions and
+/// agents by id
+pub fn checks(state: &DashboardState, budget: &Budget) -> Vec<BudgetCheck> {
+    let mut usage = vec![(
+        BudgetScope::Total,
+        state.total_tokens(),
+        state.total_cost(),
+        budget.tokens,
+        budget.cost_usd,
+    )];
+    let mut sessions: Vec<_> = state.sessions.values().collect();
+    sessions.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+    usage.extend(sessions.into_iter().map(|s| {
+        (
+            BudgetScope::Session(s.session_id.clone()),
+            s.tokens,
+            s.cost_usd,
+            budget.session_tokens,
+            budget.session_cost_usd,
+        )
+    }));
+    let mut agents: Vec<_> = state.agents.values().collect();
+    agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+    usage.extend(agents.into_iter().map(|a| {
+        (
+            BudgetScope::Agent(a.agent_id.clone()),
+            a.input_tokens + a.output_tokens,
+            a.cost_usd,
+            budget.agent_tokens,
+            budget.agent_cost_usd,
+        )
+    }));
+
+    let mut checks = Vec::new();
+    for (scope, tokens, cost, token_limit, cost_limit) in usage {
+        let limits = [
+            (
+                BudgetMetric::Tokens,
+                tokens as f64,
+                token_limit.map(|t| t as f64),
+            ),
+            (BudgetMetric::Cost, cost, cost_limit),
+        ];
+        for (metric, used, limit) in limits {
+            let Some(limit) = limit.filter(|&l| l > 0.0) else {
+                continue;
+            };
+            checks.push(BudgetCheck {
+                scope: scope.clone(),
+                metric,
+                used,
+                limit,
+                level: budget.level_for(used, Some(limit)),
+            });
+        }
+    }
+    checks
+}
+
+/// Budget levels at the previous update
+#[derive(Debug, Clone, Default)]
+pub struct BudgetWatch {
+    levels: HashMap<(BudgetScope, BudgetMetric), BudgetLevel>,
+}
+
+impl BudgetWatch {
+    pub fn capture(state: &DashboardState, budget: &Budget) -> Self {
+        let mut watch = Self::default();
+        watch.advance(state, budget);
+        watch
+    }
+
+    /// Scopes that moved into the warning band or past their limit since
+    /// the previous update. Dropping back (e.g. after a session reset) is
+    /// not news, but a later rise is again.
+    pub fn advance(&mut self, state: &DashboardState, budget: &Budget) -> Vec<Transition> {
+        let mut transitions = Vec::new();
+        let mut levels = HashMap::new();
+        for check in checks(state, budget) {
+            let key = (check.scope.clone(), check.metric);
+            let before = self.levels.get(&key).copied().unwrap_or(BudgetLevel::Ok);
+            levels.insert(key, check.level);
+            if check.level > before {
+                transitions.push(Transition::Budget(check));
+            }
+        }
+        self.levels = levels;
+        transitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn usage(agent: &str, session: &str, minute: u32, cost: f64) -> String {
+        format!(
+            r#"{{"event_type":"tool_end","timestamp":"2026-02-08T10:{minute:02}:00Z","agent_id":"{agent}","task_id":"T1","session_id":"{session}","tool_name":"Bash","usage":{{"input_tokens":1000,"output_tokens":0,"cost_usd":{cost}}}}}"#
+        )
+    }
+
+    #[test]
+    fn crossings_fire_once_per_level() {
+        let budget = Budget {
+            agent_cost_usd: Some(1.0),
+            session_tokens: Some(3000),
+            ..Budget::default()
+        };
+        let mut state = DashboardState::default();
+        let mut watch = BudgetWatch::capture(&state, &budget);
+
+        state.update_from_events(&parse_hook_events(&usage("a", "s", 1, 0.85)).events);
+        let summaries: Vec<String> = watch
+            .advance(&state, &budget)
+            .iter()
+            .map(Transition::summary)
+            .collect();
+        assert_eq!(
+            summaries,
+            ["agent a at 85% of its cost budget ($0.85 of $1.00)"]
+        );
+        assert!(watch.advance(&state, &budget).is_empty());
+
+        let more = [usage("a", "s", 2, 0.25), usage("b", "s", 3, 0.1)].join("\n");
+        state.update_from_events(&parse_hook_events(&more).events);
+        let summaries: Vec<String> = watch
+            .advance(&state, &budget)
+            .iter()
+            .map(Transition::summary)
+            .collect();
+        assert_eq!(
+            summaries,
+            [
+                "session s exhausted its token budget (3.0k of 3.0k tokens): consider ending the session",
+                "agent a exhausted its cost budget ($1.10 of $1.00): consider stopping the agent",
+            ]
+        );
+
+        // Nothing is checked without limits
+        assert!(checks(&state, &Budget::default()).is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+//! Copyable status summary
+//!
+//! `Y` renders the status bar totals and each phase's progress as plain
+//! text that reads well pasted into Slack or Markdown, e.g.
+//!
+//! ```text
+//! Board status: 15/24 done (62%), 2 in progress, 1 failed, 3 open errors
+//! - P1 Setup: 5/5 done
+//! - P2 Core: 6/9 done, 1 failed (P2-T4 — type error)
+//! ```
+//!
+//! and puts it on the clipboard: through the platform's clipboard tool when
+//! one works, otherwise with an OSC 52 escape the terminal handles (also
+//! over SSH).
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use chrono::{DateTime, Utc};
+
+use crate::alerts::tmux_passthrough;
+use crate::analysis::metrics::format_tokens;
+use crate::analysis::rules::ErrorCategory;
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus};
+use crate::web::server::base64;
+
+/// Clipboard tools tried in order, with their arguments
+const TOOLS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip", &[]),
+];
+
+/// Longest error excerpt quoted for a failed task
+const MAX_REASON_CHARS: usize = 40;
+
+/// Why `task` failed: the category of its latest error, or the error's
+/// first line when it has none
+fn failure_reason(state: &DashboardState, task: &ParsedTask) -> Option<String> {
+    let error = state
+        .recent_errors
+        .iter()
+        .rev()
+        .find(|e| e.task_id == task.id)?;
+    if error.category != ErrorCategory::Unknown {
+        return Some(format!(
+            "{} error",
+            error.category.to_string().to_lowercase()
+        ));
+    }
+    let line = error.message.lines().next().unwrap_or("").trim();
+    if line.chars().count() <= MAX_REASON_CHARS {
+        return Some(line.to_string()).filter(|l| !l.is_empty());
+    }
+    let short: String = line.chars().take(MAX_REASON_CHARS - 1).collect();
+    Some(format!("{}\u{2026}", short.trim_end()))
+}
+
+fn phase_line(state: &DashboardState, phase: &ParsedPhase) -> String {
+    let done = phase
+        .tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Completed)
+        .count();
+    let mut line = format!(
+        "- {} {}: {done}/{} done",
+        phase.id,
+        phase.name,
+        phase.tasks.len()
+    );
+    let failed: Vec<String> = phase
+        .tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Failed)
+        .map(|t| match failure_reason(state, t) {
+            Some(reason) => format!("{} \u{2014} {reason}", t.id),
+            None => t.id.clone(),
+        })
+        .collect();
+    if !failed.is_empty() {
+        line.push_str(&format!(
+            ", {} failed ({})",
+            failed.len(),
+            failed.join(", ")
+        ));
+    }
+    line
+}
+
+/// The status bar totals and per-phase progress of `state`
+pub fn status_summary(state: &DashboardState, now: DateTime<Utc>) -> String {
+    let count = |status: TaskStatus| {
+        state
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| t.status == status)
+            .count()
+    };
+    let mut totals = vec![format!(
+        "{}/{} done ({}%)",
+        state.completed_tasks,
+        state.total_tasks,
+        (state.overall_progress * 100.0) as u8
+    )];
+    let in_progress = count(TaskStatus::InProgress);
+    if in_progress > 0 {
+        totals.push(format!("{in_progress} in progress"));
+    }
+    let failed = count(TaskStatus::Failed);
+    if failed > 0 {
+        totals.push(format!("{failed} failed"));
+    }
+    match state.total_open_errors(now) {
+        0 => {}
+        1 => totals.push("1 open error".to_string()),
+        n => totals.push(format!("{n} open errors")),
+    }
+    let (tokens, cost) = (state.total_tokens(), state.total_cost());
+    if tokens > 0 || cost > 0.0 {
+        totals.push(format!("{} tok / ${cost:.2}", format_tokens(tokens)));
+    }
+
+    let mut text = format!("Board status: {}\n", totals.join(", "));
+    for phase in &state.phases {
+        text.push_str(&phase_line(state, phase));
+        text.push('\n');
+    }
+    text
+}
+
+/// OSC 52 escape setting the clipboard to `text`, wrapped for tmux
+/// passthrough when `in_tmux`
+pub fn osc52(text: &str, in_tmux: bool) -> String {
+    tmux_passthrough(
+        format!("\x1b]52;c;{}\x07", base64(text.as_bytes())),
+        in_tmux,
+    )
+}
+
+/// Whether `program` took `text` on stdin and exited successfully
+fn pipe_to(program: &str, args: &[&str], text: &str) -> bool {
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if stdin.write_all(text.as_bytes()).is_err() {
+            let _ = child.kill();
+        }
+    }
+    child.wait().is_ok_and(|status| status.success())
+}
+
+/// Put `text` on the clipboard and name how: the clipboard tool that took
+/// it, or `OSC 52` once the escape is written to stdout
+pub fn copy(text: &str) -> std::io::Result<&'static str> {
+    for (program, args) in TOOLS {
+        if pipe_to(program, args, text) {
+            return Ok(program);
+        }
+    }
+    let in_tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52(text, in_tmux).as_bytes())?;
+    stdout.flush()?;
+    Ok("OSC 52")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn summary_lists_phases_and_failure_reasons() {
+        let tasks = "# Phase 1: Setup\n### [x] P1-T1: Init\n\
+                     # Phase 2: Core\n### [x] P2-T1: Parser\n### [Failed] P2-T4: Types\n\
+                     ### [Failed] P2-T5: Build\n### [InProgress] P2-T6: Api\n";
+        let mut state = DashboardState::from_tasks_content(tasks).unwrap();
+        let input = r#"{"event_type":"error","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P2-T4","session_id":"s","error_message":"type mismatch in parser.rs"}"#;
+        state.update_from_events(&parse_hook_events(input).events);
+
+        let text = status_summary(&state, Utc::now());
+        assert_eq!(
+            text,
+            "Board status: 2/5 done (40%), 1 in progress, 2 failed, 1 open error\n\
+             - P1 Setup: 1/1 done\n\
+             - P2 Core: 1/4 done, 2 failed (P2-T4 \u{2014} type error, P2-T5)\n"
+        );
+    }
+
+    #[test]
+    fn osc52_encodes_the_text() {
+        assert_eq!(osc52("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(osc52("hi", true), "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\");
+    }
+}
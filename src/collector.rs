@@ -0,0 +1,258 @@
+//! Headless collector (`collector` subcommand)
+//!
+//! Polls the event files, aggregates whatever was appended and rewrites the
+//! snapshot store after every change, so a dashboard starting later attaches
+//! to current state instead of parsing the whole history. Also prints
+//! systemd and launchd service definitions that keep it running.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::data::identity::AgentIdentity;
+use crate::data::loader;
+use crate::data::state::{DashboardState, Limits};
+use crate::data::store::{self, Store};
+use crate::data::tail::Tail;
+
+/// Service manager to generate a definition for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceKind {
+    /// systemd user unit (Linux)
+    Systemd,
+    /// launchd agent plist (macOS)
+    Launchd,
+}
+
+impl FromStr for ServiceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "systemd" => Ok(ServiceKind::Systemd),
+            "launchd" => Ok(ServiceKind::Launchd),
+            other => Err(format!(
+                "unknown service manager `{other}` (expected systemd or launchd)"
+            )),
+        }
+    }
+}
+
+/// systemd unit description
+const SERVICE_NAME: &str = "simple-claude-board collector";
+/// launchd job label
+const LAUNCHD_LABEL: &str = "com.simple-claude-board.collector";
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// systemd quotes arguments with double quotes and backslash escapes
+fn systemd_quote(arg: &str) -> String {
+    if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        arg.to_string()
+    }
+}
+
+/// Service definition running `program` with `args` in `working_dir`
+pub fn service_file(
+    kind: ServiceKind,
+    program: &str,
+    args: &[String],
+    working_dir: &Path,
+) -> String {
+    match kind {
+        ServiceKind::Systemd => {
+            let command: Vec<String> = std::iter::once(program)
+                .chain(args.iter().map(String::as_str))
+                .map(systemd_quote)
+                .collect();
+            format!(
+                "[Unit]\n\
+                 Description={SERVICE_NAME}\n\
+                 \n\
+                 [Service]\n\
+                 ExecStart={}\n\
+                 WorkingDirectory={}\n\
+                 Restart=on-failure\n\
+                 RestartSec=5\n\
+                 \n\
+                 [Install]\n\
+                 WantedBy=default.target\n",
+                command.join(" "),
+                working_dir.display()
+            )
+        }
+        ServiceKind::Launchd => {
+            let arguments: String = std::iter::once(program)
+                .chain(args.iter().map(String::as_str))
+                .map(|arg| format!("    <string>{}</string>\n", xml_escape(arg)))
+                .collect();
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                 <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+                 <plist version=\"1.0\">\n\
+                 <dict>\n\
+                 \x20 <key>Label</key>\n\
+                 \x20 <string>{LAUNCHD_LABEL}</string>\n\
+                 \x20 <key>ProgramArguments</key>\n\
+                 \x20 <array>\n\
+                 {arguments}\
+                 \x20 </array>\n\
+                 \x20 <key>WorkingDirectory</key>\n\
+                 \x20 <string>{}</string>\n\
+                 \x20 <key>RunAtLoad</key>\n\
+                 \x20 <true/>\n\
+                 \x20 <key>KeepAlive</key>\n\
+                 \x20 <true/>\n\
+                 </dict>\n\
+                 </plist>\n",
+                xml_escape(&working_dir.display().to_string())
+            )
+        }
+    }
+}
+
+/// Aggregated state and read positions, saved as a snapshot after changes
+pub struct Collector {
+    state: DashboardState,
+    tail: Tail,
+}
+
+impl Collector {
+    /// Resume from the snapshot at `store_path` when there is one
+    pub fn new(store_path: &Path) -> Self {
+        match Store::read(store_path) {
+            Some(store) => Self {
+                tail: Tail::from_offsets(store.offsets.clone()),
+                state: store.into_state(),
+            },
+            None => Self {
+                state: DashboardState::default(),
+                tail: Tail::default(),
+            },
+        }
+    }
+
+    /// Fold agent instances into logical agents from now on
+    pub fn with_agent_identity(mut self, identity: Option<AgentIdentity>) -> Self {
+        self.state.agent_identity = identity;
+        self
+    }
+
+    /// Cap the agents and errors kept in the snapshot
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.state.limits = limits;
+        self.state.enforce_limits();
+        self
+    }
+
+    /// Aggregate what was appended to the files in `dirs`; true when
+    /// anything was
+    pub fn poll(&mut self, dirs: &[PathBuf]) -> bool {
+        let mut changed = false;
+        for (path, _) in loader::collect_jsonl_files(dirs) {
+            let state = &mut self.state;
+            self.tail.read_new_batches(&path, |events| {
+                state.update_from_events(&events);
+                changed = true;
+            });
+        }
+        changed
+    }
+
+    pub fn store(&self) -> Store {
+        Store::capture(&self.state, self.tail.offsets())
+    }
+}
+
+/// Keep the snapshot in `store_dir` current with `dirs`, checking every
+/// `interval`, until killed
+pub fn run(
+    dirs: &[PathBuf],
+    store_dir: &Path,
+    interval: Duration,
+    agent_identity: Option<AgentIdentity>,
+    limits: Limits,
+) -> anyhow::Result<()> {
+    std::fs::create_dir_all(store_dir)
+        .with_context(|| format!("cannot create {}", store_dir.display()))?;
+    let store_path = store_dir.join(store::STORE_FILE);
+    let mut collector = Collector::new(&store_path)
+        .with_agent_identity(agent_identity)
+        .with_limits(limits);
+    let mut first = true;
+    loop {
+        if collector.poll(dirs) || first {
+            collector
+                .store()
+                .write(&store_path)
+                .with_context(|| format!("cannot write {}", store_path.display()))?;
+            first = false;
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#;
+
+    #[test]
+    fn resumes_from_its_snapshot() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let events = tmp.path().join("events.jsonl");
+        let store_path = tmp.path().join(store::STORE_FILE);
+        std::fs::write(&events, format!("{LINE}\n")).unwrap();
+        let dirs = [tmp.path().to_path_buf()];
+
+        let mut collector = Collector::new(&store_path);
+        assert!(collector.poll(&dirs));
+        assert!(!collector.poll(&dirs));
+        collector.store().write(&store_path).unwrap();
+
+        // The snapshot is not read as events, and a new collector picks up
+        // only the appended line
+        std::fs::write(&events, format!("{LINE}\n{LINE}\n")).unwrap();
+        let mut resumed = Collector::new(&store_path);
+        assert!(resumed.poll(&dirs));
+        assert_eq!(resumed.state.agents["a"].event_count, 2);
+    }
+
+    #[test]
+    fn service_files_run_the_collector() {
+        let args = vec![
+            "--events".to_string(),
+            "/home/me/my events".to_string(),
+            "collector".to_string(),
+        ];
+        let unit = service_file(
+            ServiceKind::Systemd,
+            "/usr/local/bin/simple-claude-board",
+            &args,
+            Path::new("/home/me/project"),
+        );
+        assert!(unit.contains(
+            "ExecStart=/usr/local/bin/simple-claude-board --events \"/home/me/my events\" collector\n"
+        ));
+        assert!(unit.contains("WorkingDirectory=/home/me/project\n"));
+
+        let plist = service_file(
+            ServiceKind::Launchd,
+            "/usr/local/bin/simple-claude-board",
+            &args,
+            Path::new("/Users/me/a&b"),
+        );
+        assert!(plist.contains("    <string>/home/me/my events</string>\n"));
+        assert!(plist.contains("<string>/Users/me/a&amp;b</string>"));
+        assert!("upstart".parse::<ServiceKind>().is_err());
+    }
+}
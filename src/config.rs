@@ -0,0 +1,1028 @@
+//! Dashboard configuration
+//!
+//! Read from `.claude/board.toml` in the project, falling back to
+//! `~/.claude/board.toml`. Only the TOML subset the dashboard needs is
+//! supported: `[section]` headers, `key = value` pairs with string, integer,
+//! float, boolean or array values, and `#` comments.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::{char, space0},
+    combinator::{map, map_res, opt},
+    multi::separated_list0,
+    sequence::{delimited, preceded, terminated, tuple},
+    IResult,
+};
+
+use crate::actions::CustomAction;
+use crate::alerts::{self, AlertConfig};
+use crate::analysis::health::HealthThresholds;
+use crate::analysis::loops::LoopThresholds;
+use crate::data::identity::AgentIdentity;
+use crate::data::state::{Limits, MAX_TOOL_INVOCATIONS};
+use crate::digest::DigestConfig;
+use crate::init::HookOptions;
+#[cfg(feature = "tui")]
+use crate::ui::{gantt::TaskColumn, icons::IconSet, layout::LayoutMode, theme::ThemeName};
+use crate::webhooks::Webhook;
+
+/// File name of the dashboard config
+pub const CONFIG_FILE: &str = "board.toml";
+
+/// A parsed config value
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    Array(Vec<ConfigValue>),
+}
+
+impl ConfigValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ConfigValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// Flattened config table keyed by `section.key` (top-level keys have no prefix)
+pub type ConfigTable = BTreeMap<String, ConfigValue>;
+
+/// How close usage is to the configured budget, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BudgetLevel {
+    Ok,
+    Warn,
+    Over,
+}
+
+/// Token / cost budget from the `[budget]` section
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Budget {
+    /// Token budget (input + output)
+    pub tokens: Option<u64>,
+    /// Cost budget in USD
+    pub cost_usd: Option<f64>,
+    /// Token budget of each session
+    pub session_tokens: Option<u64>,
+    /// Cost budget of each session in USD
+    pub session_cost_usd: Option<f64>,
+    /// Token budget of each agent
+    pub agent_tokens: Option<u64>,
+    /// Cost budget of each agent in USD
+    pub agent_cost_usd: Option<f64>,
+    /// Fraction of a budget at which the status bar turns to a warning
+    pub warn_ratio: f64,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            tokens: None,
+            cost_usd: None,
+            session_tokens: None,
+            session_cost_usd: None,
+            agent_tokens: None,
+            agent_cost_usd: None,
+            warn_ratio: 0.8,
+        }
+    }
+}
+
+impl Budget {
+    /// Level of `used` against `limit`; always `Ok` without a limit
+    pub fn level_for(&self, used: f64, limit: Option<f64>) -> BudgetLevel {
+        match limit {
+            Some(limit) if limit > 0.0 && used >= limit => BudgetLevel::Over,
+            Some(limit) if limit > 0.0 && used >= limit * self.warn_ratio => BudgetLevel::Warn,
+            _ => BudgetLevel::Ok,
+        }
+    }
+
+    /// Level of token usage against the token budget
+    pub fn token_level(&self, tokens: u64) -> BudgetLevel {
+        self.level_for(tokens as f64, self.tokens.map(|t| t as f64))
+    }
+
+    /// Level of spend against the cost budget
+    pub fn cost_level(&self, cost_usd: f64) -> BudgetLevel {
+        self.level_for(cost_usd, self.cost_usd)
+    }
+}
+
+/// Typed dashboard configuration
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Config {
+    /// Glyph set for statuses, agents and tools
+    #[cfg(feature = "tui")]
+    pub icons: IconSet,
+    /// Color theme preset
+    #[cfg(feature = "tui")]
+    pub theme: ThemeName,
+    pub budget: Budget,
+    /// Initial pane arrangement
+    #[cfg(feature = "tui")]
+    pub layout: LayoutMode,
+    /// Show the full-width overall progress gauge above the status bar
+    pub progress_gauge: bool,
+    /// Task row columns in display order; empty means the default layout
+    #[cfg(feature = "tui")]
+    pub task_columns: Vec<TaskColumn>,
+    /// Resetting a failed task to pending also dismisses its recorded errors
+    pub reset_clears_errors: bool,
+    /// Write task statuses implied by agent events back to TASKS.md
+    pub auto_sync: bool,
+    /// Shell commands bound to function keys (`[actions]`), by key number
+    pub actions: Vec<CustomAction>,
+    /// How long `z` mutes an agent's alerts; `None` means the default
+    pub mute_minutes: Option<u64>,
+    /// How long until a `t` reminder comes due; `None` means the default
+    pub reminder_minutes: Option<u64>,
+    /// Minutes without events after which a running agent is presumed
+    /// finished; `None` means the default
+    pub idle_minutes: Option<u64>,
+    /// Retries after which a task is flagged for a human look; `None` means
+    /// the default
+    pub retry_threshold: Option<u64>,
+    /// Error rates at which agents count as degraded or failing (`[health]`)
+    pub health: HealthThresholds,
+    /// Identical tool calls within a window that make a loop (`[loops]`)
+    pub loops: LoopThresholds,
+    /// Agents, sessions and errors kept in memory (`[limits]`)
+    pub limits: Limits,
+    /// Matcher, timeout and events directory `init` sets the hook up with
+    /// (`[init]`); command-line options override them
+    pub hook: HookOptions,
+    /// Regex folding agent instance IDs into a logical agent
+    /// (`agent_identity`), already checked to compile
+    pub agent_identity: Option<String>,
+    /// OTLP/HTTP collector `otel` sends traces to (`[otel] endpoint`)
+    pub otel_endpoint: Option<String>,
+    /// `[webhooks.<name>]` sections, by name
+    pub webhooks: Vec<Webhook>,
+    /// Bell / OSC 9 escalation of errors and failed tasks (`[alerts]`)
+    pub alerts: AlertConfig,
+    /// SMTP settings of the `digest` email (`[digest]`)
+    pub digest: Option<DigestConfig>,
+}
+
+/// Minutes an agent stays muted when `mute_minutes` is not set
+pub const DEFAULT_MUTE_MINUTES: u64 = 15;
+
+/// Minutes until a reminder comes due when `reminder_minutes` is not set
+pub const DEFAULT_REMINDER_MINUTES: u64 = 15;
+
+/// Minutes of silence before a running agent goes idle when `idle_minutes`
+/// is not set
+pub const DEFAULT_IDLE_MINUTES: u64 = 10;
+
+/// Retries that flag a task when `retry_threshold` is not set
+pub const DEFAULT_RETRY_THRESHOLD: u64 = 3;
+
+impl Config {
+    /// Minutes `z` mutes an agent's alerts for
+    pub fn mute_minutes(&self) -> u64 {
+        self.mute_minutes.unwrap_or(DEFAULT_MUTE_MINUTES)
+    }
+
+    /// Minutes after which a `t` reminder comes due
+    pub fn reminder_minutes(&self) -> u64 {
+        self.reminder_minutes.unwrap_or(DEFAULT_REMINDER_MINUTES)
+    }
+
+    /// Minutes without events after which a running agent is presumed
+    /// finished
+    pub fn idle_minutes(&self) -> u64 {
+        self.idle_minutes.unwrap_or(DEFAULT_IDLE_MINUTES)
+    }
+
+    /// Retries after which a task needs a human look
+    pub fn retry_threshold(&self) -> usize {
+        self.retry_threshold.unwrap_or(DEFAULT_RETRY_THRESHOLD) as usize
+    }
+
+    /// Resolver of logical agent identities, when `agent_identity` is set
+    pub fn agent_identity(&self) -> Option<AgentIdentity> {
+        self.agent_identity
+            .as_deref()
+            .and_then(|pattern| AgentIdentity::new(pattern).ok())
+    }
+
+    /// Keys only the terminal UI uses; ignored in a headless build
+    #[cfg(feature = "tui")]
+    fn read_tui_keys(&mut self, table: &ConfigTable) -> Result<(), String> {
+        if let Some(value) = table.get("icons") {
+            self.icons = expect_str("icons", value)?.parse()?;
+        }
+        if let Some(value) = table.get("theme") {
+            self.theme = expect_str("theme", value)?.parse()?;
+        }
+        if let Some(value) = table.get("layout") {
+            self.layout = expect_str("layout", value)?.parse()?;
+        }
+        if let Some(value) = table.get("task_columns") {
+            self.task_columns = expect_str_array("task_columns", value)?
+                .into_iter()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?;
+            if self.task_columns.is_empty() {
+                return Err("`task_columns` must list at least one column".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a config from TOML text; unknown keys are ignored
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table = parse_table(content)?;
+        let mut config = Config::default();
+
+        #[cfg(feature = "tui")]
+        config.read_tui_keys(&table)?;
+        if let Some(value) = table.get("progress_gauge") {
+            config.progress_gauge = expect_bool("progress_gauge", value)?;
+        }
+        if let Some(value) = table.get("reset_clears_errors") {
+            config.reset_clears_errors = expect_bool("reset_clears_errors", value)?;
+        }
+        if let Some(value) = table.get("auto_sync") {
+            config.auto_sync = expect_bool("auto_sync", value)?;
+        }
+        if let Some(value) = table.get("mute_minutes") {
+            let minutes = expect_u64("mute_minutes", value)?;
+            if minutes == 0 {
+                return Err("`mute_minutes` must be at least 1".to_string());
+            }
+            config.mute_minutes = Some(minutes);
+        }
+        if let Some(value) = table.get("reminder_minutes") {
+            let minutes = expect_u64("reminder_minutes", value)?;
+            if minutes == 0 {
+                return Err("`reminder_minutes` must be at least 1".to_string());
+            }
+            config.reminder_minutes = Some(minutes);
+        }
+        if let Some(value) = table.get("idle_minutes") {
+            let minutes = expect_u64("idle_minutes", value)?;
+            if minutes == 0 {
+                return Err("`idle_minutes` must be at least 1".to_string());
+            }
+            config.idle_minutes = Some(minutes);
+        }
+        if let Some(value) = table.get("retry_threshold") {
+            let retries = expect_u64("retry_threshold", value)?;
+            if retries == 0 {
+                return Err("`retry_threshold` must be at least 1".to_string());
+            }
+            config.retry_threshold = Some(retries);
+        }
+        if let Some(value) = table.get("agent_identity") {
+            let pattern = expect_str("agent_identity", value)?;
+            AgentIdentity::new(pattern)?;
+            config.agent_identity = Some(pattern.to_string());
+        }
+        if let Some(value) = table.get("otel.endpoint") {
+            config.otel_endpoint = Some(expect_str("otel.endpoint", value)?.to_string());
+        }
+        if let Some(value) = table.get("alerts.bell") {
+            config.alerts.bell = expect_bool("alerts.bell", value)?;
+        }
+        if let Some(value) = table.get("alerts.osc") {
+            config.alerts.osc = expect_bool("alerts.osc", value)?;
+        }
+        if let Some(value) = table.get("alerts.when_focused") {
+            config.alerts.when_focused = expect_bool("alerts.when_focused", value)?;
+        }
+        for category in alerts::CATEGORIES {
+            let key = format!("alerts.{category}");
+            if let Some(value) = table.get(&key) {
+                let threshold = expect_u64(&key, value)?;
+                config
+                    .alerts
+                    .thresholds
+                    .insert(category.to_string(), threshold);
+            }
+        }
+        if let Some(value) = table.get("budget.tokens") {
+            config.budget.tokens = Some(expect_u64("budget.tokens", value)?);
+        }
+        if let Some(value) = table.get("budget.cost_usd") {
+            config.budget.cost_usd = Some(expect_f64("budget.cost_usd", value)?);
+        }
+        if let Some(value) = table.get("budget.session_tokens") {
+            config.budget.session_tokens = Some(expect_u64("budget.session_tokens", value)?);
+        }
+        if let Some(value) = table.get("budget.session_cost_usd") {
+            config.budget.session_cost_usd = Some(expect_f64("budget.session_cost_usd", value)?);
+        }
+        if let Some(value) = table.get("budget.agent_tokens") {
+            config.budget.agent_tokens = Some(expect_u64("budget.agent_tokens", value)?);
+        }
+        if let Some(value) = table.get("budget.agent_cost_usd") {
+            config.budget.agent_cost_usd = Some(expect_f64("budget.agent_cost_usd", value)?);
+        }
+        if let Some(value) = table.get("budget.warn_ratio") {
+            config.budget.warn_ratio = expect_f64("budget.warn_ratio", value)?;
+        }
+        if let Some(value) = table.get("health.degraded") {
+            config.health.degraded = expect_f64("health.degraded", value)?;
+        }
+        if let Some(value) = table.get("health.failing") {
+            config.health.failing = expect_f64("health.failing", value)?;
+        }
+        let health = config.health;
+        if !(0.0 < health.degraded && health.degraded <= health.failing && health.failing <= 1.0) {
+            return Err("`health`: need 0 < degraded <= failing <= 1 (error rates)".to_string());
+        }
+        if let Some(value) = table.get("loops.repeats") {
+            let repeats = expect_u64("loops.repeats", value)? as usize;
+            if !(2..=MAX_TOOL_INVOCATIONS).contains(&repeats) {
+                return Err(format!(
+                    "`loops.repeats` must be between 2 and {MAX_TOOL_INVOCATIONS}"
+                ));
+            }
+            config.loops.repeats = repeats;
+        }
+        if let Some(value) = table.get("loops.window_secs") {
+            let secs = expect_u64("loops.window_secs", value)?;
+            if secs == 0 {
+                return Err("`loops.window_secs` must be at least 1".to_string());
+            }
+            config.loops.window_secs = secs as i64;
+        }
+        if let Some(value) = table.get("limits.max_agents") {
+            config.limits.max_agents = expect_u64("limits.max_agents", value)? as usize;
+            if config.limits.max_agents == 0 {
+                return Err("`limits.max_agents` must be at least 1".to_string());
+            }
+        }
+        if let Some(value) = table.get("init.matcher") {
+            let matcher = expect_str("init.matcher", value)?;
+            if matcher.is_empty() {
+                return Err("`init.matcher` must not be empty".to_string());
+            }
+            config.hook.matcher = matcher.to_string();
+        }
+        if let Some(value) = table.get("init.timeout") {
+            config.hook.timeout = expect_u64("init.timeout", value)?;
+            if config.hook.timeout == 0 {
+                return Err("`init.timeout` must be at least 1".to_string());
+            }
+        }
+        if let Some(value) = table.get("init.events_dir") {
+            config.hook.events_dir = Some(PathBuf::from(expect_str("init.events_dir", value)?));
+        }
+        if let Some(value) = table.get("limits.max_errors") {
+            config.limits.max_errors = expect_u64("limits.max_errors", value)? as usize;
+            if config.limits.max_errors == 0 {
+                return Err("`limits.max_errors` must be at least 1".to_string());
+            }
+        }
+        if let Some(value) = table.get("limits.max_sessions") {
+            config.limits.max_sessions = expect_u64("limits.max_sessions", value)? as usize;
+            if config.limits.max_sessions == 0 {
+                return Err("`limits.max_sessions` must be at least 1".to_string());
+            }
+        }
+        if let Some(value) = table.get("limits.max_tasks") {
+            config.limits.max_tasks = expect_u64("limits.max_tasks", value)? as usize;
+            if config.limits.max_tasks == 0 {
+                return Err("`limits.max_tasks` must be at least 1".to_string());
+            }
+        }
+        for (full_key, value) in table.range("actions.".to_string()..) {
+            let Some(key) = full_key.strip_prefix("actions.") else {
+                break;
+            };
+            let command = expect_str(full_key, value)?;
+            config.actions.push(CustomAction::parse(key, command)?);
+        }
+        config.actions.sort_by_key(|a| a.key);
+        if let Some(pair) = config.actions.windows(2).find(|p| p[0].key == p[1].key) {
+            return Err(format!("`actions`: F{} is bound twice", pair[0].key));
+        }
+        config.webhooks = webhooks(&table)?;
+        config.digest = digest(&table)?;
+
+        Ok(config)
+    }
+
+    /// Read a config file
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Self::from_toml(&content).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Load the project config, then the user config, or defaults if neither exists
+    pub fn load(home: &Path) -> Result<Self, String> {
+        match find_config_file(home) {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Collect the `[webhooks.<name>]` sections
+fn webhooks(table: &ConfigTable) -> Result<Vec<Webhook>, String> {
+    let mut sections: BTreeMap<&str, BTreeMap<&str, &ConfigValue>> = BTreeMap::new();
+    for (full_key, value) in table.range("webhooks.".to_string()..) {
+        let Some(key) = full_key.strip_prefix("webhooks.") else {
+            break;
+        };
+        let Some((name, field)) = key.rsplit_once('.') else {
+            return Err(format!(
+                "`{full_key}` must be inside a [webhooks.<name>] section"
+            ));
+        };
+        sections.entry(name).or_default().insert(field, value);
+    }
+
+    sections
+        .into_iter()
+        .map(|(name, fields)| {
+            let key = |field: &str| format!("webhooks.{name}.{field}");
+            let string = |field: &str| {
+                fields
+                    .get(field)
+                    .map(|value| expect_str(&key(field), value))
+                    .transpose()
+            };
+            let url = string("url")?.ok_or_else(|| format!("`{}` is required", key("url")))?;
+            let on = match fields.get("on") {
+                Some(value) => expect_str_array(&key("on"), value)?,
+                None => Vec::new(),
+            };
+            Webhook::parse(name, url, &on, string("template")?, string("secret")?)
+        })
+        .collect()
+}
+
+/// Read the `[digest]` section; `None` without an `smtp_url`
+fn digest(table: &ConfigTable) -> Result<Option<DigestConfig>, String> {
+    let string = |key: &str| {
+        table
+            .get(key)
+            .map(|value| expect_str(key, value).map(str::to_string))
+            .transpose()
+    };
+    let Some(smtp_url) = string("digest.smtp_url")? else {
+        return Ok(None);
+    };
+    if !smtp_url.starts_with("smtp://") && !smtp_url.starts_with("smtps://") {
+        return Err("`digest.smtp_url` must be an smtp:// or smtps:// URL".to_string());
+    }
+    let from = string("digest.from")?.ok_or("`digest.from` is required")?;
+    let to: Vec<String> = match table.get("digest.to") {
+        Some(value) => expect_str_array("digest.to", value)?
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        None => Vec::new(),
+    };
+    if to.is_empty() {
+        return Err("`digest.to` must list at least one address".to_string());
+    }
+    Ok(Some(DigestConfig {
+        smtp_url,
+        from,
+        to,
+        username: string("digest.username")?,
+        password: string("digest.password")?,
+        subject: string("digest.subject")?,
+        tls: match table.get("digest.tls") {
+            Some(value) => expect_bool("digest.tls", value)?,
+            None => true,
+        },
+    }))
+}
+
+/// Where the config file may be, in order of precedence: `.claude/board.toml`
+/// then `~/.claude/board.toml`
+pub fn config_file_candidates(home: &Path) -> [PathBuf; 2] {
+    [
+        PathBuf::from(".claude").join(CONFIG_FILE),
+        home.join(".claude").join(CONFIG_FILE),
+    ]
+}
+
+/// Locate the config file: `.claude/board.toml` > `~/.claude/board.toml`
+pub fn find_config_file(home: &Path) -> Option<PathBuf> {
+    config_file_candidates(home)
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+fn expect_str<'a>(key: &str, value: &'a ConfigValue) -> Result<&'a str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("`{key}` must be a string"))
+}
+
+fn expect_str_array<'a>(key: &str, value: &'a ConfigValue) -> Result<Vec<&'a str>, String> {
+    let err = || format!("`{key}` must be an array of strings");
+    match value {
+        ConfigValue::Array(items) => items.iter().map(|v| v.as_str().ok_or_else(err)).collect(),
+        _ => Err(err()),
+    }
+}
+
+fn expect_bool(key: &str, value: &ConfigValue) -> Result<bool, String> {
+    match value {
+        ConfigValue::Bool(b) => Ok(*b),
+        _ => Err(format!("`{key}` must be true or false")),
+    }
+}
+
+fn expect_u64(key: &str, value: &ConfigValue) -> Result<u64, String> {
+    match value {
+        ConfigValue::Integer(n) if *n >= 0 => Ok(*n as u64),
+        _ => Err(format!("`{key}` must be a non-negative integer")),
+    }
+}
+
+fn expect_f64(key: &str, value: &ConfigValue) -> Result<f64, String> {
+    match value {
+        ConfigValue::Integer(n) => Ok(*n as f64),
+        ConfigValue::Float(f) => Ok(*f),
+        _ => Err(format!("`{key}` must be a number")),
+    }
+}
+
+/// Parse TOML text into a flat table
+pub fn parse_table(content: &str) -> Result<ConfigTable, String> {
+    let mut table = ConfigTable::new();
+    let mut section = String::new();
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_number = idx + 1;
+
+        if let Ok((rest, name)) = section_header(line) {
+            if !is_blank_or_comment(rest) {
+                return Err(format!("line {line_number}: unexpected text after section"));
+            }
+            section = name.to_string();
+            continue;
+        }
+
+        match key_value(line) {
+            Ok((rest, (key, value))) if is_blank_or_comment(rest) => {
+                let full_key = if section.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{section}.{key}")
+                };
+                table.insert(full_key, value);
+            }
+            _ => return Err(format!("line {line_number}: invalid entry `{line}`")),
+        }
+    }
+
+    Ok(table)
+}
+
+fn is_blank_or_comment(rest: &str) -> bool {
+    let rest = rest.trim();
+    rest.is_empty() || rest.starts_with('#')
+}
+
+fn bare_key(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.')(input)
+}
+
+fn section_header(input: &str) -> IResult<&str, &str> {
+    delimited(char('['), delimited(space0, bare_key, space0), char(']'))(input)
+}
+
+fn key_value(input: &str) -> IResult<&str, (&str, ConfigValue)> {
+    map(
+        tuple((bare_key, space0, char('='), space0, value)),
+        |(key, _, _, _, value)| (key, value),
+    )(input)
+}
+
+fn value(input: &str) -> IResult<&str, ConfigValue> {
+    alt((
+        map(basic_string, ConfigValue::String),
+        map(literal_string, |s: &str| ConfigValue::String(s.to_string())),
+        map(tag("true"), |_| ConfigValue::Bool(true)),
+        map(tag("false"), |_| ConfigValue::Bool(false)),
+        array,
+        number,
+    ))(input)
+}
+
+/// `"..."` with `\"`, `\\`, `\n` and `\t` escapes
+fn basic_string(input: &str) -> IResult<&str, String> {
+    let (mut rest, _) = char('"')(input)?;
+    let mut out = String::new();
+    loop {
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some('"') => return Ok((chars.as_str(), out)),
+            Some('\\') => {
+                let escaped = match chars.next() {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some(c @ ('"' | '\\')) => c,
+                    _ => {
+                        return Err(nom::Err::Error(nom::error::Error::new(
+                            rest,
+                            nom::error::ErrorKind::Escaped,
+                        )))
+                    }
+                };
+                out.push(escaped);
+            }
+            Some(c) => out.push(c),
+            None => {
+                return Err(nom::Err::Error(nom::error::Error::new(
+                    rest,
+                    nom::error::ErrorKind::Char,
+                )))
+            }
+        }
+        rest = chars.as_str();
+    }
+}
+
+/// `'...'` without escapes
+fn literal_string(input: &str) -> IResult<&str, &str> {
+    delimited(char('\''), take_while(|c| c != '\''), char('\''))(input)
+}
+
+fn number(input: &str) -> IResult<&str, ConfigValue> {
+    map_res(
+        take_while1(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | '_' | 'e' | 'E')),
+        |s: &str| {
+            let s = s.replace('_', "");
+            s.parse::<i64>()
+                .map(ConfigValue::Integer)
+                .or_else(|_| s.parse::<f64>().map(ConfigValue::Float))
+        },
+    )(input)
+}
+
+fn array(input: &str) -> IResult<&str, ConfigValue> {
+    map(
+        delimited(
+            terminated(char('['), space0),
+            terminated(
+                separated_list0(tuple((space0, char(','), space0)), value),
+                preceded(space0, opt(terminated(char(','), space0))),
+            ),
+            char(']'),
+        ),
+        ConfigValue::Array,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scalar_values() {
+        let table = parse_table(
+            r#"
+# comment
+name = "board"
+count = 42
+ratio = 0.5
+enabled = true
+path = 'C:\tmp'
+"#,
+        )
+        .unwrap();
+        assert_eq!(table["name"], ConfigValue::String("board".into()));
+        assert_eq!(table["count"], ConfigValue::Integer(42));
+        assert_eq!(table["ratio"], ConfigValue::Float(0.5));
+        assert_eq!(table["enabled"], ConfigValue::Bool(true));
+        assert_eq!(table["path"], ConfigValue::String("C:\\tmp".into()));
+    }
+
+    #[test]
+    fn parse_sections_and_arrays() {
+        let table =
+            parse_table("[budget]\nmax = 10 # usd\n\n[ui]\ncolumns = [\"id\", \"name\",]\n")
+                .unwrap();
+        assert_eq!(table["budget.max"], ConfigValue::Integer(10));
+        assert_eq!(
+            table["ui.columns"],
+            ConfigValue::Array(vec![
+                ConfigValue::String("id".into()),
+                ConfigValue::String("name".into())
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_string_escapes() {
+        let table = parse_table(r#"s = "a \"b\" \\ c""#).unwrap();
+        assert_eq!(table["s"], ConfigValue::String("a \"b\" \\ c".into()));
+    }
+
+    #[test]
+    fn parse_error_reports_line() {
+        let err = parse_table("icons = \"nerd\"\nnot valid\n").unwrap_err();
+        assert!(err.contains("line 2"), "got: {err}");
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn config_defaults_to_ascii_icons() {
+        let config = Config::from_toml("").unwrap();
+        assert_eq!(config.icons, IconSet::Ascii);
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn config_reads_icons() {
+        let config = Config::from_toml("icons = \"nerd\"").unwrap();
+        assert_eq!(config.icons, IconSet::Nerd);
+        assert!(Config::from_toml("icons = \"emoji\"").is_err());
+        assert!(Config::from_toml("icons = 3").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn config_reads_theme() {
+        let config = Config::from_toml("theme = \"high-contrast\"").unwrap();
+        assert_eq!(config.theme, ThemeName::HighContrast);
+        assert!(Config::from_toml("theme = \"neon\"").is_err());
+    }
+
+    #[test]
+    fn config_reads_progress_gauge() {
+        assert!(!Config::default().progress_gauge);
+        let config = Config::from_toml("progress_gauge = true").unwrap();
+        assert!(config.progress_gauge);
+        assert!(Config::from_toml("progress_gauge = \"yes\"").is_err());
+    }
+
+    #[test]
+    fn config_reads_reset_clears_errors() {
+        assert!(!Config::default().reset_clears_errors);
+        let config = Config::from_toml("reset_clears_errors = true").unwrap();
+        assert!(config.reset_clears_errors);
+    }
+
+    #[test]
+    fn config_reads_auto_sync() {
+        assert!(!Config::default().auto_sync);
+        assert!(Config::from_toml("auto_sync = true").unwrap().auto_sync);
+        assert!(Config::from_toml("auto_sync = 1").is_err());
+    }
+
+    #[test]
+    fn config_reads_mute_minutes() {
+        assert_eq!(Config::default().mute_minutes(), DEFAULT_MUTE_MINUTES);
+        let config = Config::from_toml("mute_minutes = 45").unwrap();
+        assert_eq!(config.mute_minutes(), 45);
+        assert!(Config::from_toml("mute_minutes = 0").is_err());
+    }
+
+    #[test]
+    fn config_reads_reminder_minutes() {
+        assert_eq!(
+            Config::default().reminder_minutes(),
+            DEFAULT_REMINDER_MINUTES
+        );
+        let config = Config::from_toml("reminder_minutes = 5").unwrap();
+        assert_eq!(config.reminder_minutes(), 5);
+        assert!(Config::from_toml("reminder_minutes = 0").is_err());
+    }
+
+    #[test]
+    fn config_reads_idle_minutes() {
+        assert_eq!(Config::default().idle_minutes(), DEFAULT_IDLE_MINUTES);
+        let config = Config::from_toml("idle_minutes = 30").unwrap();
+        assert_eq!(config.idle_minutes(), 30);
+        assert!(Config::from_toml("idle_minutes = 0").is_err());
+    }
+
+    #[test]
+    fn config_reads_retry_threshold() {
+        assert_eq!(Config::default().retry_threshold(), 3);
+        let config = Config::from_toml("retry_threshold = 5").unwrap();
+        assert_eq!(config.retry_threshold(), 5);
+        assert!(Config::from_toml("retry_threshold = 0").is_err());
+    }
+
+    #[test]
+    fn config_reads_health_thresholds() {
+        assert_eq!(Config::default().health, HealthThresholds::default());
+        let config = Config::from_toml("[health]\ndegraded = 0.1\nfailing = 0.3").unwrap();
+        assert_eq!(config.health.degraded, 0.1);
+        assert_eq!(config.health.failing, 0.3);
+        assert!(Config::from_toml("[health]\ndegraded = 0.6").is_err());
+        assert!(Config::from_toml("[health]\nfailing = 2").is_err());
+    }
+
+    #[test]
+    fn config_reads_loop_thresholds() {
+        assert_eq!(Config::default().loops, LoopThresholds::default());
+        let config = Config::from_toml("[loops]\nrepeats = 8\nwindow_secs = 300").unwrap();
+        assert_eq!(config.loops.repeats, 8);
+        assert_eq!(config.loops.window_secs, 300);
+        assert!(Config::from_toml("[loops]\nrepeats = 1").is_err());
+        assert!(Config::from_toml("[loops]\nrepeats = 50").is_err());
+        assert!(Config::from_toml("[loops]\nwindow_secs = 0").is_err());
+    }
+
+    #[test]
+    fn config_reads_limits() {
+        assert_eq!(Config::default().limits, Limits::default());
+        let config = Config::from_toml(
+            "[limits]\nmax_agents = 20\nmax_errors = 500\nmax_sessions = 7\nmax_tasks = 9",
+        )
+        .unwrap();
+        assert_eq!(config.limits.max_agents, 20);
+        assert_eq!(config.limits.max_errors, 500);
+        assert_eq!(config.limits.max_sessions, 7);
+        assert_eq!(config.limits.max_tasks, 9);
+        assert!(Config::from_toml("[limits]\nmax_agents = 0").is_err());
+        assert!(Config::from_toml("[limits]\nmax_sessions = 0").is_err());
+        assert!(Config::from_toml("[limits]\nmax_tasks = 0").is_err());
+    }
+
+    #[test]
+    fn config_reads_init_options() {
+        assert_eq!(Config::default().hook, HookOptions::default());
+        let config = Config::from_toml(
+            "[init]\nmatcher = \"Task|Bash\"\ntimeout = 10\nevents_dir = \"/var/log/claude\"",
+        )
+        .unwrap();
+        assert_eq!(config.hook.matcher, "Task|Bash");
+        assert_eq!(config.hook.timeout, 10);
+        assert_eq!(
+            config.hook.events_dir,
+            Some(std::path::PathBuf::from("/var/log/claude"))
+        );
+        assert!(Config::from_toml("[init]\nmatcher = \"\"").is_err());
+    }
+
+    #[test]
+    fn config_reads_agent_identity() {
+        assert!(Config::default().agent_identity().is_none());
+        let config = Config::from_toml(r"agent_identity = '^(.+)-\d+$'").unwrap();
+        let identity = config.agent_identity().unwrap();
+        assert_eq!(
+            identity.resolve("backend-specialist-2").as_deref(),
+            Some("backend-specialist")
+        );
+        assert!(Config::from_toml("agent_identity = '('").is_err());
+    }
+
+    #[test]
+    fn config_reads_digest() {
+        assert_eq!(Config::default().digest, None);
+        let config = Config::from_toml(
+            "[digest]\nsmtp_url = \"smtps://smtp.example.com:465\"\nfrom = \"board@example.com\"\nto = [\"a@example.com\"]\nusername = \"board\"",
+        )
+        .unwrap();
+        let digest = config.digest.unwrap();
+        assert_eq!(digest.to, vec!["a@example.com"]);
+        assert_eq!(digest.username.as_deref(), Some("board"));
+        assert!(digest.tls);
+
+        assert!(Config::from_toml("[digest]\nsmtp_url = \"https://x\"").is_err());
+        assert!(
+            Config::from_toml("[digest]\nsmtp_url = \"smtp://x\"\nfrom = \"a@x\"\nto = []")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn config_reads_otel_endpoint() {
+        assert_eq!(Config::default().otel_endpoint, None);
+        let config = Config::from_toml("[otel]\nendpoint = \"http://localhost:4318\"").unwrap();
+        assert_eq!(
+            config.otel_endpoint.as_deref(),
+            Some("http://localhost:4318")
+        );
+        assert!(Config::from_toml("[otel]\nendpoint = 4318").is_err());
+    }
+
+    #[test]
+    fn config_reads_alerts() {
+        assert!(!Config::default().alerts.enabled());
+        let config =
+            Config::from_toml("[alerts]\nosc = true\nnetwork = 3\ntask_failed = 0\n").unwrap();
+        assert!(config.alerts.enabled() && !config.alerts.bell);
+        assert_eq!(config.alerts.threshold("network"), 3);
+        assert_eq!(config.alerts.threshold("task_failed"), 0);
+        assert_eq!(config.alerts.threshold("runtime"), 1);
+        assert!(Config::from_toml("[alerts]\nbell = 1").is_err());
+    }
+
+    #[test]
+    fn config_reads_webhooks() {
+        let config = Config::from_toml(
+            "[webhooks.ci]\nurl = \"https://ci.example/hook\"\non = [\"phase_complete\"]\nsecret = \"s\"\n\
+             [webhooks.chat]\nurl = \"http://chat/in\"\ntemplate = '{\"text\": \"{summary}\"}'\n",
+        )
+        .unwrap();
+        let names: Vec<&str> = config.webhooks.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["chat", "ci"]);
+        assert_eq!(config.webhooks[1].secret.as_deref(), Some("s"));
+        assert_eq!(
+            config.webhooks[0].template.as_deref(),
+            Some("{\"text\": \"{summary}\"}")
+        );
+
+        assert!(Config::from_toml("[webhooks.ci]\non = [\"agent_error\"]").is_err());
+        assert!(Config::from_toml("[webhooks]\nurl = \"https://x\"").is_err());
+        assert!(Config::from_toml("[webhooks.ci]\nurl = \"https://x\"\non = [\"bogus\"]").is_err());
+    }
+
+    #[test]
+    fn config_reads_key_actions() {
+        assert!(Config::default().actions.is_empty());
+        let config = Config::from_toml(
+            "theme = \"default\"\n[actions]\nF7 = \"make\"\nF5 = \"cargo test {task_id}\"\n[budget]\ntokens = 5\n",
+        )
+        .unwrap();
+        let keys: Vec<u8> = config.actions.iter().map(|a| a.key).collect();
+        assert_eq!(keys, vec![5, 7]);
+        assert_eq!(config.actions[0].command, "cargo test {task_id}");
+        assert_eq!(config.budget.tokens, Some(5));
+
+        assert!(Config::from_toml("[actions]\nF5 = 1\n").is_err());
+        assert!(Config::from_toml("[actions]\nF5 = \"a\"\nf5 = \"b\"\n").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn config_reads_task_columns() {
+        assert!(Config::default().task_columns.is_empty());
+        let config = Config::from_toml("task_columns = [\"id\", \"Name\", \"eta\"]").unwrap();
+        assert_eq!(
+            config.task_columns,
+            vec![TaskColumn::Id, TaskColumn::Name, TaskColumn::Eta]
+        );
+        assert!(Config::from_toml("task_columns = [\"size\"]").is_err());
+        assert!(Config::from_toml("task_columns = []").is_err());
+        assert!(Config::from_toml("task_columns = \"name\"").is_err());
+    }
+
+    #[test]
+    fn config_reads_budget() {
+        let config =
+            Config::from_toml("[budget]\ntokens = 2_000_000\ncost_usd = 5\nwarn_ratio = 0.5\n")
+                .unwrap();
+        assert_eq!(config.budget.tokens, Some(2_000_000));
+        assert_eq!(config.budget.cost_usd, Some(5.0));
+        assert_eq!(config.budget.warn_ratio, 0.5);
+        assert!(Config::from_toml("[budget]\ntokens = -1\n").is_err());
+
+        let config =
+            Config::from_toml("[budget]\nsession_tokens = 500_000\nagent_cost_usd = 1.5\n")
+                .unwrap();
+        assert_eq!(config.budget.session_tokens, Some(500_000));
+        assert_eq!(config.budget.agent_cost_usd, Some(1.5));
+        assert_eq!(config.budget.tokens, None);
+    }
+
+    #[test]
+    fn budget_levels() {
+        let budget = Budget {
+            tokens: Some(1000),
+            cost_usd: Some(10.0),
+            warn_ratio: 0.8,
+            ..Budget::default()
+        };
+        assert_eq!(budget.token_level(100), BudgetLevel::Ok);
+        assert_eq!(budget.token_level(800), BudgetLevel::Warn);
+        assert_eq!(budget.token_level(1000), BudgetLevel::Over);
+        assert_eq!(budget.cost_level(9.0), BudgetLevel::Warn);
+        assert_eq!(Budget::default().cost_level(1e9), BudgetLevel::Ok);
+    }
+
+    #[test]
+    fn load_missing_config_uses_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config::load(dir.path()).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn load_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        std::fs::write(&path, "progress_gauge = true\n").unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert!(config.progress_gauge);
+    }
+}
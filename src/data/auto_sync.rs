@@ -0,0 +1,131 @@
+//! Task statuses implied by agent events (`auto_sync`)
+//!
+//! With `auto_sync = true` in board.toml, the dashboard writes what the hook
+//! events say back to TASKS.md: `agent_start` on a task marks it in progress,
+//! an `error` marks it failed, and an `agent_end` marks it completed unless
+//! the run since its latest start had errors. Only events newer than the
+//! last sync count, so history replayed at startup and statuses set by hand
+//! afterwards are left alone. Events arrive a batch at a time, so whether a
+//! task's run had already failed in an earlier batch is passed in.
+
+use chrono::{DateTime, Utc};
+
+use crate::data::hook_parser::{EventType, HookEvent};
+use crate::data::tasks_parser::TaskStatus;
+
+/// Status each task's events imply, for tasks with a status-changing event
+/// after `since`, in the order the tasks first appear. `failed_before` tells
+/// whether a task's latest run had failed before these events.
+pub fn implied_statuses(
+    events: &[HookEvent],
+    since: DateTime<Utc>,
+    failed_before: impl Fn(&str) -> bool,
+) -> Vec<(String, TaskStatus)> {
+    // Task id, implied status, whether the latest run has failed
+    let mut tasks: Vec<(String, Option<TaskStatus>, bool)> = Vec::new();
+    for event in events {
+        let index = match tasks.iter().position(|(id, ..)| *id == event.task_id) {
+            Some(index) => index,
+            None => {
+                let failed = failed_before(&event.task_id);
+                tasks.push((event.task_id.clone(), None, failed));
+                tasks.len() - 1
+            }
+        };
+        let (_, implied, failed) = &mut tasks[index];
+        let status = match event.event_type {
+            EventType::AgentStart => {
+                *failed = false;
+                TaskStatus::InProgress
+            }
+            EventType::Error => {
+                *failed = true;
+                TaskStatus::Failed
+            }
+            EventType::AgentEnd if *failed => TaskStatus::Failed,
+            EventType::AgentEnd => TaskStatus::Completed,
+            EventType::ToolStart | EventType::ToolEnd => continue,
+        };
+        if event.timestamp > since {
+            *implied = Some(status);
+        }
+    }
+    tasks
+        .into_iter()
+        .filter_map(|(id, implied, _)| Some((id, implied?)))
+        .collect()
+}
+
+/// Tag written between the brackets of a TASKS.md heading for `status`
+pub fn status_tag(status: &TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::Pending => " ",
+        TaskStatus::InProgress => "InProgress",
+        TaskStatus::Completed => "x",
+        TaskStatus::Failed => "Failed",
+        TaskStatus::Blocked => "Blocked",
+    }
+}
+
+/// Latest event time in `events`, the next sync's `since`
+pub fn latest(events: &[HookEvent]) -> Option<DateTime<Utc>> {
+    events.iter().map(|e| e.timestamp).max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn events(lines: &[&str]) -> Vec<HookEvent> {
+        parse_hook_events(&lines.join("\n")).events
+    }
+
+    #[test]
+    fn events_after_the_last_sync_imply_statuses() {
+        let events = events(&[
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:10:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:20:00Z","agent_id":"a","task_id":"T2","session_id":"s"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:21:00Z","agent_id":"a","task_id":"T2","session_id":"s","error_message":"boom"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:22:00Z","agent_id":"a","task_id":"T2","session_id":"s","tool_name":"Bash"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:30:00Z","agent_id":"a","task_id":"T2","session_id":"s"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:40:00Z","agent_id":"b","task_id":"T3","session_id":"s"}"#,
+        ]);
+
+        let all = implied_statuses(&events, DateTime::<Utc>::MIN_UTC, |_| false);
+        assert_eq!(
+            all,
+            [
+                ("T1".to_string(), TaskStatus::Completed),
+                ("T2".to_string(), TaskStatus::Failed),
+                ("T3".to_string(), TaskStatus::InProgress),
+            ]
+        );
+
+        // T1 finished before the last sync and is left as it is
+        let since = "2026-02-08T10:15:00Z".parse().unwrap();
+        let ids: Vec<String> = implied_statuses(&events, since, |_| false)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, ["T2", "T3"]);
+        // The tool call after the error does not count as a new start
+        let since = "2026-02-08T10:25:00Z".parse().unwrap();
+        assert_eq!(
+            implied_statuses(&events, since, |_| false)[0].1,
+            TaskStatus::Failed
+        );
+        // An error in an earlier batch still fails the run it ends
+        assert_eq!(
+            implied_statuses(&events[5..], since, |id| id == "T2")[0].1,
+            TaskStatus::Failed
+        );
+
+        assert_eq!(
+            latest(&events),
+            Some("2026-02-08T10:40:00Z".parse().unwrap())
+        );
+        assert_eq!(status_tag(&TaskStatus::Completed), "x");
+    }
+}
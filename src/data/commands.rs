@@ -0,0 +1,115 @@
+//! Control commands for running agents
+//!
+//! The dashboard appends one JSON record per signal to `commands.jsonl` in
+//! the events directory, e.g. `{"type":"pause","agent_id":"backend",...}`.
+//! The hook script reads the file before each tool call and refuses to run
+//! it while the agent's latest signal is `pause` or `abort`.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// File name of the commands log; skipped when loading hook events
+pub const COMMANDS_FILE: &str = "commands.jsonl";
+
+/// A control signal sent to an agent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Signal {
+    /// Hold tool calls until resumed
+    Pause,
+    /// Lift an earlier pause or abort
+    Resume,
+    /// Refuse tool calls and tell the agent to stop
+    Abort,
+}
+
+impl Signal {
+    pub const ALL: [Signal; 3] = [Signal::Pause, Signal::Resume, Signal::Abort];
+
+    /// The `type` value written to the commands file
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Signal::Pause => "pause",
+            Signal::Resume => "resume",
+            Signal::Abort => "abort",
+        }
+    }
+}
+
+/// One line of the commands file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlCommand {
+    #[serde(rename = "type")]
+    pub signal: Signal,
+    pub agent_id: String,
+    /// Limits the signal to one session; any session when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Path of the commands file in `dir`
+pub fn commands_path(dir: &Path) -> PathBuf {
+    dir.join(COMMANDS_FILE)
+}
+
+/// Whether `path` is a commands file rather than a hook event log
+pub fn is_commands_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == COMMANDS_FILE)
+}
+
+/// Append `command` to the commands file in `dir`, creating both if missing
+pub fn append_command(dir: &Path, command: &ControlCommand) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let line = serde_json::to_string(command)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(commands_path(dir))?;
+    writeln!(file, "{line}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_appended_as_json_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("dashboard");
+        let timestamp = "2026-01-02T03:04:05Z".parse().unwrap();
+        let pause = ControlCommand {
+            signal: Signal::Pause,
+            agent_id: "backend".to_string(),
+            session_id: Some("sess-1".to_string()),
+            task_id: None,
+            timestamp,
+        };
+        append_command(&dir, &pause).unwrap();
+        append_command(
+            &dir,
+            &ControlCommand {
+                signal: Signal::Resume,
+                ..pause.clone()
+            },
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(commands_path(&dir)).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"type":"pause","agent_id":"backend","session_id":"sess-1","timestamp":"2026-01-02T03:04:05Z"}"#
+        );
+        let resume: ControlCommand = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(resume.signal, Signal::Resume);
+        assert!(is_commands_file(&commands_path(&dir)));
+        assert!(!is_commands_file(&dir.join("events.jsonl")));
+    }
+}
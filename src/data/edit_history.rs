@@ -0,0 +1,288 @@
+//! Undo/redo of dashboard edits
+//!
+//! Every TASKS.md write made from the dashboard is recorded as the file
+//! contents before and after it. Undo writes the earlier contents back;
+//! when the file was changed outside the dashboard since, only the edited
+//! lines are reverted, and only if they can still be found unchanged.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::bail;
+
+use crate::data::tasks_writer::write_atomic;
+
+/// Edits kept for undo; older ones are dropped
+const MAX_EDITS: usize = 50;
+
+/// Contents of one file around an edit; `None` when the file did not exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEdit {
+    pub path: PathBuf,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A dashboard action and the files it changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// What was done, e.g. "rename P1-T2"
+    pub label: String,
+    pub files: Vec<FileEdit>,
+}
+
+/// Contents of files read before an edit, handed back to [`EditHistory::record`]
+#[derive(Debug, Clone)]
+pub struct Snapshot(Vec<(PathBuf, Option<String>)>);
+
+impl Snapshot {
+    /// Read the current contents of `paths`
+    pub fn take(paths: &[&Path]) -> Self {
+        Self(
+            paths
+                .iter()
+                .map(|p| (p.to_path_buf(), std::fs::read_to_string(p).ok()))
+                .collect(),
+        )
+    }
+}
+
+/// Undo and redo stacks of dashboard edits
+#[derive(Debug, Clone, Default)]
+pub struct EditHistory {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl EditHistory {
+    /// Record an edit by comparing `before` with the files as they are now.
+    /// Nothing is recorded when no file changed. A new edit clears redo.
+    pub fn record(&mut self, label: &str, before: Snapshot) {
+        let files: Vec<FileEdit> = before
+            .0
+            .into_iter()
+            .filter_map(|(path, before)| {
+                let after = std::fs::read_to_string(&path).ok();
+                (after != before).then_some(FileEdit {
+                    path,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+        if files.is_empty() {
+            return;
+        }
+        self.undo.push(Edit {
+            label: label.to_string(),
+            files,
+        });
+        if self.undo.len() > MAX_EDITS {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Revert the most recent edit; returns its label, or `None` when there
+    /// is nothing to undo. On error the files and the stacks are untouched.
+    pub fn undo(&mut self) -> anyhow::Result<Option<String>> {
+        let Some(edit) = self.undo.last() else {
+            return Ok(None);
+        };
+        apply(edit, false)?;
+        let edit = self.undo.pop().expect("checked above");
+        let label = edit.label.clone();
+        self.redo.push(edit);
+        Ok(Some(label))
+    }
+
+    /// Re-apply the most recently undone edit
+    pub fn redo(&mut self) -> anyhow::Result<Option<String>> {
+        let Some(edit) = self.redo.last() else {
+            return Ok(None);
+        };
+        apply(edit, true)?;
+        let edit = self.redo.pop().expect("checked above");
+        let label = edit.label.clone();
+        self.undo.push(edit);
+        Ok(Some(label))
+    }
+}
+
+/// Move every file of `edit` from one side to the other (`after` → `before`
+/// for undo). All new contents are worked out before anything is written.
+fn apply(edit: &Edit, forward: bool) -> anyhow::Result<()> {
+    let mut writes = Vec::new();
+    for file in &edit.files {
+        let (from, to) = if forward {
+            (&file.before, &file.after)
+        } else {
+            (&file.after, &file.before)
+        };
+        let current = std::fs::read_to_string(&file.path).ok();
+        let content = match (current, from, to) {
+            (current, from, to) if current == *from => to.clone(),
+            (Some(current), Some(from), Some(to)) => match rebase(&current, from, to) {
+                Some(content) => Some(content),
+                None => bail!(
+                    "{} changed since \"{}\"",
+                    file.path.file_name().unwrap_or_default().to_string_lossy(),
+                    edit.label
+                ),
+            },
+            _ => bail!("{} was created or removed since", file.path.display()),
+        };
+        writes.push((&file.path, content));
+    }
+    for (path, content) in writes {
+        match content {
+            Some(content) => write_atomic(path, &content)?,
+            None => std::fs::remove_file(path)?,
+        }
+    }
+    Ok(())
+}
+
+/// Apply the `from` → `to` change to `current`, a later version of `from`.
+/// The changed lines must appear exactly once in `current`; the line on
+/// each side of them is used to tell repeats apart (and to place inserted
+/// lines). Otherwise `None`.
+fn rebase(current: &str, from: &str, to: &str) -> Option<String> {
+    let from: Vec<&str> = from.split_inclusive('\n').collect();
+    let to: Vec<&str> = to.split_inclusive('\n').collect();
+    let current: Vec<&str> = current.split_inclusive('\n').collect();
+
+    let prefix = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let suffix = from[prefix..]
+        .iter()
+        .rev()
+        .zip(to[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let replaced = &from[prefix..from.len() - suffix];
+    let replacement = &to[prefix..to.len() - suffix];
+
+    let with_context = (usize::from(prefix > 0), usize::from(suffix > 0));
+    let start = [(0, 0), with_context]
+        .into_iter()
+        .find_map(|(before, after)| {
+            let pattern = &from[prefix - before..from.len() - suffix + after];
+            if pattern.is_empty() {
+                return None;
+            }
+            let mut matches = current
+                .windows(pattern.len())
+                .enumerate()
+                .filter(|(_, window)| window == &pattern)
+                .map(|(i, _)| i + before);
+            let at = matches.next()?;
+            matches.next().is_none().then_some(at)
+        })?;
+
+    let mut out: String = current[..start].concat();
+    out.push_str(&replacement.concat());
+    out.push_str(&current[start + replaced.len()..].concat());
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn undo_and_redo_restore_both_sides() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let archive = tmp.path().join("TASKS.archive.md");
+        fs::write(&path, "# Phase 0: A\n### [ ] T1: a\n").unwrap();
+
+        let mut history = EditHistory::default();
+        let before = Snapshot::take(&[&path, &archive]);
+        fs::write(&path, "# Phase 0: A\n### [x] T1: a\n").unwrap();
+        fs::write(&archive, "archived\n").unwrap();
+        history.record("mark T1 done", before);
+
+        // Nothing changed: nothing recorded
+        history.record("no-op", Snapshot::take(&[&path]));
+
+        assert_eq!(history.undo().unwrap().as_deref(), Some("mark T1 done"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 0: A\n### [ ] T1: a\n"
+        );
+        assert!(!archive.exists());
+        assert!(!history.can_undo());
+
+        assert_eq!(history.redo().unwrap().as_deref(), Some("mark T1 done"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 0: A\n### [x] T1: a\n"
+        );
+        assert_eq!(fs::read_to_string(&archive).unwrap(), "archived\n");
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn undo_keeps_external_changes_elsewhere_in_the_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(&path, "# Phase 0: A\n### [ ] T1: a\n### [ ] T2: b\n").unwrap();
+
+        let mut history = EditHistory::default();
+        let before = Snapshot::take(&[&path]);
+        fs::write(&path, "# Phase 0: A\n### [ ] T1: renamed\n### [ ] T2: b\n").unwrap();
+        history.record("rename T1", before);
+
+        // An agent marks T2 done in the meantime
+        fs::write(&path, "# Phase 0: A\n### [ ] T1: renamed\n### [x] T2: b\n").unwrap();
+        history.undo().unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 0: A\n### [ ] T1: a\n### [x] T2: b\n"
+        );
+    }
+
+    #[test]
+    fn undo_refuses_when_the_edited_lines_changed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(&path, "# Phase 0: A\n### [ ] T1: a\n").unwrap();
+
+        let mut history = EditHistory::default();
+        let before = Snapshot::take(&[&path]);
+        fs::write(&path, "# Phase 0: A\n### [ ] T1: renamed\n").unwrap();
+        history.record("rename T1", before);
+
+        let external = "# Phase 0: A\n### [x] T1: renamed\n";
+        fs::write(&path, external).unwrap();
+        let err = history.undo().unwrap_err();
+        assert!(err.to_string().contains("changed since"), "{err}");
+        assert_eq!(fs::read_to_string(&path).unwrap(), external);
+        assert!(history.can_undo());
+    }
+
+    #[test]
+    fn rebase_needs_a_unique_match() {
+        let from = "a\nx\nb\n";
+        let to = "a\ny\nb\n";
+        assert_eq!(
+            rebase("0\na\nx\nb\n", from, to).as_deref(),
+            Some("0\na\ny\nb\n")
+        );
+        assert!(rebase("a\nx\nb\na\nx\nb\n", from, to).is_none());
+
+        // Inserted lines are removed again using the lines around them
+        assert_eq!(
+            rebase("a\nnew\nb\nc\n", "a\nnew\nb\n", "a\nb\n").as_deref(),
+            Some("a\nb\nc\n")
+        );
+    }
+}
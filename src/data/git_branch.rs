@@ -0,0 +1,107 @@
+//! Git branch and worktree of an agent's working directory
+//!
+//! Resolved by reading `.git` and `HEAD` from disk rather than running git,
+//! since it is repeated for every agent after each batch of hook events.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The checkout a directory belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkout {
+    /// Branch name, or `detached <hash>` when HEAD is not on a branch
+    pub branch: String,
+    /// Directory name of a linked worktree; `None` for the main checkout
+    pub worktree: Option<String>,
+    /// Git directory shared by all worktrees of the repository, so checkouts
+    /// of different repositories never compare equal
+    pub repo: PathBuf,
+}
+
+impl Checkout {
+    /// Whether both are on the same branch of the same repository
+    pub fn same_branch(&self, other: &Checkout) -> bool {
+        self.repo == other.repo && self.branch == other.branch
+    }
+}
+
+/// The checkout containing `dir`, or `None` outside a git repository
+pub fn detect(dir: &Path) -> Option<Checkout> {
+    let (top, dot_git) = dir
+        .ancestors()
+        .map(|d| (d, d.join(".git")))
+        .find(|(_, dot_git)| dot_git.exists())?;
+
+    let (git_dir, worktree) = if dot_git.is_dir() {
+        (dot_git, None)
+    } else {
+        // Linked worktree: `.git` is a file holding `gitdir: <path>`
+        let content = fs::read_to_string(&dot_git).ok()?;
+        let target = content.trim().strip_prefix("gitdir:")?.trim();
+        let name = top.file_name().map(|n| n.to_string_lossy().into_owned());
+        (top.join(target), name)
+    };
+    let repo = match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir.clone(),
+    };
+
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    let branch = match head.strip_prefix("ref:") {
+        Some(reference) => {
+            let reference = reference.trim();
+            reference
+                .strip_prefix("refs/heads/")
+                .unwrap_or(reference)
+                .to_string()
+        }
+        None => format!("detached {}", &head[..head.len().min(7)]),
+    };
+
+    Some(Checkout {
+        branch,
+        worktree,
+        repo: fs::canonicalize(&repo).unwrap_or(repo),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_branch_worktree_and_detached_head() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let main = tmp.path().join("repo");
+        let git = main.join(".git");
+        fs::create_dir_all(git.join("worktrees/feature")).unwrap();
+        fs::create_dir_all(main.join("src/deep")).unwrap();
+        fs::write(git.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+
+        let checkout = detect(&main.join("src/deep")).unwrap();
+        assert_eq!(checkout.branch, "main");
+        assert_eq!(checkout.worktree, None);
+
+        // A linked worktree next to the main checkout
+        let wt = tmp.path().join("feature");
+        fs::create_dir_all(&wt).unwrap();
+        fs::write(
+            wt.join(".git"),
+            format!("gitdir: {}\n", git.join("worktrees/feature").display()),
+        )
+        .unwrap();
+        let wt_git = git.join("worktrees/feature");
+        fs::write(wt_git.join("commondir"), "../..\n").unwrap();
+        fs::write(wt_git.join("HEAD"), "ref: refs/heads/feat/login\n").unwrap();
+
+        let linked = detect(&wt).unwrap();
+        assert_eq!(linked.branch, "feat/login");
+        assert_eq!(linked.worktree.as_deref(), Some("feature"));
+        assert_eq!(linked.repo, checkout.repo);
+        assert!(!linked.same_branch(&checkout));
+
+        fs::write(wt_git.join("HEAD"), "0123456789abcdef\n").unwrap();
+        assert_eq!(detect(&wt).unwrap().branch, "detached 0123456");
+    }
+}
@@ -0,0 +1,137 @@
+//! Git commits that mention tasks
+//!
+//! Reads the subjects of the project's recent commits and matches them to
+//! task IDs (`P1-T3: add parser`, `fix(P1-T3) ...`), so the detail panel can
+//! list the commits a task produced and open their diff.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::actions::shell_quote;
+
+/// Commits read from `git log`, newest first
+const MAX_COMMITS: usize = 500;
+
+/// Separates the hash from the subject in the log format
+const FIELD_SEP: char = '\u{1f}';
+
+/// One commit from the log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitCommit {
+    pub hash: String,
+    pub subject: String,
+}
+
+impl GitCommit {
+    /// Abbreviated hash shown in the detail panel
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+
+    /// Whether the subject names `task_id` as a whole ID, so `P1-T3` does
+    /// not match `P1-T30` or `P1-T3.1`
+    pub fn mentions(&self, task_id: &str) -> bool {
+        if task_id.is_empty() {
+            return false;
+        }
+        let is_id_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+        self.subject.match_indices(task_id).any(|(start, _)| {
+            let before = self.subject[..start].chars().next_back();
+            let mut after = self.subject[start + task_id.len()..].chars();
+            let next = after.next();
+            let continues = match next {
+                Some('.') => after.next().is_some_and(|c| c.is_ascii_digit()),
+                Some(c) => is_id_char(c),
+                None => false,
+            };
+            !before.is_some_and(is_id_char) && !continues
+        })
+    }
+}
+
+/// Parse `git log --format=%H%x1f%s` output
+pub fn parse_log(output: &str) -> Vec<GitCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (hash, subject) = line.split_once(FIELD_SEP)?;
+            Some(GitCommit {
+                hash: hash.trim().to_string(),
+                subject: subject.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Recent commits of the repository containing `dir`; empty when it is not
+/// a git repository or git is not installed
+pub fn read_log(dir: &Path) -> Vec<GitCommit> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["log", "-n", &MAX_COMMITS.to_string(), "--format=%H%x1f%s"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            parse_log(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Shell command showing the diffs of `commits` in `dir`
+pub fn show_command(dir: &Path, commits: &[&GitCommit]) -> String {
+    let hashes: Vec<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+    format!(
+        "git -C {} show {}",
+        shell_quote(&dir.to_string_lossy()),
+        hashes.join(" ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(subject: &str) -> GitCommit {
+        GitCommit {
+            hash: "0123456789abcdef".to_string(),
+            subject: subject.to_string(),
+        }
+    }
+
+    #[test]
+    fn mentions_whole_task_ids_only() {
+        assert!(commit("P1-T3: add parser").mentions("P1-T3"));
+        assert!(commit("fix(P1-T3) handle EOF").mentions("P1-T3"));
+        assert!(commit("P1-T2, P1-T3 done").mentions("P1-T3"));
+        assert!(commit("finish P1-T3.").mentions("P1-T3"));
+        assert!(!commit("P1-T30: other").mentions("P1-T3"));
+        assert!(!commit("P1-T3.1: subtask").mentions("P1-T3"));
+        assert!(!commit("XP1-T3 typo").mentions("P1-T3"));
+        assert!(commit("P0-T0.1: init").mentions("P0-T0.1"));
+        assert_eq!(commit("x").short_hash(), "0123456");
+    }
+
+    #[test]
+    fn log_output_is_parsed_and_quoted() {
+        let commits = parse_log("abc\u{1f}P1-T1: one\ndef\u{1f}two: a\u{1f}b\n\n");
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "P1-T1: one");
+        assert_eq!(commits[1].subject, "two: a\u{1f}b");
+        assert_eq!(
+            show_command(Path::new("my dir"), &[&commits[0], &commits[1]]),
+            "git -C 'my dir' show abc def"
+        );
+    }
+
+    #[test]
+    fn read_log_outside_a_repository_is_empty() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        // The temp dir may sit inside a checkout; only the call must not fail
+        let _ = read_log(tmp.path());
+        assert!(read_log(&tmp.path().join("missing")).is_empty());
+    }
+}
@@ -5,8 +5,10 @@
 //! Gracefully skips malformed lines.
 
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
-use std::path::Path;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
 /// Raw event as deserialized from JSON Lines
 #[derive(Debug, Clone, Deserialize)]
@@ -20,10 +22,58 @@ pub struct HookEvent {
     pub tool_name: Option<String>,
     #[serde(default)]
     pub error_message: Option<String>,
+    /// File the tool call touched (Edit/Write/Read/NotebookEdit)
+    #[serde(default, deserialize_with = "slash_path")]
+    pub file_path: Option<String>,
+    /// Short summary of the other tool arguments (Bash command, search
+    /// pattern, URL), for telling repeated calls apart
+    #[serde(default)]
+    pub args: Option<String>,
+    /// Working directory of the session that ran the tool
+    #[serde(default, deserialize_with = "slash_path")]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+}
+
+/// Whether `path` is spelled the Windows way: a drive letter, a UNC share,
+/// or backslashes only
+fn is_windows_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let drive = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    drive || path.starts_with(r"\\") || (path.contains('\\') && !path.contains('/'))
+}
+
+/// Windows paths with `/` separators, which Windows accepts as well, so a
+/// file reads the same in every view and loop detection; others as given
+pub fn to_slashes(path: &str) -> String {
+    if is_windows_path(path) {
+        path.replace('\\', "/")
+    } else {
+        path.to_string()
+    }
+}
+
+fn slash_path<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(Option::<String>::deserialize(deserializer)?.map(|path| to_slashes(&path)))
+}
+
+/// Token usage and cost reported alongside an event (optional)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
 }
 
 /// Known event types from Claude Code hooks
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventType {
     AgentStart,
@@ -33,6 +83,19 @@ pub enum EventType {
     Error,
 }
 
+impl EventType {
+    /// The `event_type` value in the JSONL
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventType::AgentStart => "agent_start",
+            EventType::AgentEnd => "agent_end",
+            EventType::ToolStart => "tool_start",
+            EventType::ToolEnd => "tool_end",
+            EventType::Error => "error",
+        }
+    }
+}
+
 /// Result of parsing a JSONL file: events + any parse errors
 #[derive(Debug)]
 pub struct ParseResult {
@@ -50,32 +113,60 @@ pub struct ParseError {
 
 /// Parse a JSONL string into hook events, collecting errors for malformed lines
 pub fn parse_hook_events(input: &str) -> ParseResult {
-    let mut events = Vec::new();
-    let mut errors = Vec::new();
+    let mut result = ParseResult {
+        events: Vec::new(),
+        errors: Vec::new(),
+    };
 
     for (idx, line) in input.lines().enumerate() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
+        parse_hook_line(line, idx + 1, &mut result);
+    }
 
-        match serde_json::from_str::<HookEvent>(trimmed) {
-            Ok(event) => events.push(event),
-            Err(e) => errors.push(ParseError {
-                line_number: idx + 1,
-                line_content: trimmed.to_string(),
-                error: e.to_string(),
-            }),
-        }
+    result
+}
+
+/// Parse one JSONL line into `result`, skipping blank lines
+pub fn parse_hook_line(line: &str, line_number: usize, result: &mut ParseResult) {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    match serde_json::from_str::<HookEvent>(trimmed) {
+        Ok(event) => result.events.push(event),
+        Err(e) => result.errors.push(ParseError {
+            line_number,
+            line_content: trimmed.to_string(),
+            error: e.to_string(),
+        }),
     }
+}
 
-    ParseResult { events, errors }
+/// Why hook events could not be ingested. Malformed lines are
+/// [`ParseError`]s in the [`ParseResult`], not failures.
+#[derive(Debug, thiserror::Error)]
+pub enum HookIngestError {
+    #[error("cannot read hooks directory {}: {source}", dir.display())]
+    ReadDir { dir: PathBuf, source: io::Error },
+    #[error("cannot read hook events {}: {source}", path.display())]
+    ReadFile { path: PathBuf, source: io::Error },
 }
 
-/// Parse a JSONL file from disk
-pub fn parse_hook_file(path: &Path) -> Result<ParseResult, std::io::Error> {
-    let content = std::fs::read_to_string(path)?;
-    Ok(parse_hook_events(&content))
+/// Parse a JSONL file from disk, line by line rather than as one string
+pub fn parse_hook_file(path: &Path) -> Result<ParseResult, HookIngestError> {
+    let read_file = |source| HookIngestError::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    };
+    let reader = BufReader::new(File::open(path).map_err(read_file)?);
+    let mut result = ParseResult {
+        events: Vec::new(),
+        errors: Vec::new(),
+    };
+    for (idx, line) in reader.lines().enumerate() {
+        parse_hook_line(&line.map_err(read_file)?, idx + 1, &mut result);
+    }
+    Ok(result)
 }
 
 /// Filter events by agent ID
@@ -116,6 +207,7 @@ mod tests {
         assert_eq!(result.events[1].event_type, EventType::ToolStart);
         assert_eq!(result.events[2].event_type, EventType::ToolEnd);
         assert_eq!(result.events[5].event_type, EventType::AgentEnd);
+        assert_eq!(result.events[0].event_type.as_str(), "agent_start");
     }
 
     #[test]
@@ -131,10 +223,33 @@ mod tests {
 
     #[test]
     fn parse_tool_event_has_tool_name() {
-        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let input = include_str!("../../tests/fixtures/tool_file_events.jsonl");
         let result = parse_hook_events(input);
         let tool_start = &result.events[1];
         assert_eq!(tool_start.tool_name.as_deref(), Some("Read"));
+        assert_eq!(tool_start.file_path.as_deref(), Some("src/main.rs"));
+        assert!(result.events[0].file_path.is_none());
+        assert!(result.events[0].cwd.is_none());
+
+        let with_cwd = parse_hook_events(
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"t","session_id":"s","cwd":"/work/repo"}"#,
+        );
+        assert_eq!(with_cwd.events[0].cwd.as_deref(), Some("/work/repo"));
+    }
+
+    #[test]
+    fn windows_paths_use_forward_slashes() {
+        let windows = parse_hook_events(
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"t","session_id":"s","file_path":"C:\\repo\\src\\app.rs","cwd":"C:\\repo"}"#,
+        );
+        let event = &windows.events[0];
+        assert_eq!(event.file_path.as_deref(), Some("C:/repo/src/app.rs"));
+        assert_eq!(event.cwd.as_deref(), Some("C:/repo"));
+
+        assert_eq!(to_slashes(r"\\server\share\x.md"), "//server/share/x.md");
+        assert_eq!(to_slashes(r"src\lib.rs"), "src/lib.rs");
+        // A backslash in a POSIX path is part of a file name
+        assert_eq!(to_slashes(r"/tmp/a\b"), r"/tmp/a\b");
     }
 
     #[test]
@@ -209,6 +324,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_usage_when_present() {
+        let input = r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:06Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Read","usage":{"input_tokens":1200,"output_tokens":300,"cost_usd":0.012}}"#;
+        let result = parse_hook_events(input);
+        let usage = result.events[0].usage.as_ref().expect("usage parsed");
+        assert_eq!(usage.input_tokens, 1200);
+        assert_eq!(usage.output_tokens, 300);
+        assert!((usage.cost_usd - 0.012).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn usage_defaults_to_none() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = parse_hook_events(input);
+        assert!(result.events.iter().all(|e| e.usage.is_none()));
+    }
+
     #[test]
     fn parse_file_from_disk() {
         let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
@@ -220,6 +352,9 @@ mod tests {
     #[test]
     fn parse_file_nonexistent() {
         let result = parse_hook_file(Path::new("/nonexistent/path.jsonl"));
-        assert!(result.is_err());
+        assert!(matches!(
+            result,
+            Err(HookIngestError::ReadFile { ref path, .. }) if path.ends_with("path.jsonl")
+        ));
     }
 }
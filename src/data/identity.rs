@@ -0,0 +1,75 @@
+//! Logical agent identities across restarts
+//!
+//! Orchestrators number agent instances (`backend-specialist-1`, `-2`, ...)
+//! and start new ones after a crash or a new session. With `agent_identity`
+//! set in board.toml to a regex, every agent ID it matches is folded into
+//! one logical agent: the `role` capture group, else the first group, else
+//! the whole match. History, stats and the agent panel then aggregate by
+//! role, and the instance IDs seen are kept on the agent. IDs the regex does
+//! not match stay as they are.
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Compiled `agent_identity` pattern
+#[derive(Debug, Clone)]
+pub struct AgentIdentity {
+    pattern: Regex,
+}
+
+impl AgentIdentity {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        let pattern = Regex::new(pattern).map_err(|e| format!("invalid `agent_identity`: {e}"))?;
+        Ok(Self { pattern })
+    }
+
+    /// Logical identity of the agent instance `agent_id`; `None` when it is
+    /// its own identity
+    pub fn resolve(&self, agent_id: &str) -> Option<String> {
+        let captures = self.pattern.captures(agent_id)?;
+        let role = captures
+            .name("role")
+            .or_else(|| captures.get(1))
+            .or_else(|| captures.get(0))?
+            .as_str();
+        (!role.is_empty() && role != agent_id).then(|| role.to_string())
+    }
+}
+
+/// Serialized as the pattern text
+impl Serialize for AgentIdentity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.pattern.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for AgentIdentity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Self::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instances_resolve_to_their_role() {
+        let numbered = AgentIdentity::new(r"^(.+)-\d+$").unwrap();
+        assert_eq!(
+            numbered.resolve("backend-specialist-2").as_deref(),
+            Some("backend-specialist")
+        );
+        assert_eq!(numbered.resolve("main"), None);
+
+        let named = AgentIdentity::new(r"^(?:team-\w+/)?(?P<role>[a-z-]+?)(?:-\d+)?$").unwrap();
+        assert_eq!(
+            named.resolve("team-a/frontend-3").as_deref(),
+            Some("frontend")
+        );
+        assert_eq!(named.resolve("frontend"), None);
+
+        assert!(AgentIdentity::new("(").is_err());
+    }
+}
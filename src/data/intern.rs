@@ -0,0 +1,60 @@
+//! Shared names
+//!
+//! The same few agent, task and tool names repeat in every activity entry,
+//! tool call and timeline entry. Interning them keeps one allocation per
+//! distinct name however long the dashboard runs.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A set of shared strings; `intern` hands out clones of the stored one
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    names: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    /// The shared copy of `name`, stored on first use
+    pub fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(shared) = self.names.get(name) {
+            return Arc::clone(shared);
+        }
+        let shared: Arc<str> = Arc::from(name);
+        self.names.insert(Arc::clone(&shared));
+        shared
+    }
+
+    /// Forget names nothing else refers to any more
+    pub fn prune(&mut self) {
+        self.names.retain(|name| Arc::strong_count(name) > 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_names_share_one_allocation_until_pruned() {
+        let mut names = Interner::default();
+        let a = names.intern("Read");
+        let b = names.intern("Read");
+        assert!(Arc::ptr_eq(&a, &b));
+        names.intern("Bash");
+        assert_eq!(names.len(), 2);
+
+        names.prune();
+        assert_eq!(names.len(), 1);
+        drop((a, b));
+        names.prune();
+        assert!(names.is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+//! Background history loader
+//!
+//! Reads and aggregates existing hook event files on a worker thread so the
+//! TUI can render TASKS.md data immediately. Files are streamed a batch of
+//! lines at a time, so a week of logs costs the aggregated state rather
+//! than every parsed event at once. Progress (by bytes read) and the
+//! aggregated result are sent over a tokio channel, like the file watcher.
+//! The result comes with how far each file was read, so the dashboard can
+//! go on applying only what is appended afterwards.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use tokio::sync::mpsc;
+
+use crate::data::commands;
+use crate::data::hook_parser::HookEvent;
+use crate::data::identity::AgentIdentity;
+use crate::data::state::DashboardState;
+use crate::data::store::Store;
+use crate::data::tail::{self, Tail};
+
+/// Messages sent from the loader thread
+#[derive(Debug)]
+pub enum LoadMessage {
+    /// Fraction of history bytes processed so far (0.0..=1.0)
+    Progress(f32),
+    /// Aggregation finished; holds the event-derived state (no phases) and
+    /// the read positions it covers
+    Finished(Box<DashboardState>, Tail),
+}
+
+/// Collect all `*.jsonl` event files in `dirs` with their sizes (the
+/// dashboard's own commands file is not one)
+pub fn collect_jsonl_files(dirs: &[PathBuf]) -> Vec<(PathBuf, u64)> {
+    let mut files = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("jsonl")
+                && !commands::is_commands_file(&path)
+            {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                files.push((path, size));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Most recently modified `*.jsonl` file across `dirs`
+pub fn newest_jsonl_file(dirs: &[PathBuf]) -> Option<PathBuf> {
+    collect_jsonl_files(dirs)
+        .into_iter()
+        .filter_map(|(path, _)| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max()
+        .map(|(_, path)| path)
+}
+
+/// Aggregate one file into `state` a batch of lines at a time, reporting
+/// progress as bytes are consumed and recording in `tail` how far the
+/// complete lines go
+fn load_file(
+    path: &Path,
+    state: &mut DashboardState,
+    done_before: u64,
+    total_bytes: u64,
+    tail: &mut Tail,
+    tx: &mpsc::UnboundedSender<LoadMessage>,
+) {
+    let Ok(file) = File::open(path) else {
+        return;
+    };
+    let offset = tail::read_lines_batched(
+        BufReader::new(file),
+        |batch| state.update_from_events(&batch),
+        |read| {
+            let done = progress(done_before + read, total_bytes);
+            let _ = tx.send(LoadMessage::Progress(done));
+        },
+    );
+    tail.mark_read(path, offset);
+}
+
+fn progress(done: u64, total: u64) -> f32 {
+    if total == 0 {
+        1.0
+    } else {
+        (done as f32 / total as f32).min(1.0)
+    }
+}
+
+/// Feed the events in the `*.jsonl` files of `dirs` to `apply` a batch of
+/// lines at a time, file by file (so not in time order across files), for
+/// the one-shot commands: they hold what they fold the events into rather
+/// than every event at once
+pub fn for_each_batch(dirs: &[PathBuf], mut apply: impl FnMut(Vec<HookEvent>)) {
+    for (path, _) in collect_jsonl_files(dirs) {
+        let Ok(file) = File::open(&path) else {
+            continue;
+        };
+        tail::read_lines_batched(BufReader::new(file), &mut apply, |_| {});
+    }
+}
+
+/// Load and aggregate every `*.jsonl` file in `dirs` on a background thread.
+///
+/// Only events matching `session_filter` update agent state, as in
+/// `DashboardState::update_from_events`, and agents are folded by
+/// `agent_identity`.
+pub fn spawn_history_load(
+    dirs: Vec<PathBuf>,
+    session_filter: Option<String>,
+    agent_identity: Option<AgentIdentity>,
+) -> mpsc::UnboundedReceiver<LoadMessage> {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    std::thread::spawn(move || {
+        let files = collect_jsonl_files(&dirs);
+        let total_bytes: u64 = files.iter().map(|(_, size)| size).sum();
+        let mut done_bytes = 0u64;
+        let mut tail = Tail::default();
+
+        let mut state = DashboardState {
+            session_filter,
+            agent_identity,
+            ..Default::default()
+        };
+        let _ = tx.send(LoadMessage::Progress(0.0));
+        for (path, size) in &files {
+            load_file(path, &mut state, done_bytes, total_bytes, &mut tail, &tx);
+            done_bytes += size;
+        }
+        let _ = tx.send(LoadMessage::Finished(Box::new(state), tail));
+    });
+
+    rx
+}
+
+/// Start from the collector's snapshot `store` and aggregate only what was
+/// appended to the files in `dirs` since, on a background thread
+pub fn spawn_store_load(
+    store: Store,
+    dirs: Vec<PathBuf>,
+    agent_identity: Option<AgentIdentity>,
+) -> mpsc::UnboundedReceiver<LoadMessage> {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    std::thread::spawn(move || {
+        let mut tail = Tail::from_offsets(store.offsets.clone());
+        let mut state = store.into_state();
+        state.agent_identity = agent_identity;
+        let _ = tx.send(LoadMessage::Progress(0.0));
+        for (path, _) in collect_jsonl_files(&dirs) {
+            tail.read_new_batches(&path, |batch| state.update_from_events(&batch));
+        }
+        let _ = tx.send(LoadMessage::Progress(1.0));
+        let _ = tx.send(LoadMessage::Finished(Box::new(state), tail));
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks")
+    }
+
+    #[test]
+    fn collects_only_jsonl_files() {
+        let files = collect_jsonl_files(&[fixtures_dir()]);
+        assert_eq!(files.len(), 3);
+        assert!(files.iter().all(|(_, size)| *size > 0));
+    }
+
+    #[test]
+    fn missing_dir_is_skipped() {
+        let files = collect_jsonl_files(&[PathBuf::from("/nonexistent/hooks")]);
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn for_each_batch_reads_every_file() {
+        let mut count = 0;
+        for_each_batch(&[fixtures_dir(), PathBuf::from("/nonexistent")], |batch| {
+            count += batch.len()
+        });
+        let expected: usize = collect_jsonl_files(&[fixtures_dir()])
+            .iter()
+            .map(|(path, _)| {
+                crate::data::hook_parser::parse_hook_file(path)
+                    .unwrap()
+                    .events
+                    .len()
+            })
+            .sum();
+        assert!(count > 0);
+        assert_eq!(count, expected);
+    }
+
+    #[test]
+    fn progress_handles_empty_total() {
+        assert_eq!(progress(0, 0), 1.0);
+        assert!((progress(21, 50) - 0.42).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn background_load_matches_sync_load() {
+        let mut rx = spawn_history_load(vec![fixtures_dir()], None, None);
+
+        let mut last_progress = 0.0;
+        let loaded = loop {
+            match rx.recv().await.expect("loader should finish") {
+                LoadMessage::Progress(p) => {
+                    assert!(p >= last_progress);
+                    last_progress = p;
+                }
+                LoadMessage::Finished(state, tail) => {
+                    // Every file was read to its end
+                    let mut tail = tail;
+                    for (path, _) in collect_jsonl_files(&[fixtures_dir()]) {
+                        assert!(tail.read_new(&path).is_empty());
+                    }
+                    break state;
+                }
+            }
+        };
+        assert!((last_progress - 1.0).abs() < f32::EPSILON);
+
+        let mut expected = DashboardState::default();
+        expected.load_hook_events(&fixtures_dir()).unwrap();
+        assert_eq!(loaded.agents.len(), expected.agents.len());
+        assert_eq!(loaded.recent_errors.len(), expected.recent_errors.len());
+        assert_eq!(loaded.sessions.len(), expected.sessions.len());
+    }
+}
@@ -1,5 +1,22 @@
+pub mod auto_sync;
+pub mod commands;
+pub mod edit_history;
+pub mod git_branch;
+pub mod git_log;
 pub mod hook_parser;
+pub mod identity;
+pub mod intern;
+pub mod loader;
+pub mod remote;
+pub mod session_history;
+pub mod source;
 pub mod state;
+pub mod status_file;
+pub mod store;
+pub mod tail;
 pub mod tasks_parser;
 pub mod tasks_writer;
+pub mod transitions;
+pub mod versioned_file;
 pub mod watcher;
+pub mod write_guard;
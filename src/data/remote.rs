@@ -0,0 +1,177 @@
+//! Remote event source over SSH (`--remote user@host:/path`)
+//!
+//! Runs `tail -F` on the remote `*.jsonl` files through an `ssh` subprocess
+//! and appends every line it prints to a local mirror file, which the usual
+//! loader and file watcher then read like any events directory. The mirror
+//! starts empty on each connect because the remote history is replayed, and
+//! a dropped connection is retried after a short pause.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::actions::shell_quote;
+
+/// Pause before reconnecting after ssh exits
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// A remote events directory, `user@host:/path/to/events`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remote {
+    /// What ssh connects to: `host`, `user@host` or a `~/.ssh/config` alias
+    pub destination: String,
+    pub dir: String,
+}
+
+impl FromStr for Remote {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((destination, dir)) if !destination.is_empty() && !dir.is_empty() => Ok(Self {
+                destination: destination.to_string(),
+                dir: dir.trim_end_matches('/').to_string(),
+            }),
+            _ => Err(format!("expected user@host:/path, got `{s}`")),
+        }
+    }
+}
+
+impl Remote {
+    /// ssh arguments that stream every line of the remote event files,
+    /// history first. The destination follows `--` so one starting with `-`
+    /// is not read as an ssh option; the glob and a leading `~/` stay
+    /// unquoted so the remote shell expands them.
+    pub fn ssh_args(&self) -> Vec<String> {
+        let dir = match self.dir.strip_prefix("~/") {
+            Some(rest) => format!("~/{}", shell_quote(rest)),
+            None if self.dir.is_empty() => "/".to_string(),
+            None => shell_quote(&self.dir),
+        };
+        vec![
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+            "-o".to_string(),
+            "ServerAliveInterval=15".to_string(),
+            "--".to_string(),
+            self.destination.clone(),
+            format!("tail -q -n +1 -F {dir}/*.jsonl"),
+        ]
+    }
+
+    /// Local directory mirroring this remote under `cache_root`
+    pub fn mirror_dir(&self, cache_root: &Path) -> PathBuf {
+        let name: String = format!("{}{}", self.destination, self.dir)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        cache_root.join(name)
+    }
+}
+
+/// Append complete lines from `reader` to `out`, flushing each so the file
+/// watcher sees it right away
+fn copy_lines(reader: impl BufRead, out: &mut File) -> io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(out, "{line}")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Start mirroring `remote` into `events.jsonl` in `mirror_dir` on a
+/// background thread, reconnecting for as long as the process runs
+pub fn spawn(remote: Remote, mirror_dir: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(mirror_dir)?;
+    let mirror = mirror_dir.join("events.jsonl");
+    File::create(&mirror)?;
+    std::thread::spawn(move || loop {
+        let child = Command::new("ssh")
+            .args(remote.ssh_args())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Ok(mut child) = child {
+            let out = OpenOptions::new().write(true).truncate(true).open(&mirror);
+            if let (Some(stdout), Ok(mut out)) = (child.stdout.take(), out) {
+                let _ = copy_lines(BufReader::new(stdout), &mut out);
+            }
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        std::thread::sleep(RECONNECT_DELAY);
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_destination_and_dir() {
+        let remote: Remote = "ci@build-01:/home/ci/.claude/dashboard/".parse().unwrap();
+        assert_eq!(remote.destination, "ci@build-01");
+        assert_eq!(remote.dir, "/home/ci/.claude/dashboard");
+        assert_eq!(
+            remote.ssh_args().last().unwrap(),
+            "tail -q -n +1 -F '/home/ci/.claude/dashboard'/*.jsonl"
+        );
+        assert_eq!(
+            remote.mirror_dir(Path::new("/cache")),
+            Path::new("/cache/ci_build-01_home_ci_.claude_dashboard")
+        );
+        let home: Remote = "box:~/.claude/dashboard".parse().unwrap();
+        assert_eq!(
+            home.ssh_args().last().unwrap(),
+            "tail -q -n +1 -F ~/'.claude/dashboard'/*.jsonl"
+        );
+
+        assert!("build-01".parse::<Remote>().is_err());
+        assert!(":/path".parse::<Remote>().is_err());
+        assert!("host:".parse::<Remote>().is_err());
+    }
+
+    #[test]
+    fn destination_is_never_an_ssh_option() {
+        let remote: Remote = "-oProxyCommand=touch /tmp/pwned:/events".parse().unwrap();
+        assert_eq!(
+            remote.ssh_args(),
+            vec![
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "ServerAliveInterval=15",
+                "--",
+                "-oProxyCommand=touch /tmp/pwned",
+                "tail -q -n +1 -F '/events'/*.jsonl",
+            ]
+        );
+    }
+
+    #[test]
+    fn copies_non_empty_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("events.jsonl");
+        let mut out = File::create(&path).unwrap();
+        copy_lines(io::Cursor::new("{\"a\":1}\n\n{\"b\":2}\n"), &mut out).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "{\"a\":1}\n{\"b\":2}\n"
+        );
+    }
+}
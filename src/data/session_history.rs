@@ -0,0 +1,108 @@
+//! Past session summaries (`sessions.json`)
+//!
+//! The dashboard keeps a summary of every hook session it has seen in the
+//! events directory: start and end, agents, tasks finished, errors, cost and
+//! a timeline of agent starts, ends and errors. Sessions stay listed in the
+//! history view after their event files are cleaned up.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::state::SessionSummary;
+use crate::data::versioned_file::VersionedFile;
+
+/// File name of the session history, inside the events directory
+pub const HISTORY_FILE: &str = "sessions.json";
+
+const FILE: VersionedFile = VersionedFile::new(HISTORY_FILE, 1);
+
+/// Whether `path` is the history file or its partial copy (not an event
+/// file)
+pub fn is_history_file(path: &Path) -> bool {
+    FILE.matches(path)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct HistoryFile<S> {
+    sessions: S,
+}
+
+/// The sessions stored in `dir`; empty when there are none or the file is
+/// unreadable
+pub fn read(dir: &Path) -> Vec<SessionSummary> {
+    FILE.read::<HistoryFile<Vec<SessionSummary>>>(dir)
+        .map(|file| file.sessions)
+        .unwrap_or_default()
+}
+
+/// Stored sessions updated with `current` ones, newest first. A session in
+/// both keeps the summary covering more events, so a partly cleaned up
+/// event file does not shrink it.
+pub fn merge(
+    stored: Vec<SessionSummary>,
+    current: &HashMap<String, SessionSummary>,
+) -> Vec<SessionSummary> {
+    let mut sessions: HashMap<String, SessionSummary> = stored
+        .into_iter()
+        .map(|s| (s.session_id.clone(), s))
+        .collect();
+    for session in current.values() {
+        let stored = sessions.get(&session.session_id);
+        if !matches!(stored, Some(s) if s.event_count > session.event_count) {
+            sessions.insert(session.session_id.clone(), session.clone());
+        }
+    }
+    let mut sessions: Vec<SessionSummary> = sessions.into_values().collect();
+    sessions.sort_by(|a, b| {
+        b.started_at
+            .cmp(&a.started_at)
+            .then_with(|| a.session_id.cmp(&b.session_id))
+    });
+    sessions
+}
+
+/// Replace the history in `dir` in one step, so readers never see a
+/// half-written file
+pub fn write(dir: &Path, sessions: &[SessionSummary]) -> std::io::Result<()> {
+    FILE.write(dir, &HistoryFile { sessions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+    use crate::data::state::DashboardState;
+
+    fn state(lines: &[&str]) -> DashboardState {
+        let mut state = DashboardState::default();
+        state.update_from_events(&parse_hook_events(&lines.join("\n")).events);
+        state
+    }
+
+    #[test]
+    fn keeps_sessions_whose_events_are_gone() {
+        let old = r#"{"event_type":"agent_end","timestamp":"2026-02-07T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"old","usage":{"cost_usd":0.5}}"#;
+        let new = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"b","task_id":"P1-T2","session_id":"new"}"#;
+        let tmp = tempfile::TempDir::new().unwrap();
+
+        let first = state(&[old]);
+        write(tmp.path(), &merge(read(tmp.path()), &first.sessions)).unwrap();
+
+        // The old session's events were cleaned up
+        let second = state(&[new]);
+        let sessions = merge(read(tmp.path()), &second.sessions);
+        let ids: Vec<&str> = sessions.iter().map(|s| s.session_id.as_str()).collect();
+        assert_eq!(ids, ["new", "old"]);
+        assert_eq!(sessions[1].cost_usd, 0.5);
+        assert!(sessions[1].tasks_finished.contains("P1-T1"));
+        assert_eq!(sessions[1].timeline.len(), 1);
+
+        // A summary of fewer events does not replace the stored one
+        let mut shrunk = first.sessions.clone();
+        shrunk.get_mut("old").unwrap().event_count = 0;
+        assert_eq!(merge(sessions, &shrunk)[1].event_count, 1);
+        assert!(is_history_file(&tmp.path().join(HISTORY_FILE)));
+    }
+}
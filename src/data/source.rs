@@ -0,0 +1,374 @@
+//! Ingestion sources
+//!
+//! Everything the dashboard learns from the outside arrives as an [`Update`]
+//! from a [`DataSource`]. The files source covers the usual setup (history
+//! load, then the watcher on TASKS.md and the hook directories); the lines
+//! sources take hook events pushed as JSONL on stdin or a TCP socket. All
+//! started sources share one channel, so `App::handle_update` is the only
+//! consumer and a new ingestion mode (HTTP polling, a database of past runs)
+//! is one more implementation rather than another receiver in every mode.
+
+use std::io::{self, BufRead, BufReader};
+use std::net::{SocketAddr, TcpListener};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use tokio::sync::mpsc;
+
+use crate::data::hook_parser::{self, HookEvent, ParseResult};
+use crate::data::identity::AgentIdentity;
+use crate::data::loader::{self, LoadMessage};
+use crate::data::store::Store;
+use crate::data::watcher::{self, FileChange, WatchConfig};
+
+/// One piece of news for the dashboard
+#[derive(Debug)]
+pub enum Update {
+    /// History load progress or result
+    Load(LoadMessage),
+    /// A watched file changed
+    File(FileChange),
+    /// Hook events pushed by a source rather than written to a file
+    Events(Vec<HookEvent>),
+    /// The named source stopped delivering live updates
+    Lost(&'static str),
+}
+
+/// Something that produces dashboard updates
+pub trait DataSource: Send {
+    /// Short name for messages, e.g. `files` or `stdin`
+    fn name(&self) -> &'static str;
+
+    /// Start delivering updates on `tx`, typically from a background
+    /// thread, and return once running
+    fn start(self: Box<Self>, tx: mpsc::UnboundedSender<Update>) -> io::Result<()>;
+}
+
+/// Start every source on one channel. A source that cannot start is
+/// reported as [`Update::Lost`] on the channel.
+pub fn start_all(sources: Vec<Box<dyn DataSource>>) -> mpsc::UnboundedReceiver<Update> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    start_on(&tx, sources);
+    rx
+}
+
+/// Start every source on `tx`, for a consumer that keeps a sender of its
+/// own (to [`reload_history`] later)
+pub fn start_on(tx: &mpsc::UnboundedSender<Update>, sources: Vec<Box<dyn DataSource>>) {
+    for source in sources {
+        let name = source.name();
+        if source.start(tx.clone()).is_err() {
+            let _ = tx.send(Update::Lost(name));
+        }
+    }
+}
+
+/// Aggregate the event history again on a background thread, e.g. for a
+/// new session filter; progress and result arrive on `tx` as
+/// [`Update::Load`], like the first load
+pub fn reload_history(
+    tx: &mpsc::UnboundedSender<Update>,
+    event_dirs: Vec<PathBuf>,
+    session_filter: Option<String>,
+    agent_identity: Option<AgentIdentity>,
+) {
+    let load_rx = loader::spawn_history_load(event_dirs, session_filter, agent_identity);
+    forward(load_rx, tx.clone(), Update::Load, None, ());
+}
+
+/// Pass everything from `rx` on to `tx` on a background thread, then report
+/// `lost` when `rx` closes (if `lost` is given). `guard` lives as long as
+/// the forwarding, which stops as soon as `tx`'s receiver is dropped.
+fn forward<T: Send + 'static>(
+    mut rx: mpsc::UnboundedReceiver<T>,
+    tx: mpsc::UnboundedSender<Update>,
+    wrap: fn(T) -> Update,
+    lost: Option<&'static str>,
+    guard: impl Send + 'static,
+) {
+    std::thread::spawn(move || {
+        let _guard = guard;
+        let Ok(rt) = tokio::runtime::Builder::new_current_thread().build() else {
+            return;
+        };
+        rt.block_on(async {
+            loop {
+                tokio::select! {
+                    msg = rx.recv() => match msg {
+                        Some(msg) => {
+                            if tx.send(wrap(msg)).is_err() {
+                                return;
+                            }
+                        }
+                        None => break,
+                    },
+                    () = tx.closed() => return,
+                }
+            }
+            if let Some(name) = lost {
+                let _ = tx.send(Update::Lost(name));
+            }
+        });
+    });
+}
+
+/// Event history from the hook and dashboard directories (or the
+/// collector's snapshot of it), then live changes from the file watcher
+pub struct FilesSource {
+    event_dirs: Vec<PathBuf>,
+    store: Option<Store>,
+    agent_identity: Option<AgentIdentity>,
+    watch: WatchConfig,
+}
+
+impl FilesSource {
+    pub fn new(event_dirs: Vec<PathBuf>, watch: WatchConfig) -> Self {
+        Self {
+            event_dirs,
+            store: None,
+            agent_identity: None,
+            watch,
+        }
+    }
+
+    /// Start the history from the collector's snapshot
+    pub fn with_store(mut self, store: Option<Store>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// Fold agents while loading history, as `[agents]` does
+    pub fn with_agent_identity(mut self, identity: Option<AgentIdentity>) -> Self {
+        self.agent_identity = identity;
+        self
+    }
+}
+
+impl DataSource for FilesSource {
+    fn name(&self) -> &'static str {
+        "files"
+    }
+
+    fn start(self: Box<Self>, tx: mpsc::UnboundedSender<Update>) -> io::Result<()> {
+        let this = *self;
+        let load_rx = match this.store {
+            Some(store) => loader::spawn_store_load(store, this.event_dirs, this.agent_identity),
+            None => loader::spawn_history_load(this.event_dirs, None, this.agent_identity),
+        };
+        forward(load_rx, tx.clone(), Update::Load, None, ());
+
+        let watching = this
+            .watch
+            .validate()
+            .and_then(|()| watcher::start_watching(this.watch));
+        match watching {
+            Ok((watcher, change_rx)) => {
+                // Watching lasts as long as someone receives the updates
+                forward(change_rx, tx, Update::File, Some("files"), watcher);
+            }
+            Err(_) => {
+                let _ = tx.send(Update::Lost("files"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Hook events as JSONL lines read from any reader; malformed lines are
+/// skipped
+pub struct LinesSource<R> {
+    name: &'static str,
+    reader: R,
+}
+
+impl<R: BufRead + Send + 'static> LinesSource<R> {
+    pub fn new(name: &'static str, reader: R) -> Self {
+        Self { name, reader }
+    }
+}
+
+/// Send the events of each line in `reader` until it ends
+fn pump_lines(reader: impl BufRead, tx: &mpsc::UnboundedSender<Update>) {
+    for (idx, line) in reader.lines().enumerate() {
+        let Ok(line) = line else {
+            return;
+        };
+        let mut result = ParseResult {
+            events: Vec::new(),
+            errors: Vec::new(),
+        };
+        hook_parser::parse_hook_line(&line, idx + 1, &mut result);
+        if !result.events.is_empty() && tx.send(Update::Events(result.events)).is_err() {
+            return;
+        }
+    }
+}
+
+impl<R: BufRead + Send + 'static> DataSource for LinesSource<R> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn start(self: Box<Self>, tx: mpsc::UnboundedSender<Update>) -> io::Result<()> {
+        let this = *self;
+        std::thread::spawn(move || {
+            pump_lines(this.reader, &tx);
+            let _ = tx.send(Update::Lost(this.name));
+        });
+        Ok(())
+    }
+}
+
+/// Hook events as JSONL lines from any number of TCP connections
+pub struct SocketSource {
+    listener: TcpListener,
+}
+
+impl SocketSource {
+    pub fn bind(addr: SocketAddr) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+}
+
+impl DataSource for SocketSource {
+    fn name(&self) -> &'static str {
+        "socket"
+    }
+
+    fn start(self: Box<Self>, tx: mpsc::UnboundedSender<Update>) -> io::Result<()> {
+        std::thread::spawn(move || {
+            for stream in self.listener.incoming().flatten() {
+                let tx = tx.clone();
+                std::thread::spawn(move || pump_lines(BufReader::new(stream), &tx));
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Extra source named on the command line (`--ingest`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceSpec {
+    /// `stdin`
+    Stdin,
+    /// `tcp:ADDR`
+    Tcp(SocketAddr),
+}
+
+impl FromStr for SourceSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "stdin" {
+            return Ok(Self::Stdin);
+        }
+        match s.strip_prefix("tcp:") {
+            Some(addr) => addr
+                .parse()
+                .map(Self::Tcp)
+                .map_err(|e| format!("invalid address `{addr}`: {e}")),
+            None => Err(format!("unknown source `{s}` (expected stdin or tcp:ADDR)")),
+        }
+    }
+}
+
+impl SourceSpec {
+    /// Open the source; binding a socket can fail
+    pub fn open(&self) -> io::Result<Box<dyn DataSource>> {
+        Ok(match self {
+            Self::Stdin => Box::new(LinesSource::new("stdin", BufReader::new(io::stdin()))),
+            Self::Tcp(addr) => Box::new(SocketSource::bind(*addr)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const EVENT: &str = r#"{"event_type":"agent_start","agent_id":"main","task_id":"T1","session_id":"s","timestamp":"2026-02-08T10:00:00Z"}"#;
+
+    fn events(update: Update) -> Vec<HookEvent> {
+        match update {
+            Update::Events(events) => events,
+            other => panic!("expected events, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lines_source_sends_events_then_reports_the_end() {
+        let input = format!("{EVENT}\nnot json\n\n{EVENT}\n");
+        let source = LinesSource::new("stdin", io::Cursor::new(input));
+        let mut rx = start_all(vec![Box::new(source)]);
+        assert_eq!(events(rx.blocking_recv().unwrap()).len(), 1);
+        assert_eq!(events(rx.blocking_recv().unwrap())[0].agent_id, "main");
+        assert!(matches!(rx.blocking_recv(), Some(Update::Lost("stdin"))));
+    }
+
+    #[test]
+    fn socket_source_reads_each_connection() {
+        let source = SocketSource::bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let addr = source.local_addr().unwrap();
+        let mut rx = start_all(vec![Box::new(source)]);
+        let mut client = std::net::TcpStream::connect(addr).unwrap();
+        writeln!(client, "{EVENT}").unwrap();
+        assert_eq!(events(rx.blocking_recv().unwrap())[0].task_id, "T1");
+    }
+
+    #[test]
+    fn files_source_without_paths_loads_history_but_is_lost() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let watch = WatchConfig::new(tmp.path().join("TASKS.md"), tmp.path().join("hooks"));
+        let source = FilesSource::new(vec![tmp.path().to_path_buf()], watch);
+        let mut rx = start_all(vec![Box::new(source)]);
+        let mut finished = false;
+        let mut lost = false;
+        while !(finished && lost) {
+            match rx.blocking_recv().unwrap() {
+                Update::Load(LoadMessage::Finished(..)) => finished = true,
+                Update::Lost("files") => lost = true,
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn forwarding_drops_its_guard_when_the_receiver_closes() {
+        struct Guard(std::sync::mpsc::Sender<()>);
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                let _ = self.0.send(());
+            }
+        }
+
+        let (dropped_tx, dropped_rx) = std::sync::mpsc::channel();
+        let (_change_tx, change_rx) = mpsc::unbounded_channel::<FileChange>();
+        let (tx, rx) = mpsc::unbounded_channel();
+        forward(change_rx, tx, Update::File, None, Guard(dropped_tx));
+        assert!(dropped_rx
+            .recv_timeout(std::time::Duration::from_millis(100))
+            .is_err());
+        drop(rx);
+        dropped_rx
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .expect("guard dropped");
+    }
+
+    #[test]
+    fn parses_source_specs() {
+        assert_eq!("stdin".parse(), Ok(SourceSpec::Stdin));
+        assert_eq!(
+            "tcp:127.0.0.1:9000".parse(),
+            Ok(SourceSpec::Tcp("127.0.0.1:9000".parse().unwrap()))
+        );
+        assert!("tcp:nowhere".parse::<SourceSpec>().is_err());
+        assert!("http://x".parse::<SourceSpec>().is_err());
+    }
+}
@@ -3,25 +3,56 @@
 //! Combines parsed TASKS.md data, hook events, and file watcher
 //! into a single dashboard state for the TUI to consume.
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 
+use crate::analysis::critical_path::CriticalPath;
+use crate::analysis::forecast::Forecast;
+use crate::analysis::health::HEALTH_WINDOW;
+use crate::analysis::metrics::Metrics;
 use crate::analysis::rules::{analyze_error, ErrorCategory};
-use crate::data::hook_parser::{self, EventType, HookEvent};
-use crate::data::tasks_parser::{self, ParsedPhase, TaskStatus};
+use crate::analysis::velocity::{self, ProgressSample, Velocity};
+use crate::data::git_branch::{self, Checkout};
+use crate::data::git_log::GitCommit;
+use crate::data::hook_parser::{self, EventType, HookEvent, HookIngestError};
+use crate::data::identity::AgentIdentity;
+use crate::data::intern::Interner;
+use crate::data::tasks_parser::{
+    self, ParsedPhase, ParsedTask, TaskStatus, TaskWarning, TasksParseError,
+};
+use crate::data::write_guard::LoadedTasks;
 
 /// Agent activity status derived from hook events
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AgentStatus {
+    // Aliases read collector snapshots written before the names were lowercase
+    #[serde(alias = "Idle")]
     Idle,
+    #[serde(alias = "Running")]
     Running,
+    #[serde(alias = "Error")]
     Error,
 }
 
+impl AgentStatus {
+    /// Lowercase name used by the web mirror and machine output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentStatus::Idle => "idle",
+            AgentStatus::Running => "running",
+            AgentStatus::Error => "error",
+        }
+    }
+}
+
 /// Record of an agent working on a task
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskHistoryEntry {
     pub task_id: String,
     pub started_at: DateTime<Utc>,
@@ -31,47 +62,333 @@ pub struct TaskHistoryEntry {
 /// Maximum number of recent tools to track per agent
 const MAX_RECENT_TOOLS: usize = 10;
 
+/// Maximum number of tool invocations kept per agent for the drill-down view
+/// and loop detection
+pub const MAX_TOOL_INVOCATIONS: usize = 20;
+
+/// Maximum number of status transitions kept per agent
+const MAX_STATUS_HISTORY: usize = 20;
+
+/// A single tool call made by an agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolInvocation {
+    pub tool_name: Arc<str>,
+    /// File the call touched, when the hook reported one
+    pub file_path: Option<String>,
+    /// Other arguments of the call, when the hook reported them
+    #[serde(default)]
+    pub args: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl ToolInvocation {
+    /// Elapsed time between tool_start and tool_end, if the call has finished
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        self.ended_at
+            .map(|end| end.signed_duration_since(self.started_at))
+    }
+}
+
+/// Maximum number of hook events kept per task for the detail panel
+const MAX_TASK_ACTIVITY: usize = 10;
+
+/// A hook event recorded against a task, shown as "Recent activity"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskActivity {
+    pub event_type: EventType,
+    pub agent_id: Arc<str>,
+    pub tool_name: Option<Arc<str>>,
+    pub error_message: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A status transition observed for an agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub status: AgentStatus,
+    pub at: DateTime<Utc>,
+}
+
+/// Time an agent has spent in each status, in milliseconds, up to its
+/// latest status change
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusTime {
+    pub idle_ms: i64,
+    pub running_ms: i64,
+    pub error_ms: i64,
+}
+
+impl StatusTime {
+    pub fn get(&self, status: &AgentStatus) -> i64 {
+        match status {
+            AgentStatus::Idle => self.idle_ms,
+            AgentStatus::Running => self.running_ms,
+            AgentStatus::Error => self.error_ms,
+        }
+    }
+
+    fn add(&mut self, status: &AgentStatus, ms: i64) {
+        match status {
+            AgentStatus::Idle => self.idle_ms += ms,
+            AgentStatus::Running => self.running_ms += ms,
+            AgentStatus::Error => self.error_ms += ms,
+        }
+    }
+}
+
+/// Finished calls of one tool and the time they took
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolTime {
+    pub ended: usize,
+    pub total_ms: i64,
+}
+
 /// A snapshot of one agent's current state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentState {
     pub agent_id: String,
     pub status: AgentStatus,
     pub current_task: Option<String>,
     pub current_tool: Option<String>,
     pub event_count: usize,
+    /// Every error seen, acknowledged or not
     pub error_count: usize,
+    /// Errors not acknowledged yet; these drive the error badges
+    pub open_errors: usize,
     pub task_history: Vec<TaskHistoryEntry>,
     pub first_seen: Option<DateTime<Utc>>,
     pub last_seen: Option<DateTime<Utc>>,
     pub tool_counts: HashMap<String, usize>,
-    pub recent_tools: Vec<String>,
+    /// Error events per tool that was running (or named by the event)
+    #[serde(default)]
+    pub tool_failures: HashMap<String, usize>,
+    /// Time spent in each tool, over calls with both a start and an end
+    #[serde(default)]
+    pub tool_time: HashMap<Arc<str>, ToolTime>,
+    pub recent_tools: Vec<Arc<str>>,
     pub session_id: Option<String>,
+    pub tool_invocations: Vec<ToolInvocation>,
+    pub status_history: Vec<StatusChange>,
+    /// Time spent in each status before the latest change; unlike
+    /// `status_history` this covers the whole event history
+    #[serde(default)]
+    pub status_time: StatusTime,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    /// Working directory reported by the agent's latest tool event
+    pub cwd: Option<String>,
+    /// Branch and worktree of `cwd`, filled in by `refresh_checkouts`
+    #[serde(skip)]
+    pub checkout: Option<Checkout>,
+    /// Went idle because no events came for the idle window, not through an
+    /// `agent_end` (e.g. the agent crashed); cleared by its next event
+    #[serde(default)]
+    pub presumed_finished: bool,
+    /// Instance IDs folded into this agent by `agent_identity`
+    #[serde(default)]
+    pub instances: BTreeSet<String>,
+    /// Outcomes of the latest tool calls and errors, oldest first; `true`
+    /// for an error
+    #[serde(default)]
+    pub recent_outcomes: Vec<bool>,
 }
 
 /// Timing info for a task derived from hook events
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TaskTiming {
     pub started_at: Option<DateTime<Utc>>,
+    /// Earliest hook event of any kind on the task
+    #[serde(default)]
+    pub first_event_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
+    /// `agent_start` events that followed a run with errors
+    #[serde(default)]
+    pub retries: usize,
+    /// The latest run has reported an error
+    #[serde(default)]
+    pub errored: bool,
+    /// Tokens (input + output) and cost reported by events on the task
+    #[serde(default)]
+    pub tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+/// Elapsed time and estimated time left for a phase, from hook event timings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhaseTiming {
+    /// Time since the phase's first event (until its last task finished)
+    pub elapsed: Option<chrono::Duration>,
+    /// What is left of the open tasks' estimates, with the average tracked
+    /// task duration for each open task without one
+    pub remaining: Option<chrono::Duration>,
+}
+
+/// Planned effort of a group of tasks from their `est:` annotations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EstimateRollup {
+    /// Sum of the estimates, finished tasks included
+    pub total: chrono::Duration,
+    /// What is left of the open tasks' estimates after the time already
+    /// spent on them
+    pub remaining: chrono::Duration,
+    /// Open tasks without an estimate, not counted in `remaining`
+    pub unestimated: usize,
+}
+
+/// Caps on what grows with every new agent, session, task or error (`[limits]`),
+/// so an always-on dashboard stays bounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Agents kept; beyond it the least recently active go first, idle
+    /// ones before running ones
+    pub max_agents: usize,
+    /// Recent errors kept, oldest dropped first
+    pub max_errors: usize,
+    /// Sessions kept; beyond it the least recently seen go first, except
+    /// the one the dashboard is filtered to
+    pub max_sessions: usize,
+    /// Task ids tracked from events but missing from TASKS.md (timings,
+    /// owners, activity, the `unknown` bucket included); beyond it the
+    /// least recently active go first
+    pub max_tasks: usize,
 }
 
-/// Maximum number of recent errors to keep
-const MAX_RECENT_ERRORS: usize = 50;
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_agents: 200,
+            max_errors: 50,
+            max_sessions: 100,
+            max_tasks: 500,
+        }
+    }
+}
 
 /// A recorded error with analysis results
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorRecord {
     pub agent_id: String,
     pub task_id: String,
     pub message: String,
     pub category: ErrorCategory,
     pub retryable: bool,
-    pub suggestion: &'static str,
+    /// The collector's snapshot looks it up again from the message after
+    /// loading, so rule changes apply
+    #[serde(default)]
+    pub suggestion: Cow<'static, str>,
+    /// Fix command the retry popup offers; looked up again like `suggestion`
+    #[serde(default)]
+    pub command: Option<Cow<'static, str>>,
     pub timestamp: DateTime<Utc>,
+    /// Tool that was running when the error was reported, if known
+    pub tool_name: Option<String>,
+    /// Occurrences grouped into this record: the same agent, category and
+    /// message apart from numbers. The other fields are the latest one's.
+    #[serde(default = "one")]
+    pub count: usize,
+}
+
+fn one() -> usize {
+    1
+}
+
+impl ErrorRecord {
+    /// ` × 14` after a grouped error's message; empty for a single one
+    pub fn repeat_suffix(&self) -> String {
+        if self.count > 1 {
+            format!(" \u{00D7} {}", self.count)
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// `message` with digit runs replaced and whitespace collapsed, so errors
+/// differing only in line numbers, ports or ids group together
+fn normalize_error(message: &str) -> String {
+    let mut normalized = String::with_capacity(message.len());
+    let mut last = ' ';
+    for c in message.trim().chars() {
+        let c = if c.is_ascii_digit() {
+            '#'
+        } else if c.is_whitespace() {
+            ' '
+        } else {
+            c.to_ascii_lowercase()
+        };
+        if !(c == last && (c == '#' || c == ' ')) {
+            normalized.push(c);
+        }
+        last = c;
+    }
+    normalized
+}
+
+/// Timeline entries kept per session (the most recent ones)
+const MAX_TIMELINE: usize = 200;
+
+/// Maximum number of open/closed samples kept for the burndown
+const MAX_PROGRESS_SAMPLES: usize = 1000;
+
+/// An agent start, end or error in a session's timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub at: DateTime<Utc>,
+    pub event_type: EventType,
+    pub agent_id: Arc<str>,
+    pub task_id: Arc<str>,
+    /// Message of an `error` event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Summary of one hook session, used by the session picker and the
+/// history view
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub started_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    #[serde(serialize_with = "sorted")]
+    pub agent_ids: HashSet<String>,
+    pub event_count: usize,
+    pub error_count: usize,
+    /// Tokens and cost reported by the session's events
+    #[serde(default)]
+    pub tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
+    /// Tasks an agent finished (`agent_end`) during the session
+    #[serde(default)]
+    pub tasks_finished: BTreeSet<String>,
+    /// Agent starts, ends and errors, oldest first
+    #[serde(default)]
+    pub timeline: Vec<TimelineEntry>,
+}
+
+impl SessionSummary {
+    pub fn agent_count(&self) -> usize {
+        self.agent_ids.len()
+    }
+}
+
+/// Serialize a set in sorted order, so equal states serialize alike
+fn sorted<S: serde::Serializer>(set: &HashSet<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(set.iter().collect::<BTreeSet<_>>())
 }
 
 /// The complete dashboard state
-#[derive(Debug, Clone)]
+///
+/// Serializes with the field names below; fields missing from the input
+/// take their defaults. Maps serialize in hash order, so compare or store
+/// through `serde_json::Value` (whose objects are sorted) when the output
+/// has to be byte-stable. What was read from disk (`loaded_tasks`) is not
+/// part of it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DashboardState {
     pub phases: Vec<ParsedPhase>,
     pub agents: HashMap<String, AgentState>,
@@ -82,7 +399,42 @@ pub struct DashboardState {
     pub completed_tasks: usize,
     pub failed_tasks: usize,
     pub overall_progress: f32,
+    /// Problems the tolerant TASKS.md parser skipped over
+    pub task_warnings: Vec<TaskWarning>,
     pub recent_errors: Vec<ErrorRecord>,
+    /// Per task_id, errors at or before this time were dismissed by a reset
+    /// and are not shown again when events are re-aggregated
+    pub dismissed_errors: HashMap<String, DateTime<Utc>>,
+    /// Per agent_id, errors at or before this time were acknowledged; they
+    /// stay in the history but no longer count as open
+    pub acknowledged_errors: HashMap<String, DateTime<Utc>>,
+    /// Per agent_id, error alerts are muted until this time
+    pub muted_agents: HashMap<String, DateTime<Utc>>,
+    /// Last few hook events per task_id, oldest first
+    pub task_activity: HashMap<String, Vec<TaskActivity>>,
+    /// Every session seen in the loaded events, regardless of the filter
+    pub sessions: HashMap<String, SessionSummary>,
+    /// When set, only events from this session update agent state
+    pub session_filter: Option<String>,
+    /// TASKS.md as last read from disk; `None` when built from a string
+    #[serde(skip)]
+    pub loaded_tasks: Option<LoadedTasks>,
+    /// Recent commits of the project's git repository, newest first
+    pub commits: Vec<GitCommit>,
+    /// Open/closed task counts, sampled whenever a TASKS.md reload changed
+    /// them, oldest first
+    pub progress_samples: Vec<ProgressSample>,
+    /// Per task_id, how often a TASKS.md reload moved it from failed back
+    /// to in progress
+    pub status_retries: HashMap<String, usize>,
+    /// Folds agent instances into logical agents (`agent_identity`)
+    pub agent_identity: Option<AgentIdentity>,
+    /// Caps on agents and errors, from the config rather than the events
+    #[serde(skip)]
+    pub limits: Limits,
+    /// Agent, task and tool names shared by the per-event records
+    #[serde(skip)]
+    pub names: Interner,
 }
 
 impl Default for DashboardState {
@@ -96,35 +448,93 @@ impl Default for DashboardState {
             completed_tasks: 0,
             failed_tasks: 0,
             overall_progress: 0.0,
+            task_warnings: Vec::new(),
             recent_errors: Vec::new(),
+            dismissed_errors: HashMap::new(),
+            acknowledged_errors: HashMap::new(),
+            muted_agents: HashMap::new(),
+            task_activity: HashMap::new(),
+            sessions: HashMap::new(),
+            session_filter: None,
+            loaded_tasks: None,
+            commits: Vec::new(),
+            progress_samples: Vec::new(),
+            status_retries: HashMap::new(),
+            agent_identity: None,
+            limits: Limits::default(),
+            names: Interner::default(),
         }
     }
 }
 
+/// Fill in `waiting_on` of pending tasks: the dependencies that exist and
+/// are not completed. The status stays pending, so finishing them is all it
+/// takes for the task to stop looking blocked.
+fn mark_waiting_tasks(phases: &mut [ParsedPhase]) {
+    let completed: HashMap<String, bool> = phases
+        .iter()
+        .flat_map(|p| &p.tasks)
+        .map(|t| (t.id.clone(), t.status == TaskStatus::Completed))
+        .collect();
+    for task in phases.iter_mut().flat_map(|p| &mut p.tasks) {
+        task.waiting_on = if task.status == TaskStatus::Pending {
+            task.blocked_by
+                .iter()
+                .filter(|dep| completed.get(*dep) == Some(&false))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+    }
+}
+
 impl DashboardState {
+    /// Logical agent of the instance `agent_id`, when `agent_identity`
+    /// folds it into another
+    fn resolve_agent(&self, agent_id: &str) -> Option<String> {
+        self.agent_identity.as_ref()?.resolve(agent_id)
+    }
+
+    /// ID under which the instance `agent_id` is aggregated
+    pub fn logical_agent_id(&self, agent_id: &str) -> String {
+        self.resolve_agent(agent_id)
+            .unwrap_or_else(|| agent_id.to_string())
+    }
+
     /// Build state from a TASKS.md file path
-    pub fn from_tasks_file(path: &Path) -> Result<Self, String> {
-        let content =
-            std::fs::read_to_string(path).map_err(|e| format!("failed to read tasks: {e}"))?;
-        Self::from_tasks_content(&content)
+    pub fn from_tasks_file(path: &Path) -> Result<Self, TasksParseError> {
+        let mut state = Self::default();
+        state.reload_tasks_file(path)?;
+        Ok(state)
     }
 
     /// Build state from TASKS.md content string
-    pub fn from_tasks_content(content: &str) -> Result<Self, String> {
-        let phases = tasks_parser::parse_tasks_md(content)?;
+    pub fn from_tasks_content(content: &str) -> Result<Self, TasksParseError> {
         let mut state = Self::default();
-        state.update_from_phases(phases);
+        state.reload_tasks(content)?;
         Ok(state)
     }
 
     /// Update task-related fields from parsed phases
-    fn update_from_phases(&mut self, phases: Vec<ParsedPhase>) {
+    fn update_from_phases(&mut self, mut phases: Vec<ParsedPhase>) {
+        mark_waiting_tasks(&mut phases);
         let mut total = 0;
         let mut completed = 0;
         let mut failed = 0;
+        let was_failed: Vec<&str> = self
+            .phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| t.status == TaskStatus::Failed)
+            .map(|t| t.id.as_str())
+            .collect();
 
         for phase in &phases {
             for task in &phase.tasks {
+                if task.status == TaskStatus::InProgress && was_failed.contains(&task.id.as_str()) {
+                    *self.status_retries.entry(task.id.clone()).or_insert(0) += 1;
+                }
                 total += 1;
                 match task.status {
                     TaskStatus::Completed => completed += 1,
@@ -143,11 +553,129 @@ impl DashboardState {
         } else {
             0.0
         };
+        self.apply_task_retries();
+        self.refresh_phase_times();
+        self.record_progress(Utc::now());
+    }
+
+    /// Set each task's retry count from the status changes and the events,
+    /// whichever saw more
+    fn apply_task_retries(&mut self) {
+        for task in self.phases.iter_mut().flat_map(|p| &mut p.tasks) {
+            let from_status = self.status_retries.get(&task.id).copied().unwrap_or(0);
+            let from_events = self.task_times.get(&task.id).map_or(0, |t| t.retries);
+            task.retries = from_status.max(from_events);
+        }
+    }
+
+    /// Set each phase's actual start and end from its tasks' event times:
+    /// the first event on any task, and the last completion once all are
+    /// done
+    pub fn refresh_phase_times(&mut self) {
+        for phase in &mut self.phases {
+            let timings: Vec<&TaskTiming> = phase
+                .tasks
+                .iter()
+                .filter_map(|t| self.task_times.get(&t.id))
+                .collect();
+            phase.started_at = timings
+                .iter()
+                .flat_map(|t| [t.first_event_at, t.started_at])
+                .flatten()
+                .min();
+            let done = !phase.tasks.is_empty()
+                && phase
+                    .tasks
+                    .iter()
+                    .all(|t| t.status == TaskStatus::Completed);
+            phase.ended_at = timings
+                .iter()
+                .filter_map(|t| t.completed_at)
+                .max()
+                .filter(|_| done);
+        }
+    }
+
+    /// Tasks retried at least `threshold` times, candidates for a human look
+    pub fn tasks_over_retry_threshold(&self, threshold: usize) -> Vec<&ParsedTask> {
+        self.phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| t.retries >= threshold)
+            .collect()
+    }
+
+    /// Sample the open/closed task counts at `at` if they changed since the
+    /// last sample
+    pub fn record_progress(&mut self, at: DateTime<Utc>) {
+        let sample = ProgressSample {
+            at,
+            open: self.total_tasks - self.completed_tasks,
+            closed: self.completed_tasks,
+        };
+        if self
+            .progress_samples
+            .last()
+            .is_some_and(|last| last.open == sample.open && last.closed == sample.closed)
+        {
+            return;
+        }
+        self.progress_samples.push(sample);
+        if self.progress_samples.len() > MAX_PROGRESS_SAMPLES {
+            self.progress_samples.remove(0);
+        }
+    }
+
+    /// Open/closed counts over time, including completions from the events
+    /// before the first sample
+    pub fn burndown(&self) -> Vec<ProgressSample> {
+        velocity::burndown(self)
+    }
+
+    /// Critical path and slack of the open tasks over their dependencies
+    pub fn critical_path(&self, now: DateTime<Utc>) -> CriticalPath {
+        CriticalPath::compute(self, now)
+    }
+
+    /// Projected finish of the open tasks as of `now`
+    pub fn forecast(&self, now: DateTime<Utc>) -> Forecast {
+        Forecast::compute(self, now)
+    }
+
+    /// Tasks closed per hour and per day up to `now`
+    pub fn velocity(&self, now: DateTime<Utc>) -> Velocity {
+        Velocity::from_samples(&self.burndown(), now)
     }
 
-    /// Update agent states from hook events
+    /// Update agent states from hook events, on top of what earlier events
+    /// built, so each batch costs only its own size
     pub fn update_from_events(&mut self, events: &[HookEvent]) {
+        let mut retried = false;
         for event in events {
+            let (event, instance) = match self.resolve_agent(&event.agent_id) {
+                Some(role) => {
+                    let instance = event.agent_id.clone();
+                    let event = HookEvent {
+                        agent_id: role,
+                        ..event.clone()
+                    };
+                    (Cow::Owned(event), Some(instance))
+                }
+                None => (Cow::Borrowed(event), None),
+            };
+            let event = event.as_ref();
+            self.record_session(event);
+            if self
+                .session_filter
+                .as_deref()
+                .is_some_and(|id| id != event.session_id)
+            {
+                continue;
+            }
+            self.record_task_activity(event);
+            let dismissed = self.is_dismissed(&event.task_id, event.timestamp);
+            let acknowledged = self.is_acknowledged(&event.agent_id, event.timestamp);
+
             let agent = self
                 .agents
                 .entry(event.agent_id.clone())
@@ -158,21 +686,68 @@ impl DashboardState {
                     current_tool: None,
                     event_count: 0,
                     error_count: 0,
+                    open_errors: 0,
                     task_history: Vec::new(),
                     first_seen: None,
                     last_seen: None,
                     tool_counts: HashMap::new(),
+                    tool_failures: HashMap::new(),
+                    tool_time: HashMap::new(),
                     recent_tools: Vec::new(),
                     session_id: None,
+                    tool_invocations: Vec::new(),
+                    status_history: Vec::new(),
+                    status_time: StatusTime::default(),
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cost_usd: 0.0,
+                    cwd: None,
+                    checkout: None,
+                    presumed_finished: false,
+                    instances: BTreeSet::new(),
+                    recent_outcomes: Vec::new(),
                 });
 
+            if let Some(instance) = instance {
+                agent.instances.insert(instance);
+            }
             agent.event_count += 1;
             agent.last_seen = Some(event.timestamp);
             if agent.first_seen.is_none() {
                 agent.first_seen = Some(event.timestamp);
             }
             agent.session_id = Some(event.session_id.clone());
+            if event.cwd.is_some() {
+                agent.cwd = event.cwd.clone();
+            }
+            if let Some(ref usage) = event.usage {
+                agent.input_tokens += usage.input_tokens;
+                agent.output_tokens += usage.output_tokens;
+                agent.cost_usd += usage.cost_usd;
+                let task_id = Some(&event.task_id)
+                    .filter(|id| !id.is_empty())
+                    .or(agent.current_task.as_ref());
+                if let Some(task_id) = task_id {
+                    let timing = self.task_times.entry(task_id.clone()).or_default();
+                    timing.tokens += usage.input_tokens + usage.output_tokens;
+                    timing.cost_usd += usage.cost_usd;
+                }
+            }
+            let prev_status = agent.status.clone();
+            // A presumed-finished agent that speaks up was still working
+            if agent.presumed_finished {
+                agent.presumed_finished = false;
+                agent.status = AgentStatus::Running;
+            }
 
+            if matches!(event.event_type, EventType::ToolEnd | EventType::Error) {
+                agent
+                    .recent_outcomes
+                    .push(event.event_type == EventType::Error);
+                if agent.recent_outcomes.len() > HEALTH_WINDOW {
+                    agent.recent_outcomes.remove(0);
+                }
+            }
             match event.event_type {
                 EventType::AgentStart => {
                     agent.status = AgentStatus::Running;
@@ -189,6 +764,11 @@ impl DashboardState {
                     if timing.started_at.is_none() {
                         timing.started_at = Some(event.timestamp);
                     }
+                    if timing.errored {
+                        timing.retries += 1;
+                        timing.errored = false;
+                        retried = true;
+                    }
                 }
                 EventType::AgentEnd => {
                     agent.status = AgentStatus::Idle;
@@ -210,13 +790,41 @@ impl DashboardState {
                     agent.current_tool = event.tool_name.clone();
                     if let Some(ref name) = event.tool_name {
                         *agent.tool_counts.entry(name.clone()).or_insert(0) += 1;
-                        agent.recent_tools.push(name.clone());
+                        let name = self.names.intern(name);
+                        agent.recent_tools.push(Arc::clone(&name));
                         if agent.recent_tools.len() > MAX_RECENT_TOOLS {
                             agent.recent_tools.remove(0);
                         }
+                        agent.tool_invocations.push(ToolInvocation {
+                            tool_name: name,
+                            file_path: event.file_path.clone(),
+                            args: event.args.clone(),
+                            started_at: event.timestamp,
+                            ended_at: None,
+                        });
+                        if agent.tool_invocations.len() > MAX_TOOL_INVOCATIONS {
+                            agent.tool_invocations.remove(0);
+                        }
                     }
                 }
                 EventType::ToolEnd => {
+                    // Close the most recent open invocation of the same tool
+                    if let Some(inv) = agent.tool_invocations.iter_mut().rev().find(|inv| {
+                        inv.ended_at.is_none()
+                            && event
+                                .tool_name
+                                .as_deref()
+                                .map_or(true, |n| n == &*inv.tool_name)
+                    }) {
+                        inv.ended_at = Some(event.timestamp);
+                        let time = agent
+                            .tool_time
+                            .entry(Arc::clone(&inv.tool_name))
+                            .or_default();
+                        time.ended += 1;
+                        time.total_ms +=
+                            (event.timestamp - inv.started_at).num_milliseconds().max(0);
+                    }
                     agent.current_tool = None;
                     // Return to Idle only if no active task (subagent)
                     if agent.current_task.is_none() {
@@ -226,9 +834,29 @@ impl DashboardState {
                 EventType::Error => {
                     agent.status = AgentStatus::Error;
                     agent.error_count += 1;
+                    self.task_times
+                        .entry(event.task_id.clone())
+                        .or_default()
+                        .errored = true;
+                    if let Some(tool) = event.tool_name.as_ref().or(agent.current_tool.as_ref()) {
+                        *agent.tool_failures.entry(tool.clone()).or_insert(0) += 1;
+                    }
+                    if !acknowledged {
+                        agent.open_errors += 1;
+                    }
 
-                    if let Some(ref msg) = event.error_message {
+                    if let Some(msg) = event.error_message.as_ref().filter(|_| !dismissed) {
                         let analysis = analyze_error(msg);
+                        let normalized = normalize_error(msg);
+                        // A repeat of an earlier error replaces it, moving to the end
+                        let count = match self.recent_errors.iter().rposition(|e| {
+                            e.agent_id == event.agent_id
+                                && e.category == analysis.category
+                                && normalize_error(&e.message) == normalized
+                        }) {
+                            Some(i) => self.recent_errors.remove(i).count + 1,
+                            None => 1,
+                        };
                         self.recent_errors.push(ErrorRecord {
                             agent_id: event.agent_id.clone(),
                             task_id: event.task_id.clone(),
@@ -236,53 +864,686 @@ impl DashboardState {
                             category: analysis.category,
                             retryable: analysis.retryable,
                             suggestion: analysis.suggestion,
+                            command: analysis.command,
                             timestamp: event.timestamp,
+                            tool_name: event.tool_name.clone(),
+                            count,
                         });
-                        if self.recent_errors.len() > MAX_RECENT_ERRORS {
+                        if self.recent_errors.len() > self.limits.max_errors {
                             self.recent_errors.remove(0);
                         }
                     }
                 }
             }
+
+            if agent.status != prev_status || agent.status_history.is_empty() {
+                if let Some(last) = agent.status_history.last() {
+                    let spent = event.timestamp - last.at;
+                    agent
+                        .status_time
+                        .add(&prev_status, spent.num_milliseconds());
+                }
+                agent.status_history.push(StatusChange {
+                    status: agent.status.clone(),
+                    at: event.timestamp,
+                });
+                if agent.status_history.len() > MAX_STATUS_HISTORY {
+                    agent.status_history.remove(0);
+                }
+            }
         }
+        // Task retry counts only move when a failed run restarts
+        if retried {
+            self.apply_task_retries();
+        }
+        self.refresh_phase_times();
+        self.enforce_limits();
     }
 
-    /// Clear agent state and re-process all events from scratch.
-    /// Use this when a hook events file is re-read entirely (avoids duplicate accumulation).
-    pub fn reload_from_events(&mut self, events: &[HookEvent]) {
+    /// Drop the least recently active agents and sessions and the oldest
+    /// errors beyond `limits`, then forget names no longer used
+    pub fn enforce_limits(&mut self) {
+        let excess = self.agents.len().saturating_sub(self.limits.max_agents);
+        if excess > 0 {
+            let mut by_activity: Vec<(bool, Option<DateTime<Utc>>, String)> = self
+                .agents
+                .values()
+                .map(|a| {
+                    (
+                        a.status == AgentStatus::Running,
+                        a.last_seen,
+                        a.agent_id.clone(),
+                    )
+                })
+                .collect();
+            by_activity.sort();
+            for (_, _, agent_id) in by_activity.into_iter().take(excess) {
+                self.agents.remove(&agent_id);
+            }
+        }
+        let excess = self
+            .recent_errors
+            .len()
+            .saturating_sub(self.limits.max_errors);
+        self.recent_errors.drain(..excess);
+        let excess = self.sessions.len().saturating_sub(self.limits.max_sessions);
+        if excess > 0 {
+            let mut by_activity: Vec<(DateTime<Utc>, String)> = self
+                .sessions
+                .values()
+                .filter(|s| self.session_filter.as_deref() != Some(s.session_id.as_str()))
+                .map(|s| (s.last_seen, s.session_id.clone()))
+                .collect();
+            by_activity.sort();
+            for (_, session_id) in by_activity.into_iter().take(excess) {
+                self.sessions.remove(&session_id);
+            }
+        }
+        self.evict_tasks();
+        self.names.prune();
+    }
+
+    /// Drop the least recently active task ids not in TASKS.md from the
+    /// per-task maps, beyond `max_tasks`
+    fn evict_tasks(&mut self) {
+        let listed: HashSet<&str> = self
+            .phases
+            .iter()
+            .flat_map(|p| p.tasks.iter().map(|t| t.id.as_str()))
+            .collect();
+        let tracked: HashSet<&String> = self
+            .task_times
+            .keys()
+            .chain(self.task_agents.keys())
+            .chain(self.task_activity.keys())
+            .filter(|id| !listed.contains(id.as_str()))
+            .collect();
+        let excess = tracked.len().saturating_sub(self.limits.max_tasks);
+        if excess == 0 {
+            return;
+        }
+        let mut by_activity: Vec<(Option<DateTime<Utc>>, String)> = tracked
+            .into_iter()
+            .map(|id| {
+                let timing = self.task_times.get(id);
+                let last = [
+                    timing.and_then(|t| t.first_event_at),
+                    timing.and_then(|t| t.started_at),
+                    timing.and_then(|t| t.completed_at),
+                    self.task_activity
+                        .get(id)
+                        .and_then(|a| a.last())
+                        .map(|a| a.timestamp),
+                ]
+                .into_iter()
+                .flatten()
+                .max();
+                (last, id.clone())
+            })
+            .collect();
+        by_activity.sort();
+        for (_, task_id) in by_activity.into_iter().take(excess) {
+            self.task_times.remove(&task_id);
+            self.task_agents.remove(&task_id);
+            self.task_activity.remove(&task_id);
+        }
+    }
+
+    /// Remember the event on its task, dropping the oldest beyond the cap
+    fn record_task_activity(&mut self, event: &HookEvent) {
+        let activity = self.task_activity.entry(event.task_id.clone()).or_default();
+        activity.push(TaskActivity {
+            event_type: event.event_type.clone(),
+            agent_id: self.names.intern(&event.agent_id),
+            tool_name: event.tool_name.as_deref().map(|n| self.names.intern(n)),
+            error_message: event.error_message.clone(),
+            timestamp: event.timestamp,
+        });
+        if activity.len() > MAX_TASK_ACTIVITY {
+            activity.remove(0);
+        }
+        if !event.task_id.is_empty() {
+            let timing = self.task_times.entry(event.task_id.clone()).or_default();
+            timing.first_event_at = Some(
+                timing
+                    .first_event_at
+                    .map_or(event.timestamp, |t| t.min(event.timestamp)),
+            );
+        }
+    }
+
+    /// Track per-session counters for the session picker
+    fn record_session(&mut self, event: &HookEvent) {
+        let summary = self
+            .sessions
+            .entry(event.session_id.clone())
+            .or_insert_with(|| SessionSummary {
+                session_id: event.session_id.clone(),
+                started_at: event.timestamp,
+                last_seen: event.timestamp,
+                agent_ids: HashSet::new(),
+                event_count: 0,
+                error_count: 0,
+                tokens: 0,
+                cost_usd: 0.0,
+                tasks_finished: BTreeSet::new(),
+                timeline: Vec::new(),
+            });
+        summary.started_at = summary.started_at.min(event.timestamp);
+        summary.last_seen = summary.last_seen.max(event.timestamp);
+        summary.agent_ids.insert(event.agent_id.clone());
+        summary.event_count += 1;
+        if let Some(ref usage) = event.usage {
+            summary.tokens += usage.input_tokens + usage.output_tokens;
+            summary.cost_usd += usage.cost_usd;
+        }
+        match event.event_type {
+            EventType::Error => summary.error_count += 1,
+            EventType::AgentEnd if !event.task_id.is_empty() => {
+                summary.tasks_finished.insert(event.task_id.clone());
+            }
+            _ => {}
+        }
+        if matches!(
+            event.event_type,
+            EventType::AgentStart | EventType::AgentEnd | EventType::Error
+        ) {
+            summary.timeline.push(TimelineEntry {
+                at: event.timestamp,
+                event_type: event.event_type.clone(),
+                agent_id: self.names.intern(&event.agent_id),
+                task_id: self.names.intern(&event.task_id),
+                detail: event.error_message.clone(),
+            });
+            if summary.timeline.len() > MAX_TIMELINE {
+                summary.timeline.remove(0);
+            }
+        }
+    }
+
+    fn is_dismissed(&self, task_id: &str, at: DateTime<Utc>) -> bool {
+        self.dismissed_errors
+            .get(task_id)
+            .is_some_and(|&cutoff| at <= cutoff)
+    }
+
+    fn is_acknowledged(&self, agent_id: &str, at: DateTime<Utc>) -> bool {
+        self.acknowledged_errors
+            .get(agent_id)
+            .is_some_and(|&cutoff| at <= cutoff)
+    }
+
+    /// Whether `error` was acknowledged on its agent
+    pub fn error_acknowledged(&self, error: &ErrorRecord) -> bool {
+        self.is_acknowledged(&error.agent_id, error.timestamp)
+    }
+
+    /// Errors of `agent_id` that have not been acknowledged, oldest first
+    pub fn open_errors_for_agent<'a>(
+        &'a self,
+        agent_id: &'a str,
+    ) -> impl DoubleEndedIterator<Item = &'a ErrorRecord> + 'a {
+        self.recent_errors
+            .iter()
+            .filter(move |e| e.agent_id == agent_id && !self.error_acknowledged(e))
+    }
+
+    /// Agents currently running
+    pub fn active_agents(&self) -> usize {
+        self.agents
+            .values()
+            .filter(|a| a.status == AgentStatus::Running)
+            .count()
+    }
+
+    /// Mark running agents with no events for `window` before `now` as idle
+    /// and presumed finished; the change is dated when the window ran out.
+    /// Returns how many agents went idle.
+    pub fn expire_idle_agents(&mut self, now: DateTime<Utc>, window: chrono::Duration) -> usize {
+        let mut expired = 0;
+        for agent in self.agents.values_mut() {
+            let Some(last_seen) = agent.last_seen else {
+                continue;
+            };
+            let at = last_seen + window;
+            if agent.status != AgentStatus::Running || at > now {
+                continue;
+            }
+            if let Some(last) = agent.status_history.last() {
+                agent
+                    .status_time
+                    .add(&AgentStatus::Running, (at - last.at).num_milliseconds());
+            }
+            agent.status = AgentStatus::Idle;
+            agent.current_tool = None;
+            agent.presumed_finished = true;
+            agent.status_history.push(StatusChange {
+                status: AgentStatus::Idle,
+                at,
+            });
+            if agent.status_history.len() > MAX_STATUS_HISTORY {
+                agent.status_history.remove(0);
+            }
+            expired += 1;
+        }
+        expired
+    }
+
+    /// Tasks past their due date on `today` and not completed
+    pub fn overdue_tasks(&self, today: NaiveDate) -> usize {
+        self.phases
+            .iter()
+            .flat_map(|p| &p.tasks)
+            .filter(|t| t.is_overdue(today))
+            .count()
+    }
+
+    /// Open errors across all agents that are not muted at `now`
+    pub fn total_open_errors(&self, now: DateTime<Utc>) -> usize {
+        self.agents
+            .values()
+            .filter(|a| self.muted_until(&a.agent_id, now).is_none())
+            .map(|a| a.open_errors)
+            .sum()
+    }
+
+    /// End of `agent_id`'s mute, if it is still muted at `now`
+    pub fn muted_until(&self, agent_id: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.muted_agents
+            .get(agent_id)
+            .copied()
+            .filter(|&until| until > now)
+    }
+
+    /// Hide `agent_id`'s error alerts (badges and inline summary) until `until`
+    pub fn mute_agent(&mut self, agent_id: &str, until: DateTime<Utc>) {
+        self.muted_agents.insert(agent_id.to_string(), until);
+    }
+
+    pub fn unmute_agent(&mut self, agent_id: &str) {
+        self.muted_agents.remove(agent_id);
+    }
+
+    /// Acknowledge every error `agent_id` has reported so far. They are kept
+    /// (drill-down, task details) but leave the badges and the inline
+    /// summary; later errors count again. Returns how many were open.
+    pub fn acknowledge_agent_errors(&mut self, agent_id: &str) -> usize {
+        let Some(agent) = self.agents.get_mut(agent_id) else {
+            return 0;
+        };
+        let Some(last_seen) = agent.last_seen else {
+            return 0;
+        };
+        let open = std::mem::take(&mut agent.open_errors);
+        self.acknowledged_errors
+            .insert(agent_id.to_string(), last_seen);
+        open
+    }
+
+    /// Drop the recorded errors of `task_id`; they stay hidden when the
+    /// events are loaded again, while later errors still show up
+    pub fn dismiss_task_errors(&mut self, task_id: &str) {
+        let Some(latest) = self
+            .recent_errors
+            .iter()
+            .filter(|e| e.task_id == task_id)
+            .map(|e| e.timestamp)
+            .max()
+        else {
+            return;
+        };
+        self.dismissed_errors.insert(task_id.to_string(), latest);
+        self.recent_errors.retain(|e| e.task_id != task_id);
+    }
+
+    /// Drop all event-derived state (tasks are kept)
+    pub fn clear_events(&mut self) {
         self.agents.clear();
         self.task_times.clear();
         self.task_agents.clear();
         self.recent_errors.clear();
+        self.task_activity.clear();
+        self.sessions.clear();
+    }
+
+    /// Clear agent state and re-process all events from scratch.
+    /// Use this when a hook events file is re-read entirely (avoids duplicate accumulation).
+    pub fn reload_from_events(&mut self, events: &[HookEvent]) {
+        self.clear_events();
         self.update_from_events(events);
+        self.refresh_checkouts();
     }
 
-    /// Load hook events from a directory and update agent states
-    pub fn load_hook_events(&mut self, hooks_dir: &Path) -> Result<(), String> {
-        let entries =
-            std::fs::read_dir(hooks_dir).map_err(|e| format!("failed to read hooks dir: {e}"))?;
+    /// Take over the event-derived state aggregated elsewhere (e.g. by the
+    /// background history loader), keeping the parsed tasks
+    pub fn absorb_events(&mut self, loaded: DashboardState) {
+        self.agents = loaded.agents;
+        self.task_times = loaded.task_times;
+        self.task_agents = loaded.task_agents;
+        self.recent_errors = loaded.recent_errors;
+        let dismissed = &self.dismissed_errors;
+        self.recent_errors.retain(|e| {
+            !dismissed
+                .get(&e.task_id)
+                .is_some_and(|&cutoff| e.timestamp <= cutoff)
+        });
+        self.task_activity = loaded.task_activity;
+        self.sessions = loaded.sessions;
+        // The loader did not know about acknowledgements made meanwhile
+        for (agent_id, agent) in &mut self.agents {
+            if let Some(&cutoff) = self.acknowledged_errors.get(agent_id) {
+                agent.open_errors = self
+                    .recent_errors
+                    .iter()
+                    .filter(|e| &e.agent_id == agent_id && e.timestamp > cutoff)
+                    .map(|e| e.count)
+                    .sum();
+            }
+        }
+        self.apply_task_retries();
+        self.refresh_phase_times();
+        self.refresh_checkouts();
+        self.enforce_limits();
+    }
+
+    /// Sessions ordered by start time, most recent first
+    pub fn sorted_sessions(&self) -> Vec<&SessionSummary> {
+        let mut sessions: Vec<&SessionSummary> = self.sessions.values().collect();
+        sessions.sort_by(|a, b| {
+            b.started_at
+                .cmp(&a.started_at)
+                .then_with(|| a.session_id.cmp(&b.session_id))
+        });
+        sessions
+    }
 
-        for entry in entries {
-            let entry = entry.map_err(|e| format!("failed to read entry: {e}"))?;
-            let path = entry.path();
+    /// Load hook events from a directory and update agent states
+    pub fn load_hook_events(&mut self, hooks_dir: &Path) -> Result<(), HookIngestError> {
+        let read_dir = |source| HookIngestError::ReadDir {
+            dir: hooks_dir.to_path_buf(),
+            source,
+        };
+        for entry in std::fs::read_dir(hooks_dir).map_err(read_dir)? {
+            let path = entry.map_err(read_dir)?.path();
             if path.extension().and_then(|e| e.to_str()) == Some("jsonl") {
-                let result = hook_parser::parse_hook_file(&path)
-                    .map_err(|e| format!("failed to parse {}: {e}", path.display()))?;
+                let result = hook_parser::parse_hook_file(&path)?;
                 self.update_from_events(&result.events);
             }
         }
         Ok(())
     }
 
+    /// Total input + output tokens across all agents
+    pub fn total_tokens(&self) -> u64 {
+        self.agents
+            .values()
+            .map(|a| a.input_tokens + a.output_tokens)
+            .sum()
+    }
+
+    /// Total cost in USD across all agents
+    pub fn total_cost(&self) -> f64 {
+        self.agents.values().map(|a| a.cost_usd).sum()
+    }
+
+    /// Task duration, throughput, tool failure and time-in-status metrics,
+    /// with current statuses lasting until `now`
+    pub fn metrics(&self, now: DateTime<Utc>) -> Metrics {
+        Metrics::compute(self, now)
+    }
+
+    /// Elapsed time and ETA for a phase. The ETA adds up the open tasks'
+    /// estimates; tasks without one take the average duration of the phase's
+    /// finished tasks, falling back to all finished tasks. Before the phase
+    /// has started only the estimates count.
+    pub fn phase_timing(&self, phase: &ParsedPhase, now: DateTime<Utc>) -> PhaseTiming {
+        let timings: Vec<&TaskTiming> = phase
+            .tasks
+            .iter()
+            .filter_map(|t| self.task_times.get(&t.id))
+            .collect();
+        let estimate = self
+            .estimate_rollup(&phase.tasks, now)
+            .filter(|e| e.remaining > chrono::Duration::zero());
+        let Some(first) = phase.started_at else {
+            return PhaseTiming {
+                elapsed: None,
+                remaining: estimate.map(|e| e.remaining),
+            };
+        };
+
+        let open = phase
+            .tasks
+            .iter()
+            .filter(|t| t.status != TaskStatus::Completed)
+            .count();
+        let end = phase.ended_at.unwrap_or(now);
+
+        let average = |durations: Vec<chrono::Duration>| {
+            let n = durations.len() as i32;
+            (n > 0).then(|| durations.into_iter().sum::<chrono::Duration>() / n)
+        };
+        let finished = |t: &TaskTiming| match (t.started_at, t.completed_at) {
+            (Some(s), Some(c)) => Some(c - s),
+            _ => None,
+        };
+        let avg = average(timings.iter().filter_map(|t| finished(t)).collect())
+            .or_else(|| average(self.task_times.values().filter_map(finished).collect()));
+
+        let remaining = match estimate {
+            Some(e) => Some(
+                e.remaining + avg.map_or(chrono::Duration::zero(), |a| a * e.unestimated as i32),
+            ),
+            None => avg.filter(|_| open > 0).map(|a| a * open as i32),
+        };
+        PhaseTiming {
+            elapsed: Some(end - first),
+            remaining,
+        }
+    }
+
+    /// Time spent on a task since its run started, zero before that
+    fn time_spent(&self, task: &ParsedTask, now: DateTime<Utc>) -> chrono::Duration {
+        self.task_times
+            .get(&task.id)
+            .and_then(|t| t.started_at)
+            .map_or(chrono::Duration::zero(), |s| now - s)
+    }
+
+    /// Estimated time left for an open task: its `est:` estimate, else the
+    /// average duration of finished tasks, minus the time already spent
+    /// when it has started
+    pub fn task_eta(&self, task: &ParsedTask, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        if task.status == TaskStatus::Completed {
+            return None;
+        }
+        if let Some(estimate) = task
+            .estimate
+            .and_then(|e| chrono::Duration::from_std(e).ok())
+        {
+            return Some((estimate - self.time_spent(task, now)).max(chrono::Duration::zero()));
+        }
+        let finished: Vec<chrono::Duration> = self
+            .task_times
+            .values()
+            .filter_map(|t| Some(t.completed_at? - t.started_at?))
+            .collect();
+        if finished.is_empty() {
+            return None;
+        }
+        let avg = finished.iter().copied().sum::<chrono::Duration>() / finished.len() as i32;
+        Some((avg - self.time_spent(task, now)).max(chrono::Duration::zero()))
+    }
+
+    /// Estimates of `tasks` added up as of `now`; `None` when none of them
+    /// has one
+    pub fn estimate_rollup<'t>(
+        &self,
+        tasks: impl IntoIterator<Item = &'t ParsedTask>,
+        now: DateTime<Utc>,
+    ) -> Option<EstimateRollup> {
+        let mut rollup = EstimateRollup {
+            total: chrono::Duration::zero(),
+            remaining: chrono::Duration::zero(),
+            unestimated: 0,
+        };
+        let mut estimated = false;
+        for task in tasks {
+            let open = task.status != TaskStatus::Completed;
+            match task
+                .estimate
+                .and_then(|e| chrono::Duration::from_std(e).ok())
+            {
+                Some(estimate) => {
+                    estimated = true;
+                    rollup.total += estimate;
+                    if open {
+                        let left = estimate - self.time_spent(task, now);
+                        rollup.remaining += left.max(chrono::Duration::zero());
+                    }
+                }
+                None if open => rollup.unestimated += 1,
+                None => {}
+            }
+        }
+        estimated.then_some(rollup)
+    }
+
+    /// Estimates of all tasks added up as of `now`
+    pub fn overall_estimate(&self, now: DateTime<Utc>) -> Option<EstimateRollup> {
+        self.estimate_rollup(self.phases.iter().flat_map(|p| &p.tasks), now)
+    }
+
+    /// Agent names seen in hook events or `@agent` annotations, sorted
+    pub fn known_agents(&self) -> Vec<String> {
+        let names: BTreeSet<&String> = self
+            .agents
+            .keys()
+            .chain(self.task_agents.values())
+            .chain(
+                self.phases
+                    .iter()
+                    .flat_map(|p| p.tasks.iter())
+                    .filter_map(|t| t.agent.as_ref()),
+            )
+            .collect();
+        names.into_iter().cloned().collect()
+    }
+
+    /// Look up a task by id across all phases
+    pub fn find_task(&self, task_id: &str) -> Option<&ParsedTask> {
+        self.phases
+            .iter()
+            .flat_map(|p| p.tasks.iter())
+            .find(|t| t.id == task_id)
+    }
+
+    /// Dependencies of `task` that are not completed, with their status
+    /// (`None` when the dependency id does not exist in TASKS.md)
+    pub fn unmet_dependencies(&self, task: &ParsedTask) -> Vec<(String, Option<TaskStatus>)> {
+        task.blocked_by
+            .iter()
+            .filter_map(|dep| match self.find_task(dep) {
+                Some(t) if t.status == TaskStatus::Completed => None,
+                Some(t) => Some((dep.clone(), Some(t.status.clone()))),
+                None => Some((dep.clone(), None)),
+            })
+            .collect()
+    }
+
+    /// Hook events recorded for a task, oldest first
+    pub fn activity_for_task(&self, task_id: &str) -> &[TaskActivity] {
+        self.task_activity
+            .get(task_id)
+            .map(|a| a.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Re-detect the git branch of every agent's working directory
+    pub fn refresh_checkouts(&mut self) {
+        let mut seen: HashMap<String, Option<Checkout>> = HashMap::new();
+        for agent in self.agents.values_mut() {
+            agent.checkout = agent.cwd.as_ref().and_then(|cwd| {
+                seen.entry(cwd.clone())
+                    .or_insert_with(|| git_branch::detect(Path::new(cwd)))
+                    .clone()
+            });
+        }
+    }
+
+    /// Other running agents on the same branch as `agent_id` (which must be
+    /// running itself), sorted; editing one branch from two agents tends to
+    /// end in conflicts
+    pub fn agents_sharing_branch(&self, agent_id: &str) -> Vec<&str> {
+        let Some(checkout) = self
+            .agents
+            .get(agent_id)
+            .filter(|a| a.status == AgentStatus::Running)
+            .and_then(|a| a.checkout.as_ref())
+        else {
+            return Vec::new();
+        };
+        let mut others: Vec<&str> = self
+            .agents
+            .values()
+            .filter(|a| a.agent_id != agent_id && a.status == AgentStatus::Running)
+            .filter(|a| a.checkout.as_ref().is_some_and(|c| c.same_branch(checkout)))
+            .map(|a| a.agent_id.as_str())
+            .collect();
+        others.sort();
+        others
+    }
+
+    /// Commits whose subject mentions a task, newest first
+    pub fn commits_for_task(&self, task_id: &str) -> Vec<&GitCommit> {
+        self.commits
+            .iter()
+            .filter(|c| c.mentions(task_id))
+            .collect()
+    }
+
+    /// Parser warnings on the lines of one task
+    pub fn warnings_for_task(&self, task_id: &str) -> Vec<&TaskWarning> {
+        self.task_warnings
+            .iter()
+            .filter(|w| w.task_id.as_deref() == Some(task_id))
+            .collect()
+    }
+
+    /// Parser warnings anywhere inside a phase, including its tasks
+    pub fn warnings_for_phase(&self, phase_id: &str) -> Vec<&TaskWarning> {
+        self.task_warnings
+            .iter()
+            .filter(|w| w.phase_id.as_deref() == Some(phase_id))
+            .collect()
+    }
+
     /// Find the agent assigned to a task (from hook event history)
     pub fn agent_for_task(&self, task_id: &str) -> Option<&str> {
         self.task_agents.get(task_id).map(|s| s.as_str())
     }
 
     /// Reload tasks from content (used when file watcher detects changes)
-    pub fn reload_tasks(&mut self, content: &str) -> Result<(), String> {
-        let phases = tasks_parser::parse_tasks_md(content)?;
+    pub fn reload_tasks(&mut self, content: &str) -> Result<(), TasksParseError> {
+        let (phases, warnings) = tasks_parser::parse_tasks_md_with_warnings(content);
         self.update_from_phases(phases);
+        self.task_warnings = warnings;
+        Ok(())
+    }
+
+    /// Re-read TASKS.md from disk, remembering what was parsed so later
+    /// write-backs can detect external changes
+    pub fn reload_tasks_file(&mut self, path: &Path) -> Result<(), TasksParseError> {
+        let loaded = LoadedTasks::read(path).map_err(|e| TasksParseError::from_io(path, e))?;
+        // Saving without changes (or a duplicate watcher event) is not a change
+        let unchanged = self
+            .loaded_tasks
+            .as_ref()
+            .is_some_and(|l| l.content() == loaded.content());
+        if !unchanged {
+            self.reload_tasks(loaded.content())?;
+        }
+        self.loaded_tasks = Some(loaded);
         Ok(())
     }
 }
@@ -301,6 +1562,47 @@ mod tests {
         assert_eq!(state.overall_progress, 0.0);
     }
 
+    #[test]
+    fn state_round_trips_through_json() {
+        let mut state = DashboardState::from_tasks_content(include_str!(
+            "../../tests/fixtures/sample_tasks.md"
+        ))
+        .unwrap();
+        for input in [
+            include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl"),
+            include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl"),
+        ] {
+            state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        }
+        state.agent_identity = Some(AgentIdentity::new(r"^(?P<role>.+)-\d+$").unwrap());
+
+        let value = serde_json::to_value(&state).unwrap();
+        assert_eq!(value["phases"][0]["tasks"][0]["status"], "completed");
+        assert_eq!(value["agent_identity"], r"^(?P<role>.+)-\d+$");
+        assert!(value.get("loaded_tasks").is_none());
+        let first_error = &value["recent_errors"][0];
+        assert!(first_error["suggestion"]
+            .as_str()
+            .is_some_and(|s| !s.is_empty()));
+
+        let back: DashboardState = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), value);
+        assert_eq!(back.total_tasks, state.total_tasks);
+    }
+
+    #[test]
+    fn state_fields_default_when_missing() {
+        let state: DashboardState =
+            serde_json::from_str(r#"{"total_tasks":3,"agents":{"a":{"agent_id":"a","status":"Running","current_task":null,"current_tool":null,"event_count":1,"error_count":0,"open_errors":0,"task_history":[],"first_seen":null,"last_seen":null,"tool_counts":{},"recent_tools":[],"session_id":null,"tool_invocations":[],"status_history":[],"input_tokens":0,"output_tokens":0,"cost_usd":0.0,"cwd":null}}}"#)
+                .unwrap();
+        assert_eq!(state.total_tasks, 3);
+        assert!(state.phases.is_empty());
+        // Snapshots from before the lowercase names still read
+        assert_eq!(state.agents["a"].status, AgentStatus::Running);
+        let value = serde_json::to_value(&state.agents["a"]).unwrap();
+        assert_eq!(value["status"], "running");
+    }
+
     #[test]
     fn from_tasks_content() {
         let input = include_str!("../../tests/fixtures/sample_tasks.md");
@@ -343,6 +1645,82 @@ mod tests {
         assert!(agent.current_tool.is_none());
     }
 
+    #[test]
+    fn dismissed_errors_stay_hidden_after_reload() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        let events = hook_parser::parse_hook_events(input).events;
+
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+        state.dismiss_task_errors("P1-R3-T1");
+        assert!(state.recent_errors.is_empty());
+
+        state.reload_from_events(&events);
+        assert!(state.recent_errors.is_empty());
+        // The agent's own error count is history and is kept
+        assert_eq!(state.agents["backend-specialist-2"].error_count, 2);
+
+        // A new error after the reset shows up again
+        let mut later = events[1].clone();
+        later.timestamp += chrono::Duration::minutes(5);
+        state.update_from_events(&[later]);
+        assert_eq!(state.recent_errors.len(), 1);
+    }
+
+    #[test]
+    fn acknowledged_errors_are_kept_but_no_longer_open() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        let events = hook_parser::parse_hook_events(input).events;
+        let agent_id = "backend-specialist-2";
+
+        let mut state = DashboardState::default();
+        state.update_from_events(&events);
+        assert_eq!(state.total_open_errors(Utc::now()), 2);
+        assert_eq!(state.acknowledge_agent_errors(agent_id), 2);
+        assert_eq!(state.acknowledge_agent_errors("nobody"), 0);
+
+        state.reload_from_events(&events);
+        let agent = &state.agents[agent_id];
+        assert_eq!((agent.error_count, agent.open_errors), (2, 0));
+        assert_eq!(state.recent_errors.len(), 2);
+        assert!(state
+            .recent_errors
+            .iter()
+            .all(|e| state.error_acknowledged(e)));
+        assert_eq!(state.open_errors_for_agent(agent_id).count(), 0);
+
+        // A later error is open again
+        let mut later = events[2].clone();
+        later.timestamp += chrono::Duration::minutes(5);
+        state.update_from_events(&[later]);
+        assert_eq!(state.total_open_errors(Utc::now()), 1);
+        let open: Vec<_> = state.open_errors_for_agent(agent_id).collect();
+        assert_eq!(open.len(), 1);
+        assert!(open[0].message.contains("connection refused"));
+    }
+
+    #[test]
+    fn muted_agents_leave_the_open_error_total_until_the_mute_ends() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        let now = Utc::now();
+        let until = now + chrono::Duration::minutes(15);
+
+        state.mute_agent("backend-specialist-2", until);
+        assert_eq!(state.muted_until("backend-specialist-2", now), Some(until));
+        assert_eq!(state.total_open_errors(now), 0);
+        // Muting hides alerts; the errors themselves stay open
+        assert_eq!(state.agents["backend-specialist-2"].open_errors, 2);
+
+        let later = until + chrono::Duration::seconds(1);
+        assert_eq!(state.muted_until("backend-specialist-2", later), None);
+        assert_eq!(state.total_open_errors(later), 2);
+
+        state.unmute_agent("backend-specialist-2");
+        assert_eq!(state.total_open_errors(now), 2);
+    }
+
     #[test]
     fn update_from_error_events() {
         let input = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
@@ -373,16 +1751,21 @@ mod tests {
     #[test]
     fn recent_errors_capped_at_max() {
         let mut state = DashboardState::default();
-        // Generate 55 error events to exceed the 50 cap
+        // Generate 55 error events to exceed the 50 cap; one agent each so
+        // they are not grouped
         let events: Vec<HookEvent> = (0..55)
             .map(|i| HookEvent {
                 event_type: EventType::Error,
                 timestamp: Utc::now(),
-                agent_id: "test-agent".to_string(),
+                agent_id: format!("agent-{i}"),
                 task_id: format!("T-{i}"),
                 session_id: "sess-cap".to_string(),
                 tool_name: None,
                 error_message: Some(format!("error {i}")),
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             })
             .collect();
         state.update_from_events(&events);
@@ -391,6 +1774,171 @@ mod tests {
         assert_eq!(state.recent_errors[0].task_id, "T-5");
     }
 
+    #[test]
+    fn untracked_task_maps_capped_at_max() {
+        let mut state = DashboardState::from_tasks_content(include_str!(
+            "../../tests/fixtures/sample_tasks.md"
+        ))
+        .unwrap();
+        state.limits.max_tasks = 3;
+        let line = |minute: u32, task: &str| {
+            format!(
+                r#"{{"event_type":"agent_start","timestamp":"2026-02-08T10:{minute:02}:00Z","agent_id":"a{minute}","task_id":"{task}","session_id":"s"}}"#
+            )
+        };
+        let mut lines = vec![line(0, "P1-R1-T1"), line(1, "unknown")];
+        lines.extend((2..=5).map(|minute| line(minute, &format!("T-{minute}"))));
+        state.update_from_events(&hook_parser::parse_hook_events(&lines.join("\n")).events);
+
+        // Tasks listed in TASKS.md stay however old; the rest keep the newest
+        for map_keys in [
+            state.task_times.keys().collect::<Vec<_>>(),
+            state.task_agents.keys().collect(),
+            state.task_activity.keys().collect(),
+        ] {
+            let mut kept: Vec<&str> = map_keys.into_iter().map(String::as_str).collect();
+            kept.sort();
+            assert_eq!(kept, vec!["P1-R1-T1", "T-3", "T-4", "T-5"]);
+        }
+    }
+
+    #[test]
+    fn least_recently_active_agents_are_evicted_beyond_the_limit() {
+        let mut state = DashboardState {
+            limits: Limits {
+                max_agents: 2,
+                max_errors: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let line = |minute: u32, kind: &str, agent: &str| {
+            format!(
+                r#"{{"event_type":"{kind}","timestamp":"2026-02-08T10:{minute:02}:00Z","agent_id":"{agent}","task_id":"T-{agent}","session_id":"s","tool_name":"Read","error_message":"boom {agent}"}}"#
+            )
+        };
+        let lines = [
+            line(0, "agent_start", "old-runner"),
+            line(1, "error", "stale"),
+            line(2, "agent_end", "stale"),
+            line(3, "tool_start", "fresh"),
+            line(4, "error", "fresh"),
+            line(5, "agent_end", "fresh"),
+        ];
+        state.update_from_events(&hook_parser::parse_hook_events(&lines.join("\n")).events);
+
+        // Idle agents go before running ones, oldest activity first
+        let mut kept: Vec<&str> = state.agents.keys().map(String::as_str).collect();
+        kept.sort();
+        assert_eq!(kept, vec!["fresh", "old-runner"]);
+        assert_eq!(state.recent_errors.len(), 1);
+        assert_eq!(state.recent_errors[0].agent_id, "fresh");
+        // Activity still names the evicted agent, so its name stays interned
+        assert!(state.names.len() >= 3);
+    }
+
+    #[test]
+    fn least_recently_seen_sessions_are_evicted_beyond_the_limit() {
+        let mut state = DashboardState {
+            limits: Limits {
+                max_sessions: 2,
+                ..Default::default()
+            },
+            session_filter: Some("s1".to_string()),
+            ..Default::default()
+        };
+        let lines: Vec<String> = (1..=4)
+            .map(|n| {
+                format!(
+                    r#"{{"event_type":"agent_start","timestamp":"2026-02-08T10:0{n}:00Z","agent_id":"a{n}","task_id":"T1","session_id":"s{n}"}}"#
+                )
+            })
+            .collect();
+        state.update_from_events(&hook_parser::parse_hook_events(&lines.join("\n")).events);
+
+        // The filtered session stays however old it is
+        let mut kept: Vec<&str> = state.sessions.keys().map(String::as_str).collect();
+        kept.sort();
+        assert_eq!(kept, vec!["s1", "s4"]);
+    }
+
+    #[test]
+    fn tool_time_shares_interned_names() {
+        let input = r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Read"}
+{"event_type":"tool_end","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Read"}"#;
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        let agent = &state.agents["a"];
+        let (name, time) = agent.tool_time.get_key_value("Read").unwrap();
+        assert_eq!(time.ended, 1);
+        assert!(Arc::ptr_eq(name, &agent.tool_invocations[0].tool_name));
+    }
+
+    #[test]
+    fn repeated_errors_are_grouped_with_a_count() {
+        let input = r#"{"event_type":"error","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"Connection refused on port 5432"}
+{"event_type":"error","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"permission denied"}
+{"event_type":"error","timestamp":"2026-02-08T10:02:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"connection refused on port 6543"}
+{"event_type":"error","timestamp":"2026-02-08T10:03:00Z","agent_id":"b","task_id":"T2","session_id":"s","error_message":"connection refused on port 5432"}"#;
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+
+        let errors: Vec<(&str, &str, usize)> = state
+            .recent_errors
+            .iter()
+            .map(|e| (e.agent_id.as_str(), e.message.as_str(), e.count))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                ("a", "permission denied", 1),
+                ("a", "connection refused on port 6543", 2),
+                ("b", "connection refused on port 5432", 1),
+            ]
+        );
+        assert_eq!(state.recent_errors[1].repeat_suffix(), " \u{00D7} 2");
+        assert_eq!(state.recent_errors[0].repeat_suffix(), "");
+        // Every occurrence still counts against the agent
+        assert_eq!(state.agents["a"].error_count, 3);
+    }
+
+    #[test]
+    fn retries_count_restarts_after_errors_and_failed_reloads() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [InProgress] T1: A\n\n### [Failed] T2: B\n",
+        )
+        .unwrap();
+        let input = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}
+{"event_type":"error","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"boom"}
+{"event_type":"error","timestamp":"2026-02-08T10:02:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"boom"}
+{"event_type":"agent_start","timestamp":"2026-02-08T10:03:00Z","agent_id":"a","task_id":"T1","session_id":"s"}
+{"event_type":"agent_start","timestamp":"2026-02-08T10:04:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#;
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        // Two errors in one run, then one restart; the clean restart is not a retry
+        assert_eq!(state.phases[0].tasks[0].retries, 1);
+
+        state
+            .reload_tasks("# Phase 1: Core\n\n### [InProgress] T1: A\n\n### [InProgress] T2: B\n")
+            .unwrap();
+        assert_eq!(state.phases[0].tasks[1].retries, 1);
+        state
+            .reload_tasks("# Phase 1: Core\n\n### [InProgress] T1: A\n\n### [Failed] T2: B\n")
+            .unwrap();
+        state
+            .reload_tasks("# Phase 1: Core\n\n### [InProgress] T1: A\n\n### [InProgress] T2: B\n")
+            .unwrap();
+        // Events still count after the reload
+        assert_eq!(state.phases[0].tasks[0].retries, 1);
+        assert_eq!(state.phases[0].tasks[1].retries, 2);
+
+        let flagged: Vec<&str> = state
+            .tasks_over_retry_threshold(2)
+            .into_iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(flagged, ["T2"]);
+    }
+
     #[test]
     fn agent_running_state() {
         let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
@@ -443,6 +1991,11 @@ mod tests {
         state.reload_tasks(content2).unwrap();
         assert_eq!(state.completed_tasks, 2);
         assert!((state.overall_progress - 1.0).abs() < f32::EPSILON);
+
+        // Each change of the counts is sampled once
+        state.reload_tasks(content2).unwrap();
+        let closed: Vec<usize> = state.progress_samples.iter().map(|s| s.closed).collect();
+        assert_eq!(closed, [1, 2]);
     }
 
     #[test]
@@ -456,6 +2009,10 @@ mod tests {
             session_id: "sess-1".to_string(),
             tool_name: Some("Edit".to_string()),
             error_message: None,
+            file_path: None,
+            args: None,
+            cwd: None,
+            usage: None,
         }];
         state.update_from_events(&events);
 
@@ -476,6 +2033,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolEnd,
@@ -485,6 +2046,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             },
         ];
         state.update_from_events(&events);
@@ -506,6 +2071,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: None,
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolStart,
@@ -515,6 +2084,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolEnd,
@@ -524,6 +2097,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             },
         ];
         state.update_from_events(&events);
@@ -547,6 +2124,10 @@ mod tests {
             session_id: "sess-1".to_string(),
             tool_name: Some("Edit".to_string()),
             error_message: None,
+            file_path: None,
+            args: None,
+            cwd: None,
+            usage: None,
         }];
         state.update_from_events(&events);
         assert_eq!(state.agents.get("main").unwrap().event_count, 1);
@@ -561,6 +2142,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Edit".to_string()),
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             },
             HookEvent {
                 event_type: EventType::ToolStart,
@@ -570,6 +2155,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some("Bash".to_string()),
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             },
         ];
         state.reload_from_events(&events2);
@@ -645,7 +2234,7 @@ mod tests {
         state.update_from_events(&result.events);
 
         let agent = state.agents.get("backend-specialist-1").unwrap();
-        assert_eq!(agent.recent_tools, vec!["Read", "Write"]);
+        assert_eq!(agent.recent_tools, vec!["Read".into(), "Write".into()]);
     }
 
     #[test]
@@ -660,6 +2249,10 @@ mod tests {
                 session_id: "sess-1".to_string(),
                 tool_name: Some(format!("Tool{i}")),
                 error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
             })
             .collect();
         state.update_from_events(&events);
@@ -667,8 +2260,145 @@ mod tests {
         let agent = state.agents.get("agent-1").unwrap();
         assert_eq!(agent.recent_tools.len(), 10);
         // Oldest 5 should be evicted; first remaining is Tool5
-        assert_eq!(agent.recent_tools[0], "Tool5");
-        assert_eq!(agent.recent_tools[9], "Tool14");
+        assert_eq!(&*agent.recent_tools[0], "Tool5");
+        assert_eq!(&*agent.recent_tools[9], "Tool14");
+    }
+
+    #[test]
+    fn tool_invocations_record_durations() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+
+        let mut state = DashboardState::default();
+        state.update_from_events(&result.events);
+
+        let agent = state.agents.get("backend-specialist-1").unwrap();
+        assert_eq!(agent.tool_invocations.len(), 2);
+        assert_eq!(&*agent.tool_invocations[0].tool_name, "Read");
+        // Read: tool_start 10:00:05 → tool_end 10:00:06
+        assert_eq!(
+            agent.tool_invocations[0]
+                .duration()
+                .map(|d| d.num_seconds()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn tool_invocations_capped_at_max() {
+        let mut state = DashboardState::default();
+        let events: Vec<HookEvent> = (0..25)
+            .map(|i| HookEvent {
+                event_type: EventType::ToolStart,
+                timestamp: Utc::now(),
+                agent_id: "agent-1".to_string(),
+                task_id: "T-1".to_string(),
+                session_id: "sess-1".to_string(),
+                tool_name: Some(format!("Tool{i}")),
+                error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
+            })
+            .collect();
+        state.update_from_events(&events);
+
+        let agent = state.agents.get("agent-1").unwrap();
+        assert_eq!(agent.tool_invocations.len(), 20);
+        assert_eq!(&*agent.tool_invocations[0].tool_name, "Tool5");
+        assert!(agent.tool_invocations.iter().all(|t| t.ended_at.is_none()));
+    }
+
+    #[test]
+    fn status_history_records_transitions() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+
+        let mut state = DashboardState::default();
+        state.update_from_events(&result.events);
+
+        let agent = state.agents.get("backend-specialist-2").unwrap();
+        let statuses: Vec<AgentStatus> = agent
+            .status_history
+            .iter()
+            .map(|c| c.status.clone())
+            .collect();
+        // start → error (twice, collapsed) → end
+        assert_eq!(
+            statuses,
+            vec![AgentStatus::Running, AgentStatus::Error, AgentStatus::Idle]
+        );
+    }
+
+    #[test]
+    fn unmet_dependencies_lists_incomplete_and_missing() {
+        let input = "# Phase 0: Setup\n\n### [x] P0-T1: Done\n\n### [ ] P0-T2: Todo\n\n### [Blocked] P0-T3: Waits\n- **blocked_by**: P0-T1, P0-T2, P9-X\n";
+        let state = DashboardState::from_tasks_content(input).unwrap();
+        let task = state.find_task("P0-T3").unwrap();
+        let unmet = state.unmet_dependencies(task);
+        assert_eq!(
+            unmet,
+            vec![
+                ("P0-T2".to_string(), Some(TaskStatus::Pending)),
+                ("P9-X".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn pending_tasks_are_blocked_until_their_dependencies_complete() {
+        let input = "# Phase 1: Core\n### [x] P1-T1: a\n### [ ] P1-T2: b\n### [ ] P1-T3: c\n- depends: P1-T1, P1-T2\n### [InProgress] P1-T4: d\n- depends: P1-T2\n### [ ] P1-T5: e\n- depends: P1-T1, P9-X\n";
+        let mut state = DashboardState::from_tasks_content(input).unwrap();
+        let shown =
+            |state: &DashboardState, id| state.find_task(id).unwrap().shown_status().clone();
+        let waiting = state.find_task("P1-T3").unwrap();
+        assert_eq!(waiting.status, TaskStatus::Pending);
+        assert_eq!(waiting.waiting_on, vec!["P1-T2".to_string()]);
+        assert_eq!(shown(&state, "P1-T3"), TaskStatus::Blocked);
+        // Work already started and unknown IDs are left alone
+        assert_eq!(shown(&state, "P1-T4"), TaskStatus::InProgress);
+        assert_eq!(shown(&state, "P1-T5"), TaskStatus::Pending);
+
+        state
+            .reload_tasks(&input.replace("[ ] P1-T2", "[x] P1-T2"))
+            .unwrap();
+        assert!(state.find_task("P1-T3").unwrap().waiting_on.is_empty());
+        assert_eq!(shown(&state, "P1-T3"), TaskStatus::Pending);
+    }
+
+    #[test]
+    fn token_usage_accumulates_per_agent() {
+        use crate::data::hook_parser::TokenUsage;
+
+        let mut state = DashboardState::default();
+        let events: Vec<HookEvent> = (0..3)
+            .map(|_| HookEvent {
+                event_type: EventType::ToolEnd,
+                timestamp: Utc::now(),
+                agent_id: "agent-1".to_string(),
+                task_id: "T-1".to_string(),
+                session_id: "sess-1".to_string(),
+                tool_name: Some("Read".to_string()),
+                error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: Some(TokenUsage {
+                    input_tokens: 1000,
+                    output_tokens: 200,
+                    cost_usd: 0.5,
+                }),
+            })
+            .collect();
+        state.update_from_events(&events);
+
+        let agent = state.agents.get("agent-1").unwrap();
+        assert_eq!(agent.input_tokens, 3000);
+        assert_eq!(agent.output_tokens, 600);
+        assert!((agent.cost_usd - 1.5).abs() < 1e-9);
+        assert_eq!(state.total_tokens(), 3600);
+        assert!((state.total_cost() - 1.5).abs() < 1e-9);
     }
 
     #[test]
@@ -683,6 +2413,290 @@ mod tests {
         assert_eq!(agent.session_id.as_deref(), Some("sess-001"));
     }
 
+    #[test]
+    fn sessions_summarized_from_events() {
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let mut state = DashboardState::default();
+        state.load_hook_events(&hooks_dir).unwrap();
+
+        let errs = state.sessions.get("sess-002").unwrap();
+        assert_eq!(errs.agent_count(), 1);
+        assert_eq!(errs.error_count, 2);
+        assert_eq!(errs.event_count, 4);
+
+        // Most recent session first
+        let sorted = state.sorted_sessions();
+        assert!(sorted.len() >= 2);
+        assert!(sorted[0].started_at >= sorted[1].started_at);
+    }
+
+    #[test]
+    fn session_filter_limits_agents() {
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let mut state = DashboardState {
+            session_filter: Some("sess-001".to_string()),
+            ..Default::default()
+        };
+        state.load_hook_events(&hooks_dir).unwrap();
+
+        assert_eq!(state.agents.len(), 1);
+        assert!(state.agents.contains_key("backend-specialist-1"));
+        assert!(state.recent_errors.is_empty());
+        // The picker still sees every session
+        assert!(state.sessions.len() >= 2);
+    }
+
+    #[test]
+    fn agent_identity_folds_instances() {
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let mut state = DashboardState {
+            agent_identity: Some(AgentIdentity::new(r"^(.+)-\d+$").unwrap()),
+            ..Default::default()
+        };
+        state.load_hook_events(&hooks_dir).unwrap();
+
+        let mut ids: Vec<&str> = state.agents.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, ["backend-specialist", "test"]);
+        let backend = &state.agents["backend-specialist"];
+        assert_eq!(backend.event_count, 10);
+        assert_eq!(
+            backend.instances.iter().collect::<Vec<_>>(),
+            ["backend-specialist-1", "backend-specialist-2"]
+        );
+        assert_eq!(state.logical_agent_id("test-1"), "test");
+        assert_eq!(state.logical_agent_id("main"), "main");
+    }
+
+    #[test]
+    fn phase_timing_elapsed_and_eta() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [x] T1: a\n### [/] T2: b\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        let t0 = Utc::now();
+        let phase = state.phases[0].clone();
+        assert_eq!(state.phase_timing(&phase, t0), PhaseTiming::default());
+
+        let mins = chrono::Duration::minutes;
+        state.task_times.insert(
+            "T1".into(),
+            TaskTiming {
+                started_at: Some(t0),
+                completed_at: Some(t0 + mins(20)),
+                ..Default::default()
+            },
+        );
+        state.task_times.insert(
+            "T2".into(),
+            TaskTiming {
+                started_at: Some(t0 + mins(20)),
+                completed_at: None,
+                ..Default::default()
+            },
+        );
+        state.refresh_phase_times();
+        let phase = state.phases[0].clone();
+        let timing = state.phase_timing(&phase, t0 + mins(30));
+        assert_eq!(timing.elapsed, Some(mins(30)));
+        // Two open tasks at 20m average
+        assert_eq!(timing.remaining, Some(mins(40)));
+    }
+
+    #[test]
+    fn task_eta_subtracts_time_spent() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [x] T1: a\n### [/] T2: b\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        let t0 = Utc::now();
+        let mins = chrono::Duration::minutes;
+        let tasks = state.phases[0].tasks.clone();
+        assert!(state.task_eta(&tasks[2], t0).is_none());
+
+        state.task_times.insert(
+            "T1".into(),
+            TaskTiming {
+                started_at: Some(t0),
+                completed_at: Some(t0 + mins(20)),
+                ..Default::default()
+            },
+        );
+        state.task_times.insert(
+            "T2".into(),
+            TaskTiming {
+                started_at: Some(t0 + mins(20)),
+                completed_at: None,
+                ..Default::default()
+            },
+        );
+        let now = t0 + mins(25);
+        assert!(state.task_eta(&tasks[0], now).is_none());
+        assert_eq!(state.task_eta(&tasks[1], now), Some(mins(15)));
+        assert_eq!(state.task_eta(&tasks[2], now), Some(mins(20)));
+        // Overrunning tasks bottom out at zero
+        assert_eq!(state.task_eta(&tasks[1], t0 + mins(90)), Some(mins(0)));
+    }
+
+    #[test]
+    fn estimates_roll_up_per_phase_and_overall() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [x] T1: a\n- est: 1h\n### [/] T2: b\n- est: 2h\n### [ ] T3: c\n# Phase 1: B\n### [ ] T4: d\n- est: 30m\n",
+        )
+        .unwrap();
+        let t0 = Utc::now();
+        let mins = chrono::Duration::minutes;
+        state.task_times.insert(
+            "T2".into(),
+            TaskTiming {
+                started_at: Some(t0),
+                ..Default::default()
+            },
+        );
+        let now = t0 + mins(30);
+        let tasks = state.phases[0].tasks.clone();
+        // An estimate wins over the (missing) average and counts down
+        assert_eq!(state.task_eta(&tasks[1], now), Some(mins(90)));
+
+        let phase = state.estimate_rollup(&state.phases[0].tasks, now).unwrap();
+        assert_eq!(phase.total, mins(180));
+        assert_eq!(phase.remaining, mins(90));
+        assert_eq!(phase.unestimated, 1);
+        let overall = state.overall_estimate(now).unwrap();
+        assert_eq!(overall.remaining, mins(120));
+
+        // Not started yet: the estimates alone
+        let phase = state.phases[1].clone();
+        assert_eq!(state.phase_timing(&phase, now).remaining, Some(mins(30)));
+        assert!(state
+            .estimate_rollup(&state.phases[0].tasks[2..], now)
+            .is_none());
+    }
+
+    #[test]
+    fn huge_estimates_do_not_overflow() {
+        let huge = "- est: 2000000000000h\n";
+        let state = DashboardState::from_tasks_content(&format!(
+            "# Phase 0: A\n### [ ] T1: a\n{huge}### [ ] T2: b\n{huge}### [ ] T3: c\n{huge}"
+        ))
+        .unwrap();
+        let now = Utc::now();
+        assert!(state.overall_estimate(now).is_none());
+        assert_eq!(state.phase_timing(&state.phases[0], now).remaining, None);
+
+        let max = "- est: 10000h\n";
+        let state = DashboardState::from_tasks_content(&format!(
+            "# Phase 0: A\n### [ ] T1: a\n{max}### [ ] T2: b\n{max}### [ ] T3: c\n{max}"
+        ))
+        .unwrap();
+        let overall = state.overall_estimate(now).unwrap();
+        assert_eq!(overall.total, chrono::Duration::hours(30_000));
+        crate::analysis::forecast::Forecast::compute(&state, now);
+    }
+
+    #[test]
+    fn phase_timing_completed_phase_has_no_eta() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 0: A\n### [x] T1: a\n").unwrap();
+        let t0 = Utc::now();
+        state.task_times.insert(
+            "T1".into(),
+            TaskTiming {
+                started_at: Some(t0),
+                completed_at: Some(t0 + chrono::Duration::minutes(5)),
+                ..Default::default()
+            },
+        );
+        state.refresh_phase_times();
+        let phase = state.phases[0].clone();
+        let timing = state.phase_timing(&phase, t0 + chrono::Duration::hours(3));
+        assert_eq!(timing.elapsed, Some(chrono::Duration::minutes(5)));
+        assert!(timing.remaining.is_none());
+    }
+
+    #[test]
+    fn task_warnings_kept_across_reloads() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 0: A\n### [x] T1: a\n### [x] T1: b\n")
+                .unwrap();
+        assert_eq!(state.warnings_for_task("T1").len(), 1);
+        assert_eq!(state.warnings_for_phase("P0").len(), 1);
+        assert!(state.warnings_for_phase("P1").is_empty());
+
+        state.reload_tasks("# Phase 0: A\n### [x] T1: a\n").unwrap();
+        assert!(state.task_warnings.is_empty());
+    }
+
+    #[test]
+    fn known_agents_merges_hooks_and_annotations() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [ ] T1: a\n- @writer\n### [ ] T2: b\n- @backend-specialist-1\n",
+        )
+        .unwrap();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        assert_eq!(
+            state.known_agents(),
+            vec!["backend-specialist-1".to_string(), "writer".to_string()]
+        );
+    }
+
+    #[test]
+    fn task_activity_recorded_and_capped() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+        let mut state = DashboardState::default();
+        state.update_from_events(&result.events);
+
+        let activity = state.activity_for_task("P1-R1-T1");
+        assert_eq!(activity.len(), 6);
+        assert_eq!(activity[0].event_type, EventType::AgentStart);
+        assert_eq!(activity[1].tool_name.as_deref(), Some("Read"));
+        assert!(state.activity_for_task("nope").is_empty());
+
+        let burst: Vec<HookEvent> = (0..MAX_TASK_ACTIVITY + 5)
+            .map(|_| HookEvent {
+                event_type: EventType::ToolStart,
+                timestamp: Utc::now(),
+                agent_id: "main".to_string(),
+                task_id: "P1-R1-T1".to_string(),
+                session_id: "sess-001".to_string(),
+                tool_name: Some("Bash".to_string()),
+                error_message: None,
+                file_path: None,
+                args: None,
+                cwd: None,
+                usage: None,
+            })
+            .collect();
+        state.update_from_events(&burst);
+        let activity = state.activity_for_task("P1-R1-T1");
+        assert_eq!(activity.len(), MAX_TASK_ACTIVITY);
+        assert!(activity.iter().all(|a| &*a.agent_id == "main"));
+
+        state.clear_events();
+        assert!(state.activity_for_task("P1-R1-T1").is_empty());
+    }
+
+    #[test]
+    fn absorb_events_keeps_tasks() {
+        let tasks_input = include_str!("../../tests/fixtures/sample_tasks.md");
+        let mut state = DashboardState::from_tasks_content(tasks_input).unwrap();
+
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let mut loaded = DashboardState::default();
+        loaded.load_hook_events(&hooks_dir).unwrap();
+        let agent_count = loaded.agents.len();
+
+        state.absorb_events(loaded);
+        assert_eq!(state.total_tasks, 8);
+        assert_eq!(state.agents.len(), agent_count);
+    }
+
     #[test]
     fn full_pipeline() {
         let tasks_input = include_str!("../../tests/fixtures/sample_tasks.md");
@@ -701,4 +2715,75 @@ mod tests {
         assert!(state.agents.contains_key("backend-specialist-1"));
         assert!(state.agents.contains_key("backend-specialist-2"));
     }
+
+    #[test]
+    fn running_agents_on_one_branch_are_flagged() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let git = tmp.path().join(".git");
+        std::fs::create_dir_all(&git).unwrap();
+        std::fs::write(git.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        let cwd = tmp.path().to_string_lossy().into_owned();
+
+        let event = |agent: &str, event_type: EventType, cwd: Option<&str>| HookEvent {
+            event_type,
+            timestamp: Utc::now(),
+            agent_id: agent.to_string(),
+            task_id: "T1".to_string(),
+            session_id: "s".to_string(),
+            tool_name: None,
+            error_message: None,
+            file_path: None,
+            args: None,
+            cwd: cwd.map(str::to_string),
+            usage: None,
+        };
+        let mut state = DashboardState::default();
+        state.reload_from_events(&[
+            event("a", EventType::AgentStart, Some(&cwd)),
+            event("b", EventType::AgentStart, Some(&cwd)),
+            event("c", EventType::AgentStart, None),
+        ]);
+        assert_eq!(state.agents["a"].checkout.as_ref().unwrap().branch, "main");
+        assert!(state.agents["c"].checkout.is_none());
+        assert_eq!(state.agents_sharing_branch("a"), vec!["b"]);
+        assert!(state.agents_sharing_branch("c").is_empty());
+
+        // Once b is done, a is alone on the branch again
+        state.update_from_events(&[event("b", EventType::AgentEnd, None)]);
+        assert!(state.agents_sharing_branch("a").is_empty());
+        assert_eq!(state.agents["b"].cwd.as_deref(), Some(cwd.as_str()));
+    }
+
+    #[test]
+    fn silent_running_agents_are_presumed_finished() {
+        let input = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}
+{"event_type":"tool_start","timestamp":"2026-02-08T10:05:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}
+{"event_type":"agent_start","timestamp":"2026-02-08T10:20:00Z","agent_id":"b","task_id":"T2","session_id":"s"}"#;
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        let window = chrono::Duration::minutes(10);
+
+        let now = "2026-02-08T10:25:00Z".parse().unwrap();
+        assert_eq!(state.expire_idle_agents(now, window), 1);
+        assert_eq!(state.active_agents(), 1);
+        let a = &state.agents["a"];
+        assert_eq!(a.status, AgentStatus::Idle);
+        assert!(a.presumed_finished);
+        assert!(a.current_tool.is_none());
+        assert_eq!(a.current_task.as_deref(), Some("T1"));
+        // Running until the window ran out at 10:15
+        assert_eq!(a.status_time.running_ms, 15 * 60 * 1000);
+        assert_eq!(
+            a.status_history.last().unwrap().at,
+            "2026-02-08T10:15:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(state.expire_idle_agents(now, window), 0);
+
+        // It was still working after all
+        let late = r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:30:00Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Bash"}"#;
+        state.update_from_events(&hook_parser::parse_hook_events(late).events);
+        assert_eq!(state.agents["a"].status, AgentStatus::Running);
+        assert!(!state.agents["a"].presumed_finished);
+        assert_eq!(state.active_agents(), 2);
+    }
 }
@@ -0,0 +1,125 @@
+//! Compact `status.json` for editor integrations
+//!
+//! The running dashboard (TUI, `serve` or `mcp`) rewrites `status.json` in
+//! the dashboard directory whenever the summary in it changes, so editor
+//! extensions and statuslines can poll or watch one small file instead of
+//! talking to the dashboard process.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+
+/// File name, inside the dashboard directory
+pub const STATUS_FILE: &str = "status.json";
+
+/// Written first and renamed over `STATUS_FILE`
+const PARTIAL_FILE: &str = "status.json.tmp";
+
+/// Whether `path` is the status file or its partial copy (not an event file)
+pub fn is_status_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == STATUS_FILE || name == PARTIAL_FILE)
+}
+
+/// The summary of `state`, without the time it was written
+pub fn summary(state: &DashboardState, now: DateTime<Utc>) -> Value {
+    let tasks: Vec<_> = state.phases.iter().flat_map(|p| &p.tasks).collect();
+    let in_progress = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::InProgress)
+        .count();
+    let phase = state
+        .phases
+        .iter()
+        .find(|p| p.tasks.iter().any(|t| t.status != TaskStatus::Completed));
+
+    let mut agents: Vec<_> = state.agents.values().collect();
+    agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+
+    json!({
+        "tasks": {
+            "total": state.total_tasks,
+            "completed": state.completed_tasks,
+            "failed": state.failed_tasks,
+            "in_progress": in_progress,
+            "progress": (state.overall_progress * 100.0).round() / 100.0,
+        },
+        "phase": phase.map(|p| json!({
+            "id": p.id,
+            "name": p.name,
+            "completed": p.tasks.iter().filter(|t| t.status == TaskStatus::Completed).count(),
+            "total": p.tasks.len(),
+        })),
+        "agents": agents
+            .iter()
+            .map(|a| json!({
+                "id": a.agent_id,
+                "status": a.status.as_str(),
+                "task": a.current_task,
+                "tool": a.current_tool,
+                "open_errors": a.open_errors,
+            }))
+            .collect::<Vec<_>>(),
+        "failures": {
+            "tasks": tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Failed)
+                .map(|t| json!({ "id": t.id, "name": t.name }))
+                .collect::<Vec<_>>(),
+            "open_errors": state.total_open_errors(now),
+        },
+    })
+}
+
+/// Write `summary` with `updated_at` to `status.json` in `dir`, replacing
+/// the old file in one step
+pub fn write(dir: &Path, summary: &Value, now: DateTime<Utc>) -> std::io::Result<()> {
+    let mut content = summary.clone();
+    content["updated_at"] = json!(now.to_rfc3339());
+    std::fs::create_dir_all(dir)?;
+    let partial = dir.join(PARTIAL_FILE);
+    std::fs::write(&partial, content.to_string())?;
+    std::fs::rename(&partial, dir.join(STATUS_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn summarizes_progress_agents_and_failures() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n### [x] P1-T1: Parser\n### [Failed] P1-T2: Writer\n\
+             # Phase 2: UI\n### [InProgress] P2-T1: Layout\n",
+        )
+        .unwrap();
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P2-T1","session_id":"s"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"P2-T1","session_id":"s","tool_name":"Edit"}"#,
+        ]
+        .join("\n");
+        state.update_from_events(&parse_hook_events(&events).events);
+
+        let summary = summary(&state, Utc::now());
+        assert_eq!(summary["tasks"]["completed"], 1);
+        assert_eq!(summary["tasks"]["in_progress"], 1);
+        assert_eq!(summary["phase"]["id"], "P1");
+        assert_eq!(summary["agents"][0]["task"], "P2-T1");
+        assert_eq!(summary["agents"][0]["tool"], "Edit");
+        assert_eq!(summary["failures"]["tasks"][0]["id"], "P1-T2");
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        write(tmp.path(), &summary, Utc::now()).unwrap();
+        let written: Value =
+            serde_json::from_str(&std::fs::read_to_string(tmp.path().join(STATUS_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(written["tasks"], summary["tasks"]);
+        assert!(written["updated_at"].is_string());
+        assert!(is_status_file(&tmp.path().join(STATUS_FILE)));
+    }
+}
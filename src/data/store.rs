@@ -0,0 +1,127 @@
+//! Snapshot store kept fresh by the collector
+//!
+//! The collector (`collector` subcommand) writes the event-derived dashboard
+//! state to `collector.json` in the events directory, together with how far
+//! it read each event file. Starting from the snapshot, the dashboard only
+//! parses what was appended since instead of the whole history.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::rules::analyze_error;
+use crate::data::state::{
+    AgentState, DashboardState, ErrorRecord, SessionSummary, TaskActivity, TaskTiming,
+};
+use crate::data::versioned_file::VersionedFile;
+
+/// File name of the snapshot, inside the events directory
+pub const STORE_FILE: &str = "collector.json";
+
+const FILE: VersionedFile = VersionedFile::new(STORE_FILE, 1);
+
+/// Whether `path` is the snapshot file or its partial copy (not an event
+/// file)
+pub fn is_store_file(path: &Path) -> bool {
+    FILE.matches(path)
+}
+
+/// The event-derived parts of the dashboard state and the read positions
+/// they cover
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Store {
+    pub written_at: DateTime<Utc>,
+    /// Bytes of each event file aggregated into the state
+    pub offsets: BTreeMap<PathBuf, u64>,
+    agents: HashMap<String, AgentState>,
+    task_times: HashMap<String, TaskTiming>,
+    task_agents: HashMap<String, String>,
+    recent_errors: Vec<ErrorRecord>,
+    task_activity: HashMap<String, Vec<TaskActivity>>,
+    sessions: HashMap<String, SessionSummary>,
+}
+
+impl Store {
+    pub fn capture(state: &DashboardState, offsets: &BTreeMap<PathBuf, u64>) -> Self {
+        Self {
+            written_at: Utc::now(),
+            offsets: offsets.clone(),
+            agents: state.agents.clone(),
+            task_times: state.task_times.clone(),
+            task_agents: state.task_agents.clone(),
+            recent_errors: state.recent_errors.clone(),
+            task_activity: state.task_activity.clone(),
+            sessions: state.sessions.clone(),
+        }
+    }
+
+    /// The stored state, without tasks
+    pub fn into_state(self) -> DashboardState {
+        let mut recent_errors = self.recent_errors;
+        for error in &mut recent_errors {
+            let analysis = analyze_error(&error.message);
+            error.suggestion = analysis.suggestion;
+            error.command = analysis.command;
+        }
+        DashboardState {
+            agents: self.agents,
+            task_times: self.task_times,
+            task_agents: self.task_agents,
+            recent_errors,
+            task_activity: self.task_activity,
+            sessions: self.sessions,
+            ..Default::default()
+        }
+    }
+
+    /// The snapshot at `path`; `None` when missing, unreadable or written by
+    /// another version
+    pub fn read(path: &Path) -> Option<Self> {
+        FILE.read_at(path)
+    }
+
+    /// Replace the snapshot at `path` in one step, so readers never see a
+    /// half-written file
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        FILE.write_at(path, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn round_trips_the_event_state() {
+        let input = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s","error_message":"permission denied"}"#,
+        ]
+        .join("\n");
+        let mut state = DashboardState::default();
+        state.update_from_events(&parse_hook_events(&input).events);
+        let offsets = BTreeMap::from([(PathBuf::from("/e/events.jsonl"), 42)]);
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(STORE_FILE);
+        Store::capture(&state, &offsets).write(&path).unwrap();
+        let store = Store::read(&path).unwrap();
+        assert_eq!(store.offsets, offsets);
+
+        let loaded = store.into_state();
+        assert_eq!(loaded.agents["a"].status, state.agents["a"].status);
+        assert_eq!(loaded.agents["a"].error_count, 1);
+        assert_eq!(
+            loaded.recent_errors[0].suggestion,
+            state.recent_errors[0].suggestion
+        );
+        assert_eq!(loaded.sessions.len(), 1);
+
+        std::fs::write(&path, r#"{"version":0}"#).unwrap();
+        assert!(Store::read(&path).is_none());
+        assert!(is_store_file(&path));
+    }
+}
@@ -0,0 +1,225 @@
+//! Incremental reads of growing event files
+//!
+//! Remembers how far each `*.jsonl` file has been read, so following a file
+//! only parses the lines appended since. Used by `stream`, the collector
+//! and the dashboard itself, which applies each file change as a delta.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::data::hook_parser::{self, HookEvent, ParseResult};
+
+/// Most events parsed and held before they are handed on; bounds the memory
+/// of reading a large file to one batch plus the aggregated state
+pub const BATCH_EVENTS: usize = 2_000;
+
+/// Read positions of the followed files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tail {
+    /// Bytes of each file (by canonical path, so relative and absolute
+    /// spellings agree) already read, up to its last full line
+    offsets: BTreeMap<PathBuf, u64>,
+}
+
+impl Tail {
+    /// Continue from positions recorded earlier
+    pub fn from_offsets(offsets: BTreeMap<PathBuf, u64>) -> Self {
+        Self { offsets }
+    }
+
+    pub fn offsets(&self) -> &BTreeMap<PathBuf, u64> {
+        &self.offsets
+    }
+
+    /// Record that `path` has been read up to `offset`, e.g. by a loader
+    /// that parsed it in full
+    pub fn mark_read(&mut self, path: &Path, offset: u64) {
+        self.offsets.insert(key(path), offset);
+    }
+
+    /// Events in the complete lines appended to `path` since the last read;
+    /// a file that shrank is read again from the start
+    pub fn read_new(&mut self, path: &Path) -> Vec<HookEvent> {
+        let mut events = Vec::new();
+        self.read_new_batches(path, |batch| events.extend(batch));
+        events
+    }
+
+    /// Like [`Tail::read_new`], but hands the events to `apply` a batch at a
+    /// time, so a large backlog is never held in memory at once
+    pub fn read_new_batches(&mut self, path: &Path, mut apply: impl FnMut(Vec<HookEvent>)) {
+        if !self.read_batches(path, &mut apply) {
+            self.read_batches(path, &mut apply);
+        }
+    }
+
+    /// Like [`Tail::read_new`], but `None` when `path` shrank since the last
+    /// read (it was truncated or replaced), so whatever was built from the
+    /// earlier lines is stale. The file is then read from the start next time.
+    pub fn read_appended(&mut self, path: &Path) -> Option<Vec<HookEvent>> {
+        let mut events = Vec::new();
+        self.read_batches(path, |batch| events.extend(batch))
+            .then_some(events)
+    }
+
+    /// Hand the events appended to `path` to `apply` in batches; false when
+    /// `path` shrank, as for [`Tail::read_appended`]
+    fn read_batches(&mut self, path: &Path, apply: impl FnMut(Vec<HookEvent>)) -> bool {
+        let Ok(mut file) = File::open(path) else {
+            return true;
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let offset = self.offsets.entry(key(path)).or_default();
+        if len < *offset {
+            *offset = 0;
+            return false;
+        }
+        if file.seek(SeekFrom::Start(*offset)).is_err() {
+            return true;
+        }
+        *offset += read_lines_batched(BufReader::new(file), apply, |_| {});
+        true
+    }
+}
+
+/// Parse the complete lines of `reader` without holding more than
+/// [`BATCH_EVENTS`] events at a time: each batch goes to `apply`, and
+/// `progress` hears the bytes consumed after each one. Returns the bytes of
+/// the complete lines; reading stops at a line still being written.
+pub(crate) fn read_lines_batched(
+    mut reader: impl BufRead,
+    mut apply: impl FnMut(Vec<HookEvent>),
+    mut progress: impl FnMut(u64),
+) -> u64 {
+    let mut batch = ParseResult {
+        events: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut read = 0u64;
+    while let Ok(n) = reader.read_line(&mut line) {
+        if n == 0 {
+            break;
+        }
+        line_number += 1;
+        if !parse_complete_line(&line, line_number, &mut batch) {
+            break;
+        }
+        read += n as u64;
+        line.clear();
+        // Malformed lines are skipped here, so their details are not kept
+        batch.errors.clear();
+        if batch.events.len() >= BATCH_EVENTS {
+            apply(std::mem::take(&mut batch.events));
+            progress(read);
+        }
+    }
+    if !batch.events.is_empty() {
+        apply(batch.events);
+    }
+    progress(read);
+    read
+}
+
+/// Offsets are kept by canonical path, so relative and absolute spellings
+/// agree
+fn key(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Parse `line` into `result` if it is complete, returning whether it was.
+/// A line is complete once it ends in a newline, or, for the last line of a
+/// file written without one, once it parses as an event; anything else is
+/// still being written and is left for the next read.
+pub(crate) fn parse_complete_line(
+    line: &str,
+    line_number: usize,
+    result: &mut ParseResult,
+) -> bool {
+    if line.ends_with('\n') {
+        hook_parser::parse_hook_line(line, line_number, result);
+        return true;
+    }
+    let mut last = ParseResult {
+        events: Vec::new(),
+        errors: Vec::new(),
+    };
+    hook_parser::parse_hook_line(line, line_number, &mut last);
+    if last.events.is_empty() {
+        return false;
+    }
+    result.events.append(&mut last.events);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_files_are_read_in_bounded_batches() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("events.jsonl");
+        let line = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#;
+        let lines = BATCH_EVENTS * 2 + 10;
+        std::fs::write(&path, format!("{line}\nnot json\n").repeat(lines)).unwrap();
+
+        let mut tail = Tail::default();
+        let mut sizes = Vec::new();
+        tail.read_new_batches(&path, |batch| sizes.push(batch.len()));
+        assert_eq!(sizes, vec![BATCH_EVENTS, BATCH_EVENTS, 10]);
+        assert!(tail.read_new(&path).is_empty());
+
+        let mut reports = Vec::new();
+        let file = std::fs::File::open(&path).unwrap();
+        let read = read_lines_batched(BufReader::new(file), |_| {}, |n| reports.push(n));
+        assert_eq!(read, std::fs::metadata(&path).unwrap().len());
+        assert_eq!(reports.len(), 3);
+        assert_eq!(reports.last(), Some(&read));
+    }
+
+    #[test]
+    fn reads_only_complete_new_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("events.jsonl");
+        let line = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#;
+        std::fs::write(&path, format!("{line}\n{}", &line[..20])).unwrap();
+
+        let mut tail = Tail::default();
+        assert_eq!(tail.read_new(&path).len(), 1);
+        assert!(tail.read_new(&path).is_empty());
+        assert_eq!(
+            tail.offsets()[&path.canonicalize().unwrap()],
+            line.len() as u64 + 1
+        );
+
+        std::fs::write(&path, format!("{line}\n{line}\n")).unwrap();
+        assert_eq!(tail.read_new(&path).len(), 1);
+        std::fs::write(&path, format!("{line}\n")).unwrap();
+        assert_eq!(tail.read_new(&path).len(), 1);
+    }
+
+    #[test]
+    fn reports_files_that_shrank() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("events.jsonl");
+        let line = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}"#;
+        // A finished last line counts even without its newline
+        std::fs::write(&path, format!("{line}\n{line}")).unwrap();
+
+        let mut tail = Tail::default();
+        assert_eq!(tail.read_appended(&path).map(|e| e.len()), Some(2));
+        std::fs::write(&path, format!("{line}\n{line}\n")).unwrap();
+        assert_eq!(tail.read_appended(&path).map(|e| e.len()), Some(0));
+
+        std::fs::write(&path, format!("{line}\n")).unwrap();
+        assert!(tail.read_appended(&path).is_none());
+        assert_eq!(tail.read_appended(&path).map(|e| e.len()), Some(1));
+
+        tail.mark_read(&path, line.len() as u64 + 1);
+        assert_eq!(tail.read_appended(&path).map(|e| e.len()), Some(0));
+    }
+}
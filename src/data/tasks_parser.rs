@@ -2,6 +2,16 @@
 //!
 //! Parses TASKS.md format into structured Phase/Task data.
 //! Supports statuses: [x], [ ], [InProgress], [Failed], [Blocked]
+//! Parsing is tolerant: problems are reported as [`TaskWarning`]s instead of
+//! failing the whole file.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 
 use nom::{
     branch::alt,
@@ -12,8 +22,36 @@ use nom::{
     IResult,
 };
 
+/// Why TASKS.md could not be loaded. Problems inside the file are
+/// [`TaskWarning`]s, not errors.
+#[derive(Debug, thiserror::Error)]
+pub enum TasksParseError {
+    #[error("tasks file not found: {}", .0.display())]
+    NotFound(PathBuf),
+    /// Not UTF-8, so not a TASKS.md at all
+    #[error("tasks file is not text: {}", .0.display())]
+    NotText(PathBuf),
+    #[error("cannot read {}: {source}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+}
+
+impl TasksParseError {
+    /// Classify a failure to read the tasks file at `path`
+    pub fn from_io(path: &Path, source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::NotFound => Self::NotFound(path.to_path_buf()),
+            io::ErrorKind::InvalidData => Self::NotText(path.to_path_buf()),
+            _ => Self::Read {
+                path: path.to_path_buf(),
+                source,
+            },
+        }
+    }
+}
+
 /// Task status parsed from TASKS.md
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TaskStatus {
     Pending,
     InProgress,
@@ -22,23 +60,116 @@ pub enum TaskStatus {
     Blocked,
 }
 
+impl TaskStatus {
+    /// Snake-case name used in JSON output
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Failed => "failed",
+            TaskStatus::Blocked => "blocked",
+        }
+    }
+}
+
 /// A single task parsed from TASKS.md
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedTask {
     pub id: String,
     pub name: String,
     pub status: TaskStatus,
     pub agent: Option<String>,
+    /// Task IDs from `blocked_by:` / `depends:` lines
+    #[serde(default)]
     pub blocked_by: Vec<String>,
+    /// Value of a `!high` marker in the heading, else of a `priority:` /
+    /// `우선순위:` line
+    pub priority: Option<String>,
+    /// Comma-separated values of a `tags:` / `태그:` line
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
     pub body: String,
+    /// Date of a `due:` / `마감:` line (`- **due**: 2025-07-01`)
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+    /// Planned effort from an `est:` / `예상:` line (`- **est**: 1h30m`)
+    #[serde(default)]
+    pub estimate: Option<Duration>,
+    /// Reason given when the task was blocked (`- **blocked**: reason`)
+    pub blocked_reason: Option<String>,
+    /// Lines of `<!-- note ... -->` comments in the body, markers stripped
+    #[serde(default)]
+    pub notes: Vec<String>,
+    /// 1-based line of the task heading in TASKS.md
+    #[serde(default)]
+    pub line_number: usize,
+    /// Times the task went back to work after failing; filled in by
+    /// `DashboardState`, not the parser
+    #[serde(default)]
+    pub retries: usize,
+    /// Dependencies of a pending task that are not completed yet; filled in
+    /// by `DashboardState` like `retries`
+    #[serde(default)]
+    pub waiting_on: Vec<String>,
+}
+
+/// How urgent a task is, from its priority value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PriorityLevel {
+    Low,
+    Normal,
+    High,
+}
+
+impl PriorityLevel {
+    /// Level of a `!marker` / `priority:` value; unknown values are normal
+    pub fn of(priority: &str) -> Self {
+        match priority.to_ascii_lowercase().as_str() {
+            "critical" | "urgent" | "high" | "p0" | "p1" => Self::High,
+            "low" | "minor" | "p3" | "p4" => Self::Low,
+            _ => Self::Normal,
+        }
+    }
+}
+
+impl ParsedTask {
+    /// The task has a due date before `today` and is not completed
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.status != TaskStatus::Completed && self.due.is_some_and(|due| due < today)
+    }
+
+    /// Status the views show: a pending task still waiting on a dependency
+    /// looks blocked, while `status` stays what TASKS.md says
+    pub fn shown_status(&self) -> &TaskStatus {
+        if self.status == TaskStatus::Pending && !self.waiting_on.is_empty() {
+            &TaskStatus::Blocked
+        } else {
+            &self.status
+        }
+    }
+
+    /// Level of the task's priority; tasks without one are normal
+    pub fn priority_level(&self) -> PriorityLevel {
+        self.priority
+            .as_deref()
+            .map_or(PriorityLevel::Normal, PriorityLevel::of)
+    }
 }
 
 /// A phase containing multiple tasks
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedPhase {
     pub id: String,
     pub name: String,
     pub tasks: Vec<ParsedTask>,
+    /// First hook event on any of its tasks; filled in by
+    /// `DashboardState`, not the parser
+    pub started_at: Option<DateTime<Utc>>,
+    /// Last task completion once every task is done; filled in by
+    /// `DashboardState`, not the parser
+    pub ended_at: Option<DateTime<Utc>>,
 }
 
 impl ParsedPhase {
@@ -56,6 +187,19 @@ impl ParsedPhase {
     }
 }
 
+/// A recoverable problem found while parsing TASKS.md
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskWarning {
+    /// 1-based line number in TASKS.md
+    pub line_number: usize,
+    pub line_content: String,
+    pub message: String,
+    /// Phase the line belongs to, if any
+    pub phase_id: Option<String>,
+    /// Task the line belongs to, if any
+    pub task_id: Option<String>,
+}
+
 /// Parse a task status tag like [x], [ ], [InProgress], etc.
 fn parse_status(input: &str) -> IResult<&str, TaskStatus> {
     delimited(
@@ -72,9 +216,33 @@ fn parse_status(input: &str) -> IResult<&str, TaskStatus> {
     )(input)
 }
 
+/// Start of a dashboard note comment in a task body
+pub(crate) const NOTE_PREFIX: &str = "<!-- note";
+
+/// Whether a body line is an HTML comment; comments carry no annotations
+fn is_comment(line: &str) -> bool {
+    line.trim_start().starts_with("<!--")
+}
+
+/// Body lines that may hold annotations (comments skipped)
+fn annotation_lines(body: &str) -> impl Iterator<Item = &str> {
+    body.lines().filter(|line| !is_comment(line))
+}
+
+/// Text of the `<!-- note ... -->` lines in a task body
+fn extract_notes(body: &str) -> Vec<String> {
+    body.lines()
+        .filter_map(|line| {
+            let text = line.trim().strip_prefix(NOTE_PREFIX)?;
+            let text = text.trim_end_matches("-->").trim();
+            Some(text.replace("--&gt;", "-->"))
+        })
+        .collect()
+}
+
 /// Extract @agent-name from task body text
 fn extract_agent(body: &str) -> Option<String> {
-    for line in body.lines() {
+    for line in annotation_lines(body) {
         let trimmed = line.trim();
         if let Some(pos) = trimmed.find('@') {
             let agent_start = pos + 1;
@@ -91,18 +259,29 @@ fn extract_agent(body: &str) -> Option<String> {
     None
 }
 
-/// Extract blocked_by task IDs from task body text
-/// Supports both `blocked_by:` and `**blocked_by**:` (markdown bold) formats
+/// Keys of the dependency lines, `blocked_by: T1, T2` or `depends: T1, T2`
+const DEPENDENCY_KEYS: &[&str] = &["blocked_by", "depends"];
+
+/// What follows a dependency key in `line`, with the key found
+fn dependency_list(line: &str) -> Option<(&'static str, String)> {
+    let stripped = line.replace("**", "");
+    DEPENDENCY_KEYS.iter().find_map(|key| {
+        let marker = format!("{key}:");
+        let pos = stripped.find(&marker)?;
+        Some((*key, stripped[pos + marker.len()..].trim().to_string()))
+    })
+}
+
+/// Extract dependency task IDs from task body text
+/// Supports `blocked_by:` / `depends:` and their `**blocked_by**:` (markdown
+/// bold) forms
 fn extract_blocked_by(body: &str) -> Vec<String> {
     let mut blocked = Vec::new();
-    for line in body.lines() {
-        let trimmed = line.trim();
-        let stripped = trimmed.replace("**", "");
-        if let Some(pos) = stripped.find("blocked_by:") {
-            let rest = stripped[pos + "blocked_by:".len()..].trim();
+    for line in annotation_lines(body) {
+        if let Some((_, rest)) = dependency_list(line.trim()) {
             for part in rest.split(',') {
                 let dep = part.trim().to_string();
-                if !dep.is_empty() {
+                if !dep.is_empty() && !blocked.contains(&dep) {
                     blocked.push(dep);
                 }
             }
@@ -111,15 +290,151 @@ fn extract_blocked_by(body: &str) -> Vec<String> {
     blocked
 }
 
+/// Keys of the `- **blocked**: reason` line written when a task is blocked
+pub(crate) const BLOCKED_REASON_KEYS: &[&str] = &["blocked", "차단"];
+
+/// Keys of the `- **tags**: a, b` line
+pub(crate) const TAG_KEYS: &[&str] = &["tags", "태그"];
+
+/// Keys of the `- **due**: 2025-07-01` line
+pub(crate) const DUE_KEYS: &[&str] = &["due", "마감"];
+
+/// Keys of the `- **est**: 2h` line
+pub(crate) const ESTIMATE_KEYS: &[&str] = &["est", "예상"];
+
+/// Largest `est:` value accepted, so adding estimates up cannot overflow
+pub const MAX_ESTIMATE_HOURS: u64 = 10_000;
+
+/// Parse an estimate such as `2h`, `45m` or `1h30m`, up to
+/// [`MAX_ESTIMATE_HOURS`]
+pub fn parse_estimate(value: &str) -> Option<Duration> {
+    estimate_secs(value)
+        .filter(|&secs| secs <= MAX_ESTIMATE_HOURS * 3600)
+        .map(Duration::from_secs)
+}
+
+/// Seconds in an estimate, without the upper limit
+fn estimate_secs(value: &str) -> Option<u64> {
+    let mut secs = 0u64;
+    let mut number = String::new();
+    for c in value.trim().chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'h' | 'm' if !number.is_empty() => {
+                let n: u64 = number.parse().ok()?;
+                let unit = if c == 'h' { 3600 } else { 60 };
+                secs = secs.checked_add(n.checked_mul(unit)?)?;
+                number.clear();
+            }
+            _ => return None,
+        }
+    }
+    (number.is_empty() && secs > 0).then_some(secs)
+}
+
+/// Value of `line` when it is a `- **key**: value` metadata line for any of
+/// `keys`; the value may be empty
+pub(crate) fn field_value(line: &str, keys: &[&str]) -> Option<String> {
+    let stripped = line.replace("**", "");
+    let item = stripped.trim().trim_start_matches('-').trim_start();
+    keys.iter().find_map(|key| {
+        let value = item.strip_prefix(key)?.trim_start().strip_prefix(':')?;
+        Some(value.trim().to_string())
+    })
+}
+
+/// Value of a `- **key**: value` metadata line for any of `keys`
+fn extract_field(body: &str, keys: &[&str]) -> Option<String> {
+    annotation_lines(body).find_map(|line| field_value(line, keys).filter(|v| !v.is_empty()))
+}
+
+/// Take a `!high` / `!low` priority marker off the end of a task heading's
+/// name, among its trailing `@agent` and `#tag` tokens
+fn split_priority_marker(name: &str) -> (String, Option<String>) {
+    let tokens: Vec<&str> = name.split_whitespace().collect();
+    let marker = tokens
+        .iter()
+        .enumerate()
+        .skip(1)
+        .rev()
+        .take_while(|(_, t)| t.len() > 1 && t.starts_with(['@', '#', '!']))
+        .find(|(_, t)| {
+            t.strip_prefix('!')
+                .is_some_and(|p| p.chars().all(char::is_alphanumeric))
+        });
+    match marker {
+        Some((at, token)) => {
+            let rest: Vec<&str> = tokens[..at]
+                .iter()
+                .chain(&tokens[at + 1..])
+                .copied()
+                .collect();
+            (rest.join(" "), Some(token[1..].to_string()))
+        }
+        None => (name.to_string(), None),
+    }
+}
+
+/// Extract the task priority (`priority:` or `우선순위:`)
+fn extract_priority(body: &str) -> Option<String> {
+    extract_field(body, &["priority", "우선순위"])
+}
+
+/// Extract the due date (`due:` or `마감:`, as YYYY-MM-DD)
+fn extract_due(body: &str) -> Option<NaiveDate> {
+    let value = extract_field(body, DUE_KEYS)?;
+    NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok()
+}
+
+/// Extract the planned effort (`est:` or `예상:`)
+fn extract_estimate(body: &str) -> Option<Duration> {
+    parse_estimate(&extract_field(body, ESTIMATE_KEYS)?)
+}
+
+/// Extract why a task was blocked (`blocked:` or `차단:`)
+fn extract_blocked_reason(body: &str) -> Option<String> {
+    extract_field(body, BLOCKED_REASON_KEYS)
+}
+
+/// Extract task tags (`tags: a, b` or `태그: a, b`), without leading `#`
+fn extract_tags(body: &str) -> Vec<String> {
+    extract_field(body, TAG_KEYS)
+        .map(|value| {
+            value
+                .split(',')
+                .map(|t| t.trim().trim_start_matches('#').to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Parse the entire TASKS.md content into phases
-pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
+pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, TasksParseError> {
+    Ok(parse_tasks_md_with_warnings(input).0)
+}
+
+/// Parse TASKS.md, also collecting warnings for unknown status markers,
+/// duplicate task IDs and malformed annotations
+pub fn parse_tasks_md_with_warnings(input: &str) -> (Vec<ParsedPhase>, Vec<TaskWarning>) {
     let mut phases = Vec::new();
+    let mut warnings = Vec::new();
+    let mut seen_ids: HashSet<String> = HashSet::new();
     let mut current_phase: Option<ParsedPhase> = None;
     let mut current_task_body = String::new();
-    let mut pending_task: Option<(String, String, TaskStatus)> = None;
+    let mut pending_task: Option<(String, String, TaskStatus, usize)> = None;
 
-    for line in input.lines() {
+    for (idx, line) in input.lines().enumerate() {
         let trimmed = line.trim();
+        let mut warn = |message: String, phase: &Option<ParsedPhase>, task_id: Option<String>| {
+            warnings.push(TaskWarning {
+                line_number: idx + 1,
+                line_content: trimmed.to_string(),
+                message,
+                phase_id: phase.as_ref().map(|p| p.id.clone()),
+                task_id,
+            });
+        };
 
         // Phase heading: "# Phase N: Name" (H1) or "## Phase N: Name" (H2)
         let phase_header = if trimmed.starts_with("# ") && !trimmed.starts_with("## ") {
@@ -164,22 +479,54 @@ pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
                 &mut current_phase,
             );
 
-            if let Ok((remaining, status)) = parse_status(rest) {
-                let remaining = remaining.trim();
-                let (id, name) = if let Some(colon_pos) = remaining.find(':') {
-                    let id = remaining[..colon_pos].trim().to_string();
-                    let name = remaining[colon_pos + 1..].trim().to_string();
-                    (id, name)
-                } else {
-                    (remaining.to_string(), remaining.to_string())
-                };
-                pending_task = Some((id, name, status));
+            match parse_status(rest) {
+                Ok((remaining, status)) => {
+                    let remaining = remaining.trim();
+                    let (id, name) = if let Some(colon_pos) = remaining.find(':') {
+                        let id = remaining[..colon_pos].trim().to_string();
+                        let name = remaining[colon_pos + 1..].trim().to_string();
+                        (id, name)
+                    } else {
+                        (remaining.to_string(), remaining.to_string())
+                    };
+                    if current_phase.is_none() {
+                        warn(
+                            "task appears before any phase heading; skipped".to_string(),
+                            &current_phase,
+                            Some(id.clone()),
+                        );
+                    } else if !seen_ids.insert(id.clone()) {
+                        warn(
+                            format!("duplicate task ID `{id}`"),
+                            &current_phase,
+                            Some(id.clone()),
+                        );
+                    }
+                    pending_task = Some((id, name, status, idx + 1));
+                }
+                Err(_) if rest.starts_with('[') => {
+                    let marker = rest.find(']').map_or(rest, |end| &rest[..=end]);
+                    warn(
+                        format!("unknown status marker `{marker}`; task skipped"),
+                        &current_phase,
+                        None,
+                    );
+                }
+                Err(_) => {}
             }
             continue;
         }
 
         // Accumulate body lines for current task
-        if pending_task.is_some() {
+        if let Some((ref id, ..)) = pending_task {
+            let message = if is_comment(trimmed) {
+                None
+            } else {
+                malformed_annotation(trimmed)
+            };
+            if let Some(message) = message {
+                warn(message, &current_phase, Some(id.clone()));
+            }
             current_task_body.push_str(line);
             current_task_body.push('\n');
         }
@@ -195,26 +542,79 @@ pub fn parse_tasks_md(input: &str) -> Result<Vec<ParsedPhase>, String> {
         phases.push(phase);
     }
 
-    Ok(phases)
+    (phases, warnings)
+}
+
+/// Check a task body line for an annotation that would be silently ignored
+fn malformed_annotation(line: &str) -> Option<String> {
+    if let Some((key, rest)) = dependency_list(line) {
+        if rest.split(',').all(|dep| dep.trim().is_empty()) {
+            return Some(format!("`{key}:` lists no task IDs"));
+        }
+        if rest
+            .split(',')
+            .any(|dep| dep.trim().contains(char::is_whitespace))
+        {
+            return Some(format!("`{key}:` IDs must be separated by commas"));
+        }
+    }
+    if let Some(value) = field_value(line, DUE_KEYS).filter(|v| !v.is_empty()) {
+        if NaiveDate::parse_from_str(&value, "%Y-%m-%d").is_err() {
+            return Some(format!("`due:` date `{value}` is not YYYY-MM-DD"));
+        }
+    }
+    if let Some(value) = field_value(line, ESTIMATE_KEYS).filter(|v| !v.is_empty()) {
+        match estimate_secs(&value) {
+            None => {
+                return Some(format!(
+                    "`est:` value `{value}` is not like 2h, 45m or 1h30m"
+                ))
+            }
+            Some(secs) if secs > MAX_ESTIMATE_HOURS * 3600 => {
+                return Some(format!(
+                    "`est:` value `{value}` is over {MAX_ESTIMATE_HOURS}h"
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    if let Some(pos) = line.find('@') {
+        if line[pos + 1..].starts_with(|c: char| c.is_whitespace() || c == ',')
+            || pos + 1 == line.len()
+        {
+            return Some("`@` is not followed by an agent name".to_string());
+        }
+    }
+    None
 }
 
 /// Helper to flush a pending task into its phase
 fn flush_task(
-    pending_task: &mut Option<(String, String, TaskStatus)>,
+    pending_task: &mut Option<(String, String, TaskStatus, usize)>,
     body: &mut String,
     phase: &mut Option<ParsedPhase>,
 ) {
-    if let Some((id, name, status)) = pending_task.take() {
+    if let Some((id, name, status, line_number)) = pending_task.take() {
         if let Some(ref mut p) = phase {
             let agent = extract_agent(body);
             let blocked_by = extract_blocked_by(body);
+            let (name, marker) = split_priority_marker(&name);
             p.tasks.push(ParsedTask {
                 id,
                 name,
                 status,
                 agent,
                 blocked_by,
+                priority: marker.or_else(|| extract_priority(body)),
+                tags: extract_tags(body),
+                due: extract_due(body),
+                estimate: extract_estimate(body),
+                blocked_reason: extract_blocked_reason(body),
+                notes: extract_notes(body),
                 body: body.trim().to_string(),
+                line_number,
+                retries: 0,
+                waiting_on: Vec::new(),
             });
         }
         body.clear();
@@ -222,7 +622,7 @@ fn flush_task(
 }
 
 /// Parse phase header text like "Phase 0: Setup"
-fn parse_phase_header(header: &str) -> Option<ParsedPhase> {
+pub(crate) fn parse_phase_header(header: &str) -> Option<ParsedPhase> {
     let header = header.trim();
     if !header.starts_with("Phase") {
         return None;
@@ -239,6 +639,8 @@ fn parse_phase_header(header: &str) -> Option<ParsedPhase> {
         id,
         name: name_part.to_string(),
         tasks: Vec::new(),
+        started_at: None,
+        ended_at: None,
     })
 }
 
@@ -246,6 +648,28 @@ fn parse_phase_header(header: &str) -> Option<ParsedPhase> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn read_failures_are_classified() {
+        let path = Path::new("TASKS.md");
+        let err = |kind| TasksParseError::from_io(path, io::Error::from(kind));
+        assert!(matches!(
+            err(io::ErrorKind::NotFound),
+            TasksParseError::NotFound(_)
+        ));
+        assert!(matches!(
+            err(io::ErrorKind::InvalidData),
+            TasksParseError::NotText(_)
+        ));
+        assert!(matches!(
+            err(io::ErrorKind::PermissionDenied),
+            TasksParseError::Read { .. }
+        ));
+        assert_eq!(
+            err(io::ErrorKind::NotFound).to_string(),
+            "tasks file not found: TASKS.md"
+        );
+    }
+
     #[test]
     fn status_completed() {
         let (_, s) = parse_status("[x]").unwrap();
@@ -305,11 +729,101 @@ mod tests {
         assert_eq!(extract_blocked_by(body), vec!["P1-R1-T1", "P1-R2-T1"]);
     }
 
+    #[test]
+    fn depends_is_read_like_blocked_by() {
+        let body = "- **depends**: P1-T2, P1-T3\n- blocked_by: P1-T3, P0-T1\n";
+        assert_eq!(extract_blocked_by(body), vec!["P1-T2", "P1-T3", "P0-T1"]);
+        assert_eq!(
+            malformed_annotation("- depends: P1-T2 P1-T3").as_deref(),
+            Some("`depends:` IDs must be separated by commas")
+        );
+    }
+
     #[test]
     fn blocked_by_none() {
         assert!(extract_blocked_by("no deps here").is_empty());
     }
 
+    #[test]
+    fn priority_marker_is_taken_off_the_heading() {
+        let input = "# Phase 1: Core\n### [ ] T1: Fix login !high @dev\n- priority: low\n### [ ] T2: Say hi !\n### [ ] T3: !low\n";
+        let phases = parse_tasks_md(input).unwrap();
+        let tasks = &phases[0].tasks;
+        assert_eq!(tasks[0].name, "Fix login @dev");
+        assert_eq!(tasks[0].priority.as_deref(), Some("high"));
+        assert_eq!(tasks[0].priority_level(), PriorityLevel::High);
+        assert_eq!(tasks[1].name, "Say hi !");
+        assert_eq!(tasks[1].priority_level(), PriorityLevel::Normal);
+        // A name that is only a marker stays a name
+        assert_eq!(tasks[2].name, "!low");
+    }
+
+    #[test]
+    fn due_dates_are_parsed_and_bad_ones_warned_about() {
+        let input = "# Phase 1: Core\n### [ ] T1: a\n- **due**: 2025-07-01\n### [x] T2: b\n- 마감: 2025-06-01\n### [ ] T3: c\n- due: next week\n";
+        let (phases, warnings) = parse_tasks_md_with_warnings(input);
+        let tasks = &phases[0].tasks;
+        let date = |s| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        assert_eq!(tasks[0].due, Some(date("2025-07-01")));
+        assert!(tasks[0].is_overdue(date("2025-07-02")));
+        assert!(!tasks[0].is_overdue(date("2025-07-01")));
+        // Completed work is never overdue
+        assert!(!tasks[1].is_overdue(date("2025-07-02")));
+        assert_eq!(tasks[2].due, None);
+        assert_eq!(
+            warnings[0].message,
+            "`due:` date `next week` is not YYYY-MM-DD"
+        );
+    }
+
+    #[test]
+    fn estimates_are_parsed() {
+        let hours = |h: u64, m: u64| Some(Duration::from_secs(h * 3600 + m * 60));
+        assert_eq!(parse_estimate("2h"), hours(2, 0));
+        assert_eq!(parse_estimate(" 1h30m "), hours(1, 30));
+        assert_eq!(parse_estimate("90m"), hours(1, 30));
+        for bad in ["", "2", "h", "2 h", "1.5h", "0m", "2d"] {
+            assert_eq!(parse_estimate(bad), None, "{bad}");
+        }
+
+        let input = "# Phase 1: Core\n### [ ] T1: a\n- **est**: 45m\n### [ ] T2: b\n- est: soon\n";
+        let (phases, warnings) = parse_tasks_md_with_warnings(input);
+        assert_eq!(phases[0].tasks[0].estimate, hours(0, 45));
+        assert_eq!(phases[0].tasks[1].estimate, None);
+        assert_eq!(
+            warnings[0].message,
+            "`est:` value `soon` is not like 2h, 45m or 1h30m"
+        );
+    }
+
+    #[test]
+    fn huge_estimates_are_rejected() {
+        assert_eq!(
+            parse_estimate("10000h"),
+            Some(Duration::from_secs(MAX_ESTIMATE_HOURS * 3600))
+        );
+        assert_eq!(parse_estimate("10000h1m"), None);
+        assert_eq!(parse_estimate("2000000000000h"), None);
+        assert_eq!(parse_estimate("99999999999999999999h"), None);
+
+        let input = "# Phase 1: Core\n### [ ] T1: a\n- est: 2000000000000h\n";
+        let (phases, warnings) = parse_tasks_md_with_warnings(input);
+        assert_eq!(phases[0].tasks[0].estimate, None);
+        assert_eq!(
+            warnings[0].message,
+            "`est:` value `2000000000000h` is over 10000h"
+        );
+    }
+
+    #[test]
+    fn priority_and_tags_extraction() {
+        let body = "- **우선순위**: high\n- **tags**: #api, backend ,\n- **스펙**: x";
+        assert_eq!(extract_priority(body), Some("high".to_string()));
+        assert_eq!(extract_tags(body), vec!["api", "backend"]);
+        assert_eq!(extract_priority("- priority:"), None);
+        assert!(extract_tags("- **스펙**: tags: not metadata").is_empty());
+    }
+
     #[test]
     fn phase_header_basic() {
         let p = parse_phase_header("Phase 0: Setup").unwrap();
@@ -345,6 +859,7 @@ mod tests {
         assert_eq!(phases[0].name, "Setup");
         assert_eq!(phases[0].tasks.len(), 2);
         assert_eq!(phases[0].tasks[0].status, TaskStatus::Completed);
+        assert_eq!(phases[0].tasks[0].line_number, 12);
 
         assert_eq!(phases[1].id, "P1");
         assert_eq!(phases[1].tasks.len(), 3);
@@ -431,6 +946,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sample_tasks_have_no_warnings() {
+        let input = include_str!("../../tests/fixtures/sample_tasks.md");
+        let (_, warnings) = parse_tasks_md_with_warnings(input);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn note_comments_are_collected_and_carry_no_annotations() {
+        let input = "# Phase 0: A\n### [ ] T1: a\n- @dev\n  <!-- note 2026-10-16 14:03: ask @ops -->\n  <!-- note   retry blocked_by: -->\n### [ ] T2: b\n  <!-- note see @qa -->\n";
+        let (phases, warnings) = parse_tasks_md_with_warnings(input);
+        let tasks = &phases[0].tasks;
+        assert_eq!(tasks[0].agent.as_deref(), Some("dev"));
+        assert!(tasks[0].blocked_by.is_empty());
+        assert_eq!(
+            tasks[0].notes,
+            vec!["2026-10-16 14:03: ask @ops", "retry blocked_by:"]
+        );
+        assert!(tasks[1].agent.is_none());
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn blocked_reason_is_read_without_clashing_with_blocked_by() {
+        let input = "# Phase 0: A\n### [Blocked] T1: a\n- **blocked_by**: T0\n- **blocked**: waiting on API keys\n### [Blocked] T2: b\n- 차단: 디자인 확정 대기\n### [Blocked] T3: c\n- **blocked**:\n";
+        let (phases, warnings) = parse_tasks_md_with_warnings(input);
+        let tasks = &phases[0].tasks;
+        assert_eq!(tasks[0].blocked_by, vec!["T0"]);
+        assert_eq!(
+            tasks[0].blocked_reason.as_deref(),
+            Some("waiting on API keys")
+        );
+        assert_eq!(tasks[1].blocked_reason.as_deref(), Some("디자인 확정 대기"));
+        assert!(tasks[2].blocked_reason.is_none());
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn unknown_marker_warns_with_line() {
+        let input = "# Phase 0: Setup\n\n### [?] T1: Mystery\n### [x] T2: Done\n";
+        let (phases, warnings) = parse_tasks_md_with_warnings(input);
+        assert_eq!(phases[0].tasks.len(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line_number, 3);
+        assert_eq!(warnings[0].line_content, "### [?] T1: Mystery");
+        assert!(warnings[0].message.contains("`[?]`"));
+        assert_eq!(warnings[0].phase_id.as_deref(), Some("P0"));
+        assert!(warnings[0].task_id.is_none());
+    }
+
+    #[test]
+    fn duplicate_id_warns_on_second_occurrence() {
+        let input = "# Phase 0: A\n### [x] T1: First\n# Phase 1: B\n### [ ] T1: Again\n";
+        let (phases, warnings) = parse_tasks_md_with_warnings(input);
+        assert_eq!(phases[1].tasks.len(), 1, "duplicate is still shown");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line_number, 4);
+        assert_eq!(warnings[0].phase_id.as_deref(), Some("P1"));
+        assert_eq!(warnings[0].task_id.as_deref(), Some("T1"));
+    }
+
+    #[test]
+    fn malformed_annotations_warn() {
+        let input = "# Phase 0: Setup\n### [ ] T1: Task\n- **담당**: @\n- **blocked_by**:\n- blocked_by: T2 T3\n";
+        let (_, warnings) = parse_tasks_md_with_warnings(input);
+        let lines: Vec<usize> = warnings.iter().map(|w| w.line_number).collect();
+        assert_eq!(lines, vec![3, 4, 5]);
+        assert!(warnings.iter().all(|w| w.task_id.as_deref() == Some("T1")));
+    }
+
+    #[test]
+    fn task_before_phase_warns() {
+        let (phases, warnings) = parse_tasks_md_with_warnings("### [x] T1: Orphan\n");
+        assert!(phases.is_empty());
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("before any phase"));
+    }
+
     #[test]
     fn task_without_body_has_empty_body() {
         let input = "# Phase 0: Setup\n\n### [x] T1: Done\n### [ ] T2: Pending\n";
@@ -1,9 +1,65 @@
 //! TASKS.md write-back
 //!
 //! Updates task status in TASKS.md by finding and replacing status tags
-//! in task header lines.
+//! in task header lines (with the reason when a task is blocked), renames
+//! tasks and phases, reassigns agents, appends new tasks and notes, and
+//! moves finished phases to an archive file.
 
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::data::tasks_parser::{
+    field_value, parse_phase_header, BLOCKED_REASON_KEYS, NOTE_PREFIX, TAG_KEYS,
+};
+
+/// A task to append to TASKS.md
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewTask<'a> {
+    pub phase_id: &'a str,
+    pub task_id: &'a str,
+    pub name: &'a str,
+    /// Status tag without brackets, e.g. ` `, `x`, `InProgress`
+    pub status: &'a str,
+    pub agent: Option<&'a str>,
+    /// Description saved as a `- **스펙**:` line
+    pub spec: Option<&'a str>,
+}
+
+impl NewTask<'_> {
+    /// The task's lines in TASKS.md, preceded by a blank line
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            String::new(),
+            format!("### [{}] {}: {}", self.status, self.task_id, self.name),
+        ];
+        if let Some(agent) = self.agent {
+            lines.push(format!("- **담당**: @{agent}"));
+        }
+        if let Some(spec) = self.spec {
+            lines.push(format!("- **스펙**: {spec}"));
+        }
+        lines
+    }
+}
+
+/// A phase moved out of TASKS.md into the archive file, kept for undo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedPhase {
+    pub phase_id: String,
+    /// The phase's lines as written to the archive
+    pub lines: Vec<String>,
+    /// Phase heading that followed it, where an undo puts it back
+    pub next_phase_id: Option<String>,
+}
+
+/// Phase id (`P1`) of an H1/H2 `Phase N: Name` heading line
+pub(crate) fn heading_phase_id(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let text = trimmed
+        .strip_prefix("## ")
+        .or_else(|| trimmed.strip_prefix("# "))?;
+    parse_phase_header(text).map(|p| p.id)
+}
 
 /// Replace a task's status in TASKS.md.
 ///
@@ -13,6 +69,7 @@ use std::path::Path;
 /// Uses line-by-line string matching (no regex) for safety.
 pub fn update_task_status(path: &Path, task_id: &str, new_status: &str) -> anyhow::Result<bool> {
     let content = std::fs::read_to_string(path)?;
+    let eol = line_ending(&content);
     let mut found = false;
     let mut output = String::with_capacity(content.len());
 
@@ -27,19 +84,19 @@ pub fn update_task_status(path: &Path, task_id: &str, new_status: &str) -> anyho
                     let prefix = &line[..line.find('[').unwrap_or(0)];
                     let suffix = &after_bracket;
                     output.push_str(&format!("{prefix}[{new_status}] {suffix}"));
-                    output.push('\n');
+                    output.push_str(eol);
                     found = true;
                     continue;
                 }
             }
         }
         output.push_str(line);
-        output.push('\n');
+        output.push_str(eol);
     }
 
     // Preserve original trailing newline behavior
     if !content.ends_with('\n') {
-        output.pop();
+        output.truncate(output.len().saturating_sub(eol.len()));
     }
 
     if found {
@@ -49,6 +106,498 @@ pub fn update_task_status(path: &Path, task_id: &str, new_status: &str) -> anyho
     Ok(found)
 }
 
+/// Split a heading's name into the name proper and its trailing annotations
+/// (`@agent`, `#tag`, `!priority` tokens and `<!-- ... -->` comments), so a
+/// rename keeps them.
+///
+/// `"Parser @dev #core"` → `("Parser", " @dev #core")`
+pub fn split_annotations(text: &str) -> (&str, &str) {
+    let mut end = text.trim_end().len();
+    loop {
+        let head = &text[..end];
+        if head.ends_with("-->") {
+            match head.rfind("<!--") {
+                Some(start) => end = head[..start].trim_end().len(),
+                None => break,
+            }
+            continue;
+        }
+        let token_start = head.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let token = &head[token_start..];
+        if token_start > 0 && token.len() > 1 && token.starts_with(['@', '#', '!']) {
+            end = head[..token_start].trim_end().len();
+        } else {
+            break;
+        }
+    }
+    (&text[..end], &text[end..])
+}
+
+/// Rewrite `line` with the text after its first `:` replaced by `new_name`,
+/// keeping trailing annotations of the old name
+fn rename_after_colon(line: &str, new_name: &str) -> Option<String> {
+    let colon = line.find(':')?;
+    let (_, annotations) = split_annotations(line[colon + 1..].trim());
+    Some(format!("{}: {new_name}{annotations}", &line[..colon]))
+}
+
+/// Apply `edit` to the file's lines and write them back, keeping the file's
+/// trailing newline. The file is left untouched when `edit` returns `false`.
+///
+/// The new content goes to a sibling temp file that is renamed over the
+/// original, so readers never see a half-written file.
+fn edit_lines(path: &Path, edit: impl FnOnce(&mut Vec<String>) -> bool) -> anyhow::Result<bool> {
+    let content = std::fs::read_to_string(path)?;
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    if !edit(&mut lines) {
+        return Ok(false);
+    }
+
+    let eol = line_ending(&content);
+    let mut output = lines.join(eol);
+    if !lines.is_empty() && (content.ends_with('\n') || content.is_empty()) {
+        output.push_str(eol);
+    }
+    write_atomic(path, &output)?;
+    Ok(true)
+}
+
+/// Line ending of `content`: CRLF when it uses them (a file edited on
+/// Windows), so rewrites keep it
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Replace `path` with `content` through a sibling temp file and a rename,
+/// so the watcher never sees a half-written file
+pub(crate) fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map_or_else(|| "TASKS.md".into(), |n| n.to_string_lossy());
+    let tmp = path.with_file_name(format!(".{file_name}.tmp"));
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Rewrite the first line matching `is_target` with `rewrite`. Returns
+/// `false` (file untouched) when none match.
+fn rewrite_line(
+    path: &Path,
+    is_target: impl Fn(&str) -> bool,
+    rewrite: impl Fn(&str) -> Option<String>,
+) -> anyhow::Result<bool> {
+    edit_lines(path, |lines| {
+        let Some(index) = lines.iter().position(|l| is_target(l)) else {
+            return false;
+        };
+        match rewrite(&lines[index]) {
+            Some(new_line) => {
+                lines[index] = new_line;
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Whether `line` is the `### [status] ID: name` heading of `task_id`
+pub(crate) fn is_task_heading(line: &str, task_id: &str) -> bool {
+    line.trim()
+        .strip_prefix("### [")
+        .and_then(|rest| rest.split_once("] "))
+        .and_then(|(_, rest)| rest.strip_prefix(task_id))
+        .is_some_and(|rest| rest.trim_start().starts_with(':'))
+}
+
+/// Lines of a task: its heading and body up to the next heading or `---`,
+/// without trailing blank lines
+pub(crate) fn task_block(lines: &[String], task_id: &str) -> Option<Range<usize>> {
+    let heading = lines.iter().position(|l| is_task_heading(l, task_id))?;
+    let body_end = lines[heading + 1..]
+        .iter()
+        .position(|l| {
+            let trimmed = l.trim();
+            trimmed.starts_with('#') || trimmed == "---"
+        })
+        .map_or(lines.len(), |i| heading + 1 + i);
+    let end = (heading + 1..body_end)
+        .rev()
+        .find(|&i| !lines[i].trim().is_empty())
+        .map_or(heading + 1, |i| i + 1);
+    Some(heading..end)
+}
+
+/// Line after which a new task goes at the end of a phase: after its last
+/// non-blank line, before any `---` separator and the next phase heading
+fn phase_insert_point(lines: &[String], phase_id: &str) -> Option<usize> {
+    let start = lines
+        .iter()
+        .position(|l| heading_phase_id(l).as_deref() == Some(phase_id))?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| heading_phase_id(l).is_some())
+        .map_or(lines.len(), |i| start + 1 + i);
+    Some(
+        (start + 1..end)
+            .rev()
+            .find(|&i| {
+                let trimmed = lines[i].trim();
+                !trimmed.is_empty() && trimmed != "---"
+            })
+            .map_or(start + 1, |i| i + 1),
+    )
+}
+
+/// Lines of a phase: its heading up to the next phase heading, without
+/// trailing blank lines
+pub(crate) fn phase_block(lines: &[String], phase_id: &str) -> Option<Range<usize>> {
+    let start = lines
+        .iter()
+        .position(|l| heading_phase_id(l).as_deref() == Some(phase_id))?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| heading_phase_id(l).is_some())
+        .map_or(lines.len(), |i| start + 1 + i);
+    let end = (start + 1..end)
+        .rev()
+        .find(|&i| !lines[i].trim().is_empty())
+        .map_or(start + 1, |i| i + 1);
+    Some(start..end)
+}
+
+/// Archive file kept next to the tasks file: `TASKS.md` → `TASKS.archive.md`
+pub fn archive_path(tasks_path: &Path) -> PathBuf {
+    let stem = tasks_path
+        .file_stem()
+        .map_or_else(|| "TASKS".into(), |s| s.to_string_lossy());
+    tasks_path.with_file_name(format!("{stem}.archive.md"))
+}
+
+/// Move a phase from TASKS.md to the end of the archive file (created if
+/// missing). The archive is written first, so a failure never loses the
+/// phase. Returns `None` when the phase heading is not found.
+pub fn archive_phase(
+    path: &Path,
+    archive: &Path,
+    phase_id: &str,
+) -> anyhow::Result<Option<ArchivedPhase>> {
+    let content = std::fs::read_to_string(path)?;
+    let lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let Some(block) = phase_block(&lines, phase_id) else {
+        return Ok(None);
+    };
+    let archived = ArchivedPhase {
+        phase_id: phase_id.to_string(),
+        lines: lines[block.clone()].to_vec(),
+        next_phase_id: lines[block.end..].iter().find_map(|l| heading_phase_id(l)),
+    };
+
+    let mut archive_content = std::fs::read_to_string(archive).unwrap_or_default();
+    if !archive_content.is_empty() {
+        if !archive_content.ends_with('\n') {
+            archive_content.push('\n');
+        }
+        archive_content.push('\n');
+    }
+    archive_content.push_str(&archived.lines.join("\n"));
+    archive_content.push('\n');
+    std::fs::write(archive, archive_content)?;
+
+    edit_lines(path, |lines| {
+        let Some(block) = phase_block(lines, phase_id) else {
+            return false;
+        };
+        // Drop the blank lines that separated it from the next phase too
+        let end = (block.end..lines.len())
+            .find(|&i| !lines[i].trim().is_empty())
+            .unwrap_or(lines.len());
+        lines.drain(block.start..end);
+        true
+    })?;
+    Ok(Some(archived))
+}
+
+/// Undo [`archive_phase`]: put the phase back before the heading that
+/// followed it (or at the end) and remove it from the archive file
+pub fn restore_phase(
+    path: &Path,
+    archive: &Path,
+    archived: &ArchivedPhase,
+) -> anyhow::Result<bool> {
+    let restored = edit_lines(path, |lines| {
+        let next = archived.next_phase_id.as_deref().and_then(|id| {
+            lines
+                .iter()
+                .position(|l| heading_phase_id(l).as_deref() == Some(id))
+        });
+        let mut inserted = archived.lines.clone();
+        match next {
+            Some(at) => {
+                inserted.push(String::new());
+                lines.splice(at..at, inserted);
+            }
+            None => {
+                if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                    inserted.insert(0, String::new());
+                }
+                lines.extend(inserted);
+            }
+        }
+        true
+    })?;
+
+    if archive.exists() {
+        edit_lines(archive, |lines| {
+            // The most recent copy, with the blank line written before it
+            let Some(found) = (0..lines.len())
+                .rev()
+                .find(|&i| lines[i..].starts_with(&archived.lines))
+            else {
+                return false;
+            };
+            let start = if found > 0 && lines[found - 1].trim().is_empty() {
+                found - 1
+            } else {
+                found
+            };
+            lines.drain(start..found + archived.lines.len());
+            true
+        })?;
+    }
+    Ok(restored)
+}
+
+/// Set the status of several tasks in a single write. Tasks whose heading
+/// is not found are skipped; returns how many headings were changed.
+pub fn set_task_statuses(path: &Path, updates: &[(&str, &str)]) -> anyhow::Result<usize> {
+    let mut changed = 0;
+    edit_lines(path, |lines| {
+        for line in lines.iter_mut() {
+            let Some(&(_, status)) = updates.iter().find(|(id, _)| is_task_heading(line, id))
+            else {
+                continue;
+            };
+            let (Some(open), Some(close)) = (line.find('['), line.find("] ")) else {
+                continue;
+            };
+            line.replace_range(open + 1..close, status);
+            changed += 1;
+        }
+        changed > 0
+    })?;
+    Ok(changed)
+}
+
+/// Mark tasks Blocked and record `reason` as a `- **blocked**: reason` line
+/// under each heading, replacing any earlier reason. An empty reason removes
+/// the line. Returns how many tasks were found.
+pub fn block_tasks(path: &Path, task_ids: &[&str], reason: &str) -> anyhow::Result<usize> {
+    let mut changed = 0;
+    edit_lines(path, |lines| {
+        for id in task_ids {
+            let Some(block) = task_block(lines, id) else {
+                continue;
+            };
+            let heading = &mut lines[block.start];
+            if let (Some(open), Some(close)) = (heading.find('['), heading.find("] ")) {
+                heading.replace_range(open + 1..close, "Blocked");
+            }
+            let existing = (block.start + 1..block.end)
+                .find(|&i| field_value(&lines[i], BLOCKED_REASON_KEYS).is_some());
+            let line = format!("- **blocked**: {reason}");
+            match (existing, reason.is_empty()) {
+                (Some(i), false) => lines[i] = line,
+                (Some(i), true) => {
+                    lines.remove(i);
+                }
+                (None, false) => lines.insert(block.start + 1, line),
+                (None, true) => {}
+            }
+            changed += 1;
+        }
+        changed > 0
+    })?;
+    Ok(changed)
+}
+
+/// Rename a task in its `### [status] ID: name` line, keeping the status,
+/// ID and trailing annotations
+pub fn rename_task(path: &Path, task_id: &str, new_name: &str) -> anyhow::Result<bool> {
+    rewrite_line(
+        path,
+        |line| is_task_heading(line, task_id),
+        |line| rename_after_colon(line, new_name),
+    )
+}
+
+/// Byte range of the first `@name` mention in `line` (the one the parser
+/// takes as the task's agent)
+fn agent_mention(line: &str) -> Option<Range<usize>> {
+    let at = line.find('@')?;
+    let end = line[at + 1..]
+        .find(|c: char| c.is_whitespace() || c == ',')
+        .map_or(line.len(), |i| at + 1 + i);
+    (end > at + 1).then_some(at..end)
+}
+
+/// Set a task's agent: replaces the `@name` in its body, or adds a
+/// `- **담당**: @agent` line under the heading when it has none
+pub fn set_task_agent(path: &Path, task_id: &str, agent: &str) -> anyhow::Result<bool> {
+    edit_lines(path, |lines| {
+        let Some(block) = task_block(lines, task_id) else {
+            return false;
+        };
+        let heading = block.start;
+        let mention =
+            (heading + 1..block.end).find_map(|i| agent_mention(&lines[i]).map(|range| (i, range)));
+        match mention {
+            Some((i, range)) => lines[i].replace_range(range, &format!("@{agent}")),
+            None => lines.insert(heading + 1, format!("- **담당**: @{agent}")),
+        }
+        true
+    })
+}
+
+/// Set a task's tags: replaces its `- **tags**:` line, or adds one under the
+/// heading. No tags removes the line.
+pub fn set_task_tags(path: &Path, task_id: &str, tags: &[String]) -> anyhow::Result<bool> {
+    edit_lines(path, |lines| {
+        let Some(block) = task_block(lines, task_id) else {
+            return false;
+        };
+        let existing =
+            (block.start + 1..block.end).find(|&i| field_value(&lines[i], TAG_KEYS).is_some());
+        let line = format!("- **tags**: {}", tags.join(", "));
+        match (existing, tags.is_empty()) {
+            (Some(i), false) => lines[i] = line,
+            (Some(i), true) => {
+                lines.remove(i);
+            }
+            (None, false) => lines.insert(block.start + 1, line),
+            (None, true) => return false,
+        }
+        true
+    })
+}
+
+/// Append a note under a task as indented `<!-- note ... -->` lines, the
+/// first one stamped with `timestamp`. Blank lines around the note are
+/// dropped; returns `false` when the task or the note text is missing.
+pub fn append_task_note(
+    path: &Path,
+    task_id: &str,
+    note: &str,
+    timestamp: &str,
+) -> anyhow::Result<bool> {
+    let text: Vec<&str> = note.trim().lines().map(str::trim_end).collect();
+    if text.is_empty() {
+        return Ok(false);
+    }
+    let note_lines: Vec<String> = text
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            // `-->` inside the text would end the comment early
+            let line = line.replace("-->", "--&gt;");
+            if i == 0 {
+                format!("  {NOTE_PREFIX} {timestamp}: {line} -->")
+            } else {
+                format!("  {NOTE_PREFIX} {line} -->")
+            }
+        })
+        .collect();
+
+    edit_lines(path, |lines| {
+        let Some(block) = task_block(lines, task_id) else {
+            return false;
+        };
+        lines.splice(block.end..block.end, note_lines);
+        true
+    })
+}
+
+/// Rename a phase in its `# Phase N: title` heading
+pub fn rename_phase(path: &Path, phase_id: &str, new_name: &str) -> anyhow::Result<bool> {
+    rewrite_line(
+        path,
+        |line| heading_phase_id(line).as_deref() == Some(phase_id),
+        |line| rename_after_colon(line, new_name),
+    )
+}
+
+/// Swap two tasks' blocks (heading and body), leaving the lines between
+/// them (blank lines, section headings) in place
+pub fn swap_tasks(path: &Path, first_id: &str, second_id: &str) -> anyhow::Result<bool> {
+    edit_lines(path, |lines| {
+        let (Some(a), Some(b)) = (task_block(lines, first_id), task_block(lines, second_id)) else {
+            return false;
+        };
+        let (a, b) = if a.start < b.start { (a, b) } else { (b, a) };
+        if a.end > b.start {
+            return false;
+        }
+        let first = lines[a.clone()].to_vec();
+        let second = lines[b.clone()].to_vec();
+        // Replace the later block first so the earlier range stays valid
+        lines.splice(b, first);
+        lines.splice(a, second);
+        true
+    })
+}
+
+/// Move a task's block to the end of another phase
+pub fn move_task_to_phase(path: &Path, task_id: &str, phase_id: &str) -> anyhow::Result<bool> {
+    edit_lines(path, |lines| {
+        let Some(block) = task_block(lines, task_id) else {
+            return false;
+        };
+        if phase_insert_point(lines, phase_id).is_none() {
+            return false;
+        }
+        // Take the blank line separating the task from what came before too
+        let start = if block.start > 0 && lines[block.start - 1].trim().is_empty() {
+            block.start - 1
+        } else {
+            block.start
+        };
+        let moved: Vec<String> = lines.drain(start..block.end).collect();
+        let task_lines = if start < block.start {
+            &moved[1..]
+        } else {
+            &moved[..]
+        };
+
+        let Some(insert_at) = phase_insert_point(lines, phase_id) else {
+            return false;
+        };
+        let mut inserted = vec![String::new()];
+        inserted.extend_from_slice(task_lines);
+        lines.splice(insert_at..insert_at, inserted);
+        true
+    })
+}
+
+/// Append a task at the end of its phase in TASKS.md.
+///
+/// The task goes after the phase's last non-blank line, before any `---`
+/// separator and the next phase heading. Returns `false` when the phase
+/// heading is not found.
+pub fn append_task(path: &Path, task: &NewTask) -> anyhow::Result<bool> {
+    edit_lines(path, |lines| {
+        match phase_insert_point(lines, task.phase_id) {
+            Some(insert_at) => {
+                lines.splice(insert_at..insert_at, task.lines());
+                true
+            }
+            None => false,
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,6 +621,24 @@ mod tests {
         assert!(!result.contains("[Failed]"));
     }
 
+    #[test]
+    fn edits_keep_crlf_line_endings() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(&path, "# Phase 1: Core\r\n\r\n### [ ] P1-T1: Parser\r\n").unwrap();
+
+        assert!(update_task_status(&path, "P1-T1", "InProgress").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 1: Core\r\n\r\n### [InProgress] P1-T1: Parser\r\n"
+        );
+        assert!(rename_task(&path, "P1-T1", "Lexer").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 1: Core\r\n\r\n### [InProgress] P1-T1: Lexer\r\n"
+        );
+    }
+
     #[test]
     fn update_blocked_to_in_progress() {
         let tmp = tempfile::TempDir::new().unwrap();
@@ -113,4 +680,368 @@ mod tests {
         assert!(result.contains("[x] T2: Second"));
         assert!(result.contains("- body"));
     }
+
+    #[test]
+    fn split_annotations_keeps_trailing_tokens() {
+        assert_eq!(split_annotations("Parser"), ("Parser", ""));
+        assert_eq!(
+            split_annotations("Parser @dev #core"),
+            ("Parser", " @dev #core")
+        );
+        assert_eq!(
+            split_annotations("Fix C# build <!-- id:7 -->"),
+            ("Fix C# build", " <!-- id:7 -->")
+        );
+        assert_eq!(split_annotations("@dev"), ("@dev", ""));
+        assert_eq!(
+            split_annotations("Login !high @dev"),
+            ("Login", " !high @dev")
+        );
+    }
+
+    #[test]
+    fn rename_task_keeps_status_id_and_annotations() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 1: Data\n\n### [InProgress] T1: Parser @dev #core\n- **blocked_by**: T0\n### [ ] T10: Other\n",
+        )
+        .unwrap();
+
+        assert!(rename_task(&path, "T1", "TASKS.md parser").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert!(result
+            .contains("### [InProgress] T1: TASKS.md parser @dev #core\n- **blocked_by**: T0\n"));
+        assert!(result.contains("### [ ] T10: Other\n"));
+
+        assert!(!rename_task(&path, "T2", "x").unwrap());
+    }
+
+    #[test]
+    fn rename_phase_heading() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 0: Setup\n\n## Phase 1: Data\n### [ ] T1: a\n",
+        )
+        .unwrap();
+
+        assert!(rename_phase(&path, "P1", "Data Engine").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            result,
+            "# Phase 0: Setup\n\n## Phase 1: Data Engine\n### [ ] T1: a\n"
+        );
+    }
+
+    #[test]
+    fn set_task_agent_replaces_existing_mention() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "### [ ] T1: First\n- **담당**: @old-agent, reviewer\n\n### [ ] T2: Second\n- **담당**: @other\n",
+        )
+        .unwrap();
+
+        assert!(set_task_agent(&path, "T1", "frontend").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            result,
+            "### [ ] T1: First\n- **담당**: @frontend, reviewer\n\n### [ ] T2: Second\n- **담당**: @other\n"
+        );
+    }
+
+    #[test]
+    fn set_task_tags_replaces_adds_and_removes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "### [ ] T1: First\n- **태그**: #old\n### [ ] T2: Second\n",
+        )
+        .unwrap();
+
+        let tags = vec!["api".to_string(), "backend".to_string()];
+        assert!(set_task_tags(&path, "T1", &tags).unwrap());
+        assert!(set_task_tags(&path, "T2", &tags[..1]).unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            result,
+            "### [ ] T1: First\n- **tags**: api, backend\n### [ ] T2: Second\n- **tags**: api\n"
+        );
+        let phases =
+            crate::data::tasks_parser::parse_tasks_md(&format!("# Phase 0: A\n{result}")).unwrap();
+        assert_eq!(phases[0].tasks[0].tags, tags);
+
+        assert!(set_task_tags(&path, "T2", &[]).unwrap());
+        assert!(!set_task_tags(&path, "T2", &[]).unwrap());
+        assert!(!fs::read_to_string(&path)
+            .unwrap()
+            .ends_with("- **tags**: api\n"));
+    }
+
+    #[test]
+    fn set_task_agent_adds_line_when_unassigned() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "### [ ] T1: First\n- spec\n### [ ] T2: Second\n- @other\n",
+        )
+        .unwrap();
+
+        assert!(set_task_agent(&path, "T1", "dev").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            result,
+            "### [ ] T1: First\n- **담당**: @dev\n- spec\n### [ ] T2: Second\n- @other\n"
+        );
+        let phases =
+            crate::data::tasks_parser::parse_tasks_md(&format!("# Phase 0: A\n{result}")).unwrap();
+        assert_eq!(phases[0].tasks[0].agent.as_deref(), Some("dev"));
+        assert!(!set_task_agent(&path, "T9", "dev").unwrap());
+    }
+
+    #[test]
+    fn archive_phase_and_restore_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("TASKS.md");
+        let original = "# Tasks\n\n## Phase 0: Setup\n\n### [x] T1: a\n- @dev\n\n---\n\n## Phase 1: Core\n\n### [ ] T2: b\n";
+        fs::write(&path, original).unwrap();
+        let archive = archive_path(&path);
+        assert_eq!(archive, dir.path().join("TASKS.archive.md"));
+
+        let archived = archive_phase(&path, &archive, "P0").unwrap().unwrap();
+        assert_eq!(archived.next_phase_id.as_deref(), Some("P1"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Tasks\n\n## Phase 1: Core\n\n### [ ] T2: b\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&archive).unwrap(),
+            "## Phase 0: Setup\n\n### [x] T1: a\n- @dev\n\n---\n"
+        );
+
+        // A second archive is appended after a blank line
+        let last = archive_phase(&path, &archive, "P1").unwrap().unwrap();
+        assert!(last.next_phase_id.is_none());
+        assert!(fs::read_to_string(&archive)
+            .unwrap()
+            .ends_with("---\n\n## Phase 1: Core\n\n### [ ] T2: b\n"));
+
+        assert!(restore_phase(&path, &archive, &last).unwrap());
+        assert!(restore_phase(&path, &archive, &archived).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+        assert_eq!(fs::read_to_string(&archive).unwrap(), "");
+        assert!(archive_phase(&path, &archive, "P9").unwrap().is_none());
+    }
+
+    #[test]
+    fn set_task_statuses_writes_all_at_once() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 1: Core\n\n### [ ] T1: a\n\n### [InProgress] T2: b\n\n### [x] T3: c\n",
+        )
+        .unwrap();
+
+        let changed =
+            set_task_statuses(&path, &[("T1", "Blocked"), ("T2", "Blocked"), ("T9", "x")]).unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 1: Core\n\n### [Blocked] T1: a\n\n### [Blocked] T2: b\n\n### [x] T3: c\n"
+        );
+        // No leftover temp file
+        assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 1);
+        assert_eq!(set_task_statuses(&path, &[("T9", "x")]).unwrap(), 0);
+    }
+
+    #[test]
+    fn block_tasks_sets_status_and_replaces_reason() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 1: Core\n\n### [ ] T1: a\n- @dev\n\n### [Blocked] T2: b\n- **blocked_by**: T1\n- **blocked**: old\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            block_tasks(&path, &["T1", "T2", "T9"], "API keys").unwrap(),
+            2
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 1: Core\n\n### [Blocked] T1: a\n- **blocked**: API keys\n- @dev\n\n### [Blocked] T2: b\n- **blocked_by**: T1\n- **blocked**: API keys\n"
+        );
+
+        assert_eq!(block_tasks(&path, &["T2"], "").unwrap(), 1);
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .ends_with("### [Blocked] T2: b\n- **blocked_by**: T1\n"));
+        assert_eq!(block_tasks(&path, &["T9"], "x").unwrap(), 0);
+    }
+
+    #[test]
+    fn append_task_note_goes_after_task_body() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 1: Core\n\n### [ ] T1: a\n- @dev\n\n### [ ] T2: b\n",
+        )
+        .unwrap();
+
+        let note = "\nflaky test, see run 3\nlooks like a --> race\n\n";
+        assert!(append_task_note(&path, "T1", note, "2026-10-16 14:03").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Phase 1: Core\n\n### [ ] T1: a\n- @dev\n  <!-- note 2026-10-16 14:03: flaky test, see run 3 -->\n  <!-- note looks like a --&gt; race -->\n\n### [ ] T2: b\n"
+        );
+        assert!(!append_task_note(&path, "T9", "x", "now").unwrap());
+        assert!(!append_task_note(&path, "T1", "  \n ", "now").unwrap());
+    }
+
+    #[test]
+    fn swap_tasks_keeps_lines_between() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 0: A\n\n### [x] T1: One\n- @dev\n\n## Section\n\n### [ ] T2: Two\n\n---\n",
+        )
+        .unwrap();
+
+        assert!(swap_tasks(&path, "T2", "T1").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            result,
+            "# Phase 0: A\n\n### [ ] T2: Two\n\n## Section\n\n### [x] T1: One\n- @dev\n\n---\n"
+        );
+        assert!(!swap_tasks(&path, "T1", "T9").unwrap());
+    }
+
+    #[test]
+    fn move_task_to_other_phase() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 0: A\n\n### [ ] T1: One\n- @dev\n\n### [ ] T2: Two\n\n---\n\n# Phase 1: B\n\n### [ ] T3: Three\n",
+        )
+        .unwrap();
+
+        assert!(move_task_to_phase(&path, "T1", "P1").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            result,
+            "# Phase 0: A\n\n### [ ] T2: Two\n\n---\n\n# Phase 1: B\n\n### [ ] T3: Three\n\n### [ ] T1: One\n- @dev\n"
+        );
+
+        // Back again: lands after T2, before the separator
+        assert!(move_task_to_phase(&path, "T1", "P0").unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert!(result
+            .starts_with("# Phase 0: A\n\n### [ ] T2: Two\n\n### [ ] T1: One\n- @dev\n\n---\n"));
+
+        assert!(!move_task_to_phase(&path, "T1", "P9").unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), result);
+    }
+
+    fn new_task<'a>(phase_id: &'a str, agent: Option<&'a str>) -> NewTask<'a> {
+        NewTask {
+            phase_id,
+            task_id: "P1-T9",
+            name: "Follow-up",
+            status: " ",
+            agent,
+            spec: None,
+        }
+    }
+
+    #[test]
+    fn append_task_goes_to_end_of_phase() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 0: A\n\n### [x] P0-T1: Init\n\n---\n\n# Phase 1: B\n\n### [ ] P1-T1: Work\n- **담당**: @dev\n\n---\n\n## Phase 2: C\n",
+        )
+        .unwrap();
+
+        assert!(append_task(&path, &new_task("P1", Some("dev"))).unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert!(
+            result.contains("- **담당**: @dev\n\n### [ ] P1-T9: Follow-up\n- **담당**: @dev\n\n---\n\n## Phase 2"),
+            "got: {result}"
+        );
+
+        let phases = crate::data::tasks_parser::parse_tasks_md(&result).unwrap();
+        let added = &phases[1].tasks[1];
+        assert_eq!(added.id, "P1-T9");
+        assert_eq!(added.agent.as_deref(), Some("dev"));
+    }
+
+    #[test]
+    fn append_task_writes_spec_line() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 1: B
+
+### [ ] P1-T1: Work
+",
+        )
+        .unwrap();
+
+        let task = NewTask {
+            spec: Some("Check file permissions"),
+            ..new_task("P1", None)
+        };
+        assert!(append_task(&path, &task).unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert!(
+            result.ends_with(
+                "### [ ] P1-T9: Follow-up
+- **스펙**: Check file permissions
+"
+            ),
+            "got: {result}"
+        );
+    }
+
+    #[test]
+    fn append_task_to_last_and_empty_phase() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(
+            &path,
+            "# Phase 0: A\n\n### [x] P0-T1: Init\n\n# Phase 1: B\n",
+        )
+        .unwrap();
+
+        assert!(append_task(&path, &new_task("P1", None)).unwrap());
+        let result = fs::read_to_string(&path).unwrap();
+        assert!(
+            result.ends_with("# Phase 1: B\n\n### [ ] P1-T9: Follow-up\n"),
+            "got: {result}"
+        );
+    }
+
+    #[test]
+    fn append_task_unknown_phase_leaves_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        let content = "# Phase 0: A\n";
+        fs::write(&path, content).unwrap();
+
+        assert!(!append_task(&path, &new_task("P7", None)).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), content);
+    }
 }
@@ -0,0 +1,258 @@
+//! State transitions between dashboard updates
+//!
+//! A [`Baseline`] remembers task statuses, finished phases and the newest
+//! error seen; advancing it to the updated state yields what changed, which
+//! outbound notifications react to. Budget crossings come from
+//! [`crate::budget::BudgetWatch`], which knows the configured limits.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+
+use crate::analysis::rules::ErrorCategory;
+use crate::budget::BudgetCheck;
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::{ParsedPhase, TaskStatus};
+
+/// Kinds of transition, as named in board.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    TaskStatus,
+    AgentError,
+    PhaseComplete,
+    Budget,
+}
+
+impl TransitionKind {
+    pub const ALL: [TransitionKind; 4] = [
+        TransitionKind::TaskStatus,
+        TransitionKind::AgentError,
+        TransitionKind::PhaseComplete,
+        TransitionKind::Budget,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TransitionKind::TaskStatus => "task_status",
+            TransitionKind::AgentError => "agent_error",
+            TransitionKind::PhaseComplete => "phase_complete",
+            TransitionKind::Budget => "budget",
+        }
+    }
+}
+
+impl FromStr for TransitionKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| {
+                format!("unknown transition `{s}` (expected task_status, agent_error, phase_complete or budget)")
+            })
+    }
+}
+
+/// Something that changed since the previous update
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transition {
+    TaskStatus {
+        task_id: String,
+        task_name: String,
+        phase_id: String,
+        from: TaskStatus,
+        to: TaskStatus,
+    },
+    AgentError {
+        agent_id: String,
+        task_id: String,
+        message: String,
+        category: ErrorCategory,
+        timestamp: DateTime<Utc>,
+    },
+    PhaseComplete {
+        phase_id: String,
+        phase_name: String,
+    },
+    /// A session, agent or the board moved closer to one of its limits
+    Budget(BudgetCheck),
+}
+
+impl Transition {
+    pub fn kind(&self) -> TransitionKind {
+        match self {
+            Transition::TaskStatus { .. } => TransitionKind::TaskStatus,
+            Transition::AgentError { .. } => TransitionKind::AgentError,
+            Transition::PhaseComplete { .. } => TransitionKind::PhaseComplete,
+            Transition::Budget(_) => TransitionKind::Budget,
+        }
+    }
+
+    /// One-line description for notifications
+    pub fn summary(&self) -> String {
+        match self {
+            Transition::TaskStatus {
+                task_id,
+                task_name,
+                from,
+                to,
+                ..
+            } => format!(
+                "{task_id} {task_name}: {} -> {}",
+                from.as_str(),
+                to.as_str()
+            ),
+            Transition::AgentError {
+                agent_id,
+                task_id,
+                message,
+                ..
+            } => format!(
+                "{agent_id} error on {task_id}: {}",
+                message.lines().next().unwrap_or("")
+            ),
+            Transition::PhaseComplete {
+                phase_id,
+                phase_name,
+            } => format!("{phase_id} {phase_name} complete"),
+            Transition::Budget(check) => check.summary(),
+        }
+    }
+}
+
+fn is_complete(phase: &ParsedPhase) -> bool {
+    !phase.tasks.is_empty()
+        && phase
+            .tasks
+            .iter()
+            .all(|t| t.status == TaskStatus::Completed)
+}
+
+/// What the previous update looked like
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    statuses: HashMap<String, TaskStatus>,
+    phases: HashSet<String>,
+    complete_phases: HashSet<String>,
+    last_error: Option<DateTime<Utc>>,
+}
+
+impl Baseline {
+    pub fn capture(state: &DashboardState) -> Self {
+        let tasks = state.phases.iter().flat_map(|p| &p.tasks);
+        Self {
+            statuses: tasks.map(|t| (t.id.clone(), t.status.clone())).collect(),
+            phases: state.phases.iter().map(|p| p.id.clone()).collect(),
+            complete_phases: state
+                .phases
+                .iter()
+                .filter(|p| is_complete(p))
+                .map(|p| p.id.clone())
+                .collect(),
+            last_error: state.recent_errors.iter().map(|e| e.timestamp).max(),
+        }
+    }
+
+    /// Transitions from the baseline to `state`, which becomes the new
+    /// baseline. Tasks and phases that just appeared do not count.
+    pub fn advance(&mut self, state: &DashboardState) -> Vec<Transition> {
+        let mut transitions = Vec::new();
+        for phase in &state.phases {
+            for task in &phase.tasks {
+                match self.statuses.get(&task.id) {
+                    Some(from) if *from != task.status => {
+                        transitions.push(Transition::TaskStatus {
+                            task_id: task.id.clone(),
+                            task_name: task.name.clone(),
+                            phase_id: phase.id.clone(),
+                            from: from.clone(),
+                            to: task.status.clone(),
+                        })
+                    }
+                    _ => {}
+                }
+            }
+            if is_complete(phase)
+                && self.phases.contains(&phase.id)
+                && !self.complete_phases.contains(&phase.id)
+            {
+                transitions.push(Transition::PhaseComplete {
+                    phase_id: phase.id.clone(),
+                    phase_name: phase.name.clone(),
+                });
+            }
+        }
+
+        let mut errors: Vec<_> = state
+            .recent_errors
+            .iter()
+            .filter(|e| self.last_error.map_or(true, |last| e.timestamp > last))
+            .collect();
+        errors.sort_by_key(|e| e.timestamp);
+        transitions.extend(errors.into_iter().map(|e| Transition::AgentError {
+            agent_id: e.agent_id.clone(),
+            task_id: e.task_id.clone(),
+            message: e.message.clone(),
+            category: e.category.clone(),
+            timestamp: e.timestamp,
+        }));
+
+        let last_error = self.last_error;
+        *self = Self::capture(state);
+        self.last_error = self.last_error.max(last_error);
+        transitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    const TASKS: &str = "# Phase 1: Core\n### [x] P1-T1: Parser\n### [ ] P1-T2: Writer\n";
+
+    #[test]
+    fn detects_status_changes_completed_phases_and_new_errors() {
+        let mut state = DashboardState::from_tasks_content(TASKS).unwrap();
+        let mut baseline = Baseline::capture(&state);
+        assert!(baseline.advance(&state).is_empty());
+
+        state
+            .reload_tasks(&TASKS.replace("[ ] P1-T2", "[x] P1-T2"))
+            .unwrap();
+        let error = r#"{"event_type":"error","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T2","session_id":"s","error_message":"permission denied"}"#;
+        state.update_from_events(&parse_hook_events(error).events);
+
+        let transitions = baseline.advance(&state);
+        let summaries: Vec<String> = transitions.iter().map(|t| t.summary()).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "P1-T2 Writer: pending -> completed",
+                "P1 Core complete",
+                "a error on P1-T2: permission denied",
+            ]
+        );
+        assert_eq!(transitions[2].kind(), TransitionKind::AgentError);
+
+        // Nothing repeats, and a newly added phase is not a completion
+        assert!(baseline.advance(&state).is_empty());
+        state
+            .reload_tasks(&format!(
+                "{}# Phase 2: New\n### [x] P2-T1: Done already\n",
+                TASKS.replace("[ ] P1-T2", "[x] P1-T2")
+            ))
+            .unwrap();
+        assert!(baseline.advance(&state).is_empty());
+    }
+
+    #[test]
+    fn kinds_parse_from_config_names() {
+        for kind in TransitionKind::ALL {
+            assert_eq!(kind.as_str().parse::<TransitionKind>(), Ok(kind));
+        }
+        assert!("task_done".parse::<TransitionKind>().is_err());
+    }
+}
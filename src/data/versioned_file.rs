@@ -0,0 +1,128 @@
+//! Versioned JSON files replaced in one step
+//!
+//! The collector snapshot, the session history and the saved views are each
+//! a JSON object in the events directory with a `version` field next to its
+//! contents. A file written by another version is ignored rather than
+//! misread, and writes go to a partial copy that is renamed over the file,
+//! so readers never see it half-written.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Suffix of the partial copy a write goes to first
+const PARTIAL_SUFFIX: &str = ".tmp";
+
+/// A JSON file name and the format version its contents must have
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionedFile {
+    name: &'static str,
+    version: u32,
+}
+
+#[derive(Serialize)]
+struct Tagged<'a, T> {
+    version: u32,
+    #[serde(flatten)]
+    content: &'a T,
+}
+
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+impl VersionedFile {
+    /// Bump `version` whenever the stored fields change
+    pub const fn new(name: &'static str, version: u32) -> Self {
+        Self { name, version }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Whether `path` is the file or its partial copy (not an event file)
+    pub fn matches(&self, path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.strip_suffix(PARTIAL_SUFFIX).unwrap_or(name) == self.name)
+    }
+
+    /// Contents of the file in `dir`
+    pub fn read<T: DeserializeOwned>(&self, dir: &Path) -> Option<T> {
+        self.read_at(&dir.join(self.name))
+    }
+
+    /// Contents of the file at `path`; `None` when missing, unreadable or
+    /// written by another version
+    pub fn read_at<T: DeserializeOwned>(&self, path: &Path) -> Option<T> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let Version { version } = serde_json::from_str(&content).ok()?;
+        if version != self.version {
+            return None;
+        }
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Replace the file in `dir` with `content`, creating `dir` if needed
+    pub fn write<T: Serialize>(&self, dir: &Path, content: &T) -> io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        self.write_at(&dir.join(self.name), content)
+    }
+
+    /// Replace the file at `path` with `content` in one step
+    pub fn write_at<T: Serialize>(&self, path: &Path, content: &T) -> io::Result<()> {
+        let tagged = Tagged {
+            version: self.version,
+            content,
+        };
+        let partial = self.partial_path(path);
+        std::fs::write(&partial, serde_json::to_vec(&tagged)?)?;
+        std::fs::rename(&partial, path)
+    }
+
+    fn partial_path(&self, path: &Path) -> PathBuf {
+        path.with_file_name(format!("{}{PARTIAL_SUFFIX}", self.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Notes {
+        notes: Vec<String>,
+    }
+
+    const FILE: VersionedFile = VersionedFile::new("notes.json", 2);
+
+    #[test]
+    fn round_trips_and_ignores_other_versions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("events");
+        let notes = Notes {
+            notes: vec!["a".to_string()],
+        };
+        FILE.write(&dir, &notes).unwrap();
+        assert_eq!(FILE.read(&dir), Some(notes));
+        assert!(!dir.join("notes.json.tmp").exists());
+
+        let raw = std::fs::read_to_string(dir.join("notes.json")).unwrap();
+        assert_eq!(raw, r#"{"version":2,"notes":["a"]}"#);
+        std::fs::write(dir.join("notes.json"), raw.replace(":2", ":1")).unwrap();
+        assert_eq!(FILE.read::<Notes>(&dir), None);
+        assert_eq!(FILE.read::<Notes>(tmp.path()), None);
+    }
+
+    #[test]
+    fn matches_the_file_and_its_partial_copy() {
+        assert!(FILE.matches(Path::new("/e/notes.json")));
+        assert!(FILE.matches(Path::new("/e/notes.json.tmp")));
+        assert!(!FILE.matches(Path::new("/e/notes.jsonl")));
+        assert!(!FILE.matches(Path::new("/e/other.json")));
+    }
+}
@@ -7,6 +7,11 @@ use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watche
 use std::path::{Path, PathBuf};
 use tokio::sync::mpsc;
 
+use crate::data::commands;
+use crate::data::{session_history, status_file, store};
+#[cfg(feature = "tui")]
+use crate::view_state::is_views_file;
+
 /// Types of file changes we care about
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileChange {
@@ -16,11 +21,13 @@ pub enum FileChange {
     HookEventModified(PathBuf),
     /// A hook event file was created (new session)
     HookEventCreated(PathBuf),
+    /// The dashboard config or the error rules file was written
+    ConfigModified(PathBuf),
 }
 
 /// Errors from the file watcher
 #[derive(Debug, thiserror::Error)]
-pub enum WatcherError {
+pub enum WatchError {
     #[error("notify error: {0}")]
     Notify(#[from] notify::Error),
     #[error("channel send error")]
@@ -36,6 +43,9 @@ pub struct WatchConfig {
     pub hooks_dir: PathBuf,
     /// Optional secondary directory for dashboard JSONL events (e.g. ~/.claude/dashboard/)
     pub events_dir: Option<PathBuf>,
+    /// Config files (board.toml, rules.toml) to report as `ConfigModified`;
+    /// they need not exist yet
+    pub config_files: Vec<PathBuf>,
 }
 
 impl WatchConfig {
@@ -44,6 +54,7 @@ impl WatchConfig {
             tasks_path,
             hooks_dir,
             events_dir: None,
+            config_files: Vec::new(),
         }
     }
 
@@ -53,21 +64,58 @@ impl WatchConfig {
         self
     }
 
+    /// Also watch these config files for changes
+    pub fn with_config_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.config_files = files;
+        self
+    }
+
     /// Validate that watched paths exist (events_dir is optional)
-    pub fn validate(&self) -> Result<(), WatcherError> {
+    pub fn validate(&self) -> Result<(), WatchError> {
         if !self.tasks_path.exists() {
-            return Err(WatcherError::PathNotFound(self.tasks_path.clone()));
+            return Err(WatchError::PathNotFound(self.tasks_path.clone()));
         }
         if !self.hooks_dir.exists() {
-            return Err(WatcherError::PathNotFound(self.hooks_dir.clone()));
+            return Err(WatchError::PathNotFound(self.hooks_dir.clone()));
         }
         Ok(())
     }
 }
 
+/// Saved views are only written by the terminal UI
+#[cfg(not(feature = "tui"))]
+fn is_views_file(_path: &Path) -> bool {
+    false
+}
+
+/// `path` made absolute for comparison with what notify reports. Windows
+/// reports paths without the `\\?\` prefix `canonicalize` adds and NTFS
+/// ignores case, so there both are dropped too.
+fn normalize(path: &Path) -> PathBuf {
+    let absolute = match std::env::current_dir() {
+        Ok(cwd) if path.is_relative() => cwd.join(path),
+        _ => path.to_path_buf(),
+    };
+    if cfg!(windows) {
+        let text = absolute.to_string_lossy();
+        let text = text.strip_prefix(r"\\?\").unwrap_or(&text);
+        PathBuf::from(text.replace('/', "\\").to_lowercase())
+    } else {
+        absolute
+    }
+}
+
+/// Directory to watch for `file`; a bare file name lives in the current one
+fn parent_dir(file: &Path) -> PathBuf {
+    match file.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
 /// Check if two paths refer to the same location (handles symlinks like /var -> /private/var)
 fn paths_match(a: &Path, b: &Path) -> bool {
-    if a == b {
+    if a == b || normalize(a) == normalize(b) {
         return true;
     }
     // Try canonical comparison for symlink resolution
@@ -79,11 +127,11 @@ fn paths_match(a: &Path, b: &Path) -> bool {
 
 /// Check if `child` is under `parent` directory (handles symlinks)
 fn is_under_dir(child: &Path, parent: &Path) -> bool {
-    if child.starts_with(parent) {
+    if child.starts_with(parent) || normalize(child).starts_with(normalize(parent)) {
         return true;
     }
     if let (Ok(cc), Ok(cp)) = (child.canonicalize(), parent.canonicalize()) {
-        return cc.starts_with(cp);
+        return normalize(&cc).starts_with(normalize(&cp));
     }
     false
 }
@@ -100,10 +148,29 @@ fn classify_event(event: &Event, config: &WatchConfig) -> Option<FileChange> {
     }
 
     for path in &event.paths {
+        // Signals, status, session history and saved views written by the
+        // dashboard and the collector's snapshot, not hook events
+        if commands::is_commands_file(path)
+            || store::is_store_file(path)
+            || status_file::is_status_file(path)
+            || session_history::is_history_file(path)
+            || is_views_file(path)
+        {
+            continue;
+        }
+
         if paths_match(path, &config.tasks_path) {
             return Some(FileChange::TasksModified(path.clone()));
         }
 
+        if config
+            .config_files
+            .iter()
+            .any(|file| paths_match(path, file))
+        {
+            return Some(FileChange::ConfigModified(path.clone()));
+        }
+
         if is_under_dir(path, &config.hooks_dir) {
             if matches!(event.kind, EventKind::Create(_)) {
                 return Some(FileChange::HookEventCreated(path.clone()));
@@ -130,7 +197,7 @@ fn classify_event(event: &Event, config: &WatchConfig) -> Option<FileChange> {
 /// Returns `(watcher, receiver)`. The watcher must be kept alive for events to flow.
 pub fn start_watching(
     config: WatchConfig,
-) -> Result<(RecommendedWatcher, mpsc::UnboundedReceiver<FileChange>), WatcherError> {
+) -> Result<(RecommendedWatcher, mpsc::UnboundedReceiver<FileChange>), WatchError> {
     config.validate()?;
 
     let (tx, rx) = mpsc::unbounded_channel();
@@ -148,11 +215,7 @@ pub fn start_watching(
     )?;
 
     // Watch the parent directory of TASKS.md (FSEvents on macOS needs directories)
-    let tasks_parent = config
-        .tasks_path
-        .parent()
-        .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| config.tasks_path.clone());
+    let tasks_parent = parent_dir(&config.tasks_path);
     watcher.watch(&tasks_parent, RecursiveMode::NonRecursive)?;
     watcher.watch(&config.hooks_dir, RecursiveMode::Recursive)?;
 
@@ -163,6 +226,20 @@ pub fn start_watching(
         }
     }
 
+    // Config files are optional: watch the directories they would be in, so
+    // creating one counts too
+    let mut config_dirs: Vec<PathBuf> = config
+        .config_files
+        .iter()
+        .map(|file| parent_dir(file))
+        .filter(|dir| dir.is_dir())
+        .collect();
+    config_dirs.sort();
+    config_dirs.dedup();
+    for dir in config_dirs {
+        let _ = watcher.watch(&dir, RecursiveMode::NonRecursive);
+    }
+
     Ok((watcher, rx))
 }
 
@@ -172,7 +249,7 @@ pub fn start_watching(
 fn start_watching_poll(
     config: WatchConfig,
     interval: std::time::Duration,
-) -> Result<(notify::PollWatcher, mpsc::UnboundedReceiver<FileChange>), WatcherError> {
+) -> Result<(notify::PollWatcher, mpsc::UnboundedReceiver<FileChange>), WatchError> {
     config.validate()?;
 
     // Canonicalize config paths so they match what PollWatcher reports
@@ -200,11 +277,7 @@ fn start_watching_poll(
         poll_config,
     )?;
 
-    let tasks_parent = canon_config
-        .tasks_path
-        .parent()
-        .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| canon_config.tasks_path.clone());
+    let tasks_parent = parent_dir(&canon_config.tasks_path);
     watcher.watch(&tasks_parent, RecursiveMode::NonRecursive)?;
     watcher.watch(&canon_config.hooks_dir, RecursiveMode::Recursive)?;
 
@@ -295,6 +368,41 @@ mod tests {
         assert_eq!(change, Some(FileChange::HookEventModified(hook_file)));
     }
 
+    #[test]
+    fn classify_config_write() {
+        let tmp = TempDir::new().unwrap();
+        let board = tmp.path().join("board.toml");
+        let rules = tmp.path().join("rules.toml");
+        let config = make_config(&tmp).with_config_files(vec![board.clone(), rules]);
+        // Editors often save by writing a new file and renaming it over
+        let event = Event {
+            kind: EventKind::Create(CreateKind::File),
+            paths: vec![board.clone()],
+            attrs: Default::default(),
+        };
+        let change = classify_event(&event, &config);
+        assert_eq!(change, Some(FileChange::ConfigModified(board)));
+
+        let other = Event {
+            kind: EventKind::Create(CreateKind::File),
+            paths: vec![tmp.path().join("notes.toml")],
+            attrs: Default::default(),
+        };
+        assert!(classify_event(&other, &config).is_none());
+    }
+
+    #[test]
+    fn classify_commands_file_ignored() {
+        let tmp = TempDir::new().unwrap();
+        let config = make_config(&tmp);
+        let event = Event {
+            kind: EventKind::Modify(ModifyKind::Data(notify::event::DataChange::Content)),
+            paths: vec![config.hooks_dir.join(commands::COMMANDS_FILE)],
+            attrs: Default::default(),
+        };
+        assert!(classify_event(&event, &config).is_none());
+    }
+
     #[test]
     fn classify_unrelated_path_ignored() {
         let tmp = TempDir::new().unwrap();
@@ -307,6 +415,19 @@ mod tests {
         assert!(classify_event(&event, &config).is_none());
     }
 
+    #[test]
+    fn relative_paths_match_reported_absolute_ones() {
+        assert_eq!(parent_dir(Path::new("TASKS.md")), PathBuf::from("."));
+        // Gone files cannot be canonicalized, so this relies on normalizing
+        let absolute = std::env::current_dir()
+            .unwrap()
+            .join("gone")
+            .join("TASKS.md");
+        assert!(paths_match(Path::new("gone/TASKS.md"), &absolute));
+        assert!(is_under_dir(&absolute, Path::new("gone")));
+        assert!(!is_under_dir(&absolute, Path::new("elsewhere")));
+    }
+
     #[test]
     fn classify_remove_event_ignored() {
         let tmp = TempDir::new().unwrap();
@@ -0,0 +1,232 @@
+//! Conflict check for TASKS.md write-back
+//!
+//! The dashboard remembers TASKS.md as it was last parsed (mtime, hash and
+//! text). Before an edit, the file is compared with it: when it changed in
+//! the meantime, every task or phase the edit touches is located again by
+//! ID and must be found exactly once, unchanged. Otherwise the edit is
+//! refused with a [`Conflict`] holding both versions, so changes made by the
+//! orchestrator are never overwritten by a stale view.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use crate::data::tasks_writer::{heading_phase_id, is_task_heading, phase_block, task_block};
+
+/// A file modified this recently may be rewritten again within the same
+/// mtime tick, so its mtime alone cannot prove it unchanged
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// TASKS.md as the dashboard last parsed it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedTasks {
+    /// Modification time, kept only when old enough to be trusted
+    modified: Option<SystemTime>,
+    hash: u64,
+    content: String,
+}
+
+impl LoadedTasks {
+    /// Read the file and remember its modification time and hash
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let modified = std::fs::metadata(path)?.modified().ok().filter(|m| {
+            SystemTime::now()
+                .duration_since(*m)
+                .is_ok_and(|age| age >= RACY_WINDOW)
+        });
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self {
+            modified,
+            hash: content_hash(&content),
+            content,
+        })
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Part of TASKS.md an edit depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditTarget<'a> {
+    /// A task's heading and body
+    Task(&'a str),
+    /// A whole phase, with all its tasks
+    Phase(&'a str),
+    /// Only a phase's heading line (e.g. to add a task under it)
+    PhaseHeading(&'a str),
+}
+
+impl EditTarget<'_> {
+    fn id(&self) -> &str {
+        match self {
+            EditTarget::Task(id) | EditTarget::Phase(id) | EditTarget::PhaseHeading(id) => id,
+        }
+    }
+
+    /// Every occurrence of the target in `lines`
+    fn blocks(&self, lines: &[String]) -> Vec<Vec<String>> {
+        (0..lines.len())
+            .filter_map(|start| {
+                let rest = &lines[start..];
+                let range = match *self {
+                    EditTarget::Task(id) if is_task_heading(&rest[0], id) => task_block(rest, id),
+                    EditTarget::Phase(id) if heading_phase_id(&rest[0]).as_deref() == Some(id) => {
+                        phase_block(rest, id)
+                    }
+                    EditTarget::PhaseHeading(id)
+                        if heading_phase_id(&rest[0]).as_deref() == Some(id) =>
+                    {
+                        Some(0..1)
+                    }
+                    _ => None,
+                }?;
+                Some(rest[range].to_vec())
+            })
+            .collect()
+    }
+}
+
+/// Why an edit target could not be matched up with the current file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    Changed,
+    Missing,
+    Duplicated,
+}
+
+impl ConflictKind {
+    pub fn describe(self) -> &'static str {
+        match self {
+            ConflictKind::Changed => "changed in TASKS.md since it was loaded",
+            ConflictKind::Missing => "is no longer in TASKS.md",
+            ConflictKind::Duplicated => "appears more than once in TASKS.md",
+        }
+    }
+}
+
+/// An edit refused because its target changed outside the dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// Task or phase ID
+    pub target: String,
+    pub kind: ConflictKind,
+    /// The target's lines as the dashboard loaded them
+    pub ours: Vec<String>,
+    /// The target's lines in TASKS.md now (every occurrence, blank-separated)
+    pub theirs: Vec<String>,
+}
+
+/// Check whether an edit of `targets` can be applied to `path`. Returns
+/// `None` when the file is unchanged since `loaded`, or when every target is
+/// found exactly once and as it was. A file that cannot be read is left to
+/// the write itself to report.
+pub fn check(path: &Path, loaded: &LoadedTasks, targets: &[EditTarget]) -> Option<Conflict> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok();
+    if modified.is_some() && modified == loaded.modified {
+        return None;
+    }
+    let current = std::fs::read_to_string(path).ok()?;
+    if content_hash(&current) == loaded.hash {
+        return None;
+    }
+
+    let old_lines: Vec<String> = loaded.content.lines().map(str::to_string).collect();
+    let new_lines: Vec<String> = current.lines().map(str::to_string).collect();
+    targets.iter().find_map(|target| {
+        let ours = target
+            .blocks(&old_lines)
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        let found = target.blocks(&new_lines);
+        let kind = match found.len() {
+            0 => ConflictKind::Missing,
+            1 if found[0] == ours => return None,
+            1 => ConflictKind::Changed,
+            _ => ConflictKind::Duplicated,
+        };
+        Some(Conflict {
+            target: target.id().to_string(),
+            kind,
+            ours,
+            theirs: found.join(&String::new()),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const TASKS: &str =
+        "# Phase 1: Engine\n\n### [ ] P1-T1: Parser\n- **담당**: @dev\n\n### [ ] P1-T2: Watcher\n";
+
+    fn loaded(content: &str) -> (tempfile::TempDir, std::path::PathBuf, LoadedTasks) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("TASKS.md");
+        fs::write(&path, content).unwrap();
+        let loaded = LoadedTasks::read(&path).unwrap();
+        (tmp, path, loaded)
+    }
+
+    #[test]
+    fn unchanged_or_unrelated_changes_pass() {
+        let (_tmp, path, loaded) = loaded(TASKS);
+        assert!(check(&path, &loaded, &[EditTarget::Task("P1-T1")]).is_none());
+
+        // The orchestrator added a task and reordered: P1-T1 is still intact
+        fs::write(
+            &path,
+            "# Phase 1: Engine\n\n### [ ] P1-T2: Watcher\n\n### [ ] P1-T1: Parser\n- **담당**: @dev\n\n### [ ] P1-T3: New\n",
+        )
+        .unwrap();
+        assert!(check(&path, &loaded, &[EditTarget::Task("P1-T1")]).is_none());
+        assert!(check(&path, &loaded, &[EditTarget::PhaseHeading("P1")]).is_none());
+        assert!(check(&path, &loaded, &[EditTarget::Phase("P1")]).is_some());
+    }
+
+    #[test]
+    fn changed_target_is_a_conflict_with_both_versions() {
+        let (_tmp, path, loaded) = loaded(TASKS);
+        fs::write(&path, TASKS.replace("[ ] P1-T1", "[Failed] P1-T1")).unwrap();
+
+        assert!(check(&path, &loaded, &[EditTarget::Task("P1-T2")]).is_none());
+        let conflict = check(
+            &path,
+            &loaded,
+            &[EditTarget::Task("P1-T2"), EditTarget::Task("P1-T1")],
+        )
+        .unwrap();
+        assert_eq!(conflict.target, "P1-T1");
+        assert_eq!(conflict.kind, ConflictKind::Changed);
+        assert_eq!(conflict.ours[0], "### [ ] P1-T1: Parser");
+        assert_eq!(conflict.theirs[0], "### [Failed] P1-T1: Parser");
+    }
+
+    #[test]
+    fn missing_and_duplicated_targets_are_conflicts() {
+        let (_tmp, path, loaded) = loaded(TASKS);
+        fs::write(&path, "# Phase 1: Engine\n\n### [ ] P1-T2: Watcher\n").unwrap();
+        let conflict = check(&path, &loaded, &[EditTarget::Task("P1-T1")]).unwrap();
+        assert_eq!(conflict.kind, ConflictKind::Missing);
+        assert!(conflict.theirs.is_empty());
+
+        fs::write(&path, format!("{TASKS}\n### [ ] P1-T2: Watcher copy\n")).unwrap();
+        let conflict = check(&path, &loaded, &[EditTarget::Task("P1-T2")]).unwrap();
+        assert_eq!(conflict.kind, ConflictKind::Duplicated);
+        assert_eq!(
+            conflict.theirs,
+            vec!["### [ ] P1-T2: Watcher", "", "### [ ] P1-T2: Watcher copy"]
+        );
+    }
+}
@@ -0,0 +1,561 @@
+//! Email digest of recent activity (`digest` subcommand)
+//!
+//! Summarizes the runs, failures and costs of a time window (the last day by
+//! default) as an HTML email and sends it through the SMTP server in the
+//! `[digest]` section of board.toml. Mail goes out with curl, like webhooks,
+//! so the run is a single blocking call that suits cron.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+
+use crate::analysis::rules::analyze_error;
+use crate::data::hook_parser::{EventType, HookEvent};
+use crate::data::identity::AgentIdentity;
+use crate::data::loader;
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+use crate::export::parse_since;
+
+/// Most errors listed in one digest; the rest are only counted
+const MAX_ERRORS: usize = 20;
+
+/// The `[digest]` config section
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DigestConfig {
+    /// `smtps://host:465` or `smtp://host:587`
+    pub smtp_url: String,
+    pub from: String,
+    pub to: Vec<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Subject line; a summary of the numbers when unset
+    pub subject: Option<String>,
+    /// Require TLS (STARTTLS for `smtp://`); off only for local relays
+    pub tls: bool,
+}
+
+impl DigestConfig {
+    /// curl arguments sending the message read from stdin; the login goes
+    /// in [`Self::curl_credentials`]
+    pub fn curl_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = ["--silent", "--show-error", "--url", &self.smtp_url]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        if self.tls {
+            args.push("--ssl-reqd".to_string());
+        }
+        args.extend(["--mail-from".to_string(), self.from.clone()]);
+        for to in &self.to {
+            args.extend(["--mail-rcpt".to_string(), to.clone()]);
+        }
+        args.extend(["--upload-file".to_string(), "-".to_string()]);
+        args
+    }
+
+    /// curl config file (`--config`) with the login, so the password never
+    /// shows up in the process list
+    pub fn curl_credentials(&self) -> Option<String> {
+        let username = self.username.as_deref()?;
+        let password = self.password.as_deref().unwrap_or("");
+        let quoted = format!("{username}:{password}")
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n");
+        Some(format!("user = \"{quoted}\"\n"))
+    }
+}
+
+/// A file only the current user can read, removed when dropped
+struct SecretFile(PathBuf);
+
+impl SecretFile {
+    fn create(contents: &str) -> std::io::Result<Self> {
+        let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+        let path = std::env::temp_dir().join(format!(
+            "simple-claude-board-digest-{}-{nanos}.curlrc",
+            std::process::id()
+        ));
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path)?;
+        let secret = Self(path);
+        file.write_all(contents.as_bytes())?;
+        Ok(secret)
+    }
+}
+
+impl Drop for SecretFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Activity of one agent in the window
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentTotals {
+    pub runs: usize,
+    pub errors: usize,
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// An error reported in the window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorLine {
+    pub timestamp: DateTime<Utc>,
+    pub agent_id: String,
+    pub task_id: String,
+    pub category: String,
+    /// First line of the message
+    pub message: String,
+}
+
+/// What the digest reports
+#[derive(Debug, Clone, PartialEq)]
+pub struct Digest {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub completed_tasks: usize,
+    pub total_tasks: usize,
+    /// Tasks TASKS.md marks failed, as (id, name)
+    pub failed_tasks: Vec<(String, String)>,
+    pub agents: BTreeMap<String, AgentTotals>,
+    /// Errors in the window, newest first
+    pub errors: Vec<ErrorLine>,
+}
+
+impl Digest {
+    /// Summarize `events` from `since` to `until` against the tasks in `state`
+    pub fn build(
+        state: &DashboardState,
+        events: &[HookEvent],
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Self {
+        let mut digest = Self::new(state, since, until);
+        digest.add(state, events);
+        digest.finish()
+    }
+
+    /// A digest from `since` to `until` with no events yet, for adding them
+    /// a batch at a time
+    pub fn new(state: &DashboardState, since: DateTime<Utc>, until: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            until,
+            completed_tasks: state.completed_tasks,
+            total_tasks: state.total_tasks,
+            failed_tasks: state
+                .phases
+                .iter()
+                .flat_map(|p| &p.tasks)
+                .filter(|t| t.status == TaskStatus::Failed)
+                .map(|t| (t.id.clone(), t.name.clone()))
+                .collect(),
+            agents: BTreeMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Count the `events` inside the window, with agents folded as `state`
+    /// does
+    pub fn add(&mut self, state: &DashboardState, events: &[HookEvent]) {
+        for event in events
+            .iter()
+            .filter(|e| e.timestamp >= self.since && e.timestamp <= self.until)
+        {
+            let agent_id = state.logical_agent_id(&event.agent_id);
+            let totals = self.agents.entry(agent_id.clone()).or_default();
+            if let Some(usage) = &event.usage {
+                totals.tokens += usage.input_tokens + usage.output_tokens;
+                totals.cost_usd += usage.cost_usd;
+            }
+            match event.event_type {
+                EventType::AgentStart => totals.runs += 1,
+                EventType::Error => {
+                    totals.errors += 1;
+                    let message = event.error_message.as_deref().unwrap_or("");
+                    self.errors.push(ErrorLine {
+                        timestamp: event.timestamp,
+                        agent_id,
+                        task_id: event.task_id.clone(),
+                        category: analyze_error(message).category.to_string(),
+                        message: message.lines().next().unwrap_or("").to_string(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Once every event is added: errors newest first
+    pub fn finish(mut self) -> Self {
+        self.errors.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        self
+    }
+
+    pub fn runs(&self) -> usize {
+        self.agents.values().map(|a| a.runs).sum()
+    }
+
+    pub fn cost_usd(&self) -> f64 {
+        self.agents.values().map(|a| a.cost_usd).sum()
+    }
+
+    /// Default subject: the headline numbers
+    pub fn subject(&self) -> String {
+        format!(
+            "Board digest: {}/{} tasks done, {} runs, {} errors, ${:.2}",
+            self.completed_tasks,
+            self.total_tasks,
+            self.runs(),
+            self.errors.len(),
+            self.cost_usd()
+        )
+    }
+
+    pub fn to_html(&self) -> String {
+        let time = |t: DateTime<Utc>| t.format("%Y-%m-%d %H:%M UTC").to_string();
+        let mut html =
+            String::from("<!DOCTYPE html>\n<html><body style=\"font-family: sans-serif\">\n");
+        html.push_str(&format!(
+            "<h2>Board digest</h2>\n<p>{} to {}</p>\n",
+            time(self.since),
+            time(self.until)
+        ));
+        html.push_str(&format!(
+            "<p><b>{}/{}</b> tasks done &middot; <b>{}</b> agent runs &middot; <b>{}</b> errors &middot; <b>${:.2}</b> spent</p>\n",
+            self.completed_tasks,
+            self.total_tasks,
+            self.runs(),
+            self.errors.len(),
+            self.cost_usd()
+        ));
+
+        if !self.failed_tasks.is_empty() {
+            html.push_str("<h3>Failed tasks</h3>\n<ul>\n");
+            for (id, name) in &self.failed_tasks {
+                html.push_str(&format!("<li>{} {}</li>\n", escape(id), escape(name)));
+            }
+            html.push_str("</ul>\n");
+        }
+
+        if !self.agents.is_empty() {
+            html.push_str(
+                "<h3>Agents</h3>\n<table cellpadding=\"4\" border=\"1\" style=\"border-collapse: collapse\">\n\
+                 <tr><th>Agent</th><th>Runs</th><th>Errors</th><th>Tokens</th><th>Cost</th></tr>\n",
+            );
+            for (agent, totals) in &self.agents {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>${:.2}</td></tr>\n",
+                    escape(agent),
+                    totals.runs,
+                    totals.errors,
+                    totals.tokens,
+                    totals.cost_usd
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        if !self.errors.is_empty() {
+            html.push_str("<h3>Errors</h3>\n<ul>\n");
+            for error in self.errors.iter().take(MAX_ERRORS) {
+                html.push_str(&format!(
+                    "<li>{} <b>{}</b> on {} [{}]: {}</li>\n",
+                    time(error.timestamp),
+                    escape(&error.agent_id),
+                    escape(&error.task_id),
+                    error.category,
+                    escape(&error.message)
+                ));
+            }
+            html.push_str("</ul>\n");
+            if self.errors.len() > MAX_ERRORS {
+                html.push_str(&format!(
+                    "<p>and {} more</p>\n",
+                    self.errors.len() - MAX_ERRORS
+                ));
+            }
+        }
+        html.push_str("</body></html>\n");
+        html
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Longest run of Q-encoded text in one RFC 2047 encoded word, keeping the
+/// word within 75 characters
+const ENCODED_WORD_TEXT: usize = 75 - "=?UTF-8?Q??=".len();
+
+/// `text` as a header value: line breaks become spaces so they cannot start
+/// another header, and non-ASCII text is RFC 2047 encoded
+fn header_value(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c == '\r' || c == '\n' { ' ' } else { c })
+        .collect();
+    if text.is_ascii() {
+        return text;
+    }
+
+    let mut words = vec![String::new()];
+    for c in text.chars() {
+        let mut encoded = String::new();
+        if c == ' ' {
+            encoded.push('_');
+        } else if c.is_ascii_alphanumeric() || "!*+-/".contains(c) {
+            encoded.push(c);
+        } else {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("={byte:02X}"));
+            }
+        }
+        let word = words.last_mut().expect("at least one word");
+        if word.len() + encoded.len() > ENCODED_WORD_TEXT {
+            words.push(encoded);
+        } else {
+            word.push_str(&encoded);
+        }
+    }
+    words
+        .iter()
+        .map(|w| format!("=?UTF-8?Q?{w}?="))
+        .collect::<Vec<_>>()
+        .join("\r\n ")
+}
+
+/// The email for `digest`: headers and the HTML body, with CRLF line ends
+pub fn message(config: &DigestConfig, digest: &Digest) -> String {
+    let subject = config.subject.clone().unwrap_or_else(|| digest.subject());
+    let headers = [
+        format!("From: {}", config.from),
+        format!("To: {}", config.to.join(", ")),
+        format!("Subject: {}", header_value(&subject)),
+        format!("Date: {}", digest.until.to_rfc2822()),
+        "MIME-Version: 1.0".to_string(),
+        "Content-Type: text/html; charset=utf-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+    ];
+    format!(
+        "{}\r\n\r\n{}",
+        headers.join("\r\n"),
+        digest.to_html().replace('\n', "\r\n")
+    )
+}
+
+/// Send the digest of the last `since` (e.g. `24h`) of the events in `dirs`,
+/// or print it when `dry_run`
+pub fn run(
+    config: Option<&DigestConfig>,
+    tasks_path: &Path,
+    dirs: &[PathBuf],
+    since: &str,
+    dry_run: bool,
+    agent_identity: Option<AgentIdentity>,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let since = parse_since(since, now).map_err(anyhow::Error::msg)?;
+    let mut state = DashboardState::from_tasks_file(tasks_path).unwrap_or_default();
+    state.agent_identity = agent_identity;
+    let mut digest = Digest::new(&state, since, now);
+    loader::for_each_batch(dirs, |batch| digest.add(&state, &batch));
+    let digest = digest.finish();
+
+    let Some(config) = config else {
+        if dry_run {
+            print!("{}", digest.to_html());
+            return Ok(());
+        }
+        bail!("no SMTP server: set [digest] smtp_url, from and to in board.toml");
+    };
+    let message = message(config, &digest);
+    if dry_run {
+        print!("{message}");
+        return Ok(());
+    }
+
+    let credentials = config
+        .curl_credentials()
+        .map(|c| SecretFile::create(&c))
+        .transpose()
+        .context("cannot write the SMTP credentials")?;
+    let mut args = config.curl_args();
+    if let Some(file) = &credentials {
+        args.extend(["--config".to_string(), file.0.display().to_string()]);
+    }
+    let mut child = Command::new("curl")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .context("cannot run curl")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(message.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        bail!("sending the digest to {} failed", config.smtp_url);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn digest() -> Digest {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n### [x] P1-T1: Parser\n### [Failed] P1-T2: <Writer>\n",
+        )
+        .unwrap();
+        let input = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-06T10:00:00Z","agent_id":"old","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T2","session_id":"s"}"#,
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"P1-T2","session_id":"s","usage":{"input_tokens":100,"output_tokens":20,"cost_usd":0.5}}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:02:00Z","agent_id":"a","task_id":"P1-T2","session_id":"s","error_message":"permission denied\nat line 2"}"#,
+        ]
+        .join("\n");
+        Digest::build(
+            &state,
+            &parse_hook_events(&input).events,
+            "2026-02-07T12:00:00Z".parse().unwrap(),
+            "2026-02-08T12:00:00Z".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn summarizes_runs_failures_and_costs_in_the_window() {
+        let digest = digest();
+        assert_eq!(digest.runs(), 1);
+        assert!(!digest.agents.contains_key("old"));
+        assert_eq!(digest.agents["a"].tokens, 120);
+        assert_eq!(digest.errors[0].message, "permission denied");
+        assert_eq!(digest.errors[0].category, "Permission");
+        assert_eq!(
+            digest.subject(),
+            "Board digest: 1/2 tasks done, 1 runs, 1 errors, $0.50"
+        );
+
+        let html = digest.to_html();
+        assert!(html.contains("<li>P1-T2 &lt;Writer&gt;</li>"));
+        assert!(html.contains("<td>a</td><td>1</td><td>1</td><td>120</td><td>$0.50</td>"));
+    }
+
+    #[test]
+    fn batches_from_several_files_list_errors_newest_first() {
+        let state = DashboardState::default();
+        let error = |minute: u32| {
+            parse_hook_events(&format!(
+                r#"{{"event_type":"error","timestamp":"2026-02-08T10:{minute:02}:00Z","agent_id":"a","task_id":"T1","session_id":"s","error_message":"at {minute}"}}"#
+            ))
+            .events
+        };
+        let mut digest = Digest::new(
+            &state,
+            "2026-02-08T00:00:00Z".parse().unwrap(),
+            "2026-02-08T12:00:00Z".parse().unwrap(),
+        );
+        digest.add(&state, &error(5));
+        digest.add(&state, &error(1));
+        digest.add(&state, &error(9));
+        let digest = digest.finish();
+        let messages: Vec<&str> = digest.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["at 9", "at 5", "at 1"]);
+        assert_eq!(digest.agents["a"].errors, 3);
+    }
+
+    #[test]
+    fn message_and_curl_args_follow_the_config() {
+        let config = DigestConfig {
+            smtp_url: "smtps://smtp.example.com:465".to_string(),
+            from: "board@example.com".to_string(),
+            to: vec!["a@example.com".to_string(), "b@example.com".to_string()],
+            username: Some("board".to_string()),
+            password: Some("pw".to_string()),
+            subject: None,
+            tls: true,
+        };
+        let message = message(&config, &digest());
+        assert!(message.starts_with("From: board@example.com\r\nTo: a@example.com, b@example.com\r\nSubject: Board digest: "));
+        assert!(message.contains("\r\n\r\n<!DOCTYPE html>\r\n"));
+
+        let args = config.curl_args();
+        assert!(args.contains(&"--ssl-reqd".to_string()));
+        assert_eq!(args.iter().filter(|a| *a == "--mail-rcpt").count(), 2);
+        assert!(!args.iter().any(|a| a.contains("pw")));
+        assert_eq!(args.last().unwrap(), "-");
+        assert_eq!(
+            config.curl_credentials().as_deref(),
+            Some("user = \"board:pw\"\n")
+        );
+    }
+
+    #[test]
+    fn credentials_are_quoted_for_curl() {
+        let config = DigestConfig {
+            username: Some("board".to_string()),
+            password: Some("p\"w\\x\nuser = \"evil\"".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.curl_credentials().unwrap(),
+            "user = \"board:p\\\"w\\\\x\\nuser = \\\"evil\\\"\"\n"
+        );
+        assert_eq!(DigestConfig::default().curl_credentials(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn secret_file_is_private_and_removed() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = SecretFile::create("user = \"a:b\"\n").unwrap();
+        let path = file.0.clone();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        drop(file);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn subjects_cannot_inject_headers() {
+        assert_eq!(
+            header_value("Done\r\nBcc: x@example.com"),
+            "Done  Bcc: x@example.com"
+        );
+        assert_eq!(
+            header_value("프로젝트 digest"),
+            "=?UTF-8?Q?=ED=94=84=EB=A1=9C=EC=A0=9D=ED=8A=B8_digest?="
+        );
+
+        let long = header_value(&"é".repeat(40));
+        let words: Vec<&str> = long.split("\r\n ").collect();
+        assert!(words.len() > 1);
+        assert!(words
+            .iter()
+            .all(|w| w.len() <= 75 && w.starts_with("=?UTF-8?Q?") && w.ends_with("?=")));
+
+        let config = DigestConfig {
+            subject: Some("Board\nX-Injected: yes".to_string()),
+            ..Default::default()
+        };
+        assert!(!message(&config, &digest()).contains("\nX-Injected"));
+    }
+}
@@ -0,0 +1,75 @@
+//! External editor
+//!
+//! Builds the `$VISUAL` / `$EDITOR` command that opens a file, optionally at
+//! a line. The main loop suspends the TUI while the editor runs.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set
+const FALLBACK_EDITOR: &str = if cfg!(windows) { "notepad" } else { "vi" };
+
+/// A file to open in the external editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorRequest {
+    pub path: PathBuf,
+    /// 1-based line to place the cursor on
+    pub line: Option<usize>,
+}
+
+/// The user's editor: `$VISUAL` > `$EDITOR` > `vi` (`notepad` on Windows)
+pub fn resolve_editor() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+/// `editor [args..] [+line] path`; `editor` may carry its own arguments
+/// (`code --wait`). `None` when it is blank.
+pub fn editor_command(editor: &str, request: &EditorRequest) -> Option<Command> {
+    let mut words = editor.split_whitespace();
+    let mut command = Command::new(words.next()?);
+    command.args(words);
+    if let Some(line) = request.line {
+        command.arg(format!("+{line}"));
+    }
+    command.arg(&request.path);
+    Some(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn command_puts_line_before_path() {
+        let request = EditorRequest {
+            path: PathBuf::from("TASKS.md"),
+            line: Some(42),
+        };
+        let command = editor_command("nvim", &request).unwrap();
+        assert_eq!(command.get_program(), "nvim");
+        assert_eq!(args(&command), vec!["+42", "TASKS.md"]);
+    }
+
+    #[test]
+    fn command_keeps_editor_arguments() {
+        let request = EditorRequest {
+            path: PathBuf::from("src/main.rs"),
+            line: None,
+        };
+        let command = editor_command("code  --wait", &request).unwrap();
+        assert_eq!(command.get_program(), "code");
+        assert_eq!(args(&command), vec!["--wait", "src/main.rs"]);
+        assert!(editor_command("  ", &request).is_none());
+    }
+}
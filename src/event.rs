@@ -1,42 +1,108 @@
-//! Keyboard, file, and timer event integration
+//! Keyboard, ingestion, and timer event integration
 //!
-//! Merges crossterm keyboard events with file-watcher events into a unified
-//! event stream for the main loop.
+//! Merges crossterm terminal events with updates from the ingestion sources
+//! and a UI tick into one async stream for the main loop. Waiting happens in
+//! a `select!`, so neither a quiet terminal nor a busy source holds up the
+//! other.
 
 use std::time::Duration;
 
 use crossterm::event::{
-    self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+    Event as CrosstermEvent, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
 };
+use futures_util::StreamExt;
+use tokio::sync::mpsc;
+use tokio::time::{Interval, MissedTickBehavior};
 
-use crate::data::watcher::FileChange;
+use crate::data::source::Update;
 
 /// Unified application event
 #[derive(Debug)]
 pub enum AppEvent {
     /// Keyboard input
     Key(KeyEvent),
-    /// File change detected
-    FileChanged(FileChange),
+    /// News from an ingestion source
+    Update(Update),
     /// Periodic tick for UI refresh
     Tick,
     /// Terminal resize
     Resize(u16, u16),
+    /// Terminal gained (`true`) or lost focus
+    Focus(bool),
 }
 
-/// Polls for crossterm events with a timeout.
-/// Returns `Some(AppEvent)` if an event occurred, `None` on timeout.
-pub fn poll_event(timeout: Duration) -> anyhow::Result<Option<AppEvent>> {
-    if event::poll(timeout)? {
-        match event::read()? {
-            CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => {
-                Ok(Some(AppEvent::Key(key)))
+/// The terminal event this loop cares about, if any
+fn from_terminal(event: CrosstermEvent) -> Option<AppEvent> {
+    match event {
+        CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => Some(AppEvent::Key(key)),
+        CrosstermEvent::Resize(w, h) => Some(AppEvent::Resize(w, h)),
+        CrosstermEvent::FocusGained => Some(AppEvent::Focus(true)),
+        CrosstermEvent::FocusLost => Some(AppEvent::Focus(false)),
+        _ => None,
+    }
+}
+
+/// Next message from an optional channel; never resolves once it is gone
+async fn recv<T>(rx: &mut Option<mpsc::UnboundedReceiver<T>>) -> Option<T> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Terminal input, ingestion updates and ticks as one stream
+pub struct Events {
+    /// Opened on first use and dropped by [`Events::release_terminal`]
+    terminal: Option<EventStream>,
+    updates: Option<mpsc::UnboundedReceiver<Update>>,
+    tick: Interval,
+}
+
+impl Events {
+    pub fn new(updates: Option<mpsc::UnboundedReceiver<Update>>, tick_rate: Duration) -> Self {
+        let mut tick = tokio::time::interval(tick_rate);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            terminal: None,
+            updates,
+            tick,
+        }
+    }
+
+    /// Wait for the next event. Terminal input goes first, so a flood of
+    /// updates never delays a key press.
+    pub async fn next(&mut self) -> anyhow::Result<AppEvent> {
+        loop {
+            let terminal = self.terminal.get_or_insert_with(EventStream::new);
+            tokio::select! {
+                biased;
+                event = terminal.next() => match event {
+                    Some(event) => {
+                        if let Some(event) = from_terminal(event?) {
+                            return Ok(event);
+                        }
+                    }
+                    None => anyhow::bail!("terminal input closed"),
+                },
+                update = recv(&mut self.updates) => match update {
+                    Some(update) => return Ok(AppEvent::Update(update)),
+                    None => self.updates = None,
+                },
+                _ = self.tick.tick() => return Ok(AppEvent::Tick),
             }
-            CrosstermEvent::Resize(w, h) => Ok(Some(AppEvent::Resize(w, h))),
-            _ => Ok(None),
         }
-    } else {
-        Ok(None)
+    }
+
+    /// An update that is already waiting, for applying a burst before the
+    /// next redraw
+    pub fn pending_update(&mut self) -> Option<Update> {
+        self.updates.as_mut()?.try_recv().ok()
+    }
+
+    /// Stop reading the terminal so a child process (editor, pager, shell)
+    /// gets the keys; reading resumes on the next [`Events::next`]
+    pub fn release_terminal(&mut self) {
+        self.terminal = None;
     }
 }
 
@@ -50,7 +116,37 @@ pub enum Action {
     ToggleHelp,
     ToggleCollapse,
     ToggleView,
+    ToggleWrap,
+    Select,
+    ToggleSessionPicker,
+    OpenLogViewer,
+    OpenMinimap,
+    ToggleLayout,
+    ToggleGauge,
     RetryRequest,
+    AddTask,
+    Rename,
+    AssignAgent,
+    MoveTaskDown,
+    MoveTaskUp,
+    MoveToPhase,
+    ResetTask,
+    OpenEditor,
+    ArchivePhase,
+    Undo,
+    Redo,
+    BulkStatus,
+    BlockTask,
+    SignalAgent,
+    AcknowledgeErrors,
+    MuteAgent,
+    Remind,
+    OpenDiff,
+    OpenPager,
+    CopySummary,
+    OpenHistory,
+    /// Function key, for `[actions]` commands
+    Custom(u8),
     Confirm,
     Cancel,
     None,
@@ -62,15 +158,44 @@ pub fn key_to_action(key: KeyEvent) -> Action {
     match key.code {
         KeyCode::Char('q' | 'ㅂ') | KeyCode::Esc => Action::Quit,
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Redo,
         KeyCode::Char('j' | 'ㅓ') | KeyCode::Down => Action::MoveDown,
         KeyCode::Char('k' | 'ㅏ') | KeyCode::Up => Action::MoveUp,
         KeyCode::Tab => Action::ToggleFocus,
+        KeyCode::Enter => Action::Select,
         KeyCode::Char('?') => Action::ToggleHelp,
         KeyCode::Char(' ') => Action::ToggleCollapse,
         KeyCode::Char('v' | 'ㅍ') => Action::ToggleView,
+        KeyCode::Char('w' | 'ㅈ') => Action::ToggleWrap,
         KeyCode::Char('r' | 'ㄱ') => Action::RetryRequest,
+        KeyCode::Char('s' | 'ㄴ') => Action::ToggleSessionPicker,
+        KeyCode::Char('l' | 'ㅣ') => Action::OpenLogViewer,
+        KeyCode::Char('m' | 'ㅡ') => Action::OpenMinimap,
+        KeyCode::Char('L') => Action::ToggleLayout,
+        KeyCode::Char('p' | 'ㅔ') => Action::ToggleGauge,
+        KeyCode::Char('a' | 'ㅁ') => Action::AddTask,
+        KeyCode::Char('e' | 'ㄷ') => Action::Rename,
+        KeyCode::Char('A') => Action::AssignAgent,
+        KeyCode::Char('J') => Action::MoveTaskDown,
+        KeyCode::Char('K') => Action::MoveTaskUp,
+        KeyCode::Char('M') => Action::MoveToPhase,
+        KeyCode::Char('R') => Action::ResetTask,
+        KeyCode::Char('o' | 'ㅐ') => Action::OpenEditor,
+        KeyCode::Char('X') => Action::ArchivePhase,
+        KeyCode::Char('u' | 'ㅕ') => Action::Undo,
+        KeyCode::Char('B') => Action::BulkStatus,
+        KeyCode::Char('b' | 'ㅠ') => Action::BlockTask,
+        KeyCode::Char('S') => Action::SignalAgent,
+        KeyCode::Char('c' | 'ㅊ') => Action::AcknowledgeErrors,
+        KeyCode::Char('z' | 'ㅋ') => Action::MuteAgent,
+        KeyCode::Char('t' | 'ㅅ') => Action::Remind,
+        KeyCode::Char('d' | 'ㅇ') => Action::OpenDiff,
+        KeyCode::Char('P') => Action::OpenPager,
+        KeyCode::Char('Y') => Action::CopySummary,
+        KeyCode::Char('H') => Action::OpenHistory,
+        KeyCode::F(n) => Action::Custom(n),
         KeyCode::Char('y') => Action::Confirm,
-        KeyCode::Char('n') => Action::Cancel,
+        KeyCode::Char('n' | 'ㅜ') => Action::Cancel,
         _ => Action::None,
     }
 }
@@ -89,6 +214,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn only_key_presses_and_window_changes_become_events() {
+        let press = make_key(KeyCode::Char('j'), KeyModifiers::NONE);
+        let release = KeyEvent {
+            kind: KeyEventKind::Release,
+            ..press
+        };
+        assert!(matches!(
+            from_terminal(CrosstermEvent::Key(press)),
+            Some(AppEvent::Key(_))
+        ));
+        assert!(from_terminal(CrosstermEvent::Key(release)).is_none());
+        assert!(matches!(
+            from_terminal(CrosstermEvent::FocusLost),
+            Some(AppEvent::Focus(false))
+        ));
+        assert!(from_terminal(CrosstermEvent::Paste("x".into())).is_none());
+    }
+
     #[test]
     fn quit_on_q() {
         assert_eq!(
@@ -153,6 +297,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn select_on_enter() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Enter, KeyModifiers::NONE)),
+            Action::Select
+        );
+    }
+
     #[test]
     fn toggle_help_question() {
         assert_eq!(
@@ -161,6 +313,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn toggle_wrap_w() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('w'), KeyModifiers::NONE)),
+            Action::ToggleWrap
+        );
+    }
+
     #[test]
     fn retry_request_on_r() {
         assert_eq!(
@@ -177,6 +337,254 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_picker_on_s() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('s'), KeyModifiers::NONE)),
+            Action::ToggleSessionPicker
+        );
+    }
+
+    #[test]
+    fn log_viewer_on_l() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('l'), KeyModifiers::NONE)),
+            Action::OpenLogViewer
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅣ'), KeyModifiers::NONE)),
+            Action::OpenLogViewer
+        );
+    }
+
+    #[test]
+    fn minimap_on_m() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('m'), KeyModifiers::NONE)),
+            Action::OpenMinimap
+        );
+    }
+
+    #[test]
+    fn layout_toggle_on_shift_l() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('L'), KeyModifiers::SHIFT)),
+            Action::ToggleLayout
+        );
+    }
+
+    #[test]
+    fn gauge_toggle_on_p() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('p'), KeyModifiers::NONE)),
+            Action::ToggleGauge
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅔ'), KeyModifiers::NONE)),
+            Action::ToggleGauge
+        );
+    }
+
+    #[test]
+    fn add_task_on_a() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('a'), KeyModifiers::NONE)),
+            Action::AddTask
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅁ'), KeyModifiers::NONE)),
+            Action::AddTask
+        );
+    }
+
+    #[test]
+    fn rename_on_e() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('e'), KeyModifiers::NONE)),
+            Action::Rename
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㄷ'), KeyModifiers::NONE)),
+            Action::Rename
+        );
+    }
+
+    #[test]
+    fn assign_agent_on_shift_a() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+            Action::AssignAgent
+        );
+    }
+
+    #[test]
+    fn shifted_keys_reorder_tasks() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('J'), KeyModifiers::SHIFT)),
+            Action::MoveTaskDown
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('K'), KeyModifiers::SHIFT)),
+            Action::MoveTaskUp
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('M'), KeyModifiers::SHIFT)),
+            Action::MoveToPhase
+        );
+    }
+
+    #[test]
+    fn reset_task_on_shift_r() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('R'), KeyModifiers::SHIFT)),
+            Action::ResetTask
+        );
+    }
+
+    #[test]
+    fn open_editor_on_o_and_korean() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('o'), KeyModifiers::NONE)),
+            Action::OpenEditor
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅐ'), KeyModifiers::NONE)),
+            Action::OpenEditor
+        );
+    }
+
+    #[test]
+    fn archive_undo_and_redo_keys() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('X'), KeyModifiers::SHIFT)),
+            Action::ArchivePhase
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('u'), KeyModifiers::NONE)),
+            Action::Undo
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅕ'), KeyModifiers::NONE)),
+            Action::Undo
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('r'), KeyModifiers::CONTROL)),
+            Action::Redo
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('r'), KeyModifiers::NONE)),
+            Action::RetryRequest
+        );
+    }
+
+    #[test]
+    fn bulk_status_on_shift_b() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('B'), KeyModifiers::SHIFT)),
+            Action::BulkStatus
+        );
+    }
+
+    #[test]
+    fn function_keys_are_custom_actions() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::F(5), KeyModifiers::NONE)),
+            Action::Custom(5)
+        );
+    }
+
+    #[test]
+    fn block_task_on_b() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('b'), KeyModifiers::NONE)),
+            Action::BlockTask
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅠ'), KeyModifiers::NONE)),
+            Action::BlockTask
+        );
+    }
+
+    #[test]
+    fn signal_agent_on_shift_s() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('S'), KeyModifiers::SHIFT)),
+            Action::SignalAgent
+        );
+    }
+
+    #[test]
+    fn acknowledge_errors_on_c() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('c'), KeyModifiers::NONE)),
+            Action::AcknowledgeErrors
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅊ'), KeyModifiers::NONE)),
+            Action::AcknowledgeErrors
+        );
+    }
+
+    #[test]
+    fn mute_agent_on_z() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('z'), KeyModifiers::NONE)),
+            Action::MuteAgent
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅋ'), KeyModifiers::NONE)),
+            Action::MuteAgent
+        );
+    }
+
+    #[test]
+    fn remind_on_t() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('t'), KeyModifiers::NONE)),
+            Action::Remind
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅅ'), KeyModifiers::NONE)),
+            Action::Remind
+        );
+    }
+
+    #[test]
+    fn open_diff_on_d() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('d'), KeyModifiers::NONE)),
+            Action::OpenDiff
+        );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅇ'), KeyModifiers::NONE)),
+            Action::OpenDiff
+        );
+    }
+
+    #[test]
+    fn open_pager_on_shift_p() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('P'), KeyModifiers::SHIFT)),
+            Action::OpenPager
+        );
+    }
+
+    #[test]
+    fn copy_summary_on_shift_y() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('Y'), KeyModifiers::SHIFT)),
+            Action::CopySummary
+        );
+    }
+
+    #[test]
+    fn open_history_on_shift_h() {
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('H'), KeyModifiers::SHIFT)),
+            Action::OpenHistory
+        );
+    }
+
     #[test]
     fn confirm_on_y() {
         assert_eq!(
@@ -191,6 +599,10 @@ mod tests {
             key_to_action(make_key(KeyCode::Char('n'), KeyModifiers::NONE)),
             Action::Cancel
         );
+        assert_eq!(
+            key_to_action(make_key(KeyCode::Char('ㅜ'), KeyModifiers::NONE)),
+            Action::Cancel
+        );
     }
 
     #[test]
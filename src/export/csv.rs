@@ -0,0 +1,147 @@
+//! CSV export of the raw event history (`export-events --format csv`)
+//!
+//! One row per hook event with every `HookEvent` field, plus two derived
+//! columns: `duration_ms` on `tool_end` / `agent_end` rows (time since the
+//! matching start) and `error_category` on `error` rows.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::analysis::rules::analyze_error;
+use crate::data::hook_parser::{EventType, HookEvent};
+
+/// Header row, in column order
+pub const COLUMNS: [&str; 14] = [
+    "timestamp",
+    "event_type",
+    "session_id",
+    "agent_id",
+    "task_id",
+    "tool_name",
+    "file_path",
+    "cwd",
+    "duration_ms",
+    "error_category",
+    "error_message",
+    "input_tokens",
+    "output_tokens",
+    "cost_usd",
+];
+
+/// Quote a field when it holds a separator, quote or line break (RFC 4180)
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Session, agent and (for tool calls) tool name a start and end pair on
+type PairKey<'a> = (&'a str, &'a str, Option<&'a str>);
+
+/// Milliseconds from each end event's matching start, by index into `events`.
+/// Starts are matched per session and agent (and tool name for tool calls),
+/// most recent first.
+fn durations(events: &[HookEvent]) -> HashMap<usize, i64> {
+    let mut open: HashMap<PairKey, Vec<DateTime<Utc>>> = HashMap::new();
+    let mut durations = HashMap::new();
+    for (idx, event) in events.iter().enumerate() {
+        let agent = (event.session_id.as_str(), event.agent_id.as_str());
+        let key = match event.event_type {
+            EventType::AgentStart | EventType::AgentEnd => (agent.0, agent.1, None),
+            EventType::ToolStart | EventType::ToolEnd => (
+                agent.0,
+                agent.1,
+                Some(event.tool_name.as_deref().unwrap_or("")),
+            ),
+            EventType::Error => continue,
+        };
+        match event.event_type {
+            EventType::AgentStart | EventType::ToolStart => {
+                open.entry(key).or_default().push(event.timestamp);
+            }
+            _ => {
+                if let Some(start) = open.get_mut(&key).and_then(Vec::pop) {
+                    durations.insert(idx, (event.timestamp - start).num_milliseconds());
+                }
+            }
+        }
+    }
+    durations
+}
+
+/// CSV of the events at or after `since`; durations also pair with starts
+/// before it. `events` must be oldest first.
+pub fn to_csv(events: &[HookEvent], since: Option<DateTime<Utc>>) -> String {
+    let durations = durations(events);
+    let mut out = COLUMNS.join(",");
+    out.push('\n');
+    for (idx, event) in events.iter().enumerate() {
+        if since.is_some_and(|since| event.timestamp < since) {
+            continue;
+        }
+        let usage = event.usage.clone().unwrap_or_default();
+        let category = match (&event.event_type, &event.error_message) {
+            (EventType::Error, Some(message)) => analyze_error(message).category.to_string(),
+            _ => String::new(),
+        };
+        let row = [
+            event.timestamp.to_rfc3339(),
+            event.event_type.as_str().to_string(),
+            event.session_id.clone(),
+            event.agent_id.clone(),
+            event.task_id.clone(),
+            event.tool_name.clone().unwrap_or_default(),
+            event.file_path.clone().unwrap_or_default(),
+            event.cwd.clone().unwrap_or_default(),
+            durations
+                .get(&idx)
+                .map(|ms| ms.to_string())
+                .unwrap_or_default(),
+            category,
+            event.error_message.clone().unwrap_or_default(),
+            usage.input_tokens.to_string(),
+            usage.output_tokens.to_string(),
+            usage.cost_usd.to_string(),
+        ];
+        let row: Vec<String> = row.iter().map(|value| field(value)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn rows_carry_durations_and_categories() {
+        let input = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"P1-T1","session_id":"s","tool_name":"Bash"}"#,
+            r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:00:03.500Z","agent_id":"a","task_id":"P1-T1","session_id":"s","tool_name":"Bash","usage":{"input_tokens":5,"cost_usd":0.25}}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:04Z","agent_id":"a","task_id":"P1-T1","session_id":"s","error_message":"connection refused, \"db\""}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+        ]
+        .join("\n");
+        let events = parse_hook_events(&input).events;
+
+        let csv = to_csv(&events, None);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[0], COLUMNS.join(","));
+        assert!(rows[3].contains(",Bash,,,2500,,,5,0,0.25"));
+        assert!(rows[4].ends_with(",Network,\"connection refused, \"\"db\"\"\",0,0,0"));
+        assert!(rows[5].contains(",60000,"));
+
+        // Durations still pair with starts before the cut-off
+        let since = "2026-02-08T10:00:02Z".parse().unwrap();
+        let csv = to_csv(&events, Some(since));
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.contains(",2500,"));
+    }
+}
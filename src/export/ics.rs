@@ -0,0 +1,188 @@
+//! iCalendar timeline export (`export --format ics`)
+//!
+//! Each completed or in-progress task with a recorded start becomes an event
+//! from its first `agent_start` to its last `agent_end` (or the export time
+//! while it is still running), categorized by phase, so the calendar shows
+//! when the work actually happened. Its description names the agent and the
+//! tokens and cost the task used.
+
+use chrono::{DateTime, Utc};
+
+use crate::analysis::metrics::format_tokens;
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Longest content line before folding, in octets (RFC 5545 §3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+fn ics_time(time: &DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value (RFC 5545 §3.3.11)
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Append `line` folded at 75 octets, with CRLF line endings
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Calendar with one event per timed completed or in-progress task
+pub fn to_ics(state: &DashboardState, now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(
+        &mut out,
+        &format!("PRODID:-//{}//Task timeline//EN", env!("CARGO_PKG_NAME")),
+    );
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+
+    for phase in &state.phases {
+        let category = format!("{}: {}", phase.id, phase.name);
+        for task in &phase.tasks {
+            let done = match task.status {
+                TaskStatus::Completed => true,
+                TaskStatus::InProgress => false,
+                _ => continue,
+            };
+            let Some(timing) = state.task_times.get(&task.id) else {
+                continue;
+            };
+            let Some(start) = timing.started_at else {
+                continue;
+            };
+            let end = match timing.completed_at {
+                Some(end) if done => end,
+                _ if done => start,
+                _ => now,
+            };
+
+            push_line(&mut out, "BEGIN:VEVENT");
+            push_line(
+                &mut out,
+                &format!(
+                    "UID:{}-{}@{}",
+                    task.id,
+                    ics_time(&start),
+                    env!("CARGO_PKG_NAME")
+                ),
+            );
+            push_line(&mut out, &format!("DTSTAMP:{}", ics_time(&now)));
+            push_line(&mut out, &format!("DTSTART:{}", ics_time(&start)));
+            push_line(&mut out, &format!("DTEND:{}", ics_time(&end.max(start))));
+            push_line(
+                &mut out,
+                &format!("SUMMARY:{}", escape(&format!("{}: {}", task.id, task.name))),
+            );
+            push_line(&mut out, &format!("CATEGORIES:{}", escape(&category)));
+            let mut description = format!("Phase {category}");
+            if let Some(agent) = task
+                .agent
+                .as_deref()
+                .or_else(|| state.agent_for_task(&task.id))
+            {
+                description.push_str(&format!("\nAgent: {agent}"));
+            }
+            if timing.tokens > 0 || timing.cost_usd > 0.0 {
+                description.push_str(&format!(
+                    "\nUsage: {} tokens, ${:.2}",
+                    format_tokens(timing.tokens),
+                    timing.cost_usd
+                ));
+            }
+            if !done {
+                description.push_str("\nStill in progress at export time");
+            }
+            push_line(&mut out, &format!("DESCRIPTION:{}", escape(&description)));
+            push_line(
+                &mut out,
+                if done {
+                    "STATUS:CONFIRMED"
+                } else {
+                    "STATUS:TENTATIVE"
+                },
+            );
+            push_line(&mut out, "END:VEVENT");
+        }
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn timed_tasks_become_events() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Setup, core\n\
+             ### [x] P1-T1: Parser; lexer\n\
+             ### [InProgress] P1-T2: Watcher\n\
+             ### [ ] P1-T3: Writer\n\
+             ### [x] P1-T4: Untracked\n",
+        )
+        .unwrap();
+        let events = [
+            ("agent_start", "P1-T1", "10:00"),
+            ("agent_end", "P1-T1", "10:30"),
+            ("agent_start", "P1-T2", "11:00"),
+            ("agent_start", "P1-T3", "11:05"),
+        ]
+        .map(|(kind, task, time)| {
+            format!(
+                r#"{{"event_type":"{kind}","timestamp":"2026-02-08T{time}:00Z","agent_id":"a-{task}","task_id":"{task}","session_id":"s"}}"#
+            )
+        })
+        .join("\n");
+        let usage = r#"{"event_type":"tool_end","timestamp":"2026-02-08T10:10:00Z","agent_id":"a-P1-T1","task_id":"P1-T1","session_id":"s","tool_name":"Read","usage":{"input_tokens":1200,"output_tokens":300,"cost_usd":0.02}}"#;
+        state.update_from_events(&parse_hook_events(&format!("{usage}\n{events}")).events);
+
+        let now = "2026-02-08T12:00:00Z".parse().unwrap();
+        let ics = to_ics(&state, now);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART:20260208T100000Z\r\nDTEND:20260208T103000Z\r\n"));
+        assert!(ics.contains("SUMMARY:P1-T1: Parser\\; lexer\r\n"));
+        assert!(ics.contains("CATEGORIES:P1: Setup\\, core\r\n"));
+        assert!(ics.contains("DTEND:20260208T120000Z\r\nSUMMARY:P1-T2"));
+        assert!(ics.contains("STATUS:TENTATIVE"));
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(
+            unfolded.contains("\\nUsage: 1.5k tokens\\, $0.02\r\n"),
+            "{ics}"
+        );
+        assert!(!ics.contains("P1-T3") && !ics.contains("P1-T4"));
+    }
+
+    #[test]
+    fn long_lines_are_folded() {
+        let mut out = String::new();
+        push_line(&mut out, &format!("SUMMARY:{}", "é".repeat(50)));
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert!(lines.iter().all(|l| l.len() <= MAX_LINE_OCTETS));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(
+            out.replace("\r\n ", ""),
+            format!("SUMMARY:{}\r\n", "é".repeat(50))
+        );
+    }
+}
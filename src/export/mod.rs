@@ -0,0 +1,149 @@
+//! One-shot exports of the board's tasks and event history to other tools
+
+pub mod csv;
+pub mod ics;
+pub mod otel;
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::data::loader;
+use crate::data::state::DashboardState;
+
+/// File formats of the `export` subcommand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// iCalendar timeline of task work
+    #[default]
+    Ics,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ics" | "ical" => Ok(ExportFormat::Ics),
+            other => Err(format!("unknown export format `{other}` (expected ics)")),
+        }
+    }
+}
+
+/// File formats of the `export-events` subcommand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventFormat {
+    /// One row per hook event, for spreadsheets
+    #[default]
+    Csv,
+}
+
+impl FromStr for EventFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(EventFormat::Csv),
+            other => Err(format!("unknown event format `{other}` (expected csv)")),
+        }
+    }
+}
+
+/// Start of a `--since` window: a span back from `now` (`30m`, `24h`, `7d`)
+/// or an RFC 3339 timestamp
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let split = value.len() - value.chars().last().map_or(0, char::len_utf8);
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("invalid --since `{value}` (expected e.g. 30m, 24h, 7d)"))?;
+    let span = match unit {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        "w" => Duration::weeks(amount),
+        _ => {
+            return Err(format!(
+                "invalid --since `{value}` (unit must be s, m, h, d or w)"
+            ))
+        }
+    };
+    Ok(now - span)
+}
+
+fn write_output(content: &str, output: Option<&Path>) -> anyhow::Result<()> {
+    match output {
+        Some(path) => std::fs::write(path, content)
+            .with_context(|| format!("cannot write {}", path.display())),
+        None => {
+            print!("{content}");
+            Ok(())
+        }
+    }
+}
+
+/// Export the events in `dirs` from `since` on as `format`, to `output` or
+/// stdout
+pub fn run_events(
+    format: EventFormat,
+    dirs: &[PathBuf],
+    since: Option<&str>,
+    output: Option<&Path>,
+) -> anyhow::Result<()> {
+    let since = since
+        .map(|s| parse_since(s, Utc::now()))
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+    // Every event is a row, and durations pair across the whole history
+    let mut events = Vec::new();
+    loader::for_each_batch(dirs, |batch| events.extend(batch));
+    events.sort_by_key(|e| e.timestamp);
+    let content = match format {
+        EventFormat::Csv => csv::to_csv(&events, since),
+    };
+    write_output(&content, output)
+}
+
+/// Export TASKS.md at `tasks_path` with the events in `dirs` as `format`,
+/// to `output` or stdout
+pub fn run(
+    format: ExportFormat,
+    tasks_path: &Path,
+    dirs: &[PathBuf],
+    output: Option<&Path>,
+) -> anyhow::Result<()> {
+    let mut state = DashboardState::from_tasks_file(tasks_path)?;
+    loader::for_each_batch(dirs, |batch| state.update_from_events(&batch));
+    let content = match format {
+        ExportFormat::Ics => ics::to_ics(&state, Utc::now()),
+    };
+    write_output(&content, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn since_accepts_spans_and_timestamps() {
+        let now: DateTime<Utc> = "2026-02-08T12:00:00Z".parse().unwrap();
+        let expect = |s: &str| parse_since(s, now).unwrap().to_rfc3339();
+        assert_eq!(expect("24h"), "2026-02-07T12:00:00+00:00");
+        assert_eq!(expect("30m"), "2026-02-08T11:30:00+00:00");
+        assert_eq!(expect("2d"), "2026-02-06T12:00:00+00:00");
+        assert_eq!(
+            expect("2026-02-08T09:00:00+09:00"),
+            "2026-02-08T00:00:00+00:00"
+        );
+        assert!(parse_since("24x", now).is_err());
+        assert!(parse_since("h", now).is_err());
+        assert!(parse_since("", now).is_err());
+    }
+}
@@ -0,0 +1,511 @@
+//! OpenTelemetry trace export (`otel` subcommand)
+//!
+//! Pairs hook events into spans: each agent run (`agent_start` to
+//! `agent_end`) is a span with one child span per tool call, and `error`
+//! events become `exception` span events on the innermost open span. Each
+//! session is one trace. Spans are sent as OTLP/HTTP JSON, so any collector
+//! feeding Jaeger or Tempo can take them.
+//!
+//! Trace IDs are derived from the session and span IDs from the event that
+//! opens the span, so exporting the same history twice, or one session of
+//! it, yields the same IDs. The history is streamed a batch of lines at a
+//! time and the spans are posted with curl (like webhooks and the digest)
+//! in batches as they finish, so memory stays bounded by the open runs.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use crate::data::hook_parser::{EventType, HookEvent};
+use crate::data::loader;
+
+/// Instrumentation scope and `service.name` of the exported spans
+const SERVICE_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// Path OTLP/HTTP collectors accept traces on
+const TRACES_PATH: &str = "/v1/traces";
+
+/// Seconds curl may spend on one request to the collector
+const TIMEOUT_SECS: u32 = 10;
+
+/// Finished spans posted per request
+const BATCH_SPANS: usize = 500;
+
+/// An error recorded on a span
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanEvent {
+    pub time: DateTime<Utc>,
+    pub message: String,
+}
+
+/// One finished span
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    /// 32 hex digits, shared by every span of a session
+    pub trace_id: String,
+    /// 16 hex digits
+    pub span_id: String,
+    /// The agent span of a tool span
+    pub parent_id: Option<String>,
+    pub name: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub attributes: Vec<(&'static str, String)>,
+    pub errors: Vec<SpanEvent>,
+    /// No end event was seen; the span ends at the agent's last event
+    pub incomplete: bool,
+}
+
+impl Span {
+    fn new(trace_id: &str, span_id: String, name: String, event: &HookEvent) -> Self {
+        Span {
+            trace_id: trace_id.to_string(),
+            span_id,
+            parent_id: None,
+            name,
+            start: event.timestamp,
+            end: event.timestamp,
+            attributes: vec![
+                ("session.id", event.session_id.clone()),
+                ("agent.id", event.agent_id.clone()),
+                ("task.id", event.task_id.clone()),
+            ],
+            errors: Vec::new(),
+            incomplete: false,
+        }
+    }
+}
+
+/// Hex of the first `bytes` bytes of the SHA-1 of `key`
+fn hex_id(key: &str, bytes: usize) -> String {
+    sha1_smol::Sha1::from(key).digest().bytes()[..bytes]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// ID of the span opened by `event` as `kind` (`agent` or `tool`): the same
+/// event always gets the same ID, whatever else is exported with it
+fn span_id(kind: &str, event: &HookEvent, repeat: usize) -> String {
+    let key = format!(
+        "{kind}/{}/{}/{}/{}/{}/{repeat}",
+        event.session_id,
+        event.agent_id,
+        event.timestamp.to_rfc3339(),
+        event.event_type.as_str(),
+        event.tool_name.as_deref().unwrap_or(""),
+    );
+    hex_id(&key, 8)
+}
+
+/// The open agent span of one session/agent pair and its open tool spans
+struct OpenRun {
+    agent: Span,
+    tools: Vec<Span>,
+    last: DateTime<Utc>,
+    /// The last tool start and how many identical ones came before it, so
+    /// repeated events still get distinct IDs
+    last_start: Option<(String, usize)>,
+}
+
+impl OpenRun {
+    /// Close every open span at the last event seen, marking them incomplete
+    fn close(mut self, done: &mut Vec<Span>) {
+        for mut tool in self.tools.drain(..) {
+            tool.end = self.last;
+            tool.incomplete = true;
+            done.push(tool);
+        }
+        self.agent.end = self.last;
+        done.push(self.agent);
+    }
+}
+
+/// Pairs events into spans as they arrive, oldest first for each agent.
+/// Tool and error events outside an `agent_start` open an agent span of
+/// their own.
+#[derive(Default)]
+pub struct SpanBuilder {
+    open: HashMap<(String, String), OpenRun>,
+    done: Vec<Span>,
+}
+
+impl SpanBuilder {
+    pub fn push(&mut self, event: &HookEvent) {
+        let key = (event.session_id.clone(), event.agent_id.clone());
+        let trace_id = hex_id(&event.session_id, 16);
+
+        if event.event_type == EventType::AgentStart {
+            if let Some(mut run) = self.open.remove(&key) {
+                // Started again without ending
+                run.agent.incomplete = true;
+                run.close(&mut self.done);
+            }
+        }
+        let run = self.open.entry(key).or_insert_with(|| OpenRun {
+            agent: Span::new(
+                &trace_id,
+                span_id("agent", event, 0),
+                format!("agent {}", event.agent_id),
+                event,
+            ),
+            tools: Vec::new(),
+            last: event.timestamp,
+            last_start: None,
+        });
+        run.last = event.timestamp;
+
+        match event.event_type {
+            EventType::AgentStart => {}
+            EventType::AgentEnd => {
+                let key = (event.session_id.clone(), event.agent_id.clone());
+                if let Some(run) = self.open.remove(&key) {
+                    run.close(&mut self.done);
+                }
+            }
+            EventType::ToolStart => {
+                let tool = event
+                    .tool_name
+                    .clone()
+                    .unwrap_or_else(|| "tool".to_string());
+                let id = span_id("tool", event, 0);
+                let repeat = match &run.last_start {
+                    Some((last, n)) if *last == id => n + 1,
+                    _ => 0,
+                };
+                let span_id = if repeat == 0 {
+                    id.clone()
+                } else {
+                    span_id("tool", event, repeat)
+                };
+                run.last_start = Some((id, repeat));
+                let mut span = Span::new(&trace_id, span_id, tool.clone(), event);
+                span.parent_id = Some(run.agent.span_id.clone());
+                span.attributes.push(("tool.name", tool));
+                if let Some(path) = &event.file_path {
+                    span.attributes.push(("file.path", path.clone()));
+                }
+                run.tools.push(span);
+            }
+            EventType::ToolEnd => {
+                let name = event.tool_name.as_deref().unwrap_or("tool");
+                if let Some(idx) = run.tools.iter().rposition(|t| t.name == name) {
+                    let mut span = run.tools.remove(idx);
+                    span.end = event.timestamp;
+                    self.done.push(span);
+                }
+            }
+            EventType::Error => {
+                let target = run.tools.last_mut().unwrap_or(&mut run.agent);
+                target.errors.push(SpanEvent {
+                    time: event.timestamp,
+                    message: event.error_message.clone().unwrap_or_default(),
+                });
+            }
+        }
+    }
+
+    /// Spans finished so far, in the order they finished
+    pub fn take_done(&mut self) -> Vec<Span> {
+        std::mem::take(&mut self.done)
+    }
+
+    /// The remaining spans, with runs still open closed at their last event
+    pub fn finish(mut self) -> Vec<Span> {
+        let mut rest: Vec<OpenRun> = self.open.into_values().collect();
+        rest.sort_by_key(|run| run.agent.start);
+        for mut run in rest {
+            run.agent.incomplete = true;
+            run.close(&mut self.done);
+        }
+        self.done
+    }
+}
+
+/// Pair `events` into spans, in the order they finish
+pub fn build_spans(events: &[HookEvent]) -> Vec<Span> {
+    let mut sorted: Vec<&HookEvent> = events.iter().collect();
+    sorted.sort_by_key(|e| e.timestamp);
+    let mut builder = SpanBuilder::default();
+    for event in sorted {
+        builder.push(event);
+    }
+    builder.finish()
+}
+
+fn nanos(time: &DateTime<Utc>) -> String {
+    time.timestamp_nanos_opt().unwrap_or_default().to_string()
+}
+
+fn attributes(pairs: &[(&str, String)]) -> Value {
+    pairs
+        .iter()
+        .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+        .collect()
+}
+
+/// OTLP/JSON `ExportTraceServiceRequest` body for `spans`
+pub fn otlp_json(spans: &[Span]) -> Value {
+    let spans: Vec<Value> = spans
+        .iter()
+        .map(|span| {
+            let mut attrs = span.attributes.clone();
+            if span.incomplete {
+                attrs.push(("board.incomplete", "true".to_string()));
+            }
+            let status = match span.errors.last() {
+                Some(error) => json!({ "code": 2, "message": error.message }),
+                None => json!({}),
+            };
+            json!({
+                "traceId": span.trace_id,
+                "spanId": span.span_id,
+                "parentSpanId": span.parent_id.clone().unwrap_or_default(),
+                "name": span.name,
+                "kind": 1,
+                "startTimeUnixNano": nanos(&span.start),
+                "endTimeUnixNano": nanos(&span.end),
+                "attributes": attributes(&attrs),
+                "events": span.errors.iter().map(|error| json!({
+                    "timeUnixNano": nanos(&error.time),
+                    "name": "exception",
+                    "attributes": attributes(&[("exception.message", error.message.clone())]),
+                })).collect::<Vec<_>>(),
+                "status": status,
+            })
+        })
+        .collect();
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": attributes(&[("service.name", SERVICE_NAME.to_string())]) },
+            "scopeSpans": [{
+                "scope": { "name": SERVICE_NAME, "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// Traces URL of a collector given as `http://host:4318` or the full path
+pub fn traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with(TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}{TRACES_PATH}")
+    }
+}
+
+/// curl arguments posting a JSON body (read from stdin) to `url`
+fn curl_args(url: &str) -> Vec<String> {
+    [
+        "--silent",
+        "--show-error",
+        "--fail",
+        "--max-time",
+        &TIMEOUT_SECS.to_string(),
+        "--request",
+        "POST",
+        "--header",
+        "Content-Type: application/json",
+        "--data-binary",
+        "@-",
+        url,
+    ]
+    .iter()
+    .map(|a| a.to_string())
+    .collect()
+}
+
+/// POST `body` to `url` with curl and fail unless it answers 2xx
+fn post_json(url: &str, body: &str) -> anyhow::Result<()> {
+    let mut child = Command::new("curl")
+        .args(curl_args(url))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .context("cannot run curl")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(body.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        bail!("sending spans to {url} failed");
+    }
+    Ok(())
+}
+
+/// Spans and traces sent so far
+#[derive(Default)]
+struct Sent {
+    spans: usize,
+    traces: BTreeSet<String>,
+}
+
+impl Sent {
+    fn post(&mut self, url: &str, spans: Vec<Span>) -> anyhow::Result<()> {
+        if spans.is_empty() {
+            return Ok(());
+        }
+        post_json(url, &otlp_json(&spans).to_string())?;
+        self.spans += spans.len();
+        self.traces.extend(spans.into_iter().map(|s| s.trace_id));
+        Ok(())
+    }
+}
+
+/// Send the events in `dirs` (optionally one session's) to the collector
+pub fn run(dirs: &[PathBuf], endpoint: &str, session: Option<&str>) -> anyhow::Result<()> {
+    let url = traces_url(endpoint);
+    let mut builder = SpanBuilder::default();
+    let mut sent = Sent::default();
+    let mut result = Ok(());
+    loader::for_each_batch(dirs, |mut batch| {
+        if result.is_err() {
+            return;
+        }
+        batch.retain(|e| session.map_or(true, |session| e.session_id == session));
+        batch.sort_by_key(|e| e.timestamp);
+        for event in &batch {
+            builder.push(event);
+        }
+        if builder.done.len() >= BATCH_SPANS {
+            result = sent.post(&url, builder.take_done());
+        }
+    });
+    result?;
+    sent.post(&url, builder.finish())?;
+
+    if sent.spans == 0 {
+        println!("No agent or tool events to export");
+        return Ok(());
+    }
+    println!(
+        "Exported {} spans in {} trace(s) to {url}",
+        sent.spans,
+        sent.traces.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    fn event(kind: &str, secs: u32, agent: &str, extra: &str) -> String {
+        format!(
+            r#"{{"event_type":"{kind}","timestamp":"2026-02-08T10:00:{secs:02}Z","agent_id":"{agent}","task_id":"P1-T1","session_id":"s1"{extra}}}"#
+        )
+    }
+
+    #[test]
+    fn events_pair_into_agent_and_tool_spans() {
+        let lines = [
+            event("agent_start", 0, "a", ""),
+            event(
+                "tool_start",
+                1,
+                "a",
+                r#","tool_name":"Edit","file_path":"src/x.rs""#,
+            ),
+            event(
+                "error",
+                2,
+                "a",
+                r#","tool_name":"Edit","error_message":"boom""#,
+            ),
+            event("tool_end", 3, "a", r#","tool_name":"Edit""#),
+            event("agent_end", 4, "a", ""),
+            event("tool_start", 5, "b", r#","tool_name":"Bash""#),
+        ]
+        .join("\n");
+        let spans = build_spans(&parse_hook_events(&lines).events);
+        assert_eq!(spans.len(), 4);
+
+        let (tool, agent) = (&spans[0], &spans[1]);
+        assert_eq!(tool.name, "Edit");
+        assert_eq!(tool.parent_id.as_deref(), Some(agent.span_id.as_str()));
+        assert_eq!(tool.trace_id, agent.trace_id);
+        assert_eq!((tool.end - tool.start).num_seconds(), 2);
+        assert_eq!(tool.errors[0].message, "boom");
+        assert!(tool
+            .attributes
+            .contains(&("file.path", "src/x.rs".to_string())));
+        assert_eq!(agent.name, "agent a");
+        assert!(!agent.incomplete && agent.errors.is_empty());
+
+        // Agent b never ended: its spans close at its last event
+        assert!(spans[2].incomplete && spans[3].incomplete);
+        assert_eq!(spans[3].name, "agent b");
+        assert_eq!(build_spans(&parse_hook_events(&lines).events), spans);
+    }
+
+    #[test]
+    fn otlp_body_and_url() {
+        let lines = [
+            event("agent_start", 0, "a", ""),
+            event("error", 1, "a", r#","error_message":"bad""#),
+        ]
+        .join("\n");
+        let spans = build_spans(&parse_hook_events(&lines).events);
+        let body = otlp_json(&spans);
+        let span = &body["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["traceId"].as_str().unwrap().len(), 32);
+        assert_eq!(span["spanId"].as_str().unwrap().len(), 16);
+        assert_eq!(span["startTimeUnixNano"], "1770544800000000000");
+        assert_eq!(span["status"]["code"], 2);
+        assert_eq!(span["events"][0]["name"], "exception");
+
+        assert_eq!(
+            traces_url("http://localhost:4318/"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(traces_url("http://c/v1/traces"), "http://c/v1/traces");
+    }
+
+    #[test]
+    fn span_ids_follow_the_event_not_the_export() {
+        let lines = [
+            event("agent_start", 0, "a", ""),
+            event("tool_start", 1, "a", r#","tool_name":"Bash""#),
+            event("tool_end", 2, "a", r#","tool_name":"Bash""#),
+            event("tool_start", 2, "a", r#","tool_name":"Bash""#),
+        ]
+        .join("\n");
+        let other = event("agent_start", 0, "b", "").replace("\"s1\"", "\"s2\"");
+        let alone = build_spans(&parse_hook_events(&lines).events);
+        let mixed = build_spans(&parse_hook_events(&format!("{other}\n{lines}")).events);
+        let ids = |spans: &[Span]| -> BTreeSet<String> {
+            spans
+                .iter()
+                .filter(|s| s.trace_id == hex_id("s1", 16))
+                .map(|s| s.span_id.clone())
+                .collect()
+        };
+        assert_eq!(ids(&alone).len(), 3);
+        assert_eq!(ids(&alone), ids(&mixed));
+    }
+
+    #[test]
+    fn identical_tool_starts_get_distinct_ids() {
+        let start = event("tool_start", 1, "a", r#","tool_name":"Read""#);
+        let lines = [event("agent_start", 0, "a", ""), start.clone(), start].join("\n");
+        let spans = build_spans(&parse_hook_events(&lines).events);
+        assert_eq!(spans.len(), 3);
+        assert_ne!(spans[0].span_id, spans[1].span_id);
+    }
+
+    #[test]
+    fn spans_are_posted_with_curl() {
+        let args = curl_args("https://otel.example.com/v1/traces");
+        assert!(args.windows(2).any(|w| w == ["--max-time", "10"]));
+        assert!(args.contains(&"--fail".to_string()));
+        assert!(args.windows(2).any(|w| w == ["--data-binary", "@-"]));
+        assert_eq!(args.last().unwrap(), "https://otel.example.com/v1/traces");
+    }
+}
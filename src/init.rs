@@ -4,33 +4,194 @@
 //! 1. Creates `~/.claude/dashboard/` and `~/.claude/hooks/` directories
 //! 2. Deploys the embedded `event-logger.js` to `~/.claude/hooks/`
 //! 3. Patches `~/.claude/settings.json` with Pre/PostToolUse hook entries
+//!
+//! With `--project` the hook script and settings go to `.claude/` at the
+//! root of the current git repository instead, with a hook command relative
+//! to that root, so the setup can be committed and shared. Events are still
+//! logged to `~/.claude/dashboard/`.
+//!
+//! The matcher, timeout and events directory of the hook come from
+//! [`HookOptions`] (`--matcher`, `--timeout`, `--events-dir` or `[init]` in
+//! the config) and are written into both the settings entry and the
+//! deployed script.
+//!
+//! Before an existing settings file is changed it is copied to a
+//! timestamped `<name>.bak-<YYYYmmdd-HHMMSS>` next to it; `--rollback`
+//! puts the most recent copy back.
 
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
 use serde_json::Value;
 
+/// Why `init` stopped; every step before it has been done
+#[derive(Debug, thiserror::Error)]
+pub enum InitError {
+    #[error("could not determine home directory (HOME or USERPROFILE)")]
+    NoHome,
+    #[error("{} is not inside a git repository", dir.display())]
+    NotInRepo { dir: PathBuf },
+    #[error("failed to create directory {}: {source}", path.display())]
+    CreateDir { path: PathBuf, source: io::Error },
+    #[error("failed to write hook script {}: {source}", path.display())]
+    WriteHook { path: PathBuf, source: io::Error },
+    #[error("failed to read {}: {source}", path.display())]
+    ReadSettings { path: PathBuf, source: io::Error },
+    #[error("failed to parse {}: {source}", path.display())]
+    ParseSettings {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    /// A settings.json entry has a shape the patch cannot extend
+    #[error("settings.json {key} is not {expected}")]
+    UnexpectedShape { key: String, expected: &'static str },
+    #[error("failed to write {}: {source}", path.display())]
+    WriteSettings { path: PathBuf, source: io::Error },
+    #[error("failed to back up settings to {}: {source}", path.display())]
+    Backup { path: PathBuf, source: io::Error },
+    #[error("no backup of {} to roll back to", path.display())]
+    NoBackup { path: PathBuf },
+    #[error("failed to restore {}: {source}", path.display())]
+    Restore { path: PathBuf, source: io::Error },
+}
+
+type Result<T> = std::result::Result<T, InitError>;
+
 /// The standalone event-logger.js embedded at compile time.
 const EVENT_LOGGER_JS: &str = include_str!("../hooks/event-logger.js");
 
-/// The hook matcher pattern for tool events.
-const HOOK_MATCHER: &str = "Task|Edit|Write|Read|Bash|Grep|Glob";
+/// The default hook matcher pattern for tool events.
+pub const HOOK_MATCHER: &str = "Task|Edit|Write|Read|Bash|Grep|Glob";
 
 /// The hook command template.
 const HOOK_COMMAND: &str = "node \"${HOME}/.claude/hooks/event-logger.js\"";
 
-/// Hook timeout in seconds.
-const HOOK_TIMEOUT: u64 = 3;
+/// The hook command for `home`. Windows shells do not expand `${HOME}`, so
+/// there the script's absolute path is spelled out, with forward slashes
+/// that both cmd and Git Bash pass to node unchanged.
+fn home_hook_command(home: &Path, windows: bool) -> String {
+    if !windows {
+        return HOOK_COMMAND.to_string();
+    }
+    let script = home.join(".claude").join("hooks").join("event-logger.js");
+    format!("node \"{}\"", script.to_string_lossy().replace('\\', "/"))
+}
+
+/// The hook command of a project setup, relative to the repository root
+/// Claude Code runs hooks from
+const PROJECT_HOOK_COMMAND: &str = "node .claude/hooks/event-logger.js";
+
+/// Default hook timeout in seconds.
+pub const HOOK_TIMEOUT: u64 = 3;
+
+/// Which tool calls the hook runs on and where it logs them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookOptions {
+    /// Claude Code matcher of the tools to capture, e.g. `Task|Edit|Bash`
+    pub matcher: String,
+    /// Seconds Claude Code waits for the hook
+    pub timeout: u64,
+    /// Directory the hook appends events to; `None` means
+    /// `~/.claude/dashboard`
+    pub events_dir: Option<PathBuf>,
+}
+
+impl Default for HookOptions {
+    fn default() -> Self {
+        Self {
+            matcher: HOOK_MATCHER.to_string(),
+            timeout: HOOK_TIMEOUT,
+            events_dir: None,
+        }
+    }
+}
+
+/// Where `init` installs the hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InitTarget {
+    /// `~/.claude`, for every project of the user
+    #[default]
+    Home,
+    /// `.claude/settings.json` of the current repository, to be committed
+    Project,
+    /// `.claude/settings.local.json` of the current repository, kept out
+    /// of version control
+    ProjectLocal,
+}
+
+/// Settings directory, settings file name and hook command of `target`
+fn locate(
+    target: InitTarget,
+    home: &Path,
+    repo: Option<&Path>,
+) -> Result<(PathBuf, &'static str, String)> {
+    match target {
+        InitTarget::Home => Ok((
+            home.join(".claude"),
+            "settings.json",
+            home_hook_command(home, cfg!(windows)),
+        )),
+        InitTarget::Project | InitTarget::ProjectLocal => {
+            let root = repo.ok_or_else(|| InitError::NotInRepo {
+                dir: std::env::current_dir().unwrap_or_default(),
+            })?;
+            let settings_name = if target == InitTarget::Project {
+                "settings.json"
+            } else {
+                "settings.local.json"
+            };
+            Ok((
+                root.join(".claude"),
+                settings_name,
+                PROJECT_HOOK_COMMAND.to_string(),
+            ))
+        }
+    }
+}
 
 /// Run the init command: create dirs, deploy hook script, patch settings.
-pub fn run_init() -> Result<()> {
+pub fn run_init(target: InitTarget, options: &HookOptions) -> Result<()> {
     let home = home_dir()?;
-    let claude_dir = home.join(".claude");
-    let dashboard_dir = claude_dir.join("dashboard");
+    let repo = repo_root(&std::env::current_dir().unwrap_or_default());
+
+    let (claude_dir, settings_name, command) = locate(target, &home, repo.as_deref())?;
+    install(&home, &claude_dir, settings_name, &command, options)?;
+
+    println!();
+    match options.events_dir {
+        Some(ref dir) => println!(
+            "Setup complete! Run `simple-claude-board --events {}` to start the dashboard.",
+            dir.display()
+        ),
+        None => println!("Setup complete! Run `simple-claude-board` to start the dashboard."),
+    }
+    if let Some(root) = repo.filter(|_| target == InitTarget::Home) {
+        println!(
+            "{} is a git repository: `simple-claude-board init --project` sets the hook up \
+             there instead, to commit and share with the team.",
+            root.display()
+        );
+    }
+    Ok(())
+}
+
+/// The three setup steps, with the hook script and `settings_name` in
+/// `claude_dir`
+fn install(
+    home: &Path,
+    claude_dir: &Path,
+    settings_name: &str,
+    command: &str,
+    options: &HookOptions,
+) -> Result<()> {
+    let dashboard_dir = match options.events_dir {
+        Some(ref dir) => dir.clone(),
+        None => home.join(".claude").join("dashboard"),
+    };
     let hooks_dir = claude_dir.join("hooks");
     let hook_file = hooks_dir.join("event-logger.js");
-    let settings_file = claude_dir.join("settings.json");
+    let settings_file = claude_dir.join(settings_name);
 
     // Step 1: Create directories
     println!("[1/3] Creating directories...");
@@ -39,72 +200,161 @@ pub fn run_init() -> Result<()> {
 
     // Step 2: Deploy event-logger.js
     println!("[2/3] Deploying event-logger.js...");
-    deploy_hook_script(&hook_file)?;
+    deploy_hook_script(&hook_file, options)?;
 
     // Step 3: Patch settings.json
-    println!("[3/3] Patching settings.json...");
-    patch_settings(&settings_file)?;
+    println!("[3/3] Patching {settings_name}...");
+    patch_settings(&settings_file, &build_hook_entry(command, options))
+}
 
-    println!();
-    println!("Setup complete! Run `simple-claude-board` to start the dashboard.");
+/// Restore the settings file of `target` from its most recent backup
+pub fn run_rollback(target: InitTarget) -> Result<()> {
+    let home = home_dir()?;
+    let repo = repo_root(&std::env::current_dir().unwrap_or_default());
+    let (claude_dir, settings_name, _) = locate(target, &home, repo.as_deref())?;
+    let settings_file = claude_dir.join(settings_name);
+    let backup = restore_latest_backup(&settings_file)?;
+    println!(
+        "Restored {} from {}",
+        settings_file.display(),
+        backup.display()
+    );
     Ok(())
 }
 
+/// Prefix of the backups of `path`: `settings.json.bak-`
+fn backup_prefix(path: &Path) -> String {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    format!("{name}.bak-")
+}
+
+/// Copy `path` to a timestamped backup beside it; an existing backup from
+/// the same second is kept, being the older state
+fn back_up(path: &Path) -> Result<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let backup = path.with_file_name(format!("{}{stamp}", backup_prefix(path)));
+    if !backup.exists() {
+        fs::copy(path, &backup).map_err(|source| InitError::Backup {
+            path: backup.clone(),
+            source,
+        })?;
+    }
+    Ok(backup)
+}
+
+/// Move the most recent backup of `path` back in place; returns the backup
+/// used, so a second rollback goes one further back
+fn restore_latest_backup(path: &Path) -> Result<PathBuf> {
+    let prefix = backup_prefix(path);
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let latest = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| {
+            p.file_name()
+                .is_some_and(|n| n.to_string_lossy().starts_with(&prefix))
+        })
+        .max()
+        .ok_or_else(|| InitError::NoBackup {
+            path: path.to_path_buf(),
+        })?;
+    fs::rename(&latest, path).map_err(|source| InitError::Restore {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(latest)
+}
+
+/// Top of the git repository (or worktree) containing `dir`
+fn repo_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|d| d.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
 /// Get the user's home directory.
 fn home_dir() -> Result<PathBuf> {
     std::env::var("HOME")
         .or_else(|_| std::env::var("USERPROFILE"))
         .map(PathBuf::from)
-        .context("Could not determine home directory (HOME or USERPROFILE)")
+        .map_err(|_| InitError::NoHome)
 }
 
 /// Create a directory if it does not already exist.
-fn create_dir_if_missing(path: &PathBuf) -> Result<()> {
+fn create_dir_if_missing(path: &Path) -> Result<()> {
     if path.is_dir() {
         println!("  Already exists: {}", path.display());
     } else {
-        fs::create_dir_all(path)
-            .with_context(|| format!("Failed to create directory: {}", path.display()))?;
+        fs::create_dir_all(path).map_err(|source| InitError::CreateDir {
+            path: path.to_path_buf(),
+            source,
+        })?;
         println!("  Created: {}", path.display());
     }
     Ok(())
 }
 
+/// The embedded event-logger.js with `options` filled in: the tools it
+/// tracks follow the matcher, and it logs to the events directory
+fn render_event_logger(options: &HookOptions) -> String {
+    let tracked = Value::from(format!("^(?:{})$", options.matcher));
+    let events_dir = options
+        .events_dir
+        .as_ref()
+        .map(|dir| Value::from(dir.to_string_lossy()));
+    let mut script = String::with_capacity(EVENT_LOGGER_JS.len());
+    for line in EVENT_LOGGER_JS.lines() {
+        if line.starts_with("const TRACKED_TOOLS = ") {
+            script.push_str(&format!("const TRACKED_TOOLS = new RegExp({tracked});"));
+        } else if let (true, Some(dir)) = (line.starts_with("const EVENTS_DIR = "), &events_dir) {
+            script.push_str(&format!("const EVENTS_DIR = {dir};"));
+        } else {
+            script.push_str(line);
+        }
+        script.push('\n');
+    }
+    script
+}
+
 /// Write the embedded event-logger.js to disk.
-fn deploy_hook_script(path: &PathBuf) -> Result<()> {
+fn deploy_hook_script(path: &PathBuf, options: &HookOptions) -> Result<()> {
     if path.is_file() {
         println!("  Overwriting: {}", path.display());
     } else {
         println!("  Writing: {}", path.display());
     }
-    fs::write(path, EVENT_LOGGER_JS)
-        .with_context(|| format!("Failed to write hook script: {}", path.display()))?;
+    let write_hook = |source| InitError::WriteHook {
+        path: path.clone(),
+        source,
+    };
+    fs::write(path, render_event_logger(options)).map_err(write_hook)?;
     // Make executable on Unix
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
         let perms = fs::Permissions::from_mode(0o755);
-        fs::set_permissions(path, perms)
-            .with_context(|| format!("Failed to set permissions: {}", path.display()))?;
+        fs::set_permissions(path, perms).map_err(write_hook)?;
     }
     Ok(())
 }
 
 /// Build the hook entry JSON value.
-fn build_hook_entry() -> Value {
+fn build_hook_entry(command: &str, options: &HookOptions) -> Value {
     serde_json::json!({
-        "matcher": HOOK_MATCHER,
+        "matcher": options.matcher,
         "hooks": [{
             "type": "command",
-            "command": HOOK_COMMAND,
-            "timeout": HOOK_TIMEOUT
+            "command": command,
+            "timeout": options.timeout
         }]
     })
 }
 
-/// Check if a hook array already contains an event-logger entry.
-fn has_event_logger_entry(arr: &[Value]) -> bool {
-    arr.iter().any(|entry| {
+/// Index of the event-logger entry in a hook array
+fn event_logger_position(arr: &[Value]) -> Option<usize> {
+    arr.iter().position(|entry| {
         entry
             .get("hooks")
             .and_then(|h| h.as_array())
@@ -119,21 +369,29 @@ fn has_event_logger_entry(arr: &[Value]) -> bool {
     })
 }
 
-/// Read, patch, and write settings.json.
-fn patch_settings(path: &PathBuf) -> Result<()> {
+/// Read, patch, and write settings.json with the hook `entry`; an
+/// event-logger entry with other options is replaced.
+fn patch_settings(path: &PathBuf, entry: &Value) -> Result<()> {
     // Read existing settings or start with empty object
     let mut settings: Value = if path.is_file() {
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read: {}", path.display()))?;
-        serde_json::from_str(&content)
-            .with_context(|| format!("Failed to parse: {}", path.display()))?
+        let content = fs::read_to_string(path).map_err(|source| InitError::ReadSettings {
+            path: path.clone(),
+            source,
+        })?;
+        serde_json::from_str(&content).map_err(|source| InitError::ParseSettings {
+            path: path.clone(),
+            source,
+        })?
     } else {
         serde_json::json!({})
     };
 
     let root = settings
         .as_object_mut()
-        .context("settings.json root is not an object")?;
+        .ok_or_else(|| InitError::UnexpectedShape {
+            key: "root".to_string(),
+            expected: "an object",
+        })?;
 
     // Ensure "hooks" object exists
     if !root.contains_key("hooks") {
@@ -142,9 +400,11 @@ fn patch_settings(path: &PathBuf) -> Result<()> {
     let hooks = root
         .get_mut("hooks")
         .and_then(|v| v.as_object_mut())
-        .context("settings.json 'hooks' is not an object")?;
+        .ok_or_else(|| InitError::UnexpectedShape {
+            key: "'hooks'".to_string(),
+            expected: "an object",
+        })?;
 
-    let entry = build_hook_entry();
     let mut patched = false;
 
     for key in &["PreToolUse", "PostToolUse"] {
@@ -154,22 +414,39 @@ fn patch_settings(path: &PathBuf) -> Result<()> {
         let arr = hooks
             .get_mut(*key)
             .and_then(|v| v.as_array_mut())
-            .with_context(|| format!("settings.json 'hooks.{key}' is not an array"))?;
-
-        if has_event_logger_entry(arr) {
-            println!("  hooks.{key}: event-logger already registered");
-        } else {
-            arr.push(entry.clone());
-            println!("  hooks.{key}: added event-logger entry");
-            patched = true;
+            .ok_or_else(|| InitError::UnexpectedShape {
+                key: format!("'hooks.{key}'"),
+                expected: "an array",
+            })?;
+
+        match event_logger_position(arr) {
+            Some(i) if arr[i] == *entry => {
+                println!("  hooks.{key}: event-logger already registered");
+            }
+            Some(i) => {
+                arr[i] = entry.clone();
+                println!("  hooks.{key}: updated event-logger entry");
+                patched = true;
+            }
+            None => {
+                arr.push(entry.clone());
+                println!("  hooks.{key}: added event-logger entry");
+                patched = true;
+            }
         }
     }
 
     if patched {
-        let pretty =
-            serde_json::to_string_pretty(&settings).context("Failed to serialize settings.json")?;
-        fs::write(path, pretty.as_bytes())
-            .with_context(|| format!("Failed to write: {}", path.display()))?;
+        if path.is_file() {
+            let backup = back_up(path)?;
+            println!("  Backed up: {}", backup.display());
+        }
+        // A JSON value always serializes
+        let pretty = serde_json::to_string_pretty(&settings).unwrap_or_default();
+        fs::write(path, pretty.as_bytes()).map_err(|source| InitError::WriteSettings {
+            path: path.clone(),
+            source,
+        })?;
         println!("  Saved: {}", path.display());
     } else {
         println!("  No changes needed");
@@ -182,9 +459,17 @@ fn patch_settings(path: &PathBuf) -> Result<()> {
 mod tests {
     use super::*;
 
+    fn default_entry() -> Value {
+        build_hook_entry(HOOK_COMMAND, &HookOptions::default())
+    }
+
+    fn has_event_logger_entry(arr: &[Value]) -> bool {
+        event_logger_position(arr).is_some()
+    }
+
     #[test]
     fn test_build_hook_entry_shape() {
-        let entry = build_hook_entry();
+        let entry = default_entry();
         assert_eq!(entry["matcher"], "Task|Edit|Write|Read|Bash|Grep|Glob");
         let hooks = entry["hooks"].as_array().expect("hooks is array");
         assert_eq!(hooks.len(), 1);
@@ -196,9 +481,19 @@ mod tests {
         assert_eq!(hooks[0]["timeout"], 3);
     }
 
+    #[test]
+    fn test_home_hook_command_spells_out_the_path_on_windows() {
+        let home = Path::new("/home/me");
+        assert_eq!(home_hook_command(home, false), HOOK_COMMAND);
+        let windows = home_hook_command(Path::new(r"C:\Users\me"), true);
+        assert!(windows.starts_with("node \"C:/Users/me/.claude"));
+        assert!(windows.ends_with("event-logger.js\""));
+        assert!(!windows.contains('\\'));
+    }
+
     #[test]
     fn test_has_event_logger_entry_detects_existing() {
-        let entry = build_hook_entry();
+        let entry = default_entry();
         assert!(has_event_logger_entry(&[entry]));
     }
 
@@ -221,7 +516,7 @@ mod tests {
         let dir = tempfile::tempdir().expect("tempdir");
         let settings_path = dir.path().join("settings.json");
 
-        patch_settings(&settings_path).expect("patch succeeds");
+        patch_settings(&settings_path, &default_entry()).expect("patch succeeds");
 
         let content = fs::read_to_string(&settings_path).expect("read");
         let val: Value = serde_json::from_str(&content).expect("parse");
@@ -254,7 +549,7 @@ mod tests {
         )
         .expect("write");
 
-        patch_settings(&settings_path).expect("patch succeeds");
+        patch_settings(&settings_path, &default_entry()).expect("patch succeeds");
 
         let content = fs::read_to_string(&settings_path).expect("read");
         let val: Value = serde_json::from_str(&content).expect("parse");
@@ -269,15 +564,123 @@ mod tests {
         assert!(has_event_logger_entry(pre));
     }
 
+    #[test]
+    fn test_patch_settings_rejects_unexpected_shapes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let settings_path = dir.path().join("settings.json");
+
+        fs::write(&settings_path, r#"{"hooks": {"PreToolUse": {}}}"#).expect("write");
+        assert!(matches!(
+            patch_settings(&settings_path, &default_entry()),
+            Err(InitError::UnexpectedShape { ref key, expected: "an array" })
+                if key == "'hooks.PreToolUse'"
+        ));
+
+        fs::write(&settings_path, "{").expect("write");
+        assert!(matches!(
+            patch_settings(&settings_path, &default_entry()),
+            Err(InitError::ParseSettings { .. })
+        ));
+    }
+
+    #[test]
+    fn test_project_install_uses_relative_hook_path() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().join("repo");
+        fs::create_dir_all(root.join(".git")).expect("mkdir");
+        let nested = root.join("src").join("app");
+        fs::create_dir_all(&nested).expect("mkdir");
+        assert_eq!(repo_root(&nested), Some(root.clone()));
+
+        let claude_dir = root.join(".claude");
+        install(
+            dir.path(),
+            &claude_dir,
+            "settings.local.json",
+            PROJECT_HOOK_COMMAND,
+            &HookOptions::default(),
+        )
+        .expect("install succeeds");
+
+        assert!(claude_dir.join("hooks/event-logger.js").is_file());
+        assert!(!claude_dir.join("settings.json").exists());
+        let content = fs::read_to_string(claude_dir.join("settings.local.json")).expect("read");
+        let val: Value = serde_json::from_str(&content).expect("parse");
+        assert_eq!(
+            val["hooks"]["PreToolUse"][0]["hooks"][0]["command"],
+            "node .claude/hooks/event-logger.js"
+        );
+    }
+
+    #[test]
+    fn test_patch_backs_up_and_rollback_restores() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let settings_path = dir.path().join("settings.json");
+        assert!(matches!(
+            restore_latest_backup(&settings_path),
+            Err(InitError::NoBackup { .. })
+        ));
+
+        // A fresh file has nothing to back up
+        patch_settings(&settings_path, &default_entry()).expect("patch succeeds");
+        assert!(restore_latest_backup(&settings_path).is_err());
+
+        let original = r#"{"model": "opus"}"#;
+        fs::write(&settings_path, original).expect("write");
+        patch_settings(&settings_path, &default_entry()).expect("patch succeeds");
+        assert_ne!(fs::read_to_string(&settings_path).unwrap(), original);
+
+        let backup = restore_latest_backup(&settings_path).expect("rollback");
+        assert!(!backup.exists());
+        assert_eq!(fs::read_to_string(&settings_path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_options_reach_the_entry_and_the_script() {
+        let options = HookOptions {
+            matcher: "Task|Bash".to_string(),
+            timeout: 10,
+            events_dir: Some(PathBuf::from("/var/log/claude")),
+        };
+        let entry = build_hook_entry(HOOK_COMMAND, &options);
+        assert_eq!(entry["matcher"], "Task|Bash");
+        assert_eq!(entry["hooks"][0]["timeout"], 10);
+
+        let script = render_event_logger(&options);
+        assert!(script.contains("const TRACKED_TOOLS = new RegExp(\"^(?:Task|Bash)$\");"));
+        assert!(script.contains("const EVENTS_DIR = \"/var/log/claude\";"));
+        assert_eq!(script.lines().count(), EVENT_LOGGER_JS.lines().count());
+
+        // Re-running with other options updates the entry in place
+        let dir = tempfile::tempdir().expect("tempdir");
+        let settings_path = dir.path().join("settings.json");
+        patch_settings(&settings_path, &default_entry()).expect("first patch");
+        patch_settings(&settings_path, &entry).expect("second patch");
+        let content = fs::read_to_string(&settings_path).expect("read");
+        let val: Value = serde_json::from_str(&content).expect("parse");
+        let pre = val["hooks"]["PreToolUse"].as_array().expect("array");
+        assert_eq!(pre.len(), 1);
+        assert_eq!(pre[0]["matcher"], "Task|Bash");
+    }
+
+    #[test]
+    fn test_default_script_is_unchanged_apart_from_the_matcher() {
+        let script = render_event_logger(&HookOptions::default());
+        assert!(
+            script.contains("const EVENTS_DIR = path.join(os.homedir(), '.claude', 'dashboard');")
+        );
+        assert!(script.contains("new RegExp(\"^(?:Task|Edit|Write|Read|Bash|Grep|Glob)$\")"));
+    }
+
     #[test]
     fn test_patch_settings_idempotent() {
         let dir = tempfile::tempdir().expect("tempdir");
         let settings_path = dir.path().join("settings.json");
 
-        patch_settings(&settings_path).expect("first patch");
+        patch_settings(&settings_path, &default_entry()).expect("first patch");
         let first = fs::read_to_string(&settings_path).expect("read");
 
-        patch_settings(&settings_path).expect("second patch");
+        patch_settings(&settings_path, &default_entry()).expect("second patch");
         let second = fs::read_to_string(&settings_path).expect("read");
 
         // Content should be identical (no duplicate entries)
@@ -1,6 +1,66 @@
+//! Claude Code orchestration dashboard
+//!
+//! The crate splits into a headless core and the frontends built on it.
+//! The core parses TASKS.md and hook event logs ([`data`]), ingests them
+//! from disk as they grow ([`data::loader`], [`data::tail`],
+//! [`data::watcher`]), aggregates them into a [`DashboardState`] and derives
+//! metrics, forecasts and error analysis from it ([`analysis`]). It needs no
+//! terminal libraries, so it can be embedded in a bot or another service
+//! with `default-features = false`:
+//!
+//! ```
+//! use simple_claude_board::{parse_hook_events, DashboardState};
+//!
+//! let mut state = DashboardState::from_tasks_content("# Phase 1: Core\n### [ ] P1-T1: Parser\n")?;
+//! let line = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#;
+//! state.update_from_events(&parse_hook_events(line).events);
+//! assert_eq!(state.active_agents(), 1);
+//! # Ok::<(), simple_claude_board::TasksParseError>(())
+//! ```
+//!
+//! The `tui` feature (on by default) adds the terminal dashboard, the web
+//! view and the MCP server, which all drive an [`app::App`] over that state.
+
+pub mod actions;
+pub mod alerts;
 pub mod analysis;
+#[cfg(feature = "tui")]
 pub mod app;
+pub mod budget;
+#[cfg(feature = "tui")]
+pub mod clipboard;
+pub mod collector;
+pub mod config;
 pub mod data;
+pub mod digest;
+pub mod editor;
+#[cfg(feature = "tui")]
 pub mod event;
+pub mod export;
 pub mod init;
+#[cfg(feature = "tui")]
+pub mod mcp;
+pub mod pager;
+#[cfg(feature = "tui")]
+pub mod recording;
+pub mod reminders;
+pub mod statusline;
+pub mod stream;
+pub mod sync;
+#[cfg(feature = "tui")]
 pub mod ui;
+#[cfg(feature = "tui")]
+pub mod view_state;
+#[cfg(feature = "tui")]
+pub mod web;
+pub mod webhooks;
+
+pub use analysis::metrics::Metrics;
+pub use config::Config;
+pub use data::hook_parser::{parse_hook_events, EventType, HookEvent, HookIngestError};
+pub use data::state::DashboardState;
+pub use data::tasks_parser::{
+    parse_tasks_md, ParsedPhase, ParsedTask, TaskStatus, TasksParseError,
+};
+pub use data::watcher::WatchError;
+pub use init::InitError;
@@ -1,27 +1,40 @@
 use std::io;
-use std::path::PathBuf;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use crossterm::{
+    event::{DisableFocusChange, EnableFocusChange},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use tokio::sync::mpsc;
 
+use simple_claude_board::actions::{self, ShellRequest};
+use simple_claude_board::alerts;
+use simple_claude_board::analysis::rules;
 use simple_claude_board::app::App;
+use simple_claude_board::clipboard;
+use simple_claude_board::collector::ServiceKind;
+use simple_claude_board::config::{self, Config};
+use simple_claude_board::data::loader;
+use simple_claude_board::data::remote::{self, Remote};
+use simple_claude_board::data::source::{self, DataSource, FilesSource, SourceSpec, Update};
 use simple_claude_board::data::state::DashboardState;
-use simple_claude_board::data::watcher::{self, FileChange, WatchConfig};
-use simple_claude_board::event::{key_to_action, poll_event, Action, AppEvent};
-use simple_claude_board::ui::claude_output::AgentPanel;
-use simple_claude_board::ui::detail::DetailWidget;
-use simple_claude_board::ui::gantt::GanttWidget;
-use simple_claude_board::ui::help::HelpOverlay;
-use simple_claude_board::ui::layout::{DashboardLayout, FocusedPane};
-use simple_claude_board::ui::retry_modal::RetryModal;
-use simple_claude_board::ui::statusbar::StatusBar;
+use simple_claude_board::data::store::{self, Store};
+use simple_claude_board::data::watcher::{FileChange, WatchConfig};
+use simple_claude_board::editor::{self, EditorRequest};
+use simple_claude_board::event::{AppEvent, Events};
+use simple_claude_board::export::{EventFormat, ExportFormat};
+use simple_claude_board::init::{self, InitTarget};
+use simple_claude_board::pager;
+use simple_claude_board::recording::{Player, Recorder, Recording};
+use simple_claude_board::statusline;
+use simple_claude_board::ui::{self, theme::ThemeName};
+use simple_claude_board::web;
 
 /// Claude Code orchestration TUI dashboard
 #[derive(Parser, Debug)]
@@ -41,6 +54,25 @@ struct Cli {
     /// Path to dashboard JSONL events directory (default: ~/.claude/dashboard)
     #[arg(long, global = true)]
     events: Option<String>,
+
+    /// Stream events from a remote directory over SSH instead of --events
+    /// (user@host:/path), for the dashboard, `serve`, `mcp` and `stream`
+    #[arg(long, global = true, value_name = "USER@HOST:PATH")]
+    remote: Option<Remote>,
+
+    /// Record TASKS.md snapshots and ingested events with their timing to
+    /// this file, for `replay` (dashboard, `serve` and `mcp`)
+    #[arg(long, global = true, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Also take hook events as JSONL from `stdin` or `tcp:ADDR`, for the
+    /// dashboard, `serve` and `mcp`; repeatable
+    #[arg(long, global = true, value_name = "SOURCE")]
+    ingest: Vec<SourceSpec>,
+
+    /// Color theme: default, high-contrast or deuteranopia (overrides board.toml)
+    #[arg(long, global = true)]
+    theme: Option<ThemeName>,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -48,7 +80,127 @@ enum Commands {
     /// Watch TASKS.md and Hook events in real-time (default)
     Watch,
     /// Initialize configuration
-    Init,
+    Init {
+        /// Set the hook up in `.claude/` of the current git repository, to
+        /// commit and share, instead of in ~/.claude
+        #[arg(long)]
+        project: bool,
+        /// With --project, patch `.claude/settings.local.json`, which stays
+        /// out of version control
+        #[arg(long, requires = "project")]
+        local: bool,
+        /// Restore the settings file from its most recent backup instead
+        #[arg(long)]
+        rollback: bool,
+        /// Tools the hook captures, as a Claude Code matcher (default from
+        /// `[init]`, else Task|Edit|Write|Read|Bash|Grep|Glob)
+        #[arg(long, value_parser = clap::builder::NonEmptyStringValueParser::new())]
+        matcher: Option<String>,
+        /// Seconds Claude Code waits for the hook (default 3)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+        /// Directory the hook logs events to (default ~/.claude/dashboard)
+        #[arg(long)]
+        events_dir: Option<PathBuf>,
+    },
+    /// Serve a read-only web mirror of the dashboard with live updates
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        bind: SocketAddr,
+    },
+    /// Serve the dashboard state as MCP tools and resources on stdin/stdout
+    Mcp,
+    /// Export tasks with their recorded timings
+    Export {
+        /// File format: ics
+        #[arg(long, default_value = "ics")]
+        format: ExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Export the raw hook event history for spreadsheet analysis
+    ExportEvents {
+        /// File format: csv
+        #[arg(long, default_value = "csv")]
+        format: EventFormat,
+        /// Only events from this far back (30m, 24h, 7d) or this RFC 3339 time
+        #[arg(long)]
+        since: Option<String>,
+        /// Write to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Print task duration, agent throughput, tool failure and time-in-status
+    /// metrics
+    Stats {
+        /// Print JSON instead of a text report
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show which error rule matches a message, to debug rules.toml
+    Analyze {
+        /// Error message to categorize
+        message: String,
+    },
+    /// Send the hook event history as OpenTelemetry traces to an OTLP collector
+    Otel {
+        /// OTLP/HTTP endpoint, e.g. http://localhost:4318 (default: [otel] endpoint)
+        #[arg(long)]
+        endpoint: Option<String>,
+        /// Only export this session
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Print a one-line progress summary for Claude Code's statusline
+    /// (reads the session JSON on stdin)
+    Statusline,
+    /// Print every event and the state changes derived from it as NDJSON,
+    /// history first, then live
+    Stream,
+    /// Keep a snapshot of the event state fresh in the background, so the
+    /// dashboard starts without parsing the whole history
+    Collector {
+        /// Seconds between checks for new events
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+        /// Print a systemd or launchd service running the collector instead
+        #[arg(long, value_name = "MANAGER")]
+        print_service: Option<ServiceKind>,
+    },
+    /// Email a summary of recent runs, failures and costs ([digest] in board.toml)
+    Digest {
+        /// Window to summarize (30m, 24h, 7d) or its RFC 3339 start
+        #[arg(long, default_value = "24h")]
+        since: String,
+        /// Print the email instead of sending it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Play back a session recorded with --record
+    Replay {
+        /// Recording file
+        file: PathBuf,
+    },
+    /// Sync TASKS.md with an external tracker
+    Sync {
+        #[command(subcommand)]
+        target: SyncTarget,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum SyncTarget {
+    /// Phases to milestones and tasks to issues; prints the plan unless --apply
+    Github {
+        /// Repository as owner/name (default: the current directory's)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Make the changes instead of only listing them
+        #[arg(long)]
+        apply: bool,
+    },
 }
 
 /// Get the user's home directory (cross-platform)
@@ -61,7 +213,7 @@ fn home_dir() -> PathBuf {
 
 /// Resolve the hooks directory: .claude/hooks > ~/.claude/hooks
 fn resolve_hooks_path() -> PathBuf {
-    let local = PathBuf::from(".claude/hooks");
+    let local = Path::new(".claude").join("hooks");
     if local.is_dir() {
         return local;
     }
@@ -90,11 +242,158 @@ fn resolve_tasks_path(explicit: Option<&str>) -> String {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let error_rules =
+        rules::load_rules(&rules::user_rules_path(&home_dir())).map_err(anyhow::Error::msg)?;
+    rules::set_rules(error_rules.clone());
     let tasks_path = resolve_tasks_path(cli.tasks.as_deref());
+    // The live views read a local mirror of the remote events
+    let command = cli.command.unwrap_or(Commands::Watch);
+    let live = matches!(
+        command,
+        Commands::Watch
+            | Commands::Serve { .. }
+            | Commands::Mcp
+            | Commands::Stream
+            | Commands::Collector { .. }
+    );
+    let live_events = match cli.remote.clone() {
+        Some(remote) if live => Some(start_remote(remote)?),
+        _ => cli.events.clone(),
+    };
 
-    match cli.command.unwrap_or(Commands::Watch) {
-        Commands::Watch => run_tui(&tasks_path, cli.hooks.as_deref(), cli.events.as_deref()),
-        Commands::Init => simple_claude_board::init::run_init(),
+    match command {
+        Commands::Watch => run_tui(
+            &tasks_path,
+            cli.hooks.as_deref(),
+            live_events.as_deref(),
+            &cli.ingest,
+            cli.record.as_deref(),
+            cli.theme,
+        ),
+        Commands::Init {
+            project,
+            local,
+            rollback,
+            matcher,
+            timeout,
+            events_dir,
+        } => {
+            let target = match (project, local) {
+                (false, _) => InitTarget::Home,
+                (true, false) => InitTarget::Project,
+                (true, true) => InitTarget::ProjectLocal,
+            };
+            if rollback {
+                return Ok(init::run_rollback(target)?);
+            }
+            let mut options = Config::load(&home_dir()).map_err(anyhow::Error::msg)?.hook;
+            options.matcher = matcher.unwrap_or(options.matcher);
+            options.timeout = timeout.unwrap_or(options.timeout);
+            options.events_dir = events_dir.or(options.events_dir);
+            Ok(init::run_init(target, &options)?)
+        }
+        Commands::Serve { bind } => run_serve(
+            &tasks_path,
+            cli.hooks.as_deref(),
+            live_events.as_deref(),
+            &cli.ingest,
+            cli.record.as_deref(),
+            bind,
+        ),
+        Commands::Mcp => run_mcp(
+            &tasks_path,
+            cli.hooks.as_deref(),
+            live_events.as_deref(),
+            &cli.ingest,
+            cli.record.as_deref(),
+        ),
+        Commands::Export { format, output } => {
+            let (hooks_path, events_path) =
+                resolve_event_dirs(cli.hooks.as_deref(), cli.events.as_deref());
+            simple_claude_board::export::run(
+                format,
+                std::path::Path::new(&tasks_path),
+                &[hooks_path, events_path],
+                output.as_deref(),
+            )
+        }
+        Commands::ExportEvents {
+            format,
+            since,
+            output,
+        } => {
+            let (hooks_path, events_path) =
+                resolve_event_dirs(cli.hooks.as_deref(), cli.events.as_deref());
+            simple_claude_board::export::run_events(
+                format,
+                &[hooks_path, events_path],
+                since.as_deref(),
+                output.as_deref(),
+            )
+        }
+        Commands::Otel { endpoint, session } => run_otel(
+            cli.hooks.as_deref(),
+            cli.events.as_deref(),
+            endpoint,
+            session,
+        ),
+        Commands::Analyze { message } => {
+            print!("{}", rules::explain(&error_rules, &message));
+            Ok(())
+        }
+        Commands::Stats { json } => run_stats(
+            cli.tasks.as_deref(),
+            cli.hooks.as_deref(),
+            cli.events.as_deref(),
+            json,
+        ),
+        Commands::Statusline => run_statusline(
+            cli.tasks.as_deref(),
+            cli.hooks.as_deref(),
+            cli.events.as_deref(),
+        ),
+        Commands::Stream => {
+            let (hooks_path, events_path) =
+                resolve_event_dirs(cli.hooks.as_deref(), live_events.as_deref());
+            let identity = Config::load(&home_dir())
+                .unwrap_or_default()
+                .agent_identity();
+            simple_claude_board::stream::run(
+                std::path::Path::new(&tasks_path),
+                &[hooks_path, events_path],
+                identity,
+            )
+        }
+        Commands::Collector {
+            interval,
+            print_service,
+        } => run_collector(
+            cli.hooks.as_deref(),
+            live_events.as_deref(),
+            interval,
+            print_service,
+        ),
+        Commands::Digest { since, dry_run } => {
+            let config = Config::load(&home_dir()).map_err(anyhow::Error::msg)?;
+            let (hooks_path, events_path) =
+                resolve_event_dirs(cli.hooks.as_deref(), cli.events.as_deref());
+            simple_claude_board::digest::run(
+                config.digest.as_ref(),
+                std::path::Path::new(&tasks_path),
+                &[hooks_path, events_path],
+                &since,
+                dry_run,
+                config.agent_identity(),
+            )
+        }
+        Commands::Replay { file } => run_replay(&file, cli.theme),
+        Commands::Sync {
+            target: SyncTarget::Github { repo, apply },
+        } => simple_claude_board::sync::github::run(
+            std::path::Path::new(&tasks_path),
+            repo.as_deref(),
+            apply,
+        ),
     }
 }
 
@@ -103,75 +402,148 @@ fn install_panic_hook() {
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), DisableFocusChange, LeaveAlternateScreen);
         original_hook(panic_info);
     }));
 }
 
-fn run_tui(tasks_path: &str, hooks_dir: Option<&str>, events_dir: Option<&str>) -> Result<()> {
-    // Load initial state
-    let dashboard = match std::fs::read_to_string(tasks_path) {
-        Ok(content) => DashboardState::from_tasks_content(&content)
-            .unwrap_or_else(|_| DashboardState::default()),
-        Err(_) => DashboardState::default(),
-    };
-
-    let mut dashboard = dashboard;
+/// Resolve the hooks and dashboard events directories: CLI arg > default
+fn resolve_event_dirs(hooks_dir: Option<&str>, events_dir: Option<&str>) -> (PathBuf, PathBuf) {
     let hooks_path = hooks_dir
         .map(PathBuf::from)
         .unwrap_or_else(resolve_hooks_path);
-
-    // Resolve events directory: CLI arg > default ~/.claude/dashboard
     let events_path = events_dir
         .map(PathBuf::from)
         .unwrap_or_else(|| home_dir().join(".claude").join("dashboard"));
+    (hooks_path, events_path)
+}
+
+/// Start mirroring `remote` under ~/.claude/dashboard/remote and return the
+/// mirror directory
+fn start_remote(remote: Remote) -> Result<String> {
+    let mirror_dir =
+        remote.mirror_dir(&home_dir().join(".claude").join("dashboard").join("remote"));
+    remote::spawn(remote, &mirror_dir)?;
+    Ok(mirror_dir.to_string_lossy().into_owned())
+}
 
-    // Load existing hook events at startup
-    if hooks_path.is_dir() {
-        let _ = dashboard.load_hook_events(&hooks_path);
+/// Build the app shared by the TUI, `serve` and `mcp` and start its
+/// ingestion sources: the history load and file watcher, plus `ingest`
+fn start_app(
+    tasks_path: &str,
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+    ingest: &[SourceSpec],
+    record: Option<&std::path::Path>,
+    theme: Option<ThemeName>,
+) -> Result<(App, mpsc::UnboundedReceiver<Update>)> {
+    // Load initial state
+    let dashboard =
+        DashboardState::from_tasks_file(std::path::Path::new(tasks_path)).unwrap_or_default();
+
+    let (hooks_path, events_path) = resolve_event_dirs(hooks_dir, events_dir);
+
+    let mut config = Config::load(&home_dir()).map_err(anyhow::Error::msg)?;
+    if let Some(theme) = theme {
+        config.theme = theme;
     }
-    // Also load events from the dashboard events directory
+
+    // Existing hook events (hooks dir + dashboard events dir) load in the
+    // background, starting from the collector's snapshot when there is one
+    let event_dirs = vec![hooks_path.clone(), events_path.clone()];
+    let home = home_dir();
+    let mut watch_config = WatchConfig::new(PathBuf::from(tasks_path), hooks_path)
+        .with_config_files(
+            config::config_file_candidates(&home)
+                .into_iter()
+                .chain([rules::user_rules_path(&home)])
+                .collect(),
+        );
     if events_path.is_dir() {
-        let _ = dashboard.load_hook_events(&events_path);
+        watch_config = watch_config.with_events_dir(events_path.clone());
+    }
+    let files = FilesSource::new(event_dirs.clone(), watch_config)
+        .with_store(Store::read(&events_path.join(store::STORE_FILE)))
+        .with_agent_identity(config.agent_identity());
+    let mut sources: Vec<Box<dyn DataSource>> = vec![Box::new(files)];
+    for spec in ingest {
+        sources.push(spec.open().context("cannot open an --ingest source")?);
     }
 
+    let (tx, updates) = mpsc::unbounded_channel();
+    source::start_on(&tx, sources);
     let mut app = App::new()
+        .with_updates(tx)
+        .with_config(config)
+        .with_config_reload(home, theme)
         .with_dashboard(dashboard)
-        .with_tasks_path(PathBuf::from(tasks_path));
-    let mut watch_config = WatchConfig::new(PathBuf::from(tasks_path), hooks_path);
-    if events_path.is_dir() {
-        watch_config = watch_config.with_events_dir(events_path);
+        .with_tasks_path(PathBuf::from(tasks_path))
+        .with_event_dirs(event_dirs.clone())
+        .with_commands_dir(events_path);
+    if let Some(record) = record {
+        let recorder = Recorder::create(record, std::path::Path::new(tasks_path), &event_dirs)
+            .with_context(|| format!("cannot record to {}", record.display()))?;
+        app = app.with_recorder(recorder);
     }
-    let watcher_rx = if watch_config.validate().is_ok() {
-        match watcher::start_watching(watch_config) {
-            Ok((_watcher, rx)) => {
-                let watcher = _watcher;
-                std::mem::forget(watcher);
-                Some(rx)
-            }
-            Err(_) => None,
-        }
-    } else {
-        None
-    };
+    app.history_progress = Some(0.0);
+    app.refresh_commits();
+    Ok((app, updates))
+}
 
+fn run_tui(
+    tasks_path: &str,
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+    ingest: &[SourceSpec],
+    record: Option<&std::path::Path>,
+    theme: Option<ThemeName>,
+) -> Result<()> {
+    let (mut app, updates) = start_app(tasks_path, hooks_dir, events_dir, ingest, record, theme)?;
+    app.load_view();
+    run_terminal(&mut app, Some(updates), None)?;
+    if let Err(e) = app.save_view() {
+        eprintln!("Could not save the dashboard view: {e}");
+    }
+    Ok(())
+}
+
+/// Play a recording back in the dashboard, without a TASKS.md to edit
+fn run_replay(file: &std::path::Path, theme: Option<ThemeName>) -> Result<()> {
+    let recording = Recording::load(file)?;
+    let mut config = Config::load(&home_dir()).map_err(anyhow::Error::msg)?;
+    if let Some(theme) = theme {
+        config.theme = theme;
+    }
+    let mut app = App::new().with_config(config);
+    run_terminal(&mut app, None, Some(Player::new(recording)))
+}
+
+/// Run the dashboard in the alternate screen until it quits
+fn run_terminal(
+    app: &mut App,
+    updates: Option<mpsc::UnboundedReceiver<Update>>,
+    player: Option<Player>,
+) -> Result<()> {
     // Install panic hook before entering raw mode
     install_panic_hook();
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableFocusChange)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    let result = run_loop(&mut terminal, &mut app, watcher_rx);
+    let result = tokio::runtime::Runtime::new()
+        .map_err(anyhow::Error::from)
+        .and_then(|runtime| runtime.block_on(run_loop(&mut terminal, app, updates, player)));
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
+        DisableFocusChange,
         LeaveAlternateScreen,
         crossterm::cursor::MoveTo(0, 0),
         crossterm::cursor::Show
@@ -180,119 +552,374 @@ fn run_tui(tasks_path: &str, hooks_dir: Option<&str>, events_dir: Option<&str>)
     result
 }
 
-fn run_loop(
+/// Serve the web mirror on `bind` until interrupted
+fn run_serve(
+    tasks_path: &str,
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+    ingest: &[SourceSpec],
+    record: Option<&std::path::Path>,
+    bind: SocketAddr,
+) -> Result<()> {
+    let (app, updates) = start_app(tasks_path, hooks_dir, events_dir, ingest, record, None)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async move {
+        let listener = tokio::net::TcpListener::bind(bind).await?;
+        println!("Serving dashboard on http://{}", listener.local_addr()?);
+        let (tx, rx) = tokio::sync::watch::channel(Default::default());
+        tokio::spawn(web::mirror(app, updates, tx));
+        web::server::serve(listener, rx).await?;
+        Ok(())
+    })
+}
+
+/// Answer MCP requests on stdin/stdout until stdin closes
+fn run_mcp(
+    tasks_path: &str,
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+    ingest: &[SourceSpec],
+    record: Option<&std::path::Path>,
+) -> Result<()> {
+    // stdin carries the protocol
+    if ingest.contains(&SourceSpec::Stdin) {
+        anyhow::bail!("mcp reads requests on stdin; use --ingest tcp:ADDR instead");
+    }
+    let (app, updates) = start_app(tasks_path, hooks_dir, events_dir, ingest, record, None)?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(simple_claude_board::mcp::run(app, updates))?;
+    Ok(())
+}
+
+/// Export the event history to the OTLP collector from `--endpoint` or board.toml
+fn run_otel(
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+    endpoint: Option<String>,
+    session: Option<String>,
+) -> Result<()> {
+    let config = Config::load(&home_dir()).map_err(anyhow::Error::msg)?;
+    let endpoint = endpoint.or(config.otel_endpoint).ok_or_else(|| {
+        anyhow::anyhow!("no OTLP endpoint: pass --endpoint or set [otel] endpoint in board.toml")
+    })?;
+    let (hooks_path, events_path) = resolve_event_dirs(hooks_dir, events_dir);
+    simple_claude_board::export::otel::run(
+        &[hooks_path, events_path],
+        &endpoint,
+        session.as_deref(),
+    )
+}
+
+/// Run the collector, or print a service definition that runs it with the
+/// same directories
+fn run_collector(
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+    interval: u64,
+    print_service: Option<ServiceKind>,
+) -> Result<()> {
+    let (hooks_path, events_path) = resolve_event_dirs(hooks_dir, events_dir);
+    let Some(kind) = print_service else {
+        let config = Config::load(&home_dir()).map_err(anyhow::Error::msg)?;
+        return simple_claude_board::collector::run(
+            &[hooks_path, events_path.clone()],
+            &events_path,
+            std::time::Duration::from_secs(interval.max(1)),
+            config.agent_identity(),
+            config.limits,
+        );
+    };
+
+    let cwd = std::env::current_dir()?;
+    let absolute = |path: PathBuf| {
+        path.canonicalize()
+            .unwrap_or_else(|_| cwd.join(path))
+            .to_string_lossy()
+            .into_owned()
+    };
+    let args = vec![
+        "--hooks".to_string(),
+        absolute(hooks_path),
+        "--events".to_string(),
+        absolute(events_path),
+        "collector".to_string(),
+        "--interval".to_string(),
+        interval.to_string(),
+    ];
+    let program = std::env::current_exe()?;
+    print!(
+        "{}",
+        simple_claude_board::collector::service_file(kind, &program.to_string_lossy(), &args, &cwd)
+    );
+    Ok(())
+}
+
+/// Print the statusline summary of the project named in the stdin JSON
+fn run_statusline(
+    tasks: Option<&str>,
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+) -> Result<()> {
+    let mut input = String::new();
+    let _ = io::Read::read_to_string(&mut io::stdin(), &mut input);
+    // Resolve TASKS.md and .claude/ relative to the session's project
+    if tasks.is_none() {
+        if let Some(dir) = statusline::project_dir(&input) {
+            let _ = std::env::set_current_dir(dir);
+        }
+    }
+
+    let tasks_path = resolve_tasks_path(tasks);
+    let mut state =
+        DashboardState::from_tasks_file(std::path::Path::new(&tasks_path)).unwrap_or_default();
+    let (hooks_path, events_path) = resolve_event_dirs(hooks_dir, events_dir);
+    // A bad config must not blank the statusline; fall back to the default
+    let config = Config::load(&home_dir()).unwrap_or_default();
+    state.agent_identity = config.agent_identity();
+    loader::for_each_batch(&[hooks_path, events_path], |batch| {
+        state.update_from_events(&batch)
+    });
+    let idle_minutes = config.idle_minutes();
+    let now = chrono::Utc::now();
+    state.expire_idle_agents(now, chrono::Duration::minutes(idle_minutes as i64));
+    println!("{}", statusline::render(&state, now));
+    Ok(())
+}
+
+fn run_stats(
+    tasks: Option<&str>,
+    hooks_dir: Option<&str>,
+    events_dir: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let tasks_path = resolve_tasks_path(tasks);
+    let mut state =
+        DashboardState::from_tasks_file(std::path::Path::new(&tasks_path)).unwrap_or_default();
+    let (hooks_path, events_path) = resolve_event_dirs(hooks_dir, events_dir);
+    state.agent_identity = Config::load(&home_dir())
+        .unwrap_or_default()
+        .agent_identity();
+    loader::for_each_batch(&[hooks_path, events_path], |batch| {
+        state.update_from_events(&batch)
+    });
+    let metrics = state.metrics(chrono::Utc::now());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&metrics)?);
+    } else {
+        print!("{}", metrics.to_text());
+    }
+    Ok(())
+}
+
+/// Suspend the TUI, run the editor until it exits, then restore the TUI
+fn run_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    request: &EditorRequest,
+) -> Result<()> {
+    let Some(mut command) = editor::editor_command(&editor::resolve_editor(), request) else {
+        return Ok(());
+    };
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        LeaveAlternateScreen,
+        crossterm::cursor::Show
+    )?;
+
+    // A missing editor binary just returns to the dashboard
+    let _ = command.status();
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableFocusChange
+    )?;
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Suspend the TUI, pipe `text` to the pager until it exits, then restore
+/// the TUI; a notice when the pager cannot be started
+fn run_pager(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    text: &str,
+) -> Result<Option<String>> {
+    let pager_name = pager::resolve_pager();
+    let Some(mut command) = pager::pager_command(&pager_name) else {
+        return Ok(None);
+    };
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        LeaveAlternateScreen,
+        crossterm::cursor::Show
+    )?;
+
+    let notice = match command.spawn() {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // A pager quit before reading everything closes the pipe
+                let _ = io::Write::write_all(&mut stdin, text.as_bytes());
+            }
+            let _ = child.wait();
+            None
+        }
+        Err(e) => Some(format!("cannot run {pager_name}: {e}")),
+    };
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableFocusChange
+    )?;
+    terminal.clear()?;
+    Ok(notice)
+}
+
+/// Run a custom action with the TUI suspended, wait for Enter so its output
+/// can be read, and return a notice with the exit status
+fn run_shell(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    request: &ShellRequest,
+) -> Result<String> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        DisableFocusChange,
+        LeaveAlternateScreen,
+        crossterm::cursor::Show
+    )?;
+
+    println!("$ {}", request.command);
+    let notice = match actions::shell_command(&request.command).status() {
+        Ok(status) => match status.code() {
+            Some(0) => format!("{}: exit 0", request.label),
+            Some(code) => format!("{}: failed with exit {code}", request.label),
+            None => format!("{}: killed by a signal", request.label),
+        },
+        Err(e) => format!("{}: cannot run sh: {e}", request.label),
+    };
+    print!("\n{notice}. Press Enter to return to the dashboard");
+    io::Write::flush(&mut io::stdout())?;
+    let mut line = String::new();
+    let _ = io::stdin().read_line(&mut line);
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableFocusChange
+    )?;
+    terminal.clear()?;
+    Ok(notice)
+}
+
+async fn run_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
-    mut watcher_rx: Option<mpsc::UnboundedReceiver<FileChange>>,
+    updates: Option<mpsc::UnboundedReceiver<Update>>,
+    mut player: Option<Player>,
 ) -> Result<()> {
-    let tick_rate = Duration::from_millis(250);
+    let mut events = Events::new(updates, Duration::from_millis(250));
+    // Whether anything on screen changed since the last frame; the status
+    // bar clock still moves once a second
+    let mut dirty = true;
+    let mut drawn_second = None;
 
     while app.running {
-        // Draw
-        terminal.draw(|frame| {
-            let area = frame.area();
-            let layout = DashboardLayout::compute(area);
-
-            // Left panel: Gantt chart
-            let gantt = GanttWidget::new(&app.dashboard, app.focused == FocusedPane::TaskList);
-            frame.render_stateful_widget(gantt, layout.task_list, &mut app.gantt_state);
-
-            // Right panel: Detail view (content depends on focused pane)
-            let selected_task = app.selected_task();
-            let detail = if app.focused == FocusedPane::Agents {
-                DetailWidget::from_agent_selection(&app.dashboard, app.selected_agent)
-            } else {
-                DetailWidget::from_selection(
-                    &app.dashboard,
-                    selected_task,
-                    app.gantt_state.selected,
-                    app.focused == FocusedPane::Detail,
-                )
-            };
-            frame.render_widget(detail, layout.detail);
-
-            // Right bottom: Agent activity (highlights agent for selected task)
-            let selected_agent_name = selected_task
-                .and_then(|(pi, ti)| {
-                    app.dashboard
-                        .phases
-                        .get(pi)
-                        .and_then(|phase| phase.tasks.get(ti))
-                })
-                .and_then(|task| app.dashboard.agent_for_task(&task.id));
-            let agents = AgentPanel::new(&app.dashboard)
-                .with_selected_agent(selected_agent_name)
-                .with_focused(app.focused == FocusedPane::Agents)
-                .with_selected_index(app.selected_agent);
-            frame.render_widget(agents, layout.agents);
-
-            // Bottom: Status bar
-            let statusbar = StatusBar::new(&app.dashboard, app.start_time);
-            frame.render_widget(statusbar, layout.status_bar);
-
-            // Help overlay (on top if active)
-            if app.show_help {
-                frame.render_widget(HelpOverlay, area);
+        // Move a replay along and show where it is
+        if let Some(ref mut player) = player {
+            player.advance(&mut app.dashboard);
+            app.status_message = Some(player.label());
+            dirty = true;
+        }
+
+        // Draw, unless nothing changed since the last frame
+        let second = app.start_time.elapsed().as_secs();
+        if dirty || drawn_second != Some(second) {
+            terminal.draw(|frame| ui::draw(frame, app))?;
+            dirty = false;
+            drawn_second = Some(second);
+        }
+
+        // Ring the terminal bell for a reminder that just came due
+        if std::mem::take(&mut app.bell) {
+            print!("\x07");
+            io::Write::flush(&mut io::stdout())?;
+        }
+
+        // Escalate errors and failures seen while the terminal was unfocused
+        if let Some(message) = app.alert.take() {
+            if app.config.alerts.bell {
+                print!("\x07");
+            }
+            if app.config.alerts.osc {
+                let in_tmux = std::env::var_os("TMUX").is_some();
+                print!("{}", alerts::osc9(&message, in_tmux));
             }
+            io::Write::flush(&mut io::stdout())?;
+        }
 
-            // Retry modal (on top if active)
-            if app.show_retry_modal {
-                if let Some(ref target) = app.retry_target {
-                    let modal = RetryModal {
-                        task_id: target.task_id.clone(),
-                        task_name: target.task_name.clone(),
-                        retryable: target.retryable,
-                    };
-                    frame.render_widget(modal, area);
-                }
+        // Hand the terminal to $EDITOR, then pick up any edits to TASKS.md
+        if let Some(request) = app.editor_request.take() {
+            events.release_terminal();
+            run_editor(terminal, &request)?;
+            dirty = true;
+            if app.tasks_path.as_ref() == Some(&request.path) {
+                app.handle_file_change(&FileChange::TasksModified(request.path));
             }
-        })?;
+        }
 
-        // Process file watcher events (non-blocking)
-        if let Some(ref mut rx) = watcher_rx {
-            while let Ok(change) = rx.try_recv() {
-                app.handle_file_change(&change);
+        // Page long detail text with the TUI suspended
+        if let Some(text) = app.pager_request.take() {
+            events.release_terminal();
+            if let Some(notice) = run_pager(terminal, &text)? {
+                app.status_message = Some(notice);
             }
+            dirty = true;
+        }
+
+        // Copy text to the clipboard, possibly with an escape on stdout
+        if let Some(text) = app.clipboard_request.take() {
+            app.status_message = Some(match clipboard::copy(&text) {
+                Ok(via) => format!("Copied status summary ({via})"),
+                Err(e) => format!("Cannot copy: {e}"),
+            });
+            dirty = true;
+        }
+
+        // Run a custom action command, then report how it exited
+        if let Some(request) = app.shell_request.take() {
+            events.release_terminal();
+            app.status_message = Some(run_shell(terminal, &request)?);
+            dirty = true;
         }
 
-        // Handle keyboard events
-        if let Some(event) = poll_event(tick_rate)? {
-            match event {
-                AppEvent::Key(key) => {
-                    if app.show_retry_modal {
-                        // Modal takes priority: only y/n/q/Esc
-                        let retryable = app.retry_target.as_ref().is_some_and(|t| t.retryable);
-                        match key_to_action(key) {
-                            Action::Confirm if retryable => app.confirm_retry(),
-                            Action::Cancel | Action::Quit => app.cancel_retry(),
-                            // Non-retryable: any key closes
-                            _ if !retryable => app.cancel_retry(),
-                            _ => {}
-                        }
-                    } else {
-                        match key_to_action(key) {
-                            Action::Quit => app.quit(),
-                            Action::MoveDown => match app.focused {
-                                FocusedPane::Agents => app.agent_move_down(),
-                                _ => app.move_down(),
-                            },
-                            Action::MoveUp => match app.focused {
-                                FocusedPane::Agents => app.agent_move_up(),
-                                _ => app.move_up(),
-                            },
-                            Action::ToggleFocus => app.toggle_focus(),
-                            Action::ToggleHelp => app.toggle_help(),
-                            Action::ToggleCollapse => app.toggle_collapse(),
-                            Action::ToggleView => app.toggle_view(),
-                            Action::RetryRequest => app.open_retry_modal(),
-                            Action::Confirm | Action::Cancel | Action::None => {}
-                        }
-                    }
+        // Wait for a key, an update from the ingestion sources or a tick
+        let event = events.next().await?;
+        dirty |= !matches!(event, AppEvent::Tick);
+        match event {
+            AppEvent::Key(key) => match player.as_mut() {
+                // Playback control sees what the overlays leave
+                Some(player) => app.handle_key_with(key, |key| player.handle_key(key)),
+                None => app.handle_key(key),
+            },
+            AppEvent::Resize(_, _) => {} // terminal auto-handles resize
+            AppEvent::Focus(focused) => app.set_terminal_focus(focused),
+            AppEvent::Update(update) => {
+                // Apply a burst of updates before drawing again
+                app.handle_update(update);
+                while let Some(update) = events.pending_update() {
+                    app.handle_update(update);
                 }
-                AppEvent::Resize(_, _) => {} // terminal auto-handles resize
-                AppEvent::FileChanged(change) => app.handle_file_change(&change),
-                AppEvent::Tick => {}
             }
+            AppEvent::Tick => dirty |= app.on_tick(),
         }
     }
 
@@ -0,0 +1,292 @@
+//! MCP server mode (`mcp` subcommand)
+//!
+//! Speaks newline-delimited JSON-RPC 2.0 on stdin/stdout so a supervising
+//! Claude Code session can query the board. The state comes from the same
+//! ingestion sources as `serve` ([`crate::web::mirror`]); every request is
+//! answered from the latest [`Snapshot`].
+
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, watch};
+
+use crate::app::App;
+use crate::data::source::Update;
+use crate::web::{self, snapshot::Snapshot};
+
+/// Protocol revision this server implements
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Errors returned by `get_recent_errors` when no limit is given
+const DEFAULT_ERROR_LIMIT: usize = 10;
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Resources offered alongside the tools, as (uri, name, description)
+const RESOURCES: [(&str, &str, &str); 3] = [
+    ("board://tasks", "Tasks", "Phases and tasks from TASKS.md"),
+    ("board://agents", "Agents", "Agent status from hook events"),
+    (
+        "board://errors",
+        "Errors",
+        "Open agent errors, newest first",
+    ),
+];
+
+/// Serve MCP on stdin/stdout until stdin closes
+pub async fn run(app: App, updates: mpsc::UnboundedReceiver<Update>) -> std::io::Result<()> {
+    let (tx, rx) = watch::channel(Arc::new(Snapshot::from_state(&app.dashboard)));
+    tokio::spawn(web::mirror(app, updates, tx));
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let snapshot = Arc::clone(&rx.borrow());
+        if let Some(response) = handle_message(&line, &snapshot) {
+            stdout.write_all(response.to_string().as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+/// Response to one JSON-RPC message; `None` for notifications
+pub fn handle_message(line: &str, snapshot: &Snapshot) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+    };
+    let id = message.get("id")?.clone();
+    let method = message["method"].as_str().unwrap_or("");
+    let params = &message["params"];
+    let result = match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {}, "resources": {} },
+            "serverInfo": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(params, snapshot),
+        "resources/list" => Ok(json!({
+            "resources": RESOURCES
+                .iter()
+                .map(|(uri, name, description)| json!({
+                    "uri": uri,
+                    "name": name,
+                    "description": description,
+                    "mimeType": "application/json",
+                }))
+                .collect::<Vec<_>>(),
+        })),
+        "resources/read" => read_resource(params, snapshot),
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method: {method}"))),
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "list_tasks",
+            "description": "List phases and their tasks from TASKS.md with status, agent and blocked reason",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "status": {
+                        "type": "string",
+                        "enum": ["pending", "in_progress", "completed", "failed", "blocked"],
+                        "description": "Only include tasks with this status",
+                    },
+                },
+            },
+        },
+        {
+            "name": "get_agent_status",
+            "description": "Status, current task and tool, and open error count of the agents",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "agent_id": { "type": "string", "description": "Only this agent" },
+                },
+            },
+        },
+        {
+            "name": "get_recent_errors",
+            "description": "Open agent errors, newest first, with a suggested fix",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "limit": { "type": "integer", "minimum": 1, "description": "At most this many (default 10)" },
+                },
+            },
+        },
+    ])
+}
+
+type RpcResult = Result<Value, (i64, String)>;
+
+fn call_tool(params: &Value, snapshot: &Snapshot) -> RpcResult {
+    let args = &params["arguments"];
+    let content = match params["name"].as_str().unwrap_or("") {
+        "list_tasks" => {
+            let status = args["status"].as_str();
+            let mut phases = snapshot.phases.clone();
+            if let Some(status) = status {
+                for phase in &mut phases {
+                    phase.tasks.retain(|t| t.status == status);
+                }
+                phases.retain(|p| !p.tasks.is_empty());
+            }
+            json!(phases)
+        }
+        "get_agent_status" => match args["agent_id"].as_str() {
+            Some(id) => match snapshot.agents.iter().find(|a| a.id == id) {
+                Some(agent) => json!(agent),
+                None => return Ok(tool_result(&format!("Unknown agent: {id}"), true)),
+            },
+            None => json!(snapshot.agents),
+        },
+        "get_recent_errors" => {
+            let limit = args["limit"]
+                .as_u64()
+                .map_or(DEFAULT_ERROR_LIMIT, |n| n as usize);
+            json!(snapshot.errors.iter().take(limit).collect::<Vec<_>>())
+        }
+        name => return Err((INVALID_PARAMS, format!("Unknown tool: {name}"))),
+    };
+    Ok(tool_result(&content.to_string(), false))
+}
+
+fn tool_result(text: &str, is_error: bool) -> Value {
+    json!({ "content": [{ "type": "text", "text": text }], "isError": is_error })
+}
+
+fn read_resource(params: &Value, snapshot: &Snapshot) -> RpcResult {
+    let uri = params["uri"].as_str().unwrap_or("");
+    let text = match uri {
+        "board://tasks" => json!(snapshot.phases),
+        "board://agents" => json!(snapshot.agents),
+        "board://errors" => json!(snapshot.errors),
+        _ => return Err((INVALID_PARAMS, format!("Unknown resource: {uri}"))),
+    };
+    Ok(json!({
+        "contents": [{ "uri": uri, "mimeType": "application/json", "text": text.to_string() }],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::state::DashboardState;
+
+    fn sample_snapshot() -> Snapshot {
+        let tasks = include_str!("../tests/fixtures/sample_tasks.md");
+        let mut state = DashboardState::from_tasks_content(tasks).unwrap();
+        let hooks = include_str!("../tests/fixtures/sample_hooks/error_events.jsonl");
+        state.update_from_events(&crate::data::hook_parser::parse_hook_events(hooks).events);
+        Snapshot::from_state(&state)
+    }
+
+    fn call(snapshot: &Snapshot, method: &str, params: Value) -> Value {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        handle_message(&request.to_string(), snapshot).unwrap()
+    }
+
+    /// The JSON inside a tool result's text content
+    fn tool_json(response: &Value) -> Value {
+        serde_json::from_str(response["result"]["content"][0]["text"].as_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn handshake_and_listing() {
+        let snapshot = sample_snapshot();
+        let init = call(&snapshot, "initialize", json!({}));
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(handle_message(
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            &snapshot
+        )
+        .is_none());
+
+        let tools = call(&snapshot, "tools/list", json!({}));
+        assert_eq!(tools["result"]["tools"].as_array().unwrap().len(), 3);
+        let resources = call(&snapshot, "resources/list", json!({}));
+        assert_eq!(resources["result"]["resources"][0]["uri"], "board://tasks");
+
+        assert_eq!(
+            call(&snapshot, "nope", json!({}))["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            handle_message("{", &snapshot).unwrap()["error"]["code"],
+            PARSE_ERROR
+        );
+    }
+
+    #[test]
+    fn tools_answer_from_the_snapshot() {
+        let snapshot = sample_snapshot();
+        let failed = call(
+            &snapshot,
+            "tools/call",
+            json!({ "name": "list_tasks", "arguments": { "status": "failed" } }),
+        );
+        let phases = tool_json(&failed);
+        assert_eq!(phases.as_array().unwrap().len(), 1);
+        assert_eq!(phases[0]["tasks"][0]["id"], "P1-R3-T1");
+
+        let agent = call(
+            &snapshot,
+            "tools/call",
+            json!({ "name": "get_agent_status", "arguments": { "agent_id": "backend-specialist-2" } }),
+        );
+        assert_eq!(tool_json(&agent)["open_errors"], 2);
+        let unknown = call(
+            &snapshot,
+            "tools/call",
+            json!({ "name": "get_agent_status", "arguments": { "agent_id": "ghost" } }),
+        );
+        assert_eq!(unknown["result"]["isError"], true);
+
+        let errors = call(
+            &snapshot,
+            "tools/call",
+            json!({ "name": "get_recent_errors", "arguments": { "limit": 1 } }),
+        );
+        let errors = tool_json(&errors);
+        assert_eq!(errors.as_array().unwrap().len(), 1);
+        assert!(errors[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("connection refused"));
+
+        let resource = call(
+            &snapshot,
+            "resources/read",
+            json!({ "uri": "board://agents" }),
+        );
+        assert!(resource["result"]["contents"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("backend-specialist-2"));
+    }
+}
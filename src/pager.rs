@@ -0,0 +1,81 @@
+//! External pager
+//!
+//! Builds the `$PAGER` command the full task detail or an agent's event
+//! history is piped to. The main loop suspends the TUI while it runs.
+
+use std::process::{Command, Stdio};
+
+use crate::data::hook_parser::HookEvent;
+
+/// Pager used when `$PAGER` is not set
+const FALLBACK_PAGER: &str = if cfg!(windows) { "more" } else { "less" };
+
+/// The user's pager: `$PAGER` > `less` (`more` on Windows)
+pub fn resolve_pager() -> String {
+    std::env::var("PAGER")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_PAGER.to_string())
+}
+
+/// `pager [args..]` reading the text on stdin; `pager` may carry its own
+/// arguments (`less -S`). `None` when it is blank.
+pub fn pager_command(pager: &str) -> Option<Command> {
+    let mut words = pager.split_whitespace();
+    let mut command = Command::new(words.next()?);
+    command.args(words).stdin(Stdio::piped());
+    Some(command)
+}
+
+/// Every event of `agent_id`, oldest first, one per line
+pub fn agent_history(agent_id: &str, events: &[HookEvent]) -> String {
+    let mut text = format!("Event history of {agent_id} ({} events)\n\n", events.len());
+    for event in events {
+        let mut line = format!(
+            "{}  {:<10}  {}",
+            event.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            event.event_type.as_str(),
+            event.task_id
+        );
+        for value in [&event.tool_name, &event.file_path, &event.error_message]
+            .into_iter()
+            .flatten()
+        {
+            line.push_str("  ");
+            line.push_str(value);
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    #[test]
+    fn command_keeps_pager_arguments() {
+        let command = pager_command("less  -S").unwrap();
+        assert_eq!(command.get_program(), "less");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["-S"]);
+        assert!(pager_command(" ").is_none());
+    }
+
+    #[test]
+    fn history_lists_events_with_their_details() {
+        let input = [
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s","tool_name":"Edit","file_path":"src/lib.rs"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:05Z","agent_id":"a","task_id":"P1-T1","session_id":"s","error_message":"permission denied"}"#,
+        ]
+        .join("\n");
+        let text = agent_history("a", &parse_hook_events(&input).events);
+        assert_eq!(
+            text,
+            "Event history of a (2 events)\n\n\
+             2026-02-08 10:00:00  tool_start  P1-T1  Edit  src/lib.rs\n\
+             2026-02-08 10:00:05  error       P1-T1  permission denied\n"
+        );
+    }
+}
@@ -0,0 +1,410 @@
+//! Session recording (`--record`) and playback (`replay` subcommand)
+//!
+//! A recording is NDJSON: a header line, then one frame per change with its
+//! offset in milliseconds from the start. A frame carries either a TASKS.md
+//! snapshot or the hook events appended to the watched files:
+//!
+//! ```text
+//! {"format":"omcb","version":1,"started_at":"2026-02-08T10:00:00Z"}
+//! {"t":0,"tasks":"# Phase 1: Core\n..."}
+//! {"t":0,"events":[{"event_type":"agent_start",...}]}
+//! {"t":5120,"events":[...]}
+//! ```
+//!
+//! The history present when recording starts is stored at offset 0, so a
+//! replay begins from the same dashboard the recording user saw.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::{json, Value};
+
+use crate::data::hook_parser::HookEvent;
+use crate::data::loader;
+use crate::data::state::DashboardState;
+use crate::data::tail::Tail;
+use crate::data::watcher::FileChange;
+use crate::stream::event_record;
+
+/// Value of the header's `format` field
+const FORMAT: &str = "omcb";
+/// Bumped whenever the frame layout changes
+const VERSION: u64 = 1;
+
+/// Seek step of the arrow keys
+const SEEK_STEP: Duration = Duration::from_secs(10);
+/// Playback speed bounds for `<` and `>`
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 64.0;
+
+/// What changed at a point of the recording
+#[derive(Debug, Clone)]
+pub enum FrameKind {
+    /// TASKS.md content after the change
+    Tasks(String),
+    /// Hook events appended to the event files
+    Events(Vec<HookEvent>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Offset from the start of the recording
+    pub at: Duration,
+    pub kind: FrameKind,
+}
+
+/// Writes the changes the dashboard ingests to a recording file
+pub struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+    tail: Tail,
+}
+
+impl Recorder {
+    /// Start a recording at `path` with the current TASKS.md and the event
+    /// history in `dirs`
+    pub fn create(path: &Path, tasks_path: &Path, dirs: &[PathBuf]) -> io::Result<Self> {
+        let file = File::create(path)?;
+        let mut recorder = Self {
+            out: BufWriter::new(file),
+            started: Instant::now(),
+            tail: Tail::default(),
+        };
+        let header = json!({
+            "format": FORMAT,
+            "version": VERSION,
+            "started_at": Utc::now().to_rfc3339(),
+        });
+        writeln!(recorder.out, "{header}")?;
+        if let Ok(content) = std::fs::read_to_string(tasks_path) {
+            recorder.write_frame(&FrameKind::Tasks(content))?;
+        }
+        let mut history: Vec<HookEvent> = loader::collect_jsonl_files(dirs)
+            .into_iter()
+            .flat_map(|(path, _)| recorder.tail.read_new(&path))
+            .collect();
+        history.sort_by_key(|e| e.timestamp);
+        if !history.is_empty() {
+            recorder.write_frame(&FrameKind::Events(history))?;
+        }
+        Ok(recorder)
+    }
+
+    /// Add the effect of a watcher change; appended event lines are read
+    /// once, so a rewritten file does not repeat earlier events
+    pub fn record(&mut self, change: &FileChange) -> io::Result<()> {
+        let kind = match change {
+            FileChange::TasksModified(path) => match std::fs::read_to_string(path) {
+                Ok(content) => FrameKind::Tasks(content),
+                Err(_) => return Ok(()),
+            },
+            FileChange::HookEventCreated(path) | FileChange::HookEventModified(path) => {
+                let events = self.tail.read_new(path);
+                if events.is_empty() {
+                    return Ok(());
+                }
+                FrameKind::Events(events)
+            }
+            // The replay uses the config of whoever plays it back
+            FileChange::ConfigModified(_) => return Ok(()),
+        };
+        self.write_frame(&kind)
+    }
+
+    fn write_frame(&mut self, kind: &FrameKind) -> io::Result<()> {
+        let at = self.started.elapsed().as_millis() as u64;
+        let frame = match kind {
+            FrameKind::Tasks(content) => json!({ "t": at, "tasks": content }),
+            FrameKind::Events(events) => {
+                let events: Vec<Value> = events.iter().map(event_record).collect();
+                json!({ "t": at, "events": events })
+            }
+        };
+        writeln!(self.out, "{frame}")?;
+        // Flushed per frame so an interrupted dashboard keeps its recording
+        self.out.flush()
+    }
+}
+
+/// A loaded recording
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub started_at: DateTime<Utc>,
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+        let header: Value = lines
+            .next()
+            .and_then(|l| serde_json::from_str(l).ok())
+            .context("not a dashboard recording: missing header")?;
+        if header["format"] != FORMAT {
+            bail!("not a dashboard recording: unknown format");
+        }
+        if header["version"] != VERSION {
+            bail!("unsupported recording version {}", header["version"]);
+        }
+        let started_at = header["started_at"]
+            .as_str()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .context("recording header has no valid started_at")?
+            .with_timezone(&Utc);
+
+        let mut frames = Vec::new();
+        for (index, line) in lines.enumerate() {
+            // Line 1 is the header
+            let line_number = index + 2;
+            let value: Value = serde_json::from_str(line)
+                .with_context(|| format!("line {line_number}: invalid JSON"))?;
+            let at = Duration::from_millis(value["t"].as_u64().unwrap_or(0));
+            let kind = if let Some(content) = value["tasks"].as_str() {
+                FrameKind::Tasks(content.to_string())
+            } else if value["events"].is_array() {
+                let events = serde_json::from_value(value["events"].clone())
+                    .with_context(|| format!("line {line_number}: invalid events"))?;
+                FrameKind::Events(events)
+            } else {
+                bail!("line {line_number}: frame has neither tasks nor events");
+            };
+            frames.push(Frame { at, kind });
+        }
+        Ok(Self { started_at, frames })
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("cannot replay {}", path.display()))
+    }
+
+    /// Offset of the last frame
+    pub fn duration(&self) -> Duration {
+        self.frames.last().map(|f| f.at).unwrap_or_default()
+    }
+}
+
+/// Playback position and controls of a recording
+pub struct Player {
+    recording: Recording,
+    position: Duration,
+    /// Frames already applied to the dashboard
+    applied: usize,
+    speed: f64,
+    paused: bool,
+    last_advance: Instant,
+}
+
+impl Player {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            position: Duration::ZERO,
+            applied: 0,
+            speed: 1.0,
+            paused: false,
+            last_advance: Instant::now(),
+        }
+    }
+
+    /// Move the position by the wall time since the last call and update
+    /// `dashboard`; true when it changed
+    pub fn advance(&mut self, dashboard: &mut DashboardState) -> bool {
+        let elapsed = self.last_advance.elapsed();
+        self.last_advance = Instant::now();
+        if !self.paused {
+            self.position += elapsed.mul_f64(self.speed);
+        }
+        self.apply(dashboard)
+    }
+
+    /// Bring `dashboard` to the current position, starting over when it
+    /// moved back; true when it changed
+    fn apply(&mut self, dashboard: &mut DashboardState) -> bool {
+        let end = self.recording.duration();
+        if self.position >= end {
+            self.position = end;
+        }
+        let due = self
+            .recording
+            .frames
+            .partition_point(|f| f.at <= self.position);
+        if due == self.applied {
+            return false;
+        }
+        if due < self.applied {
+            dashboard.reload_from_events(&[]);
+            self.applied = 0;
+        }
+        for frame in &self.recording.frames[self.applied..due] {
+            match &frame.kind {
+                FrameKind::Tasks(content) => {
+                    let _ = dashboard.reload_tasks(content);
+                }
+                FrameKind::Events(events) => dashboard.update_from_events(events),
+            }
+        }
+        self.applied = due;
+        true
+    }
+
+    /// Move the position by `delta`, clamped to the recording
+    pub fn seek(&mut self, forward: bool, delta: Duration) {
+        self.position = if forward {
+            (self.position + delta).min(self.recording.duration())
+        } else {
+            self.position.saturating_sub(delta)
+        };
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Double (`faster`) or halve the speed within its bounds
+    pub fn change_speed(&mut self, faster: bool) {
+        let speed = if faster {
+            self.speed * 2.0
+        } else {
+            self.speed / 2.0
+        };
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    /// Handle a playback key; false for keys the dashboard should handle
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char(' ') => self.toggle_pause(),
+            KeyCode::Left => self.seek(false, SEEK_STEP),
+            KeyCode::Right => self.seek(true, SEEK_STEP),
+            KeyCode::Home => self.position = Duration::ZERO,
+            KeyCode::End => self.position = self.recording.duration(),
+            KeyCode::Char('<') => self.change_speed(false),
+            KeyCode::Char('>') => self.change_speed(true),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Status bar text: state, speed, position and length
+    pub fn label(&self) -> String {
+        let state = if self.paused {
+            "paused"
+        } else if self.position >= self.recording.duration() {
+            "ended"
+        } else {
+            "playing"
+        };
+        format!(
+            "Replay {state} {}x {} / {} (space pause, ←/→ seek, </> speed)",
+            self.speed,
+            clock(self.position),
+            clock(self.recording.duration())
+        )
+    }
+}
+
+/// `m:ss`, or `h:mm:ss` from an hour on
+fn clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#;
+    const TASKS: &str = "# Phase 1: Core\n### [ ] P1-T1: Parser\n";
+
+    #[test]
+    fn records_history_and_changes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = tmp.path().join("TASKS.md");
+        let events = tmp.path().join("events.jsonl");
+        let path = tmp.path().join("session.omcb");
+        std::fs::write(&tasks, TASKS).unwrap();
+        std::fs::write(&events, format!("{LINE}\n")).unwrap();
+
+        let mut recorder = Recorder::create(&path, &tasks, &[tmp.path().to_path_buf()]).unwrap();
+        std::fs::write(
+            &events,
+            format!("{LINE}\n{}\n", LINE.replace("\"a\"", "\"b\"")),
+        )
+        .unwrap();
+        recorder
+            .record(&FileChange::HookEventModified(events.clone()))
+            .unwrap();
+        // Nothing new: no frame
+        recorder
+            .record(&FileChange::HookEventModified(events))
+            .unwrap();
+        std::fs::write(&tasks, TASKS.replace("[ ]", "[x]")).unwrap();
+        recorder.record(&FileChange::TasksModified(tasks)).unwrap();
+
+        let recording = Recording::load(&path).unwrap();
+        let kinds: Vec<String> = recording
+            .frames
+            .iter()
+            .map(|f| match &f.kind {
+                FrameKind::Tasks(content) => format!("tasks {}", content.contains("[x]")),
+                FrameKind::Events(events) => format!("events {}", events[0].agent_id),
+            })
+            .collect();
+        assert_eq!(kinds, ["tasks false", "events a", "events b", "tasks true"]);
+    }
+
+    #[test]
+    fn playback_seeks_both_ways() {
+        let content = [
+            r#"{"format":"omcb","version":1,"started_at":"2026-02-08T10:00:00Z"}"#.to_string(),
+            format!(r#"{{"t":0,"tasks":{}}}"#, json!(TASKS)),
+            format!(r#"{{"t":20000,"events":[{LINE}]}}"#),
+            format!(
+                r#"{{"t":30000,"tasks":{}}}"#,
+                json!(TASKS.replace("[ ]", "[x]"))
+            ),
+        ]
+        .join("\n");
+        let recording = Recording::parse(&content).unwrap();
+        assert_eq!(recording.duration(), Duration::from_secs(30));
+        let mut player = Player::new(recording);
+        let mut dashboard = DashboardState::default();
+
+        assert!(player.apply(&mut dashboard));
+        assert_eq!(dashboard.total_tasks, 1);
+        assert!(dashboard.agents.is_empty());
+
+        player.seek(true, Duration::from_secs(25));
+        assert!(player.apply(&mut dashboard));
+        assert!(dashboard.agents.contains_key("a"));
+        assert!(!player.apply(&mut dashboard));
+
+        player.seek(true, Duration::from_secs(60));
+        player.apply(&mut dashboard);
+        assert_eq!(dashboard.completed_tasks, 1);
+        assert!(player.label().starts_with("Replay ended 1x 0:30 / 0:30"));
+
+        // Seeking back rebuilds the state from the start
+        player.seek(false, Duration::from_secs(15));
+        assert!(player.apply(&mut dashboard));
+        assert_eq!(dashboard.completed_tasks, 0);
+        assert!(dashboard.agents.is_empty());
+
+        player.change_speed(true);
+        player.toggle_pause();
+        assert!(player.label().starts_with("Replay paused 2x 0:15"));
+        assert!(Recording::parse("{\"format\":\"other\"}").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Follow-up reminders
+//!
+//! "Check back on this" reminders set on a task or an agent's error. They
+//! live for the session only; when one comes due the main loop shows it in
+//! the status bar and rings the terminal bell.
+
+use std::time::{Duration, Instant};
+
+/// A scheduled reminder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reminder {
+    /// What the reminder is about (task ID or `agent:<id>`); one per target
+    pub target: String,
+    /// Text shown when it comes due
+    pub message: String,
+    pub due: Instant,
+}
+
+/// Pending reminders, soonest first
+#[derive(Debug, Clone, Default)]
+pub struct Reminders {
+    pending: Vec<Reminder>,
+}
+
+impl Reminders {
+    /// Schedule a reminder on `target` after `delay`, or cancel the one it
+    /// already has. Returns `true` when a reminder was scheduled.
+    pub fn toggle(&mut self, target: &str, message: &str, now: Instant, delay: Duration) -> bool {
+        if let Some(i) = self.pending.iter().position(|r| r.target == target) {
+            self.pending.remove(i);
+            return false;
+        }
+        self.pending.push(Reminder {
+            target: target.to_string(),
+            message: message.to_string(),
+            due: now + delay,
+        });
+        self.pending.sort_by_key(|r| r.due);
+        true
+    }
+
+    /// Remove and return the reminders due at `now`, oldest first
+    pub fn take_due(&mut self, now: Instant) -> Vec<Reminder> {
+        let split = self.pending.partition_point(|r| r.due <= now);
+        self.pending.drain(..split).collect()
+    }
+
+    /// The reminder set on `target`, if any
+    pub fn get(&self, target: &str) -> Option<&Reminder> {
+        self.pending.iter().find(|r| r.target == target)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_schedules_then_cancels() {
+        let mut reminders = Reminders::default();
+        let now = Instant::now();
+        assert!(reminders.toggle("T1", "check T1", now, Duration::from_secs(60)));
+        assert_eq!(reminders.len(), 1);
+        assert!(!reminders.toggle("T1", "check T1", now, Duration::from_secs(60)));
+        assert!(reminders.is_empty());
+    }
+
+    #[test]
+    fn due_reminders_come_out_in_order() {
+        let mut reminders = Reminders::default();
+        let now = Instant::now();
+        reminders.toggle("T2", "second", now, Duration::from_secs(120));
+        reminders.toggle("T1", "first", now, Duration::from_secs(60));
+        reminders.toggle("T3", "later", now, Duration::from_secs(600));
+
+        assert!(reminders.take_due(now).is_empty());
+        let due = reminders.take_due(now + Duration::from_secs(300));
+        let messages: Vec<&str> = due.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["first", "second"]);
+        assert!(reminders.get("T3").is_some());
+        assert_eq!(reminders.len(), 1);
+    }
+}
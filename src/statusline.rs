@@ -0,0 +1,130 @@
+//! Claude Code statusline output (`statusline` subcommand)
+//!
+//! Claude Code runs the configured statusline command with the session's
+//! JSON on stdin and shows the first line it prints. This prints the task
+//! being worked on, its phase's progress and the open error count, e.g.
+//! `P2-T3 Build API | P2 3/7 | 2 errors`.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::data::state::{AgentStatus, DashboardState};
+use crate::data::tasks_parser::{ParsedPhase, TaskStatus};
+
+/// Project directory from the statusline JSON (`workspace.current_dir`,
+/// falling back to `cwd`)
+pub fn project_dir(input: &str) -> Option<PathBuf> {
+    let json: Value = serde_json::from_str(input).ok()?;
+    json["workspace"]["current_dir"]
+        .as_str()
+        .or_else(|| json["cwd"].as_str())
+        .map(PathBuf::from)
+}
+
+fn phase_progress(phase: &ParsedPhase) -> String {
+    let done = phase
+        .tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Completed)
+        .count();
+    format!("{} {done}/{}", phase.id, phase.tasks.len())
+}
+
+/// The one-line summary of `state`
+pub fn render(state: &DashboardState, now: DateTime<Utc>) -> String {
+    if state.phases.is_empty() {
+        return "no TASKS.md".to_string();
+    }
+    let tasks = || state.phases.iter().flat_map(|p| &p.tasks);
+
+    // What an active agent is on, else what TASKS.md marks in progress,
+    // else what comes next
+    let running = state
+        .agents
+        .values()
+        .filter(|a| a.status != AgentStatus::Idle)
+        .max_by_key(|a| a.last_seen)
+        .and_then(|a| a.current_task.as_deref())
+        .and_then(|id| state.find_task(id));
+    let current = running
+        .or_else(|| tasks().find(|t| t.status == TaskStatus::InProgress))
+        .map(|t| (t, ""))
+        .or_else(|| {
+            tasks()
+                .find(|t| t.status == TaskStatus::Pending)
+                .map(|t| (t, "next: "))
+        });
+
+    let mut parts = Vec::new();
+    match current {
+        Some((task, prefix)) => {
+            parts.push(format!("{prefix}{} {}", task.id, task.name));
+            if let Some(phase) = state
+                .phases
+                .iter()
+                .find(|p| p.tasks.iter().any(|t| t.id == task.id))
+            {
+                parts.push(phase_progress(phase));
+            }
+        }
+        None => parts.push(format!(
+            "{}/{} done",
+            state.completed_tasks, state.total_tasks
+        )),
+    }
+    match state.total_open_errors(now) {
+        0 => {}
+        1 => parts.push("1 error".to_string()),
+        n => parts.push(format!("{n} errors")),
+    }
+    parts.join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    const TASKS: &str = "# Phase 1: Core\n\
+        ### [x] P1-T1: Parser\n\
+        ### [InProgress] P1-T2: Writer\n\
+        ### [ ] P1-T3: Watcher\n\
+        # Phase 2: UI\n\
+        ### [ ] P2-T1: Layout\n";
+
+    #[test]
+    fn shows_current_task_phase_progress_and_errors() {
+        let now = Utc::now();
+        let mut state = DashboardState::from_tasks_content(TASKS).unwrap();
+        assert_eq!(render(&state, now), "P1-T2 Writer | P1 1/3");
+
+        let events = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P2-T1","session_id":"s"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"P2-T1","session_id":"s","error_message":"boom"}"#,
+        ]
+        .join("\n");
+        state.update_from_events(&parse_hook_events(&events).events);
+        assert_eq!(render(&state, now), "P2-T1 Layout | P2 0/1 | 1 error");
+
+        let state = DashboardState::from_tasks_content(
+            &TASKS.replace("[InProgress]", "[x]").replace("[ ]", "[x]"),
+        )
+        .unwrap();
+        assert_eq!(render(&state, now), "4/4 done");
+        let state =
+            DashboardState::from_tasks_content(&TASKS.replace("[InProgress]", "[x]")).unwrap();
+        assert_eq!(render(&state, now), "next: P1-T3 Watcher | P1 2/3");
+    }
+
+    #[test]
+    fn reads_the_project_dir_from_stdin_json() {
+        assert_eq!(
+            project_dir(r#"{"cwd":"/a","workspace":{"current_dir":"/b"}}"#),
+            Some(PathBuf::from("/b"))
+        );
+        assert_eq!(project_dir(r#"{"cwd":"/a"}"#), Some(PathBuf::from("/a")));
+        assert_eq!(project_dir("not json"), None);
+    }
+}
@@ -0,0 +1,263 @@
+//! NDJSON machine stream (`stream` subcommand)
+//!
+//! Prints one JSON object per line on stdout: every hook event as it is
+//! ingested, plus the records derived from it. The `record` field names the
+//! kind:
+//!
+//! - `event`: a hook event with its fields
+//! - `agent_status`: an agent's status changed (`from` is null for a new agent)
+//! - `error_analysis`: an `error` event's category, retryability and suggestion
+//! - `task_status` / `phase_complete`: TASKS.md changes
+//!
+//! The existing history is streamed first, oldest event first, then new
+//! lines as the watched files grow.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::analysis::rules::analyze_error;
+use crate::data::hook_parser::{EventType, HookEvent};
+use crate::data::identity::AgentIdentity;
+use crate::data::loader;
+use crate::data::state::{AgentStatus, DashboardState};
+use crate::data::tail::Tail;
+use crate::data::transitions::{Baseline, Transition};
+use crate::data::watcher::{self, FileChange, WatchConfig};
+
+/// The `event` record of `event`; unset optional fields are left out
+pub fn event_record(event: &HookEvent) -> Value {
+    let mut record = json!({
+        "record": "event",
+        "event_type": event.event_type.as_str(),
+        "timestamp": event.timestamp.to_rfc3339(),
+        "session_id": event.session_id,
+        "agent_id": event.agent_id,
+        "task_id": event.task_id,
+    });
+    let optional = [
+        ("tool_name", &event.tool_name),
+        ("file_path", &event.file_path),
+        ("args", &event.args),
+        ("cwd", &event.cwd),
+        ("error_message", &event.error_message),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value {
+            record[key] = json!(value);
+        }
+    }
+    if let Some(usage) = &event.usage {
+        record["usage"] = json!({
+            "input_tokens": usage.input_tokens,
+            "output_tokens": usage.output_tokens,
+            "cost_usd": usage.cost_usd,
+        });
+    }
+    record
+}
+
+/// The record of a TASKS.md transition; errors are reported per event instead
+fn transition_record(transition: &Transition) -> Option<Value> {
+    match transition {
+        Transition::TaskStatus {
+            task_id,
+            task_name,
+            phase_id,
+            from,
+            to,
+        } => Some(json!({
+            "record": "task_status",
+            "task_id": task_id,
+            "task_name": task_name,
+            "phase_id": phase_id,
+            "from": from.as_str(),
+            "to": to.as_str(),
+        })),
+        Transition::PhaseComplete {
+            phase_id,
+            phase_name,
+        } => Some(json!({
+            "record": "phase_complete",
+            "phase_id": phase_id,
+            "phase_name": phase_name,
+        })),
+        Transition::AgentError { .. } | Transition::Budget(_) => None,
+    }
+}
+
+/// Dashboard state and read positions carried between updates
+pub struct Stream {
+    state: DashboardState,
+    baseline: Baseline,
+    statuses: HashMap<String, AgentStatus>,
+    tail: Tail,
+}
+
+impl Stream {
+    pub fn new(state: DashboardState) -> Self {
+        Self {
+            baseline: Baseline::capture(&state),
+            statuses: HashMap::new(),
+            tail: Tail::default(),
+            state,
+        }
+    }
+
+    /// Records for `events`, in order
+    pub fn ingest(&mut self, events: &[HookEvent]) -> Vec<Value> {
+        let mut records = Vec::new();
+        for event in events {
+            records.push(event_record(event));
+            self.state.update_from_events(std::slice::from_ref(event));
+
+            let agent_id = self.state.logical_agent_id(&event.agent_id);
+            if let Some(agent) = self.state.agents.get(&agent_id) {
+                let previous = self
+                    .statuses
+                    .insert(agent.agent_id.clone(), agent.status.clone());
+                if previous.as_ref() != Some(&agent.status) {
+                    records.push(json!({
+                        "record": "agent_status",
+                        "agent_id": agent.agent_id,
+                        "task_id": event.task_id,
+                        "from": previous.map(|s| s.as_str()),
+                        "to": agent.status.as_str(),
+                        "timestamp": event.timestamp.to_rfc3339(),
+                    }));
+                }
+            }
+
+            if let (EventType::Error, Some(message)) = (&event.event_type, &event.error_message) {
+                let analysis = analyze_error(message);
+                records.push(json!({
+                    "record": "error_analysis",
+                    "agent_id": event.agent_id,
+                    "task_id": event.task_id,
+                    "message": message,
+                    "category": analysis.category.to_string(),
+                    "retryable": analysis.retryable,
+                    "suggestion": analysis.suggestion,
+                    "timestamp": event.timestamp.to_rfc3339(),
+                }));
+            }
+        }
+        records
+    }
+
+    /// Records for the task and phase changes in the reloaded TASKS.md
+    pub fn reload_tasks(&mut self, path: &Path) -> Vec<Value> {
+        let _ = self.state.reload_tasks_file(path);
+        self.baseline
+            .advance(&self.state)
+            .iter()
+            .filter_map(transition_record)
+            .collect()
+    }
+}
+
+/// Print `records` as NDJSON; false once stdout is closed
+fn emit(out: &mut impl Write, records: &[Value]) -> bool {
+    for record in records {
+        if writeln!(out, "{record}").is_err() {
+            return false;
+        }
+    }
+    out.flush().is_ok()
+}
+
+/// Stream the history in `dirs`, then follow them and `tasks_path`, until
+/// stdout is closed
+pub fn run(
+    tasks_path: &Path,
+    dirs: &[PathBuf],
+    agent_identity: Option<AgentIdentity>,
+) -> Result<()> {
+    let mut state = DashboardState::from_tasks_file(tasks_path).unwrap_or_default();
+    state.agent_identity = agent_identity;
+    let mut stream = Stream::new(state);
+    let mut out = io::stdout().lock();
+
+    let mut history: Vec<HookEvent> = loader::collect_jsonl_files(dirs)
+        .into_iter()
+        .flat_map(|(path, _)| stream.tail.read_new(&path))
+        .collect();
+    history.sort_by_key(|e| e.timestamp);
+    if !emit(&mut out, &stream.ingest(&history)) {
+        return Ok(());
+    }
+
+    let mut config = WatchConfig::new(tasks_path.to_path_buf(), dirs[0].clone());
+    if let Some(events_dir) = dirs.get(1).filter(|d| d.is_dir()) {
+        config = config.with_events_dir(events_dir.clone());
+    }
+    let (_watcher, mut rx) = watcher::start_watching(config)
+        .map_err(|e| anyhow::anyhow!("cannot follow the event files: {e}"))?;
+    while let Some(change) = rx.blocking_recv() {
+        let records = match change {
+            FileChange::TasksModified(path) => stream.reload_tasks(&path),
+            FileChange::HookEventCreated(path) | FileChange::HookEventModified(path) => {
+                let events = stream.tail.read_new(&path);
+                stream.ingest(&events)
+            }
+            FileChange::ConfigModified(_) => continue,
+        };
+        if !emit(&mut out, &records) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+
+    const TASKS: &str = "# Phase 1: Core\n### [ ] P1-T1: Parser\n";
+
+    #[test]
+    fn derives_status_changes_and_error_analysis() {
+        let mut stream = Stream::new(DashboardState::from_tasks_content(TASKS).unwrap());
+        let input = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"P1-T1","session_id":"s"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:00:01Z","agent_id":"a","task_id":"P1-T1","session_id":"s","tool_name":"Bash"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:00:02Z","agent_id":"a","task_id":"P1-T1","session_id":"s","error_message":"permission denied"}"#,
+        ]
+        .join("\n");
+        let records = stream.ingest(&parse_hook_events(&input).events);
+        let kinds: Vec<&str> = records
+            .iter()
+            .map(|r| r["record"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "event",
+                "agent_status",
+                "event",
+                "event",
+                "agent_status",
+                "error_analysis"
+            ]
+        );
+        assert_eq!(records[1]["from"], Value::Null);
+        assert_eq!(records[1]["to"], "running");
+        assert_eq!(records[2]["tool_name"], "Bash");
+        assert!(records[0].get("tool_name").is_none());
+        assert_eq!(records[4]["from"], "running");
+        assert_eq!(records[5]["category"], "Permission");
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks, TASKS.replace("[ ]", "[x]")).unwrap();
+        let records = stream.reload_tasks(&tasks);
+        assert_eq!(records[0]["record"], "task_status");
+        assert_eq!(records[0]["to"], "completed");
+        assert_eq!(records[1]["record"], "phase_complete");
+        assert_eq!(records.len(), 2);
+    }
+}
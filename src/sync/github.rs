@@ -0,0 +1,527 @@
+//! Two-way sync between TASKS.md and GitHub Issues (`sync github`)
+//!
+//! Phases map to milestones titled `P1: Name` and tasks to issues titled
+//! `P1-T1: Name`, matched by the ID before the colon. TASKS.md owns status:
+//! missing milestones and issues are created and completed tasks close their
+//! issue. GitHub owns people and labels: an issue's assignee and labels are
+//! written back as the task's `@agent` and `tags:`. Everything goes through
+//! the `gh` CLI, so its login and repository detection apply.
+
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+use crate::data::tasks_parser::{self, ParsedPhase, TaskStatus};
+use crate::data::tasks_writer;
+
+/// Issues fetched per sync; larger boards are not supported
+const MAX_ISSUES: usize = 1000;
+
+/// Body of the issues this sync creates
+const ISSUE_BODY: &str = "Tracked in TASKS.md by simple-claude-board.";
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct Login {
+    login: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct Label {
+    name: String,
+}
+
+/// An issue as `gh issue list --json` reports it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    /// `OPEN` or `CLOSED`
+    pub state: String,
+    #[serde(default)]
+    assignees: Vec<Login>,
+    #[serde(default)]
+    labels: Vec<Label>,
+}
+
+impl Issue {
+    pub fn is_open(&self) -> bool {
+        self.state.eq_ignore_ascii_case("open")
+    }
+
+    fn assignee(&self) -> Option<&str> {
+        self.assignees.first().map(|a| a.login.as_str())
+    }
+
+    fn label_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.labels.iter().map(|l| l.name.clone()).collect();
+        names.sort();
+        names
+    }
+}
+
+/// What is on GitHub now
+#[derive(Debug, Clone, Default)]
+pub struct Remote {
+    pub milestones: Vec<Milestone>,
+    pub issues: Vec<Issue>,
+}
+
+/// One step of a sync, on GitHub or in TASKS.md
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    CreateMilestone {
+        title: String,
+    },
+    CreateIssue {
+        task_id: String,
+        title: String,
+        milestone: String,
+        labels: Vec<String>,
+        /// The task is already done, so the new issue is closed right away
+        close: bool,
+    },
+    CloseIssue {
+        number: u64,
+        task_id: String,
+    },
+    SetAgent {
+        task_id: String,
+        from: Option<String>,
+        to: String,
+    },
+    SetTags {
+        task_id: String,
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+}
+
+impl Change {
+    /// Whether the change is written to TASKS.md rather than GitHub
+    pub fn is_local(&self) -> bool {
+        matches!(self, Change::SetAgent { .. } | Change::SetTags { .. })
+    }
+}
+
+/// One line of the dry-run diff: `+` creates, `~` updates on GitHub, `<`
+/// pulls into TASKS.md
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::CreateMilestone { title } => write!(f, "+ milestone \"{title}\""),
+            Change::CreateIssue {
+                title,
+                milestone,
+                labels,
+                close,
+                ..
+            } => {
+                write!(f, "+ issue \"{title}\" in \"{milestone}\"")?;
+                if !labels.is_empty() {
+                    write!(f, " [{}]", labels.join(", "))?;
+                }
+                if *close {
+                    write!(f, " (closed)")?;
+                }
+                Ok(())
+            }
+            Change::CloseIssue { number, task_id } => {
+                write!(f, "~ close #{number} ({task_id} is completed)")
+            }
+            Change::SetAgent { task_id, from, to } => {
+                let from = from.as_deref().map_or("-".to_string(), |a| format!("@{a}"));
+                write!(f, "< {task_id} agent: {from} -> @{to}")
+            }
+            Change::SetTags { task_id, from, to } => {
+                let list = |tags: &[String]| {
+                    if tags.is_empty() {
+                        "-".to_string()
+                    } else {
+                        tags.join(", ")
+                    }
+                };
+                write!(f, "< {task_id} tags: {} -> {}", list(from), list(to))
+            }
+        }
+    }
+}
+
+/// The ID a `P1-T1: Name` style title is keyed by
+fn title_id(title: &str) -> &str {
+    title.split_once(':').map_or(title, |(id, _)| id).trim()
+}
+
+/// Changes that bring GitHub and TASKS.md in line, GitHub changes first
+pub fn plan(phases: &[ParsedPhase], remote: &Remote) -> Vec<Change> {
+    let mut push = Vec::new();
+    let mut pull = Vec::new();
+    for phase in phases {
+        let milestone = remote
+            .milestones
+            .iter()
+            .find(|m| title_id(&m.title) == phase.id)
+            .map(|m| m.title.clone())
+            .unwrap_or_else(|| {
+                let title = format!("{}: {}", phase.id, phase.name);
+                push.push(Change::CreateMilestone {
+                    title: title.clone(),
+                });
+                title
+            });
+
+        for task in &phase.tasks {
+            let done = task.status == TaskStatus::Completed;
+            let Some(issue) = remote.issues.iter().find(|i| title_id(&i.title) == task.id) else {
+                push.push(Change::CreateIssue {
+                    task_id: task.id.clone(),
+                    title: format!("{}: {}", task.id, task.name),
+                    milestone: milestone.clone(),
+                    labels: task.tags.clone(),
+                    close: done,
+                });
+                continue;
+            };
+            if done && issue.is_open() {
+                push.push(Change::CloseIssue {
+                    number: issue.number,
+                    task_id: task.id.clone(),
+                });
+            }
+            if let Some(login) = issue.assignee() {
+                if task.agent.as_deref() != Some(login) {
+                    pull.push(Change::SetAgent {
+                        task_id: task.id.clone(),
+                        from: task.agent.clone(),
+                        to: login.to_string(),
+                    });
+                }
+            }
+            let mut tags = task.tags.clone();
+            tags.sort();
+            let labels = issue.label_names();
+            if labels != tags {
+                pull.push(Change::SetTags {
+                    task_id: task.id.clone(),
+                    from: task.tags.clone(),
+                    to: labels,
+                });
+            }
+        }
+    }
+    push.extend(pull);
+    push
+}
+
+/// `owner/name` for `gh api` paths; `{owner}/{repo}` lets gh fill in the
+/// current directory's repository
+fn api_repo(repo: Option<&str>) -> &str {
+    repo.unwrap_or("{owner}/{repo}")
+}
+
+/// `--repo owner/name` for `gh issue` commands, when given
+fn repo_args(repo: Option<&str>) -> Vec<String> {
+    repo.map(|r| vec!["--repo".to_string(), r.to_string()])
+        .unwrap_or_default()
+}
+
+/// `gh` invocations carrying out a GitHub change. A closed new issue is
+/// closed by `apply` once its number is known.
+pub fn gh_args(change: &Change, repo: Option<&str>) -> Vec<Vec<String>> {
+    let strings = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+    match change {
+        Change::CreateMilestone { title } => vec![strings(&[
+            "api",
+            &format!("repos/{}/milestones", api_repo(repo)),
+            "-f",
+            &format!("title={title}"),
+            "--silent",
+        ])],
+        Change::CreateIssue {
+            title,
+            milestone,
+            labels,
+            ..
+        } => {
+            // Labels must exist before an issue can use them
+            let mut commands: Vec<Vec<String>> = labels
+                .iter()
+                .map(|label| {
+                    let mut args = strings(&["label", "create", label, "--force"]);
+                    args.extend(repo_args(repo));
+                    args
+                })
+                .collect();
+            let mut create = strings(&[
+                "issue",
+                "create",
+                "--title",
+                title,
+                "--milestone",
+                milestone,
+                "--body",
+                ISSUE_BODY,
+            ]);
+            for label in labels {
+                create.extend(strings(&["--label", label]));
+            }
+            create.extend(repo_args(repo));
+            commands.push(create);
+            commands
+        }
+        Change::CloseIssue { number, .. } => {
+            let mut args = strings(&["issue", "close", &number.to_string()]);
+            args.extend(repo_args(repo));
+            vec![args]
+        }
+        Change::SetAgent { .. } | Change::SetTags { .. } => Vec::new(),
+    }
+}
+
+/// Run `gh` and return its stdout; the error carries its stderr
+fn gh(args: &[String]) -> anyhow::Result<String> {
+    let output = Command::new("gh")
+        .args(args)
+        .output()
+        .context("cannot run `gh`; install the GitHub CLI and run `gh auth login`")?;
+    if !output.status.success() {
+        bail!(
+            "gh {} failed: {}",
+            args.first().map_or("", String::as_str),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Milestones from `gh api --paginate --jq '.[]'`, one JSON object per line
+pub fn parse_milestones(output: &str) -> anyhow::Result<Vec<Milestone>> {
+    output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).context("unexpected milestone JSON from gh"))
+        .collect()
+}
+
+fn fetch_remote(repo: Option<&str>) -> anyhow::Result<Remote> {
+    let milestones = gh(&[
+        "api".to_string(),
+        "--paginate".to_string(),
+        format!("repos/{}/milestones?state=all", api_repo(repo)),
+        "--jq".to_string(),
+        ".[] | {number, title}".to_string(),
+    ])?;
+    let mut args: Vec<String> = [
+        "issue",
+        "list",
+        "--state",
+        "all",
+        "--limit",
+        &MAX_ISSUES.to_string(),
+        "--json",
+        "number,title,state,assignees,labels",
+    ]
+    .iter()
+    .map(|a| a.to_string())
+    .collect();
+    args.extend(repo_args(repo));
+    let issues = gh(&args)?;
+    Ok(Remote {
+        milestones: parse_milestones(&milestones)?,
+        issues: serde_json::from_str(&issues).context("unexpected issue JSON from gh")?,
+    })
+}
+
+/// Carry out `change`, on GitHub through `gh` or in the TASKS.md at `path`
+fn apply(change: &Change, path: &Path, repo: Option<&str>) -> anyhow::Result<()> {
+    match change {
+        Change::SetAgent { task_id, to, .. } => {
+            tasks_writer::set_task_agent(path, task_id, to)?;
+        }
+        Change::SetTags { task_id, to, .. } => {
+            tasks_writer::set_task_tags(path, task_id, to)?;
+        }
+        _ => {
+            let mut url = String::new();
+            for args in gh_args(change, repo) {
+                url = gh(&args)?;
+            }
+            if let Change::CreateIssue { close: true, .. } = change {
+                // `gh issue create` prints the new issue's URL
+                let number = url.trim().rsplit('/').next().unwrap_or_default();
+                let mut args = vec!["issue".to_string(), "close".to_string(), number.to_string()];
+                args.extend(repo_args(repo));
+                gh(&args)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the sync plan for the TASKS.md at `path`, and carry it out when
+/// `apply_changes` is set
+pub fn run(path: &Path, repo: Option<&str>, apply_changes: bool) -> anyhow::Result<()> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+    let phases = tasks_parser::parse_tasks_md(&content).map_err(anyhow::Error::msg)?;
+    let remote = fetch_remote(repo)?;
+    let changes = plan(&phases, &remote);
+    let target = repo.unwrap_or("the current repository");
+    if changes.is_empty() {
+        println!("TASKS.md and {target} are in sync");
+        return Ok(());
+    }
+
+    if !apply_changes {
+        println!("GitHub sync plan for {target} (dry run; pass --apply to make these changes):");
+        for change in &changes {
+            println!("  {change}");
+        }
+        return Ok(());
+    }
+    for change in &changes {
+        apply(change, path, repo).with_context(|| format!("while applying `{change}`"))?;
+        println!("  {change}");
+    }
+    let local = changes.iter().filter(|c| c.is_local()).count();
+    println!(
+        "Applied {} GitHub change(s) and {local} TASKS.md change(s)",
+        changes.len() - local
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: u64, title: &str, state: &str, assignee: &str, labels: &[&str]) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            state: state.to_string(),
+            assignees: (!assignee.is_empty())
+                .then(|| Login {
+                    login: assignee.to_string(),
+                })
+                .into_iter()
+                .collect(),
+            labels: labels
+                .iter()
+                .map(|l| Label {
+                    name: l.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn phases() -> Vec<ParsedPhase> {
+        tasks_parser::parse_tasks_md(
+            "# Phase 1: Engine\n\
+             ### [x] P1-T1: Parser\n- **tags**: core\n\
+             ### [ ] P1-T2: Watcher\n- **담당**: @bot\n\
+             ### [x] P1-T3: Writer\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn plan_creates_missing_items() {
+        let changes = plan(&phases(), &Remote::default());
+        let text: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            text,
+            vec![
+                "+ milestone \"P1: Engine\"",
+                "+ issue \"P1-T1: Parser\" in \"P1: Engine\" [core] (closed)",
+                "+ issue \"P1-T2: Watcher\" in \"P1: Engine\"",
+                "+ issue \"P1-T3: Writer\" in \"P1: Engine\" (closed)",
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_pushes_status_and_pulls_people_and_labels() {
+        let remote = Remote {
+            milestones: vec![Milestone {
+                number: 1,
+                title: "P1: Old name".to_string(),
+            }],
+            issues: vec![
+                issue(10, "P1-T1: Parser", "CLOSED", "", &["core"]),
+                issue(11, "P1-T2: Watcher", "OPEN", "alice", &["ui", "api"]),
+                issue(12, "P1-T3: Writer (renamed)", "OPEN", "", &[]),
+            ],
+        };
+        let changes = plan(&phases(), &remote);
+        let text: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            text,
+            vec![
+                "~ close #12 (P1-T3 is completed)",
+                "< P1-T2 agent: @bot -> @alice",
+                "< P1-T2 tags: - -> api, ui",
+            ]
+        );
+        assert!(!changes[0].is_local() && changes[1].is_local());
+    }
+
+    #[test]
+    fn gh_commands_for_changes() {
+        let create = Change::CreateIssue {
+            task_id: "P1-T1".to_string(),
+            title: "P1-T1: Parser".to_string(),
+            milestone: "P1: Engine".to_string(),
+            labels: vec!["core".to_string()],
+            close: false,
+        };
+        let commands = gh_args(&create, Some("o/r"));
+        assert_eq!(
+            commands[0],
+            ["label", "create", "core", "--force", "--repo", "o/r"]
+        );
+        assert!(commands[1].starts_with(&["issue".to_string(), "create".to_string()]));
+        assert!(commands[1].ends_with(&[
+            "--label".to_string(),
+            "core".to_string(),
+            "--repo".to_string(),
+            "o/r".to_string()
+        ]));
+
+        let milestone = Change::CreateMilestone {
+            title: "P1: Engine".to_string(),
+        };
+        assert_eq!(
+            gh_args(&milestone, None)[0][1],
+            "repos/{owner}/{repo}/milestones"
+        );
+        let close = Change::CloseIssue {
+            number: 7,
+            task_id: "P1-T1".to_string(),
+        };
+        assert_eq!(gh_args(&close, None), vec![vec!["issue", "close", "7"]]);
+    }
+
+    #[test]
+    fn gh_output_is_parsed() {
+        let milestones = parse_milestones("{\"number\":1,\"title\":\"P1: A\"}\n\n").unwrap();
+        assert_eq!(milestones[0].title, "P1: A");
+        assert!(parse_milestones("not json").is_err());
+
+        let issues: Vec<Issue> = serde_json::from_str(
+            r#"[{"number":3,"title":"P1-T1: x","state":"OPEN","assignees":[{"login":"al","name":""}],"labels":[{"name":"b","color":"fff"},{"name":"a"}]}]"#,
+        )
+        .unwrap();
+        assert!(issues[0].is_open());
+        assert_eq!(issues[0].assignee(), Some("al"));
+        assert_eq!(issues[0].label_names(), vec!["a", "b"]);
+    }
+}
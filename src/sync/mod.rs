@@ -0,0 +1,3 @@
+//! Syncing TASKS.md with external trackers (`sync` subcommand)
+
+pub mod github;
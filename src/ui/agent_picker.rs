@@ -0,0 +1,215 @@
+//! Agent picker
+//!
+//! Centered popup listing the agent names known from hook history and
+//! `@agent` annotations. Choosing one assigns it to the selected task.
+//! Follows the same pattern as `SessionPicker`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::ParsedTask;
+use crate::ui::theme::Theme;
+
+/// Task being (re)assigned and the agent list offered for it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentPickerState {
+    pub task_id: String,
+    /// The task's current agent, if any
+    pub current: Option<String>,
+    pub agents: Vec<String>,
+    pub selected: usize,
+}
+
+impl AgentPickerState {
+    /// Picker for `task` with its current agent preselected; `None` when no
+    /// agent names are known
+    pub fn new(state: &DashboardState, task: &ParsedTask) -> Option<Self> {
+        let agents = state.known_agents();
+        if agents.is_empty() {
+            return None;
+        }
+        let selected = task
+            .agent
+            .as_ref()
+            .and_then(|a| agents.iter().position(|n| n == a))
+            .unwrap_or(0);
+        Some(Self {
+            task_id: task.id.clone(),
+            current: task.agent.clone(),
+            agents,
+            selected,
+        })
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.agents.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// The highlighted agent name
+    pub fn selected_agent(&self) -> Option<&str> {
+        self.agents.get(self.selected).map(String::as_str)
+    }
+}
+
+/// Agent picker popup widget
+pub struct AgentPicker<'a> {
+    state: &'a AgentPickerState,
+    theme: Theme,
+}
+
+impl<'a> AgentPicker<'a> {
+    pub fn new(state: &'a AgentPickerState) -> Self {
+        Self {
+            state,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(&self, area: Rect) -> Rect {
+        let wanted = self.state.agents.len() as u16 + 6;
+        let width = 44.min(area.width.saturating_sub(4));
+        let height = wanted.min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self, visible_rows: usize) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" Assign agent to ", Style::default().fg(self.theme.muted)),
+                Span::styled(
+                    self.state.task_id.clone(),
+                    Style::default()
+                        .fg(self.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::raw(""),
+        ];
+
+        // Keep the selection in view when the list is taller than the popup
+        let skip = (self.state.selected + 1).saturating_sub(visible_rows);
+        for (i, name) in self
+            .state
+            .agents
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible_rows)
+        {
+            let is_selected = i == self.state.selected;
+            let prefix = if is_selected { " > " } else { "   " };
+            let marker = if self.state.current.as_ref() == Some(name) {
+                "* "
+            } else {
+                "  "
+            };
+            let name_style = if is_selected {
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(self.theme.accent)),
+                Span::raw(marker),
+                Span::styled(format!("@{name}"), name_style),
+            ]));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            " j/k move  Enter assign  Esc close",
+            Style::default().fg(self.theme.muted),
+        ));
+        lines
+    }
+}
+
+impl<'a> Widget for AgentPicker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Agents ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        // Borders, two header lines and two footer lines
+        let visible_rows = popup_area.height.saturating_sub(6).max(1) as usize;
+        Paragraph::new(self.build_lines(visible_rows))
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> DashboardState {
+        DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [ ] T1: a\n- @writer\n### [ ] T2: b\n- @backend\n### [ ] T3: c\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn preselects_current_agent_and_clamps_moves() {
+        let state = state();
+        let mut picker = AgentPickerState::new(&state, &state.phases[0].tasks[0]).unwrap();
+        assert_eq!(picker.agents, vec!["backend", "writer"]);
+        assert_eq!(picker.selected_agent(), Some("writer"));
+        picker.move_down();
+        assert_eq!(picker.selected, 1);
+        picker.move_up();
+        picker.move_up();
+        assert_eq!(picker.selected_agent(), Some("backend"));
+
+        let unassigned = AgentPickerState::new(&state, &state.phases[0].tasks[2]).unwrap();
+        assert_eq!(unassigned.selected, 0);
+        assert!(unassigned.current.is_none());
+    }
+
+    #[test]
+    fn no_known_agents_no_picker() {
+        let state = DashboardState::from_tasks_content("# Phase 0: A\n### [ ] T1: a\n").unwrap();
+        assert!(AgentPickerState::new(&state, &state.phases[0].tasks[0]).is_none());
+    }
+
+    #[test]
+    fn picker_marks_current_and_scrolls_to_selection() {
+        let state = state();
+        let picker = AgentPickerState::new(&state, &state.phases[0].tasks[0]).unwrap();
+        let text: Vec<String> = AgentPicker::new(&picker)
+            .build_lines(1)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l == " > * @writer"), "{text:?}");
+        assert!(!text.iter().any(|l| l.contains("@backend")), "{text:?}");
+
+        let area = Rect::new(0, 0, 10, 5);
+        AgentPicker::new(&picker).render(area, &mut Buffer::empty(area));
+    }
+}
@@ -0,0 +1,172 @@
+//! Blocked reason prompt
+//!
+//! Single-line popup asking why tasks are being blocked. The reason is
+//! written under each task as a `- **blocked**: reason` line; leaving it
+//! empty blocks without one.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::ui::line_input::LineInput;
+use crate::ui::task_form::FormOutcome;
+use crate::ui::theme::Theme;
+
+/// Longest reason accepted, in chars
+pub const MAX_REASON_CHARS: usize = 60;
+
+/// Tasks being blocked and the reason typed so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockReasonState {
+    pub task_ids: Vec<String>,
+    pub input: LineInput,
+    /// Write error shown above the key hints
+    pub error: Option<String>,
+}
+
+impl BlockReasonState {
+    /// Prompt for `task_ids`, starting from an earlier `reason` if any
+    pub fn new(task_ids: Vec<String>, reason: Option<&str>) -> Self {
+        Self {
+            task_ids,
+            input: LineInput::new(reason.unwrap_or_default()).with_max_chars(MAX_REASON_CHARS),
+            error: None,
+        }
+    }
+
+    /// Handle a key; Enter submits (an empty reason is allowed), Esc cancels
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormOutcome {
+        match key.code {
+            KeyCode::Esc => return FormOutcome::Cancel,
+            KeyCode::Enter => return FormOutcome::Submit,
+            _ => {
+                self.input.handle_key(key);
+            }
+        }
+        FormOutcome::Editing
+    }
+}
+
+/// Blocked reason popup widget
+pub struct BlockReasonPrompt<'a> {
+    state: &'a BlockReasonState,
+    theme: Theme,
+}
+
+impl<'a> BlockReasonPrompt<'a> {
+    pub fn new(state: &'a BlockReasonState) -> Self {
+        Self {
+            state,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(area: Rect) -> Rect {
+        let width = (MAX_REASON_CHARS as u16 + 6).min(area.width.saturating_sub(4));
+        let height = 8.min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(self.theme.muted);
+        let target = match self.state.task_ids.as_slice() {
+            [id] => id.clone(),
+            ids => format!("{} tasks", ids.len()),
+        };
+        let text_style = Style::default().fg(self.theme.text);
+
+        vec![
+            Line::from(vec![
+                Span::styled(" Block ", muted),
+                Span::styled(target, text_style.add_modifier(Modifier::BOLD)),
+                Span::styled(" because:", muted),
+            ]),
+            Line::raw(""),
+            Line::from(
+                std::iter::once(Span::styled(" ", text_style))
+                    .chain(self.state.input.spans(MAX_REASON_CHARS + 1, text_style))
+                    .collect::<Vec<_>>(),
+            ),
+            match self.state.error {
+                Some(ref e) => {
+                    Line::styled(format!(" {e}"), Style::default().fg(self.theme.failed))
+                }
+                None => Line::raw(""),
+            },
+            Line::styled(" Enter block (reason optional)  Esc cancel", muted),
+        ]
+    }
+}
+
+impl<'a> Widget for BlockReasonPrompt<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Blocked reason ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.blocked));
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn editing_keeps_reason_short() {
+        let mut state = BlockReasonState::new(vec!["T1".into()], Some("API"));
+        assert_eq!(state.input.cursor(), 3);
+        state.handle_key(key(KeyCode::Home));
+        state.handle_key(key(KeyCode::Char('파')));
+        state.handle_key(key(KeyCode::Delete));
+        assert_eq!(state.input.text(), "파PI");
+        for _ in 0..100 {
+            state.handle_key(key(KeyCode::Char('x')));
+        }
+        assert_eq!(state.input.text().chars().count(), MAX_REASON_CHARS);
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), FormOutcome::Submit);
+        assert_eq!(state.handle_key(key(KeyCode::Esc)), FormOutcome::Cancel);
+    }
+
+    #[test]
+    fn prompt_names_single_task_or_count() {
+        let text = |state: &BlockReasonState| -> Vec<String> {
+            BlockReasonPrompt::new(state)
+                .build_lines()
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        let single = BlockReasonState::new(vec!["T1".into()], Some("keys"));
+        assert_eq!(text(&single)[0], " Block T1 because:");
+        assert_eq!(text(&single)[2], " keys ");
+
+        let bulk = BlockReasonState::new(vec!["T1".into(), "T2".into()], None);
+        assert_eq!(text(&bulk)[0], " Block 2 tasks because:");
+
+        let area = Rect::new(0, 0, 10, 5);
+        BlockReasonPrompt::new(&bulk).render(area, &mut Buffer::empty(area));
+    }
+}
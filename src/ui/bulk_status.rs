@@ -0,0 +1,270 @@
+//! Bulk phase status popup
+//!
+//! Lists whole-phase status operations with the number of tasks each would
+//! change, then asks for a y/n confirmation before they are written to
+//! TASKS.md in one go. Follows the same pattern as `AgentPicker`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::tasks_parser::{ParsedPhase, TaskStatus};
+use crate::ui::theme::Theme;
+
+/// A status change applied to several tasks of a phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkOp {
+    /// Every unfinished task becomes Completed
+    MarkDone,
+    /// Unfinished tasks that are not already blocked become Blocked
+    BlockRemaining,
+    /// Unfinished tasks go back to Pending
+    ResetRemaining,
+}
+
+impl BulkOp {
+    const ALL: [BulkOp; 3] = [
+        BulkOp::MarkDone,
+        BulkOp::BlockRemaining,
+        BulkOp::ResetRemaining,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BulkOp::MarkDone => "Mark phase done",
+            BulkOp::BlockRemaining => "Block remaining tasks",
+            BulkOp::ResetRemaining => "Reset remaining to pending",
+        }
+    }
+
+    /// Status tag written to TASKS.md, without brackets
+    pub fn status_tag(self) -> &'static str {
+        match self {
+            BulkOp::MarkDone => "x",
+            BulkOp::BlockRemaining => "Blocked",
+            BulkOp::ResetRemaining => " ",
+        }
+    }
+
+    fn applies_to(self, status: &TaskStatus) -> bool {
+        match self {
+            BulkOp::MarkDone => *status != TaskStatus::Completed,
+            BulkOp::BlockRemaining => {
+                !matches!(status, TaskStatus::Completed | TaskStatus::Blocked)
+            }
+            BulkOp::ResetRemaining => {
+                !matches!(status, TaskStatus::Completed | TaskStatus::Pending)
+            }
+        }
+    }
+}
+
+/// Phase being edited, the operations that would change something, and
+/// whether the y/n confirmation is showing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkStatusState {
+    pub phase_id: String,
+    /// Each available operation with the IDs of the tasks it changes
+    pub ops: Vec<(BulkOp, Vec<String>)>,
+    pub selected: usize,
+    pub confirming: bool,
+}
+
+impl BulkStatusState {
+    /// Popup for `phase`; `None` when no operation would change any task
+    pub fn new(phase: &ParsedPhase) -> Option<Self> {
+        let ops: Vec<(BulkOp, Vec<String>)> = BulkOp::ALL
+            .iter()
+            .map(|&op| {
+                let ids = phase
+                    .tasks
+                    .iter()
+                    .filter(|t| op.applies_to(&t.status))
+                    .map(|t| t.id.clone())
+                    .collect::<Vec<_>>();
+                (op, ids)
+            })
+            .filter(|(_, ids)| !ids.is_empty())
+            .collect();
+        (!ops.is_empty()).then(|| Self {
+            phase_id: phase.id.clone(),
+            ops,
+            selected: 0,
+            confirming: false,
+        })
+    }
+
+    pub fn move_down(&mut self) {
+        if !self.confirming && self.selected + 1 < self.ops.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if !self.confirming {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    /// The highlighted operation and the tasks it changes
+    pub fn selected_op(&self) -> Option<&(BulkOp, Vec<String>)> {
+        self.ops.get(self.selected)
+    }
+}
+
+/// Bulk status popup widget
+pub struct BulkStatusPopup<'a> {
+    state: &'a BulkStatusState,
+    theme: Theme,
+}
+
+impl<'a> BulkStatusPopup<'a> {
+    pub fn new(state: &'a BulkStatusState) -> Self {
+        Self {
+            state,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(&self, area: Rect) -> Rect {
+        let width = 44.min(area.width.saturating_sub(4));
+        let height = (self.state.ops.len() as u16 + 6).min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(self.theme.muted);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" Phase ", muted),
+                Span::styled(
+                    self.state.phase_id.clone(),
+                    Style::default()
+                        .fg(self.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::raw(""),
+        ];
+
+        if self.state.confirming {
+            if let Some((op, ids)) = self.state.selected_op() {
+                let noun = if ids.len() == 1 { "task" } else { "tasks" };
+                lines.push(Line::styled(
+                    format!(" {}: {} {noun}?", op.label(), ids.len()),
+                    Style::default().fg(self.theme.blocked),
+                ));
+            }
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled(" [y]", Style::default().fg(self.theme.completed)),
+                Span::raw(" Yes  "),
+                Span::styled("[n]", Style::default().fg(self.theme.failed)),
+                Span::raw(" No"),
+            ]));
+            return lines;
+        }
+
+        for (i, (op, ids)) in self.state.ops.iter().enumerate() {
+            let is_selected = i == self.state.selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    if is_selected { " > " } else { "   " },
+                    Style::default().fg(self.theme.accent),
+                ),
+                Span::styled(op.label(), style),
+                Span::styled(format!(" ({})", ids.len()), muted),
+            ]));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(" j/k move  Enter choose  Esc close", muted));
+        lines
+    }
+}
+
+impl<'a> Widget for BulkStatusPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Phase status ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::state::DashboardState;
+
+    fn sample_state() -> DashboardState {
+        let input = include_str!("../../tests/fixtures/sample_tasks.md");
+        DashboardState::from_tasks_content(input).unwrap()
+    }
+
+    #[test]
+    fn ops_list_only_tasks_they_change() {
+        let state = sample_state();
+        // P1: InProgress, Pending, Failed
+        let popup = BulkStatusState::new(&state.phases[1]).unwrap();
+        let ids = |op| {
+            popup
+                .ops
+                .iter()
+                .find(|(o, _)| *o == op)
+                .map(|(_, ids)| ids.len())
+        };
+        assert_eq!(ids(BulkOp::MarkDone), Some(3));
+        assert_eq!(ids(BulkOp::BlockRemaining), Some(3));
+        assert_eq!(ids(BulkOp::ResetRemaining), Some(2));
+
+        // P0 is fully completed: nothing to do
+        assert!(BulkStatusState::new(&state.phases[0]).is_none());
+    }
+
+    #[test]
+    fn confirmation_freezes_selection_and_shows_count() {
+        let state = sample_state();
+        let mut popup = BulkStatusState::new(&state.phases[1]).unwrap();
+        popup.move_down();
+        popup.confirming = true;
+        popup.move_down();
+        assert_eq!(popup.selected, 1);
+
+        let text: Vec<String> = BulkStatusPopup::new(&popup)
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(
+            text.contains(&" Block remaining tasks: 3 tasks?".to_string()),
+            "{text:?}"
+        );
+
+        let area = Rect::new(0, 0, 10, 5);
+        BulkStatusPopup::new(&popup).render(area, &mut Buffer::empty(area));
+    }
+}
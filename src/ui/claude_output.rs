@@ -11,7 +11,39 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
+use chrono::{DateTime, Utc};
+
+use crate::analysis::health::{error_rate, AgentHealth, HealthThresholds};
+use crate::analysis::loops::LoopThresholds;
 use crate::data::state::{AgentState, AgentStatus, DashboardState};
+use crate::ui::gantt::truncate_to_width;
+use crate::ui::icons::IconSet;
+use crate::ui::theme::Theme;
+
+/// Seconds of silence after which an active agent's age turns yellow
+const AGE_WARN_SECS: i64 = 30;
+/// Seconds of silence after which an active agent's age turns red
+const AGE_STALE_SECS: i64 = 120;
+
+/// Braille frames for the running-agent spinner
+const SPINNER_FRAMES: [&str; 10] = [
+    "\u{280B}", "\u{2819}", "\u{2839}", "\u{2838}", "\u{283C}", "\u{2834}", "\u{2826}", "\u{2827}",
+    "\u{2807}", "\u{280F}",
+];
+
+/// Compact "time since" label: `12s ago`, `3m ago`, `2h ago`, `4d ago`
+fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs < 60 {
+        format!("{secs}s ago")
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86_400)
+    }
+}
 
 /// Agent activity panel widget
 pub struct AgentPanel<'a> {
@@ -20,6 +52,14 @@ pub struct AgentPanel<'a> {
     selected_agent: Option<&'a str>,
     focused: bool,
     selected_index: usize,
+    icons: IconSet,
+    theme: Theme,
+    /// Reference time for last-activity ages
+    now: DateTime<Utc>,
+    /// Spinner frame shown for Running agents instead of the status icon
+    spinner_frame: Option<usize>,
+    health: HealthThresholds,
+    loops: LoopThresholds,
 }
 
 impl<'a> AgentPanel<'a> {
@@ -29,9 +69,56 @@ impl<'a> AgentPanel<'a> {
             selected_agent: None,
             focused: false,
             selected_index: 0,
+            icons: IconSet::default(),
+            theme: Theme::default(),
+            now: Utc::now(),
+            spinner_frame: None,
+            health: HealthThresholds::default(),
+            loops: LoopThresholds::default(),
         }
     }
 
+    pub fn with_loops(mut self, loops: LoopThresholds) -> Self {
+        self.loops = loops;
+        self
+    }
+
+    pub fn with_health(mut self, health: HealthThresholds) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_spinner(mut self, frame: usize) -> Self {
+        self.spinner_frame = Some(frame);
+        self
+    }
+
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Color for an agent's last-activity age; idle agents are expected to be quiet
+    fn age_color(&self, agent: &AgentState, secs: i64) -> Color {
+        if agent.status == AgentStatus::Idle || secs < AGE_WARN_SECS {
+            self.theme.muted
+        } else if secs < AGE_STALE_SECS {
+            self.theme.in_progress
+        } else {
+            self.theme.failed
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
     pub fn with_selected_agent(mut self, agent: Option<&'a str>) -> Self {
         self.selected_agent = agent;
         self
@@ -80,11 +167,13 @@ impl<'a> AgentPanel<'a> {
                     .selected_agent
                     .is_some_and(|name| agent.agent_id.contains(name));
 
-            let (status_icon, status_color) = match agent.status {
-                AgentStatus::Running => (">>", Color::Green),
-                AgentStatus::Error => ("!!", Color::Red),
-                AgentStatus::Idle => ("--", Color::DarkGray),
+            let status_icon = match self.spinner_frame {
+                Some(frame) if agent.status == AgentStatus::Running => {
+                    SPINNER_FRAMES[frame % SPINNER_FRAMES.len()]
+                }
+                _ => self.icons.agent_status(&agent.status),
             };
+            let status_color = self.theme.agent_status(&agent.status);
 
             let name_style = if is_highlighted {
                 Style::default()
@@ -105,6 +194,34 @@ impl<'a> AgentPanel<'a> {
                 Span::styled(agent.agent_id.clone(), name_style),
             ];
 
+            // Degraded / failing badge with the rolling error rate
+            let health = self.health.assess(agent);
+            if let (AgentHealth::Degraded | AgentHealth::Failing, Some(rate)) =
+                (health, error_rate(agent))
+            {
+                let color = match health {
+                    AgentHealth::Failing => self.theme.failed,
+                    _ => self.theme.in_progress,
+                };
+                spans.push(Span::styled(
+                    format!(" [{} {:.0}%]", health.as_str(), rate * 100.0),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+            }
+
+            // Same tool call repeated within the loop window
+            if let Some(tool_loop) = self.loops.detect(agent) {
+                spans.push(Span::styled(
+                    format!(
+                        " [loop {} \u{00D7}{}]",
+                        tool_loop.tool_name, tool_loop.count
+                    ),
+                    Style::default()
+                        .fg(self.theme.in_progress)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+
             if let Some(ref task) = agent.current_task {
                 spans.push(Span::styled(
                     format!(" [{task}]"),
@@ -112,17 +229,53 @@ impl<'a> AgentPanel<'a> {
                 ));
             }
 
+            if let Some(ref checkout) = agent.checkout {
+                spans.push(Span::styled(
+                    format!(" {} {}", self.icons.branch(), checkout.branch),
+                    Style::default().fg(self.theme.muted),
+                ));
+            }
+
             if let Some(ref tool) = agent.current_tool {
+                let glyph = self
+                    .icons
+                    .tool(tool)
+                    .map(|g| format!("{g} "))
+                    .unwrap_or_default();
                 spans.push(Span::styled(
-                    format!(" -> {tool}"),
+                    format!(" -> {glyph}{tool}"),
                     Style::default().fg(Color::Yellow),
                 ));
             }
 
-            if agent.error_count > 0 {
+            if agent.presumed_finished {
+                spans.push(Span::styled(
+                    " (presumed finished)",
+                    Style::default().fg(self.theme.muted),
+                ));
+            }
+
+            // Most recent open error for this agent colors the count and
+            // summary line; a muted agent shows neither
+            let muted = self.state.muted_until(&agent.agent_id, self.now).is_some();
+            let last_error = self
+                .state
+                .open_errors_for_agent(&agent.agent_id)
+                .next_back()
+                .filter(|_| !muted);
+
+            if muted {
+                spans.push(Span::styled(
+                    " (muted)",
+                    Style::default().fg(self.theme.muted),
+                ));
+            } else if agent.open_errors > 0 {
+                let color = last_error
+                    .map(|e| self.theme.error_category(&e.category))
+                    .unwrap_or(self.theme.failed);
                 spans.push(Span::styled(
-                    format!(" ({} errs)", agent.error_count),
-                    Style::default().fg(Color::Red),
+                    format!(" ({} errs)", agent.open_errors),
+                    Style::default().fg(color),
                 ));
             }
 
@@ -131,25 +284,37 @@ impl<'a> AgentPanel<'a> {
                 Style::default().fg(Color::DarkGray),
             ));
 
+            if let Some(last_seen) = agent.last_seen {
+                let secs = (self.now - last_seen).num_seconds();
+                spans.push(Span::styled(
+                    format!(" ({})", format_age(secs)),
+                    Style::default().fg(self.age_color(agent, secs)),
+                ));
+            }
+
             lines.push(Line::from(spans));
 
-            // Show most recent error for this agent (if any)
-            if let Some(err) = self
-                .state
-                .recent_errors
-                .iter()
-                .rev()
-                .find(|e| e.agent_id == agent.agent_id)
-            {
+            let sharing = self.state.agents_sharing_branch(&agent.agent_id);
+            if !sharing.is_empty() {
+                lines.push(Line::styled(
+                    format!("    ! same branch as {}", sharing.join(", ")),
+                    Style::default().fg(self.theme.in_progress),
+                ));
+            }
+
+            if let Some(err) = last_error {
                 let retry_str = if err.retryable { "retry" } else { "no retry" };
-                let msg_short = if err.message.len() > 40 {
-                    format!("{}...", &err.message[..37])
-                } else {
-                    err.message.clone()
-                };
+                let msg_short = truncate_to_width(&err.message, 40);
+                let color = self.theme.error_category(&err.category);
                 lines.push(Line::from(vec![
-                    Span::styled("    !! ", Style::default().fg(Color::Red)),
-                    Span::styled(msg_short, Style::default().fg(Color::Red)),
+                    Span::styled(
+                        format!("    [{}] ", err.category.badge()),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{msg_short}{}", err.repeat_suffix()),
+                        Style::default().fg(color),
+                    ),
                     Span::styled(
                         format!(" → {} ({retry_str})", err.category),
                         Style::default().fg(Color::DarkGray),
@@ -172,12 +337,16 @@ impl<'a> AgentPanel<'a> {
 impl<'a> Widget for AgentPanel<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let border_color = if self.focused {
-            Color::Cyan
+            self.theme.accent
         } else {
-            Color::DarkGray
+            self.theme.muted
+        };
+        let title = match self.state.session_filter {
+            Some(ref id) => format!(" Agents [{id}] "),
+            None => " Agents ".to_string(),
         };
         let block = Block::default()
-            .title(" Agents ")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(border_color));
 
@@ -202,6 +371,35 @@ mod tests {
         state
     }
 
+    #[test]
+    fn branch_shown_and_shared_branch_warned() {
+        use crate::data::git_branch::Checkout;
+
+        let mut state = state_with_agents();
+        let checkout = Checkout {
+            branch: "feat/login".to_string(),
+            worktree: None,
+            repo: "/repo/.git".into(),
+        };
+        let mut second = state.agents["backend-specialist-1"].clone();
+        second.agent_id = "backend-specialist-2".to_string();
+        state.agents.insert(second.agent_id.clone(), second);
+        for agent in state.agents.values_mut() {
+            agent.status = AgentStatus::Running;
+            agent.checkout = Some(checkout.clone());
+        }
+        let text: Vec<String> = AgentPanel::new(&state)
+            .with_icons(IconSet::Ascii)
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text[0].contains(" on feat/login"), "{text:?}");
+        assert!(text
+            .iter()
+            .any(|l| l.contains("! same branch as backend-specialist-2")));
+    }
+
     #[test]
     fn agent_panel_empty() {
         let state = DashboardState::default();
@@ -228,6 +426,112 @@ mod tests {
         assert!(!lines.is_empty());
     }
 
+    #[test]
+    fn build_lines_use_configured_icons() {
+        let state = state_with_agents();
+        let text = |panel: AgentPanel| -> String {
+            panel
+                .build_lines()
+                .iter()
+                .flat_map(|l| l.spans.iter())
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+        let ascii = text(AgentPanel::new(&state));
+        let unicode = text(AgentPanel::new(&state).with_icons(IconSet::Unicode));
+        assert!(ascii.contains("--") || ascii.contains(">>") || ascii.contains("!!"));
+        assert!(!unicode.contains(">>") && !unicode.contains("!!"));
+    }
+
+    #[test]
+    fn unhealthy_agents_get_a_badge() {
+        let mut state = state_with_agents();
+        let agent = state.agents.get_mut("backend-specialist-1").unwrap();
+        agent.recent_outcomes = vec![true, false, false, false, false];
+        let text = |state: &DashboardState, health: HealthThresholds| -> String {
+            AgentPanel::new(state)
+                .with_health(health)
+                .build_lines()
+                .iter()
+                .flat_map(|l| l.spans.iter())
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+        assert!(text(&state, HealthThresholds::default()).contains("[degraded 20%]"));
+        let lenient = HealthThresholds {
+            degraded: 0.3,
+            failing: 0.6,
+        };
+        assert!(!text(&state, lenient).contains("degraded"));
+    }
+
+    #[test]
+    fn long_multibyte_error_is_cut_on_a_char_boundary() {
+        let line = r#"{"event_type":"error","timestamp":"2026-02-08T10:00:00Z","agent_id":"db-agent","task_id":"T1","session_id":"s","error_message":"데이터베이스 연결이 시간 초과로 끊어졌습니다 다시 시도하세요"}"#;
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(line).events);
+        let text: String = AgentPanel::new(&state)
+            .build_lines()
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.to_string())
+            .collect();
+        assert!(text.contains("데이터베이스 연결이"), "got: {text}");
+        assert!(text.contains('\u{2026}'), "got: {text}");
+    }
+
+    #[test]
+    fn format_age_units() {
+        assert_eq!(format_age(12), "12s ago");
+        assert_eq!(format_age(-3), "0s ago");
+        assert_eq!(format_age(180), "3m ago");
+        assert_eq!(format_age(7200), "2h ago");
+        assert_eq!(format_age(90_000), "1d ago");
+    }
+
+    #[test]
+    fn build_lines_show_age_colored_by_silence() {
+        let mut state = state_with_agents();
+        let agent = state.agents.values_mut().next().unwrap();
+        agent.status = AgentStatus::Running;
+        let last_seen = agent.last_seen.unwrap();
+        let id = agent.agent_id.clone();
+
+        let span_for = |secs: i64| {
+            let panel =
+                AgentPanel::new(&state).with_now(last_seen + chrono::Duration::seconds(secs));
+            panel
+                .build_lines()
+                .into_iter()
+                .filter(|l| l.spans.iter().any(|s| s.content == id))
+                .flat_map(|l| l.spans.into_iter())
+                .find(|s| s.content.ends_with("ago)"))
+                .unwrap()
+        };
+
+        let theme = Theme::default();
+        let fresh = span_for(12);
+        assert_eq!(fresh.content, " (12s ago)");
+        assert_eq!(fresh.style.fg, Some(theme.muted));
+        assert_eq!(span_for(45).style.fg, Some(theme.in_progress));
+        assert_eq!(span_for(600).style.fg, Some(theme.failed));
+    }
+
+    #[test]
+    fn running_agents_show_spinner_frame() {
+        let mut state = state_with_agents();
+        for agent in state.agents.values_mut() {
+            agent.status = AgentStatus::Running;
+        }
+        let first_span = |frame: usize| {
+            let lines = AgentPanel::new(&state).with_spinner(frame).build_lines();
+            lines[0].spans[0].content.to_string()
+        };
+        assert_eq!(first_span(0), " \u{280B} ");
+        assert_eq!(first_span(1), " \u{2819} ");
+        assert_eq!(first_span(10), first_span(0));
+    }
+
     #[test]
     fn build_lines_empty() {
         let state = DashboardState::default();
@@ -262,7 +566,7 @@ mod tests {
         assert!(lines.len() >= 2);
         let error_line = lines
             .iter()
-            .find(|l| l.spans.iter().any(|s| s.content.contains("!!")));
+            .find(|l| l.spans.iter().any(|s| s.content.contains("[NET]")));
         assert!(error_line.is_some(), "should have error summary line");
     }
 
@@ -279,6 +583,42 @@ mod tests {
         assert!(has_category, "error summary should show category");
     }
 
+    #[test]
+    fn error_summary_colored_by_category() {
+        let state = state_with_errors();
+        let theme = Theme::default();
+        let lines = AgentPanel::new(&state).with_theme(theme).build_lines();
+        // Latest error in the fixture is a connection refused → Network
+        let badge = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|s| s.content.contains("[NET]"))
+            .expect("network badge");
+        assert_eq!(badge.style.fg, Some(theme.network));
+        let count = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|s| s.content.contains("errs)"))
+            .unwrap();
+        assert_eq!(count.style.fg, Some(theme.network));
+    }
+
+    #[test]
+    fn muted_agent_hides_error_badge_and_summary() {
+        let mut state = state_with_errors();
+        let now = Utc::now();
+        state.mute_agent("backend-specialist-2", now + chrono::Duration::minutes(5));
+        let text: String = AgentPanel::new(&state)
+            .with_now(now)
+            .build_lines()
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|s| s.content.to_string())
+            .collect();
+        assert!(text.contains("(muted)"), "{text}");
+        assert!(!text.contains("errs)") && !text.contains("[NET]"), "{text}");
+    }
+
     #[test]
     fn focused_panel_highlights_selected() {
         let state = state_with_agents();
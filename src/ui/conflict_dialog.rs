@@ -0,0 +1,133 @@
+//! Write-back conflict dialog
+//!
+//! Shown when a dashboard edit was refused because its task or phase changed
+//! in TASKS.md after the dashboard loaded it. Lists the dashboard's version
+//! above the file's current one; any key reloads TASKS.md. Follows the same
+//! pattern as `SignalPicker`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::write_guard::Conflict;
+use crate::ui::theme::Theme;
+
+/// Conflict dialog widget
+pub struct ConflictDialog<'a> {
+    conflict: &'a Conflict,
+    theme: Theme,
+}
+
+impl<'a> ConflictDialog<'a> {
+    pub fn new(conflict: &'a Conflict) -> Self {
+        Self {
+            conflict,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(area: Rect, lines: usize) -> Rect {
+        let width = 72.min(area.width.saturating_sub(4));
+        let height = (lines as u16 + 2).min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    /// One version of the target under a heading, or a placeholder when empty
+    fn version(&self, title: &str, body: &[String], lines: &mut Vec<Line<'static>>) {
+        lines.push(Line::styled(
+            format!(" {title}"),
+            Style::default()
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+        if body.is_empty() {
+            lines.push(Line::styled(
+                "   (not found)",
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        for line in body {
+            lines.push(Line::styled(
+                format!("   {line}"),
+                Style::default().fg(self.theme.text),
+            ));
+        }
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(self.theme.muted);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} ", self.conflict.target),
+                    Style::default()
+                        .fg(self.theme.failed)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(self.conflict.kind.describe(), muted),
+            ]),
+            Line::raw(""),
+        ];
+        self.version("Dashboard (as loaded)", &self.conflict.ours, &mut lines);
+        lines.push(Line::raw(""));
+        self.version("TASKS.md now", &self.conflict.theirs, &mut lines);
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            " Edit not applied. Any key reloads TASKS.md",
+            muted,
+        ));
+        lines
+    }
+}
+
+impl<'a> Widget for ConflictDialog<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = self.build_lines();
+        let popup_area = Self::centered_rect(area, lines.len());
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Conflict ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.failed));
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::write_guard::ConflictKind;
+
+    #[test]
+    fn shows_both_versions() {
+        let conflict = Conflict {
+            target: "P1-T1".to_string(),
+            kind: ConflictKind::Missing,
+            ours: vec!["### [ ] P1-T1: Parser".to_string()],
+            theirs: Vec::new(),
+        };
+        let text: Vec<String> = ConflictDialog::new(&conflict)
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text[0], " P1-T1 is no longer in TASKS.md");
+        assert!(text.contains(&"   ### [ ] P1-T1: Parser".to_string()));
+        assert!(text.contains(&"   (not found)".to_string()), "{text:?}");
+
+        let area = Rect::new(0, 0, 10, 5);
+        ConflictDialog::new(&conflict).render(area, &mut Buffer::empty(area));
+    }
+}
@@ -10,10 +10,20 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
 
-use chrono::Utc;
+use chrono::{DateTime, Local, Utc};
 
-use crate::data::state::{AgentState, AgentStatus, DashboardState, ErrorRecord};
-use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus};
+use crate::analysis::metrics::{
+    format_ms, format_secs, format_tokens, worst_offenders, AgentMetrics,
+};
+use crate::config::DEFAULT_RETRY_THRESHOLD;
+use crate::data::git_log::GitCommit;
+use crate::data::hook_parser::EventType;
+use crate::data::state::{
+    AgentState, AgentStatus, DashboardState, ErrorRecord, EstimateRollup, TaskActivity,
+};
+use crate::data::tasks_parser::{ParsedPhase, ParsedTask, TaskStatus, TaskWarning, NOTE_PREFIX};
+use crate::ui::gantt::truncate_to_width;
+use crate::ui::theme::Theme;
 
 /// Parse a markdown line into styled spans.
 /// Handles **bold**, `code`, and plain text segments.
@@ -64,11 +74,29 @@ fn parse_md_spans(line: &str) -> Vec<Span<'static>> {
     spans
 }
 
+/// Format a tool call duration compactly: `850ms`, `1.2s`, `2m 5s`
+fn format_tool_duration(d: chrono::Duration) -> String {
+    let ms = d.num_milliseconds().max(0);
+    if ms < 1000 {
+        format!("{ms}ms")
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        format!("{}m {}s", ms / 60_000, (ms % 60_000) / 1000)
+    }
+}
+
+/// Number of hook events listed under "Recent activity" for a task
+const RECENT_ACTIVITY_ROWS: usize = 5;
+
 /// What the detail panel is showing
 pub enum DetailContent<'a> {
     Phase(&'a ParsedPhase),
     Task(&'a ParsedTask, &'a str, Vec<&'a ErrorRecord>), // task + phase name + errors
     Agent(&'a AgentState, Vec<&'a ErrorRecord>, &'a [ParsedPhase]),
+    /// Agent-centric drill-down: full status history, tool calls, usage, all
+    /// errors (each with whether it was acknowledged)
+    AgentDrillDown(&'a AgentState, Vec<(&'a ErrorRecord, bool)>),
     None,
 }
 
@@ -76,23 +104,97 @@ pub enum DetailContent<'a> {
 pub struct DetailWidget<'a> {
     content: DetailContent<'a>,
     focused: bool,
+    theme: Theme,
+    /// Unmet dependencies of the selected task (only filled for Blocked tasks)
+    blockers: Vec<(String, Option<TaskStatus>)>,
+    /// Hook events recorded against the selected task, newest first
+    activity: Vec<&'a TaskActivity>,
+    /// TASKS.md parser warnings for the selected phase or task
+    warnings: Vec<&'a TaskWarning>,
+    /// Git commits mentioning the selected task, newest first
+    commits: Vec<&'a GitCommit>,
+    /// Highlighted drill-down tool call, counted from the newest
+    tool_cursor: Option<usize>,
+    /// End of the drilled-down agent's alert mute
+    muted_until: Option<DateTime<Utc>>,
+    /// Other running agents on the selected agent's branch
+    shared_branch: Vec<&'a str>,
+    /// Slack of the selected task over its dependencies, and whether it is
+    /// the in-progress task gating completion
+    slack: Option<(i64, bool)>,
+    /// Retries at which a task is flagged for a human look
+    retry_threshold: usize,
+    /// Tokens and cost reported by the selected task's events
+    usage: Option<(u64, f64)>,
+    /// `est:` estimates of the selected task or phase
+    estimate: Option<EstimateRollup>,
 }
 
 impl<'a> DetailWidget<'a> {
     pub fn new(content: DetailContent<'a>, focused: bool) -> Self {
-        Self { content, focused }
+        Self {
+            content,
+            focused,
+            theme: Theme::default(),
+            blockers: Vec::new(),
+            activity: Vec::new(),
+            warnings: Vec::new(),
+            commits: Vec::new(),
+            tool_cursor: None,
+            muted_until: None,
+            shared_branch: Vec::new(),
+            slack: None,
+            retry_threshold: DEFAULT_RETRY_THRESHOLD as usize,
+            usage: None,
+            estimate: None,
+        }
+    }
+
+    pub fn with_blockers(mut self, blockers: Vec<(String, Option<TaskStatus>)>) -> Self {
+        self.blockers = blockers;
+        self
+    }
+
+    pub fn with_retry_threshold(mut self, threshold: usize) -> Self {
+        self.retry_threshold = threshold;
+        self
+    }
+
+    pub fn with_activity(mut self, activity: Vec<&'a TaskActivity>) -> Self {
+        self.activity = activity;
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<&'a TaskWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    pub fn with_commits(mut self, commits: Vec<&'a GitCommit>) -> Self {
+        self.commits = commits;
+        self
+    }
+
+    pub fn with_tool_cursor(mut self, cursor: usize) -> Self {
+        self.tool_cursor = Some(cursor);
+        self
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
     }
 
     pub fn from_agent_selection(state: &'a DashboardState, selected_agent: usize) -> Self {
         let mut ids: Vec<&String> = state.agents.keys().collect();
         ids.sort();
 
+        let mut shared_branch = Vec::new();
         let content = if let Some(agent_id) = ids.get(selected_agent) {
             if let Some(agent) = state.agents.get(*agent_id) {
+                shared_branch = state.agents_sharing_branch(agent_id);
                 let errors: Vec<&ErrorRecord> = state
-                    .recent_errors
-                    .iter()
-                    .filter(|e| e.agent_id == agent.agent_id)
+                    .open_errors_for_agent(&agent.agent_id)
                     .rev()
                     .take(3)
                     .collect();
@@ -103,10 +205,28 @@ impl<'a> DetailWidget<'a> {
         } else {
             DetailContent::None
         };
-        Self {
-            content,
-            focused: true,
-        }
+        let mut widget = Self::new(content, true);
+        widget.shared_branch = shared_branch;
+        widget
+    }
+
+    /// Build the drill-down view for one agent, including every recorded error
+    pub fn from_agent_drilldown(state: &'a DashboardState, agent_id: &str) -> Self {
+        let content = match state.agents.get(agent_id) {
+            Some(agent) => {
+                let errors: Vec<(&ErrorRecord, bool)> = state
+                    .recent_errors
+                    .iter()
+                    .filter(|e| e.agent_id == agent.agent_id)
+                    .map(|e| (e, state.error_acknowledged(e)))
+                    .collect();
+                DetailContent::AgentDrillDown(agent, errors)
+            }
+            None => DetailContent::None,
+        };
+        let mut widget = Self::new(content, true);
+        widget.muted_until = state.muted_until(agent_id, Utc::now());
+        widget
     }
 
     pub fn from_selection(
@@ -115,6 +235,38 @@ impl<'a> DetailWidget<'a> {
         selected_index: usize,
         focused: bool,
     ) -> Self {
+        Self::selection(state, selected_task, selected_index, focused, false)
+    }
+
+    /// Like `from_selection`, but with every error and activity row of the
+    /// task instead of the most recent ones (for the pager)
+    pub fn from_selection_full(
+        state: &'a DashboardState,
+        selected_task: Option<(usize, usize)>,
+        selected_index: usize,
+    ) -> Self {
+        Self::selection(state, selected_task, selected_index, false, true)
+    }
+
+    fn selection(
+        state: &'a DashboardState,
+        selected_task: Option<(usize, usize)>,
+        selected_index: usize,
+        focused: bool,
+        full: bool,
+    ) -> Self {
+        let (error_rows, activity_rows) = if full {
+            (usize::MAX, usize::MAX)
+        } else {
+            (3, RECENT_ACTIVITY_ROWS)
+        };
+        let mut blockers = Vec::new();
+        let mut activity = Vec::new();
+        let mut warnings = Vec::new();
+        let mut commits = Vec::new();
+        let mut slack = None;
+        let mut usage = None;
+        let mut estimate = None;
         let content = if let Some((pi, ti)) = selected_task {
             let phase = &state.phases[pi];
             let task = &phase.tasks[ti];
@@ -123,8 +275,30 @@ impl<'a> DetailWidget<'a> {
                 .iter()
                 .filter(|e| e.task_id == task.id)
                 .rev()
-                .take(3)
+                .take(error_rows)
                 .collect();
+            if *task.shown_status() == TaskStatus::Blocked {
+                blockers = state.unmet_dependencies(task);
+            }
+            activity = state
+                .activity_for_task(&task.id)
+                .iter()
+                .rev()
+                .take(activity_rows)
+                .collect();
+            warnings = state.warnings_for_task(&task.id);
+            commits = state.commits_for_task(&task.id);
+            let path = state.critical_path(Utc::now());
+            slack = path
+                .slack_secs
+                .get(&task.id)
+                .map(|&secs| (secs, path.gating.as_ref() == Some(&task.id)));
+            usage = state
+                .task_times
+                .get(&task.id)
+                .filter(|t| t.tokens > 0 || t.cost_usd > 0.0)
+                .map(|t| (t.tokens, t.cost_usd));
+            estimate = state.estimate_rollup([task], Utc::now());
             DetailContent::Task(task, &phase.name, errors)
         } else {
             // Check if a phase header is selected
@@ -138,11 +312,208 @@ impl<'a> DetailWidget<'a> {
                 idx += 1 + phase.tasks.len();
             }
             match found_phase {
-                Some(phase) => DetailContent::Phase(phase),
+                Some(phase) => {
+                    warnings = state.warnings_for_phase(&phase.id);
+                    estimate = state.estimate_rollup(&phase.tasks, Utc::now());
+                    DetailContent::Phase(phase)
+                }
                 None => DetailContent::None,
             }
         };
-        Self { content, focused }
+        let mut widget = Self::new(content, focused)
+            .with_blockers(blockers)
+            .with_activity(activity)
+            .with_warnings(warnings)
+            .with_commits(commits);
+        widget.slack = slack;
+        widget.usage = usage;
+        widget.estimate = estimate;
+        widget
+    }
+
+    /// `2h00m (~1h20m left, 1 unestimated)`, or `None` without estimates
+    fn estimate_text(&self) -> Option<String> {
+        let estimate = self.estimate?;
+        let mut left = Vec::new();
+        if estimate.remaining > chrono::Duration::zero() {
+            left.push(format!(
+                "~{} left",
+                format_secs(estimate.remaining.num_seconds())
+            ));
+        }
+        if estimate.unestimated > 0 {
+            left.push(format!("{} unestimated", estimate.unestimated));
+        }
+        let total = format_secs(estimate.total.num_seconds());
+        Some(if left.is_empty() {
+            total
+        } else {
+            format!("{total} ({})", left.join(", "))
+        })
+    }
+
+    /// "Commits" section: short hash and subject of each commit naming the task
+    fn commit_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::raw(""),
+            Line::from(vec![
+                Span::styled(
+                    format!("Commits ({}):", self.commits.len()),
+                    Style::default()
+                        .fg(self.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("  d: open diff", Style::default().fg(self.theme.muted)),
+            ]),
+        ];
+        for commit in &self.commits {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}  ", commit.short_hash()),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(commit.subject.clone(), Style::default().fg(self.theme.text)),
+            ]));
+        }
+        lines
+    }
+
+    /// "Parser warnings" section: each warning with its TASKS.md source line
+    fn warning_lines(&self) -> Vec<Line<'static>> {
+        let warn_style = Style::default().fg(self.theme.in_progress);
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("Parser warnings ({}):", self.warnings.len()),
+                warn_style.add_modifier(Modifier::BOLD),
+            ),
+        ];
+        for w in &self.warnings {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  L{}: ", w.line_number), warn_style),
+                Span::styled(w.message.clone(), Style::default().fg(self.theme.text)),
+            ]));
+            lines.push(Line::styled(
+                format!("      {}", w.line_content),
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        lines
+    }
+
+    /// "Recent activity" section: one row per hook event on the task
+    /// (time, tool or event kind, outcome)
+    fn activity_lines(&self) -> Vec<Line<'static>> {
+        let label = Style::default().fg(self.theme.muted);
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled(
+                "Recent activity:",
+                Style::default()
+                    .fg(self.theme.text)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ];
+
+        for act in &self.activity {
+            let what = match act.event_type {
+                EventType::AgentStart => "agent start".to_string(),
+                EventType::AgentEnd => "agent end".to_string(),
+                EventType::ToolStart | EventType::ToolEnd | EventType::Error => act
+                    .tool_name
+                    .as_deref()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{:?}", act.event_type).to_lowercase()),
+            };
+            let (outcome, color) = match act.event_type {
+                EventType::AgentStart | EventType::ToolStart => {
+                    ("started".to_string(), self.theme.in_progress)
+                }
+                EventType::AgentEnd | EventType::ToolEnd => {
+                    ("done".to_string(), self.theme.completed)
+                }
+                EventType::Error => (
+                    act.error_message
+                        .clone()
+                        .unwrap_or_else(|| "error".to_string()),
+                    self.theme.failed,
+                ),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}  ", act.timestamp.format("%H:%M:%S")), label),
+                Span::styled(format!("{what:<12}"), Style::default().fg(Color::Yellow)),
+                Span::styled(outcome, Style::default().fg(color)),
+                Span::styled(format!("  @{}", act.agent_id), label),
+            ]));
+        }
+
+        lines
+    }
+
+    /// Explain why a Blocked task cannot proceed: unmet dependencies and the
+    /// latest error recorded against it
+    fn blocked_lines(&self, reason: Option<&str>, errors: &[&ErrorRecord]) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled(
+                "Blocked because:",
+                Style::default()
+                    .fg(self.theme.blocked)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ];
+
+        if let Some(reason) = reason {
+            lines.push(Line::from(vec![
+                Span::styled("  reason:   ", Style::default().fg(self.theme.muted)),
+                Span::styled(reason.to_string(), Style::default().fg(self.theme.text)),
+            ]));
+        }
+
+        for (dep, status) in &self.blockers {
+            let (status_str, color) = match status {
+                Some(s) => (format!("{s:?}"), self.theme.task_status(s)),
+                None => ("not found in TASKS.md".to_string(), self.theme.muted),
+            };
+            lines.push(Line::from(vec![
+                Span::styled("  waits on ", Style::default().fg(self.theme.muted)),
+                Span::styled(
+                    dep.clone(),
+                    Style::default()
+                        .fg(self.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" "),
+                Span::styled(status_str, Style::default().fg(color)),
+            ]));
+        }
+
+        // Errors are newest first
+        if let Some(err) = errors.first() {
+            lines.push(Line::from(vec![
+                Span::styled("  error:    ", Style::default().fg(self.theme.muted)),
+                Span::styled(
+                    format!("{}{}", err.message, err.repeat_suffix()),
+                    Style::default().fg(self.theme.error_category(&err.category)),
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("  fix:      ", Style::default().fg(self.theme.muted)),
+                Span::styled(
+                    err.suggestion.clone(),
+                    Style::default().fg(self.theme.in_progress),
+                ),
+            ]));
+        }
+
+        if reason.is_none() && self.blockers.is_empty() && errors.is_empty() {
+            lines.push(Line::styled(
+                "  All dependencies are done; set the task back to [ ] to resume",
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+
+        lines
     }
 
     fn build_lines(&self) -> Vec<Line<'static>> {
@@ -160,7 +531,7 @@ impl<'a> DetailWidget<'a> {
                     .iter()
                     .filter(|t| t.status == TaskStatus::Completed)
                     .count();
-                vec![
+                let mut lines = vec![
                     Line::from(vec![
                         Span::styled("Phase: ", Style::default().fg(Color::DarkGray)),
                         Span::styled(
@@ -182,15 +553,24 @@ impl<'a> DetailWidget<'a> {
                         Span::styled("Tasks:    ", Style::default().fg(Color::DarkGray)),
                         Span::raw(format!("{}", phase.tasks.len())),
                     ]),
-                ]
+                ];
+                if let Some(text) = self.estimate_text() {
+                    lines.push(Line::from(vec![
+                        Span::styled("Estimate: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(text, Style::default().fg(self.theme.text)),
+                    ]));
+                }
+                if !self.warnings.is_empty() {
+                    lines.extend(self.warning_lines());
+                }
+                lines
             }
             DetailContent::Agent(agent, errors, phases) => {
-                let status_str = format!("{:?}", agent.status);
-                let status_color = match agent.status {
-                    AgentStatus::Running => Color::Green,
-                    AgentStatus::Idle => Color::DarkGray,
-                    AgentStatus::Error => Color::Red,
-                };
+                let mut status_str = format!("{:?}", agent.status);
+                if agent.presumed_finished {
+                    status_str.push_str(" (presumed finished)");
+                }
+                let status_color = self.theme.agent_status(&agent.status);
 
                 let mut lines = vec![
                     Line::from(vec![
@@ -209,26 +589,69 @@ impl<'a> DetailWidget<'a> {
                     Line::from(vec![
                         Span::styled("Events: ", Style::default().fg(Color::DarkGray)),
                         Span::raw(format!("{}", agent.event_count)),
-                        if agent.error_count > 0 {
+                        if agent.open_errors > 0 {
                             Span::styled(
-                                format!(" ({} errors)", agent.error_count),
+                                format!(" ({} errors)", agent.open_errors),
                                 Style::default().fg(Color::Red),
                             )
                         } else {
                             Span::raw("".to_string())
                         },
+                        if agent.error_count > agent.open_errors {
+                            Span::styled(
+                                format!(
+                                    " ({} acknowledged)",
+                                    agent.error_count - agent.open_errors
+                                ),
+                                Style::default().fg(Color::DarkGray),
+                            )
+                        } else {
+                            Span::raw("".to_string())
+                        },
                     ]),
                 ];
 
                 // Session ID
                 if let Some(ref sid) = agent.session_id {
-                    let short = if sid.len() > 8 { &sid[..8] } else { sid };
+                    let short: String = sid.chars().take(8).collect();
                     lines.push(Line::from(vec![
                         Span::styled("Session:", Style::default().fg(Color::DarkGray)),
                         Span::raw(format!(" {short}")),
                     ]));
                 }
 
+                // Instances folded into this logical agent
+                if !agent.instances.is_empty() {
+                    let instances: Vec<&str> = agent.instances.iter().map(String::as_str).collect();
+                    lines.push(Line::from(vec![
+                        Span::styled("Instances: ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(instances.join(", ")),
+                    ]));
+                }
+
+                if let Some(ref checkout) = agent.checkout {
+                    let mut spans = vec![
+                        Span::styled("Branch: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(checkout.branch.clone(), Style::default().fg(Color::Cyan)),
+                    ];
+                    if let Some(ref worktree) = checkout.worktree {
+                        spans.push(Span::styled(
+                            format!(" (worktree {worktree})"),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                    lines.push(Line::from(spans));
+                }
+                if !self.shared_branch.is_empty() {
+                    lines.push(Line::styled(
+                        format!(
+                            "  ! Also running on this branch: {}",
+                            self.shared_branch.join(", ")
+                        ),
+                        Style::default().fg(self.theme.in_progress),
+                    ));
+                }
+
                 // Active duration
                 if let Some(first) = agent.first_seen {
                     let last = agent.last_seen.unwrap_or_else(Utc::now);
@@ -268,6 +691,28 @@ impl<'a> DetailWidget<'a> {
                         Span::styled("Tools:  ", Style::default().fg(Color::DarkGray)),
                         Span::styled(tool_str, Style::default().fg(Color::Yellow)),
                     ]));
+
+                    // Tools this agent keeps failing with
+                    let offenders = worst_offenders(&[AgentMetrics::of(agent, Utc::now())], 3);
+                    if !offenders.is_empty() {
+                        let fails = offenders
+                            .iter()
+                            .map(|o| {
+                                format!(
+                                    "{} {:.0}% ({}/{})",
+                                    o.tool.tool_name,
+                                    o.tool.failure_rate * 100.0,
+                                    o.tool.failures,
+                                    o.tool.calls
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("  ");
+                        lines.push(Line::from(vec![
+                            Span::styled("Fails:  ", Style::default().fg(Color::DarkGray)),
+                            Span::styled(fails, Style::default().fg(self.theme.failed)),
+                        ]));
+                    }
                 }
 
                 // Recent tool sequence
@@ -333,25 +778,230 @@ impl<'a> DetailWidget<'a> {
                         Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                     ));
                     for err in errors {
-                        let msg_short = if err.message.len() > 50 {
-                            format!("{}...", &err.message[..47])
-                        } else {
-                            err.message.clone()
-                        };
+                        let msg_short = truncate_to_width(&err.message, 50);
+                        let color = self.theme.error_category(&err.category);
                         lines.push(Line::from(vec![
-                            Span::styled("  !! ", Style::default().fg(Color::Red)),
+                            Span::styled("  !! ", Style::default().fg(color)),
                             Span::styled(msg_short, Style::default().fg(Color::White)),
+                            Span::styled(err.repeat_suffix(), Style::default().fg(color)),
                         ]));
                         let retry_str = if err.retryable { "Retry" } else { "No retry" };
                         lines.push(Line::from(vec![
                             Span::styled("     ", Style::default()),
+                            Span::styled(format!("{}", err.category), Style::default().fg(color)),
                             Span::styled(
-                                format!("{}", err.category),
+                                format!(" | {retry_str} | {}", err.suggestion),
+                                Style::default().fg(Color::DarkGray),
+                            ),
+                        ]));
+                    }
+                }
+
+                lines
+            }
+            DetailContent::AgentDrillDown(agent, errors) => {
+                let label = Style::default().fg(Color::DarkGray);
+                let section = Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD);
+                let status_color = |s: &AgentStatus| self.theme.agent_status(s);
+
+                let mut lines = vec![
+                    Line::from(vec![
+                        Span::styled("Agent:  ", label),
+                        Span::styled(
+                            agent.agent_id.clone(),
+                            Style::default()
+                                .fg(Color::White)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled("  (Enter to close)", label),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("Status: ", label),
+                        Span::styled(
+                            format!("{:?}", agent.status),
+                            Style::default().fg(status_color(&agent.status)),
+                        ),
+                        Span::styled(
+                            if agent.presumed_finished {
+                                " (presumed finished)"
+                            } else {
+                                ""
+                            },
+                            label,
+                        ),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("Task:   ", label),
+                        Span::styled(
+                            agent
+                                .current_task
+                                .clone()
+                                .unwrap_or_else(|| "-".to_string()),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                    ]),
+                ];
+                if let Some(until) = self.muted_until {
+                    lines.push(Line::from(vec![
+                        Span::styled("Muted:  ", label),
+                        Span::styled(
+                            format!(
+                                "alerts until {} (z to unmute)",
+                                until.with_timezone(&chrono::Local).format("%H:%M")
+                            ),
+                            Style::default().fg(self.theme.muted),
+                        ),
+                    ]));
+                }
+
+                let usage = if agent.input_tokens + agent.output_tokens > 0 {
+                    format!(
+                        "{} in / {} out  ${:.2}",
+                        format_tokens(agent.input_tokens),
+                        format_tokens(agent.output_tokens),
+                        agent.cost_usd
+                    )
+                } else {
+                    "-".to_string()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled("Usage:  ", label),
+                    Span::raw(usage),
+                ]));
+
+                let metrics = AgentMetrics::of(agent, Utc::now());
+                let rate = metrics
+                    .tasks_per_hour
+                    .map(|r| format!(" ({r:.1}/h)"))
+                    .unwrap_or_default();
+                lines.push(Line::from(vec![
+                    Span::styled("Pace:   ", label),
+                    Span::raw(format!(
+                        "{} tasks{rate}  running {}  idle {}  error {}",
+                        metrics.tasks_finished,
+                        format_secs(metrics.running_secs),
+                        format_secs(metrics.idle_secs),
+                        format_secs(metrics.error_secs)
+                    )),
+                ]));
+
+                if !agent.status_history.is_empty() {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::styled("Status history:", section));
+                    for change in &agent.status_history {
+                        lines.push(Line::from(vec![
+                            Span::styled(format!("  {}  ", change.at.format("%H:%M:%S")), label),
+                            Span::styled(
+                                format!("{:?}", change.status),
+                                Style::default().fg(status_color(&change.status)),
+                            ),
+                        ]));
+                    }
+                }
+
+                if !metrics.tools.is_empty() {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::styled("Tool stats:", section));
+                    for tool in &metrics.tools {
+                        let failed_style = if tool.failures > 0 {
+                            Style::default().fg(self.theme.failed)
+                        } else {
+                            label
+                        };
+                        lines.push(Line::from(vec![
+                            Span::styled(
+                                format!("  {:<10}", tool.tool_name),
                                 Style::default().fg(Color::Yellow),
                             ),
+                            Span::raw(format!("{:>4} calls  ", tool.calls)),
                             Span::styled(
-                                format!(" | {retry_str} | {}", err.suggestion),
-                                Style::default().fg(Color::DarkGray),
+                                format!(
+                                    "{} failed ({:.0}%)",
+                                    tool.failures,
+                                    tool.failure_rate * 100.0
+                                ),
+                                failed_style,
+                            ),
+                            Span::styled(
+                                format!(
+                                    "  avg {}",
+                                    tool.mean_ms.map_or_else(|| "-".to_string(), format_ms)
+                                ),
+                                label,
+                            ),
+                        ]));
+                    }
+                }
+
+                if !agent.tool_invocations.is_empty() {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::styled(
+                        format!("Tool calls (last {}):", agent.tool_invocations.len()),
+                        section,
+                    ));
+                    for (i, inv) in agent.tool_invocations.iter().rev().enumerate() {
+                        let dur = inv
+                            .duration()
+                            .map(format_tool_duration)
+                            .unwrap_or_else(|| "(running)".to_string());
+                        let selected = self.tool_cursor == Some(i);
+                        let mut spans = vec![
+                            Span::styled(if selected { "> " } else { "  " }, label),
+                            Span::styled(format!("{}  ", inv.started_at.format("%H:%M:%S")), label),
+                            Span::styled(
+                                format!("{:<10}", inv.tool_name),
+                                Style::default().fg(Color::Yellow),
+                            ),
+                            Span::raw(dur),
+                        ];
+                        if let Some(ref path) = inv.file_path {
+                            let style = Style::default().fg(Color::Cyan);
+                            spans.push(Span::styled(
+                                format!("  {path}"),
+                                if selected {
+                                    style.add_modifier(Modifier::BOLD)
+                                } else {
+                                    style
+                                },
+                            ));
+                        }
+                        lines.push(Line::from(spans));
+                    }
+                }
+
+                if !errors.is_empty() {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::styled(
+                        format!("Errors ({}):", errors.len()),
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    ));
+                    for &(err, acknowledged) in errors {
+                        let color = self.theme.error_category(&err.category);
+                        // Acknowledged errors stay listed, marked and dimmed
+                        let (marker, text) = if acknowledged {
+                            ("ok", Color::DarkGray)
+                        } else {
+                            ("!!", Color::White)
+                        };
+                        lines.push(Line::from(vec![
+                            Span::styled(
+                                format!("  {} {marker} ", err.timestamp.format("%H:%M:%S")),
+                                Style::default().fg(color),
+                            ),
+                            Span::styled(
+                                format!("{}{}", err.message, err.repeat_suffix()),
+                                Style::default().fg(text),
+                            ),
+                        ]));
+                        let retry_str = if err.retryable { "Retry" } else { "No retry" };
+                        lines.push(Line::from(vec![
+                            Span::styled("     ", Style::default()),
+                            Span::styled(format!("{}", err.category), Style::default().fg(color)),
+                            Span::styled(
+                                format!(" | {retry_str} | {} [{}]", err.suggestion, err.task_id),
+                                label,
                             ),
                         ]));
                     }
@@ -360,14 +1010,8 @@ impl<'a> DetailWidget<'a> {
                 lines
             }
             DetailContent::Task(task, phase_name, errors) => {
-                let status_str = format!("{:?}", task.status);
-                let status_color = match task.status {
-                    TaskStatus::Completed => Color::Green,
-                    TaskStatus::InProgress => Color::Yellow,
-                    TaskStatus::Pending => Color::DarkGray,
-                    TaskStatus::Failed => Color::Red,
-                    TaskStatus::Blocked => Color::Magenta,
-                };
+                let status_str = format!("{:?}", task.shown_status());
+                let status_color = self.theme.task_status(task.shown_status());
 
                 let mut lines = vec![
                     Line::from(vec![
@@ -400,6 +1044,26 @@ impl<'a> DetailWidget<'a> {
                     ]));
                 }
 
+                if let Some(text) = self.estimate_text() {
+                    lines.push(Line::from(vec![
+                        Span::styled("Est:    ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(text, Style::default().fg(self.theme.text)),
+                    ]));
+                }
+
+                if let Some(due) = task.due {
+                    let today = Local::now().date_naive();
+                    let (text, color) = if task.is_overdue(today) {
+                        (format!("{due} (overdue)"), self.theme.failed)
+                    } else {
+                        (due.to_string(), self.theme.text)
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("Due:    ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(text, Style::default().fg(color)),
+                    ]));
+                }
+
                 if !task.blocked_by.is_empty() {
                     lines.push(Line::from(vec![
                         Span::styled("Deps:   ", Style::default().fg(Color::DarkGray)),
@@ -410,13 +1074,80 @@ impl<'a> DetailWidget<'a> {
                     ]));
                 }
 
-                if !task.body.is_empty() {
+                if let Some((secs, gating)) = self.slack {
+                    let (text, color) = match (secs, gating) {
+                        (0, true) => (
+                            "0 (critical path, gating completion)".to_string(),
+                            self.theme.failed,
+                        ),
+                        (0, false) => ("0 (critical path)".to_string(), self.theme.failed),
+                        _ => (format_secs(secs), self.theme.muted),
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("Slack:  ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(text, Style::default().fg(color)),
+                    ]));
+                }
+
+                if let Some((tokens, cost)) = self.usage {
+                    lines.push(Line::from(vec![
+                        Span::styled("Usage:  ", Style::default().fg(Color::DarkGray)),
+                        Span::raw(format!("{} tok  ${cost:.2}", format_tokens(tokens))),
+                    ]));
+                }
+
+                if task.retries > 0 {
+                    let (text, color) = if task.retries >= self.retry_threshold {
+                        (
+                            format!("{} (needs a human look)", task.retries),
+                            self.theme.failed,
+                        )
+                    } else {
+                        (task.retries.to_string(), self.theme.in_progress)
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("Retries: ", Style::default().fg(Color::DarkGray)),
+                        Span::styled(text, Style::default().fg(color)),
+                    ]));
+                }
+
+                if *task.shown_status() == TaskStatus::Blocked {
+                    lines.extend(self.blocked_lines(task.blocked_reason.as_deref(), errors));
+                }
+
+                if !self.warnings.is_empty() {
+                    lines.extend(self.warning_lines());
+                }
+
+                // Notes are listed on their own below, not as raw comments
+                let body: Vec<&str> = task
+                    .body
+                    .lines()
+                    .filter(|l| !l.trim_start().starts_with(NOTE_PREFIX))
+                    .collect();
+                if body.iter().any(|l| !l.trim().is_empty()) {
                     lines.push(Line::raw(""));
-                    for body_line in task.body.lines() {
+                    for body_line in body {
                         lines.push(Line::from(parse_md_spans(body_line)));
                     }
                 }
 
+                if !task.notes.is_empty() {
+                    lines.push(Line::raw(""));
+                    lines.push(Line::styled(
+                        "Notes:",
+                        Style::default()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    for note in &task.notes {
+                        lines.push(Line::styled(
+                            format!("  {note}"),
+                            Style::default().fg(self.theme.text),
+                        ));
+                    }
+                }
+
                 if !errors.is_empty() {
                     lines.push(Line::raw(""));
                     lines.push(Line::styled(
@@ -424,22 +1155,17 @@ impl<'a> DetailWidget<'a> {
                         Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                     ));
                     for err in errors {
-                        let msg_short = if err.message.len() > 50 {
-                            format!("{}...", &err.message[..47])
-                        } else {
-                            err.message.clone()
-                        };
+                        let msg_short = truncate_to_width(&err.message, 50);
+                        let color = self.theme.error_category(&err.category);
                         lines.push(Line::from(vec![
-                            Span::styled("  !! ", Style::default().fg(Color::Red)),
+                            Span::styled("  !! ", Style::default().fg(color)),
                             Span::styled(msg_short, Style::default().fg(Color::White)),
+                            Span::styled(err.repeat_suffix(), Style::default().fg(color)),
                         ]));
                         let retry_str = if err.retryable { "Retry" } else { "No retry" };
                         lines.push(Line::from(vec![
                             Span::styled("     ", Style::default()),
-                            Span::styled(
-                                format!("{}", err.category),
-                                Style::default().fg(Color::Yellow),
-                            ),
+                            Span::styled(format!("{}", err.category), Style::default().fg(color)),
                             Span::styled(
                                 format!(" | {retry_str} | {}", err.suggestion),
                                 Style::default().fg(Color::DarkGray),
@@ -448,18 +1174,40 @@ impl<'a> DetailWidget<'a> {
                     }
                 }
 
+                if !self.commits.is_empty() {
+                    lines.extend(self.commit_lines());
+                }
+
+                if !self.activity.is_empty() {
+                    lines.extend(self.activity_lines());
+                }
+
                 lines
             }
         }
     }
 }
 
+impl<'a> DetailWidget<'a> {
+    /// The panel's content without styling, one line per row
+    pub fn plain_text(&self) -> String {
+        self.build_lines()
+            .iter()
+            .map(|line| {
+                let mut text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+                text.push('\n');
+                text
+            })
+            .collect()
+    }
+}
+
 impl<'a> Widget for DetailWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let border_style = if self.focused {
-            Style::default().fg(Color::Cyan)
+            Style::default().fg(self.theme.accent)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(self.theme.muted)
         };
 
         let block = Block::default()
@@ -549,8 +1297,11 @@ mod tests {
             message: "permission denied: /etc/shadow".to_string(),
             category: ErrorCategory::Permission,
             retryable: false,
-            suggestion: "Check file permissions",
+            suggestion: "Check file permissions".into(),
+            command: None,
             timestamp: Utc::now(),
+            tool_name: None,
+            count: 1,
         };
         let widget = DetailWidget::new(DetailContent::Task(task, "Setup", vec![&err]), false);
         let lines = widget.build_lines();
@@ -562,6 +1313,45 @@ mod tests {
             .iter()
             .any(|l| l.spans.iter().any(|s| s.content.contains("Permission")));
         assert!(has_permission, "should show Permission category");
+        let bang = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|s| s.content.contains("!!"))
+            .unwrap();
+        assert_eq!(bang.style.fg, Some(Theme::default().blocked));
+    }
+
+    #[test]
+    fn long_multibyte_error_message_is_truncated() {
+        use crate::analysis::rules::ErrorCategory;
+        use crate::data::state::ErrorRecord;
+        use chrono::Utc;
+        use unicode_width::UnicodeWidthStr;
+
+        let state = sample_state();
+        let task = &state.phases[0].tasks[0];
+        let err = ErrorRecord {
+            agent_id: "test-agent".to_string(),
+            task_id: task.id.clone(),
+            message: "권한이 거부되었습니다: 설정 파일을 읽을 수 없습니다. 다시 시도하세요"
+                .to_string(),
+            category: ErrorCategory::Permission,
+            retryable: false,
+            suggestion: "Check file permissions".into(),
+            command: None,
+            timestamp: Utc::now(),
+            tool_name: None,
+            count: 1,
+        };
+        let widget = DetailWidget::new(DetailContent::Task(task, "Setup", vec![&err]), false);
+        let lines = widget.build_lines();
+        let shown = lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|s| s.content.starts_with("권한이"))
+            .unwrap();
+        assert!(shown.content.ends_with('\u{2026}'));
+        assert!(UnicodeWidthStr::width(shown.content.as_ref()) <= 50);
     }
 
     #[test]
@@ -642,6 +1432,26 @@ mod tests {
         assert!(has_session, "should show Session line");
     }
 
+    #[test]
+    fn detail_agent_shortens_multibyte_session() {
+        use crate::data::hook_parser;
+
+        let line = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"세션-일이삼사오육칠"}"#;
+        let mut state = DashboardState::default();
+        state.update_from_events(&hook_parser::parse_hook_events(line).events);
+
+        let widget = DetailWidget::from_agent_selection(&state, 0);
+        let text: Vec<String> = widget
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(
+            text.contains(&"Session: 세션-일이삼사오".to_string()),
+            "got: {text:?}"
+        );
+    }
+
     #[test]
     fn detail_agent_shows_tools_stats() {
         use crate::data::hook_parser;
@@ -657,6 +1467,23 @@ mod tests {
             .iter()
             .any(|l| l.spans.iter().any(|s| s.content.contains("Tools")));
         assert!(has_tools, "should show Tools stats line");
+        // No tool keeps failing here
+        let text = line_text(&lines);
+        assert!(!text.contains("Fails:"));
+
+        let mut state = DashboardState::default();
+        let failing = (0..3)
+            .map(|i| {
+                format!(
+                    "{{\"event_type\":\"tool_start\",\"timestamp\":\"2026-02-08T10:0{i}:00Z\",\"agent_id\":\"a\",\"task_id\":\"T1\",\"session_id\":\"s\",\"tool_name\":\"Bash\"}}\n\
+                     {{\"event_type\":\"error\",\"timestamp\":\"2026-02-08T10:0{i}:01Z\",\"agent_id\":\"a\",\"task_id\":\"T1\",\"session_id\":\"s\",\"error_message\":\"exit 1\"}}"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        state.update_from_events(&hook_parser::parse_hook_events(&failing).events);
+        let text = line_text(&DetailWidget::from_agent_selection(&state, 0).build_lines());
+        assert!(text.contains("Fails:  Bash 100% (3/3)"), "{text}");
     }
 
     #[test]
@@ -708,6 +1535,92 @@ mod tests {
         let _ = has_quoted_name; // use the variable
     }
 
+    #[test]
+    fn drilldown_shows_tool_calls_and_history() {
+        use crate::data::hook_parser;
+
+        let input = include_str!("../../tests/fixtures/tool_file_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+        let mut state = DashboardState::default();
+        state.update_from_events(&result.events);
+
+        let widget = DetailWidget::from_agent_drilldown(&state, "backend-specialist-1");
+        let lines = widget.build_lines();
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l.starts_with("Status history:")));
+        assert!(text.iter().any(|l| l.starts_with("Tool calls (last 2):")));
+        assert!(text
+            .iter()
+            .any(|l| l.contains("Read") && l.contains("1.0s")));
+        assert!(text.iter().any(|l| l.starts_with("Usage:  -")));
+        assert!(text.iter().any(|l| l.starts_with("Pace:   1 tasks")));
+        assert!(text.iter().any(|l| l.starts_with("Tool stats:")));
+        assert!(text
+            .iter()
+            .any(|l| l.starts_with("  Read") && l.contains("1 calls  0 failed (0%)  avg 1.0s")));
+
+        // Newest call first; the cursor marks it and shows its file
+        let text: Vec<String> = DetailWidget::from_agent_drilldown(&state, "backend-specialist-1")
+            .with_tool_cursor(0)
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(
+            text.iter()
+                .any(|l| l.starts_with("> ") && l.contains("Write") && l.ends_with("src/lib.rs")),
+            "{text:?}"
+        );
+    }
+
+    #[test]
+    fn drilldown_lists_all_errors() {
+        use crate::data::hook_parser;
+
+        let input = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        let result = hook_parser::parse_hook_events(input);
+        let mut state = DashboardState::default();
+        state.update_from_events(&result.events);
+
+        let widget = DetailWidget::from_agent_drilldown(&state, "backend-specialist-2");
+        let lines = widget.build_lines();
+        let has_header = lines
+            .iter()
+            .any(|l| l.spans.iter().any(|s| s.content.contains("Errors (2):")));
+        assert!(has_header);
+        let has_suggestion = lines.iter().any(|l| {
+            l.spans
+                .iter()
+                .any(|s| s.content.contains("Check if service is running"))
+        });
+        assert!(has_suggestion);
+    }
+
+    #[test]
+    fn drilldown_unknown_agent_is_none() {
+        let state = DashboardState::default();
+        let widget = DetailWidget::from_agent_drilldown(&state, "ghost");
+        assert_eq!(widget.build_lines().len(), 1);
+    }
+
+    #[test]
+    fn format_helpers() {
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(12_345), "12.3k");
+        assert_eq!(format_tokens(1_200_000), "1.2M");
+        assert_eq!(
+            format_tool_duration(chrono::Duration::milliseconds(850)),
+            "850ms"
+        );
+        assert_eq!(
+            format_tool_duration(chrono::Duration::milliseconds(125_000)),
+            "2m 5s"
+        );
+    }
+
     #[test]
     fn task_with_deps_shows_deps() {
         let state = sample_state();
@@ -724,6 +1637,263 @@ mod tests {
         assert!(has_deps);
     }
 
+    #[test]
+    fn task_detail_shows_slack_on_the_dependency_graph() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [InProgress] T1: Parser\n\n### [ ] T2: Writer\n- **blocked_by**: T1\n\n### [ ] T3: Docs\n- **blocked_by**: T1\n### [ ] T4: Lint\n",
+        )
+        .unwrap();
+        let text = |ti| {
+            line_text(
+                &DetailWidget::from_selection(&state, Some((0, ti)), ti + 1, true).build_lines(),
+            )
+        };
+        assert!(text(0).contains("Slack:  0 (critical path, gating completion)"));
+        // T2 and T3 tie; the path goes through one of them
+        assert!(text(1).contains("Slack:  0") || text(2).contains("Slack:  0"));
+        // T4 can wait until T1 and its follow-up are done
+        assert!(text(3).contains("Slack:  1h00m"));
+    }
+
+    #[test]
+    fn task_detail_shows_usage_of_the_task() {
+        use crate::data::hook_parser;
+
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: Core\n\n### [InProgress] T1: Parser\n")
+                .unwrap();
+        let text = |state: &DashboardState| {
+            line_text(&DetailWidget::from_selection(state, Some((0, 0)), 1, true).build_lines())
+        };
+        assert!(!text(&state).contains("Usage:"));
+
+        let input = r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"a","task_id":"T1","session_id":"s"}
+{"event_type":"tool_end","timestamp":"2026-02-08T10:01:00Z","agent_id":"a","task_id":"","session_id":"s","tool_name":"Read","usage":{"input_tokens":1200000,"output_tokens":300000,"cost_usd":4.3}}"#;
+        state.update_from_events(&hook_parser::parse_hook_events(input).events);
+        // An event without a task id counts for the agent's current task
+        assert!(text(&state).contains("Usage:  1.5M tok  $4.30"));
+    }
+
+    #[test]
+    fn task_detail_flags_tasks_retried_too_often() {
+        let mut state =
+            DashboardState::from_tasks_content("# Phase 1: Core\n\n### [Failed] T1: Parser\n")
+                .unwrap();
+        let text = |state: &DashboardState, threshold| {
+            line_text(
+                &DetailWidget::from_selection(state, Some((0, 0)), 1, true)
+                    .with_retry_threshold(threshold)
+                    .build_lines(),
+            )
+        };
+        assert!(!text(&state, 2).contains("Retries"));
+
+        state.phases[0].tasks[0].retries = 2;
+        assert!(text(&state, 3).contains("Retries: 2"));
+        assert!(!text(&state, 3).contains("human"));
+        assert!(text(&state, 2).contains("Retries: 2 (needs a human look)"));
+    }
+
+    fn line_text(lines: &[Line]) -> String {
+        lines
+            .iter()
+            .map(|l| {
+                l.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn blocked_task_lists_unmet_dependencies() {
+        let state = sample_state();
+        let (pi, ti) = state
+            .phases
+            .iter()
+            .enumerate()
+            .find_map(|(pi, p)| {
+                p.tasks
+                    .iter()
+                    .position(|t| t.status == TaskStatus::Blocked)
+                    .map(|ti| (pi, ti))
+            })
+            .unwrap();
+        let widget = DetailWidget::from_selection(&state, Some((pi, ti)), 0, false);
+        let text = line_text(&widget.build_lines());
+        assert!(text.contains("Blocked because:"), "got: {text}");
+        for dep in &state.phases[pi].tasks[ti].blocked_by {
+            assert!(text.contains(dep.as_str()));
+        }
+    }
+
+    #[test]
+    fn blocked_task_shows_error_suggestion() {
+        let state = sample_state();
+        let task = state
+            .phases
+            .iter()
+            .flat_map(|p| p.tasks.iter())
+            .find(|t| t.status == TaskStatus::Blocked)
+            .unwrap();
+        let analysis = crate::analysis::rules::analyze_error("Permission denied");
+        let err = ErrorRecord {
+            agent_id: "a".into(),
+            task_id: task.id.clone(),
+            message: "Permission denied".into(),
+            category: analysis.category,
+            retryable: analysis.retryable,
+            suggestion: analysis.suggestion.clone(),
+            command: None,
+            timestamp: Utc::now(),
+            tool_name: None,
+            count: 1,
+        };
+        let widget = DetailWidget::new(DetailContent::Task(task, "Phase", vec![&err]), false);
+        let text = line_text(&widget.build_lines());
+        assert!(text.contains("error:    Permission denied"), "got: {text}");
+        assert!(text.contains(analysis.suggestion.as_ref()));
+    }
+
+    #[test]
+    fn task_shows_due_date_and_whether_it_is_overdue() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [ ] T1: a\n- due: 2000-01-01\n### [ ] T2: b\n- due: 9999-12-31\n",
+        )
+        .unwrap();
+        let text = |ti: usize| {
+            let task = &state.phases[0].tasks[ti];
+            line_text(
+                &DetailWidget::new(DetailContent::Task(task, "A", vec![]), false).build_lines(),
+            )
+        };
+        assert!(text(0).contains("Due:    2000-01-01 (overdue)"));
+        assert!(text(1).contains("Due:    9999-12-31"));
+        assert!(!text(1).contains("overdue"));
+    }
+
+    #[test]
+    fn estimates_are_shown_for_tasks_and_phases() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [x] T1: a\n- est: 1h\n### [ ] T2: b\n- est: 1h30m\n### [ ] T3: c\n",
+        )
+        .unwrap();
+        let phase = line_text(&DetailWidget::from_selection(&state, None, 0, false).build_lines());
+        assert!(
+            phase.contains("Estimate: 2h30m (~1h30m left, 1 unestimated)"),
+            "got: {phase}"
+        );
+        let task =
+            line_text(&DetailWidget::from_selection(&state, Some((0, 0)), 1, false).build_lines());
+        assert!(task.contains("Est:    1h00m"), "got: {task}");
+        let task =
+            line_text(&DetailWidget::from_selection(&state, Some((0, 2)), 3, false).build_lines());
+        assert!(!task.contains("Est:"), "got: {task}");
+    }
+
+    #[test]
+    fn non_blocked_task_has_no_explanation() {
+        let state = sample_state();
+        let task = &state.phases[0].tasks[0];
+        let widget = DetailWidget::new(DetailContent::Task(task, "Setup", vec![]), false);
+        assert!(!line_text(&widget.build_lines()).contains("Blocked because"));
+    }
+
+    #[test]
+    fn task_shows_recent_activity_newest_first() {
+        use crate::data::hook_parser;
+
+        let tasks_input = include_str!("../../tests/fixtures/sample_tasks.md");
+        let mut state = DashboardState::from_tasks_content(tasks_input).unwrap();
+        let hooks_input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let result = hook_parser::parse_hook_events(hooks_input);
+        for event in &result.events {
+            let mut event = event.clone();
+            event.task_id = state.phases[0].tasks[0].id.clone();
+            state.update_from_events(&[event]);
+        }
+
+        let widget = DetailWidget::from_selection(&state, Some((0, 0)), 1, false);
+        let text = line_text(&widget.build_lines());
+        assert!(text.contains("Recent activity:"), "got: {text}");
+        let rows: Vec<&str> = text
+            .lines()
+            .skip_while(|l| !l.starts_with("Recent activity:"))
+            .skip(1)
+            .collect();
+        assert_eq!(rows.len(), RECENT_ACTIVITY_ROWS);
+        assert!(rows[0].contains("agent end") && rows[0].contains("done"));
+        assert!(rows
+            .iter()
+            .any(|r| r.contains("Read") && r.contains("started")));
+
+        // The pager gets every row
+        let full = DetailWidget::from_selection_full(&state, Some((0, 0)), 1).plain_text();
+        let all_rows = full
+            .lines()
+            .skip_while(|l| !l.starts_with("Recent activity:"))
+            .skip(1)
+            .count();
+        let recorded = state.activity_for_task(&state.phases[0].tasks[0].id).len();
+        assert!(recorded > RECENT_ACTIVITY_ROWS);
+        assert_eq!(all_rows, recorded);
+    }
+
+    #[test]
+    fn task_lists_commits_that_mention_it() {
+        let mut state = sample_state();
+        let id = state.phases[0].tasks[0].id.clone();
+        state.commits = crate::data::git_log::parse_log(&format!(
+            "aaaaaaaaaa\u{1f}{id}: first\nbbbbbbbbbb\u{1f}unrelated\n"
+        ));
+        let text =
+            line_text(&DetailWidget::from_selection(&state, Some((0, 0)), 1, false).build_lines());
+        assert!(text.contains("Commits (1):"), "got: {text}");
+        assert!(text.contains(&format!("aaaaaaa  {id}: first")));
+        assert!(!text.contains("unrelated"));
+
+        let other = DetailWidget::from_selection(&state, Some((0, 1)), 2, false);
+        assert!(!line_text(&other.build_lines()).contains("Commits"));
+    }
+
+    #[test]
+    fn task_without_events_has_no_activity_section() {
+        let state = sample_state();
+        let widget = DetailWidget::from_selection(&state, Some((0, 0)), 1, false);
+        assert!(!line_text(&widget.build_lines()).contains("Recent activity"));
+    }
+
+    #[test]
+    fn parser_warnings_shown_for_task_and_phase() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: Setup\n### [x] T1: One\n### [ ] T1: Two\n- **blocked_by**:\n",
+        )
+        .unwrap();
+
+        let task_text =
+            line_text(&DetailWidget::from_selection(&state, Some((0, 1)), 2, false).build_lines());
+        assert!(
+            task_text.contains("Parser warnings (2):"),
+            "got: {task_text}"
+        );
+        assert!(task_text.contains("L3: duplicate task ID `T1`"));
+        assert!(task_text.contains("      ### [ ] T1: Two"));
+        assert!(task_text.contains("L4: `blocked_by:` lists no task IDs"));
+
+        let phase_text =
+            line_text(&DetailWidget::from_selection(&state, None, 0, false).build_lines());
+        assert!(phase_text.contains("Parser warnings (2):"));
+    }
+
+    #[test]
+    fn clean_task_has_no_warning_section() {
+        let state = sample_state();
+        let widget = DetailWidget::from_selection(&state, Some((0, 0)), 1, false);
+        assert!(!line_text(&widget.build_lines()).contains("Parser warnings"));
+    }
+
     #[test]
     fn task_with_body_shows_body_lines() {
         let state = sample_state();
@@ -736,4 +1906,29 @@ mod tests {
             .any(|l| l.spans.iter().any(|s| s.content.contains("스펙")));
         assert!(has_spec, "detail should show body with spec line");
     }
+
+    #[test]
+    fn notes_are_listed_instead_of_raw_comments() {
+        let input = "# Phase 0: A\n### [ ] T1: a\n- spec\n  <!-- note 2026-10-16 14:03: flaky --&gt; retry -->\n";
+        let state = DashboardState::from_tasks_content(input).unwrap();
+        let task = &state.phases[0].tasks[0];
+        let widget = DetailWidget::new(DetailContent::Task(task, "A", vec![]), false);
+        let text = line_text(&widget.build_lines());
+        assert!(text.contains("Notes:"), "{text}");
+        assert!(
+            text.contains("  2026-10-16 14:03: flaky --> retry"),
+            "{text}"
+        );
+        assert!(!text.contains("<!--"), "{text}");
+    }
+
+    #[test]
+    fn blocked_task_shows_its_reason() {
+        let input = "# Phase 0: A\n### [Blocked] T1: Deploy\n- **blocked**: waiting on API keys\n";
+        let state = DashboardState::from_tasks_content(input).unwrap();
+        let widget = DetailWidget::from_selection(&state, Some((0, 0)), 1, false);
+        let text = line_text(&widget.build_lines());
+        assert!(text.contains("  reason:   waiting on API keys"), "{text}");
+        assert!(!text.contains("All dependencies are done"), "{text}");
+    }
 }
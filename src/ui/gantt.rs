@@ -5,8 +5,9 @@
 //! - HorizontalBar: time-based horizontal bar chart per task
 
 use std::collections::HashSet;
+use std::str::FromStr;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -14,18 +15,75 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, StatefulWidget, Widget},
 };
+use serde::{Deserialize, Serialize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use crate::data::state::DashboardState;
-use crate::data::tasks_parser::TaskStatus;
+use crate::config::DEFAULT_RETRY_THRESHOLD;
+use crate::data::state::{DashboardState, PhaseTiming};
+use crate::data::tasks_parser::{ParsedTask, PriorityLevel, TaskStatus};
+use crate::ui::icons::IconSet;
+use crate::ui::rename::{RenameState, RenameTarget};
+use crate::ui::theme::Theme;
 
 /// View mode for the gantt panel
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GanttViewMode {
     #[default]
     Tree,
     HorizontalBar,
 }
 
+/// A column of a task row in the tree view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskColumn {
+    Icon,
+    Id,
+    Name,
+    Agent,
+    Priority,
+    Eta,
+    Tags,
+}
+
+impl TaskColumn {
+    /// Columns shown when the config does not set `task_columns`
+    pub const DEFAULT: [TaskColumn; 4] = [Self::Icon, Self::Id, Self::Name, Self::Agent];
+}
+
+impl FromStr for TaskColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "icon" => Ok(Self::Icon),
+            "id" => Ok(Self::Id),
+            "name" => Ok(Self::Name),
+            "agent" => Ok(Self::Agent),
+            "priority" => Ok(Self::Priority),
+            "eta" => Ok(Self::Eta),
+            "tags" => Ok(Self::Tags),
+            other => Err(format!(
+                "unknown task column `{other}` (expected icon, id, name, agent, priority, eta or tags)"
+            )),
+        }
+    }
+}
+
+/// Optional columns dropped, in this order, when the name would get narrower
+/// than [`MIN_NAME_WIDTH`]
+const DROPPABLE_COLUMNS: [TaskColumn; 3] =
+    [TaskColumn::Tags, TaskColumn::Eta, TaskColumn::Priority];
+
+/// Narrowest the name column may get before optional columns are dropped
+const MIN_NAME_WIDTH: usize = 12;
+
+/// Widest the tags column may get
+const MAX_TAGS_WIDTH: usize = 20;
+
+/// Widest the blocked reason after a blocked task's columns may get
+const MAX_REASON_WIDTH: usize = 24;
+
 /// Selection state for the gantt view
 #[derive(Debug, Default, Clone)]
 pub struct GanttState {
@@ -39,6 +97,12 @@ pub struct GanttState {
     pub collapsed: HashSet<usize>,
     /// Current view mode
     pub view_mode: GanttViewMode,
+    /// Wrap the selected task's name onto a second line instead of truncating it
+    pub wrap_selected: bool,
+    /// Minimap cursor (flat task index across phases); `Some` while navigating the minimap
+    pub minimap_cursor: Option<usize>,
+    /// Number of minimap cells drawn in the last frame
+    pub minimap_width: usize,
 }
 
 impl GanttState {
@@ -61,6 +125,21 @@ impl GanttState {
         }
     }
 
+    /// Keep collapsed phases in step after the phase at `phase_index` was
+    /// removed (`removed`) or inserted
+    pub fn shift_collapsed(&mut self, phase_index: usize, removed: bool) {
+        self.collapsed = self
+            .collapsed
+            .iter()
+            .filter(|&&pi| !(removed && pi == phase_index))
+            .map(|&pi| match pi {
+                pi if pi < phase_index => pi,
+                pi if removed => pi - 1,
+                pi => pi + 1,
+            })
+            .collect();
+    }
+
     /// Toggle the view mode between Tree and HorizontalBar
     pub fn toggle_view(&mut self) {
         self.view_mode = match self.view_mode {
@@ -69,6 +148,87 @@ impl GanttState {
         };
     }
 
+    /// Toggle two-line wrapping of the selected task name
+    pub fn toggle_wrap(&mut self) {
+        self.wrap_selected = !self.wrap_selected;
+    }
+
+    /// Start minimap navigation at the selected task (or the first task)
+    pub fn open_minimap(&mut self, state: &DashboardState) {
+        if state.total_tasks == 0 {
+            return;
+        }
+        let flat = self
+            .selected_task(state)
+            .map(|(pi, ti)| {
+                state.phases[..pi]
+                    .iter()
+                    .map(|p| p.tasks.len())
+                    .sum::<usize>()
+                    + ti
+            })
+            .unwrap_or(0);
+        self.minimap_cursor = Some(flat);
+    }
+
+    pub fn close_minimap(&mut self) {
+        self.minimap_cursor = None;
+    }
+
+    /// Move the minimap cursor by `cells` cells (negative = left)
+    pub fn minimap_move(&mut self, state: &DashboardState, cells: isize) {
+        let total = state.total_tasks;
+        if let Some(cursor) = self.minimap_cursor {
+            let step = total.div_ceil(self.minimap_width.max(1)).max(1) as isize;
+            let next = (cursor as isize + cells * step).clamp(0, total as isize - 1);
+            self.minimap_cursor = Some(next as usize);
+        }
+    }
+
+    /// Select the task under the minimap cursor and leave minimap navigation
+    pub fn minimap_jump(&mut self, state: &DashboardState) {
+        if let Some(flat) = self.minimap_cursor.take() {
+            self.select_flat_task(state, flat);
+        }
+    }
+
+    /// Select a task by flat index, expanding its phase in the tree view
+    pub fn select_flat_task(&mut self, state: &DashboardState, flat: usize) {
+        let mut remaining = flat;
+        let mut row = match self.view_mode {
+            GanttViewMode::Tree => 0,
+            // Row 0 of the bar view is the time axis
+            GanttViewMode::HorizontalBar => 1,
+        };
+        for (pi, phase) in state.phases.iter().enumerate() {
+            if remaining < phase.tasks.len() {
+                self.collapsed.remove(&pi);
+                self.selected = row + 1 + remaining;
+                return;
+            }
+            remaining -= phase.tasks.len();
+            row += 1;
+            if self.view_mode == GanttViewMode::HorizontalBar || !self.collapsed.contains(&pi) {
+                row += phase.tasks.len();
+            }
+        }
+    }
+
+    /// Select the header row of the phase at `phase_index`
+    pub fn select_phase(&mut self, state: &DashboardState, phase_index: usize) {
+        let mut row = match self.view_mode {
+            GanttViewMode::Tree => 0,
+            GanttViewMode::HorizontalBar => 1,
+        };
+        for (pi, phase) in state.phases.iter().enumerate().take(phase_index) {
+            row += 1;
+            if self.view_mode == GanttViewMode::HorizontalBar || !self.collapsed.contains(&pi) {
+                row += phase.tasks.len();
+            }
+        }
+        self.selected = row;
+    }
+
     /// Get the phase index if the current selection is a phase header.
     /// Accounts for collapsed phases hiding their tasks.
     pub fn selected_phase_index(&self, state: &DashboardState) -> Option<usize> {
@@ -107,25 +267,14 @@ impl GanttState {
     }
 }
 
-/// Color for a task status
-fn status_color(status: &TaskStatus) -> Color {
+/// Rank used to pick a minimap cell's color when it covers several tasks
+fn status_severity(status: &TaskStatus) -> u8 {
     match status {
-        TaskStatus::Completed => Color::Green,
-        TaskStatus::InProgress => Color::Yellow,
-        TaskStatus::Pending => Color::DarkGray,
-        TaskStatus::Failed => Color::Red,
-        TaskStatus::Blocked => Color::Magenta,
-    }
-}
-
-/// Status icon character
-fn status_icon(status: &TaskStatus) -> &'static str {
-    match status {
-        TaskStatus::Completed => "[x]",
-        TaskStatus::InProgress => "[/]",
-        TaskStatus::Pending => "[ ]",
-        TaskStatus::Failed => "[!]",
-        TaskStatus::Blocked => "[B]",
+        TaskStatus::Completed => 0,
+        TaskStatus::Pending => 1,
+        TaskStatus::InProgress => 2,
+        TaskStatus::Blocked => 3,
+        TaskStatus::Failed => 4,
     }
 }
 
@@ -143,47 +292,418 @@ fn progress_bar(ratio: f32, width: usize) -> String {
     bar
 }
 
+/// Compact duration: `45s`, `12m`, `1h12m`
+fn format_span(d: chrono::Duration) -> String {
+    let secs = d.num_seconds().max(0);
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+/// Phase progress label: ` 40%`, or ` 40% (1h12m elapsed, ~45m left)` once
+/// hook events have timed the phase
+fn phase_progress_label(pct: u8, timing: &PhaseTiming) -> String {
+    let mut parts = Vec::new();
+    if let Some(elapsed) = timing.elapsed {
+        parts.push(format!("{} elapsed", format_span(elapsed)));
+    }
+    if let Some(left) = timing.remaining {
+        parts.push(format!("~{} left", format_span(left)));
+    }
+    if parts.is_empty() {
+        format!(" {pct}%")
+    } else {
+        format!(" {pct}% ({})", parts.join(", "))
+    }
+}
+
+/// Ellipsis appended to truncated names
+const ELLIPSIS: &str = "\u{2026}";
+
+/// Truncate `text` to at most `max_width` display columns, ending with `…` when cut.
+/// Cuts at a word boundary when that keeps at least two thirds of the budget.
+pub(crate) fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let budget = max_width - ELLIPSIS.width();
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    if let Some(pos) = out.rfind(' ') {
+        if pos > 0 && out[..pos].width() * 3 >= budget * 2 {
+            out.truncate(pos);
+        }
+    }
+    format!("{}{ELLIPSIS}", out.trim_end())
+}
+
+/// Split `text` into a head fitting `max_width` columns and the remaining tail.
+/// Breaks at the last space inside the head when there is one.
+fn split_at_width(text: &str, max_width: usize) -> (String, String) {
+    let mut end = 0;
+    let mut used = 0;
+    for (i, c) in text.char_indices() {
+        let w = c.width().unwrap_or(0);
+        if used + w > max_width {
+            break;
+        }
+        used += w;
+        end = i + c.len_utf8();
+    }
+    if end >= text.len() {
+        return (text.to_string(), String::new());
+    }
+    let break_at = text[..end].rfind(' ').filter(|&p| p > 0).unwrap_or(end);
+    (
+        text[..break_at].trim_end().to_string(),
+        text[break_at..].trim_start().to_string(),
+    )
+}
+
+/// A selectable row in the gantt list. Wrapped rows span more than one line.
+struct GanttRow {
+    lines: Vec<Line<'static>>,
+    selected: bool,
+    /// Flat task index (across all phases) for task rows
+    task: Option<usize>,
+    /// Index of the owning phase header row, for task rows
+    phase_row: Option<usize>,
+}
+
+impl GanttRow {
+    fn single(line: Line<'static>, selected: bool) -> Self {
+        Self {
+            lines: vec![line],
+            selected,
+            task: None,
+            phase_row: None,
+        }
+    }
+
+    /// Mark the row as showing the task at `flat` (index across all phases)
+    fn for_task(mut self, flat: usize) -> Self {
+        self.task = Some(flat);
+        self
+    }
+
+    /// Record the phase header row this task belongs to
+    fn under(mut self, phase_row: usize) -> Self {
+        self.phase_row = Some(phase_row);
+        self
+    }
+}
+
+/// Phase header to pin above the list when the first visible row is a task
+/// whose header has scrolled out of view
+fn sticky_header(rows: &[GanttRow], offset: usize) -> Option<usize> {
+    rows.get(offset)?.phase_row.filter(|&h| h < offset)
+}
+
 /// The Gantt widget renders the dashboard state as a scrollable task list
 pub struct GanttWidget<'a> {
     state: &'a DashboardState,
     focused: bool,
+    icons: IconSet,
+    theme: Theme,
+    /// Reference time for phase elapsed/ETA labels
+    now: DateTime<Utc>,
+    /// Task row columns, in display order
+    columns: Vec<TaskColumn>,
+    /// Inline rename drawn in place of the target row's name
+    rename: Option<&'a RenameState>,
+    /// Retries at which a task's badge turns into a call for a human
+    retry_threshold: usize,
 }
 
 impl<'a> GanttWidget<'a> {
     pub fn new(state: &'a DashboardState, focused: bool) -> Self {
-        Self { state, focused }
+        Self {
+            state,
+            focused,
+            icons: IconSet::default(),
+            theme: Theme::default(),
+            now: Utc::now(),
+            columns: TaskColumn::DEFAULT.to_vec(),
+            rename: None,
+            retry_threshold: DEFAULT_RETRY_THRESHOLD as usize,
+        }
+    }
+
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = now;
+        self
     }
 
-    /// Build lines for the tree view (with collapse, connectors, progress bars)
-    fn build_tree_lines(&self, gantt_state: &GanttState) -> Vec<(Line<'static>, bool)> {
-        let mut lines = Vec::new();
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// Task row columns; an empty list keeps the default layout
+    pub fn with_columns(mut self, columns: &[TaskColumn]) -> Self {
+        if !columns.is_empty() {
+            self.columns = columns.to_vec();
+        }
+        self
+    }
+
+    pub fn with_rename(mut self, rename: Option<&'a RenameState>) -> Self {
+        self.rename = rename;
+        self
+    }
+
+    pub fn with_retry_threshold(mut self, threshold: usize) -> Self {
+        self.retry_threshold = threshold;
+        self
+    }
+
+    /// "↻N" badge of a retried task, loud once it needs a human look
+    fn retry_badge(&self, task: &ParsedTask) -> Option<Span<'static>> {
+        if task.retries == 0 {
+            return None;
+        }
+        let style = if task.retries >= self.retry_threshold {
+            Style::default()
+                .fg(self.theme.failed)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.theme.in_progress)
+        };
+        Some(Span::styled(format!("\u{21BB}{} ", task.retries), style))
+    }
+
+    /// The rename editor when it targets `target`
+    fn rename_for(&self, target: &RenameTarget) -> Option<&'a RenameState> {
+        self.rename.filter(|r| &r.target == target)
+    }
+
+    /// Color for a priority value: urgent ones stand out, low and
+    /// free-form ones recede
+    fn priority_color(&self, priority: &str) -> Color {
+        if PriorityLevel::of(priority) == PriorityLevel::High {
+            return self.theme.failed;
+        }
+        match priority.to_ascii_lowercase().as_str() {
+            "medium" | "normal" | "p2" => self.theme.in_progress,
+            _ => self.theme.muted,
+        }
+    }
+
+    /// Style of a task's name: high-priority work still to do stands out
+    /// even without the priority column, and overdue tasks are red
+    fn task_name_style(&self, task: &ParsedTask) -> Style {
+        let today = self.now.with_timezone(&Local).date_naive();
+        let mut style = Style::default();
+        if task.is_overdue(today) {
+            style = style.fg(self.theme.failed);
+        }
+        if task.priority_level() == PriorityLevel::High && task.status != TaskStatus::Completed {
+            style = style.fg(self.theme.failed).add_modifier(Modifier::BOLD);
+        }
+        style
+    }
+
+    /// Content of one non-name column, or `None` when the task has no value
+    fn column_cell(&self, task: &ParsedTask, column: TaskColumn) -> Option<Span<'static>> {
+        let muted = Style::default().fg(self.theme.muted);
+        match column {
+            TaskColumn::Icon => Some(Span::styled(
+                self.icons.task_status(task.shown_status()).to_string(),
+                Style::default().fg(self.theme.task_status(task.shown_status())),
+            )),
+            TaskColumn::Id => Some(Span::styled(
+                task.id.clone(),
+                Style::default()
+                    .fg(self.theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            TaskColumn::Agent => task
+                .agent
+                .as_deref()
+                .map(|a| Span::styled(format!("@{a}"), Style::default().fg(Color::Blue))),
+            TaskColumn::Priority => task.priority.as_deref().map(|p| {
+                Span::styled(format!("!{p}"), Style::default().fg(self.priority_color(p)))
+            }),
+            TaskColumn::Eta => self
+                .state
+                .task_eta(task, self.now)
+                .map(|eta| Span::styled(format!("~{}", format_span(eta)), muted)),
+            TaskColumn::Tags => (!task.tags.is_empty()).then(|| {
+                let tags: Vec<String> = task.tags.iter().map(|t| format!("#{t}")).collect();
+                Span::styled(truncate_to_width(&tags.join(" "), MAX_TAGS_WIDTH), muted)
+            }),
+            TaskColumn::Name => None,
+        }
+    }
+
+    /// `(reason)` suffix of a blocked task: its recorded reason, else the
+    /// dependencies it still waits on
+    fn blocked_reason_span(&self, task: &ParsedTask) -> Option<Span<'static>> {
+        let reason = match (&task.status, task.blocked_reason.as_deref()) {
+            (TaskStatus::Blocked, Some(reason)) => reason.to_string(),
+            (TaskStatus::Blocked, None) => {
+                let unmet: Vec<String> = self
+                    .state
+                    .unmet_dependencies(task)
+                    .into_iter()
+                    .map(|(dep, _)| dep)
+                    .collect();
+                if unmet.is_empty() {
+                    return None;
+                }
+                format!("waits on {}", unmet.join(", "))
+            }
+            (TaskStatus::Pending, _) if !task.waiting_on.is_empty() => {
+                format!("waits on {}", task.waiting_on.join(", "))
+            }
+            _ => return None,
+        };
+        Some(Span::styled(
+            format!("({})", truncate_to_width(&reason, MAX_REASON_WIDTH)),
+            Style::default().fg(self.theme.blocked),
+        ))
+    }
+
+    /// Spans before and after the name for the given columns. Cells are
+    /// separated by a space; an ID directly before the name gets a colon.
+    /// A blocked task's reason follows the last column.
+    /// Returns `None` for the name position when `columns` has no name.
+    fn column_spans(
+        &self,
+        task: &ParsedTask,
+        columns: &[TaskColumn],
+    ) -> (Vec<Span<'static>>, Option<Vec<Span<'static>>>) {
+        let name_at = columns.iter().position(|c| *c == TaskColumn::Name);
+        let cells = |cols: &[TaskColumn]| -> Vec<Span<'static>> {
+            cols.iter()
+                .filter_map(|c| self.column_cell(task, *c))
+                .collect()
+        };
+        let reason = self.blocked_reason_span(task);
+
+        let Some(name_at) = name_at else {
+            let mut spans = Vec::new();
+            for (i, cell) in cells(columns).into_iter().chain(reason).enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(cell);
+            }
+            return (spans, None);
+        };
+
+        let mut before = Vec::new();
+        for cell in cells(&columns[..name_at]) {
+            before.push(cell);
+            before.push(Span::raw(" "));
+        }
+        if name_at > 0 && columns[name_at - 1] == TaskColumn::Id {
+            let at = before.len() - 1;
+            before[at] = Span::raw(": ");
+        }
+        let mut after = Vec::new();
+        for cell in cells(&columns[name_at + 1..]).into_iter().chain(reason) {
+            after.push(Span::raw(" "));
+            after.push(cell);
+        }
+        (before, Some(after))
+    }
+
+    /// Lay out the configured columns for `width` cells, dropping optional
+    /// columns while the name would be squeezed below [`MIN_NAME_WIDTH`]
+    fn fit_columns(
+        &self,
+        task: &ParsedTask,
+        width: usize,
+    ) -> (Vec<Span<'static>>, Option<Vec<Span<'static>>>) {
+        let mut columns = self.columns.clone();
+        loop {
+            let (before, after) = self.column_spans(task, &columns);
+            let Some(after) = after else {
+                return (before, None);
+            };
+            let fixed: usize = before.iter().chain(&after).map(|s| s.width()).sum();
+            let droppable = DROPPABLE_COLUMNS.iter().find(|c| columns.contains(c));
+            match droppable {
+                Some(drop) if width.saturating_sub(fixed) < MIN_NAME_WIDTH => {
+                    columns.retain(|c| c != drop);
+                }
+                _ => return (before, Some(after)),
+            }
+        }
+    }
+
+    /// Build rows for the tree view (with collapse, connectors, progress bars).
+    /// Names are truncated to fit `width` columns; the selected task wraps onto
+    /// a second line instead when `wrap_selected` is on.
+    fn build_tree_lines(&self, gantt_state: &GanttState, width: usize) -> Vec<GanttRow> {
+        let mut rows = Vec::new();
         let mut idx = 0;
+        let mut flat_base = 0;
+        let critical = self.state.critical_path(self.now);
 
         for (pi, phase) in self.state.phases.iter().enumerate() {
+            let phase_base = flat_base;
+            flat_base += phase.tasks.len();
             let is_selected = idx == gantt_state.selected;
             let is_collapsed = gantt_state.collapsed.contains(&pi);
             let progress = phase.progress();
             let pct = (progress * 100.0) as u8;
             let arrow = if is_collapsed { "\u{25B6}" } else { "\u{25BC}" };
             let bar = progress_bar(progress, 6);
-
-            let header = Line::from(vec![
-                Span::styled(format!(" {arrow} "), Style::default().fg(Color::Cyan)),
+            let pct_str = phase_progress_label(pct, &self.state.phase_timing(phase, self.now));
+
+            // " ▼ " + "P0 " + name + "  " + bar + " NN%"
+            let fixed_width = 3 + phase.id.width() + 1 + 2 + bar.width() + pct_str.width();
+            let name_width = width.saturating_sub(fixed_width);
+            let name_style = Style::default().add_modifier(Modifier::BOLD);
+            let name = match self.rename_for(&RenameTarget::Phase(phase.id.clone())) {
+                Some(editor) => editor.input.spans(name_width, name_style),
+                None => vec![Span::styled(
+                    truncate_to_width(&phase.name, name_width),
+                    name_style,
+                )],
+            };
+
+            let mut spans = vec![
+                Span::styled(format!(" {arrow} "), Style::default().fg(self.theme.accent)),
                 Span::styled(
                     format!("{} ", phase.id),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(self.theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(
-                    phase.name.clone(),
-                    Style::default().add_modifier(Modifier::BOLD),
-                ),
+            ];
+            spans.extend(name);
+            spans.extend([
                 Span::raw("  "),
-                Span::styled(bar, Style::default().fg(Color::Green)),
-                Span::styled(format!(" {pct}%"), Style::default().fg(Color::DarkGray)),
+                Span::styled(bar, Style::default().fg(self.theme.completed)),
+                Span::styled(pct_str, Style::default().fg(self.theme.muted)),
             ]);
-            lines.push((header, is_selected));
+            let header = Line::from(spans);
+            let header_row = rows.len();
+            rows.push(GanttRow::single(header, is_selected));
             idx += 1;
 
             if is_collapsed {
@@ -193,67 +713,112 @@ impl<'a> GanttWidget<'a> {
             let task_count = phase.tasks.len();
             for (ti, task) in phase.tasks.iter().enumerate() {
                 let is_selected = idx == gantt_state.selected;
-                let icon = status_icon(&task.status);
-                let color = status_color(&task.status);
-                let connector = if ti == task_count - 1 {
-                    "\u{2514}\u{2500}"
-                } else {
-                    "\u{251C}\u{2500}"
+                let last = ti == task_count - 1;
+                // Heavy connectors mark tasks on the critical path
+                let (connector, color) = match (critical.contains(&task.id), last) {
+                    (false, true) => ("\u{2514}\u{2500}", self.theme.muted),
+                    (false, false) => ("\u{251C}\u{2500}", self.theme.muted),
+                    (true, true) => ("\u{2517}\u{2501}", self.theme.failed),
+                    (true, false) => ("\u{2523}\u{2501}", self.theme.failed),
+                };
+                let connector = Span::styled(format!("  {connector} "), Style::default().fg(color));
+                let badge = self.retry_badge(task);
+                let badge_width = badge.as_ref().map_or(0, |b| b.width());
+                let (cells, after) =
+                    self.fit_columns(task, width.saturating_sub(connector.width() + badge_width));
+                let mut prefix = vec![connector];
+                prefix.extend(cells);
+                prefix.extend(badge);
+
+                let Some(after) = after else {
+                    rows.push(
+                        GanttRow::single(Line::from(prefix), is_selected)
+                            .for_task(phase_base + ti)
+                            .under(header_row),
+                    );
+                    idx += 1;
+                    continue;
                 };
-                let agent_str = task
-                    .agent
-                    .as_deref()
-                    .map(|a| format!(" @{a}"))
-                    .unwrap_or_default();
 
-                let line = Line::from(vec![
-                    Span::styled(
-                        format!("  {connector} "),
-                        Style::default().fg(Color::DarkGray),
-                    ),
-                    Span::styled(icon.to_string(), Style::default().fg(color)),
-                    Span::raw(" "),
-                    Span::styled(
-                        task.id.clone(),
-                        Style::default()
-                            .fg(Color::White)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(": "),
-                    Span::raw(task.name.clone()),
-                    Span::styled(agent_str, Style::default().fg(Color::Blue)),
-                ]);
-                lines.push((line, is_selected));
+                let prefix_width: usize = prefix.iter().map(|s| s.width()).sum();
+                let after_width: usize = after.iter().map(|s| s.width()).sum();
+                // Columns after the name always stay visible; the name gets what is left
+                let name_width = width.saturating_sub(prefix_width + after_width);
+
+                let editor = self.rename_for(&RenameTarget::Task(task.id.clone()));
+                let wrap = is_selected
+                    && editor.is_none()
+                    && gantt_state.wrap_selected
+                    && task.name.width() > name_width;
+                if let Some(editor) = editor {
+                    let mut spans = prefix;
+                    spans.extend(editor.input.spans(name_width, Style::default()));
+                    spans.extend(after);
+                    rows.push(
+                        GanttRow::single(Line::from(spans), is_selected)
+                            .for_task(phase_base + ti)
+                            .under(header_row),
+                    );
+                } else if wrap {
+                    let (head, tail) =
+                        split_at_width(&task.name, width.saturating_sub(prefix_width));
+                    let name_style = self.task_name_style(task);
+                    let mut first = prefix;
+                    first.push(Span::styled(head, name_style));
+                    let mut second = vec![
+                        Span::raw(" ".repeat(prefix_width)),
+                        Span::styled(truncate_to_width(&tail, name_width), name_style),
+                    ];
+                    second.extend(after);
+                    rows.push(GanttRow {
+                        lines: vec![Line::from(first), Line::from(second)],
+                        selected: is_selected,
+                        task: Some(phase_base + ti),
+                        phase_row: Some(header_row),
+                    });
+                } else {
+                    let mut spans = prefix;
+                    spans.push(Span::styled(
+                        truncate_to_width(&task.name, name_width),
+                        self.task_name_style(task),
+                    ));
+                    spans.extend(after);
+                    rows.push(
+                        GanttRow::single(Line::from(spans), is_selected)
+                            .for_task(phase_base + ti)
+                            .under(header_row),
+                    );
+                }
                 idx += 1;
             }
         }
-        lines
+        rows
     }
 
     /// Build lines for the horizontal bar view
-    fn build_bar_lines(&self, gantt_state: &GanttState) -> Vec<(Line<'static>, bool)> {
+    fn build_bar_lines(&self, gantt_state: &GanttState) -> Vec<GanttRow> {
         if self.state.phases.is_empty() {
-            return vec![(Line::raw("  No tasks"), false)];
+            return vec![GanttRow::single(Line::raw("  No tasks"), false)];
         }
 
-        // Collect timing info for time bounds calculation
+        // Time bounds from the phases' actual start and end; a phase still
+        // open runs until now
         let now = Utc::now();
-        let mut all_starts: Vec<DateTime<Utc>> = Vec::new();
-        let mut all_ends: Vec<DateTime<Utc>> = Vec::new();
-        for phase in &self.state.phases {
-            for task in &phase.tasks {
-                if let Some(timing) = self.state.task_times.get(&task.id) {
-                    if let Some(s) = timing.started_at {
-                        all_starts.push(s);
-                    }
-                    if let Some(c) = timing.completed_at {
-                        all_ends.push(c);
-                    }
-                }
-            }
-        }
-        let earliest = all_starts.iter().copied().min().unwrap_or(now);
-        let latest = all_ends.iter().copied().max().unwrap_or(now);
+        let earliest = self
+            .state
+            .phases
+            .iter()
+            .filter_map(|p| p.started_at)
+            .min()
+            .unwrap_or(now);
+        let latest = self
+            .state
+            .phases
+            .iter()
+            .filter(|p| p.started_at.is_some())
+            .map(|p| p.ended_at.unwrap_or(now))
+            .max()
+            .unwrap_or(now);
         let total_secs = (latest - earliest).num_seconds().max(1) as f64;
 
         // Determine label width from all task IDs
@@ -269,33 +834,40 @@ impl<'a> GanttWidget<'a> {
         let bar_area_width = 30usize;
         let duration_mins = total_secs / 60.0;
         let time_header = build_time_header(label_width, bar_area_width, duration_mins);
-        let mut lines: Vec<(Line<'static>, bool)> = vec![(time_header, false)];
+        let mut lines = vec![GanttRow::single(time_header, false)];
         let mut line_idx = 1usize;
+        let mut flat_base = 0;
 
         for phase in &self.state.phases {
+            let phase_base = flat_base;
+            flat_base += phase.tasks.len();
             // Phase separator header
             let pct = (phase.progress() * 100.0) as u8;
             let phase_line = Line::from(vec![
                 Span::styled(
                     format!(" {} ", phase.id),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(self.theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::styled(
                     phase.name.clone(),
                     Style::default().add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(format!(" {pct}%"), Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    phase_progress_label(pct, &self.state.phase_timing(phase, self.now)),
+                    Style::default().fg(self.theme.muted),
+                ),
             ]);
             let is_selected = line_idx == gantt_state.selected;
-            lines.push((phase_line, is_selected));
+            let header_row = lines.len();
+            lines.push(GanttRow::single(phase_line, is_selected));
             line_idx += 1;
 
             // Task bar rows
             for (ti, task) in phase.tasks.iter().enumerate() {
                 let is_selected = line_idx == gantt_state.selected;
-                let color = status_color(&task.status);
+                let color = self.theme.task_status(task.shown_status());
                 let timing = self.state.task_times.get(&task.id);
                 let started = timing.and_then(|t| t.started_at);
                 let completed = timing.and_then(|t| t.completed_at);
@@ -328,10 +900,7 @@ impl<'a> GanttWidget<'a> {
                     }
                 };
 
-                let bar_char = match task.status {
-                    TaskStatus::Completed | TaskStatus::InProgress => '\u{2588}',
-                    _ => '\u{2591}',
-                };
+                let bar_char = self.theme.bar_char(task.shown_status());
 
                 let connector = if ti == phase.tasks.len() - 1 {
                     "\u{2514} "
@@ -349,11 +918,15 @@ impl<'a> GanttWidget<'a> {
                 }
 
                 let line = Line::from(vec![
-                    Span::styled(connector.to_string(), Style::default().fg(Color::DarkGray)),
-                    Span::styled(label, Style::default().fg(Color::White)),
+                    Span::styled(connector.to_string(), Style::default().fg(self.theme.muted)),
+                    Span::styled(label, Style::default().fg(self.theme.text)),
                     Span::styled(bar, Style::default().fg(color)),
                 ]);
-                lines.push((line, is_selected));
+                lines.push(
+                    GanttRow::single(line, is_selected)
+                        .for_task(phase_base + ti)
+                        .under(header_row),
+                );
                 line_idx += 1;
             }
         }
@@ -404,44 +977,72 @@ fn build_time_header(label_width: usize, bar_width: usize, total_mins: f64) -> L
 
 /// Shared rendering logic for both view modes
 fn render_lines(
-    lines: &[(Line<'_>, bool)],
+    rows: &[GanttRow],
     inner: Rect,
     buf: &mut Buffer,
     gantt_state: &mut GanttState,
     focused: bool,
 ) {
-    gantt_state.total_items = lines.len();
+    gantt_state.total_items = rows.len();
 
-    // Adjust scroll offset to keep selection visible
+    // A pinned phase header takes the top line; skip it when that would leave
+    // no room for the list itself
     let visible_height = inner.height as usize;
-    if gantt_state.selected < gantt_state.offset {
-        gantt_state.offset = gantt_state.selected;
-    } else if gantt_state.selected >= gantt_state.offset + visible_height {
-        gantt_state.offset = gantt_state.selected - visible_height + 1;
-    }
-
-    for (i, (line, is_selected)) in lines
-        .iter()
-        .skip(gantt_state.offset)
-        .enumerate()
-        .take(visible_height)
-    {
-        let y = inner.y + i as u16;
-        if y >= inner.y + inner.height {
-            break;
+    let pinned = |offset: usize| {
+        if visible_height > 1 {
+            sticky_header(rows, offset)
+        } else {
+            None
         }
+    };
 
-        if *is_selected && focused {
-            buf.set_style(
-                Rect::new(inner.x, y, inner.width, 1),
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .add_modifier(Modifier::BOLD),
-            );
+    // Adjust scroll offset to keep the whole selected row visible
+    if gantt_state.selected < gantt_state.offset {
+        gantt_state.offset = gantt_state.selected;
+    } else {
+        let last = gantt_state.selected.min(rows.len().saturating_sub(1));
+        while gantt_state.offset < last
+            && rows[gantt_state.offset..=last]
+                .iter()
+                .map(|r| r.lines.len())
+                .sum::<usize>()
+                + pinned(gantt_state.offset).map_or(0, |_| 1)
+                > visible_height
+        {
+            gantt_state.offset += 1;
         }
+    }
 
+    let bottom = inner.y + inner.height;
+    let mut y = inner.y;
+    if let Some(header) = pinned(gantt_state.offset) {
         let line_area = Rect::new(inner.x, y, inner.width, 1);
-        Widget::render(line.clone(), line_area, buf);
+        Widget::render(rows[header].lines[0].clone(), line_area, buf);
+        buf.set_style(
+            line_area,
+            Style::default().add_modifier(Modifier::UNDERLINED),
+        );
+        y += 1;
+    }
+    for row in rows.iter().skip(gantt_state.offset) {
+        for line in &row.lines {
+            if y >= bottom {
+                return;
+            }
+
+            if row.selected && focused {
+                buf.set_style(
+                    Rect::new(inner.x, y, inner.width, 1),
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
+
+            let line_area = Rect::new(inner.x, y, inner.width, 1);
+            Widget::render(line.clone(), line_area, buf);
+            y += 1;
+        }
     }
 }
 
@@ -450,9 +1051,9 @@ impl<'a> StatefulWidget for GanttWidget<'a> {
 
     fn render(self, area: Rect, buf: &mut Buffer, gantt_state: &mut Self::State) {
         let border_style = if self.focused {
-            Style::default().fg(Color::Cyan)
+            Style::default().fg(self.theme.accent)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(self.theme.muted)
         };
 
         let view_label = match gantt_state.view_mode {
@@ -460,19 +1061,110 @@ impl<'a> StatefulWidget for GanttWidget<'a> {
             GanttViewMode::HorizontalBar => " Tasks (Gantt) ",
         };
 
-        let block = Block::default()
+        let mut block = Block::default()
             .title(view_label)
             .borders(Borders::ALL)
             .border_style(border_style);
+        if let Some(rename) = self.rename {
+            block = block.title_bottom(match rename.error {
+                Some(ref e) => {
+                    Line::styled(format!(" {e} "), Style::default().fg(self.theme.failed))
+                }
+                None => Line::styled(
+                    " Enter save  Esc cancel ",
+                    Style::default().fg(self.theme.muted),
+                ),
+            });
+        }
         let inner = block.inner(area);
         block.render(area, buf);
 
         let lines = match gantt_state.view_mode {
-            GanttViewMode::Tree => self.build_tree_lines(gantt_state),
+            GanttViewMode::Tree => self.build_tree_lines(gantt_state, inner.width as usize),
             GanttViewMode::HorizontalBar => self.build_bar_lines(gantt_state),
         };
 
-        render_lines(&lines, inner, buf, gantt_state, self.focused);
+        // One-row minimap on top when there is room for it
+        if self.state.total_tasks > 0 && inner.height >= 3 {
+            let minimap_area = Rect::new(inner.x, inner.y, inner.width, 1);
+            let list_area = Rect::new(inner.x, inner.y + 1, inner.width, inner.height - 1);
+            render_lines(&lines, list_area, buf, gantt_state, self.focused);
+            self.render_minimap(minimap_area, buf, gantt_state, &lines, list_area.height);
+        } else {
+            render_lines(&lines, inner, buf, gantt_state, self.focused);
+        }
+    }
+}
+
+impl<'a> GanttWidget<'a> {
+    /// Draw one cell per task (or per bucket of tasks when they outnumber the
+    /// columns). Cells inside the viewport are full blocks, the rest half blocks.
+    fn render_minimap(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        gantt_state: &mut GanttState,
+        rows: &[GanttRow],
+        visible_height: u16,
+    ) {
+        let statuses: Vec<&TaskStatus> = self
+            .state
+            .phases
+            .iter()
+            .flat_map(|p| p.tasks.iter().map(|t| t.shown_status()))
+            .collect();
+        let total = statuses.len();
+        let cells = total.min(area.width as usize);
+        gantt_state.minimap_width = cells;
+        if cells == 0 {
+            return;
+        }
+
+        // Tasks currently scrolled into view
+        let mut in_view = HashSet::new();
+        let mut used =
+            usize::from(visible_height > 1 && sticky_header(rows, gantt_state.offset).is_some());
+        for row in rows.iter().skip(gantt_state.offset) {
+            if used >= visible_height as usize {
+                break;
+            }
+            used += row.lines.len();
+            if let Some(flat) = row.task {
+                in_view.insert(flat);
+            }
+        }
+
+        for cell in 0..cells {
+            let start = cell * total / cells;
+            let end = ((cell + 1) * total / cells).max(start + 1);
+            let status = statuses[start..end]
+                .iter()
+                .max_by_key(|s| status_severity(s))
+                .copied()
+                .unwrap_or(&TaskStatus::Pending);
+            let color = self.theme.task_status(status);
+            let visible = (start..end).any(|i| in_view.contains(&i));
+            let is_cursor = gantt_state
+                .minimap_cursor
+                .is_some_and(|c| (start..end).contains(&c));
+
+            let (symbol, style) = if is_cursor {
+                (
+                    "\u{25C6}",
+                    Style::default()
+                        .fg(self.theme.text)
+                        .bg(color)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if visible {
+                ("\u{2588}", Style::default().fg(color))
+            } else {
+                ("\u{2584}", Style::default().fg(color))
+            };
+            buf[(area.x + cell as u16, area.y)]
+                .set_symbol(symbol)
+                .set_style(style);
+        }
     }
 }
 
@@ -484,6 +1176,110 @@ mod tests {
         DashboardState::from_tasks_content(input).unwrap()
     }
 
+    fn render_rows(state: &DashboardState, gs: &mut GanttState, w: u16, h: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, w, h);
+        let mut buf = Buffer::empty(area);
+        GanttWidget::new(state, true).render(area, &mut buf, gs);
+        (0..h)
+            .map(|y| (0..w).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn minimap_draws_one_cell_per_task() {
+        let state = sample_state();
+        let mut gs = GanttState::default();
+        let rows = render_rows(&state, &mut gs, 60, 30);
+        // Row 1 is the minimap (row 0 is the border)
+        let minimap: String = rows[1].chars().skip(1).take(state.total_tasks).collect();
+        assert_eq!(gs.minimap_width, state.total_tasks);
+        assert!(
+            minimap.chars().all(|c| c == '\u{2588}'),
+            "all visible: {minimap}"
+        );
+    }
+
+    #[test]
+    fn minimap_marks_offscreen_tasks() {
+        let state = sample_state();
+        let mut gs = GanttState::default();
+        let rows = render_rows(&state, &mut gs, 60, 6);
+        let minimap: String = rows[1].chars().skip(1).take(state.total_tasks).collect();
+        assert!(minimap.contains('\u{2588}'));
+        assert!(
+            minimap.contains('\u{2584}'),
+            "tail is off screen: {minimap}"
+        );
+    }
+
+    #[test]
+    fn minimap_buckets_tasks_when_narrow() {
+        let state = sample_state();
+        let mut gs = GanttState::default();
+        render_rows(&state, &mut gs, 6, 10);
+        assert_eq!(gs.minimap_width, 4);
+    }
+
+    #[test]
+    fn minimap_jump_selects_task_and_expands_phase() {
+        let state = sample_state();
+        let mut gs = GanttState::default();
+        gs.collapsed.insert(1);
+        gs.open_minimap(&state);
+        assert_eq!(gs.minimap_cursor, Some(0));
+        gs.minimap_width = state.total_tasks;
+        // Move to the first task of phase 1
+        let phase0 = state.phases[0].tasks.len() as isize;
+        gs.minimap_move(&state, phase0);
+        gs.minimap_jump(&state);
+        assert!(gs.minimap_cursor.is_none());
+        assert!(!gs.collapsed.contains(&1));
+        assert_eq!(gs.selected_task(&state), Some((1, 0)));
+    }
+
+    #[test]
+    fn minimap_move_clamps() {
+        let state = sample_state();
+        let mut gs = GanttState {
+            minimap_width: 100,
+            ..Default::default()
+        };
+        gs.open_minimap(&state);
+        gs.minimap_move(&state, -5);
+        assert_eq!(gs.minimap_cursor, Some(0));
+        gs.minimap_move(&state, 100);
+        assert_eq!(gs.minimap_cursor, Some(state.total_tasks - 1));
+    }
+
+    #[test]
+    fn select_flat_task_in_bar_view_skips_time_axis() {
+        let state = sample_state();
+        let mut gs = GanttState {
+            view_mode: GanttViewMode::HorizontalBar,
+            ..Default::default()
+        };
+        gs.select_flat_task(&state, 0);
+        // time axis + phase header
+        assert_eq!(gs.selected, 2);
+    }
+
+    #[test]
+    fn select_phase_skips_collapsed_tasks_and_collapse_follows_shifts() {
+        let state = sample_state();
+        let mut gs = GanttState::default();
+        gs.collapsed.insert(0);
+        gs.select_phase(&state, 2);
+        // P0 (collapsed) + P1 header + 3 tasks
+        assert_eq!(gs.selected, 5);
+        assert_eq!(gs.selected_phase_index(&state), Some(2));
+
+        gs.collapsed.insert(2);
+        gs.shift_collapsed(1, true);
+        assert_eq!(gs.collapsed, HashSet::from([0, 1]));
+        gs.shift_collapsed(0, false);
+        assert_eq!(gs.collapsed, HashSet::from([1, 2]));
+    }
+
     #[test]
     fn gantt_state_navigation() {
         let mut gs = GanttState {
@@ -553,20 +1349,135 @@ mod tests {
 
     #[test]
     fn status_colors_all_mapped() {
-        assert_eq!(status_color(&TaskStatus::Completed), Color::Green);
-        assert_eq!(status_color(&TaskStatus::InProgress), Color::Yellow);
-        assert_eq!(status_color(&TaskStatus::Pending), Color::DarkGray);
-        assert_eq!(status_color(&TaskStatus::Failed), Color::Red);
-        assert_eq!(status_color(&TaskStatus::Blocked), Color::Magenta);
+        assert_eq!(
+            Theme::default().task_status(&TaskStatus::Completed),
+            Color::Green
+        );
+        assert_eq!(
+            Theme::default().task_status(&TaskStatus::InProgress),
+            Color::Yellow
+        );
+        assert_eq!(
+            Theme::default().task_status(&TaskStatus::Pending),
+            Color::DarkGray
+        );
+        assert_eq!(
+            Theme::default().task_status(&TaskStatus::Failed),
+            Color::Red
+        );
+        assert_eq!(
+            Theme::default().task_status(&TaskStatus::Blocked),
+            Color::Magenta
+        );
+    }
+
+    #[test]
+    fn tree_uses_configured_icons() {
+        let state = sample_state();
+        let gs = GanttState::default();
+        let ascii = GanttWidget::new(&state, true).build_tree_lines(&gs, 80);
+        let nerd = GanttWidget::new(&state, true)
+            .with_icons(IconSet::Nerd)
+            .build_tree_lines(&gs, 80);
+        let text = |rows: &[GanttRow]| -> String {
+            rows.iter()
+                .flat_map(|r| r.lines.iter())
+                .flat_map(|l| l.spans.iter())
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+        assert!(text(&ascii).contains("[x]"));
+        assert!(!text(&nerd).contains("[x]"));
+        assert!(text(&nerd).contains("\u{f00c}"));
+    }
+
+    #[test]
+    fn critical_tasks_get_heavy_connectors() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [InProgress] T1: Parser\n\n### [ ] T2: Writer\n- **blocked_by**: T1\n\n### [ ] T3: Docs\n",
+        )
+        .unwrap();
+        let rows = GanttWidget::new(&state, true).build_tree_lines(&GanttState::default(), 80);
+        let connector = |row: usize| rows[row].lines[0].spans[0].content.to_string();
+        assert_eq!(connector(1), "  \u{2523}\u{2501} ");
+        assert_eq!(connector(2), "  \u{2523}\u{2501} ");
+        assert_eq!(connector(3), "  \u{2514}\u{2500} ");
+    }
+
+    #[test]
+    fn retried_tasks_get_a_badge() {
+        let mut state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n\n### [InProgress] T1: Parser\n\n### [ ] T2: Writer\n",
+        )
+        .unwrap();
+        state.phases[0].tasks[0].retries = 2;
+        let theme = Theme::default();
+        let badge = |threshold| {
+            let rows = GanttWidget::new(&state, true)
+                .with_retry_threshold(threshold)
+                .build_tree_lines(&GanttState::default(), 80);
+            let find = |row: usize| {
+                rows[row].lines[0]
+                    .spans
+                    .iter()
+                    .find(|s| s.content.starts_with('\u{21BB}'))
+                    .cloned()
+            };
+            assert!(find(2).is_none());
+            find(1).unwrap()
+        };
+        let quiet = badge(3);
+        assert_eq!(quiet.content, "\u{21BB}2 ");
+        assert_eq!(quiet.style.fg, Some(theme.in_progress));
+        assert_eq!(badge(2).style.fg, Some(theme.failed));
     }
 
     #[test]
-    fn status_icons_all_mapped() {
-        assert_eq!(status_icon(&TaskStatus::Completed), "[x]");
-        assert_eq!(status_icon(&TaskStatus::InProgress), "[/]");
-        assert_eq!(status_icon(&TaskStatus::Pending), "[ ]");
-        assert_eq!(status_icon(&TaskStatus::Failed), "[!]");
-        assert_eq!(status_icon(&TaskStatus::Blocked), "[B]");
+    fn phase_header_shows_elapsed_and_eta() {
+        use crate::data::state::TaskTiming;
+
+        let mut state = sample_state();
+        let t0 = Utc::now();
+        let mins = chrono::Duration::minutes;
+        // Phase 1: first task done in 20m, two still open
+        state.phases[1].tasks[0].status = TaskStatus::Completed;
+        let first = state.phases[1].tasks[0].id.clone();
+        state.task_times.insert(
+            first,
+            TaskTiming {
+                started_at: Some(t0),
+                completed_at: Some(t0 + mins(20)),
+                ..Default::default()
+            },
+        );
+        state.refresh_phase_times();
+
+        let widget = GanttWidget::new(&state, true).with_now(t0 + mins(72));
+        let rows = widget.build_tree_lines(&GanttState::default(), 120);
+        let header: String = rows[3].lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(
+            header.ends_with("33% (1h12m elapsed, ~40m left)"),
+            "got: {header}"
+        );
+
+        // Phases without events keep the plain percentage
+        let p0: String = rows[0].lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(p0.ends_with("100%"), "got: {p0}");
+    }
+
+    #[test]
+    fn format_span_units() {
+        assert_eq!(format_span(chrono::Duration::seconds(45)), "45s");
+        assert_eq!(format_span(chrono::Duration::minutes(12)), "12m");
+        assert_eq!(format_span(chrono::Duration::minutes(72)), "1h12m");
     }
 
     #[test]
@@ -574,7 +1485,7 @@ mod tests {
         let state = sample_state();
         let widget = GanttWidget::new(&state, true);
         let gs = GanttState::default();
-        let lines = widget.build_tree_lines(&gs);
+        let lines = widget.build_tree_lines(&gs, 80);
         // 3 phases + 8 tasks = 11 lines
         assert_eq!(lines.len(), 11);
     }
@@ -585,7 +1496,7 @@ mod tests {
         let widget = GanttWidget::new(&state, true);
         let mut gs = GanttState::default();
         gs.collapsed.insert(0); // collapse phase 0 (2 tasks hidden)
-        let lines = widget.build_tree_lines(&gs);
+        let lines = widget.build_tree_lines(&gs, 80);
         // 3 phases + (0 + 3 + 3) tasks = 9 lines
         assert_eq!(lines.len(), 9);
     }
@@ -628,6 +1539,304 @@ mod tests {
         assert_eq!(gs.view_mode, GanttViewMode::Tree);
     }
 
+    #[test]
+    fn truncate_short_text_unchanged() {
+        assert_eq!(truncate_to_width("Short name", 20), "Short name");
+    }
+
+    #[test]
+    fn truncate_long_text_adds_ellipsis() {
+        let out = truncate_to_width("Implement the hook event parser module", 20);
+        assert!(out.ends_with('\u{2026}'));
+        assert!(out.width() <= 20);
+        // Cut at a word boundary, not mid-word
+        assert_eq!(out, "Implement the hook\u{2026}");
+    }
+
+    #[test]
+    fn truncate_wide_chars_by_display_width() {
+        let out = truncate_to_width("프로젝트 셋업 작업", 9);
+        assert!(out.width() <= 9);
+        assert!(out.ends_with('\u{2026}'));
+    }
+
+    #[test]
+    fn truncate_zero_width() {
+        assert_eq!(truncate_to_width("anything", 0), "");
+    }
+
+    #[test]
+    fn split_at_width_breaks_on_space() {
+        let (head, tail) = split_at_width("TASKS.md parser implementation", 16);
+        assert_eq!(head, "TASKS.md parser");
+        assert_eq!(tail, "implementation");
+    }
+
+    #[test]
+    fn split_at_width_fits_entirely() {
+        let (head, tail) = split_at_width("short", 16);
+        assert_eq!(head, "short");
+        assert!(tail.is_empty());
+    }
+
+    #[test]
+    fn narrow_tree_keeps_agent_badge() {
+        let state = sample_state();
+        let widget = GanttWidget::new(&state, true);
+        let gs = GanttState::default();
+        let rows = widget.build_tree_lines(&gs, 40);
+        let task_row = &rows[1].lines[0];
+        let text: String = task_row.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.ends_with("@backend-specialist"), "got: {text}");
+        assert!(task_row.width() <= 40);
+    }
+
+    fn row_text(row: &GanttRow) -> String {
+        row.lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn default_columns_keep_classic_layout() {
+        let state = sample_state();
+        let rows = GanttWidget::new(&state, true)
+            .with_columns(&[])
+            .build_tree_lines(&GanttState::default(), 80);
+        assert_eq!(
+            row_text(&rows[1]),
+            "  \u{251C}\u{2500} [x] P0-T0.1: Cargo project setup @backend-specialist"
+        );
+    }
+
+    #[test]
+    fn configured_columns_follow_order_and_show_metadata() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [ ] T1: Write docs\n- **priority**: high\n- **tags**: docs, ux\n",
+        )
+        .unwrap();
+        let rows = GanttWidget::new(&state, true)
+            .with_columns(&[
+                TaskColumn::Priority,
+                TaskColumn::Name,
+                TaskColumn::Tags,
+                TaskColumn::Id,
+            ])
+            .build_tree_lines(&GanttState::default(), 80);
+        assert_eq!(
+            row_text(&rows[1]),
+            "  \u{2514}\u{2500} !high Write docs #docs #ux T1"
+        );
+    }
+
+    #[test]
+    fn high_priority_work_left_stands_out() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [ ] T1: Fix login !high\n### [x] T2: Ship it !high\n### [ ] T3: Tidy !low\n",
+        )
+        .unwrap();
+        let rows = GanttWidget::new(&state, true).build_tree_lines(&GanttState::default(), 80);
+        let name = |row: usize, text: &str| {
+            rows[row].lines[0]
+                .spans
+                .iter()
+                .find(|s| s.content == text)
+                .unwrap()
+                .style
+        };
+        let urgent = name(1, "Fix login");
+        assert_eq!(urgent.fg, Some(Theme::default().failed));
+        assert!(urgent.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(name(2, "Ship it"), Style::default());
+        assert_eq!(name(3, "Tidy"), Style::default());
+    }
+
+    #[test]
+    fn free_form_priorities_are_muted() {
+        let state = DashboardState::default();
+        let widget = GanttWidget::new(&state, true);
+        let theme = Theme::default();
+        assert_eq!(widget.priority_color("P1"), theme.failed);
+        assert_eq!(widget.priority_color("medium"), theme.in_progress);
+        assert_eq!(widget.priority_color("low"), theme.muted);
+        assert_eq!(widget.priority_color("someday"), theme.muted);
+    }
+
+    #[test]
+    fn overdue_tasks_are_red() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [ ] T1: Late\n- due: 2000-01-01\n### [x] T2: Done late\n- due: 2000-01-01\n",
+        )
+        .unwrap();
+        let rows = GanttWidget::new(&state, true).build_tree_lines(&GanttState::default(), 80);
+        let name_color = |row: usize, text: &str| {
+            rows[row].lines[0]
+                .spans
+                .iter()
+                .find(|s| s.content == text)
+                .unwrap()
+                .style
+                .fg
+        };
+        assert_eq!(name_color(1, "Late"), Some(Theme::default().failed));
+        assert_eq!(name_color(2, "Done late"), None);
+    }
+
+    #[test]
+    fn narrow_rows_drop_optional_columns_first() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [ ] T1: Write the documentation\n- **priority**: low\n- **tags**: documentation, ux\n- @writer\n",
+        )
+        .unwrap();
+        let widget = GanttWidget::new(&state, true).with_columns(&[
+            TaskColumn::Id,
+            TaskColumn::Name,
+            TaskColumn::Priority,
+            TaskColumn::Tags,
+            TaskColumn::Agent,
+        ]);
+        let wide = row_text(&widget.build_tree_lines(&GanttState::default(), 100)[1]);
+        assert!(
+            wide.contains("#documentation #ux") && wide.contains("!low"),
+            "got: {wide}"
+        );
+
+        let narrow = row_text(&widget.build_tree_lines(&GanttState::default(), 40)[1]);
+        assert!(!narrow.contains('#'), "got: {narrow}");
+        assert!(
+            narrow.contains("!low") && narrow.ends_with("@writer"),
+            "got: {narrow}"
+        );
+    }
+
+    #[test]
+    fn blocked_task_row_ends_with_reason() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [Blocked] T1: Deploy\n- **blocked**: waiting on API keys from the vendor\n- @ops\n### [ ] T2: Docs\n- **blocked**: stale\n",
+        )
+        .unwrap();
+        let rows = GanttWidget::new(&state, true).build_tree_lines(&GanttState::default(), 80);
+        let blocked = row_text(&rows[1]);
+        assert!(
+            blocked.ends_with("Deploy @ops (waiting on API keys…)"),
+            "got: {blocked}"
+        );
+        // Only shown while the task is blocked
+        assert!(!row_text(&rows[2]).contains("stale"));
+    }
+
+    #[test]
+    fn task_waiting_on_dependencies_names_them() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 0: A\n### [x] T1: Setup\n### [ ] T2: Parser\n### [ ] T3: Docs\n- depends: T1, T2\n",
+        )
+        .unwrap();
+        let rows = GanttWidget::new(&state, true).build_tree_lines(&GanttState::default(), 80);
+        let waiting = row_text(&rows[3]);
+        assert!(waiting.ends_with("Docs (waits on T2)"), "got: {waiting}");
+    }
+
+    #[test]
+    fn rename_editor_replaces_row_name() {
+        let state = sample_state();
+        let editor = RenameState::new(RenameTarget::Task("P0-T0.1".into()), "Cargo setup");
+        let rows = GanttWidget::new(&state, true)
+            .with_rename(Some(&editor))
+            .build_tree_lines(&GanttState::default(), 80);
+        assert_eq!(
+            row_text(&rows[1]),
+            "  \u{251C}\u{2500} [x] P0-T0.1: Cargo setup  @backend-specialist"
+        );
+        // Other rows are untouched
+        assert!(row_text(&rows[2]).contains("GitHub Actions CI"));
+
+        let editor = RenameState::new(RenameTarget::Phase("P0".into()), "Init");
+        let rows = GanttWidget::new(&state, true)
+            .with_rename(Some(&editor))
+            .build_tree_lines(&GanttState::default(), 80);
+        assert!(row_text(&rows[0]).starts_with(" \u{25BC} P0 Init   "));
+    }
+
+    #[test]
+    fn task_column_parses_case_insensitively() {
+        assert_eq!("ETA".parse::<TaskColumn>(), Ok(TaskColumn::Eta));
+        assert!("size".parse::<TaskColumn>().is_err());
+    }
+
+    #[test]
+    fn wrap_selected_spans_two_lines() {
+        let state = sample_state();
+        let widget = GanttWidget::new(&state, true);
+        let mut gs = GanttState {
+            selected: 4, // P1-R1-T1: TASKS.md parser implementation
+            ..Default::default()
+        };
+        let rows = widget.build_tree_lines(&gs, 44);
+        assert_eq!(rows[4].lines.len(), 1);
+
+        gs.toggle_wrap();
+        let rows = widget.build_tree_lines(&gs, 44);
+        assert_eq!(rows.len(), 11);
+        assert_eq!(rows[4].lines.len(), 2);
+        let second: String = rows[4].lines[1]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(second.contains("@backend-specialist"));
+        // Unselected rows never wrap
+        assert_eq!(rows[5].lines.len(), 1);
+    }
+
+    #[test]
+    fn render_wrapped_selection_scrolls_into_view() {
+        let state = sample_state();
+        let widget = GanttWidget::new(&state, true);
+        let mut gs = GanttState {
+            selected: 10,
+            wrap_selected: true,
+            ..Default::default()
+        };
+        let area = Rect::new(0, 0, 30, 6);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf, &mut gs);
+        // 4 inner lines: the selected row must fit entirely below the offset
+        assert!(gs.offset <= 10);
+        assert!(gs.offset >= 7);
+    }
+
+    #[test]
+    fn phase_header_sticks_while_scrolling_its_tasks() {
+        let state = sample_state();
+        // Row 6 is the last task of P1 (header at row 3)
+        let mut gs = GanttState {
+            selected: 6,
+            ..Default::default()
+        };
+        // 6 rows: border, minimap, 3 list lines, border
+        let rows = render_rows(&state, &mut gs, 60, 6);
+        assert_eq!(gs.offset, 5);
+        assert!(rows[2].contains("P1"), "got: {rows:?}");
+        assert!(rows[3].contains("P1-R2-T1"), "got: {rows:?}");
+        assert!(rows[4].contains("P1-R3-T1"), "got: {rows:?}");
+    }
+
+    #[test]
+    fn no_sticky_header_when_phase_header_visible() {
+        let state = sample_state();
+        let mut gs = GanttState {
+            selected: 4,
+            offset: 3,
+            ..Default::default()
+        };
+        let rows = render_rows(&state, &mut gs, 60, 6);
+        assert_eq!(gs.offset, 3);
+        assert_eq!(rows[2].matches("P1").count(), 1, "got: {rows:?}");
+        assert!(rows[3].contains("P1-R1-T1"), "got: {rows:?}");
+    }
+
     #[test]
     fn progress_bar_full() {
         let bar = progress_bar(1.0, 6);
@@ -661,8 +1870,10 @@ mod tests {
     fn render_bar_does_not_panic() {
         let state = sample_state();
         let widget = GanttWidget::new(&state, true);
-        let mut gs = GanttState::default();
-        gs.view_mode = GanttViewMode::HorizontalBar;
+        let mut gs = GanttState {
+            view_mode: GanttViewMode::HorizontalBar,
+            ..Default::default()
+        };
         let area = Rect::new(0, 0, 80, 20);
         let mut buf = Buffer::empty(area);
         widget.render(area, &mut buf, &mut gs);
@@ -674,8 +1885,10 @@ mod tests {
     fn render_bar_empty_state() {
         let state = DashboardState::default();
         let widget = GanttWidget::new(&state, true);
-        let mut gs = GanttState::default();
-        gs.view_mode = GanttViewMode::HorizontalBar;
+        let mut gs = GanttState {
+            view_mode: GanttViewMode::HorizontalBar,
+            ..Default::default()
+        };
         let area = Rect::new(0, 0, 80, 20);
         let mut buf = Buffer::empty(area);
         widget.render(area, &mut buf, &mut gs);
@@ -1,15 +1,153 @@
 //! Help overlay
 //!
-//! Shows keybinding help as a centered popup overlay.
+//! Shows keybinding help as a centered popup overlay, grouped by pane/mode.
+//! Typing narrows the list; arrows and PgUp/PgDn scroll it.
 
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Widget},
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
 };
 
+/// Keybindings grouped by the pane or mode they apply to
+const SECTIONS: &[(&str, &[(&str, &str)])] = &[
+    (
+        "General",
+        &[
+            ("Tab", "Focus: Tasks→Detail→Agents"),
+            ("L", "Layout: split / stacked"),
+            ("p", "Toggle progress gauge"),
+            ("s", "Pick session"),
+            ("l", "Raw log viewer"),
+            ("H", "Past sessions history"),
+            ("Y", "Copy status summary"),
+            ("F1-F12", "Run [actions] command"),
+            ("?", "Toggle help"),
+            ("q / Esc", "Quit"),
+        ],
+    ),
+    (
+        "Task list",
+        &[
+            ("j / Down", "Move down"),
+            ("k / Up", "Move up"),
+            ("Space", "Collapse/expand phase"),
+            ("v", "Switch view (Tree/Gantt)"),
+            ("w", "Wrap long task name"),
+            ("m", "Open phase minimap"),
+            ("r", "Retry failed task"),
+            ("r then a", "Track task's error as new task"),
+            ("r then f", "Run the error rule's fix command"),
+            ("R", "Reset failed task to pending"),
+            ("a", "Add task"),
+            ("e", "Rename task/phase (tree)"),
+            ("A", "Assign agent to task"),
+            ("J / K", "Move task down / up"),
+            ("M", "Move task to phase"),
+            ("n", "Add note to task"),
+            ("o", "Open task in $EDITOR"),
+            ("d", "Show diff of task's commits"),
+            ("P", "Page full task detail in $PAGER"),
+            ("X", "Archive completed phase"),
+            ("b", "Block task with reason"),
+            ("B", "Bulk status on phase"),
+            ("u", "Undo last edit"),
+            ("Ctrl+r", "Redo edit"),
+            ("t", "Remind me about task later"),
+        ],
+    ),
+    (
+        "Agents",
+        &[
+            ("j / k", "Select agent / tool call"),
+            ("Enter", "Agent drill-down"),
+            ("S", "Signal agent (pause/abort)"),
+            ("c", "Acknowledge agent errors"),
+            ("z", "Mute agent alerts for a while"),
+            ("t", "Remind me about agent's error"),
+            ("o", "Open tool call's file"),
+            ("P", "Page agent's event history"),
+        ],
+    ),
+    (
+        "Minimap",
+        &[
+            ("h / l", "Move between tasks"),
+            ("Enter", "Jump to task"),
+            ("Esc / m", "Close minimap"),
+        ],
+    ),
+    (
+        "History",
+        &[
+            ("j / k", "Select session / scroll"),
+            ("Enter", "Session timeline"),
+            ("Esc / H", "Back / close history"),
+        ],
+    ),
+    (
+        "Log viewer",
+        &[
+            ("j / k", "Move line"),
+            ("gg / G", "First / last line"),
+            (":<n>", "Go to line n"),
+            ("Esc / l", "Close viewer"),
+        ],
+    ),
+    (
+        "Help",
+        &[
+            ("type", "Filter keybindings"),
+            ("Up / Down", "Scroll"),
+            ("PgUp/PgDn", "Scroll a page"),
+            ("Backspace", "Delete filter char"),
+            ("Esc", "Clear filter / close"),
+        ],
+    ),
+];
+
+/// Rows moved by PgUp/PgDn
+const PAGE_SIZE: usize = 10;
+
+/// Filter text and scroll position of the help overlay
+#[derive(Debug, Default, Clone)]
+pub struct HelpState {
+    pub filter: String,
+    /// Index of the first visible keybinding row
+    pub offset: usize,
+}
+
+impl HelpState {
+    /// Handle a key; returns `false` when the overlay should close
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc if self.filter.is_empty() => return false,
+            KeyCode::Esc => {
+                self.filter.clear();
+                self.offset = 0;
+            }
+            KeyCode::Char('?') => return false,
+            KeyCode::Down => self.offset += 1,
+            KeyCode::Up => self.offset = self.offset.saturating_sub(1),
+            KeyCode::PageDown => self.offset += PAGE_SIZE,
+            KeyCode::PageUp => self.offset = self.offset.saturating_sub(PAGE_SIZE),
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.offset = 0;
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.offset = 0;
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
 /// Help overlay widget
 pub struct HelpOverlay;
 
@@ -17,14 +155,72 @@ impl HelpOverlay {
     /// Calculate a centered rect for the help popup
     fn centered_rect(area: Rect) -> Rect {
         let width = 40.min(area.width.saturating_sub(4));
-        let height = 16.min(area.height.saturating_sub(4));
+        let height = 22.min(area.height.saturating_sub(4));
         let x = (area.width.saturating_sub(width)) / 2;
         let y = (area.height.saturating_sub(height)) / 2;
         Rect::new(x, y, width, height)
     }
 
-    fn help_lines() -> Vec<Line<'static>> {
+    /// Keybinding rows matching `filter` (case-insensitive on key, description
+    /// or section name), with a header per non-empty section
+    fn help_lines(filter: &str) -> Vec<Line<'static>> {
+        let filter = filter.to_lowercase();
+        let mut lines = Vec::new();
+
+        for (section, bindings) in SECTIONS {
+            let section_matches = section.to_lowercase().contains(&filter);
+            let matching: Vec<_> = bindings
+                .iter()
+                .filter(|(key, desc)| {
+                    section_matches
+                        || key.to_lowercase().contains(&filter)
+                        || desc.to_lowercase().contains(&filter)
+                })
+                .collect();
+            if matching.is_empty() {
+                continue;
+            }
+
+            if !lines.is_empty() {
+                lines.push(Line::raw(""));
+            }
+            lines.push(Line::from(vec![Span::styled(
+                format!(" {section}"),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+            for (key, desc) in matching {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {key:<10}"), Style::default().fg(Color::Yellow)),
+                    Span::raw(*desc),
+                ]));
+            }
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::styled(
+                "  No matching keys",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines
+    }
+
+    fn header_lines(state: &HelpState) -> Vec<Line<'static>> {
         let version = env!("CARGO_PKG_VERSION");
+        let filter_line = if state.filter.is_empty() {
+            Line::styled(
+                " Type to filter, ↑/↓ scroll",
+                Style::default().fg(Color::DarkGray),
+            )
+        } else {
+            Line::from(vec![
+                Span::styled(" / ", Style::default().fg(Color::Cyan)),
+                Span::raw(state.filter.clone()),
+                Span::styled("\u{2588}", Style::default().fg(Color::DarkGray)),
+            ])
+        };
         vec![
             Line::from(vec![Span::styled(
                 format!(" simple-claude-board v{version} "),
@@ -32,52 +228,16 @@ impl HelpOverlay {
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             )]),
+            filter_line,
             Line::raw(""),
-            Line::from(vec![Span::styled(
-                " Keybindings ",
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            )]),
-            Line::raw(""),
-            Line::from(vec![
-                Span::styled("  j / Down  ", Style::default().fg(Color::Yellow)),
-                Span::raw("Move down"),
-            ]),
-            Line::from(vec![
-                Span::styled("  k / Up    ", Style::default().fg(Color::Yellow)),
-                Span::raw("Move up"),
-            ]),
-            Line::from(vec![
-                Span::styled("  Tab       ", Style::default().fg(Color::Yellow)),
-                Span::raw("Focus: Tasks→Detail→Agents"),
-            ]),
-            Line::from(vec![
-                Span::styled("  Space     ", Style::default().fg(Color::Yellow)),
-                Span::raw("Collapse/expand phase"),
-            ]),
-            Line::from(vec![
-                Span::styled("  v         ", Style::default().fg(Color::Yellow)),
-                Span::raw("Switch view (Tree/Gantt)"),
-            ]),
-            Line::from(vec![
-                Span::styled("  r         ", Style::default().fg(Color::Yellow)),
-                Span::raw("Retry failed task"),
-            ]),
-            Line::from(vec![
-                Span::styled("  ?         ", Style::default().fg(Color::Yellow)),
-                Span::raw("Close help"),
-            ]),
-            Line::from(vec![
-                Span::styled("  q / Esc   ", Style::default().fg(Color::Yellow)),
-                Span::raw("Quit"),
-            ]),
         ]
     }
 }
 
-impl Widget for HelpOverlay {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+impl StatefulWidget for HelpOverlay {
+    type State = HelpState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let popup_area = Self::centered_rect(area);
 
         // Clear the area behind the popup
@@ -87,22 +247,53 @@ impl Widget for HelpOverlay {
             .title(" Help ")
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
 
-        let lines = Self::help_lines();
-        let paragraph = Paragraph::new(lines).block(block);
-        paragraph.render(popup_area, buf);
+        let header = Self::header_lines(state);
+        let header_height = (header.len() as u16).min(inner.height);
+        Paragraph::new(header).render(Rect::new(inner.x, inner.y, inner.width, header_height), buf);
+
+        // Keep the scroll offset within the filtered list
+        let body = Self::help_lines(&state.filter);
+        let body_height = (inner.height - header_height) as usize;
+        state.offset = state.offset.min(body.len().saturating_sub(body_height));
+
+        let body_area = Rect::new(
+            inner.x,
+            inner.y + header_height,
+            inner.width,
+            body_height as u16,
+        );
+        let visible: Vec<Line> = body
+            .into_iter()
+            .skip(state.offset)
+            .take(body_height)
+            .collect();
+        Paragraph::new(visible).render(body_area, buf);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(c: KeyCode) -> KeyEvent {
+        KeyEvent::new(c, KeyModifiers::NONE)
+    }
+
+    fn rows(buf: &Buffer, area: Rect) -> Vec<String> {
+        (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect()
+    }
 
     #[test]
     fn help_overlay_renders() {
         let area = Rect::new(0, 0, 80, 30);
         let mut buf = Buffer::empty(area);
-        HelpOverlay.render(area, &mut buf);
+        HelpOverlay.render(area, &mut buf, &mut HelpState::default());
     }
 
     #[test]
@@ -112,19 +303,85 @@ mod tests {
         assert!(popup.x > 0);
         assert!(popup.y > 0);
         assert!(popup.width <= 40);
-        assert!(popup.height <= 16);
+        assert!(popup.height <= 22);
     }
 
     #[test]
     fn help_small_terminal() {
         let area = Rect::new(0, 0, 20, 8);
         let mut buf = Buffer::empty(area);
-        HelpOverlay.render(area, &mut buf);
+        HelpOverlay.render(area, &mut buf, &mut HelpState::default());
+        let mut state = HelpState {
+            offset: 99,
+            ..Default::default()
+        };
+        HelpOverlay.render(Rect::new(0, 0, 4, 4), &mut buf, &mut state);
     }
 
     #[test]
     fn help_lines_not_empty() {
-        let lines = HelpOverlay::help_lines();
+        let lines = HelpOverlay::help_lines("");
         assert!(lines.len() >= 5);
     }
+
+    #[test]
+    fn filter_narrows_to_matching_rows_under_their_section() {
+        let text: Vec<String> = HelpOverlay::help_lines("drill")
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text.len(), 2, "{text:?}");
+        assert_eq!(text[0].trim(), "Agents");
+        assert!(text[1].contains("Enter") && text[1].contains("Agent drill-down"));
+    }
+
+    #[test]
+    fn filter_on_section_name_keeps_whole_section() {
+        let text: Vec<String> = HelpOverlay::help_lines("log viewer")
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l.contains("First / last line")));
+        assert!(text.iter().any(|l| l.contains("Raw log viewer")));
+        assert!(!text.iter().any(|l| l.contains("Quit")));
+    }
+
+    #[test]
+    fn filter_without_match_says_so() {
+        let lines = HelpOverlay::help_lines("zzz");
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].spans[0].content.contains("No matching"));
+    }
+
+    #[test]
+    fn typing_edits_filter_and_esc_clears_then_closes() {
+        let mut state = HelpState::default();
+        for c in ['q', 'u'] {
+            assert!(state.handle_key(key(KeyCode::Char(c))));
+        }
+        assert_eq!(state.filter, "qu");
+        state.handle_key(key(KeyCode::Backspace));
+        assert_eq!(state.filter, "q");
+        assert!(state.handle_key(key(KeyCode::Esc)));
+        assert!(state.filter.is_empty());
+        assert!(!state.handle_key(key(KeyCode::Esc)));
+        assert!(!state.handle_key(key(KeyCode::Char('?'))));
+    }
+
+    #[test]
+    fn scroll_is_clamped_when_rendering() {
+        let mut state = HelpState::default();
+        for _ in 0..100 {
+            state.handle_key(key(KeyCode::PageDown));
+        }
+        let area = Rect::new(0, 0, 80, 30);
+        let mut buf = Buffer::empty(area);
+        HelpOverlay.render(area, &mut buf, &mut state);
+        let total = HelpOverlay::help_lines("").len();
+        // 22-row popup: 2 border rows, 3 header rows
+        assert_eq!(state.offset, total - 17);
+        let rendered = rows(&buf, area);
+        assert!(rendered.iter().any(|r| r.contains("Clear filter / close")));
+        assert!(!rendered.iter().any(|r| r.contains("General")));
+    }
 }
@@ -0,0 +1,329 @@
+//! Session history view
+//!
+//! Full-screen overlay listing past hook sessions, newest first, with their
+//! duration, agents, tasks finished, errors and cost. `Enter` drills into a
+//! session's timeline of agent starts, ends and errors.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::data::hook_parser::EventType;
+use crate::data::state::SessionSummary;
+use crate::ui::theme::Theme;
+
+/// Sessions shown and what is selected
+#[derive(Debug, Default, Clone)]
+pub struct HistoryState {
+    pub sessions: Vec<SessionSummary>,
+    pub selected: usize,
+    /// Showing the selected session's timeline instead of the list
+    pub timeline: bool,
+    /// First visible row of the list or timeline
+    pub offset: usize,
+}
+
+impl HistoryState {
+    pub fn new(sessions: Vec<SessionSummary>) -> Self {
+        Self {
+            sessions,
+            ..Default::default()
+        }
+    }
+
+    pub fn selected_session(&self) -> Option<&SessionSummary> {
+        self.sessions.get(self.selected)
+    }
+
+    /// Handle a key; returns `false` when the view should close
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.timeline {
+            let rows = self.selected_session().map_or(0, |s| s.timeline.len());
+            match key.code {
+                KeyCode::Char('q' | 'ㅂ') => return false,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace => {
+                    self.timeline = false;
+                    self.offset = 0;
+                }
+                KeyCode::Char('j' | 'ㅓ') | KeyCode::Down => {
+                    self.offset = (self.offset + 1).min(rows.saturating_sub(1));
+                }
+                KeyCode::Char('k' | 'ㅏ') | KeyCode::Up => {
+                    self.offset = self.offset.saturating_sub(1);
+                }
+                _ => {}
+            }
+            return true;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'H' | 'ㅂ') => return false,
+            KeyCode::Char('j' | 'ㅓ') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.sessions.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'ㅏ') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Enter if !self.sessions.is_empty() => {
+                self.timeline = true;
+                self.offset = 0;
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+/// `1h05m`, `12m`, or `40s`
+fn format_duration(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// Session history overlay widget
+pub struct HistoryView {
+    theme: Theme,
+}
+
+impl HistoryView {
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn list_lines(&self, state: &HistoryState) -> Vec<Line<'static>> {
+        let header = format!(
+            "   {:<16}  {:>8}  {:>6}  {:>5}  {:>6}  {:>8}  Session",
+            "Started", "Duration", "Agents", "Done", "Errors", "Cost"
+        );
+        let mut lines = vec![Line::styled(
+            header,
+            Style::default()
+                .fg(self.theme.muted)
+                .add_modifier(Modifier::BOLD),
+        )];
+        for (i, session) in state.sessions.iter().enumerate() {
+            let selected = i == state.selected;
+            let row = format!(
+                "{}{:<16}  {:>8}  {:>6}  {:>5}  {:>6}  {:>8}  {}",
+                if selected { " > " } else { "   " },
+                session.started_at.format("%Y-%m-%d %H:%M"),
+                format_duration(session.last_seen - session.started_at),
+                session.agent_count(),
+                session.tasks_finished.len(),
+                session.error_count,
+                format!("${:.2}", session.cost_usd),
+                session.session_id
+            );
+            let style = if selected {
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else if session.error_count > 0 {
+                Style::default().fg(self.theme.failed)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            lines.push(Line::styled(row, style));
+        }
+        lines
+    }
+
+    fn timeline_lines(&self, session: &SessionSummary) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::styled(
+            format!(
+                "{} \u{2192} {}  {} agents  {} tasks finished  {} errors  {} events  ${:.2}",
+                session.started_at.format("%Y-%m-%d %H:%M:%S"),
+                session.last_seen.format("%H:%M:%S"),
+                session.agent_count(),
+                session.tasks_finished.len(),
+                session.error_count,
+                session.event_count,
+                session.cost_usd
+            ),
+            Style::default().fg(self.theme.muted),
+        )];
+        if !session.tasks_finished.is_empty() {
+            let tasks: Vec<&str> = session.tasks_finished.iter().map(String::as_str).collect();
+            lines.push(Line::styled(
+                format!("Finished: {}", tasks.join(", ")),
+                Style::default().fg(self.theme.completed),
+            ));
+        }
+        lines.push(Line::raw(""));
+        for entry in &session.timeline {
+            let color = match entry.event_type {
+                EventType::Error => self.theme.failed,
+                EventType::AgentEnd => self.theme.completed,
+                _ => self.theme.in_progress,
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!("{}  ", entry.at.format("%H:%M:%S")),
+                    Style::default().fg(self.theme.muted),
+                ),
+                Span::styled(
+                    format!("{:<11}", entry.event_type.as_str()),
+                    Style::default().fg(color),
+                ),
+                Span::styled(
+                    format!(" {}  {}", entry.agent_id, entry.task_id),
+                    Style::default().fg(self.theme.text),
+                ),
+            ];
+            if let Some(ref detail) = entry.detail {
+                let first = detail.lines().next().unwrap_or("");
+                spans.push(Span::styled(
+                    format!("  {first}"),
+                    Style::default().fg(self.theme.failed),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+}
+
+impl Default for HistoryView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatefulWidget for HistoryView {
+    type State = HistoryState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        Clear.render(area, buf);
+
+        let (title, footer, mut lines) = match state.selected_session().filter(|_| state.timeline) {
+            Some(session) => (
+                format!(" Session {} ", session.session_id),
+                " j/k scroll  Esc back  q close",
+                self.timeline_lines(session),
+            ),
+            None => (
+                format!(" History ({} sessions) ", state.sessions.len()),
+                " j/k move  Enter timeline  Esc close",
+                self.list_lines(state),
+            ),
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        // Last row is the footer; the list keeps the selection visible and
+        // the timeline scrolls below its summary
+        let body_height = (inner.height - 1) as usize;
+        let skip = if state.timeline {
+            state.offset
+        } else {
+            // Row 0 is the column header
+            let row = state.selected + 1;
+            if row < state.offset + 1 {
+                state.offset = row.saturating_sub(1);
+            } else if row >= state.offset + body_height {
+                state.offset = row + 1 - body_height;
+            }
+            state.offset
+        };
+        let header = if state.timeline {
+            Vec::new()
+        } else {
+            vec![lines.remove(0)]
+        };
+        let visible: Vec<Line> = header
+            .into_iter()
+            .chain(lines.into_iter().skip(skip))
+            .take(body_height)
+            .collect();
+        Paragraph::new(visible).render(
+            Rect::new(inner.x, inner.y, inner.width, body_height as u16),
+            buf,
+        );
+
+        let footer_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+        Widget::render(
+            Line::styled(footer, Style::default().fg(self.theme.muted)),
+            footer_area,
+            buf,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::hook_parser::parse_hook_events;
+    use crate::data::state::DashboardState;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn render(state: &mut HistoryState) -> String {
+        let area = Rect::new(0, 0, 100, 12);
+        let mut buf = Buffer::empty(area);
+        HistoryView::new().render(area, &mut buf, state);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn lists_sessions_and_drills_into_a_timeline() {
+        let input = [
+            r#"{"event_type":"agent_start","timestamp":"2026-02-08T10:00:00Z","agent_id":"backend","task_id":"P1-T1","session_id":"s1"}"#,
+            r#"{"event_type":"tool_start","timestamp":"2026-02-08T10:01:00Z","agent_id":"backend","task_id":"P1-T1","session_id":"s1","tool_name":"Bash"}"#,
+            r#"{"event_type":"error","timestamp":"2026-02-08T10:02:00Z","agent_id":"backend","task_id":"P1-T1","session_id":"s1","error_message":"permission denied"}"#,
+            r#"{"event_type":"agent_end","timestamp":"2026-02-08T11:05:00Z","agent_id":"backend","task_id":"P1-T1","session_id":"s1"}"#,
+        ]
+        .join("\n");
+        let mut dashboard = DashboardState::default();
+        dashboard.update_from_events(&parse_hook_events(&input).events);
+        let mut state = HistoryState::new(dashboard.sessions.into_values().collect());
+
+        let list = render(&mut state);
+        assert!(list.contains("History (1 sessions)"), "got:\n{list}");
+        assert!(list.contains("2026-02-08 10:00     1h05m"), "got:\n{list}");
+
+        assert!(state.handle_key(key(KeyCode::Enter)));
+        let timeline = render(&mut state);
+        assert!(timeline.contains("Finished: P1-T1"), "got:\n{timeline}");
+        assert!(timeline.contains("error       backend  P1-T1  permission denied"));
+        // Tool calls are not part of the timeline
+        assert!(!timeline.contains("tool_start"));
+
+        assert!(state.handle_key(key(KeyCode::Esc)));
+        assert!(!state.timeline);
+        assert!(!state.handle_key(key(KeyCode::Esc)));
+    }
+}
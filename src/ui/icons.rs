@@ -0,0 +1,175 @@
+//! Icon sets for statuses, agents and tools
+//!
+//! `ascii` keeps the original bracket markers; `unicode` uses common symbols;
+//! `nerd` uses Nerd Font (Font Awesome) glyphs and needs a patched font.
+
+use std::str::FromStr;
+
+use crate::data::state::AgentStatus;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Glyph set selected by the `icons` config option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IconSet {
+    Nerd,
+    Unicode,
+    #[default]
+    Ascii,
+}
+
+impl FromStr for IconSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "nerd" => Ok(IconSet::Nerd),
+            "unicode" => Ok(IconSet::Unicode),
+            "ascii" => Ok(IconSet::Ascii),
+            other => Err(format!(
+                "unknown icon set `{other}` (expected nerd, unicode or ascii)"
+            )),
+        }
+    }
+}
+
+impl IconSet {
+    /// Marker for a task status
+    pub fn task_status(self, status: &TaskStatus) -> &'static str {
+        match (self, status) {
+            (IconSet::Ascii, TaskStatus::Completed) => "[x]",
+            (IconSet::Ascii, TaskStatus::InProgress) => "[/]",
+            (IconSet::Ascii, TaskStatus::Pending) => "[ ]",
+            (IconSet::Ascii, TaskStatus::Failed) => "[!]",
+            (IconSet::Ascii, TaskStatus::Blocked) => "[B]",
+            (IconSet::Unicode, TaskStatus::Completed) => "\u{2714}",
+            (IconSet::Unicode, TaskStatus::InProgress) => "\u{25D0}",
+            (IconSet::Unicode, TaskStatus::Pending) => "\u{25CB}",
+            (IconSet::Unicode, TaskStatus::Failed) => "\u{2718}",
+            (IconSet::Unicode, TaskStatus::Blocked) => "\u{2298}",
+            (IconSet::Nerd, TaskStatus::Completed) => "\u{f00c}",
+            (IconSet::Nerd, TaskStatus::InProgress) => "\u{f110}",
+            (IconSet::Nerd, TaskStatus::Pending) => "\u{f10c}",
+            (IconSet::Nerd, TaskStatus::Failed) => "\u{f00d}",
+            (IconSet::Nerd, TaskStatus::Blocked) => "\u{f05e}",
+        }
+    }
+
+    /// Marker for an agent status
+    pub fn agent_status(self, status: &AgentStatus) -> &'static str {
+        match (self, status) {
+            (IconSet::Ascii, AgentStatus::Running) => ">>",
+            (IconSet::Ascii, AgentStatus::Error) => "!!",
+            (IconSet::Ascii, AgentStatus::Idle) => "--",
+            (IconSet::Unicode, AgentStatus::Running) => "\u{25B6}",
+            (IconSet::Unicode, AgentStatus::Error) => "\u{2716}",
+            (IconSet::Unicode, AgentStatus::Idle) => "\u{25CB}",
+            (IconSet::Nerd, AgentStatus::Running) => "\u{f04b}",
+            (IconSet::Nerd, AgentStatus::Error) => "\u{f071}",
+            (IconSet::Nerd, AgentStatus::Idle) => "\u{f04c}",
+        }
+    }
+
+    /// Marker before an agent's git branch
+    pub fn branch(self) -> &'static str {
+        match self {
+            IconSet::Ascii => "on",
+            IconSet::Unicode => "\u{2387}",
+            IconSet::Nerd => "\u{e725}",
+        }
+    }
+
+    /// Glyph shown before a tool name; `None` for the ASCII set
+    pub fn tool(self, tool_name: &str) -> Option<&'static str> {
+        let kind = ToolKind::from_name(tool_name);
+        match self {
+            IconSet::Ascii => None,
+            IconSet::Unicode => Some(match kind {
+                ToolKind::Edit => "\u{270E}",
+                ToolKind::Shell => "\u{276F}",
+                ToolKind::Read => "\u{2261}",
+                ToolKind::Search => "\u{2315}",
+                ToolKind::Agent => "\u{2691}",
+                ToolKind::Web => "\u{25CE}",
+                ToolKind::Other => "\u{2022}",
+            }),
+            IconSet::Nerd => Some(match kind {
+                ToolKind::Edit => "\u{f044}",
+                ToolKind::Shell => "\u{f120}",
+                ToolKind::Read => "\u{f15c}",
+                ToolKind::Search => "\u{f002}",
+                ToolKind::Agent => "\u{f0c0}",
+                ToolKind::Web => "\u{f0ac}",
+                ToolKind::Other => "\u{f0ad}",
+            }),
+        }
+    }
+}
+
+/// Broad tool families that share a glyph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ToolKind {
+    Edit,
+    Shell,
+    Read,
+    Search,
+    Agent,
+    Web,
+    Other,
+}
+
+impl ToolKind {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "Edit" | "MultiEdit" | "Write" | "NotebookEdit" => ToolKind::Edit,
+            "Bash" | "BashOutput" | "KillShell" => ToolKind::Shell,
+            "Read" | "NotebookRead" => ToolKind::Read,
+            "Grep" | "Glob" | "LS" => ToolKind::Search,
+            "Task" => ToolKind::Agent,
+            "WebFetch" | "WebSearch" => ToolKind::Web,
+            _ => ToolKind::Other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_icon_set() {
+        assert_eq!("nerd".parse::<IconSet>().unwrap(), IconSet::Nerd);
+        assert_eq!("Unicode".parse::<IconSet>().unwrap(), IconSet::Unicode);
+        assert_eq!("ascii".parse::<IconSet>().unwrap(), IconSet::Ascii);
+        assert!("emoji".parse::<IconSet>().is_err());
+    }
+
+    #[test]
+    fn ascii_task_markers_unchanged() {
+        let icons = IconSet::Ascii;
+        assert_eq!(icons.task_status(&TaskStatus::Completed), "[x]");
+        assert_eq!(icons.task_status(&TaskStatus::InProgress), "[/]");
+        assert_eq!(icons.task_status(&TaskStatus::Pending), "[ ]");
+        assert_eq!(icons.task_status(&TaskStatus::Failed), "[!]");
+        assert_eq!(icons.task_status(&TaskStatus::Blocked), "[B]");
+    }
+
+    #[test]
+    fn ascii_has_no_tool_glyphs() {
+        assert_eq!(IconSet::Ascii.tool("Edit"), None);
+        assert_eq!(IconSet::Ascii.branch(), "on");
+    }
+
+    #[test]
+    fn tool_glyphs_group_by_kind() {
+        assert_eq!(IconSet::Nerd.tool("Edit"), IconSet::Nerd.tool("Write"));
+        assert_eq!(IconSet::Nerd.tool("Bash"), Some("\u{f120}"));
+        assert_eq!(IconSet::Unicode.tool("Grep"), IconSet::Unicode.tool("Glob"));
+        assert_eq!(IconSet::Unicode.tool("SomethingNew"), Some("\u{2022}"));
+    }
+
+    #[test]
+    fn agent_markers_per_set() {
+        assert_eq!(IconSet::Ascii.agent_status(&AgentStatus::Running), ">>");
+        assert_eq!(IconSet::Nerd.agent_status(&AgentStatus::Error), "\u{f071}");
+    }
+}
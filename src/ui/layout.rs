@@ -1,24 +1,74 @@
 //! Screen split layout
 //!
-//! Defines the main dashboard layout: task list (left), detail panel (right),
-//! and status bar (bottom).
+//! Defines the dashboard arrangements: the default split (task list left,
+//! detail + agents right) and a stacked one for wide-but-short terminals.
+
+use std::str::FromStr;
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::{Deserialize, Serialize};
+
+/// Named pane arrangement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutMode {
+    /// Task list left, detail and agents stacked on the right
+    #[default]
+    Split,
+    /// Task list on top, agents full-width below, detail as an overlay
+    Stacked,
+}
+
+impl LayoutMode {
+    /// Cycle to the next arrangement
+    pub fn next(self) -> Self {
+        match self {
+            Self::Split => Self::Stacked,
+            Self::Stacked => Self::Split,
+        }
+    }
+}
+
+impl FromStr for LayoutMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "split" => Ok(Self::Split),
+            "stacked" => Ok(Self::Stacked),
+            other => Err(format!(
+                "unknown layout `{other}` (expected split or stacked)"
+            )),
+        }
+    }
+}
 
 /// The pane that currently has focus
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FocusedPane {
+    #[default]
     TaskList,
     Detail,
     Agents,
+    /// A registered custom panel, by registration order
+    Custom(usize),
 }
 
 impl FocusedPane {
     pub fn toggle(self) -> Self {
+        self.next(0)
+    }
+
+    /// Next pane in Tab order when `panels` custom panels follow the
+    /// built-in ones
+    pub fn next(self, panels: usize) -> Self {
         match self {
             Self::TaskList => Self::Detail,
             Self::Detail => Self::Agents,
-            Self::Agents => Self::TaskList,
+            Self::Agents if panels > 0 => Self::Custom(0),
+            Self::Custom(i) if i + 1 < panels => Self::Custom(i + 1),
+            Self::Agents | Self::Custom(_) => Self::TaskList,
         }
     }
 }
@@ -28,7 +78,13 @@ pub struct DashboardLayout {
     pub task_list: Rect,
     pub detail: Rect,
     pub agents: Rect,
+    /// Areas of the custom panels, in registration order
+    pub panels: Vec<Rect>,
     pub status_bar: Rect,
+    /// Overall progress gauge row just above the status bar, when enabled
+    pub gauge: Option<Rect>,
+    /// The detail area floats over the other panes and is only drawn on demand
+    pub detail_overlay: bool,
 }
 
 impl DashboardLayout {
@@ -45,6 +101,50 @@ impl DashboardLayout {
     /// +------------------------------------+
     /// ```
     pub fn compute(area: Rect) -> Self {
+        Self::compute_with(area, LayoutMode::Split)
+    }
+
+    /// Compute layout for a named arrangement
+    pub fn compute_with(area: Rect, mode: LayoutMode) -> Self {
+        match mode {
+            LayoutMode::Split => Self::split(area),
+            LayoutMode::Stacked => Self::stacked(area),
+        }
+    }
+
+    /// Compute layout, optionally reserving a full-width gauge row between
+    /// the panes and the status bar
+    pub fn compute_with_gauge(area: Rect, mode: LayoutMode, gauge: bool) -> Self {
+        if !gauge || area.height < 3 {
+            return Self::compute_with(area, mode);
+        }
+        let body = Rect {
+            height: area.height - 1,
+            ..area
+        };
+        let mut layout = Self::compute_with(body, mode);
+        // The body's status row becomes the gauge; the status bar moves below it
+        layout.gauge = Some(layout.status_bar);
+        layout.status_bar.y += 1;
+        layout
+    }
+
+    /// Give `count` custom panels equal columns of the agents row, to the
+    /// right of the agent panel
+    pub fn with_panels(mut self, count: usize) -> Self {
+        if count == 0 {
+            return self;
+        }
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, count as u32 + 1); count + 1])
+            .split(self.agents);
+        self.agents = columns[0];
+        self.panels = columns[1..].to_vec();
+        self
+    }
+
+    fn split(area: Rect) -> Self {
         let vertical = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Min(3), Constraint::Length(1)])
@@ -65,7 +165,53 @@ impl DashboardLayout {
             task_list: horizontal[0],
             detail: right_split[0],
             agents: right_split[1],
+            panels: Vec::new(),
             status_bar: vertical[1],
+            gauge: None,
+            detail_overlay: false,
+        }
+    }
+
+    /// ```text
+    /// +------------------------------------+
+    /// |             Task List              |
+    /// |        +---- Detail ----+          |
+    /// +--------|   (overlay)    |----------+
+    /// |        +----------------+          |
+    /// |               Agents               |
+    /// +------------------------------------+
+    /// |            Status Bar              |
+    /// +------------------------------------+
+    /// ```
+    fn stacked(area: Rect) -> Self {
+        let vertical = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(65),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(area);
+
+        // Detail floats centered over the upper two panes
+        let body = vertical[0].union(vertical[1]);
+        let width = (body.width * 7 / 10).max(body.width.min(30));
+        let height = (body.height * 7 / 10).max(body.height.min(8));
+        let detail = Rect::new(
+            body.x + (body.width - width) / 2,
+            body.y + (body.height - height) / 2,
+            width,
+            height,
+        );
+
+        Self {
+            task_list: vertical[0],
+            detail,
+            agents: vertical[1],
+            panels: Vec::new(),
+            status_bar: vertical[2],
+            gauge: None,
+            detail_overlay: true,
         }
     }
 }
@@ -81,6 +227,31 @@ mod tests {
         assert_eq!(FocusedPane::Agents.toggle(), FocusedPane::TaskList);
     }
 
+    #[test]
+    fn focus_cycles_through_custom_panels() {
+        assert_eq!(FocusedPane::Agents.next(2), FocusedPane::Custom(0));
+        assert_eq!(FocusedPane::Custom(0).next(2), FocusedPane::Custom(1));
+        assert_eq!(FocusedPane::Custom(1).next(2), FocusedPane::TaskList);
+        // A panel that is no longer registered falls back to the task list
+        assert_eq!(FocusedPane::Custom(3).next(0), FocusedPane::TaskList);
+    }
+
+    #[test]
+    fn custom_panels_share_the_agents_row() {
+        let area = Rect::new(0, 0, 120, 40);
+        let plain = DashboardLayout::compute(area);
+        let layout = DashboardLayout::compute(area).with_panels(2);
+        assert_eq!(layout.panels.len(), 2);
+        assert_eq!(layout.agents.x, plain.agents.x);
+        assert_eq!(layout.agents.y, plain.agents.y);
+        assert_eq!(layout.panels[0].x, layout.agents.right());
+        assert_eq!(layout.panels[1].right(), plain.agents.right());
+        assert!(DashboardLayout::compute(area)
+            .with_panels(0)
+            .panels
+            .is_empty());
+    }
+
     #[test]
     fn layout_standard_size() {
         let area = Rect::new(0, 0, 120, 40);
@@ -101,6 +272,66 @@ mod tests {
         assert_eq!(layout.status_bar.height, 1);
     }
 
+    #[test]
+    fn layout_mode_cycles_and_parses() {
+        assert_eq!(LayoutMode::Split.next(), LayoutMode::Stacked);
+        assert_eq!(LayoutMode::Stacked.next(), LayoutMode::Split);
+        assert_eq!(
+            "stacked".parse::<LayoutMode>().unwrap(),
+            LayoutMode::Stacked
+        );
+        assert!("grid".parse::<LayoutMode>().is_err());
+    }
+
+    #[test]
+    fn stacked_layout_full_width_panes() {
+        let area = Rect::new(0, 0, 200, 30);
+        let layout = DashboardLayout::compute_with(area, LayoutMode::Stacked);
+        assert_eq!(layout.task_list.width, 200);
+        assert_eq!(layout.agents.width, 200);
+        assert!(layout.agents.y > layout.task_list.y);
+        assert_eq!(layout.status_bar.y, area.height - 1);
+        assert!(layout.detail_overlay);
+        assert!(area.contains(layout.detail.as_position()));
+        assert!(layout.detail.bottom() <= layout.status_bar.y);
+    }
+
+    #[test]
+    fn stacked_layout_tiny_terminal() {
+        let area = Rect::new(0, 0, 20, 6);
+        let layout = DashboardLayout::compute_with(area, LayoutMode::Stacked);
+        assert!(layout.detail.right() <= area.right());
+        assert!(layout.detail.bottom() <= layout.status_bar.y);
+    }
+
+    #[test]
+    fn split_layout_is_default() {
+        let area = Rect::new(0, 0, 120, 40);
+        let layout = DashboardLayout::compute(area);
+        assert!(!layout.detail_overlay);
+        assert_eq!(
+            layout.task_list,
+            DashboardLayout::compute_with(area, LayoutMode::Split).task_list
+        );
+    }
+
+    #[test]
+    fn gauge_row_sits_above_status_bar() {
+        let area = Rect::new(0, 0, 100, 30);
+        for mode in [LayoutMode::Split, LayoutMode::Stacked] {
+            let layout = DashboardLayout::compute_with_gauge(area, mode, true);
+            let gauge = layout.gauge.expect("gauge row");
+            assert_eq!(gauge.width, area.width);
+            assert_eq!(gauge.height, 1);
+            assert_eq!(gauge.y, area.height - 2);
+            assert_eq!(layout.status_bar.y, area.height - 1);
+            assert!(layout.task_list.bottom() <= gauge.y);
+            assert!(layout.agents.bottom() <= gauge.y);
+        }
+        let off = DashboardLayout::compute_with_gauge(area, LayoutMode::Split, false);
+        assert!(off.gauge.is_none());
+    }
+
     #[test]
     fn layout_statusbar_at_bottom() {
         let area = Rect::new(0, 0, 80, 30);
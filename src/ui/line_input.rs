@@ -0,0 +1,193 @@
+//! Single-line text input
+//!
+//! Text and cursor behind the inline rename, the blocked reason prompt and
+//! the add-task form. The cursor counts chars, so editing never splits a
+//! multibyte character, and the drawn text scrolls to keep it in view.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+use unicode_width::UnicodeWidthChar;
+
+/// Text being typed and the cursor in it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineInput {
+    text: String,
+    /// Cursor position in chars
+    cursor: usize,
+    /// Longest text accepted, in chars
+    max_chars: Option<usize>,
+}
+
+impl LineInput {
+    /// Start from `text`, cursor at the end
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            cursor: text.chars().count(),
+            max_chars: None,
+        }
+    }
+
+    /// Refuse typing past `max` chars
+    pub fn with_max_chars(mut self, max: usize) -> Self {
+        self.max_chars = Some(max);
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Cursor position in chars
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Replace the text, cursor at the end
+    pub fn set_text(&mut self, text: &str) {
+        *self = Self {
+            max_chars: self.max_chars,
+            ..Self::new(text)
+        };
+    }
+
+    fn byte_at(&self, char_idx: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_idx)
+            .map_or(self.text.len(), |(i, _)| i)
+    }
+
+    /// Move the cursor or edit the text; `false` for keys that do neither
+    /// (Enter, Esc, Tab, ...), left to the caller
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let len = self.text.chars().count();
+        match key.code {
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(len),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = len,
+            KeyCode::Backspace => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                    self.text.remove(self.byte_at(self.cursor));
+                }
+            }
+            KeyCode::Delete => {
+                if self.cursor < len {
+                    self.text.remove(self.byte_at(self.cursor));
+                }
+            }
+            KeyCode::Char(c) => {
+                if self.max_chars.map_or(true, |max| len < max) {
+                    self.text.insert(self.byte_at(self.cursor), c);
+                    self.cursor += 1;
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// The text as spans fitting `width` columns, scrolled horizontally so
+    /// the cursor (drawn reversed) stays visible
+    pub fn spans(&self, width: usize, style: Style) -> Vec<Span<'static>> {
+        let mut chars: Vec<char> = self.text.chars().collect();
+        chars.push(' '); // cursor cell past the end
+        let char_width = |c: &char| c.width().unwrap_or(0);
+
+        // Scroll right until the cursor fits
+        let mut start = 0;
+        while start < self.cursor
+            && chars[start..=self.cursor]
+                .iter()
+                .map(char_width)
+                .sum::<usize>()
+                > width
+        {
+            start += 1;
+        }
+        let mut end = start;
+        let mut used = 0;
+        while end < chars.len() && used + char_width(&chars[end]) <= width {
+            used += char_width(&chars[end]);
+            end += 1;
+        }
+        if end <= self.cursor {
+            return Vec::new();
+        }
+
+        let text = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+        vec![
+            Span::styled(text(start..self.cursor), style),
+            Span::styled(
+                text(self.cursor..self.cursor + 1),
+                style.add_modifier(Modifier::REVERSED),
+            ),
+            Span::styled(text(self.cursor + 1..end), style),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn rendered(spans: &[Span]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn edits_at_the_cursor_by_chars() {
+        let mut input = LineInput::new("Parsr");
+        input.handle_key(key(KeyCode::Left));
+        input.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(input.text(), "Parser");
+        input.handle_key(key(KeyCode::Home));
+        input.handle_key(key(KeyCode::Delete));
+        input.handle_key(key(KeyCode::Char('파')));
+        assert_eq!(input.text(), "파arser");
+        input.handle_key(key(KeyCode::Backspace));
+        input.handle_key(key(KeyCode::Backspace));
+        assert_eq!((input.text(), input.cursor()), ("arser", 0));
+        assert!(!input.handle_key(key(KeyCode::Enter)));
+        assert!(!input.handle_key(key(KeyCode::Tab)));
+    }
+
+    #[test]
+    fn typing_stops_at_the_limit() {
+        let mut input = LineInput::new("ab").with_max_chars(3);
+        input.handle_key(key(KeyCode::Char('c')));
+        input.handle_key(key(KeyCode::Char('d')));
+        assert_eq!(input.text(), "abc");
+        input.set_text("x");
+        input.handle_key(key(KeyCode::Char('y')));
+        input.handle_key(key(KeyCode::Char('z')));
+        input.handle_key(key(KeyCode::Char('w')));
+        assert_eq!(input.text(), "xyz");
+    }
+
+    #[test]
+    fn wide_text_scrolls_to_the_cursor() {
+        let mut input = LineInput::new("태스크 파서");
+        input.handle_key(key(KeyCode::Backspace));
+        assert_eq!(input.text(), "태스크 파");
+
+        let spans = input.spans(6, Style::default());
+        let text = rendered(&spans);
+        assert!(text.ends_with("파 "), "got: {text}");
+        assert_eq!(spans[1].content, " ");
+
+        input.handle_key(key(KeyCode::Home));
+        assert_eq!(rendered(&input.spans(6, Style::default())), "태스크");
+        assert!(input.spans(0, Style::default()).is_empty());
+    }
+}
@@ -0,0 +1,324 @@
+//! Raw log viewer
+//!
+//! Full-screen overlay showing a hook JSONL file with line numbers that match
+//! the source file, so parse errors reported as "line N" can be jumped to.
+//! Vim-style navigation: `j`/`k`, `gg`/`G`, `:<n>` goto.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, StatefulWidget, Widget},
+};
+
+use crate::data::hook_parser;
+use crate::ui::theme::Theme;
+
+/// Scroll/cursor state of the log viewer
+#[derive(Debug, Default, Clone)]
+pub struct LogViewerState {
+    pub path: PathBuf,
+    pub lines: Vec<String>,
+    /// 1-based line numbers that failed to parse as hook events
+    pub error_lines: HashSet<usize>,
+    /// 0-based index of the highlighted line
+    pub cursor: usize,
+    /// 0-based index of the first visible line
+    pub offset: usize,
+    /// Digits typed after `:`; `Some` while the goto prompt is open
+    pub goto_input: Option<String>,
+    /// First `g` of a `gg` sequence was pressed
+    pending_g: bool,
+}
+
+impl LogViewerState {
+    /// Build a viewer over in-memory JSONL content
+    pub fn from_content(path: PathBuf, content: &str) -> Self {
+        let error_lines = hook_parser::parse_hook_events(content)
+            .errors
+            .iter()
+            .map(|e| e.line_number)
+            .collect();
+        Self {
+            path,
+            lines: content.lines().map(str::to_string).collect(),
+            error_lines,
+            ..Default::default()
+        }
+    }
+
+    /// Read a JSONL file from disk
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        Ok(Self::from_content(path.to_path_buf(), &content))
+    }
+
+    /// Move the cursor to a 1-based line number (clamped to the file)
+    pub fn goto_line(&mut self, line_number: usize) {
+        let last = self.lines.len().saturating_sub(1);
+        self.cursor = line_number.saturating_sub(1).min(last);
+    }
+
+    pub fn move_down(&mut self) {
+        self.goto_line(self.cursor + 2);
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    /// Handle a key; returns `false` when the viewer should close
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if let Some(ref mut input) = self.goto_input {
+            match key.code {
+                KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+                KeyCode::Backspace if input.is_empty() => self.goto_input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Enter => {
+                    if let Ok(n) = input.parse::<usize>() {
+                        self.goto_line(n);
+                    }
+                    self.goto_input = None;
+                }
+                KeyCode::Esc => self.goto_input = None,
+                _ => {}
+            }
+            return true;
+        }
+
+        let pending_g = std::mem::take(&mut self.pending_g);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'l' | 'ㅂ' | 'ㅣ') => return false,
+            KeyCode::Char('j' | 'ㅓ') | KeyCode::Down => self.move_down(),
+            KeyCode::Char('k' | 'ㅏ') | KeyCode::Up => self.move_up(),
+            KeyCode::Char('G') | KeyCode::End => self.goto_line(self.lines.len()),
+            KeyCode::Home => self.goto_line(1),
+            KeyCode::Char('g' | 'ㅎ') => {
+                if pending_g {
+                    self.goto_line(1);
+                } else {
+                    self.pending_g = true;
+                }
+            }
+            KeyCode::Char(':') => self.goto_input = Some(String::new()),
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Log viewer overlay widget
+pub struct LogViewer {
+    theme: Theme,
+}
+
+impl LogViewer {
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn footer(&self, state: &LogViewerState) -> Line<'static> {
+        match state.goto_input {
+            Some(ref input) => Line::from(vec![
+                Span::styled(":", Style::default().fg(self.theme.accent)),
+                Span::raw(input.clone()),
+                Span::styled("\u{2588}", Style::default().fg(self.theme.muted)),
+            ]),
+            None => Line::styled(
+                format!(
+                    " line {}/{}  {} parse errors   j/k  gg/G  :<n> goto  Esc close",
+                    state.cursor + 1,
+                    state.lines.len(),
+                    state.error_lines.len()
+                ),
+                Style::default().fg(self.theme.muted),
+            ),
+        }
+    }
+}
+
+impl Default for LogViewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatefulWidget for LogViewer {
+    type State = LogViewerState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        Clear.render(area, buf);
+
+        let title = format!(" Log: {} ", state.path.display());
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        // Last row is the footer / goto prompt
+        let body_height = (inner.height - 1) as usize;
+        if state.cursor < state.offset {
+            state.offset = state.cursor;
+        } else if state.cursor >= state.offset + body_height {
+            state.offset = state.cursor + 1 - body_height;
+        }
+
+        let gutter = state.lines.len().max(1).to_string().len();
+        for (row, (idx, text)) in state
+            .lines
+            .iter()
+            .enumerate()
+            .skip(state.offset)
+            .take(body_height)
+            .enumerate()
+        {
+            let line_number = idx + 1;
+            let is_error = state.error_lines.contains(&line_number);
+            let number_style = if is_error {
+                Style::default().fg(self.theme.failed)
+            } else {
+                Style::default().fg(self.theme.muted)
+            };
+            let text_style = if is_error {
+                Style::default().fg(self.theme.failed)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            let line = Line::from(vec![
+                Span::styled(format!("{line_number:>gutter$} "), number_style),
+                Span::styled(text.clone(), text_style),
+            ]);
+
+            let y = inner.y + row as u16;
+            let line_area = Rect::new(inner.x, y, inner.width, 1);
+            if idx == state.cursor {
+                buf.set_style(
+                    line_area,
+                    Style::default()
+                        .bg(self.theme.muted)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
+            Widget::render(line, line_area, buf);
+        }
+
+        let footer_area = Rect::new(inner.x, inner.y + inner.height - 1, inner.width, 1);
+        Widget::render(self.footer(state), footer_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(c: KeyCode) -> KeyEvent {
+        KeyEvent::new(c, KeyModifiers::NONE)
+    }
+
+    fn sample() -> LogViewerState {
+        let content = (1..=50)
+            .map(|n| {
+                if n == 42 {
+                    "not json".to_string()
+                } else {
+                    format!(
+                        r#"{{"event_type":"tool_start","timestamp":"2026-02-08T10:00:{:02}Z","agent_id":"a","task_id":"T","session_id":"s","tool_name":"Read"}}"#,
+                        n % 60
+                    )
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        LogViewerState::from_content(PathBuf::from("events.jsonl"), &content)
+    }
+
+    #[test]
+    fn parse_errors_use_source_line_numbers() {
+        let state = sample();
+        assert_eq!(state.lines.len(), 50);
+        assert!(state.error_lines.contains(&42));
+        assert_eq!(state.error_lines.len(), 1);
+    }
+
+    #[test]
+    fn goto_prompt_jumps_to_line() {
+        let mut state = sample();
+        for c in [':', '4', '2'] {
+            assert!(state.handle_key(key(KeyCode::Char(c))));
+        }
+        assert_eq!(state.goto_input.as_deref(), Some("42"));
+        state.handle_key(key(KeyCode::Enter));
+        assert_eq!(state.cursor, 41);
+        assert!(state.goto_input.is_none());
+    }
+
+    #[test]
+    fn goto_clamps_past_end() {
+        let mut state = sample();
+        state.goto_line(9999);
+        assert_eq!(state.cursor, 49);
+    }
+
+    #[test]
+    fn gg_and_shift_g() {
+        let mut state = sample();
+        state.handle_key(key(KeyCode::Char('G')));
+        assert_eq!(state.cursor, 49);
+        state.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(state.cursor, 49, "single g does nothing");
+        state.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(state.cursor, 0);
+    }
+
+    #[test]
+    fn g_then_other_key_cancels_sequence() {
+        let mut state = sample();
+        state.goto_line(10);
+        state.handle_key(key(KeyCode::Char('g')));
+        state.handle_key(key(KeyCode::Char('j')));
+        state.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(state.cursor, 10);
+    }
+
+    #[test]
+    fn esc_closes() {
+        let mut state = sample();
+        assert!(!state.handle_key(key(KeyCode::Esc)));
+    }
+
+    #[test]
+    fn render_scrolls_cursor_into_view_with_line_numbers() {
+        let mut state = sample();
+        state.goto_line(42);
+        let area = Rect::new(0, 0, 60, 12);
+        let mut buf = Buffer::empty(area);
+        LogViewer::new().render(area, &mut buf, &mut state);
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert!(state.offset > 0);
+        assert!(rows.iter().any(|r| r.contains("42 not json")), "{rows:?}");
+        assert!(rows.iter().any(|r| r.contains("line 42/50")));
+    }
+}
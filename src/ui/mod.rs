@@ -1,7 +1,222 @@
+//! Terminal widgets of the dashboard, and [`draw`] composing them into a
+//! frame from the [`App`] state
+
+pub mod agent_picker;
+pub mod block_reason;
+pub mod bulk_status;
 pub mod claude_output;
+pub mod conflict_dialog;
 pub mod detail;
 pub mod gantt;
 pub mod help;
+pub mod history;
+pub mod icons;
 pub mod layout;
+pub mod line_input;
+pub mod log_viewer;
+pub mod note_editor;
+pub mod panel;
+pub mod phase_picker;
+pub mod progress_gauge;
+pub mod rename;
 pub mod retry_modal;
+pub mod session_picker;
+pub mod signal_picker;
 pub mod statusbar;
+pub mod task_form;
+pub mod theme;
+
+use ratatui::widgets::Clear;
+use ratatui::Frame;
+
+use crate::app::App;
+use agent_picker::AgentPicker;
+use block_reason::BlockReasonPrompt;
+use bulk_status::BulkStatusPopup;
+use claude_output::AgentPanel;
+use conflict_dialog::ConflictDialog;
+use detail::DetailWidget;
+use gantt::GanttWidget;
+use help::HelpOverlay;
+use history::HistoryView;
+use layout::{DashboardLayout, FocusedPane};
+use log_viewer::LogViewer;
+use note_editor::NoteEditor;
+use panel::PanelView;
+use phase_picker::PhasePicker;
+use progress_gauge::ProgressGauge;
+use retry_modal::RetryModal;
+use session_picker::SessionPicker;
+use signal_picker::SignalPicker;
+use statusbar::StatusBar;
+use task_form::TaskForm;
+use theme::Theme;
+
+/// Render one frame of the dashboard: the panes, then whichever overlays
+/// are open, topmost last
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let area = frame.area();
+    let layout = DashboardLayout::compute_with_gauge(area, app.layout_mode, app.show_gauge)
+        .with_panels(app.panels.len());
+    let theme = Theme::preset(app.config.theme);
+
+    // Left panel: Gantt chart
+    let gantt = GanttWidget::new(&app.dashboard, app.focused == FocusedPane::TaskList)
+        .with_icons(app.config.icons)
+        .with_columns(&app.config.task_columns)
+        .with_rename(app.rename.as_ref())
+        .with_retry_threshold(app.config.retry_threshold())
+        .with_theme(theme);
+    frame.render_stateful_widget(gantt, layout.task_list, &mut app.gantt_state);
+
+    // Right panel: Detail view (content depends on focused pane)
+    let selected_task = app.selected_task();
+    let detail = if let Some(ref agent_id) = app.agent_drilldown {
+        DetailWidget::from_agent_drilldown(&app.dashboard, agent_id)
+            .with_tool_cursor(app.drilldown_tool)
+    } else if app.focused == FocusedPane::Agents {
+        DetailWidget::from_agent_selection(&app.dashboard, app.selected_agent)
+    } else {
+        DetailWidget::from_selection(
+            &app.dashboard,
+            selected_task,
+            app.gantt_state.selected,
+            app.focused == FocusedPane::Detail,
+        )
+    }
+    .with_retry_threshold(app.config.retry_threshold())
+    .with_theme(theme);
+    // Stacked layout: detail floats over the panes, drawn last and only on demand
+    let detail = if layout.detail_overlay {
+        Some(detail).filter(|_| app.detail_overlay_visible())
+    } else {
+        frame.render_widget(detail, layout.detail);
+        None
+    };
+
+    // Right bottom: Agent activity (highlights agent for selected task)
+    let selected_agent_name = selected_task
+        .and_then(|(pi, ti)| {
+            app.dashboard
+                .phases
+                .get(pi)
+                .and_then(|phase| phase.tasks.get(ti))
+        })
+        .and_then(|task| app.dashboard.agent_for_task(&task.id));
+    let agents = AgentPanel::new(&app.dashboard)
+        .with_selected_agent(selected_agent_name)
+        .with_focused(app.focused == FocusedPane::Agents)
+        .with_selected_index(app.selected_agent)
+        .with_icons(app.config.icons)
+        .with_theme(theme)
+        .with_health(app.config.health)
+        .with_loops(app.config.loops)
+        .with_spinner(app.spinner_frame);
+    frame.render_widget(agents, layout.agents);
+
+    // Custom panels registered by the embedding code
+    for (i, (panel, panel_area)) in app.panels.iter().zip(&layout.panels).enumerate() {
+        let view = PanelView::new(panel.as_ref(), &app.dashboard)
+            .with_focused(app.focused == FocusedPane::Custom(i))
+            .with_theme(theme);
+        frame.render_widget(view, *panel_area);
+    }
+
+    if let Some(detail) = detail {
+        frame.render_widget(Clear, layout.detail);
+        frame.render_widget(detail, layout.detail);
+    }
+
+    // Optional overall progress gauge above the status bar
+    if let Some(gauge_area) = layout.gauge {
+        let gauge = ProgressGauge::new(&app.dashboard).with_theme(theme);
+        frame.render_widget(gauge, gauge_area);
+    }
+
+    // Bottom: Status bar
+    let statusbar = StatusBar::new(&app.dashboard, app.start_time)
+        .with_loading(app.history_progress)
+        .with_budget(app.config.budget)
+        .with_health(app.config.health)
+        .with_loops(app.config.loops)
+        .with_message(app.status_message.as_deref())
+        .with_reminders(app.reminders.len())
+        .with_theme(theme);
+    frame.render_widget(statusbar, layout.status_bar);
+
+    // Help overlay (on top if active)
+    if app.show_help {
+        frame.render_stateful_widget(HelpOverlay, area, &mut app.help);
+    }
+
+    // Session picker (on top if active)
+    if app.show_session_picker {
+        let picker = SessionPicker::new(&app.dashboard, app.session_picker_selected);
+        frame.render_widget(picker, area);
+    }
+
+    // Past sessions (full screen)
+    if let Some(ref mut history) = app.session_history {
+        frame.render_stateful_widget(HistoryView::new().with_theme(theme), area, history);
+    }
+
+    // Raw log viewer (full screen)
+    if let Some(ref mut viewer) = app.log_viewer {
+        frame.render_stateful_widget(LogViewer::new().with_theme(theme), area, viewer);
+    }
+
+    // Agent picker (on top if active)
+    if let Some(ref picker) = app.agent_picker {
+        frame.render_widget(AgentPicker::new(picker).with_theme(theme), area);
+    }
+
+    // Move-to-phase picker (on top if active)
+    if let Some(ref picker) = app.phase_picker {
+        frame.render_widget(PhasePicker::new(picker).with_theme(theme), area);
+    }
+
+    // Bulk phase status popup (on top if active)
+    if let Some(ref popup) = app.bulk_status {
+        frame.render_widget(BulkStatusPopup::new(popup).with_theme(theme), area);
+    }
+
+    // Agent signal popup (on top if active)
+    if let Some(ref picker) = app.signal_picker {
+        frame.render_widget(SignalPicker::new(picker).with_theme(theme), area);
+    }
+
+    // Add-task form (on top if active)
+    if let Some(ref mut form) = app.task_form {
+        frame.render_stateful_widget(TaskForm::new().with_theme(theme), area, form);
+    }
+
+    // Blocked reason prompt (on top if active)
+    if let Some(ref prompt) = app.block_reason {
+        frame.render_widget(BlockReasonPrompt::new(prompt).with_theme(theme), area);
+    }
+
+    // Task note editor (on top if active)
+    if let Some(ref mut editor) = app.note_editor {
+        frame.render_stateful_widget(NoteEditor::new().with_theme(theme), area, editor);
+    }
+
+    // Retry modal (on top if active)
+    if app.show_retry_modal {
+        if let Some(ref target) = app.retry_target {
+            let modal = RetryModal {
+                task_id: target.task_id.clone(),
+                task_name: target.task_name.clone(),
+                retryable: target.retryable,
+                error: app.retry_error().map(|e| e.message.clone()),
+                fix: app.retry_fix().map(str::to_string),
+                confirming_fix: app.confirming_fix,
+            };
+            frame.render_widget(modal, area);
+        }
+    }
+
+    // Write-back conflict dialog (topmost)
+    if let Some(ref conflict) = app.conflict {
+        frame.render_widget(ConflictDialog::new(conflict).with_theme(theme), area);
+    }
+}
@@ -0,0 +1,288 @@
+//! Task note editor
+//!
+//! Multi-line popup editor for a note on the selected task. The note is
+//! appended under the task in TASKS.md as `<!-- note ... -->` lines.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::ui::task_form::FormOutcome;
+use crate::ui::theme::Theme;
+
+/// Note text and cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteEditorState {
+    pub task_id: String,
+    pub lines: Vec<String>,
+    /// Cursor line
+    pub row: usize,
+    /// Cursor position in chars within the line
+    pub col: usize,
+    /// First line shown, kept so the cursor stays visible
+    pub scroll: usize,
+    /// Validation or write error shown above the key hints
+    pub error: Option<String>,
+}
+
+impl NoteEditorState {
+    pub fn new(task_id: &str) -> Self {
+        Self {
+            task_id: task_id.to_string(),
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            scroll: 0,
+            error: None,
+        }
+    }
+
+    /// The note as typed, lines joined with `\n`
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn byte_at(line: &str, col: usize) -> usize {
+        line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Handle a key; Ctrl+S saves a non-empty note, Esc cancels
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormOutcome {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return FormOutcome::Cancel,
+            KeyCode::Char('s') if ctrl => {
+                if self.text().trim().is_empty() {
+                    self.error = Some("Note is empty".to_string());
+                } else {
+                    return FormOutcome::Submit;
+                }
+            }
+            KeyCode::Enter => {
+                let at = Self::byte_at(&self.lines[self.row], self.col);
+                let rest = self.lines[self.row].split_off(at);
+                self.row += 1;
+                self.lines.insert(self.row, rest);
+                self.col = 0;
+            }
+            KeyCode::Backspace if self.col > 0 => {
+                self.col -= 1;
+                let at = Self::byte_at(&self.lines[self.row], self.col);
+                self.lines[self.row].remove(at);
+            }
+            KeyCode::Backspace if self.row > 0 => {
+                let line = self.lines.remove(self.row);
+                self.row -= 1;
+                self.col = self.line_len(self.row);
+                self.lines[self.row].push_str(&line);
+            }
+            KeyCode::Delete if self.col < self.line_len(self.row) => {
+                let at = Self::byte_at(&self.lines[self.row], self.col);
+                self.lines[self.row].remove(at);
+            }
+            KeyCode::Delete if self.row + 1 < self.lines.len() => {
+                let next = self.lines.remove(self.row + 1);
+                self.lines[self.row].push_str(&next);
+            }
+            KeyCode::Left => self.col = self.col.saturating_sub(1),
+            KeyCode::Right => self.col = (self.col + 1).min(self.line_len(self.row)),
+            KeyCode::Up if self.row > 0 => {
+                self.row -= 1;
+                self.col = self.col.min(self.line_len(self.row));
+            }
+            KeyCode::Down if self.row + 1 < self.lines.len() => {
+                self.row += 1;
+                self.col = self.col.min(self.line_len(self.row));
+            }
+            KeyCode::Home => self.col = 0,
+            KeyCode::End => self.col = self.line_len(self.row),
+            KeyCode::Char(c) if !ctrl => {
+                let at = Self::byte_at(&self.lines[self.row], self.col);
+                self.lines[self.row].insert(at, c);
+                self.col += 1;
+            }
+            _ => {}
+        }
+        FormOutcome::Editing
+    }
+}
+
+/// Note editor popup widget
+pub struct NoteEditor {
+    theme: Theme,
+}
+
+impl NoteEditor {
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(area: Rect) -> Rect {
+        let width = 60.min(area.width.saturating_sub(4));
+        let height = 12.min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    /// Text rows (scrolled to keep the cursor in view), error and key hints
+    fn build_lines(&self, state: &mut NoteEditorState, text_rows: usize) -> Vec<Line<'static>> {
+        let text_rows = text_rows.max(1);
+        if state.row < state.scroll {
+            state.scroll = state.row;
+        } else if state.row >= state.scroll + text_rows {
+            state.scroll = state.row + 1 - text_rows;
+        }
+
+        let text_style = Style::default().fg(self.theme.text);
+        let mut lines: Vec<Line<'static>> = state
+            .lines
+            .iter()
+            .enumerate()
+            .skip(state.scroll)
+            .take(text_rows)
+            .map(|(row, line)| {
+                if row != state.row {
+                    return Line::styled(format!(" {line}"), text_style);
+                }
+                let chars: Vec<char> = line.chars().collect();
+                let col = state.col.min(chars.len());
+                let before: String = chars[..col].iter().collect();
+                let at: String = chars.get(col).map_or(" ".to_string(), |c| c.to_string());
+                let after: String = chars
+                    .get(col + 1..)
+                    .map_or(String::new(), |rest| rest.iter().collect());
+                Line::from(vec![
+                    Span::styled(format!(" {before}"), text_style),
+                    Span::styled(at, text_style.add_modifier(Modifier::REVERSED)),
+                    Span::styled(after, text_style),
+                ])
+            })
+            .collect();
+        lines.resize(text_rows, Line::raw(""));
+
+        lines.push(match state.error {
+            Some(ref e) => Line::styled(format!(" {e}"), Style::default().fg(self.theme.failed)),
+            None => Line::raw(""),
+        });
+        lines.push(Line::styled(
+            " Enter newline  Ctrl+S save  Esc cancel",
+            Style::default().fg(self.theme.muted),
+        ));
+        lines
+    }
+}
+
+impl Default for NoteEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatefulWidget for NoteEditor {
+    type State = NoteEditorState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(format!(" Note on {} ", state.task_id))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+        // Borders, error line and key hints
+        let text_rows = popup_area.height.saturating_sub(4) as usize;
+        Paragraph::new(self.build_lines(state, text_rows))
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(state: &mut NoteEditorState, text: &str) {
+        for c in text.chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn enter_splits_and_backspace_joins_lines() {
+        let mut state = NoteEditorState::new("T1");
+        type_text(&mut state, "flaky test");
+        for _ in 0..5 {
+            state.handle_key(key(KeyCode::Left));
+        }
+        state.handle_key(key(KeyCode::Enter));
+        assert_eq!(state.lines, vec!["flaky", " test"]);
+        assert_eq!((state.row, state.col), (1, 0));
+
+        state.handle_key(key(KeyCode::Backspace));
+        assert_eq!(state.lines, vec!["flaky test"]);
+        assert_eq!(state.col, 5);
+
+        state.handle_key(key(KeyCode::End));
+        state.handle_key(key(KeyCode::Enter));
+        type_text(&mut state, "see run 3");
+        state.handle_key(key(KeyCode::Up));
+        state.handle_key(key(KeyCode::End));
+        state.handle_key(key(KeyCode::Delete));
+        assert_eq!(state.text(), "flaky testsee run 3");
+    }
+
+    #[test]
+    fn ctrl_s_saves_only_non_empty_notes() {
+        let save = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        let mut state = NoteEditorState::new("T1");
+        state.handle_key(key(KeyCode::Enter));
+        assert_eq!(state.handle_key(save), FormOutcome::Editing);
+        assert!(state.error.is_some());
+
+        type_text(&mut state, "한글 메모");
+        assert_eq!(state.handle_key(save), FormOutcome::Submit);
+        assert_eq!(state.text(), "\n한글 메모");
+        assert_eq!(state.handle_key(key(KeyCode::Esc)), FormOutcome::Cancel);
+    }
+
+    #[test]
+    fn view_scrolls_to_cursor_row() {
+        let mut state = NoteEditorState::new("T1");
+        for i in 0..5 {
+            type_text(&mut state, &format!("line {i}"));
+            state.handle_key(key(KeyCode::Enter));
+        }
+        let text: Vec<String> = NoteEditor::new()
+            .build_lines(&mut state, 2)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(state.scroll, 4);
+        assert_eq!(text[0], " line 4");
+        assert_eq!(text[1], "  ");
+
+        let area = Rect::new(0, 0, 10, 5);
+        NoteEditor::new().render(area, &mut Buffer::empty(area), &mut state);
+    }
+}
@@ -0,0 +1,148 @@
+//! Custom panels
+//!
+//! Downstream code can add its own panes (a CI status, a deploy log)
+//! without touching the built-in ones: implement [`Panel`] and register it
+//! with `App::with_panel`. Registered panels split the agents row with the
+//! agent panel, join Tab focus cycling after the built-in panes, and see key
+//! actions first while they have focus. A panel can also claim a hotkey
+//! that is not bound to anything else to jump straight to it.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use crate::data::state::DashboardState;
+use crate::event::Action;
+use crate::ui::theme::Theme;
+
+/// A pane contributed from outside the crate; `Send` so the app can move
+/// to the web and MCP mirrors
+pub trait Panel: Send {
+    /// Shown in the panel's border
+    fn title(&self) -> String;
+
+    /// Contents for the current state, `width` columns inside the border
+    fn build_lines(&self, state: &DashboardState, width: u16) -> Vec<Line<'static>>;
+
+    /// React to a key action while the panel has focus; `true` when the
+    /// panel handled it, otherwise the dashboard does. Quit, Tab and the help
+    /// overlay never reach the panel.
+    fn handle_action(&mut self, _action: &Action, _state: &DashboardState) -> bool {
+        false
+    }
+
+    /// Key that focuses the panel from anywhere, if it is not already bound
+    fn hotkey(&self) -> Option<char> {
+        None
+    }
+}
+
+/// Whether a focused panel gets to see `action` before the dashboard
+pub fn forwards(action: &Action) -> bool {
+    !matches!(
+        action,
+        Action::Quit | Action::ToggleFocus | Action::ToggleHelp | Action::None
+    )
+}
+
+/// Draws a registered panel in its layout area
+pub struct PanelView<'a> {
+    panel: &'a dyn Panel,
+    state: &'a DashboardState,
+    focused: bool,
+    theme: Theme,
+}
+
+impl<'a> PanelView<'a> {
+    pub fn new(panel: &'a dyn Panel, state: &'a DashboardState) -> Self {
+        Self {
+            panel,
+            state,
+            focused: false,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
+
+impl<'a> Widget for PanelView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_color = if self.focused {
+            self.theme.accent
+        } else {
+            self.theme.muted
+        };
+        let block = Block::default()
+            .title(format!(" {} ", self.panel.title()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color));
+
+        let lines = self
+            .panel
+            .build_lines(self.state, area.width.saturating_sub(2));
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A CI status panel taking Enter (to refresh) and `C` (to focus it)
+    #[derive(Default)]
+    pub(crate) struct Ci {
+        pub(crate) refreshed: usize,
+    }
+
+    impl Panel for Ci {
+        fn title(&self) -> String {
+            "CI".to_string()
+        }
+
+        fn build_lines(&self, state: &DashboardState, _width: u16) -> Vec<Line<'static>> {
+            vec![Line::raw(format!(
+                "{} tasks, refreshed {}",
+                state.total_tasks, self.refreshed
+            ))]
+        }
+
+        fn handle_action(&mut self, action: &Action, _: &DashboardState) -> bool {
+            if *action == Action::Select {
+                self.refreshed += 1;
+                return true;
+            }
+            false
+        }
+
+        fn hotkey(&self) -> Option<char> {
+            Some('C')
+        }
+    }
+
+    #[test]
+    fn panel_view_draws_title_and_lines() {
+        let state = DashboardState::default();
+        let area = Rect::new(0, 0, 30, 3);
+        let mut buf = Buffer::empty(area);
+        PanelView::new(&Ci::default(), &state).render(area, &mut buf);
+        let row = |y: u16| -> String { (0..area.width).map(|x| buf[(x, y)].symbol()).collect() };
+        assert!(row(0).contains(" CI "));
+        assert!(row(1).contains("0 tasks, refreshed 0"));
+    }
+}
@@ -0,0 +1,202 @@
+//! Move-to-phase picker
+//!
+//! Centered popup listing the phases; choosing one moves the selected task
+//! to the end of that phase. Follows the same pattern as `AgentPicker`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::state::DashboardState;
+use crate::ui::theme::Theme;
+
+/// Task being moved and the phases it can go to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhasePickerState {
+    pub task_id: String,
+    /// Index of the task's current phase
+    pub current: usize,
+    /// `(id, name)` of each phase
+    pub phases: Vec<(String, String)>,
+    pub selected: usize,
+}
+
+impl PhasePickerState {
+    /// Picker for the task at `(pi, ti)`, starting on the next phase
+    pub fn new(state: &DashboardState, pi: usize, ti: usize) -> Option<Self> {
+        let task = state.phases.get(pi)?.tasks.get(ti)?;
+        let phases: Vec<(String, String)> = state
+            .phases
+            .iter()
+            .map(|p| (p.id.clone(), p.name.clone()))
+            .collect();
+        Some(Self {
+            task_id: task.id.clone(),
+            current: pi,
+            selected: (pi + 1).min(phases.len() - 1),
+            phases,
+        })
+    }
+
+    pub fn move_down(&mut self) {
+        if self.selected + 1 < self.phases.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// ID of the highlighted phase, unless it is the task's own phase
+    pub fn target_phase(&self) -> Option<&str> {
+        (self.selected != self.current)
+            .then(|| self.phases.get(self.selected).map(|(id, _)| id.as_str()))
+            .flatten()
+    }
+}
+
+/// Move-to-phase popup widget
+pub struct PhasePicker<'a> {
+    state: &'a PhasePickerState,
+    theme: Theme,
+}
+
+impl<'a> PhasePicker<'a> {
+    pub fn new(state: &'a PhasePickerState) -> Self {
+        Self {
+            state,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(&self, area: Rect) -> Rect {
+        let wanted = self.state.phases.len() as u16 + 6;
+        let width = 48.min(area.width.saturating_sub(4));
+        let height = wanted.min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self, visible_rows: usize) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" Move ", Style::default().fg(self.theme.muted)),
+                Span::styled(
+                    self.state.task_id.clone(),
+                    Style::default()
+                        .fg(self.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" to phase", Style::default().fg(self.theme.muted)),
+            ]),
+            Line::raw(""),
+        ];
+
+        let skip = (self.state.selected + 1).saturating_sub(visible_rows);
+        for (i, (id, name)) in self
+            .state
+            .phases
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(visible_rows)
+        {
+            let is_selected = i == self.state.selected;
+            let prefix = if is_selected { " > " } else { "   " };
+            let marker = if i == self.state.current { "* " } else { "  " };
+            let style = if is_selected {
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, Style::default().fg(self.theme.accent)),
+                Span::raw(marker),
+                Span::styled(format!("{id} {name}"), style),
+            ]));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            " j/k move  Enter move task  Esc close",
+            Style::default().fg(self.theme.muted),
+        ));
+        lines
+    }
+}
+
+impl<'a> Widget for PhasePicker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Move to phase ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        // Borders, two header lines and two footer lines
+        let visible_rows = popup_area.height.saturating_sub(6).max(1) as usize;
+        Paragraph::new(self.build_lines(visible_rows))
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_state() -> DashboardState {
+        let input = include_str!("../../tests/fixtures/sample_tasks.md");
+        DashboardState::from_tasks_content(input).unwrap()
+    }
+
+    #[test]
+    fn starts_on_next_phase_and_skips_own_phase() {
+        let state = sample_state();
+        let mut picker = PhasePickerState::new(&state, 1, 0).unwrap();
+        assert_eq!(picker.selected, 2);
+        assert_eq!(picker.target_phase(), Some("P2"));
+        picker.move_down();
+        assert_eq!(picker.selected, 2);
+        picker.move_up();
+        assert_eq!(picker.target_phase(), None);
+
+        let last = PhasePickerState::new(&state, 2, 0).unwrap();
+        assert_eq!(last.selected, 2);
+        assert!(PhasePickerState::new(&state, 0, 9).is_none());
+    }
+
+    #[test]
+    fn picker_lists_phases_with_current_marked() {
+        let state = sample_state();
+        let picker = PhasePickerState::new(&state, 0, 0).unwrap();
+        let text: Vec<String> = PhasePicker::new(&picker)
+            .build_lines(10)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.contains(&"   * P0 Setup".to_string()), "{text:?}");
+        assert!(
+            text.contains(&" >   P1 Data Engine".to_string()),
+            "{text:?}"
+        );
+
+        let area = Rect::new(0, 0, 10, 5);
+        PhasePicker::new(&picker).render(area, &mut Buffer::empty(area));
+    }
+}
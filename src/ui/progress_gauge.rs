@@ -0,0 +1,146 @@
+//! Overall progress gauge
+//!
+//! Optional full-width bar above the status bar showing overall completion,
+//! with a tick at each phase boundary. Easier to read from across the room
+//! than the percentage in the status bar.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Widget,
+};
+
+use crate::data::state::DashboardState;
+use crate::ui::theme::Theme;
+
+/// One-row overall progress gauge
+pub struct ProgressGauge<'a> {
+    state: &'a DashboardState,
+    theme: Theme,
+}
+
+impl<'a> ProgressGauge<'a> {
+    pub fn new(state: &'a DashboardState) -> Self {
+        Self {
+            state,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Column offsets (within a bar of `width` cells) where one phase ends and
+    /// the next begins
+    fn phase_ticks(&self, width: u16) -> Vec<u16> {
+        let total = self.state.total_tasks;
+        if total == 0 {
+            return Vec::new();
+        }
+        let mut ticks = Vec::new();
+        let mut seen = 0;
+        let phases = &self.state.phases;
+        for phase in phases.iter().take(phases.len().saturating_sub(1)) {
+            seen += phase.tasks.len();
+            let col = (seen * width as usize / total) as u16;
+            if col > 0 && col < width {
+                ticks.push(col);
+            }
+        }
+        ticks
+    }
+}
+
+impl<'a> Widget for ProgressGauge<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let label = format!(
+            " {:>3}% {}/{} ",
+            (self.state.overall_progress * 100.0) as u8,
+            self.state.completed_tasks,
+            self.state.total_tasks
+        );
+        let label_width = (label.chars().count() as u16).min(area.width);
+        buf.set_stringn(
+            area.x,
+            area.y,
+            &label,
+            area.width as usize,
+            Style::default()
+                .fg(self.theme.text)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        let bar_x = area.x + label_width;
+        let bar_width = area.width - label_width;
+        let filled = (self.state.overall_progress.clamp(0.0, 1.0) * bar_width as f32) as u16;
+        let ticks = self.phase_ticks(bar_width);
+
+        for col in 0..bar_width {
+            let is_filled = col < filled;
+            let (symbol, color) = if ticks.contains(&col) {
+                ("\u{2502}", self.theme.accent) // │
+            } else if is_filled {
+                ("\u{2588}", self.theme.completed) // █
+            } else {
+                ("\u{2591}", self.theme.muted) // ░
+            };
+            let mut style = Style::default().fg(color);
+            if is_filled {
+                style = style.bg(self.theme.completed);
+            }
+            buf.set_string(bar_x + col, area.y, symbol, style);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_state() -> DashboardState {
+        let input = include_str!("../../tests/fixtures/sample_tasks.md");
+        DashboardState::from_tasks_content(input).unwrap()
+    }
+
+    fn row(buf: &Buffer, area: Rect) -> String {
+        (0..area.width).map(|x| buf[(x, area.y)].symbol()).collect()
+    }
+
+    #[test]
+    fn gauge_shows_percentage_and_phase_ticks() {
+        let state = sample_state();
+        let area = Rect::new(0, 0, 60, 1);
+        let mut buf = Buffer::empty(area);
+        ProgressGauge::new(&state).render(area, &mut buf);
+        let text = row(&buf, area);
+        assert!(text.starts_with("  25% 2/8 "), "got: {text}");
+        // 3 phases → 2 boundaries
+        assert_eq!(text.matches('\u{2502}').count(), 2, "got: {text}");
+        assert!(text.contains('\u{2588}'));
+        assert!(text.contains('\u{2591}'));
+    }
+
+    #[test]
+    fn tick_positions_follow_task_counts() {
+        let state = sample_state();
+        // Phases have 2, 3 and 3 tasks out of 8
+        let ticks = ProgressGauge::new(&state).phase_ticks(80);
+        assert_eq!(ticks, vec![20, 50]);
+    }
+
+    #[test]
+    fn empty_state_and_tiny_area_render() {
+        let state = DashboardState::default();
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buf = Buffer::empty(area);
+        ProgressGauge::new(&state).render(area, &mut buf);
+        assert!(ProgressGauge::new(&state).phase_ticks(10).is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+//! Inline rename
+//!
+//! Single-line editor for renaming the selected task or phase. The gantt
+//! tree draws it in place of the row's name; trailing `@agent` / `#tag`
+//! annotations are left out of the editor and kept on write-back.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::data::tasks_writer::split_annotations;
+use crate::ui::line_input::LineInput;
+use crate::ui::task_form::FormOutcome;
+
+/// What is being renamed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameTarget {
+    /// Task ID
+    Task(String),
+    /// Phase ID (`P1`)
+    Phase(String),
+}
+
+/// Editor text and cursor of an inline rename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameState {
+    pub target: RenameTarget,
+    pub input: LineInput,
+    /// Validation or write error shown under the task list
+    pub error: Option<String>,
+}
+
+impl RenameState {
+    /// Start editing `name` (without its annotations), cursor at the end
+    pub fn new(target: RenameTarget, name: &str) -> Self {
+        Self {
+            target,
+            input: LineInput::new(split_annotations(name).0),
+            error: None,
+        }
+    }
+
+    /// Handle a key; Enter submits a non-empty name, Esc cancels
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormOutcome {
+        match key.code {
+            KeyCode::Esc => return FormOutcome::Cancel,
+            KeyCode::Enter if self.input.text().trim().is_empty() => {
+                self.error = Some("Name cannot be empty".to_string());
+            }
+            KeyCode::Enter => return FormOutcome::Submit,
+            _ => {
+                self.input.handle_key(key);
+            }
+        }
+        FormOutcome::Editing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn editor_drops_annotations_and_edits_at_cursor() {
+        let mut state = RenameState::new(RenameTarget::Task("T1".into()), "Parsr @dev");
+        assert_eq!(state.input.text(), "Parsr");
+        state.handle_key(key(KeyCode::Left));
+        state.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(state.input.text(), "Parser");
+        state.handle_key(key(KeyCode::Home));
+        state.handle_key(key(KeyCode::Delete));
+        state.handle_key(key(KeyCode::Char('p')));
+        assert_eq!(state.input.text(), "parser");
+        state.handle_key(key(KeyCode::End));
+        state.handle_key(key(KeyCode::Backspace));
+        assert_eq!(state.input.text(), "parse");
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), FormOutcome::Submit);
+        assert_eq!(state.handle_key(key(KeyCode::Esc)), FormOutcome::Cancel);
+    }
+
+    #[test]
+    fn empty_name_is_rejected() {
+        let mut state = RenameState::new(RenameTarget::Phase("P0".into()), "");
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), FormOutcome::Editing);
+        assert!(state.error.is_some());
+    }
+}
@@ -1,6 +1,9 @@
 //! Retry confirmation modal
 //!
-//! Shows a centered popup asking the user to confirm retrying a failed task.
+//! Shows a centered popup asking the user to confirm retrying a failed task,
+//! with the task's latest error, an action to track it as a new task, and
+//! the fix command its error rule suggests, run only after a second
+//! confirmation.
 //! Follows the same pattern as `HelpOverlay`.
 
 use ratatui::{
@@ -16,12 +19,18 @@ pub struct RetryModal {
     pub task_id: String,
     pub task_name: String,
     pub retryable: bool,
+    /// Message of the task's latest recorded error
+    pub error: Option<String>,
+    /// Shell command the error's rule offers as a fix
+    pub fix: Option<String>,
+    /// Asking whether to run `fix`
+    pub confirming_fix: bool,
 }
 
 impl RetryModal {
     fn centered_rect(area: Rect) -> Rect {
-        let width = 36.min(area.width.saturating_sub(4));
-        let height = 10.min(area.height.saturating_sub(4));
+        let width = 52.min(area.width.saturating_sub(4));
+        let height = 13.min(area.height.saturating_sub(4));
         let x = (area.width.saturating_sub(width)) / 2;
         let y = (area.height.saturating_sub(height)) / 2;
         Rect::new(x, y, width, height)
@@ -42,8 +51,35 @@ impl RetryModal {
                 Span::styled("  Name: ", Style::default().fg(Color::DarkGray)),
                 Span::raw(self.task_name.clone()),
             ]),
-            Line::raw(""),
         ];
+        if let Some(ref error) = self.error {
+            lines.push(Line::from(vec![
+                Span::styled("  Error: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(error.clone(), Style::default().fg(Color::Red)),
+            ]));
+        }
+        if let Some(ref fix) = self.fix {
+            lines.push(Line::from(vec![
+                Span::styled("  Fix:   ", Style::default().fg(Color::DarkGray)),
+                Span::styled(fix.clone(), Style::default().fg(Color::Cyan)),
+            ]));
+        }
+        lines.push(Line::raw(""));
+
+        if self.confirming_fix {
+            lines.push(Line::styled(
+                "  Run the fix in a shell?",
+                Style::default().fg(Color::Yellow),
+            ));
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled("  [y]", Style::default().fg(Color::Green)),
+                Span::raw(" Run  "),
+                Span::styled("[n]", Style::default().fg(Color::Red)),
+                Span::raw(" Back"),
+            ]));
+            return lines;
+        }
 
         if self.retryable {
             lines.push(Line::styled(
@@ -69,6 +105,19 @@ impl RetryModal {
             ));
         }
 
+        if self.error.is_some() {
+            lines.push(Line::from(vec![
+                Span::styled("  [a]", Style::default().fg(Color::Cyan)),
+                Span::raw(" Track error as new task"),
+            ]));
+        }
+        if self.fix.is_some() {
+            lines.push(Line::from(vec![
+                Span::styled("  [f]", Style::default().fg(Color::Cyan)),
+                Span::raw(" Run fix"),
+            ]));
+        }
+
         lines
     }
 }
@@ -99,6 +148,9 @@ mod tests {
             task_id: "P1-R3-T1".to_string(),
             task_name: "File watcher".to_string(),
             retryable: true,
+            error: None,
+            fix: None,
+            confirming_fix: false,
         };
         let area = Rect::new(0, 0, 80, 30);
         let mut buf = Buffer::empty(area);
@@ -111,6 +163,9 @@ mod tests {
             task_id: "P1-R3-T1".to_string(),
             task_name: "File watcher".to_string(),
             retryable: false,
+            error: None,
+            fix: None,
+            confirming_fix: false,
         };
         let area = Rect::new(0, 0, 80, 30);
         let mut buf = Buffer::empty(area);
@@ -123,6 +178,9 @@ mod tests {
             task_id: "T1".to_string(),
             task_name: "Test".to_string(),
             retryable: true,
+            error: None,
+            fix: None,
+            confirming_fix: false,
         };
         let area = Rect::new(0, 0, 20, 8);
         let mut buf = Buffer::empty(area);
@@ -135,6 +193,9 @@ mod tests {
             task_id: "T1".to_string(),
             task_name: "Test".to_string(),
             retryable: true,
+            error: None,
+            fix: None,
+            confirming_fix: false,
         };
         let lines = modal.build_lines();
         let has_yes = lines
@@ -149,6 +210,9 @@ mod tests {
             task_id: "T1".to_string(),
             task_name: "Test".to_string(),
             retryable: false,
+            error: None,
+            fix: None,
+            confirming_fix: false,
         };
         let lines = modal.build_lines();
         let has_warning = lines
@@ -156,4 +220,56 @@ mod tests {
             .any(|l| l.spans.iter().any(|s| s.content.contains("Not retryable")));
         assert!(has_warning);
     }
+
+    #[test]
+    fn error_lines_offer_tracking_as_task() {
+        let modal = RetryModal {
+            task_id: "T1".to_string(),
+            task_name: "Test".to_string(),
+            retryable: false,
+            error: Some("permission denied: /etc/shadow".to_string()),
+            fix: None,
+            confirming_fix: false,
+        };
+        let text: Vec<String> = modal
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l.contains("permission denied")));
+        assert!(text
+            .iter()
+            .any(|l| l.contains("[a] Track error as new task")));
+        assert!(!text.iter().any(|l| l.contains("Run fix")));
+    }
+
+    #[test]
+    fn fix_is_offered_then_confirmed() {
+        let mut modal = RetryModal {
+            task_id: "T1".to_string(),
+            task_name: "Test".to_string(),
+            retryable: false,
+            error: Some("permission denied: build.sh".to_string()),
+            fix: Some("chmod +x build.sh".to_string()),
+            confirming_fix: false,
+        };
+        let text = |modal: &RetryModal| -> Vec<String> {
+            modal
+                .build_lines()
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        let offered = text(&modal);
+        assert!(offered.iter().any(|l| l == "  Fix:   chmod +x build.sh"));
+        assert!(offered.iter().any(|l| l == "  [f] Run fix"));
+
+        modal.confirming_fix = true;
+        let confirming = text(&modal);
+        assert!(confirming
+            .iter()
+            .any(|l| l.contains("Run the fix in a shell?")));
+        assert!(confirming.iter().any(|l| l == "  [y] Run  [n] Back"));
+        assert!(!confirming.iter().any(|l| l.contains("Not retryable")));
+    }
 }
@@ -0,0 +1,189 @@
+//! Session picker
+//!
+//! Centered popup listing the hook sessions found in the event directories,
+//! with start time, agent count, and error count. Choosing one limits the
+//! dashboard to that session; the first entry merges all sessions.
+//! Follows the same pattern as `RetryModal`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::state::{DashboardState, SessionSummary};
+
+/// Session picker popup widget
+pub struct SessionPicker<'a> {
+    sessions: Vec<&'a SessionSummary>,
+    /// Index into the entry list; 0 is "All sessions"
+    selected: usize,
+    active: Option<&'a str>,
+}
+
+impl<'a> SessionPicker<'a> {
+    pub fn new(state: &'a DashboardState, selected: usize) -> Self {
+        Self {
+            sessions: state.sorted_sessions(),
+            selected,
+            active: state.session_filter.as_deref(),
+        }
+    }
+
+    fn centered_rect(&self, area: Rect) -> Rect {
+        let wanted = self.sessions.len() as u16 + 7;
+        let width = 56.min(area.width.saturating_sub(4));
+        let height = wanted.min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn entry_line(&self, idx: usize, label: String, detail: String) -> Line<'static> {
+        let is_selected = idx == self.selected;
+        let prefix = if is_selected { " > " } else { "   " };
+        let name_style = if is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Line::from(vec![
+            Span::styled(prefix, Style::default().fg(Color::Cyan)),
+            Span::styled(label, name_style),
+            Span::styled(detail, Style::default().fg(Color::DarkGray)),
+        ])
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::styled(
+                " Select a session to monitor",
+                Style::default().fg(Color::Yellow),
+            ),
+            Line::raw(""),
+        ];
+
+        let all_marker = if self.active.is_none() { "*" } else { " " };
+        lines.push(self.entry_line(
+            0,
+            format!("{all_marker} All sessions (merged)"),
+            String::new(),
+        ));
+
+        for (i, session) in self.sessions.iter().enumerate() {
+            let marker = if self.active == Some(session.session_id.as_str()) {
+                "*"
+            } else {
+                " "
+            };
+            let short: String = session.session_id.chars().take(16).collect();
+            let detail = format!(
+                "  {}  {} agents  {} errs",
+                session.started_at.format("%m-%d %H:%M"),
+                session.agent_count(),
+                session.error_count
+            );
+            lines.push(self.entry_line(i + 1, format!("{marker} {short:<16}"), detail));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            " j/k move  Enter select  Esc close",
+            Style::default().fg(Color::DarkGray),
+        ));
+        lines
+    }
+}
+
+impl<'a> Widget for SessionPicker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = self.centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Sessions ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let lines = self.build_lines();
+        let paragraph = Paragraph::new(lines).block(block);
+        paragraph.render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_sessions() -> DashboardState {
+        let hooks_dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/sample_hooks");
+        let mut state = DashboardState::default();
+        state.load_hook_events(&hooks_dir).unwrap();
+        state
+    }
+
+    #[test]
+    fn picker_lists_all_sessions() {
+        let state = state_with_sessions();
+        let picker = SessionPicker::new(&state, 0);
+        let lines = picker.build_lines();
+        for id in state.sessions.keys() {
+            let found = lines
+                .iter()
+                .any(|l| l.spans.iter().any(|s| s.content.contains(id.as_str())));
+            assert!(found, "missing session {id}");
+        }
+    }
+
+    #[test]
+    fn picker_shows_error_count() {
+        let state = state_with_sessions();
+        let picker = SessionPicker::new(&state, 0);
+        let lines = picker.build_lines();
+        let has_errs = lines
+            .iter()
+            .any(|l| l.spans.iter().any(|s| s.content.contains("2 errs")));
+        assert!(has_errs);
+    }
+
+    #[test]
+    fn picker_marks_active_filter() {
+        let mut state = state_with_sessions();
+        state.session_filter = Some("sess-002".to_string());
+        let picker = SessionPicker::new(&state, 0);
+        let lines = picker.build_lines();
+        let active = lines
+            .iter()
+            .any(|l| l.spans.iter().any(|s| s.content.starts_with("* sess-002")));
+        assert!(active);
+    }
+
+    #[test]
+    fn picker_shortens_multibyte_session_ids() {
+        let mut state = state_with_sessions();
+        let mut session = state.sessions.values().next().unwrap().clone();
+        session.session_id = "세션아이디가아주길고긴경우에해당합니다".to_string();
+        state.sessions.insert(session.session_id.clone(), session);
+        let picker = SessionPicker::new(&state, 0);
+        let lines = picker.build_lines();
+        let shown = lines.iter().any(|l| {
+            l.spans
+                .iter()
+                .any(|s| s.content.contains("세션아이디가아주길고긴경우에해당"))
+        });
+        assert!(shown);
+    }
+
+    #[test]
+    fn picker_renders_small_terminal() {
+        let state = state_with_sessions();
+        let area = Rect::new(0, 0, 20, 8);
+        let mut buf = Buffer::empty(area);
+        SessionPicker::new(&state, 1).render(area, &mut buf);
+    }
+}
@@ -0,0 +1,188 @@
+//! Agent signal popup
+//!
+//! Lists the control signals that can be sent to an agent through the
+//! commands file. Abort asks for a y/n confirmation first. Follows the same
+//! pattern as `BulkStatusPopup`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::data::commands::Signal;
+use crate::ui::theme::Theme;
+
+fn label(signal: Signal) -> &'static str {
+    match signal {
+        Signal::Pause => "Pause tool calls",
+        Signal::Resume => "Resume",
+        Signal::Abort => "Abort task",
+    }
+}
+
+/// Agent being signalled, the highlighted signal and whether the y/n
+/// confirmation is showing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalPickerState {
+    pub agent_id: String,
+    pub selected: usize,
+    pub confirming: bool,
+}
+
+impl SignalPickerState {
+    pub fn new(agent_id: &str) -> Self {
+        Self {
+            agent_id: agent_id.to_string(),
+            selected: 0,
+            confirming: false,
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if !self.confirming && self.selected + 1 < Signal::ALL.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        if !self.confirming {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn selected_signal(&self) -> Signal {
+        Signal::ALL[self.selected.min(Signal::ALL.len() - 1)]
+    }
+}
+
+/// Agent signal popup widget
+pub struct SignalPicker<'a> {
+    state: &'a SignalPickerState,
+    theme: Theme,
+}
+
+impl<'a> SignalPicker<'a> {
+    pub fn new(state: &'a SignalPickerState) -> Self {
+        Self {
+            state,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(area: Rect) -> Rect {
+        let width = 40.min(area.width.saturating_sub(4));
+        let height = (Signal::ALL.len() as u16 + 6).min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn build_lines(&self) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(self.theme.muted);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" Agent ", muted),
+                Span::styled(
+                    self.state.agent_id.clone(),
+                    Style::default()
+                        .fg(self.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::raw(""),
+        ];
+
+        if self.state.confirming {
+            lines.push(Line::styled(
+                format!(
+                    " {} for {}?",
+                    label(self.state.selected_signal()),
+                    self.state.agent_id
+                ),
+                Style::default().fg(self.theme.failed),
+            ));
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled(" [y]", Style::default().fg(self.theme.completed)),
+                Span::raw(" Yes  "),
+                Span::styled("[n]", Style::default().fg(self.theme.failed)),
+                Span::raw(" No"),
+            ]));
+            return lines;
+        }
+
+        for (i, signal) in Signal::ALL.iter().enumerate() {
+            let is_selected = i == self.state.selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    if is_selected { " > " } else { "   " },
+                    Style::default().fg(self.theme.accent),
+                ),
+                Span::styled(label(*signal), style),
+            ]));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(" j/k move  Enter send  Esc close", muted));
+        lines
+    }
+}
+
+impl<'a> Widget for SignalPicker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Signal agent ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+        Paragraph::new(self.build_lines())
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abort_confirmation_freezes_selection() {
+        let mut state = SignalPickerState::new("backend");
+        for _ in 0..5 {
+            state.move_down();
+        }
+        assert_eq!(state.selected_signal(), Signal::Abort);
+        state.confirming = true;
+        state.move_up();
+        assert_eq!(state.selected_signal(), Signal::Abort);
+
+        let text: Vec<String> = SignalPicker::new(&state)
+            .build_lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(
+            text.contains(&" Abort task for backend?".to_string()),
+            "{text:?}"
+        );
+
+        let area = Rect::new(0, 0, 10, 5);
+        SignalPicker::new(&state).render(area, &mut Buffer::empty(area));
+    }
+}
@@ -1,6 +1,9 @@
 //! Status bar widget
 //!
-//! Shows per-status counters, progress %, uptime, and keybinding hints.
+//! Shows per-status counters, progress %, uptime, the projected finish,
+//! active agents, overdue tasks, open agent errors, agents in poor health,
+//! agents stuck in a tool loop, token/cost usage against the budgets, and
+//! keybinding hints.
 
 use std::time::Instant;
 
@@ -12,18 +15,88 @@ use ratatui::{
     widgets::Widget,
 };
 
+use crate::analysis::health::{AgentHealth, HealthThresholds};
+use crate::analysis::loops::LoopThresholds;
+use crate::analysis::metrics::{format_secs, format_tokens};
+use crate::budget::{self, BudgetScope};
+use crate::config::{Budget, BudgetLevel};
 use crate::data::state::DashboardState;
 use crate::data::tasks_parser::TaskStatus;
+use crate::ui::theme::Theme;
 
 /// Status bar at the bottom of the screen
 pub struct StatusBar<'a> {
     state: &'a DashboardState,
     start_time: Instant,
+    /// History load progress while the background loader is running
+    loading: Option<f32>,
+    /// Short notice such as the result of an undo
+    message: Option<&'a str>,
+    /// Follow-up reminders still waiting to come due
+    reminders: usize,
+    theme: Theme,
+    budget: Budget,
+    health: HealthThresholds,
+    loops: LoopThresholds,
 }
 
 impl<'a> StatusBar<'a> {
     pub fn new(state: &'a DashboardState, start_time: Instant) -> Self {
-        Self { state, start_time }
+        Self {
+            state,
+            start_time,
+            loading: None,
+            message: None,
+            reminders: 0,
+            theme: Theme::default(),
+            budget: Budget::default(),
+            health: HealthThresholds::default(),
+            loops: LoopThresholds::default(),
+        }
+    }
+
+    pub fn with_loops(mut self, loops: LoopThresholds) -> Self {
+        self.loops = loops;
+        self
+    }
+
+    pub fn with_health(mut self, health: HealthThresholds) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Background color for a budget level
+    fn budget_color(&self, level: BudgetLevel) -> Color {
+        match level {
+            BudgetLevel::Ok => self.theme.completed,
+            BudgetLevel::Warn => self.theme.in_progress,
+            BudgetLevel::Over => self.theme.failed,
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn with_loading(mut self, progress: Option<f32>) -> Self {
+        self.loading = progress;
+        self
+    }
+
+    pub fn with_message(mut self, message: Option<&'a str>) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn with_reminders(mut self, count: usize) -> Self {
+        self.reminders = count;
+        self
     }
 
     /// Count tasks by status across all phases
@@ -74,27 +147,197 @@ impl<'a> Widget for StatusBar<'a> {
                 counters,
                 Style::default()
                     .fg(Color::Black)
-                    .bg(Color::Green)
+                    .bg(self.theme.completed)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 progress,
-                Style::default().fg(Color::Black).bg(Color::Yellow),
+                Style::default().fg(Color::Black).bg(self.theme.in_progress),
             ),
             Span::styled(
                 uptime_str,
-                Style::default().fg(Color::Black).bg(Color::Cyan),
+                Style::default().fg(Color::Black).bg(self.theme.accent),
             ),
         ];
 
+        // Projected finish of the open tasks, recomputed every frame
+        let now = chrono::Utc::now();
+        let forecast = self.state.forecast(now);
+        if let (Some(remaining), Some(finish)) = (forecast.remaining_secs, forecast.finish_at) {
+            if forecast.open_tasks > 0 {
+                spans.push(Span::styled(
+                    format!(
+                        " \u{23F1} ~{} \u{2192} {} ",
+                        format_secs(remaining),
+                        finish.with_timezone(&chrono::Local).format("%H:%M")
+                    ),
+                    Style::default().fg(Color::Black).bg(self.theme.muted),
+                ));
+            }
+        }
+
+        // Running agents; silent ones are presumed finished by the app
+        let active = self.state.active_agents();
+        if active > 0 {
+            spans.push(Span::styled(
+                format!(" \u{25B6} {active} active "),
+                Style::default().fg(Color::Black).bg(self.theme.completed),
+            ));
+        }
+
+        // Unfinished tasks past their `due:` date
+        let overdue = self
+            .state
+            .overdue_tasks(now.with_timezone(&chrono::Local).date_naive());
+        if overdue > 0 {
+            spans.push(Span::styled(
+                format!(" \u{2691} {overdue} overdue "),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(self.theme.failed)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Agent errors nobody has acknowledged yet (muted agents left out)
+        let open_errors = self.state.total_open_errors(now);
+        if open_errors > 0 {
+            spans.push(Span::styled(
+                format!(" \u{26A0} {open_errors} errs "),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(self.theme.failed)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Agents whose recent error rate crossed a health threshold
+        let mut unhealthy: Vec<_> = self
+            .state
+            .agents
+            .values()
+            .map(|a| (self.health.assess(a), &a.agent_id))
+            .filter(|(health, _)| *health != AgentHealth::Healthy)
+            .collect();
+        unhealthy.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+        if let Some(&(worst, agent_id)) = unhealthy.first() {
+            let more = match unhealthy.len() {
+                1 => String::new(),
+                n => format!(" +{}", n - 1),
+            };
+            let color = match worst {
+                AgentHealth::Failing => self.theme.failed,
+                _ => self.theme.in_progress,
+            };
+            spans.push(Span::styled(
+                format!(" \u{2695} {agent_id} {}{more} ", worst.as_str()),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(color)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Agents repeating the same tool call, the longest loop named
+        let mut looping: Vec<_> = self
+            .state
+            .agents
+            .values()
+            .filter_map(|a| Some((self.loops.detect(a)?, &a.agent_id)))
+            .collect();
+        looping.sort_by(|a, b| b.0.count.cmp(&a.0.count).then(a.1.cmp(b.1)));
+        if let Some((tool_loop, agent_id)) = looping.first() {
+            let more = match looping.len() {
+                1 => String::new(),
+                n => format!(" +{}", n - 1),
+            };
+            spans.push(Span::styled(
+                format!(" \u{27F3} {agent_id} loop: {}{more} ", tool_loop.summary()),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(self.theme.in_progress)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Token / cost totals, shown once any usage has been reported
+        let tokens = self.state.total_tokens();
+        let cost = self.state.total_cost();
+        if tokens > 0 || cost > 0.0 {
+            spans.push(Span::styled(
+                format!(" \u{25B8} {} tok ", format_tokens(tokens)),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(self.budget_color(self.budget.token_level(tokens))),
+            ));
+            spans.push(Span::styled(
+                format!("/ ${cost:.2} "),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(self.budget_color(self.budget.cost_level(cost))),
+            ));
+        }
+
+        // Sessions and agents near or past their own budgets
+        let checks: Vec<_> = budget::checks(self.state, &self.budget)
+            .into_iter()
+            .filter(|c| c.scope != BudgetScope::Total && c.level != BudgetLevel::Ok)
+            .collect();
+        let worst = checks.iter().map(|c| c.level).max();
+        if let Some(level) = worst {
+            let mut scopes: Vec<String> = checks
+                .iter()
+                .filter(|c| c.level == level)
+                .map(|c| c.scope.to_string())
+                .collect();
+            scopes.dedup();
+            let more = match scopes.len() {
+                1 => String::new(),
+                n => format!(" +{}", n - 1),
+            };
+            let text = if level == BudgetLevel::Over {
+                format!(" \u{26D4} {} over budget{more} ", scopes[0])
+            } else {
+                format!(" ! {} near budget{more} ", scopes[0])
+            };
+            spans.push(Span::styled(
+                text,
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(self.budget_color(level))
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if let Some(p) = self.loading {
+            spans.push(Span::styled(
+                format!(" loading history\u{2026} {}% ", (p * 100.0) as u8),
+                Style::default().fg(Color::Black).bg(Color::Magenta),
+            ));
+        }
+
+        if self.reminders > 0 {
+            spans.push(Span::styled(
+                format!(" \u{23F2} {} ", self.reminders),
+                Style::default().fg(Color::Black).bg(self.theme.accent),
+            ));
+        }
+
+        if let Some(message) = self.message {
+            spans.push(Span::styled(
+                format!(" {message} "),
+                Style::default().fg(Color::Black).bg(self.theme.text),
+            ));
+        }
+
         // Fill remaining width with keybinding hints
-        let used_width: usize = spans.iter().map(|s| s.content.len()).sum();
+        let used_width: usize = spans.iter().map(|s| s.width()).sum();
         let remaining = (area.width as usize).saturating_sub(used_width);
         if remaining > hints.len() {
             let padding = remaining - hints.len();
             spans.push(Span::raw(" ".repeat(padding)));
         }
-        spans.push(Span::styled(hints, Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(hints, Style::default().fg(self.theme.muted)));
 
         let line = Line::from(spans);
         Widget::render(line, area, buf);
@@ -139,6 +382,153 @@ mod tests {
         assert_eq!(completed + in_progress + failed + rest, state.total_tasks);
     }
 
+    #[test]
+    fn statusbar_shows_loading_progress() {
+        let state = sample_state();
+        let bar = StatusBar::new(&state, Instant::now()).with_loading(Some(0.42));
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(text.contains("loading history"), "got: {text}");
+        assert!(text.contains("42%"));
+    }
+
+    #[test]
+    fn statusbar_shows_projected_finish() {
+        use crate::data::hook_parser::{EventType, HookEvent};
+
+        let mut state = sample_state();
+        let bar = StatusBar::new(&state, Instant::now());
+        let area = Rect::new(0, 0, 120, 1);
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(!text.contains('\u{23F1}'), "got: {text}");
+
+        // A finished task gives the open ones an average duration
+        let now = chrono::Utc::now();
+        let event = |event_type, minutes_ago| HookEvent {
+            event_type,
+            timestamp: now - chrono::Duration::minutes(minutes_ago),
+            agent_id: "agent-1".to_string(),
+            task_id: "P0-T0.1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: None,
+            error_message: None,
+            file_path: None,
+            args: None,
+            cwd: None,
+            usage: None,
+        };
+        state.update_from_events(&[
+            event(EventType::AgentStart, 60),
+            event(EventType::AgentEnd, 30),
+        ]);
+        let bar = StatusBar::new(&state, Instant::now());
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(text.contains("\u{23F1} ~"), "got: {text}");
+    }
+
+    #[test]
+    fn statusbar_forecasts_from_estimates_alone() {
+        let state =
+            DashboardState::from_tasks_content("# Phase 1: Core\n### [ ] T1: a\n- est: 1h30m\n")
+                .unwrap();
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(&state, Instant::now()).render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(text.contains("\u{23F1} ~1h30m"), "got: {text}");
+    }
+
+    #[test]
+    fn statusbar_counts_overdue_tasks() {
+        let state = DashboardState::from_tasks_content(
+            "# Phase 1: Core\n### [ ] T1: a\n- due: 2000-01-01\n### [x] T2: b\n- due: 2000-01-01\n### [ ] T3: c\n- due: 9999-12-31\n",
+        )
+        .unwrap();
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        StatusBar::new(&state, Instant::now()).render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(text.contains("\u{2691} 1 overdue"), "got: {text}");
+    }
+
+    fn state_with_usage(cost_usd: f64) -> DashboardState {
+        use crate::data::hook_parser::{EventType, HookEvent, TokenUsage};
+
+        let mut state = sample_state();
+        state.update_from_events(&[HookEvent {
+            event_type: EventType::ToolEnd,
+            timestamp: chrono::Utc::now(),
+            agent_id: "agent-1".to_string(),
+            task_id: "T-1".to_string(),
+            session_id: "sess-1".to_string(),
+            tool_name: Some("Read".to_string()),
+            error_message: None,
+            file_path: None,
+            args: None,
+            cwd: None,
+            usage: Some(TokenUsage {
+                input_tokens: 1_000_000,
+                output_tokens: 200_000,
+                cost_usd,
+            }),
+        }]);
+        state
+    }
+
+    #[test]
+    fn statusbar_hides_usage_without_tokens() {
+        let state = sample_state();
+        let bar = StatusBar::new(&state, Instant::now());
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(!text.contains("tok"));
+    }
+
+    #[test]
+    fn statusbar_shows_usage_with_budget_colors() {
+        let state = state_with_usage(4.3);
+        let budget = Budget {
+            tokens: Some(10_000_000),
+            cost_usd: Some(4.0),
+            ..Budget::default()
+        };
+        let bar = StatusBar::new(&state, Instant::now()).with_budget(budget);
+        let area = Rect::new(0, 0, 120, 1);
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(text.contains("1.2M tok"), "got: {text}");
+        assert!(text.contains("$4.30"), "got: {text}");
+
+        // Cell column of a match (count chars, not bytes)
+        let col = |needle: &str| text[..text.find(needle).unwrap()].chars().count() as u16;
+        let theme = Theme::default();
+        assert_eq!(buf[(col("1.2M"), 0)].bg, theme.completed);
+        assert_eq!(buf[(col("$4.30"), 0)].bg, theme.failed);
+        assert!(!text.contains("over budget"));
+
+        // Per-agent and per-session limits get a segment of their own
+        let budget = Budget {
+            agent_cost_usd: Some(4.0),
+            session_cost_usd: Some(5.0),
+            ..Budget::default()
+        };
+        let bar = StatusBar::new(&state, Instant::now()).with_budget(budget);
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(text.contains("agent agent-1 over budget "), "got: {text}");
+        assert!(!text.contains("near budget"), "got: {text}");
+    }
+
     #[test]
     fn format_uptime_zero() {
         let state = DashboardState::default();
@@ -146,4 +536,97 @@ mod tests {
         let uptime = bar.format_uptime();
         assert_eq!(uptime, "00:00:00");
     }
+
+    #[test]
+    fn statusbar_counts_only_open_errors() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        let mut state = sample_state();
+        state.update_from_events(&crate::data::hook_parser::parse_hook_events(input).events);
+        let area = Rect::new(0, 0, 100, 1);
+        let text = |state: &DashboardState| -> String {
+            let mut buf = Buffer::empty(area);
+            StatusBar::new(state, Instant::now()).render(area, &mut buf);
+            (0..area.width).map(|x| buf[(x, 0)].symbol()).collect()
+        };
+        assert!(
+            text(&state).contains("\u{26A0} 2 errs"),
+            "got: {}",
+            text(&state)
+        );
+
+        state.acknowledge_agent_errors("backend-specialist-2");
+        assert!(!text(&state).contains("errs"));
+    }
+
+    #[test]
+    fn statusbar_names_the_least_healthy_agent() {
+        let input = include_str!("../../tests/fixtures/sample_hooks/agent_events.jsonl");
+        let mut state = sample_state();
+        state.update_from_events(&crate::data::hook_parser::parse_hook_events(input).events);
+        let area = Rect::new(0, 0, 160, 1);
+        let text = |state: &DashboardState| -> String {
+            let mut buf = Buffer::empty(area);
+            StatusBar::new(state, Instant::now()).render(area, &mut buf);
+            (0..area.width).map(|x| buf[(x, 0)].symbol()).collect()
+        };
+        assert!(!text(&state).contains('\u{2695}'));
+
+        let mut second = state.agents["backend-specialist-1"].clone();
+        second.agent_id = "backend-specialist-2".to_string();
+        second.recent_outcomes = vec![true; 5];
+        state.agents.insert(second.agent_id.clone(), second);
+        let first = state.agents.get_mut("backend-specialist-1").unwrap();
+        first.recent_outcomes = vec![true, true, false, false, false];
+        // Failing before degraded
+        let text = text(&state);
+        assert!(
+            text.contains("\u{2695} backend-specialist-2 failing +1"),
+            "got: {text}"
+        );
+    }
+
+    #[test]
+    fn statusbar_names_an_agent_stuck_in_a_loop() {
+        let mut state = sample_state();
+        let lines: Vec<String> = (0..5)
+            .map(|i| {
+                format!(
+                    r#"{{"event_type":"tool_start","timestamp":"2026-02-08T10:00:{:02}Z","agent_id":"a","task_id":"T1","session_id":"s","tool_name":"Read","file_path":"src/app.rs"}}"#,
+                    i * 10
+                )
+            })
+            .collect();
+        state.update_from_events(
+            &crate::data::hook_parser::parse_hook_events(&lines.join("\n")).events,
+        );
+        let area = Rect::new(0, 0, 160, 1);
+        let text = |loops: LoopThresholds| -> String {
+            let mut buf = Buffer::empty(area);
+            StatusBar::new(&state, Instant::now())
+                .with_loops(loops)
+                .render(area, &mut buf);
+            (0..area.width).map(|x| buf[(x, 0)].symbol()).collect()
+        };
+        let shown = text(LoopThresholds::default());
+        assert!(
+            shown.contains("\u{27F3} a loop: Read src/app.rs \u{00D7} 5 in 40s"),
+            "got: {shown}"
+        );
+        let strict = LoopThresholds {
+            repeats: 6,
+            window_secs: 120,
+        };
+        assert!(!text(strict).contains('\u{27F3}'));
+    }
+
+    #[test]
+    fn statusbar_shows_message() {
+        let state = sample_state();
+        let bar = StatusBar::new(&state, Instant::now()).with_message(Some("Undid rename T1"));
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        bar.render(area, &mut buf);
+        let text: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+        assert!(text.contains(" Undid rename T1 "), "got: {text}");
+    }
 }
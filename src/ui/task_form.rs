@@ -0,0 +1,492 @@
+//! Add-task form
+//!
+//! Centered popup for appending a task to TASKS.md without leaving the
+//! dashboard. The ID is suggested from the chosen phase's last task and
+//! follows the phase until edited by hand.
+
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::data::state::DashboardState;
+use crate::data::tasks_parser::TaskStatus;
+use crate::data::tasks_writer::NewTask;
+use crate::ui::line_input::LineInput;
+use crate::ui::theme::Theme;
+
+/// Statuses offered by the form, with their TASKS.md tag
+const STATUSES: [(TaskStatus, &str, &str); 5] = [
+    (TaskStatus::Pending, " ", "Pending"),
+    (TaskStatus::InProgress, "InProgress", "In progress"),
+    (TaskStatus::Completed, "x", "Completed"),
+    (TaskStatus::Failed, "Failed", "Failed"),
+    (TaskStatus::Blocked, "Blocked", "Blocked"),
+];
+
+/// Columns for a text field's value: the popup's inner width less the label
+const INPUT_WIDTH: usize = 45;
+
+/// Field of the add-task form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormField {
+    Id,
+    #[default]
+    Name,
+    Phase,
+    Agent,
+    Status,
+}
+
+impl FormField {
+    const ORDER: [FormField; 5] = [Self::Id, Self::Name, Self::Phase, Self::Agent, Self::Status];
+
+    fn step(self, delta: isize) -> Self {
+        let pos = Self::ORDER.iter().position(|f| *f == self).unwrap_or(0) as isize;
+        let len = Self::ORDER.len() as isize;
+        Self::ORDER[(pos + delta).rem_euclid(len) as usize]
+    }
+}
+
+/// Result of a key press in the form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormOutcome {
+    Editing,
+    Cancel,
+    Submit,
+}
+
+/// Next free ID after `id`: its trailing number incremented (`P1-R1-T3` →
+/// `P1-R1-T4`, `P0-T0.1` → `P0-T0.2`), or `{id}-1` without one
+fn bump_id(id: &str) -> String {
+    let digits = id.len() - id.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let (head, tail) = id.split_at(id.len() - digits);
+    match tail.parse::<u64>() {
+        Ok(n) => format!("{head}{}", n + 1),
+        Err(_) => format!("{id}-1"),
+    }
+}
+
+/// Suggested ID for a new task at the end of phase `pi`
+pub fn suggest_task_id(state: &DashboardState, pi: usize) -> String {
+    let Some(phase) = state.phases.get(pi) else {
+        return String::new();
+    };
+    let mut id = match phase.tasks.last() {
+        Some(task) => bump_id(&task.id),
+        None => format!("{}-T1", phase.id),
+    };
+    while state.find_task(&id).is_some() {
+        id = bump_id(&id);
+    }
+    id
+}
+
+/// Field values and focus of the add-task form
+#[derive(Debug, Clone, Default)]
+pub struct TaskFormState {
+    pub id: LineInput,
+    pub name: LineInput,
+    /// Index into the dashboard's phases
+    pub phase: usize,
+    pub agent: LineInput,
+    /// Index into the offered statuses
+    pub status: usize,
+    pub field: FormField,
+    /// Validation or write error shown in the footer
+    pub error: Option<String>,
+    /// `(id, name)` of each phase
+    phases: Vec<(String, String)>,
+    /// Suggested ID per phase
+    suggestions: Vec<String>,
+    existing_ids: HashSet<String>,
+    /// The ID was typed by hand and no longer follows the phase
+    id_edited: bool,
+}
+
+impl TaskFormState {
+    /// Open the form on phase `phase`; `None` when there are no phases
+    pub fn new(state: &DashboardState, phase: usize) -> Option<Self> {
+        if state.phases.is_empty() {
+            return None;
+        }
+        let phase = phase.min(state.phases.len() - 1);
+        let suggestions: Vec<String> = (0..state.phases.len())
+            .map(|pi| suggest_task_id(state, pi))
+            .collect();
+        Some(Self {
+            id: LineInput::new(&suggestions[phase]),
+            phase,
+            phases: state
+                .phases
+                .iter()
+                .map(|p| (p.id.clone(), p.name.clone()))
+                .collect(),
+            suggestions,
+            existing_ids: state
+                .phases
+                .iter()
+                .flat_map(|p| p.tasks.iter().map(|t| t.id.clone()))
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    pub fn status(&self) -> &TaskStatus {
+        &STATUSES[self.status].0
+    }
+
+    fn cycle(&mut self, delta: isize) {
+        match self.field {
+            FormField::Phase => {
+                let len = self.phases.len() as isize;
+                self.phase = (self.phase as isize + delta).rem_euclid(len) as usize;
+                if !self.id_edited {
+                    self.id.set_text(&self.suggestions[self.phase]);
+                }
+            }
+            FormField::Status => {
+                let len = STATUSES.len() as isize;
+                self.status = (self.status as isize + delta).rem_euclid(len) as usize;
+            }
+            _ => {}
+        }
+    }
+
+    fn input_mut(&mut self) -> Option<&mut LineInput> {
+        match self.field {
+            FormField::Id => Some(&mut self.id),
+            FormField::Name => Some(&mut self.name),
+            FormField::Agent => Some(&mut self.agent),
+            FormField::Phase | FormField::Status => None,
+        }
+    }
+
+    /// Check the fields before writing
+    pub fn validate(&self) -> Result<(), String> {
+        let id = self.id.text().trim();
+        if id.is_empty() || id.contains(char::is_whitespace) || id.contains(':') {
+            return Err("ID must be non-empty, without spaces or `:`".to_string());
+        }
+        if self.existing_ids.contains(id) {
+            return Err(format!("ID `{id}` already exists"));
+        }
+        if self.name.text().trim().is_empty() {
+            return Err("Name is required".to_string());
+        }
+        if self.agent.text().trim().contains(char::is_whitespace) {
+            return Err("Agent name cannot contain spaces".to_string());
+        }
+        Ok(())
+    }
+
+    /// The task to append, from the current field values
+    pub fn new_task(&self) -> NewTask<'_> {
+        let agent = self.agent.text().trim().trim_start_matches('@');
+        NewTask {
+            phase_id: &self.phases[self.phase].0,
+            task_id: self.id.text().trim(),
+            name: self.name.text().trim(),
+            status: STATUSES[self.status].1,
+            agent: (!agent.is_empty()).then_some(agent),
+            spec: None,
+        }
+    }
+
+    /// Handle a key: Tab/Up/Down move between fields, Left/Right change the
+    /// phase or status and move the cursor in text fields, Enter submits
+    /// once the fields are valid
+    pub fn handle_key(&mut self, key: KeyEvent) -> FormOutcome {
+        match key.code {
+            KeyCode::Esc => return FormOutcome::Cancel,
+            KeyCode::Enter => match self.validate() {
+                Ok(()) => return FormOutcome::Submit,
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Tab | KeyCode::Down => self.field = self.field.step(1),
+            KeyCode::BackTab | KeyCode::Up => self.field = self.field.step(-1),
+            _ => match self.input_mut() {
+                Some(input) => {
+                    let before = input.text().to_string();
+                    input.handle_key(key);
+                    if self.field == FormField::Id && self.id.text() != before {
+                        self.id_edited = true;
+                    }
+                }
+                None => match key.code {
+                    KeyCode::Left => self.cycle(-1),
+                    KeyCode::Right => self.cycle(1),
+                    _ => {}
+                },
+            },
+        }
+        FormOutcome::Editing
+    }
+}
+
+/// Add-task popup widget
+pub struct TaskForm {
+    theme: Theme,
+}
+
+impl TaskForm {
+    pub fn new() -> Self {
+        Self {
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn centered_rect(area: Rect) -> Rect {
+        let width = 56.min(area.width.saturating_sub(4));
+        let height = 11.min(area.height.saturating_sub(4));
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(x, y, width, height)
+    }
+
+    fn field_line(
+        &self,
+        state: &TaskFormState,
+        field: FormField,
+        label: &str,
+        value: Vec<Span<'static>>,
+    ) -> Line<'static> {
+        let label_style = if state.field == field {
+            Style::default()
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.theme.muted)
+        };
+        let mut spans = vec![Span::styled(format!(" {label:<8}"), label_style)];
+        spans.extend(value);
+        Line::from(spans)
+    }
+
+    /// A text field, with the cursor drawn while focused
+    fn input_line(
+        &self,
+        state: &TaskFormState,
+        field: FormField,
+        label: &str,
+        input: &LineInput,
+    ) -> Line<'static> {
+        let style = Style::default().fg(self.theme.text);
+        let value = if state.field == field {
+            input.spans(INPUT_WIDTH, style)
+        } else {
+            vec![Span::styled(input.text().to_string(), style)]
+        };
+        self.field_line(state, field, label, value)
+    }
+
+    /// A field changed with Left/Right, marked ◀▶ while focused
+    fn choice_line(
+        &self,
+        state: &TaskFormState,
+        field: FormField,
+        label: &str,
+        value: String,
+    ) -> Line<'static> {
+        let mut spans = vec![Span::styled(value, Style::default().fg(self.theme.text))];
+        if state.field == field {
+            spans.push(Span::styled(
+                " \u{25C0}\u{25B6}",
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        self.field_line(state, field, label, spans)
+    }
+
+    fn build_lines(&self, state: &TaskFormState) -> Vec<Line<'static>> {
+        let (phase_id, phase_name) = state.phases.get(state.phase).cloned().unwrap_or_default();
+        let status = STATUSES[state.status].2;
+        let mut lines = vec![
+            self.input_line(state, FormField::Id, "ID", &state.id),
+            self.input_line(state, FormField::Name, "Name", &state.name),
+            self.choice_line(
+                state,
+                FormField::Phase,
+                "Phase",
+                format!("{phase_id} {phase_name}"),
+            ),
+            self.input_line(state, FormField::Agent, "Agent", &state.agent),
+            self.choice_line(state, FormField::Status, "Status", status.to_string()),
+            Line::raw(""),
+        ];
+        lines.push(match state.error {
+            Some(ref e) => Line::styled(format!(" {e}"), Style::default().fg(self.theme.failed)),
+            None => Line::raw(""),
+        });
+        lines.push(Line::styled(
+            " Tab next  \u{2190}/\u{2192} change  Enter add  Esc cancel",
+            Style::default().fg(self.theme.muted),
+        ));
+        lines
+    }
+}
+
+impl Default for TaskForm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StatefulWidget for TaskForm {
+    type State = TaskFormState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let popup_area = Self::centered_rect(area);
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(" Add task ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.accent));
+        Paragraph::new(self.build_lines(state))
+            .block(block)
+            .render(popup_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn sample_state() -> DashboardState {
+        let input = include_str!("../../tests/fixtures/sample_tasks.md");
+        DashboardState::from_tasks_content(input).unwrap()
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(form: &mut TaskFormState, text: &str) {
+        for c in text.chars() {
+            form.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn bump_id_increments_trailing_number() {
+        assert_eq!(bump_id("P1-R3-T1"), "P1-R3-T2");
+        assert_eq!(bump_id("P0-T0.9"), "P0-T0.10");
+        assert_eq!(bump_id("setup"), "setup-1");
+    }
+
+    #[test]
+    fn suggestion_follows_phase_until_edited() {
+        let state = sample_state();
+        let mut form = TaskFormState::new(&state, 1).unwrap();
+        assert_eq!(form.id.text(), "P1-R3-T2");
+
+        form.field = FormField::Phase;
+        form.handle_key(key(KeyCode::Right));
+        assert_eq!(form.phase, 2);
+        assert_eq!(form.id.text(), suggest_task_id(&state, 2));
+
+        form.field = FormField::Id;
+        form.handle_key(key(KeyCode::Backspace));
+        type_text(&mut form, "9");
+        form.field = FormField::Phase;
+        form.handle_key(key(KeyCode::Left));
+        assert_eq!(form.phase, 1);
+        assert!(form.id.text().ends_with('9'));
+    }
+
+    #[test]
+    fn arrows_move_the_cursor_in_text_fields() {
+        let state = sample_state();
+        let mut form = TaskFormState::new(&state, 1).unwrap();
+        type_text(&mut form, "Pasrer");
+        for _ in 0..4 {
+            form.handle_key(key(KeyCode::Left));
+        }
+        form.handle_key(key(KeyCode::Delete));
+        form.handle_key(key(KeyCode::Right));
+        type_text(&mut form, "s");
+        assert_eq!(form.name.text(), "Parser");
+
+        // Moving through the ID leaves it following the phase
+        form.field = FormField::Id;
+        form.handle_key(key(KeyCode::Home));
+        form.field = FormField::Phase;
+        form.handle_key(key(KeyCode::Right));
+        assert_eq!(form.id.text(), suggest_task_id(&state, 2));
+    }
+
+    #[test]
+    fn submit_requires_name_and_unique_id() {
+        let state = sample_state();
+        let mut form = TaskFormState::new(&state, 0).unwrap();
+        assert_eq!(form.handle_key(key(KeyCode::Enter)), FormOutcome::Editing);
+        assert!(form.error.as_deref().unwrap().contains("Name"));
+
+        type_text(&mut form, "Docs");
+        form.id.set_text("P0-T0.1");
+        assert_eq!(form.handle_key(key(KeyCode::Enter)), FormOutcome::Editing);
+        assert!(form.error.as_deref().unwrap().contains("already exists"));
+
+        form.id.set_text("P0-T0.3");
+        assert_eq!(form.handle_key(key(KeyCode::Enter)), FormOutcome::Submit);
+        assert_eq!(form.handle_key(key(KeyCode::Esc)), FormOutcome::Cancel);
+    }
+
+    #[test]
+    fn new_task_uses_status_tag_and_strips_at() {
+        let state = sample_state();
+        let mut form = TaskFormState::new(&state, 2).unwrap();
+        type_text(&mut form, "Write docs");
+        form.handle_key(key(KeyCode::Tab));
+        form.handle_key(key(KeyCode::Tab));
+        type_text(&mut form, "@writer");
+        form.handle_key(key(KeyCode::Tab));
+        form.handle_key(key(KeyCode::Right));
+        assert_eq!(form.status(), &TaskStatus::InProgress);
+
+        let task = form.new_task();
+        assert_eq!(task.phase_id, "P2");
+        assert_eq!(task.name, "Write docs");
+        assert_eq!(task.agent, Some("writer"));
+        assert_eq!(task.status, "InProgress");
+    }
+
+    #[test]
+    fn form_renders_fields_and_error() {
+        let state = sample_state();
+        let mut form = TaskFormState::new(&state, 1).unwrap();
+        form.error = Some("Name is required".to_string());
+        let area = Rect::new(0, 0, 80, 20);
+        let mut buf = Buffer::empty(area);
+        TaskForm::new().render(area, &mut buf, &mut form);
+        let text: String = (0..area.height)
+            .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+            .map(|(x, y)| buf[(x, y)].symbol().to_string())
+            .collect();
+        assert!(text.contains("Add task"));
+        assert!(text.contains("P1-R3-T2"));
+        assert!(text.contains("P1 Data Engine"));
+        assert!(text.contains("Name is required"));
+
+        let tiny = Rect::new(0, 0, 6, 4);
+        TaskForm::new().render(tiny, &mut Buffer::empty(tiny), &mut form);
+    }
+
+    #[test]
+    fn no_phases_no_form() {
+        assert!(TaskFormState::new(&DashboardState::default(), 0).is_none());
+    }
+}
@@ -0,0 +1,234 @@
+//! Color themes
+//!
+//! Built-in presets: `default`, `high-contrast` and `deuteranopia`. The
+//! accessible presets also switch on status shapes, so statuses never rely on
+//! a green/red distinction alone.
+
+use std::str::FromStr;
+
+use ratatui::style::Color;
+
+use crate::analysis::rules::ErrorCategory;
+use crate::data::state::AgentStatus;
+use crate::data::tasks_parser::TaskStatus;
+
+/// Built-in theme presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeName {
+    #[default]
+    Default,
+    HighContrast,
+    Deuteranopia,
+}
+
+impl FromStr for ThemeName {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "default" => Ok(ThemeName::Default),
+            "high-contrast" => Ok(ThemeName::HighContrast),
+            "deuteranopia" | "colorblind" => Ok(ThemeName::Deuteranopia),
+            other => Err(format!(
+                "unknown theme `{other}` (expected default, high-contrast or deuteranopia)"
+            )),
+        }
+    }
+}
+
+/// Semantic colors used by the widgets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub completed: Color,
+    pub in_progress: Color,
+    pub pending: Color,
+    pub failed: Color,
+    pub blocked: Color,
+    /// Network errors, which are transient and usually retryable
+    pub network: Color,
+    /// Headings, phase ids and focused borders
+    pub accent: Color,
+    /// Primary text
+    pub text: Color,
+    /// Labels, hints and unfocused borders
+    pub muted: Color,
+    /// Draw status-specific bar fills so statuses differ by shape as well as color
+    pub status_shapes: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::preset(ThemeName::Default)
+    }
+}
+
+impl Theme {
+    pub fn preset(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Default => Theme {
+                completed: Color::Green,
+                in_progress: Color::Yellow,
+                pending: Color::DarkGray,
+                failed: Color::Red,
+                blocked: Color::Magenta,
+                network: Color::Blue,
+                accent: Color::Cyan,
+                text: Color::White,
+                muted: Color::DarkGray,
+                status_shapes: false,
+            },
+            ThemeName::HighContrast => Theme {
+                completed: Color::LightGreen,
+                in_progress: Color::LightYellow,
+                pending: Color::Gray,
+                failed: Color::LightRed,
+                blocked: Color::LightMagenta,
+                network: Color::LightBlue,
+                accent: Color::LightCyan,
+                text: Color::White,
+                muted: Color::Gray,
+                status_shapes: true,
+            },
+            // Okabe-Ito palette: blue/orange instead of green/red
+            ThemeName::Deuteranopia => Theme {
+                completed: Color::Rgb(0, 114, 178),
+                in_progress: Color::Rgb(240, 228, 66),
+                pending: Color::Gray,
+                failed: Color::Rgb(213, 94, 0),
+                blocked: Color::Rgb(204, 121, 167),
+                network: Color::Rgb(86, 180, 233),
+                accent: Color::Rgb(86, 180, 233),
+                text: Color::White,
+                muted: Color::Gray,
+                status_shapes: true,
+            },
+        }
+    }
+
+    /// Color for a task status
+    pub fn task_status(&self, status: &TaskStatus) -> Color {
+        match status {
+            TaskStatus::Completed => self.completed,
+            TaskStatus::InProgress => self.in_progress,
+            TaskStatus::Pending => self.pending,
+            TaskStatus::Failed => self.failed,
+            TaskStatus::Blocked => self.blocked,
+        }
+    }
+
+    /// Color for an agent status
+    pub fn agent_status(&self, status: &AgentStatus) -> Color {
+        match status {
+            AgentStatus::Running => self.completed,
+            AgentStatus::Idle => self.muted,
+            AgentStatus::Error => self.failed,
+        }
+    }
+
+    /// Color for an error, by analysis category
+    pub fn error_category(&self, category: &ErrorCategory) -> Color {
+        match category {
+            ErrorCategory::Network => self.network,
+            ErrorCategory::Permission => self.blocked,
+            ErrorCategory::Type => self.in_progress,
+            ErrorCategory::Runtime | ErrorCategory::Unknown => self.failed,
+        }
+    }
+
+    /// Fill character for a task bar in the horizontal bar view
+    pub fn bar_char(&self, status: &TaskStatus) -> char {
+        if !self.status_shapes {
+            return match status {
+                TaskStatus::Completed | TaskStatus::InProgress => '\u{2588}',
+                _ => '\u{2591}',
+            };
+        }
+        match status {
+            TaskStatus::Completed => '\u{2588}',  // █
+            TaskStatus::InProgress => '\u{2593}', // ▓
+            TaskStatus::Pending => '\u{2591}',    // ░
+            TaskStatus::Failed => '\u{2573}',     // ╳
+            TaskStatus::Blocked => '\u{2592}',    // ▒
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_theme_names() {
+        assert_eq!("default".parse::<ThemeName>().unwrap(), ThemeName::Default);
+        assert_eq!(
+            "high_contrast".parse::<ThemeName>().unwrap(),
+            ThemeName::HighContrast
+        );
+        assert_eq!(
+            "Deuteranopia".parse::<ThemeName>().unwrap(),
+            ThemeName::Deuteranopia
+        );
+        assert!("neon".parse::<ThemeName>().is_err());
+    }
+
+    #[test]
+    fn default_theme_keeps_original_colors() {
+        let theme = Theme::default();
+        assert_eq!(theme.task_status(&TaskStatus::Completed), Color::Green);
+        assert_eq!(theme.task_status(&TaskStatus::Failed), Color::Red);
+        assert_eq!(theme.agent_status(&AgentStatus::Idle), Color::DarkGray);
+        assert!(!theme.status_shapes);
+    }
+
+    #[test]
+    fn error_categories_have_distinct_colors() {
+        for name in [
+            ThemeName::Default,
+            ThemeName::HighContrast,
+            ThemeName::Deuteranopia,
+        ] {
+            let theme = Theme::preset(name);
+            let colors: Vec<Color> = [
+                ErrorCategory::Network,
+                ErrorCategory::Permission,
+                ErrorCategory::Type,
+                ErrorCategory::Runtime,
+            ]
+            .iter()
+            .map(|c| theme.error_category(c))
+            .collect();
+            for (i, a) in colors.iter().enumerate() {
+                assert!(!colors[i + 1..].contains(a), "{name:?}: {colors:?}");
+            }
+        }
+        let theme = Theme::default();
+        assert_eq!(theme.error_category(&ErrorCategory::Network), Color::Blue);
+        assert_eq!(theme.error_category(&ErrorCategory::Runtime), Color::Red);
+    }
+
+    #[test]
+    fn deuteranopia_avoids_green_and_red() {
+        let theme = Theme::preset(ThemeName::Deuteranopia);
+        for color in [theme.completed, theme.failed] {
+            assert!(!matches!(
+                color,
+                Color::Green | Color::Red | Color::LightGreen | Color::LightRed
+            ));
+        }
+    }
+
+    #[test]
+    fn accessible_presets_distinguish_by_shape() {
+        for name in [ThemeName::HighContrast, ThemeName::Deuteranopia] {
+            let theme = Theme::preset(name);
+            assert_ne!(
+                theme.bar_char(&TaskStatus::Completed),
+                theme.bar_char(&TaskStatus::Failed)
+            );
+            assert_ne!(
+                theme.bar_char(&TaskStatus::Pending),
+                theme.bar_char(&TaskStatus::Blocked)
+            );
+        }
+    }
+}
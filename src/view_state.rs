@@ -0,0 +1,127 @@
+//! Dashboard view saved across launches (`views.json`)
+//!
+//! On quit the dashboard stores where the user was: the selected task and
+//! agent, collapsed phases, the session filter, layout and view mode, the
+//! focused pane, and which errors were acknowledged, dismissed or muted. The
+//! next launch for the same TASKS.md puts it back, so reopening the board
+//! mid-run keeps the working context. Every project's view lives in one file
+//! in the events directory, keyed by the canonical TASKS.md path.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::data::versioned_file::VersionedFile;
+use crate::ui::gantt::GanttViewMode;
+use crate::ui::layout::{FocusedPane, LayoutMode};
+
+/// File name of the saved views, inside the events directory
+pub const VIEWS_FILE: &str = "views.json";
+
+const FILE: VersionedFile = VersionedFile::new(VIEWS_FILE, 1);
+
+/// Projects remembered; the least recently saved are dropped beyond this
+const MAX_PROJECTS: usize = 50;
+
+/// Whether `path` is the views file or its partial copy (not an event file)
+pub fn is_views_file(path: &Path) -> bool {
+    FILE.matches(path)
+}
+
+/// What the dashboard showed when it was last closed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewState {
+    pub saved_at: Option<DateTime<Utc>>,
+    /// Selected task ID, or phase ID when a phase header was selected
+    pub selected: Option<String>,
+    /// IDs of the collapsed phases
+    pub collapsed: Vec<String>,
+    pub view_mode: GanttViewMode,
+    pub layout_mode: LayoutMode,
+    pub show_gauge: bool,
+    pub focused: FocusedPane,
+    pub selected_agent: Option<String>,
+    pub session_filter: Option<String>,
+    pub acknowledged_errors: HashMap<String, DateTime<Utc>>,
+    pub dismissed_errors: HashMap<String, DateTime<Utc>>,
+    pub muted_agents: HashMap<String, DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ViewsFile {
+    projects: HashMap<String, ViewState>,
+}
+
+/// Key of the project whose TASKS.md is at `tasks_path`
+pub fn project_key(tasks_path: &Path) -> String {
+    tasks_path
+        .canonicalize()
+        .unwrap_or_else(|_| tasks_path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn read_all(dir: &Path) -> HashMap<String, ViewState> {
+    FILE.read::<ViewsFile>(dir).unwrap_or_default().projects
+}
+
+/// The view saved in `dir` for `project`, if any
+pub fn read(dir: &Path, project: &str) -> Option<ViewState> {
+    read_all(dir).remove(project)
+}
+
+/// Store `view` for `project` in `dir`, keeping the other projects' views,
+/// in one step so readers never see a half-written file
+pub fn write(dir: &Path, project: &str, view: ViewState) -> std::io::Result<()> {
+    let mut projects = read_all(dir);
+    projects.insert(project.to_string(), view);
+    if projects.len() > MAX_PROJECTS {
+        let mut by_age: Vec<(Option<DateTime<Utc>>, String)> = projects
+            .iter()
+            .map(|(key, view)| (view.saved_at, key.clone()))
+            .collect();
+        by_age.sort();
+        for (_, key) in by_age.into_iter().take(projects.len() - MAX_PROJECTS) {
+            projects.remove(&key);
+        }
+    }
+    FILE.write(dir, &ViewsFile { projects })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn views_are_kept_per_project() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let view = ViewState {
+            selected: Some("P1-T2".to_string()),
+            collapsed: vec!["P2".to_string()],
+            layout_mode: LayoutMode::Stacked,
+            focused: FocusedPane::Agents,
+            ..ViewState::default()
+        };
+        write(tmp.path(), "/a/TASKS.md", view.clone()).unwrap();
+        write(tmp.path(), "/b/TASKS.md", ViewState::default()).unwrap();
+
+        assert_eq!(read(tmp.path(), "/a/TASKS.md"), Some(view));
+        assert_eq!(read(tmp.path(), "/b/TASKS.md"), Some(ViewState::default()));
+        assert_eq!(read(tmp.path(), "/c/TASKS.md"), None);
+        assert!(is_views_file(&tmp.path().join(VIEWS_FILE)));
+
+        // Only the most recently saved projects are remembered
+        for i in 0..MAX_PROJECTS {
+            let view = ViewState {
+                saved_at: Some(Utc::now()),
+                ..ViewState::default()
+            };
+            write(tmp.path(), &format!("/p{i}/TASKS.md"), view).unwrap();
+        }
+        assert_eq!(read_all(tmp.path()).len(), MAX_PROJECTS);
+        assert_eq!(read(tmp.path(), "/a/TASKS.md"), None);
+    }
+}
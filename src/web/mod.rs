@@ -0,0 +1,62 @@
+//! Read-only web mirror of the dashboard (`serve` mode)
+//!
+//! The same ingestion sources that drive the TUI update an [`App`]; after
+//! every update a fresh [`snapshot::Snapshot`] is published to the WebSocket
+//! clients of [`server::serve`].
+
+pub mod server;
+pub mod snapshot;
+
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, watch};
+
+use crate::app::App;
+use crate::data::source::Update;
+use snapshot::Snapshot;
+
+/// Apply updates from the ingestion sources to `app`, publishing a snapshot
+/// to `snapshots` after each one. Runs until every source is gone.
+pub async fn mirror(
+    mut app: App,
+    mut updates: mpsc::UnboundedReceiver<Update>,
+    snapshots: watch::Sender<Arc<Snapshot>>,
+) {
+    snapshots.send_replace(Arc::new(Snapshot::from_state(&app.dashboard)));
+    while let Some(update) = updates.recv().await {
+        if let Update::Lost(name) = update {
+            eprintln!("The {name} source stopped; the dashboard will not update from it");
+        }
+        app.handle_update(update);
+        snapshots.send_replace(Arc::new(Snapshot::from_state(&app.dashboard)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::watcher::FileChange;
+
+    #[tokio::test]
+    async fn mirror_publishes_after_each_change() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tasks = tmp.path().join("TASKS.md");
+        std::fs::write(&tasks, "# Phase 1: A\n\n### [ ] P1-T1: One\n").unwrap();
+
+        let (change_tx, change_rx) = mpsc::unbounded_channel();
+        let (tx, mut rx) = watch::channel(Arc::default());
+        let task = tokio::spawn(mirror(App::new(), change_rx, tx));
+
+        rx.changed().await.unwrap();
+        assert_eq!(rx.borrow_and_update().total_tasks, 0);
+
+        change_tx
+            .send(Update::File(FileChange::TasksModified(tasks)))
+            .unwrap();
+        rx.changed().await.unwrap();
+        assert_eq!(rx.borrow_and_update().phases[0].tasks[0].id, "P1-T1");
+
+        drop(change_tx);
+        task.await.unwrap();
+    }
+}
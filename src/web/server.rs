@@ -0,0 +1,565 @@
+//! Minimal HTTP + WebSocket server for the web mirror
+//!
+//! Serves the bundled page at `/`, the current snapshot at `/state.json`,
+//! its parts under `/api/` (`tasks`, `agents`, `errors`, `events?since=`),
+//! and pushes every new snapshot to clients connected to `/ws` as a text
+//! frame. Clients cannot send anything back; their frames are read only to
+//! answer pings and notice when they go away.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+
+use super::snapshot::Snapshot;
+
+/// Single-page dashboard served at `/`
+const INDEX_HTML: &str = include_str!("index.html");
+
+/// Appended to the client's key in the handshake (RFC 6455)
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Requests with a larger head are dropped
+const MAX_HEAD_BYTES: usize = 8 * 1024;
+
+/// Clients sending a larger frame are disconnected
+const MAX_CLIENT_FRAME_BYTES: u64 = 64 * 1024;
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// Accept connections on `listener` until the task is dropped. `snapshots`
+/// holds the latest dashboard snapshot.
+pub async fn serve(
+    listener: TcpListener,
+    snapshots: watch::Receiver<Arc<Snapshot>>,
+) -> std::io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let snapshots = snapshots.clone();
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, snapshots).await;
+        });
+    }
+}
+
+/// The parts of a request head the server looks at
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    /// Everything after `?`, still percent-encoded
+    query: Option<String>,
+    /// `Sec-WebSocket-Key`, present on upgrade requests
+    websocket_key: Option<String>,
+}
+
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.split("\r\n");
+    let mut parts = lines.next()?.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+    let websocket_key = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("sec-websocket-key")
+            .then(|| value.trim().to_string())
+    });
+    Some(Request {
+        method,
+        path,
+        query,
+        websocket_key,
+    })
+}
+
+/// Decoded value of `name` in a query string
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        (key == name).then(|| percent_decode(value))
+    })
+}
+
+/// Decode `%XX` escapes and `+` as a space
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (hex, bytes[i]) {
+            (Some(byte), _) => {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+            (None, b'+') => out.push(b' '),
+            (None, byte) => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "null".to_string())
+}
+
+/// Status and JSON body for an `/api/` path, `None` for unknown paths
+fn api_response(
+    path: &str,
+    query: Option<&str>,
+    snapshot: &Snapshot,
+) -> Option<(&'static str, String)> {
+    let body = match path {
+        "/api/tasks" => json(&snapshot.phases),
+        "/api/agents" => json(&snapshot.agents),
+        "/api/errors" => json(&snapshot.errors),
+        "/api/events" => {
+            let since = query.and_then(|q| query_param(q, "since"));
+            let since = match since.as_deref().filter(|s| !s.is_empty()) {
+                None => None,
+                Some(s) => match DateTime::parse_from_rfc3339(s) {
+                    Ok(t) => Some(t.with_timezone(&Utc)),
+                    Err(_) => {
+                        let error = serde_json::json!({
+                            "error": format!("since must be an RFC 3339 timestamp, got {s:?}")
+                        });
+                        return Some(("400 Bad Request", error.to_string()));
+                    }
+                },
+            };
+            json(&snapshot.events_since(since))
+        }
+        _ => return None,
+    };
+    Some(("200 OK", body))
+}
+
+/// Read up to the blank line ending the request head
+async fn read_head(stream: &mut (impl AsyncRead + Unpin)) -> std::io::Result<Option<String>> {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_HEAD_BYTES || stream.read(&mut byte).await? == 0 {
+            return Ok(None);
+        }
+        head.push(byte[0]);
+    }
+    Ok(Some(String::from_utf8_lossy(&head).into_owned()))
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    snapshots: watch::Receiver<Arc<Snapshot>>,
+) -> std::io::Result<()> {
+    let Some(request) = read_head(&mut stream)
+        .await?
+        .as_deref()
+        .and_then(parse_request)
+    else {
+        return Ok(());
+    };
+    if request.method != "GET" {
+        return respond(&mut stream, "405 Method Not Allowed", "text/plain", "").await;
+    }
+    match (request.path.as_str(), request.websocket_key) {
+        ("/ws", Some(key)) => push_snapshots(stream, &key, snapshots).await,
+        ("/" | "/index.html", _) => {
+            respond(
+                &mut stream,
+                "200 OK",
+                "text/html; charset=utf-8",
+                INDEX_HTML,
+            )
+            .await
+        }
+        ("/state.json", _) => {
+            let body = snapshots.borrow().to_json();
+            respond(&mut stream, "200 OK", "application/json", &body).await
+        }
+        (path, _) => {
+            let snapshot = Arc::clone(&snapshots.borrow());
+            match api_response(path, request.query.as_deref(), &snapshot) {
+                Some((status, body)) => {
+                    respond(&mut stream, status, "application/json", &body).await
+                }
+                None => respond(&mut stream, "404 Not Found", "text/plain", "not found").await,
+            }
+        }
+    }
+}
+
+async fn respond(
+    stream: &mut (impl AsyncWrite + Unpin),
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Complete the WebSocket handshake, then send the current snapshot and each
+/// new one until the client disconnects
+async fn push_snapshots(
+    stream: TcpStream,
+    key: &str,
+    mut snapshots: watch::Receiver<Arc<Snapshot>>,
+) -> std::io::Result<()> {
+    let (mut reader, mut writer) = stream.into_split();
+    let head = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(key)
+    );
+    writer.write_all(head.as_bytes()).await?;
+
+    let json = snapshots.borrow_and_update().to_json();
+    writer.write_all(&text_frame(&json)).await?;
+    // Bytes of client frames not complete yet
+    let mut incoming = Vec::new();
+    let mut chunk = [0u8; 512];
+    'connection: loop {
+        tokio::select! {
+            changed = snapshots.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                let json = snapshots.borrow_and_update().to_json();
+                writer.write_all(&text_frame(&json)).await?;
+            }
+            // Pings are answered and other frames ignored; EOF, a close
+            // frame or a malformed one ends it
+            read = reader.read(&mut chunk) => {
+                match read {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => incoming.extend_from_slice(&chunk[..n]),
+                }
+                loop {
+                    match parse_client_frame(&incoming) {
+                        Ok(Some((frame, used))) => {
+                            incoming.drain(..used);
+                            match frame.opcode {
+                                OPCODE_CLOSE => break 'connection,
+                                OPCODE_PING => {
+                                    writer.write_all(&frame_bytes(OPCODE_PONG, &frame.payload)).await?;
+                                }
+                                _ => {}
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(()) => break 'connection,
+                    }
+                }
+            }
+        }
+    }
+    let _ = writer.write_all(&frame_bytes(OPCODE_CLOSE, &[])).await;
+    Ok(())
+}
+
+/// A frame sent by a client, unmasked
+#[derive(Debug, PartialEq, Eq)]
+struct ClientFrame {
+    opcode: u8,
+    payload: Vec<u8>,
+}
+
+/// The first frame in `buf` and the bytes it takes up; `None` until it is
+/// complete, an error when it is larger than the server accepts
+fn parse_client_frame(buf: &[u8]) -> Result<Option<(ClientFrame, usize)>, ()> {
+    let [first, second, ..] = *buf else {
+        return Ok(None);
+    };
+    let opcode = first & 0x0F;
+    let masked = second & 0x80 != 0;
+    let (len, mut pos) = match second & 0x7F {
+        126 => match buf.get(2..4) {
+            Some(b) => (u16::from_be_bytes([b[0], b[1]]) as u64, 4),
+            None => return Ok(None),
+        },
+        127 => match buf.get(2..10) {
+            Some(b) => (u64::from_be_bytes(b.try_into().expect("8 bytes")), 10),
+            None => return Ok(None),
+        },
+        len => (len as u64, 2),
+    };
+    if len > MAX_CLIENT_FRAME_BYTES {
+        return Err(());
+    }
+    let mask = if masked {
+        let Some(mask) = buf.get(pos..pos + 4) else {
+            return Ok(None);
+        };
+        pos += 4;
+        Some([mask[0], mask[1], mask[2], mask[3]])
+    } else {
+        None
+    };
+    let end = pos + len as usize;
+    let Some(payload) = buf.get(pos..end) else {
+        return Ok(None);
+    };
+    let payload = payload
+        .iter()
+        .enumerate()
+        .map(|(i, b)| mask.map_or(*b, |m| b ^ m[i % 4]))
+        .collect();
+    Ok(Some((ClientFrame { opcode, payload }, end)))
+}
+
+/// `Sec-WebSocket-Accept` value for a client key
+fn accept_key(key: &str) -> String {
+    let digest = sha1_smol::Sha1::from(format!("{key}{WS_GUID}")).digest();
+    base64(&digest.bytes())
+}
+
+/// Unmasked, unfragmented server-to-client text frame
+fn text_frame(payload: &str) -> Vec<u8> {
+    frame_bytes(OPCODE_TEXT, payload.as_bytes())
+}
+
+/// Unmasked, unfragmented server-to-client frame
+fn frame_bytes(opcode: u8, bytes: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x80 | opcode];
+    match bytes.len() {
+        len @ 0..=125 => frame.push(len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(bytes);
+    frame
+}
+
+/// Standard padded base64
+pub(crate) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+        assert_eq!(base64(b"ab"), "YWI=");
+        assert_eq!(base64(b"a"), "YQ==");
+    }
+
+    #[test]
+    fn request_head_is_parsed() {
+        let request =
+            parse_request("GET /ws HTTP/1.1\r\nHost: x\r\nsec-websocket-key: abc== \r\n\r\n")
+                .unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/ws");
+        assert_eq!(
+            parse_request("GET /?t=1 HTTP/1.1\r\n\r\n").unwrap().path,
+            "/"
+        );
+        assert_eq!(request.websocket_key.as_deref(), Some("abc=="));
+        assert!(parse_request("").is_none());
+
+        let request = parse_request(
+            "GET /api/events?since=2026-02-08T11%3A00%3A30%2B00%3A00 HTTP/1.1\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(request.path, "/api/events");
+        assert_eq!(
+            query_param(request.query.as_deref().unwrap(), "since").as_deref(),
+            Some("2026-02-08T11:00:30+00:00")
+        );
+    }
+
+    #[test]
+    fn api_serves_parts_of_the_snapshot() {
+        let tasks = include_str!("../../tests/fixtures/sample_tasks.md");
+        let mut state = crate::data::state::DashboardState::from_tasks_content(tasks).unwrap();
+        let hooks = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        state.update_from_events(&crate::data::hook_parser::parse_hook_events(hooks).events);
+        let snapshot = Snapshot::from_state(&state);
+
+        let parse = |path, query| {
+            let (status, body) = api_response(path, query, &snapshot).unwrap();
+            (
+                status,
+                serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            )
+        };
+        assert_eq!(
+            parse("/api/tasks", None).1[1]["tasks"][2]["status"],
+            "failed"
+        );
+        assert_eq!(
+            parse("/api/agents", None).1[0]["id"],
+            "backend-specialist-2"
+        );
+        assert_eq!(parse("/api/errors", None).1.as_array().unwrap().len(), 2);
+
+        let all = parse("/api/events", None).1;
+        let since = parse("/api/events", Some("since=2026-02-08T11:00:30Z")).1;
+        assert_eq!(since.as_array().unwrap().len(), 2);
+        assert!(all.as_array().unwrap().len() > 2);
+
+        let (status, body) = parse("/api/events", Some("since=yesterday"));
+        assert_eq!(status, "400 Bad Request");
+        assert!(body["error"].as_str().unwrap().contains("yesterday"));
+        assert!(api_response("/api/nope", None, &snapshot).is_none());
+    }
+
+    #[test]
+    fn frames_use_extended_lengths() {
+        assert_eq!(text_frame("hi"), vec![0x81, 2, b'h', b'i']);
+        let frame = text_frame(&"x".repeat(300));
+        assert_eq!(&frame[..4], &[0x81, 126, 0x01, 0x2C]);
+        assert_eq!(frame.len(), 304);
+    }
+
+    /// A masked client frame, as browsers send them
+    fn client_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [1u8, 2, 3, 4];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        frame
+    }
+
+    #[test]
+    fn client_frames_are_parsed_across_reads() {
+        let ping = client_frame(OPCODE_PING, b"hey");
+        assert_eq!(parse_client_frame(&ping[..3]), Ok(None));
+        let mut both = ping.clone();
+        both.extend(client_frame(OPCODE_CLOSE, &[]));
+        let (frame, used) = parse_client_frame(&both).unwrap().unwrap();
+        assert_eq!(
+            frame,
+            ClientFrame {
+                opcode: OPCODE_PING,
+                payload: b"hey".to_vec()
+            }
+        );
+        assert_eq!(used, ping.len());
+        let (frame, _) = parse_client_frame(&both[used..]).unwrap().unwrap();
+        assert_eq!(frame.opcode, OPCODE_CLOSE);
+
+        let mut huge = vec![0x81, 0x80 | 127];
+        huge.extend_from_slice(&u64::MAX.to_be_bytes());
+        assert_eq!(parse_client_frame(&huge), Err(()));
+    }
+
+    #[tokio::test]
+    async fn websocket_client_gets_current_and_new_snapshots() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = watch::channel(Arc::new(Snapshot::default()));
+        tokio::spawn(serve(listener, rx));
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client
+            .write_all(b"GET /ws HTTP/1.1\r\nUpgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n")
+            .await
+            .unwrap();
+        let head = read_head(&mut client).await.unwrap().unwrap();
+        assert!(head.starts_with("HTTP/1.1 101"));
+        assert!(head.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        async fn read_frame(client: &mut TcpStream) -> String {
+            let mut head = [0u8; 2];
+            client.read_exact(&mut head).await.unwrap();
+            assert_eq!(head[0], 0x81);
+            let mut payload = vec![0u8; head[1] as usize];
+            client.read_exact(&mut payload).await.unwrap();
+            String::from_utf8(payload).unwrap()
+        }
+        assert!(read_frame(&mut client).await.contains("\"total_tasks\":0"));
+
+        tx.send(Arc::new(Snapshot {
+            total_tasks: 3,
+            ..Snapshot::default()
+        }))
+        .unwrap();
+        assert!(read_frame(&mut client).await.contains("\"total_tasks\":3"));
+    }
+
+    #[tokio::test]
+    async fn websocket_pings_get_pongs_not_snapshots() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (_tx, rx) = watch::channel(Arc::new(Snapshot::default()));
+        tokio::spawn(serve(listener, rx));
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        client
+            .write_all(b"GET /ws HTTP/1.1\r\nUpgrade: websocket\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n")
+            .await
+            .unwrap();
+        read_head(&mut client).await.unwrap().unwrap();
+        let mut head = [0u8; 4];
+        client.read_exact(&mut head[..2]).await.unwrap();
+        assert_eq!(head[0], 0x81);
+        let mut snapshot = vec![0u8; head[1] as usize];
+        client.read_exact(&mut snapshot).await.unwrap();
+
+        // A text frame, then a ping split over two writes
+        let mut frames = client_frame(OPCODE_TEXT, b"hello");
+        frames.extend(client_frame(OPCODE_PING, b"hi"));
+        let split = frames.len() - 3;
+        client.write_all(&frames[..split]).await.unwrap();
+        client.flush().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        client.write_all(&frames[split..]).await.unwrap();
+        client.read_exact(&mut head).await.unwrap();
+        assert_eq!(head, [0x80 | OPCODE_PONG, 2, b'h', b'i']);
+
+        client
+            .write_all(&client_frame(OPCODE_CLOSE, &[]))
+            .await
+            .unwrap();
+        let mut rest = Vec::new();
+        client.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, [0x80 | OPCODE_CLOSE, 0]);
+    }
+}
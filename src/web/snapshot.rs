@@ -0,0 +1,227 @@
+//! Read-only JSON view of the dashboard for the web mirror
+//!
+//! A flattened copy of what the TUI shows: phases with their tasks, agents
+//! sorted by ID, the open errors, newest first, and the recent hook events
+//! kept per task, oldest first.
+
+use std::borrow::Cow;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::data::hook_parser::EventType;
+use crate::data::state::DashboardState;
+
+/// Errors sent to the page; older ones stay in the TUI drill-down
+const MAX_ERRORS: usize = 20;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Snapshot {
+    /// Completed share of all tasks, 0.0 to 1.0
+    pub progress: f32,
+    pub total_tasks: usize,
+    pub completed_tasks: usize,
+    pub failed_tasks: usize,
+    pub phases: Vec<PhaseView>,
+    pub agents: Vec<AgentView>,
+    pub errors: Vec<ErrorView>,
+    pub events: Vec<EventView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseView {
+    pub id: String,
+    pub name: String,
+    pub progress: f32,
+    pub tasks: Vec<TaskView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TaskView {
+    pub id: String,
+    pub name: String,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_reason: Option<String>,
+    /// Hashes of the commits that mention the task, newest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AgentView {
+    pub id: String,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_task: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_tool: Option<String>,
+    /// Git branch of the agent's working directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    pub open_errors: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorView {
+    pub agent_id: String,
+    pub task_id: String,
+    pub message: String,
+    pub suggestion: Cow<'static, str>,
+    pub timestamp: DateTime<Utc>,
+    /// Occurrences grouped into this error
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventView {
+    pub timestamp: DateTime<Utc>,
+    pub event_type: EventType,
+    pub agent_id: String,
+    pub task_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+impl Snapshot {
+    pub fn from_state(state: &DashboardState) -> Self {
+        let phases = state
+            .phases
+            .iter()
+            .map(|phase| PhaseView {
+                id: phase.id.clone(),
+                name: phase.name.clone(),
+                progress: phase.progress(),
+                tasks: phase
+                    .tasks
+                    .iter()
+                    .map(|task| TaskView {
+                        id: task.id.clone(),
+                        name: task.name.clone(),
+                        status: task.shown_status().as_str(),
+                        agent: task.agent.clone(),
+                        blocked_reason: task.blocked_reason.clone(),
+                        commits: state
+                            .commits_for_task(&task.id)
+                            .iter()
+                            .map(|c| c.hash.clone())
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut agents: Vec<AgentView> = state
+            .agents
+            .values()
+            .map(|agent| AgentView {
+                id: agent.agent_id.clone(),
+                status: agent.status.as_str(),
+                current_task: agent.current_task.clone(),
+                current_tool: agent.current_tool.clone(),
+                branch: agent.checkout.as_ref().map(|c| c.branch.clone()),
+                open_errors: agent.open_errors,
+            })
+            .collect();
+        agents.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let errors = state
+            .recent_errors
+            .iter()
+            .rev()
+            .filter(|e| !state.error_acknowledged(e))
+            .take(MAX_ERRORS)
+            .map(|e| ErrorView {
+                agent_id: e.agent_id.clone(),
+                task_id: e.task_id.clone(),
+                message: e.message.clone(),
+                suggestion: e.suggestion.clone(),
+                timestamp: e.timestamp,
+                count: e.count,
+            })
+            .collect();
+
+        let mut events: Vec<EventView> = state
+            .task_activity
+            .iter()
+            .flat_map(|(task_id, activity)| {
+                activity.iter().map(move |a| EventView {
+                    timestamp: a.timestamp,
+                    event_type: a.event_type.clone(),
+                    agent_id: a.agent_id.to_string(),
+                    task_id: task_id.clone(),
+                    tool_name: a.tool_name.as_deref().map(str::to_string),
+                    error_message: a.error_message.clone(),
+                })
+            })
+            .collect();
+        events.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.task_id.cmp(&b.task_id))
+        });
+
+        Self {
+            progress: state.overall_progress,
+            total_tasks: state.total_tasks,
+            completed_tasks: state.completed_tasks,
+            failed_tasks: state.failed_tasks,
+            phases,
+            agents,
+            errors,
+            events,
+        }
+    }
+
+    /// Events strictly after `since`, or all of them
+    pub fn events_since(&self, since: Option<DateTime<Utc>>) -> Vec<&EventView> {
+        self.events
+            .iter()
+            .filter(|e| since.map_or(true, |since| e.timestamp > since))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_mirrors_tasks_agents_and_open_errors() {
+        let tasks = include_str!("../../tests/fixtures/sample_tasks.md");
+        let mut state = DashboardState::from_tasks_content(tasks).unwrap();
+        let hooks = include_str!("../../tests/fixtures/sample_hooks/error_events.jsonl");
+        state.update_from_events(&crate::data::hook_parser::parse_hook_events(hooks).events);
+
+        let snapshot = Snapshot::from_state(&state);
+        assert_eq!(snapshot.total_tasks, 8);
+        assert_eq!(snapshot.phases[1].tasks[2].id, "P1-R3-T1");
+        assert_eq!(snapshot.phases[1].tasks[2].status, "failed");
+        assert_eq!(snapshot.agents[0].id, "backend-specialist-2");
+        assert_eq!(snapshot.errors.len(), 2);
+        assert!(snapshot.errors[0].message.contains("connection refused"));
+
+        state.acknowledge_agent_errors("backend-specialist-2");
+        assert!(Snapshot::from_state(&state).errors.is_empty());
+
+        let json: serde_json::Value = serde_json::from_str(&snapshot.to_json()).unwrap();
+        assert_eq!(json["phases"][1]["tasks"][2]["status"], "failed");
+        assert!(json["phases"][0]["tasks"][0]
+            .get("blocked_reason")
+            .is_none());
+
+        let last = snapshot.events.last().unwrap();
+        assert_eq!(last.event_type, EventType::AgentEnd);
+        assert_eq!(snapshot.events_since(Some(last.timestamp)).len(), 0);
+        assert_eq!(snapshot.events_since(None).len(), snapshot.events.len());
+        assert_eq!(json["events"][1]["event_type"], "error");
+    }
+}
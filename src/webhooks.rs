@@ -0,0 +1,412 @@
+//! Outbound webhooks on state transitions
+//!
+//! Each `[webhooks.<name>]` section in board.toml posts a JSON body to its
+//! `url` for the transitions listed in `on` (all of them by default). The
+//! body is the `template` with `{variable}`s filled in JSON-escaped, or an
+//! object with every variable when no template is set. With a `secret`, the
+//! body is signed in an `X-Board-Signature: sha256=<hmac>` header.
+//!
+//! Requests are sent with curl, one at a time, by a worker thread behind a
+//! bounded queue, so a slow endpoint never stalls the dashboard and an
+//! error loop cannot pile up threads or processes: deliveries that find
+//! the queue full are dropped, as are failed ones.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::OnceLock;
+
+use hmac::{Hmac, Mac};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+
+use crate::budget::BudgetScope;
+use crate::config::BudgetLevel;
+use crate::data::transitions::{Transition, TransitionKind};
+
+/// Template variables; those a transition has no value for expand to ""
+const VARIABLES: [&str; 16] = [
+    "event",
+    "summary",
+    "task_id",
+    "task_name",
+    "phase_id",
+    "phase_name",
+    "agent",
+    "from",
+    "to",
+    "message",
+    "category",
+    "timestamp",
+    "scope",
+    "level",
+    "used",
+    "limit",
+];
+
+/// Seconds curl may spend on one delivery
+const TIMEOUT_SECS: u32 = 10;
+
+/// Deliveries waiting for the worker at most
+const QUEUE_LIMIT: usize = 64;
+
+/// A configured webhook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    /// Section name, `ci` for `[webhooks.ci]`
+    pub name: String,
+    pub url: String,
+    /// Transitions it fires on; empty means all
+    pub on: Vec<TransitionKind>,
+    pub template: Option<String>,
+    /// Key for the HMAC-SHA256 signature header
+    pub secret: Option<String>,
+}
+
+/// `{name}` placeholders in `template`: lowercase words in braces, so the
+/// braces of the JSON itself are left alone
+fn placeholders(template: &str) -> impl Iterator<Item = (usize, &str)> {
+    template.match_indices('{').filter_map(|(open, _)| {
+        let rest = &template[open + 1..];
+        let close = rest.find('}')?;
+        let name = &rest[..close];
+        (!name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+            .then_some((open, name))
+    })
+}
+
+impl Webhook {
+    /// Check a `[webhooks.<name>]` section
+    pub fn parse(
+        name: &str,
+        url: &str,
+        on: &[&str],
+        template: Option<&str>,
+        secret: Option<&str>,
+    ) -> Result<Self, String> {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(format!("`webhooks.{name}.url` must be an http(s) URL"));
+        }
+        let on = on
+            .iter()
+            .map(|kind| {
+                kind.parse()
+                    .map_err(|e| format!("`webhooks.{name}.on`: {e}"))
+            })
+            .collect::<Result<_, _>>()?;
+        if let Some(template) = template {
+            if let Some((_, unknown)) = placeholders(template).find(|(_, n)| !VARIABLES.contains(n))
+            {
+                return Err(format!(
+                    "`webhooks.{name}.template`: unknown variable `{{{unknown}}}`"
+                ));
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            url: url.to_string(),
+            on,
+            template: template.map(str::to_string),
+            secret: secret.map(str::to_string),
+        })
+    }
+
+    pub fn fires_on(&self, kind: TransitionKind) -> bool {
+        self.on.is_empty() || self.on.contains(&kind)
+    }
+
+    /// Request body for `transition`
+    pub fn body(&self, transition: &Transition) -> String {
+        let values = variables(transition);
+        let Some(template) = &self.template else {
+            let object: Map<String, Value> = values
+                .into_iter()
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(name, value)| (name.to_string(), Value::String(value)))
+                .collect();
+            return Value::Object(object).to_string();
+        };
+
+        let mut out = String::new();
+        let mut copied = 0;
+        for (open, name) in placeholders(template) {
+            let value = values
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map_or("", |(_, v)| v.as_str());
+            let escaped = Value::String(value.to_string()).to_string();
+            out.push_str(&template[copied..open]);
+            // Without the surrounding quotes, so `"{summary}"` stays one string
+            out.push_str(&escaped[1..escaped.len() - 1]);
+            copied = open + name.len() + 2;
+        }
+        out.push_str(&template[copied..]);
+        out
+    }
+
+    /// curl arguments posting `body` (read from stdin)
+    pub fn curl_args(&self, kind: TransitionKind, body: &str) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "--silent",
+            "--fail",
+            "--max-time",
+            &TIMEOUT_SECS.to_string(),
+            "--request",
+            "POST",
+            "--header",
+            "Content-Type: application/json",
+            "--header",
+            &format!("X-Board-Event: {}", kind.as_str()),
+        ]
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+        if let Some(secret) = &self.secret {
+            args.push("--header".to_string());
+            args.push(format!(
+                "X-Board-Signature: sha256={}",
+                hmac_sha256(secret.as_bytes(), body.as_bytes())
+            ));
+        }
+        args.extend(["--data-binary".to_string(), "@-".to_string()]);
+        args.push(self.url.clone());
+        args
+    }
+}
+
+/// Template variables of `transition`
+fn variables(transition: &Transition) -> Vec<(&'static str, String)> {
+    let mut values = vec![
+        ("event", transition.kind().as_str().to_string()),
+        ("summary", transition.summary()),
+    ];
+    match transition {
+        Transition::TaskStatus {
+            task_id,
+            task_name,
+            phase_id,
+            from,
+            to,
+        } => values.extend([
+            ("task_id", task_id.clone()),
+            ("task_name", task_name.clone()),
+            ("phase_id", phase_id.clone()),
+            ("from", from.as_str().to_string()),
+            ("to", to.as_str().to_string()),
+        ]),
+        Transition::AgentError {
+            agent_id,
+            task_id,
+            message,
+            category,
+            timestamp,
+        } => values.extend([
+            ("agent", agent_id.clone()),
+            ("task_id", task_id.clone()),
+            ("message", message.clone()),
+            ("category", category.to_string()),
+            ("timestamp", timestamp.to_rfc3339()),
+        ]),
+        Transition::PhaseComplete {
+            phase_id,
+            phase_name,
+        } => values.extend([
+            ("phase_id", phase_id.clone()),
+            ("phase_name", phase_name.clone()),
+        ]),
+        Transition::Budget(check) => {
+            let level = match check.level {
+                BudgetLevel::Ok => "ok",
+                BudgetLevel::Warn => "warn",
+                BudgetLevel::Over => "over",
+            };
+            values.extend([
+                ("scope", check.scope.to_string()),
+                ("level", level.to_string()),
+                ("used", check.used.to_string()),
+                ("limit", check.limit.to_string()),
+            ]);
+            if let BudgetScope::Agent(agent) = &check.scope {
+                values.push(("agent", agent.clone()));
+            }
+        }
+    }
+    values
+}
+
+/// Hex HMAC-SHA256 of `message` (RFC 2104)
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(message);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// One request for the worker
+struct Delivery {
+    args: Vec<String>,
+    body: String,
+}
+
+/// Run curl with `delivery`'s arguments and body until it exits
+fn send(delivery: &Delivery) {
+    let child = Command::new("curl")
+        .args(&delivery.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(delivery.body.as_bytes());
+        }
+        let _ = child.wait();
+    }
+}
+
+/// Queue of at most `limit` deliveries that one thread hands to `send` in
+/// order
+fn spawn_worker(limit: usize, send: impl Fn(&Delivery) + Send + 'static) -> SyncSender<Delivery> {
+    let (tx, rx) = mpsc::sync_channel::<Delivery>(limit);
+    std::thread::spawn(move || {
+        for delivery in rx {
+            send(&delivery);
+        }
+    });
+    tx
+}
+
+/// Hand `delivery` to the worker; `false` when the queue is full (or the
+/// worker gone) and it was dropped
+fn enqueue(queue: &SyncSender<Delivery>, delivery: Delivery) -> bool {
+    match queue.try_send(delivery) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => false,
+    }
+}
+
+/// Post each transition to the webhooks that fire on it, in the background
+pub fn deliver(webhooks: &[Webhook], transitions: &[Transition]) {
+    static QUEUE: OnceLock<SyncSender<Delivery>> = OnceLock::new();
+    for transition in transitions {
+        for webhook in webhooks.iter().filter(|w| w.fires_on(transition.kind())) {
+            let body = webhook.body(transition);
+            let args = webhook.curl_args(transition.kind(), &body);
+            let queue = QUEUE.get_or_init(|| spawn_worker(QUEUE_LIMIT, send));
+            enqueue(queue, Delivery { args, body });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::tasks_parser::TaskStatus;
+
+    fn done() -> Transition {
+        Transition::TaskStatus {
+            task_id: "P1-T1".to_string(),
+            task_name: "Say \"hi\"".to_string(),
+            phase_id: "P1".to_string(),
+            from: TaskStatus::InProgress,
+            to: TaskStatus::Completed,
+        }
+    }
+
+    #[test]
+    fn parse_checks_url_kinds_and_variables() {
+        let hook = Webhook::parse("ci", "https://x", &["task_status"], None, None).unwrap();
+        assert!(hook.fires_on(TransitionKind::TaskStatus));
+        assert!(!hook.fires_on(TransitionKind::AgentError));
+        assert!(Webhook::parse("ci", "https://x", &[], None, None)
+            .unwrap()
+            .fires_on(TransitionKind::PhaseComplete));
+
+        assert!(Webhook::parse("ci", "ftp://x", &[], None, None).is_err());
+        assert!(Webhook::parse("ci", "https://x", &["done"], None, None).is_err());
+        let err =
+            Webhook::parse("ci", "https://x", &[], Some(r#"{"a": "{nope}"}"#), None).unwrap_err();
+        assert!(err.contains("{nope}"), "got: {err}");
+    }
+
+    #[test]
+    fn body_fills_template_or_lists_variables() {
+        let templated = Webhook::parse(
+            "chat",
+            "https://x",
+            &[],
+            Some(r#"{"text": "{summary}", "to": "{to}", "agent": "{agent}"}"#),
+            None,
+        )
+        .unwrap();
+        let body: Value = serde_json::from_str(&templated.body(&done())).unwrap();
+        assert_eq!(body["text"], "P1-T1 Say \"hi\": in_progress -> completed");
+        assert_eq!(body["to"], "completed");
+        assert_eq!(body["agent"], "");
+
+        let plain = Webhook::parse("all", "https://x", &[], None, None).unwrap();
+        let body: Value = serde_json::from_str(&plain.body(&done())).unwrap();
+        assert_eq!(body["event"], "task_status");
+        assert_eq!(body["from"], "in_progress");
+        assert!(body.get("agent").is_none());
+    }
+
+    #[test]
+    fn signs_bodies_with_hmac_sha256() {
+        // RFC 4231 test case 1
+        assert_eq!(
+            hmac_sha256(&[0x0b; 20], b"Hi There"),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        // RFC 4231 test case 6: key longer than a block
+        assert_eq!(
+            hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+
+        let hook = Webhook::parse("ci", "https://x/hook", &[], None, Some("k")).unwrap();
+        let args = hook.curl_args(TransitionKind::TaskStatus, "{}");
+        assert!(args.contains(&format!(
+            "X-Board-Signature: sha256={}",
+            hmac_sha256(b"k", b"{}")
+        )));
+        assert!(args.contains(&"X-Board-Event: task_status".to_string()));
+        assert_eq!(args.last().unwrap(), "https://x/hook");
+    }
+
+    #[test]
+    fn a_full_queue_drops_deliveries() {
+        let (started_tx, started) = mpsc::channel();
+        let (release, release_rx) = mpsc::channel::<()>();
+        let release_rx = std::sync::Mutex::new(release_rx);
+        let queue = spawn_worker(2, move |delivery| {
+            started_tx.send(delivery.body.clone()).unwrap();
+            let _ = release_rx.lock().unwrap().recv();
+        });
+        let delivery = |body: &str| Delivery {
+            args: Vec::new(),
+            body: body.to_string(),
+        };
+
+        // The worker is busy with the first; two more wait, the rest drop
+        assert!(enqueue(&queue, delivery("1")));
+        assert_eq!(started.recv().unwrap(), "1");
+        assert!(enqueue(&queue, delivery("2")));
+        assert!(enqueue(&queue, delivery("3")));
+        assert!(!enqueue(&queue, delivery("4")));
+
+        for _ in 0..3 {
+            release.send(()).unwrap();
+        }
+        assert_eq!(started.recv().unwrap(), "2");
+        assert_eq!(started.recv().unwrap(), "3");
+        drop(queue);
+        assert!(started.recv().is_err());
+    }
+}
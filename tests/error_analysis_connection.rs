@@ -3,6 +3,8 @@
 //! Validates error analysis fields flow through detail panel and retry modal,
 //! navigation between detail→modal→TASKS.md, and end-to-end error data flow.
 
+#![cfg(feature = "tui")]
+
 use chrono::Utc;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
@@ -92,8 +94,11 @@ fn error_analysis_retryable_in_detail() {
         message: "permission denied: /etc/shadow".to_string(),
         category: ErrorCategory::Permission,
         retryable: false,
-        suggestion: "Check file permissions",
+        suggestion: "Check file permissions".into(),
+        command: None,
         timestamp: Utc::now(),
+        tool_name: None,
+        count: 1,
     };
     let widget = DetailWidget::new(
         DetailContent::Task(task, "Data Engine", vec![&err_perm]),
@@ -115,8 +120,11 @@ fn error_analysis_retryable_in_detail() {
         message: "connection refused: localhost:5432".to_string(),
         category: ErrorCategory::Network,
         retryable: true,
-        suggestion: "Check if service is running",
+        suggestion: "Check if service is running".into(),
+        command: None,
         timestamp: Utc::now(),
+        tool_name: None,
+        count: 1,
     };
     let mut buf2 = Buffer::empty(area);
     let widget2 = DetailWidget::new(
@@ -358,6 +366,9 @@ fn retry_modal_renders_with_error_fields() {
         task_id: "P1-R3-T1".to_string(),
         task_name: "File watcher".to_string(),
         retryable: true,
+        error: None,
+        fix: None,
+        confirming_fix: false,
     };
     let area = Rect::new(0, 0, 80, 30);
     let mut buf = Buffer::empty(area);
@@ -375,6 +386,9 @@ fn retry_modal_renders_with_error_fields() {
         task_id: "P1-R3-T1".to_string(),
         task_name: "File watcher".to_string(),
         retryable: false,
+        error: None,
+        fix: None,
+        confirming_fix: false,
     };
     let mut buf2 = Buffer::empty(area);
     modal2.render(area, &mut buf2);
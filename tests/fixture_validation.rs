@@ -54,6 +54,18 @@ fn agent_events_fixture_valid() {
     }
 }
 
+#[test]
+fn tool_file_events_fixture_valid() {
+    let content = std::fs::read_to_string(fixtures_dir().join("tool_file_events.jsonl")).unwrap();
+    assert!(content.contains("\"file_path\""));
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        assert!(
+            serde_json::from_str::<serde_json::Value>(line).is_ok(),
+            "Line should be valid JSON: {line}"
+        );
+    }
+}
+
 #[test]
 fn error_events_fixture_valid() {
     let content = read_hook_events("error_events.jsonl");
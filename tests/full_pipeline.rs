@@ -4,6 +4,8 @@
 //! TASKS.md → parse → state → UI render, Hook events → parse → agent panel,
 //! Error flow → analysis → retry → write-back, and keyboard interaction.
 
+#![cfg(feature = "tui")]
+
 use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget, widgets::Widget};
 
 use simple_claude_board::app::App;
@@ -14,7 +16,7 @@ use simple_claude_board::event::{key_to_action, Action};
 use simple_claude_board::ui::claude_output::AgentPanel;
 use simple_claude_board::ui::detail::DetailWidget;
 use simple_claude_board::ui::gantt::GanttWidget;
-use simple_claude_board::ui::help::HelpOverlay;
+use simple_claude_board::ui::help::{HelpOverlay, HelpState};
 use simple_claude_board::ui::layout::{DashboardLayout, FocusedPane};
 use simple_claude_board::ui::retry_modal::RetryModal;
 use simple_claude_board::ui::statusbar::StatusBar;
@@ -343,13 +345,16 @@ fn full_render_pipeline_no_panic() {
     statusbar.render(layout.status_bar, &mut buf);
 
     // Help overlay
-    HelpOverlay.render(area, &mut buf);
+    HelpOverlay.render(area, &mut buf, &mut HelpState::default());
 
     // Retry modal
     let modal = RetryModal {
         task_id: "T1".to_string(),
         task_name: "Test".to_string(),
         retryable: true,
+        error: None,
+        fix: None,
+        confirming_fix: false,
     };
     modal.render(area, &mut buf);
 
@@ -3,6 +3,8 @@
 //! Verifies Phase/Task tree display, cursor navigation,
 //! selection mapping, and live update behavior.
 
+#![cfg(feature = "tui")]
+
 use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
 
 use simple_claude_board::app::App;
@@ -3,6 +3,8 @@
 //! Validates field coverage, navigation flow, and shared component
 //! consistency across all UI panels.
 
+#![cfg(feature = "tui")]
+
 use std::time::Instant;
 
 use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget, widgets::Widget};
@@ -15,7 +17,7 @@ use simple_claude_board::event::{key_to_action, Action};
 use simple_claude_board::ui::claude_output::AgentPanel;
 use simple_claude_board::ui::detail::{DetailContent, DetailWidget};
 use simple_claude_board::ui::gantt::{GanttState, GanttWidget};
-use simple_claude_board::ui::help::HelpOverlay;
+use simple_claude_board::ui::help::{HelpOverlay, HelpState};
 use simple_claude_board::ui::layout::{DashboardLayout, FocusedPane};
 use simple_claude_board::ui::statusbar::StatusBar;
 
@@ -251,7 +253,7 @@ fn help_toggle_shows_overlay() {
     // Verify help overlay renders
     let area = Rect::new(0, 0, 80, 30);
     let mut buf = Buffer::empty(area);
-    HelpOverlay.render(area, &mut buf);
+    HelpOverlay.render(area, &mut buf, &mut HelpState::default());
     let text = buffer_text(&buf);
     assert!(text.contains("Help"));
 }
@@ -422,5 +424,5 @@ fn full_dashboard_renders_without_panic() {
     statusbar.render(layout.status_bar, &mut buf);
 
     // Help overlay
-    HelpOverlay.render(area, &mut buf);
+    HelpOverlay.render(area, &mut buf, &mut HelpState::default());
 }